@@ -1,7 +1,18 @@
 // Account module for managing trading account, positions, and orders
 
-use alpaca_markets::models::{OrderRequest, OrderSide, OrderTimeInForce, OrderType};
-use alpaca_markets::{AlpacaConfig, TradingClient};
+use crate::error::TerminalError;
+use crate::text_input::{InputMode, TextInput};
+use alpaca_markets::TradingClient;
+use alpaca_markets::models::{
+    AccountActivity, Order as ApiOrder, OrderClass, OrderRequest, OrderSide, OrderTimeInForce,
+    OrderType, StopLoss, TakeProfit,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{debug, info, warn};
+
+/// Delay before positions are re-read after a fill, so bursts of partial fills share a request
+pub const FILL_REFRESH_DELAY_MS: u64 = 1000;
 
 /// Position information
 #[derive(Clone)]
@@ -15,6 +26,22 @@ pub struct Position {
     pub unrealized_plpc: String,
 }
 
+impl Position {
+    /// One-line description for the clipboard
+    pub fn summary(&self) -> String {
+        format!(
+            "{} {} @ ${} avg, last ${}, value ${}, P&L ${} ({}%)",
+            self.symbol,
+            self.qty,
+            self.avg_entry_price,
+            self.current_price,
+            self.market_value,
+            self.unrealized_pl,
+            self.unrealized_plpc
+        )
+    }
+}
+
 /// Order information
 #[derive(Clone)]
 pub struct Order {
@@ -24,16 +51,367 @@ pub struct Order {
     pub qty: String,
     pub order_type: String,
     pub limit_price: Option<String>,
+    pub stop_price: Option<String>,
     pub status: String,
     pub created_at: String,
+    pub filled_qty: String,
+    pub filled_avg_price: Option<String>,
+    pub submitted_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub filled_at: Option<String>,
+    // Child orders of a bracket/OCO/OTO order
+    pub legs: Vec<Order>,
+}
+
+impl Order {
+    /// Whether the order is still working (not filled, canceled, expired or rejected)
+    pub fn is_open(&self) -> bool {
+        !matches!(
+            self.status.to_lowercase().as_str(),
+            "filled" | "canceled" | "expired" | "rejected" | "replaced"
+        )
+    }
+
+    /// One-line description for the clipboard
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "{} {} {} {}",
+            self.side.to_uppercase(),
+            self.qty,
+            self.symbol,
+            self.order_type
+        );
+        if let Some(limit_price) = &self.limit_price {
+            summary.push_str(&format!(" @ ${}", limit_price));
+        }
+        summary.push_str(&format!(
+            ", {} ({}/{} filled",
+            self.status, self.filled_qty, self.qty
+        ));
+        if let Some(avg_price) = &self.filled_avg_price {
+            summary.push_str(&format!(" @ ${}", avg_price));
+        }
+        summary.push_str(&format!("), created {}, id {}", self.created_at, self.id));
+        summary
+    }
+}
+
+/// A trade update event received over the stream for an order
+#[derive(Clone)]
+pub struct OrderEvent {
+    pub event: String,
+    pub status: String,
+    pub filled_qty: String,
+    pub filled_avg_price: Option<String>,
+    pub received_at: chrono::DateTime<chrono::Local>,
+}
+
+/// Account activity (fill, dividend, fee, transfer, ...)
+#[derive(Clone)]
+pub struct Activity {
+    pub id: String,
+    pub activity_type: String,
+    pub symbol: Option<String>,
+    pub side: Option<String>,
+    pub qty: Option<String>,
+    pub price: Option<String>,
+    pub net_amount: Option<String>,
+    pub description: Option<String>,
+    pub order_id: Option<String>,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+impl Activity {
+    /// Whether this activity is a trade execution
+    pub fn is_fill(&self) -> bool {
+        matches!(self.activity_type.as_str(), "FILL" | "PARTIAL_FILL")
+    }
+
+    /// Whether this fill was a buy
+    pub fn is_buy(&self) -> bool {
+        self.side
+            .as_ref()
+            .is_some_and(|side| side.to_lowercase().contains("buy"))
+    }
+
+    /// Signed cash amount of the activity (fills are negative for buys, positive for sells)
+    pub fn amount(&self) -> Option<f64> {
+        if self.is_fill() {
+            let qty = self.qty.as_ref()?.parse::<f64>().ok()?;
+            let price = self.price.as_ref()?.parse::<f64>().ok()?;
+            let gross = qty * price;
+            Some(if self.is_buy() { -gross } else { gross })
+        } else {
+            self.net_amount.as_ref()?.parse::<f64>().ok()
+        }
+    }
+}
+
+/// Category filter for the activities tab
+#[derive(Clone, Copy, PartialEq)]
+pub enum ActivityFilter {
+    All,
+    Fills,
+    Dividends,
+    Fees,
+    Transfers,
+}
+
+impl ActivityFilter {
+    pub const ALL: [ActivityFilter; 5] = [
+        ActivityFilter::All,
+        ActivityFilter::Fills,
+        ActivityFilter::Dividends,
+        ActivityFilter::Fees,
+        ActivityFilter::Transfers,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ActivityFilter::All => "All",
+            ActivityFilter::Fills => "Fills",
+            ActivityFilter::Dividends => "Dividends",
+            ActivityFilter::Fees => "Fees",
+            ActivityFilter::Transfers => "Transfers",
+        }
+    }
+
+    /// Check whether an activity type code belongs to this category
+    pub fn matches(&self, activity_type: &str) -> bool {
+        match self {
+            ActivityFilter::All => true,
+            ActivityFilter::Fills => matches!(activity_type, "FILL" | "PARTIAL_FILL"),
+            ActivityFilter::Dividends => activity_type.starts_with("DIV"),
+            ActivityFilter::Fees => matches!(activity_type, "FEE" | "PTC" | "CFEE"),
+            ActivityFilter::Transfers => {
+                matches!(activity_type, "CSD" | "CSW" | "TRANS" | "JNLC" | "JNLS")
+            }
+        }
+    }
+}
+
+/// Sortable columns in the positions table
+#[derive(Clone, Copy, PartialEq)]
+pub enum PositionSortColumn {
+    Symbol,
+    MarketValue,
+    UnrealizedPl,
+    UnrealizedPlpc,
+}
+
+/// Sortable columns in the orders table
+#[derive(Clone, Copy, PartialEq)]
+pub enum OrderSortColumn {
+    CreatedAt,
+    Symbol,
+    Status,
+}
+
+/// Side filter for the orders table
+#[derive(Clone, Copy, PartialEq)]
+pub enum OrderSideFilter {
+    All,
+    Buy,
+    Sell,
+}
+
+impl OrderSideFilter {
+    pub const ALL: [OrderSideFilter; 3] = [
+        OrderSideFilter::All,
+        OrderSideFilter::Buy,
+        OrderSideFilter::Sell,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OrderSideFilter::All => "All Sides",
+            OrderSideFilter::Buy => "Buy",
+            OrderSideFilter::Sell => "Sell",
+        }
+    }
+
+    pub fn matches(&self, side: &str) -> bool {
+        let side = side.to_lowercase();
+        match self {
+            OrderSideFilter::All => true,
+            OrderSideFilter::Buy => side.contains("buy"),
+            OrderSideFilter::Sell => side.contains("sell"),
+        }
+    }
+}
+
+/// Order type filter for the orders table
+#[derive(Clone, Copy, PartialEq)]
+pub enum OrderTypeFilter {
+    All,
+    Market,
+    Limit,
+    Stop,
+    StopLimit,
+}
+
+impl OrderTypeFilter {
+    pub const ALL: [OrderTypeFilter; 5] = [
+        OrderTypeFilter::All,
+        OrderTypeFilter::Market,
+        OrderTypeFilter::Limit,
+        OrderTypeFilter::Stop,
+        OrderTypeFilter::StopLimit,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OrderTypeFilter::All => "All Types",
+            OrderTypeFilter::Market => "Market",
+            OrderTypeFilter::Limit => "Limit",
+            OrderTypeFilter::Stop => "Stop",
+            OrderTypeFilter::StopLimit => "Stop Limit",
+        }
+    }
+
+    /// Match REST ("StopLimit") and stream ("stop_limit") spellings alike
+    pub fn matches(&self, order_type: &str) -> bool {
+        let order_type = order_type.to_lowercase().replace('_', "");
+        match self {
+            OrderTypeFilter::All => true,
+            OrderTypeFilter::Market => order_type == "market",
+            OrderTypeFilter::Limit => order_type == "limit",
+            OrderTypeFilter::Stop => order_type == "stop",
+            OrderTypeFilter::StopLimit => order_type == "stoplimit",
+        }
+    }
 }
 
 /// Footer tab selection
-#[derive(Clone, PartialEq)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub enum FooterTab {
     Account,
     Positions,
     Orders,
+    History,
+    Activities,
+    TaxLots,
+    Performance,
+    Backtest,
+    Simulator,
+    News,
+    Heatmap,
+    Movers,
+    Analytics,
+    Logs,
+}
+
+impl FooterTab {
+    pub const ALL: [FooterTab; 14] = [
+        FooterTab::Account,
+        FooterTab::Positions,
+        FooterTab::Orders,
+        FooterTab::History,
+        FooterTab::Activities,
+        FooterTab::TaxLots,
+        FooterTab::Performance,
+        FooterTab::Backtest,
+        FooterTab::Simulator,
+        FooterTab::News,
+        FooterTab::Heatmap,
+        FooterTab::Movers,
+        FooterTab::Analytics,
+        FooterTab::Logs,
+    ];
+
+    /// Next (or previous) tab, wrapping around
+    pub fn cycled(&self, forward: bool) -> FooterTab {
+        let count = Self::ALL.len();
+        let index = Self::ALL.iter().position(|tab| tab == self).unwrap_or(0);
+        let next = if forward {
+            (index + 1) % count
+        } else {
+            (index + count - 1) % count
+        };
+        Self::ALL[next].clone()
+    }
+}
+
+/// Status filter for the order history tab
+#[derive(Clone, Copy, PartialEq)]
+pub enum HistoryStatusFilter {
+    All,
+    Filled,
+    Canceled,
+    Rejected,
+}
+
+impl HistoryStatusFilter {
+    pub const ALL: [HistoryStatusFilter; 4] = [
+        HistoryStatusFilter::All,
+        HistoryStatusFilter::Filled,
+        HistoryStatusFilter::Canceled,
+        HistoryStatusFilter::Rejected,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HistoryStatusFilter::All => "All",
+            HistoryStatusFilter::Filled => "Filled",
+            HistoryStatusFilter::Canceled => "Canceled",
+            HistoryStatusFilter::Rejected => "Rejected",
+        }
+    }
+
+    /// Check whether an order status string matches this filter
+    pub fn matches(&self, status: &str) -> bool {
+        let status = status.to_lowercase();
+        match self {
+            HistoryStatusFilter::All => true,
+            HistoryStatusFilter::Filled => status == "filled",
+            HistoryStatusFilter::Canceled => {
+                status.contains("canceled") || status.contains("expired")
+            }
+            HistoryStatusFilter::Rejected => status.contains("rejected"),
+        }
+    }
+}
+
+/// Date range for the order history and performance tabs
+#[derive(Clone, Copy, PartialEq)]
+pub enum HistoryRange {
+    Today,
+    Week,
+    Month,
+    Quarter,
+    All,
+}
+
+impl HistoryRange {
+    pub const ALL: [HistoryRange; 5] = [
+        HistoryRange::Today,
+        HistoryRange::Week,
+        HistoryRange::Month,
+        HistoryRange::Quarter,
+        HistoryRange::All,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HistoryRange::Today => "Today",
+            HistoryRange::Week => "7D",
+            HistoryRange::Month => "30D",
+            HistoryRange::Quarter => "90D",
+            HistoryRange::All => "All",
+        }
+    }
+
+    /// Earliest creation time included in this range (None = unbounded)
+    pub fn start_time(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        let now = chrono::Utc::now();
+        match self {
+            HistoryRange::Today => now.date_naive().and_hms_opt(0, 0, 0).map(|t| t.and_utc()),
+            HistoryRange::Week => Some(now - chrono::Duration::days(7)),
+            HistoryRange::Month => Some(now - chrono::Duration::days(30)),
+            HistoryRange::Quarter => Some(now - chrono::Duration::days(90)),
+            HistoryRange::All => None,
+        }
+    }
 }
 
 /// Account state containing all account-related fields
@@ -45,29 +423,72 @@ pub struct Account {
     pub cash: Option<f64>,
     pub portfolio_value: Option<f64>,
     pub equity: Option<f64>,
+    pub last_equity: Option<f64>,
+    pub daytrade_count: Option<i64>,
+    pub pattern_day_trader: Option<bool>,
+    pub initial_margin: Option<f64>,
+    pub maintenance_margin: Option<f64>,
+    pub long_market_value: Option<f64>,
+    pub short_market_value: Option<f64>,
+    pub sma: Option<f64>,
+    pub multiplier: Option<f64>,
     pub account_loading: bool,
 
     // Positions information
     pub positions: Vec<Position>,
     pub positions_loading: bool,
+    pub positions_sort: Option<PositionSortColumn>,
+    pub positions_sort_ascending: bool,
+    pub positions_filter: String,
 
     // Orders information
     pub orders: Vec<Order>,
     pub orders_loading: bool,
+    // Cursor for the next (older) page of open orders, None once everything is loaded
+    pub orders_next_until: Option<chrono::DateTime<chrono::Utc>>,
+    pub orders_loading_more: bool,
+    pub orders_sort: OrderSortColumn,
+    pub orders_sort_ascending: bool,
+    pub orders_search: String,
+    pub orders_side_filter: OrderSideFilter,
+    pub orders_type_filter: OrderTypeFilter,
     pub active_footer_tab: FooterTab,
 
+    // Order history (closed orders)
+    pub order_history: Vec<Order>,
+    pub order_history_loading: bool,
+    pub history_status_filter: HistoryStatusFilter,
+    pub history_status_dropdown_open: bool,
+    pub history_range: HistoryRange,
+    pub history_symbol_filter: String,
+
+    // Account activities (fills, dividends, fees, transfers)
+    pub activities: Vec<Activity>,
+    pub activities_loading: bool,
+    // Every activity back to the account's first was loaded (see ActivityHistory)
+    pub activities_complete: bool,
+    pub activity_filter: ActivityFilter,
+
+    // Tab whose fetch is being retried, with the attempt number and total attempts
+    pub retrying: Option<(FooterTab, u32, u32)>,
+
+    // FIFO lots and realized P&L derived from fill activities
+    pub lot_ledger: crate::pnl::LotLedger,
+
+    // Position detail panel: selected symbol
+    pub selected_position: Option<String>,
+
+    // Order detail panel: selected order and stream events seen per order id
+    pub selected_order_id: Option<String>,
+    pub order_events: HashMap<String, Vec<OrderEvent>>,
+
     // Order form fields
     pub order_side: OrderSide,
     pub order_type: OrderType,
-    pub order_quantity: String,
-    pub order_limit_price: String,
+    pub order_quantity: TextInput,
+    pub order_limit_price: TextInput,
     pub order_time_in_force: OrderTimeInForce,
     pub order_submitting: bool,
-    pub order_message: Option<String>,
-
-    // Input focus tracking
-    pub quantity_focused: bool,
-    pub price_focused: bool,
 }
 
 impl Account {
@@ -79,24 +500,79 @@ impl Account {
             cash: None,
             portfolio_value: None,
             equity: None,
+            last_equity: None,
+            daytrade_count: None,
+            pattern_day_trader: None,
+            initial_margin: None,
+            maintenance_margin: None,
+            long_market_value: None,
+            short_market_value: None,
+            sma: None,
+            multiplier: None,
             account_loading: true,
             positions: Vec::new(),
             positions_loading: true,
+            positions_sort: None,
+            positions_sort_ascending: true,
+            positions_filter: String::new(),
             orders: Vec::new(),
             orders_loading: true,
+            orders_next_until: None,
+            orders_loading_more: false,
+            orders_sort: OrderSortColumn::CreatedAt,
+            orders_sort_ascending: false,
+            orders_search: String::new(),
+            orders_side_filter: OrderSideFilter::All,
+            orders_type_filter: OrderTypeFilter::All,
             active_footer_tab: FooterTab::Account,
+            order_history: Vec::new(),
+            order_history_loading: false,
+            history_status_filter: HistoryStatusFilter::All,
+            history_status_dropdown_open: false,
+            history_range: HistoryRange::Week,
+            history_symbol_filter: String::new(),
+            activities: Vec::new(),
+            activities_loading: false,
+            activities_complete: false,
+            activity_filter: ActivityFilter::All,
+            lot_ledger: crate::pnl::LotLedger::default(),
+            selected_position: None,
+            selected_order_id: None,
+            order_events: HashMap::new(),
             order_side: OrderSide::Buy,
             order_type: OrderType::Market,
-            order_quantity: String::new(),
-            order_limit_price: String::new(),
+            order_quantity: TextInput::new("", InputMode::Decimal),
+            order_limit_price: TextInput::new("", InputMode::Decimal),
             order_time_in_force: OrderTimeInForce::Day,
             order_submitting: false,
-            order_message: None,
-            quantity_focused: false,
-            price_focused: false,
+            retrying: None,
         }
     }
 
+    /// Drop everything fetched for the previous account (profile switch), keeping the
+    /// filters, sorting, active tab and order form
+    pub fn clear_data(&mut self) {
+        let previous = std::mem::replace(self, Account::new());
+        self.positions_sort = previous.positions_sort;
+        self.positions_sort_ascending = previous.positions_sort_ascending;
+        self.positions_filter = previous.positions_filter;
+        self.orders_sort = previous.orders_sort;
+        self.orders_sort_ascending = previous.orders_sort_ascending;
+        self.orders_search = previous.orders_search;
+        self.orders_side_filter = previous.orders_side_filter;
+        self.orders_type_filter = previous.orders_type_filter;
+        self.active_footer_tab = previous.active_footer_tab;
+        self.history_status_filter = previous.history_status_filter;
+        self.history_range = previous.history_range;
+        self.history_symbol_filter = previous.history_symbol_filter;
+        self.activity_filter = previous.activity_filter;
+        self.order_side = previous.order_side;
+        self.order_type = previous.order_type;
+        self.order_quantity = previous.order_quantity;
+        self.order_limit_price = previous.order_limit_price;
+        self.order_time_in_force = previous.order_time_in_force;
+    }
+
     /// Update account information from stream
     pub fn update_from_stream(&mut self, account_info: crate::stream::AccountInfo) {
         if let Ok(buying_power) = account_info.buying_power.parse::<f64>() {
@@ -109,29 +585,191 @@ impl Account {
 
         if let Ok(portfolio_value) = account_info.portfolio_value.parse::<f64>() {
             self.portfolio_value = Some(portfolio_value);
+            // Equity tracks total portfolio value, which drives the daily P&L widget
+            self.equity = Some(portfolio_value);
+        }
+
+        debug!("✓ Account updated from stream");
+    }
+
+    /// Today's P&L as (amount, percent) from equity vs. the previous close equity
+    pub fn daily_pnl(&self) -> Option<(f64, f64)> {
+        let equity = self.equity?;
+        let last_equity = self.last_equity?;
+        if last_equity <= 0.0 {
+            return None;
         }
 
-        println!("✓ Account updated from stream");
+        let change = equity - last_equity;
+        Some((change, change / last_equity * 100.0))
+    }
+
+    /// Apply a fill or partial fill to the local positions before the server's numbers arrive.
+    /// Call before `update_order_from_stream`, which records the new filled quantity.
+    /// Returns false if the event isn't a fill or adds no quantity.
+    pub fn apply_fill(&mut self, order_update: &crate::stream::OrderUpdate) -> bool {
+        if !matches!(order_update.event.as_str(), "fill" | "partial_fill") {
+            return false;
+        }
+        let number = |text: &str| text.parse::<f64>().unwrap_or(0.0);
+
+        // Fill events carry cumulative quantity and average price; the previous event (or the
+        // listed order) gives what had filled before this one
+        let previous = self
+            .order_events
+            .get(&order_update.id)
+            .and_then(|events| events.last())
+            .map(|event| (event.filled_qty.clone(), event.filled_avg_price.clone()))
+            .or_else(|| {
+                self.orders
+                    .iter()
+                    .find(|order| order.id == order_update.id)
+                    .map(|order| (order.filled_qty.clone(), order.filled_avg_price.clone()))
+            });
+        let (previous_qty, previous_avg) = previous
+            .map(|(qty, avg)| (number(&qty), avg.as_deref().map(number).unwrap_or(0.0)))
+            .unwrap_or((0.0, 0.0));
+
+        let filled_qty = number(&order_update.filled_qty);
+        let filled_avg = order_update
+            .filled_avg_price
+            .as_deref()
+            .map(number)
+            .unwrap_or(0.0);
+        let fill_qty = filled_qty - previous_qty;
+        if fill_qty <= 0.0 || filled_avg <= 0.0 {
+            return false;
+        }
+        let fill_price = if previous_qty > 0.0 && previous_avg > 0.0 {
+            (filled_avg * filled_qty - previous_avg * previous_qty) / fill_qty
+        } else {
+            filled_avg
+        };
+        let signed_qty = if order_update.side == "sell" {
+            -fill_qty
+        } else {
+            fill_qty
+        };
+
+        let Some(index) = self
+            .positions
+            .iter()
+            .position(|position| position.symbol == order_update.symbol)
+        else {
+            self.positions.push(Position {
+                symbol: order_update.symbol.clone(),
+                qty: signed_qty.to_string(),
+                avg_entry_price: format!("{:.4}", fill_price),
+                current_price: format!("{:.4}", fill_price),
+                market_value: format!("{:.2}", signed_qty * fill_price),
+                unrealized_pl: "0.00".to_string(),
+                unrealized_plpc: "0.0000".to_string(),
+            });
+            info!(
+                "📈 Opened {} position locally from fill ({} @ {:.4})",
+                order_update.symbol, signed_qty, fill_price
+            );
+            return true;
+        };
+
+        let position = &mut self.positions[index];
+        let qty = number(&position.qty);
+        let avg = number(&position.avg_entry_price);
+        let new_qty = qty + signed_qty;
+        if new_qty.abs() < 1e-9 {
+            self.positions.remove(index);
+            info!(
+                "📉 Closed {} position locally from fill",
+                order_update.symbol
+            );
+            return true;
+        }
+        // Adding to the position moves the average; reducing it doesn't; flipping sides
+        // starts a new average at the fill price
+        let new_avg = if qty == 0.0 || qty.signum() != new_qty.signum() {
+            fill_price
+        } else if qty.signum() == signed_qty.signum() {
+            (qty.abs() * avg + fill_qty * fill_price) / new_qty.abs()
+        } else {
+            avg
+        };
+        let current = match number(&position.current_price) {
+            price if price > 0.0 => price,
+            _ => fill_price,
+        };
+        position.qty = new_qty.to_string();
+        position.avg_entry_price = format!("{:.4}", new_avg);
+        position.market_value = format!("{:.2}", new_qty * current);
+        position.unrealized_pl = format!("{:.2}", (current - new_avg) * new_qty);
+        position.unrealized_plpc = format!("{:.4}", (current / new_avg - 1.0) * new_qty.signum());
+        info!(
+            "📊 Updated {} position locally from fill: {} @ {:.4}",
+            order_update.symbol, new_qty, new_avg
+        );
+        true
     }
 
     /// Update order from stream
     pub fn update_order_from_stream(&mut self, order_update: crate::stream::OrderUpdate) {
+        // Keep the raw event history for the order detail panel
+        let received_at = chrono::Local::now();
+        self.order_events
+            .entry(order_update.id.clone())
+            .or_default()
+            .push(OrderEvent {
+                event: order_update.event.clone(),
+                status: order_update.status.clone(),
+                filled_qty: order_update.filled_qty.clone(),
+                filled_avg_price: order_update.filled_avg_price.clone(),
+                received_at,
+            });
+
         // Check if this is a terminal state - remove from list immediately
         let is_terminal_state = matches!(
             order_update.status.as_str(),
             "filled" | "canceled" | "expired" | "rejected"
         );
 
+        // Legs are updated in place under their parent rather than listed on their own
+        if let Some(leg) = self
+            .orders
+            .iter_mut()
+            .flat_map(|order| order.legs.iter_mut())
+            .find(|leg| leg.id == order_update.id)
+        {
+            leg.status = order_update.status.clone();
+            leg.filled_qty = order_update.filled_qty.clone();
+            leg.filled_avg_price = order_update.filled_avg_price.clone();
+            if order_update.limit_price.is_some() {
+                leg.limit_price = order_update.limit_price.clone();
+            }
+            leg.updated_at = Some(received_at.format("%Y-%m-%d %H:%M:%S").to_string());
+            debug!("✓ Updated leg {} - Status: {}", leg.id, leg.status);
+            return;
+        }
+
         if is_terminal_state {
             // Remove the order from the list
             if let Some(pos) = self.orders.iter().position(|o| o.id == order_update.id) {
-                self.orders.remove(pos);
-                println!(
+                let removed = self.orders.remove(pos);
+                info!(
                     "🗑️  Removed {} order {} from list",
                     order_update.status, order_update.id
                 );
+                // A filled bracket entry leaves its exit legs working; they stay grouped
+                // under the first of them
+                let mut open_legs = removed.legs.into_iter().filter(|leg| leg.is_open());
+                if let Some(mut first) = open_legs.next() {
+                    first.legs = open_legs.collect();
+                    info!(
+                        "✓ Kept {} open legs of order {}",
+                        first.legs.len() + 1,
+                        order_update.id
+                    );
+                    self.orders.insert(pos, first);
+                }
             } else {
-                println!(
+                info!(
                     "ℹ️  Order {} is {} but not found in list",
                     order_update.id, order_update.status
                 );
@@ -149,8 +787,11 @@ impl Account {
             existing_order.limit_price = order_update.limit_price.clone();
             existing_order.status = order_update.status.clone();
             existing_order.created_at = order_update.created_at.clone();
+            existing_order.filled_qty = order_update.filled_qty.clone();
+            existing_order.filled_avg_price = order_update.filled_avg_price.clone();
+            existing_order.updated_at = Some(received_at.format("%Y-%m-%d %H:%M:%S").to_string());
 
-            println!(
+            info!(
                 "✓ Updated order {} - Status: {}",
                 existing_order.id, existing_order.status
             );
@@ -163,32 +804,208 @@ impl Account {
                 qty: order_update.qty.clone(),
                 order_type: order_update.order_type.clone(),
                 limit_price: order_update.limit_price.clone(),
+                stop_price: None,
                 status: order_update.status.clone(),
                 created_at: order_update.created_at.clone(),
+                filled_qty: order_update.filled_qty.clone(),
+                filled_avg_price: order_update.filled_avg_price.clone(),
+                submitted_at: None,
+                updated_at: Some(received_at.format("%Y-%m-%d %H:%M:%S").to_string()),
+                filled_at: None,
+                legs: Vec::new(),
             };
 
-            println!("✓ Added new order {}", new_order.id);
+            info!("✓ Added new order {}", new_order.id);
             self.orders.push(new_order);
         }
     }
+
+    /// Open the detail panel for an order, or close it if it is already open
+    pub fn toggle_selected_order(&mut self, order_id: &str) {
+        if self.selected_order_id.as_deref() == Some(order_id) {
+            self.selected_order_id = None;
+        } else {
+            self.selected_order_id = Some(order_id.to_string());
+        }
+    }
+
+    /// The order shown in the detail panel (searches open orders and their legs, then
+    /// history)
+    pub fn selected_order(&self) -> Option<&Order> {
+        let id = self.selected_order_id.as_ref()?;
+        self.orders
+            .iter()
+            .flat_map(|o| std::iter::once(o).chain(o.legs.iter()))
+            .chain(self.order_history.iter())
+            .find(|o| &o.id == id)
+    }
+
+    /// Select a positions sort column, flipping direction when it is already active
+    pub fn toggle_positions_sort(&mut self, column: PositionSortColumn) {
+        if self.positions_sort == Some(column) {
+            self.positions_sort_ascending = !self.positions_sort_ascending;
+        } else {
+            self.positions_sort = Some(column);
+            // Text sorts A-Z first, numeric sorts biggest first
+            self.positions_sort_ascending = column == PositionSortColumn::Symbol;
+        }
+    }
+
+    /// Sort direction for a column (Some(ascending) if it is the active sort)
+    pub fn positions_sort_direction(&self, column: PositionSortColumn) -> Option<bool> {
+        (self.positions_sort == Some(column)).then_some(self.positions_sort_ascending)
+    }
+
+    /// Positions after applying the quick filter and the selected sort
+    pub fn visible_positions(&self) -> Vec<&Position> {
+        let filter = self.positions_filter.trim().to_uppercase();
+        let mut positions: Vec<&Position> = self
+            .positions
+            .iter()
+            .filter(|p| filter.is_empty() || p.symbol.contains(&filter))
+            .collect();
+
+        if let Some(column) = self.positions_sort {
+            let number = |value: &str| value.parse::<f64>().unwrap_or(0.0);
+            positions.sort_by(|a, b| {
+                let ordering = match column {
+                    PositionSortColumn::Symbol => a.symbol.cmp(&b.symbol),
+                    PositionSortColumn::MarketValue => {
+                        number(&a.market_value).total_cmp(&number(&b.market_value))
+                    }
+                    PositionSortColumn::UnrealizedPl => {
+                        number(&a.unrealized_pl).total_cmp(&number(&b.unrealized_pl))
+                    }
+                    PositionSortColumn::UnrealizedPlpc => {
+                        number(&a.unrealized_plpc).total_cmp(&number(&b.unrealized_plpc))
+                    }
+                };
+                if self.positions_sort_ascending {
+                    ordering
+                } else {
+                    ordering.reverse()
+                }
+            });
+        }
+
+        positions
+    }
+
+    /// Select an orders sort column, flipping direction when it is already active
+    pub fn toggle_orders_sort(&mut self, column: OrderSortColumn) {
+        if self.orders_sort == column {
+            self.orders_sort_ascending = !self.orders_sort_ascending;
+        } else {
+            self.orders_sort = column;
+            // Newest orders first, text columns A-Z
+            self.orders_sort_ascending = column != OrderSortColumn::CreatedAt;
+        }
+    }
+
+    /// Sort direction for a column (Some(ascending) if it is the active sort)
+    pub fn orders_sort_direction(&self, column: OrderSortColumn) -> Option<bool> {
+        (self.orders_sort == column).then_some(self.orders_sort_ascending)
+    }
+
+    /// Open orders after applying the search box, side/type filters, and sort
+    pub fn visible_orders(&self) -> Vec<&Order> {
+        let search = self.orders_search.trim().to_uppercase();
+        // Legs are shown under their parent, so copies listed on their own are skipped
+        let leg_ids: std::collections::HashSet<&str> = self
+            .orders
+            .iter()
+            .flat_map(|order| order.legs.iter().map(|leg| leg.id.as_str()))
+            .collect();
+        let mut orders: Vec<&Order> = self
+            .orders
+            .iter()
+            .filter(|o| !leg_ids.contains(o.id.as_str()))
+            .filter(|o| search.is_empty() || o.symbol.contains(&search))
+            .filter(|o| self.orders_side_filter.matches(&o.side))
+            .filter(|o| self.orders_type_filter.matches(&o.order_type))
+            .collect();
+
+        orders.sort_by(|a, b| {
+            let ordering = match self.orders_sort {
+                OrderSortColumn::CreatedAt => a.created_at.cmp(&b.created_at),
+                OrderSortColumn::Symbol => a.symbol.cmp(&b.symbol),
+                OrderSortColumn::Status => a.status.to_lowercase().cmp(&b.status.to_lowercase()),
+            };
+            if self.orders_sort_ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+
+        orders
+    }
+
+    /// Activities filtered by the selected category
+    pub fn filtered_activities(&self) -> Vec<&Activity> {
+        self.activities
+            .iter()
+            .filter(|a| self.activity_filter.matches(&a.activity_type))
+            .collect()
+    }
+
+    /// Order history filtered by the selected status and symbol filters
+    pub fn filtered_order_history(&self) -> Vec<&Order> {
+        let symbol_filter = self.history_symbol_filter.trim().to_uppercase();
+        self.order_history
+            .iter()
+            .filter(|o| self.history_status_filter.matches(&o.status))
+            .filter(|o| symbol_filter.is_empty() || o.symbol.contains(&symbol_filter))
+            .collect()
+    }
+}
+
+/// Account snapshot returned by `fetch_account_sync`
+pub struct AccountSnapshot {
+    pub account_number: String,
+    pub status: String,
+    pub buying_power: f64,
+    pub cash: f64,
+    pub portfolio_value: f64,
+    pub equity: f64,
+    pub last_equity: f64,
+    pub daytrade_count: i64,
+    pub pattern_day_trader: bool,
+    pub initial_margin: f64,
+    pub maintenance_margin: f64,
+    pub long_market_value: f64,
+    pub short_market_value: f64,
+    pub sma: f64,
+    pub multiplier: f64,
+}
+
+impl Account {
+    /// Apply a freshly fetched account snapshot
+    pub fn apply_snapshot(&mut self, snapshot: AccountSnapshot) {
+        self.account_number = Some(snapshot.account_number);
+        self.account_status = Some(snapshot.status);
+        self.buying_power = Some(snapshot.buying_power);
+        self.cash = Some(snapshot.cash);
+        self.portfolio_value = Some(snapshot.portfolio_value);
+        self.equity = Some(snapshot.equity);
+        self.last_equity = Some(snapshot.last_equity);
+        self.daytrade_count = Some(snapshot.daytrade_count);
+        self.pattern_day_trader = Some(snapshot.pattern_day_trader);
+        self.initial_margin = Some(snapshot.initial_margin);
+        self.maintenance_margin = Some(snapshot.maintenance_margin);
+        self.long_market_value = Some(snapshot.long_market_value);
+        self.short_market_value = Some(snapshot.short_market_value);
+        self.sma = Some(snapshot.sma);
+        self.multiplier = Some(snapshot.multiplier);
+    }
 }
 
 // Synchronous API functions (run in background threads)
 
 /// Fetch account information
-pub fn fetch_account_sync() -> Result<(String, String, f64, f64, f64, f64), String> {
-    let rt = tokio::runtime::Runtime::new().map_err(|e| format!("Runtime error: {:?}", e))?;
-
-    rt.block_on(async {
-        let config = match AlpacaConfig::from_env() {
-            Ok(config) => config,
-            Err(e) => {
-                return Err(format!(
-                    "Error loading config: {:?}. Please set APCA_API_KEY_ID and APCA_API_SECRET_KEY environment variables.",
-                    e
-                ));
-            }
-        };
+pub fn fetch_account_sync() -> Result<AccountSnapshot, TerminalError> {
+    crate::api::block_on(async {
+        let config = crate::credentials::alpaca_config()?;
 
         let client = TradingClient::new(config);
 
@@ -197,39 +1014,35 @@ pub fn fetch_account_sync() -> Result<(String, String, f64, f64, f64, f64), Stri
         match result {
             Ok(account) => {
                 // Parse string values to f64
-                let buying_power = account.buying_power.parse::<f64>().unwrap_or(0.0);
-                let cash = account.cash.parse::<f64>().unwrap_or(0.0);
-                let portfolio_value = account.portfolio_value.parse::<f64>().unwrap_or(0.0);
-                let equity = account.equity.parse::<f64>().unwrap_or(0.0);
-
-                Ok((
-                    account.account_number,
-                    format!("{:?}", account.status),
-                    buying_power,
-                    cash,
-                    portfolio_value,
-                    equity,
-                ))
-            },
-            Err(e) => Err(format!("Error fetching account: {:?}", e)),
+                let parse = |value: &str| value.parse::<f64>().unwrap_or(0.0);
+
+                Ok(AccountSnapshot {
+                    status: format!("{:?}", account.status),
+                    buying_power: parse(&account.buying_power),
+                    cash: parse(&account.cash),
+                    portfolio_value: parse(&account.portfolio_value),
+                    equity: parse(&account.equity),
+                    last_equity: parse(&account.last_equity),
+                    daytrade_count: account.daytrade_count,
+                    pattern_day_trader: account.pattern_day_trader,
+                    initial_margin: parse(&account.initial_margin),
+                    maintenance_margin: parse(&account.maintenance_margin),
+                    long_market_value: parse(&account.long_market_value),
+                    short_market_value: parse(&account.short_market_value),
+                    sma: parse(&account.sma),
+                    multiplier: parse(&account.multiplier),
+                    account_number: account.account_number,
+                })
+            }
+            Err(e) => Err(TerminalError::api(e)),
         }
     })
 }
 
 /// Fetch positions
-pub fn fetch_positions_sync() -> Result<Vec<Position>, String> {
-    let rt = tokio::runtime::Runtime::new().map_err(|e| format!("Runtime error: {:?}", e))?;
-
-    rt.block_on(async {
-        let config = match AlpacaConfig::from_env() {
-            Ok(config) => config,
-            Err(e) => {
-                return Err(format!(
-                    "Error loading config: {:?}. Please set APCA_API_KEY_ID and APCA_API_SECRET_KEY environment variables.",
-                    e
-                ));
-            }
-        };
+pub fn fetch_positions_sync() -> Result<Vec<Position>, TerminalError> {
+    crate::api::block_on(async {
+        let config = crate::credentials::alpaca_config()?;
 
         let client = TradingClient::new(config);
 
@@ -251,50 +1064,207 @@ pub fn fetch_positions_sync() -> Result<Vec<Position>, String> {
                     .collect();
                 Ok(mapped_positions)
             }
-            Err(e) => Err(format!("Error fetching positions: {:?}", e)),
+            Err(e) => Err(TerminalError::api(e)),
         }
     })
 }
 
-/// Fetch orders
-pub fn fetch_orders_sync() -> Result<Vec<Order>, String> {
-    let rt = tokio::runtime::Runtime::new().map_err(|e| format!("Runtime error: {:?}", e))?;
+/// Convert an API order (and its legs) into the display model
+fn map_order(o: ApiOrder) -> Order {
+    let format_time = |t: chrono::DateTime<chrono::Utc>| t.format("%Y-%m-%d %H:%M:%S").to_string();
 
-    rt.block_on(async {
-        let config = match AlpacaConfig::from_env() {
-            Ok(config) => config,
-            Err(e) => {
-                return Err(format!(
-                    "Error loading config: {:?}. Please set APCA_API_KEY_ID and APCA_API_SECRET_KEY environment variables.",
-                    e
-                ));
-            }
-        };
+    Order {
+        id: o.id,
+        symbol: o.symbol,
+        side: format!("{:?}", o.side),
+        qty: o.qty.unwrap_or("0".to_string()),
+        order_type: format!("{:?}", o.order_type),
+        limit_price: o.limit_price,
+        stop_price: o.stop_price,
+        status: format!("{:?}", o.status),
+        created_at: o.created_at.format("%Y-%m-%d %H:%M").to_string(),
+        filled_qty: o.filled_qty,
+        filled_avg_price: o.filled_avg_price,
+        submitted_at: o.submitted_at.map(format_time),
+        updated_at: o.updated_at.map(format_time),
+        filled_at: o.filled_at.map(format_time),
+        legs: o
+            .legs
+            .unwrap_or_default()
+            .into_iter()
+            .map(map_order)
+            .collect(),
+    }
+}
+
+/// Number of open orders requested per page
+pub const ORDERS_PAGE_SIZE: usize = 100;
+
+/// A page of open orders, newest first
+pub struct OrdersPage {
+    pub orders: Vec<Order>,
+    // `until` cursor for the next page (created_at of the oldest order), None on the last page
+    pub next_until: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Fetch a page of open orders created before `until` (None = most recent page)
+pub fn fetch_orders_sync(
+    until: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<OrdersPage, TerminalError> {
+    crate::api::block_on(async {
+        let config = crate::credentials::alpaca_config()?;
 
         let client = TradingClient::new(config);
 
-        // Get open orders (status="open")
-        let result = client.get_orders(Some("open"), Some(50)).await;
+        // Get open orders (status="open"), paging backwards from `until`
+        let result = client
+            .get_orders_paginated(Some("open"), Some(ORDERS_PAGE_SIZE), None, until)
+            .await;
 
         match result {
             Ok(orders) => {
-                let mapped_orders = orders
-                    .into_iter()
-                    .map(|o| Order {
-                        id: o.id,
-                        symbol: o.symbol,
-                        side: format!("{:?}", o.side),
-                        qty: o.qty.unwrap_or("0".to_string()),
-                        order_type: format!("{:?}", o.order_type),
-                        limit_price: o.limit_price,
-                        status: format!("{:?}", o.status),
-                        created_at: o.created_at.format("%Y-%m-%d %H:%M").to_string(),
-                    })
-                    .collect();
-                Ok(mapped_orders)
+                // A full page means there may be older orders left
+                let next_until = if orders.len() >= ORDERS_PAGE_SIZE {
+                    orders.iter().map(|o| o.created_at).min()
+                } else {
+                    None
+                };
+
+                let mapped_orders = orders.into_iter().map(map_order).collect();
+                Ok(OrdersPage {
+                    orders: mapped_orders,
+                    next_until,
+                })
             }
-            Err(e) => Err(format!("Error fetching orders: {:?}", e)),
+            Err(e) => Err(TerminalError::api(e)),
+        }
+    })
+}
+
+/// Closed orders requested per history page (the API's maximum)
+const HISTORY_PAGE_SIZE: usize = 500;
+
+/// Stop paging after this many pages (25,000 orders)
+const MAX_HISTORY_PAGES: usize = 50;
+
+/// Fetch closed orders (filled, canceled, expired, rejected) created after `since`, newest
+/// first, paging backwards with the oldest order's created_at as `until`
+pub fn fetch_order_history_sync(
+    since: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<Vec<Order>, TerminalError> {
+    let config = crate::credentials::alpaca_config()?;
+
+    let client = TradingClient::new(config);
+
+    let mut orders: Vec<Order> = Vec::new();
+    let mut until: Option<chrono::DateTime<chrono::Utc>> = None;
+    for _ in 0..MAX_HISTORY_PAGES {
+        // Each page is its own call so it waits for its own rate limiter slot
+        let page = crate::api::block_on(async {
+            // Get closed orders (status="closed") in the range, newest first
+            client
+                .get_orders_paginated(Some("closed"), Some(HISTORY_PAGE_SIZE), since, until)
+                .await
+                .map_err(TerminalError::api)
+        })?;
+
+        // A full page means there may be older orders left
+        until = if page.len() >= HISTORY_PAGE_SIZE {
+            page.iter().map(|o| o.created_at).min()
+        } else {
+            None
+        };
+        orders.extend(page.into_iter().map(map_order));
+        if until.is_none() {
+            return Ok(orders);
+        }
+    }
+
+    warn!(
+        "Order history stopped after {} orders; older orders are not shown",
+        orders.len()
+    );
+    Ok(orders)
+}
+
+/// Activities per request (the API's maximum)
+pub const ACTIVITIES_PAGE_SIZE: usize = 100;
+
+/// Stop paging after this many pages (20,000 activities) and report the history as partial
+const MAX_ACTIVITY_PAGES: usize = 200;
+
+/// The account's activities, newest first
+pub struct ActivityHistory {
+    pub activities: Vec<Activity>,
+    // Paging reached the oldest activity; lots and tax reports are only right when true
+    pub complete: bool,
+}
+
+/// Fetch every account activity, paging back with the last activity's id as `page_token`
+/// until a short page shows the oldest has been reached
+pub fn fetch_activities_sync() -> Result<ActivityHistory, TerminalError> {
+    let config = crate::credentials::alpaca_config()?;
+
+    let client = TradingClient::new(config);
+
+    let mut activities: Vec<Activity> = Vec::new();
+    let mut page_token: Option<String> = None;
+    for _ in 0..MAX_ACTIVITY_PAGES {
+        // Each page is its own call so it waits for its own rate limiter slot
+        let page = crate::api::block_on(async {
+            // All activity types, newest first
+            client
+                .get_account_activities_paginated(
+                    None,
+                    Some(ACTIVITIES_PAGE_SIZE),
+                    page_token.clone(),
+                )
+                .await
+                .map_err(TerminalError::api)
+        })?;
+        let last_page = page.len() < ACTIVITIES_PAGE_SIZE;
+        activities.extend(page.into_iter().map(|a| match a {
+            AccountActivity::Trade(t) => Activity {
+                id: t.id,
+                activity_type: t.activity_type,
+                symbol: Some(t.symbol),
+                side: Some(format!("{:?}", t.side)),
+                qty: Some(t.qty),
+                price: Some(t.price),
+                net_amount: None,
+                description: None,
+                order_id: Some(t.order_id),
+                timestamp: t.transaction_time,
+            },
+            AccountActivity::NonTrade(n) => Activity {
+                id: n.id,
+                activity_type: n.activity_type,
+                symbol: n.symbol,
+                side: None,
+                qty: n.qty,
+                price: n.per_share_amount,
+                net_amount: Some(n.net_amount),
+                description: n.description,
+                order_id: None,
+                timestamp: n.date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc(),
+            },
+        }));
+        if last_page {
+            return Ok(ActivityHistory {
+                activities,
+                complete: true,
+            });
         }
+        page_token = activities.last().map(|activity| activity.id.clone());
+    }
+
+    warn!(
+        "⚠️  Stopped loading activities after {} pages; older history is missing",
+        MAX_ACTIVITY_PAGES
+    );
+    Ok(ActivityHistory {
+        activities,
+        complete: false,
     })
 }
 
@@ -304,28 +1274,19 @@ pub fn submit_order_sync(
     side: OrderSide,
     order_type: OrderType,
     qty: f64,
+    notional: Option<f64>,
     limit_price: Option<f64>,
     time_in_force: OrderTimeInForce,
-) -> Result<String, String> {
-    let rt = tokio::runtime::Runtime::new().map_err(|e| format!("Runtime error: {:?}", e))?;
-
-    rt.block_on(async {
-        let config = match AlpacaConfig::from_env() {
-            Ok(config) => config,
-            Err(e) => {
-                return Err(format!(
-                    "Error loading config: {:?}. Please set APCA_API_KEY_ID and APCA_API_SECRET_KEY environment variables.",
-                    e
-                ));
-            }
-        };
+) -> Result<String, TerminalError> {
+    crate::api::block_on(async {
+        let config = crate::credentials::alpaca_config()?;
 
         let client = TradingClient::new(config);
 
         let order_request = OrderRequest {
             symbol: symbol.clone(),
-            qty: Some(qty.to_string()),
-            notional: None,
+            qty: notional.is_none().then(|| qty.to_string()),
+            notional: notional.map(|n| format!("{:.2}", n)),
             side,
             order_type,
             time_in_force,
@@ -344,51 +1305,77 @@ pub fn submit_order_sync(
 
         match result {
             Ok(order) => Ok(order.id),
-            Err(e) => Err(format!("Failed to submit order: {:?}", e)),
+            Err(e) => Err(TerminalError::api(e)),
         }
     })
 }
 
-/// Cancel an order
-pub fn cancel_order_sync(order_id: String) -> Result<(), String> {
-    let rt = tokio::runtime::Runtime::new().map_err(|e| format!("Runtime error: {:?}", e))?;
-
-    rt.block_on(async {
-        let config = match AlpacaConfig::from_env() {
-            Ok(config) => config,
-            Err(e) => {
-                return Err(format!(
-                    "Error loading config: {:?}. Please set APCA_API_KEY_ID and APCA_API_SECRET_KEY environment variables.",
-                    e
-                ));
-            }
+/// Submit a GTC OCO exit: a take-profit limit and a stop-loss stop, where either filling
+/// cancels the other
+pub fn submit_oco_exit_sync(
+    symbol: String,
+    side: OrderSide,
+    qty: f64,
+    take_profit: f64,
+    stop_loss: f64,
+) -> Result<String, TerminalError> {
+    crate::api::block_on(async {
+        let config = crate::credentials::alpaca_config()?;
+
+        let client = TradingClient::new(config);
+
+        let order_request = OrderRequest {
+            symbol: symbol.clone(),
+            qty: Some(qty.to_string()),
+            notional: None,
+            side,
+            order_type: OrderType::Limit,
+            time_in_force: OrderTimeInForce::Gtc,
+            limit_price: None,
+            stop_price: None,
+            extended_hours: Some(false),
+            client_order_id: None,
+            order_class: Some(OrderClass::Oco),
+            take_profit: Some(TakeProfit {
+                limit_price: format!("{:.2}", take_profit),
+            }),
+            stop_loss: Some(StopLoss {
+                stop_price: format!("{:.2}", stop_loss),
+                limit_price: None,
+            }),
+            trail_price: None,
+            trail_percent: None,
         };
 
+        let result = client.submit_order(order_request).await;
+
+        match result {
+            Ok(order) => Ok(order.id),
+            Err(e) => Err(TerminalError::api(e)),
+        }
+    })
+}
+
+/// Cancel an order
+pub fn cancel_order_sync(order_id: String) -> Result<(), TerminalError> {
+    crate::api::block_on(async {
+        let config = crate::credentials::alpaca_config()?;
+
         let client = TradingClient::new(config);
 
         let result = client.cancel_order(&order_id).await;
 
         match result {
             Ok(_) => Ok(()),
-            Err(e) => Err(format!("Failed to cancel order: {:?}", e)),
+            Err(e) => Err(TerminalError::api(e)),
         }
     })
 }
 
 /// Close a position
-pub fn close_position_sync(symbol: String) -> Result<(), String> {
-    let rt = tokio::runtime::Runtime::new().map_err(|e| format!("Runtime error: {:?}", e))?;
-
-    rt.block_on(async {
-        let config = match AlpacaConfig::from_env() {
-            Ok(config) => config,
-            Err(e) => {
-                return Err(format!(
-                    "Error loading config: {:?}. Please set APCA_API_KEY_ID and APCA_API_SECRET_KEY environment variables.",
-                    e
-                ));
-            }
-        };
+pub fn close_position_sync(symbol: String) -> Result<(), TerminalError> {
+    crate::api::block_on(async {
+        let config = crate::credentials::alpaca_config()?;
 
         let client = TradingClient::new(config);
 
@@ -396,7 +1383,7 @@ pub fn close_position_sync(symbol: String) -> Result<(), String> {
 
         match result {
             Ok(_) => Ok(()),
-            Err(e) => Err(format!("Failed to close position: {:?}", e)),
+            Err(e) => Err(TerminalError::api(e)),
         }
     })
 }
@@ -0,0 +1,73 @@
+// Account configuration module for reading and updating trading restrictions
+
+use crate::error::TerminalError;
+use alpaca_markets::TradingClient;
+
+/// Trade confirmation email options
+pub const TRADE_CONFIRM_EMAIL_OPTIONS: [&str; 2] = ["all", "none"];
+
+/// Day trading buying power check options
+pub const DTBP_CHECK_OPTIONS: [&str; 3] = ["entry", "exit", "both"];
+
+/// Editable account configuration
+#[derive(Clone, PartialEq)]
+pub struct AccountConfig {
+    pub no_shorting: bool,
+    pub suspend_trade: bool,
+    pub trade_confirm_email: String,
+    pub dtbp_check: String,
+}
+
+/// Fetch the account configuration
+pub fn fetch_account_config_sync() -> Result<AccountConfig, TerminalError> {
+    crate::api::block_on(async {
+        let config = crate::credentials::alpaca_config()?;
+
+        let client = TradingClient::new(config);
+
+        let result = client.get_account_configurations().await;
+
+        match result {
+            Ok(configurations) => Ok(AccountConfig {
+                no_shorting: configurations.no_shorting,
+                suspend_trade: configurations.suspend_trade,
+                trade_confirm_email: configurations.trade_confirm_email,
+                dtbp_check: configurations.dtbp_check,
+            }),
+            Err(e) => Err(TerminalError::api(e)),
+        }
+    })
+}
+
+/// Update the account configuration, returning the configuration stored by the server
+pub fn update_account_config_sync(
+    account_config: AccountConfig,
+) -> Result<AccountConfig, TerminalError> {
+    crate::api::block_on(async {
+        let config = crate::credentials::alpaca_config()?;
+
+        let client = TradingClient::new(config);
+
+        // Start from the current configuration so fields we don't edit are preserved
+        let mut configurations = client
+            .get_account_configurations()
+            .await
+            .map_err(TerminalError::api)?;
+        configurations.no_shorting = account_config.no_shorting;
+        configurations.suspend_trade = account_config.suspend_trade;
+        configurations.trade_confirm_email = account_config.trade_confirm_email;
+        configurations.dtbp_check = account_config.dtbp_check;
+
+        let result = client.update_account_configurations(&configurations).await;
+
+        match result {
+            Ok(updated) => Ok(AccountConfig {
+                no_shorting: updated.no_shorting,
+                suspend_trade: updated.suspend_trade,
+                trade_confirm_email: updated.trade_confirm_email,
+                dtbp_check: updated.dtbp_check,
+            }),
+            Err(e) => Err(TerminalError::api(e)),
+        }
+    })
+}
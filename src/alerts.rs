@@ -0,0 +1,287 @@
+// Alerts module for price alerts, persisted between sessions
+
+use crate::config::DataFeed;
+use crate::error::TerminalError;
+use alpaca_markets::MarketDataClient;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tracing::{error, info};
+
+/// Price condition that fires an alert
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AlertCondition {
+    Above(f64),
+    Below(f64),
+    /// Price moves at least `percent` either way from `reference`
+    PercentMove {
+        reference: f64,
+        percent: f64,
+    },
+    /// Price pulls back `percent` from the highest price seen since the alert was (re)armed
+    Trailing {
+        peak: f64,
+        percent: f64,
+    },
+}
+
+impl AlertCondition {
+    /// Feed a price to the condition (trailing alerts follow new highs) and report whether it's met
+    pub fn update(&mut self, price: f64) -> bool {
+        match self {
+            AlertCondition::Above(level) => price >= *level,
+            AlertCondition::Below(level) => price <= *level,
+            AlertCondition::PercentMove { reference, percent } => {
+                (price - *reference).abs() / *reference * 100.0 >= *percent
+            }
+            AlertCondition::Trailing { peak, percent } => {
+                *peak = peak.max(price);
+                price <= *peak * (1.0 - *percent / 100.0)
+            }
+        }
+    }
+
+    /// Restart a relative condition from the price that fired it (repeating alerts).
+    /// Returns false for fixed levels, which re-arm once the price crosses back instead.
+    fn rebase(&mut self, price: f64) -> bool {
+        match self {
+            AlertCondition::PercentMove { reference, .. } => {
+                *reference = price;
+                true
+            }
+            AlertCondition::Trailing { peak, .. } => {
+                *peak = price;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            AlertCondition::Above(level) => format!("≥ ${:.2}", level),
+            AlertCondition::Below(level) => format!("≤ ${:.2}", level),
+            AlertCondition::PercentMove { reference, percent } => {
+                format!("±{}% from ${:.2}", percent, reference)
+            }
+            AlertCondition::Trailing { peak, percent } => {
+                format!("-{}% from high ${:.2}", percent, peak)
+            }
+        }
+    }
+}
+
+/// Alert type picked in the sidebar form
+#[derive(Clone, Copy, PartialEq)]
+pub enum AlertKind {
+    Above,
+    Below,
+    PercentMove,
+    Trailing,
+}
+
+impl AlertKind {
+    pub const ALL: [AlertKind; 4] = [
+        AlertKind::Above,
+        AlertKind::Below,
+        AlertKind::PercentMove,
+        AlertKind::Trailing,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AlertKind::Above => "Above",
+            AlertKind::Below => "Below",
+            AlertKind::PercentMove => "Move %",
+            AlertKind::Trailing => "Trailing %",
+        }
+    }
+
+    /// Whether the form value is a percent rather than a price
+    pub fn is_percent(&self) -> bool {
+        matches!(self, AlertKind::PercentMove | AlertKind::Trailing)
+    }
+
+    /// Build the condition from the form value and the current price
+    pub fn condition(&self, value: f64, current_price: f64) -> AlertCondition {
+        match self {
+            AlertKind::Above => AlertCondition::Above(value),
+            AlertKind::Below => AlertCondition::Below(value),
+            AlertKind::PercentMove => AlertCondition::PercentMove {
+                reference: current_price,
+                percent: value,
+            },
+            AlertKind::Trailing => AlertCondition::Trailing {
+                peak: current_price,
+                percent: value,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PriceAlert {
+    pub id: u64,
+    pub symbol: String,
+    pub condition: AlertCondition,
+    // Repeating alerts re-arm once the condition stops being met; one-shot alerts are removed
+    pub repeating: bool,
+    pub armed: bool,
+}
+
+/// Alert that fired, with the price that triggered it
+pub struct TriggeredAlert {
+    pub alert: PriceAlert,
+    pub price: f64,
+}
+
+/// Active alerts saved to `<config dir>/alpaca_terminal/alerts.json`
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertStore {
+    pub alerts: Vec<PriceAlert>,
+    next_id: u64,
+}
+
+fn alerts_path() -> Option<PathBuf> {
+    crate::settings::config_dir().map(|dir| dir.join("alerts.json"))
+}
+
+impl AlertStore {
+    pub fn add(&mut self, symbol: String, condition: AlertCondition, repeating: bool) {
+        self.next_id += 1;
+        self.alerts.push(PriceAlert {
+            id: self.next_id,
+            symbol,
+            condition,
+            repeating,
+            armed: true,
+        });
+    }
+
+    pub fn remove(&mut self, id: u64) {
+        self.alerts.retain(|alert| alert.id != id);
+    }
+
+    /// Distinct symbols with alerts
+    pub fn symbols(&self) -> Vec<String> {
+        let mut symbols: Vec<String> = Vec::new();
+        for alert in &self.alerts {
+            if !symbols.contains(&alert.symbol) {
+                symbols.push(alert.symbol.clone());
+            }
+        }
+        symbols
+    }
+
+    /// Evaluate a new price for a symbol, returning the alerts that fired.
+    /// One-shot alerts are removed; repeating alerts disarm until the condition clears.
+    pub fn check(&mut self, symbol: &str, price: f64) -> Vec<TriggeredAlert> {
+        let mut triggered = Vec::new();
+
+        for alert in self.alerts.iter_mut().filter(|a| a.symbol == symbol) {
+            let is_met = alert.condition.update(price);
+            if alert.armed && is_met {
+                triggered.push(TriggeredAlert {
+                    alert: alert.clone(),
+                    price,
+                });
+                // Relative alerts re-arm immediately from the new price
+                alert.armed = alert.repeating && alert.condition.rebase(price);
+            } else if !alert.armed && !is_met && alert.repeating {
+                alert.armed = true;
+            }
+        }
+
+        let fired_one_shot: Vec<u64> = triggered
+            .iter()
+            .filter(|t| !t.alert.repeating)
+            .map(|t| t.alert.id)
+            .collect();
+        self.alerts
+            .retain(|alert| !fired_one_shot.contains(&alert.id));
+
+        triggered
+    }
+
+    /// Load alerts from disk, falling back to none if missing or invalid
+    pub fn load() -> Self {
+        let Some(path) = alerts_path() else {
+            return Self::default();
+        };
+
+        match crate::persist::load::<AlertStore>(&path, "alerts") {
+            Some(store) => {
+                info!(
+                    "✅ Loaded {} alerts from {}",
+                    store.alerts.len(),
+                    path.display()
+                );
+                store
+            }
+            None => Self::default(),
+        }
+    }
+
+    /// Write alerts to disk
+    pub fn save(&self) {
+        let Some(path) = alerts_path() else {
+            return;
+        };
+
+        let result = crate::persist::save(&path, self);
+
+        if let Err(e) = result {
+            error!("✗ Error saving alerts to {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Fetch the latest trade price for each symbol (symbols that fail are skipped)
+pub fn fetch_latest_prices_sync(
+    symbols: Vec<String>,
+    data_feed: DataFeed,
+) -> Result<HashMap<String, f64>, TerminalError> {
+    crate::api::block_on(async {
+        let config = data_feed.configure(crate::credentials::alpaca_config()?);
+
+        let client = MarketDataClient::new(config);
+        let mut prices = HashMap::new();
+
+        for symbol in symbols {
+            let result = if crate::chart::is_crypto_symbol(&symbol) {
+                client.get_crypto_latest_trade(&symbol).await
+            } else {
+                client.get_latest_trade(&symbol).await
+            };
+
+            match result {
+                Ok(trade) => {
+                    prices.insert(symbol, trade.price);
+                }
+                Err(e) => error!("✗ Error fetching latest trade for {}: {:?}", symbol, e),
+            }
+        }
+
+        Ok(prices)
+    })
+}
+
+/// New-alert form in the sidebar
+pub struct AlertForm {
+    pub open: bool,
+    pub price: String,
+    pub kind: AlertKind,
+    pub repeating: bool,
+}
+
+impl AlertForm {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            price: String::new(),
+            kind: AlertKind::Above,
+            repeating: false,
+        }
+    }
+}
@@ -0,0 +1,51 @@
+// Api module: the shared Tokio runtime that all REST calls run on
+
+use crate::error::TerminalError;
+use crate::rate_limit;
+use std::future::Future;
+use std::sync::OnceLock;
+use tokio::runtime::{Builder, Runtime};
+use tracing::{error, info};
+
+// Built on first use; a build failure is kept so every call reports it
+static RUNTIME: OnceLock<Result<Runtime, TerminalError>> = OnceLock::new();
+
+fn runtime() -> Result<&'static Runtime, TerminalError> {
+    RUNTIME
+        .get_or_init(|| {
+            let runtime = Builder::new_multi_thread()
+                .worker_threads(2)
+                .thread_name("alpaca-api")
+                .enable_all()
+                .build();
+            match runtime {
+                Ok(runtime) => {
+                    info!("⚙️  Started shared API runtime");
+                    Ok(runtime)
+                }
+                Err(e) => {
+                    error!("❌ Failed to start API runtime: {:?}", e);
+                    Err(TerminalError::Config(format!("Runtime error: {:?}", e)))
+                }
+            }
+        })
+        .as_ref()
+        .map_err(|e| e.clone())
+}
+
+/// Run a REST call to completion on the shared runtime. Called from gpui background
+/// threads, which may block on it concurrently; each call waits its turn with the rate
+/// limiter first.
+pub fn block_on<T>(
+    future: impl Future<Output = Result<T, TerminalError>>,
+) -> Result<T, TerminalError> {
+    let runtime = runtime()?;
+    rate_limit::LIMITER.acquire();
+    let result = runtime.block_on(future);
+    match &result {
+        Ok(_) => rate_limit::LIMITER.succeeded(),
+        Err(TerminalError::RateLimited) => rate_limit::LIMITER.throttled(),
+        Err(_) => {}
+    }
+    result
+}
@@ -0,0 +1,53 @@
+// Asset module for symbol reference data (exchange, class, tradability, borrow, margin)
+
+use crate::error::TerminalError;
+use alpaca_markets::TradingClient;
+
+/// Asset information for the traded symbol
+#[derive(Clone)]
+pub struct AssetInfo {
+    pub symbol: String,
+    pub name: String,
+    pub exchange: String,
+    pub asset_class: String,
+    pub status: String,
+    pub tradable: bool,
+    pub fractionable: bool,
+    pub shortable: bool,
+    pub easy_to_borrow: bool,
+    pub marginable: bool,
+}
+
+impl AssetInfo {
+    /// Whether a short in this asset is restricted (not shortable or hard to borrow)
+    pub fn is_hard_to_borrow(&self) -> bool {
+        !self.shortable || !self.easy_to_borrow
+    }
+}
+
+/// Fetch the asset record for a symbol
+pub fn fetch_asset_sync(symbol: String) -> Result<AssetInfo, TerminalError> {
+    crate::api::block_on(async {
+        let config = crate::credentials::alpaca_config()?;
+
+        let client = TradingClient::new(config);
+
+        let result = client.get_asset(&symbol).await;
+
+        match result {
+            Ok(asset) => Ok(AssetInfo {
+                symbol: asset.symbol,
+                name: asset.name,
+                exchange: format!("{:?}", asset.exchange),
+                asset_class: format!("{:?}", asset.class),
+                status: format!("{:?}", asset.status),
+                tradable: asset.tradable,
+                fractionable: asset.fractionable,
+                shortable: asset.shortable,
+                easy_to_borrow: asset.easy_to_borrow,
+                marginable: asset.marginable,
+            }),
+            Err(e) => Err(TerminalError::for_symbol(e, &symbol)),
+        }
+    })
+}
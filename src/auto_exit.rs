@@ -0,0 +1,165 @@
+// Auto exit module: once a manual entry (order form, hotkey, chart ticket or scheduled
+// order) fills, an OCO take-profit/stop-loss exit is submitted for the filled quantity at
+// percentage offsets from the fill price
+
+use crate::hotkeys::HotkeySide;
+use crate::stream::OrderUpdate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Fill events kept for entries whose submit response hasn't come back yet
+const MAX_EARLY_FILLS: usize = 20;
+
+/// Saved with the other settings; the offsets are text so they can be edited in place
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoExitSettings {
+    pub enabled: bool,
+    // Percent above (long) or below (short) the fill price
+    pub take_profit_percent: String,
+    // Percent below (long) or above (short) the fill price
+    pub stop_loss_percent: String,
+}
+
+impl Default for AutoExitSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            take_profit_percent: "2".to_string(),
+            stop_loss_percent: "1".to_string(),
+        }
+    }
+}
+
+impl AutoExitSettings {
+    /// Take-profit and stop-loss percentages, or what's wrong with them
+    pub fn offsets(&self) -> Result<(f64, f64), String> {
+        let take_profit = self
+            .take_profit_percent
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|percent| *percent > 0.0)
+            .ok_or("Take-profit offset must be a positive percent")?;
+        let stop_loss = self
+            .stop_loss_percent
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|percent| *percent > 0.0 && *percent < 100.0)
+            .ok_or("Stop-loss offset must be a percent between 0 and 100")?;
+        Ok((take_profit, stop_loss))
+    }
+}
+
+/// OCO exit for a filled entry
+pub struct ExitOrder {
+    pub entry_id: String,
+    pub symbol: String,
+    // Opposite of the entry's side
+    pub side: HotkeySide,
+    pub qty: f64,
+    pub take_profit: f64,
+    pub stop_loss: f64,
+}
+
+impl ExitOrder {
+    /// Exit for an entry filled at `price`
+    pub fn new(
+        entry_id: String,
+        symbol: String,
+        entry_side: HotkeySide,
+        qty: f64,
+        price: f64,
+        (take_profit_percent, stop_loss_percent): (f64, f64),
+    ) -> Self {
+        let round = |price: f64| (price * 100.0).round() / 100.0;
+        let (side, take_profit, stop_loss) = match entry_side {
+            HotkeySide::Buy => (
+                HotkeySide::Sell,
+                price * (1.0 + take_profit_percent / 100.0),
+                price * (1.0 - stop_loss_percent / 100.0),
+            ),
+            HotkeySide::Sell => (
+                HotkeySide::Buy,
+                price * (1.0 - take_profit_percent / 100.0),
+                price * (1.0 + stop_loss_percent / 100.0),
+            ),
+        };
+        Self {
+            entry_id,
+            symbol,
+            side,
+            qty,
+            take_profit: round(take_profit),
+            stop_loss: round(stop_loss),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "{} {} {}: TP ${:.2} / SL ${:.2}",
+            self.side.label(),
+            self.qty,
+            self.symbol,
+            self.take_profit,
+            self.stop_loss
+        )
+    }
+}
+
+/// Whether an order update ends the order with shares filled (fully, or partially before
+/// a cancel or expiry)
+pub fn is_final_fill(update: &OrderUpdate) -> bool {
+    let filled = update.filled_qty.parse::<f64>().unwrap_or(0.0);
+    match update.event.as_str() {
+        "fill" => true,
+        "canceled" | "expired" => filled > 0.0,
+        _ => false,
+    }
+}
+
+/// Sidebar panel state and the orders being watched
+#[derive(Default)]
+pub struct AutoExits {
+    pub open: bool,
+    // Manual entries submitted while auto exits were on, waiting to fill
+    pending: HashSet<String>,
+    // Final fills of orders not (yet) known as entries, since a market order's fill can
+    // arrive over the stream before its submit response
+    early_fills: Vec<OrderUpdate>,
+    // Exit orders submitted this session, tagged in the Orders tab
+    pub exit_ids: HashSet<String>,
+}
+
+impl AutoExits {
+    /// Watch a submitted entry, returning its fill if that already arrived
+    pub fn watch(&mut self, order_id: String) -> Option<OrderUpdate> {
+        match self
+            .early_fills
+            .iter()
+            .position(|update| update.id == order_id)
+        {
+            Some(index) => Some(self.early_fills.remove(index)),
+            None => {
+                self.pending.insert(order_id);
+                None
+            }
+        }
+    }
+
+    /// Take a final fill for a watched entry, keeping ones for unknown orders briefly
+    pub fn take_fill(&mut self, update: &OrderUpdate) -> bool {
+        if !is_final_fill(update) {
+            return false;
+        }
+        if self.pending.remove(&update.id) {
+            return true;
+        }
+        self.early_fills.push(update.clone());
+        if self.early_fills.len() > MAX_EARLY_FILLS {
+            self.early_fills.remove(0);
+        }
+        false
+    }
+}
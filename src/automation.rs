@@ -0,0 +1,326 @@
+// Automation module: rules that submit an order when a streamed price crosses a level,
+// run only while the master switch is armed, with a log of what they did
+
+use crate::hotkeys::HotkeySide;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing::{error, info};
+
+/// Log entries kept
+const MAX_LOG: usize = 100;
+
+/// Price move that fires a rule
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Trigger {
+    CrossesAbove(f64),
+    CrossesBelow(f64),
+}
+
+impl Trigger {
+    /// Whether the move from `previous` to `price` crossed the level
+    fn crossed(&self, previous: f64, price: f64) -> bool {
+        match self {
+            Trigger::CrossesAbove(level) => previous < *level && price >= *level,
+            Trigger::CrossesBelow(level) => previous > *level && price <= *level,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Trigger::CrossesAbove(level) => format!("crosses above ${:.2}", level),
+            Trigger::CrossesBelow(level) => format!("crosses below ${:.2}", level),
+        }
+    }
+}
+
+/// Trigger type picked in the sidebar form
+#[derive(Clone, Copy, PartialEq)]
+pub enum TriggerKind {
+    Above,
+    Below,
+}
+
+impl TriggerKind {
+    pub const ALL: [TriggerKind; 2] = [TriggerKind::Above, TriggerKind::Below];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TriggerKind::Above => "Crosses above",
+            TriggerKind::Below => "Crosses below",
+        }
+    }
+
+    fn trigger(&self, level: f64) -> Trigger {
+        match self {
+            TriggerKind::Above => Trigger::CrossesAbove(level),
+            TriggerKind::Below => Trigger::CrossesBelow(level),
+        }
+    }
+}
+
+/// If `symbol`'s price crosses the trigger, submit an order (a limit order when
+/// `limit_price` is set, otherwise market). Rules fire once and then disable themselves,
+/// and only trade a live account once `allow_live` is turned on for them.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Rule {
+    pub id: u64,
+    pub symbol: String,
+    pub trigger: Trigger,
+    pub side: HotkeySide,
+    pub qty: f64,
+    pub limit_price: Option<f64>,
+    pub enabled: bool,
+    #[serde(default)]
+    pub allow_live: bool,
+    // Last streamed price, to detect a cross
+    #[serde(skip)]
+    last_price: Option<f64>,
+}
+
+impl Rule {
+    pub fn describe(&self) -> String {
+        let order = match self.limit_price {
+            Some(limit) => format!("limit {} {} @ ${:.2}", self.side.label(), self.qty, limit),
+            None => format!("market {} {}", self.side.label(), self.qty),
+        };
+        format!(
+            "If {} {}, {}",
+            self.symbol,
+            self.trigger.describe(),
+            order.to_lowercase()
+        )
+    }
+}
+
+/// Rule that fired, with the price that crossed
+pub struct FiredRule {
+    pub rule: Rule,
+    pub price: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub at: DateTime<Utc>,
+    pub message: String,
+    pub failed: bool,
+}
+
+/// Rules and the execution log, saved to `<config dir>/alpaca_terminal/automation.json`.
+/// The master switch isn't saved, so rules never run until armed in this session.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutomationStore {
+    #[serde(skip)]
+    pub armed: bool,
+    pub rules: Vec<Rule>,
+    // Newest last
+    pub log: Vec<LogEntry>,
+    next_id: u64,
+}
+
+fn automation_path() -> Option<PathBuf> {
+    crate::settings::config_dir().map(|dir| dir.join("automation.json"))
+}
+
+impl AutomationStore {
+    pub fn add(
+        &mut self,
+        symbol: String,
+        trigger: Trigger,
+        side: HotkeySide,
+        qty: f64,
+        limit_price: Option<f64>,
+    ) {
+        self.next_id += 1;
+        self.rules.push(Rule {
+            id: self.next_id,
+            symbol,
+            trigger,
+            side,
+            qty,
+            limit_price,
+            enabled: true,
+            allow_live: false,
+            last_price: None,
+        });
+    }
+
+    pub fn remove(&mut self, id: u64) {
+        self.rules.retain(|rule| rule.id != id);
+    }
+
+    /// Enable a fired or disabled rule, or disable it. A re-enabled rule waits for a fresh
+    /// cross rather than firing on the price it already passed.
+    pub fn toggle(&mut self, id: u64) {
+        if let Some(rule) = self.rules.iter_mut().find(|rule| rule.id == id) {
+            rule.enabled = !rule.enabled;
+            rule.last_price = None;
+        }
+    }
+
+    /// Let a rule trade a live account, or take that back
+    pub fn toggle_live(&mut self, id: u64) {
+        if let Some(rule) = self.rules.iter_mut().find(|rule| rule.id == id) {
+            rule.allow_live = !rule.allow_live;
+        }
+    }
+
+    /// Distinct symbols of enabled rules
+    pub fn symbols(&self) -> Vec<String> {
+        let mut symbols: Vec<String> = Vec::new();
+        for rule in self.rules.iter().filter(|rule| rule.enabled) {
+            if !symbols.contains(&rule.symbol) {
+                symbols.push(rule.symbol.clone());
+            }
+        }
+        symbols
+    }
+
+    /// Feed a new price for a symbol, returning the rules that fired (only while armed).
+    /// Prices are tracked while disarmed too, so arming doesn't act on an old cross.
+    pub fn check(&mut self, symbol: &str, price: f64) -> Vec<FiredRule> {
+        let mut fired = Vec::new();
+        for rule in self
+            .rules
+            .iter_mut()
+            .filter(|rule| rule.enabled && rule.symbol == symbol)
+        {
+            let previous = rule.last_price.replace(price);
+            if self.armed && previous.is_some_and(|previous| rule.trigger.crossed(previous, price))
+            {
+                rule.enabled = false;
+                fired.push(FiredRule {
+                    rule: rule.clone(),
+                    price,
+                });
+            }
+        }
+        fired
+    }
+
+    pub fn record(&mut self, message: String, failed: bool) {
+        self.log.push(LogEntry {
+            at: Utc::now(),
+            message,
+            failed,
+        });
+        if self.log.len() > MAX_LOG {
+            self.log.remove(0);
+        }
+    }
+
+    /// Load rules from disk, falling back to none if missing or invalid
+    pub fn load() -> Self {
+        let Some(path) = automation_path() else {
+            return Self::default();
+        };
+
+        match crate::persist::load::<AutomationStore>(&path, "automation") {
+            Some(store) => {
+                info!(
+                    "✅ Loaded {} automation rules from {}",
+                    store.rules.len(),
+                    path.display()
+                );
+                store
+            }
+            None => Self::default(),
+        }
+    }
+
+    /// Write rules and the log to disk
+    pub fn save(&self) {
+        let Some(path) = automation_path() else {
+            return;
+        };
+
+        let result = crate::persist::save(&path, self);
+
+        if let Err(e) = result {
+            error!(
+                "✗ Error saving automation rules to {}: {}",
+                path.display(),
+                e
+            );
+        }
+    }
+}
+
+/// Text inputs of the rule form
+#[derive(Clone, Copy, PartialEq)]
+pub enum AutomationField {
+    Level,
+    Quantity,
+    LimitPrice,
+}
+
+/// New-rule form in the sidebar
+pub struct AutomationForm {
+    pub open: bool,
+    pub kind: TriggerKind,
+    pub side: HotkeySide,
+    pub level: String,
+    pub quantity: String,
+    // Empty for a market order
+    pub limit_price: String,
+    pub message: Option<String>,
+}
+
+impl AutomationForm {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            kind: TriggerKind::Below,
+            side: HotkeySide::Buy,
+            level: String::new(),
+            quantity: String::new(),
+            limit_price: String::new(),
+            message: None,
+        }
+    }
+
+    pub fn field(&self, field: AutomationField) -> &String {
+        match field {
+            AutomationField::Level => &self.level,
+            AutomationField::Quantity => &self.quantity,
+            AutomationField::LimitPrice => &self.limit_price,
+        }
+    }
+
+    pub fn field_mut(&mut self, field: AutomationField) -> &mut String {
+        match field {
+            AutomationField::Level => &mut self.level,
+            AutomationField::Quantity => &mut self.quantity,
+            AutomationField::LimitPrice => &mut self.limit_price,
+        }
+    }
+
+    /// Trigger, quantity and limit price from the form, or what's wrong with it
+    pub fn parse(&self) -> Result<(Trigger, f64, Option<f64>), String> {
+        let level = self
+            .level
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|level| *level > 0.0)
+            .ok_or("Enter the trigger price")?;
+        let qty = self
+            .quantity
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|qty| *qty > 0.0)
+            .ok_or("Enter the quantity")?;
+        let limit_price = match self.limit_price.trim() {
+            "" => None,
+            text => Some(
+                text.parse::<f64>()
+                    .ok()
+                    .filter(|limit| *limit > 0.0)
+                    .ok_or("Limit price must be a positive number")?,
+            ),
+        };
+        Ok((self.kind.trigger(level), qty, limit_price))
+    }
+}
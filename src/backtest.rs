@@ -0,0 +1,256 @@
+// Backtest module: runs a long-only indicator strategy over historical bars. Signals are
+// taken on a bar's close and filled at the next bar's open, with slippage and a
+// commission per order.
+
+use crate::config::BacktestConfig;
+use crate::indicators;
+use crate::performance::EquityPoint;
+use crate::pnl::ClosedTrade;
+use alpaca_markets::Bar;
+use chrono::{DateTime, Utc};
+
+/// Bar counts offered in the Backtest tab
+pub const BAR_COUNTS: [u32; 3] = [250, 500, 1000];
+
+/// Strategy presets offered in the Backtest tab
+#[derive(Clone, Copy, PartialEq)]
+pub enum Strategy {
+    // Long while the fast SMA is above the slow one
+    SmaCross {
+        fast: usize,
+        slow: usize,
+    },
+    // Buy when RSI drops below `oversold`, sell when it rises above `overbought`
+    RsiReversion {
+        period: usize,
+        oversold: f64,
+        overbought: f64,
+    },
+    // Buy on a close above the prior `period`-bar high, sell on one below the prior low
+    Breakout {
+        period: usize,
+    },
+}
+
+impl Strategy {
+    pub const PRESETS: [Strategy; 5] = [
+        Strategy::SmaCross { fast: 10, slow: 30 },
+        Strategy::SmaCross {
+            fast: 50,
+            slow: 200,
+        },
+        Strategy::RsiReversion {
+            period: 14,
+            oversold: 30.0,
+            overbought: 70.0,
+        },
+        Strategy::Breakout { period: 20 },
+        Strategy::Breakout { period: 55 },
+    ];
+
+    pub fn label(&self) -> String {
+        match self {
+            Strategy::SmaCross { fast, slow } => format!("SMA {}/{}", fast, slow),
+            Strategy::RsiReversion {
+                period,
+                oversold,
+                overbought,
+            } => format!("RSI({}) {}/{}", period, oversold, overbought),
+            Strategy::Breakout { period } => format!("Breakout {}", period),
+        }
+    }
+
+    /// Whether the strategy wants to be long after each bar's close (None = no change)
+    fn signals(&self, bars: &[Bar]) -> Vec<Option<bool>> {
+        let closes: Vec<f64> = bars.iter().map(|bar| bar.close).collect();
+        match *self {
+            Strategy::SmaCross { fast, slow } => {
+                let fast = indicators::sma(&closes, fast);
+                let slow = indicators::sma(&closes, slow);
+                fast.iter()
+                    .zip(&slow)
+                    .map(|(fast, slow)| Some(fast.as_ref()? > slow.as_ref()?))
+                    .collect()
+            }
+            Strategy::RsiReversion {
+                period,
+                oversold,
+                overbought,
+            } => indicators::rsi(&closes, period)
+                .iter()
+                .map(|rsi| match rsi {
+                    Some(rsi) if *rsi < oversold => Some(true),
+                    Some(rsi) if *rsi > overbought => Some(false),
+                    _ => None,
+                })
+                .collect(),
+            Strategy::Breakout { period } => (0..bars.len())
+                .map(|index| {
+                    let prior = &bars[index.checked_sub(period)?..index];
+                    let high = prior.iter().map(|bar| bar.high).fold(f64::MIN, f64::max);
+                    let low = prior.iter().map(|bar| bar.low).fold(f64::MAX, f64::min);
+                    if bars[index].close > high {
+                        Some(true)
+                    } else if bars[index].close < low {
+                        Some(false)
+                    } else {
+                        None
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Result of a run
+pub struct BacktestReport {
+    pub symbol: String,
+    pub strategy: Strategy,
+    pub capital: f64,
+    pub first_at: DateTime<Utc>,
+    pub last_at: DateTime<Utc>,
+    // P&L from the starting capital after each bar, marked to the close
+    pub curve: Vec<EquityPoint>,
+    // P&L of buying with all the capital at the first bar's open and holding, per bar
+    pub buy_and_hold: Vec<f64>,
+    // Round trips, in closing order. A position still open at the end is closed at the
+    // last close.
+    pub trades: Vec<ClosedTrade>,
+    pub commission_paid: f64,
+    pub still_open: bool,
+}
+
+impl BacktestReport {
+    pub fn net(&self) -> f64 {
+        self.curve.last().map(|point| point.equity).unwrap_or(0.0)
+    }
+
+    pub fn return_percent(&self) -> f64 {
+        self.net() / self.capital * 100.0
+    }
+
+    pub fn buy_and_hold_percent(&self) -> f64 {
+        self.buy_and_hold.last().copied().unwrap_or(0.0) / self.capital * 100.0
+    }
+}
+
+/// Simulate `strategy` on `bars`, buying whole shares with all available cash
+pub fn run(
+    symbol: &str,
+    bars: &[Bar],
+    strategy: Strategy,
+    config: &BacktestConfig,
+) -> Result<BacktestReport, String> {
+    let (Some(first), Some(last)) = (bars.first(), bars.last()) else {
+        return Err(format!("No bars for {}", symbol));
+    };
+    let capital = config.initial_capital;
+    let slippage = config.slippage_bps / 10_000.0;
+    let commission = config.commission;
+    let signals = strategy.signals(bars);
+
+    let mut cash = capital;
+    let mut shares = 0.0;
+    let mut entry: Option<(DateTime<Utc>, f64)> = None;
+    let mut trades = Vec::new();
+    let mut commission_paid = 0.0;
+    let mut curve = Vec::with_capacity(bars.len());
+    let mut peak: f64 = 0.0;
+    let held_shares = (capital / first.open).floor();
+
+    for (index, bar) in bars.iter().enumerate() {
+        // Yesterday's signal fills at today's open
+        let wanted = index.checked_sub(1).and_then(|previous| signals[previous]);
+        match wanted {
+            Some(true) if shares == 0.0 => {
+                let price = bar.open * (1.0 + slippage);
+                let qty = ((cash - commission) / price).floor();
+                if qty > 0.0 {
+                    cash -= qty * price + commission;
+                    commission_paid += commission;
+                    shares = qty;
+                    entry = Some((bar.timestamp, price));
+                }
+            }
+            Some(false) if shares > 0.0 => {
+                let price = bar.open * (1.0 - slippage);
+                cash += shares * price - commission;
+                commission_paid += commission;
+                if let Some((opened_at, entry_price)) = entry.take() {
+                    trades.push(ClosedTrade {
+                        symbol: symbol.to_string(),
+                        qty: shares,
+                        entry_price,
+                        exit_price: price,
+                        opened_at,
+                        closed_at: bar.timestamp,
+                        is_long: true,
+                        // Both orders' commissions
+                        pnl: (price - entry_price) * shares - 2.0 * commission,
+                    });
+                }
+                shares = 0.0;
+            }
+            _ => {}
+        }
+
+        let equity = cash + shares * bar.close - capital;
+        peak = peak.max(equity);
+        curve.push(EquityPoint {
+            at: bar.timestamp,
+            equity,
+            drawdown: peak - equity,
+        });
+    }
+
+    let still_open = shares > 0.0;
+    if let Some((opened_at, entry_price)) = entry {
+        trades.push(ClosedTrade {
+            symbol: symbol.to_string(),
+            qty: shares,
+            entry_price,
+            exit_price: last.close,
+            opened_at,
+            closed_at: last.timestamp,
+            is_long: true,
+            pnl: (last.close - entry_price) * shares - commission,
+        });
+    }
+
+    Ok(BacktestReport {
+        symbol: symbol.to_string(),
+        strategy,
+        capital,
+        first_at: first.timestamp,
+        last_at: last.timestamp,
+        curve,
+        buy_and_hold: bars
+            .iter()
+            .map(|bar| held_shares * (bar.close - first.open))
+            .collect(),
+        trades,
+        commission_paid,
+        still_open,
+    })
+}
+
+/// Backtest tab state
+pub struct Backtest {
+    pub strategy: Strategy,
+    pub bar_count: u32,
+    pub running: bool,
+    pub report: Option<BacktestReport>,
+    pub error: Option<String>,
+}
+
+impl Backtest {
+    pub fn new() -> Self {
+        Self {
+            strategy: Strategy::PRESETS[0],
+            bar_count: 500,
+            running: false,
+            report: None,
+            error: None,
+        }
+    }
+}
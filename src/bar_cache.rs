@@ -0,0 +1,135 @@
+// Bar cache module: historical bars saved per symbol, timeframe and feed under
+// `<config dir>/alpaca_terminal/cache/bars`, so repeated backtests don't refetch them.
+// Entries are refetched once a day or when more bars are wanted than were saved.
+
+use crate::config::DataFeed;
+use crate::error::TerminalError;
+use alpaca_markets::Bar;
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing::{debug, info, warn};
+
+#[derive(Serialize, Deserialize)]
+struct CachedBar {
+    timestamp: DateTime<Utc>,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: u64,
+    trade_count: Option<u64>,
+    vwap: Option<f64>,
+}
+
+impl From<&Bar> for CachedBar {
+    fn from(bar: &Bar) -> Self {
+        Self {
+            timestamp: bar.timestamp,
+            open: bar.open,
+            high: bar.high,
+            low: bar.low,
+            close: bar.close,
+            volume: bar.volume,
+            trade_count: bar.trade_count,
+            vwap: bar.vwap,
+        }
+    }
+}
+
+impl From<CachedBar> for Bar {
+    fn from(bar: CachedBar) -> Self {
+        Bar {
+            timestamp: bar.timestamp,
+            open: bar.open,
+            high: bar.high,
+            low: bar.low,
+            close: bar.close,
+            volume: bar.volume,
+            trade_count: bar.trade_count,
+            vwap: bar.vwap,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    fetched_on: NaiveDate,
+    // Bars asked for; fewer come back when the history is shorter
+    requested: u32,
+    bars: Vec<CachedBar>,
+}
+
+fn entry_path(symbol: &str, timeframe: &str, feed: DataFeed) -> Option<PathBuf> {
+    // Crypto pairs contain '/'
+    let symbol = symbol.replace('/', "-");
+    crate::settings::config_dir().map(|dir| {
+        dir.join("cache")
+            .join("bars")
+            .join(format!("{}_{}_{:?}.json", symbol, timeframe, feed).to_lowercase())
+    })
+}
+
+fn read_entry(path: &PathBuf) -> Option<CacheEntry> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&contents) {
+        Ok(entry) => Some(entry),
+        Err(e) => {
+            warn!("⚠️  Invalid bar cache file {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+fn write_entry(path: &PathBuf, requested: u32, bars: &[Bar]) {
+    let entry = CacheEntry {
+        fetched_on: Local::now().date_naive(),
+        requested,
+        bars: bars.iter().map(CachedBar::from).collect(),
+    };
+    let result = path
+        .parent()
+        .map(std::fs::create_dir_all)
+        .unwrap_or(Ok(()))
+        .and_then(|_| {
+            serde_json::to_string(&entry)
+                .map_err(std::io::Error::other)
+                .and_then(|json| std::fs::write(path, json))
+        });
+    if let Err(e) = result {
+        warn!("⚠️  Error saving bar cache {}: {}", path.display(), e);
+    }
+}
+
+/// The last `limit` bars, from the cache when it was filled today with at least as many
+/// requested, otherwise fetched and cached
+pub fn load_bars(
+    symbol: &str,
+    timeframe: &str,
+    limit: u32,
+    feed: DataFeed,
+) -> Result<Vec<Bar>, TerminalError> {
+    let path = entry_path(symbol, timeframe, feed);
+    let today = Local::now().date_naive();
+    let cached = path
+        .as_ref()
+        .and_then(read_entry)
+        .filter(|entry| entry.fetched_on == today && entry.requested >= limit);
+    if let Some(entry) = cached {
+        debug!(
+            "📦 Using {} cached {} bars for {}",
+            entry.bars.len(),
+            timeframe,
+            symbol
+        );
+        let skip = entry.bars.len().saturating_sub(limit as usize);
+        return Ok(entry.bars.into_iter().skip(skip).map(Bar::from).collect());
+    }
+
+    let bars = crate::fetch_bars_sync(symbol, timeframe, limit, feed)?;
+    info!("📦 Cached {} {} bars for {}", bars.len(), timeframe, symbol);
+    if let Some(path) = &path {
+        write_entry(path, limit, &bars);
+    }
+    Ok(bars)
+}
@@ -1,17 +1,69 @@
 // Chart module for candlestick charts
 
+use crate::error::TerminalError;
+use crate::stream::BarUpdate;
+use crate::text_input::{InputMode, TextInput};
 use alpaca_markets::Bar;
-use chrono::{Datelike, Timelike};
+use chrono::{Datelike, NaiveDate, Timelike};
+use chrono_tz::America::New_York;
+use tracing::{debug, error, info};
+
+/// Which chart an element or update belongs to: the main window's, or a chart window's
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ChartId {
+    Main,
+    Window(usize),
+}
+
+/// Height of an indicator sub-panel under the chart
+pub const SUB_PANEL_HEIGHT: f32 = 90.0;
+
+/// Where the chart area sits in its window, used to map the cursor to prices
+pub struct CrosshairLayout {
+    pub offset_x: f32,
+    pub offset_y: f32,
+    // Chart width = window width * width_fraction - width_inset
+    pub width_fraction: f32,
+    pub width_inset: f32,
+    // Height of everything below the chart (scroll controls, footer, padding)
+    pub bottom_offset: f32,
+}
+
+impl ChartId {
+    pub fn crosshair_layout(&self) -> CrosshairLayout {
+        match self {
+            // Header and favorites above, the footer below, the order sidebar to the right
+            ChartId::Main => CrosshairLayout {
+                offset_x: 66.0,
+                offset_y: 212.0,
+                width_fraction: 0.875,
+                width_inset: 100.0,
+                bottom_offset: 414.0,
+            },
+            // Title row above, the order ticket below
+            ChartId::Window(_) => CrosshairLayout {
+                offset_x: 26.0,
+                offset_y: 84.0,
+                width_fraction: 1.0,
+                width_inset: 52.0,
+                bottom_offset: 190.0,
+            },
+        }
+    }
+}
 
 /// Chart state containing all chart-related fields
 pub struct Chart {
     pub symbol: String,
-    pub symbol_input: String,
+    pub symbol_input: TextInput,
     pub timeframe: String,
     pub bars: Vec<Bar>,
     pub loading: bool,
+    // Bumped for each bar fetch; only the latest fetch's response is applied
+    pub fetch_generation: u64,
+    // Attempt number and total attempts while a failed load is retried
+    pub retry: Option<(u32, u32)>,
     pub error: Option<String>,
-    pub input_focused: bool,
     // Market data stream
     pub market_data_connected: bool,
     pub last_bar_time: Option<String>,
@@ -26,24 +78,195 @@ pub struct Chart {
     pub show_crosshair: bool,
     pub chart_bounds: Option<(f32, f32)>, // (width, height) in pixels
     // Bar limit
-    pub bar_limit: String,
-    pub bar_limit_focused: bool,
+    pub bar_limit: TextInput,
     // Chart scroll offset
     pub chart_scroll_offset: f32,
     // Bars per screen (for zoom control)
     pub bars_per_screen: usize,
 }
 
+/// Where the visible bars sit in the chart area, for drawing overlays in line with the
+/// candles. Positions are percentages of the chart area.
+pub struct PlotArea {
+    pub start_index: usize,
+    pub end_index: usize,
+    pub min_price: f64,
+    pub max_price: f64,
+}
+
+impl PlotArea {
+    // Margins the candles are drawn inside
+    const PADDING_X: f32 = 5.0;
+    const PADDING_Y: f32 = 5.0;
+
+    /// Horizontal distance between bars
+    pub fn bar_step(&self) -> f32 {
+        (100.0 - 2.0 * Self::PADDING_X) / (self.end_index - self.start_index).max(1) as f32
+    }
+
+    /// Left edge of a bar's slot (absolute bar index)
+    pub fn x(&self, index: usize) -> f32 {
+        Self::PADDING_X + (index - self.start_index) as f32 * self.bar_step()
+    }
+
+    pub fn y(&self, price: f64) -> f32 {
+        Self::PADDING_Y
+            + ((self.max_price - price) / (self.max_price - self.min_price)) as f32
+                * (100.0 - 2.0 * Self::PADDING_Y)
+    }
+
+    /// Whether a price is inside the drawn range
+    pub fn contains(&self, price: f64) -> bool {
+        (self.min_price..=self.max_price).contains(&price)
+    }
+}
+
+/// The latest session's figures, shown in the stats row whatever range is scrolled into view
+pub struct SessionStats {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    // Close of the session before, when it is loaded
+    pub prev_close: Option<f64>,
+}
+
+impl SessionStats {
+    /// Change and percent change of `price` against the previous close
+    pub fn change(&self, price: f64) -> Option<(f64, f64)> {
+        let previous = self.prev_close.filter(|close| *close > 0.0)?;
+        let change = price - previous;
+        Some((change, change / previous * 100.0))
+    }
+}
+
 impl Chart {
-    pub fn new(symbol: String, timeframe: String) -> Self {
+    /// Whether the charted symbol is a crypto pair
+    pub fn is_crypto(&self) -> bool {
+        is_crypto_symbol(&self.symbol)
+    }
+
+    /// Trading day a bar belongs to: the New York date for equities, the UTC date for crypto
+    fn session_date(&self, bar: &Bar) -> NaiveDate {
+        if self.is_crypto() {
+            bar.timestamp.date_naive()
+        } else {
+            bar.timestamp.with_timezone(&New_York).date_naive()
+        }
+    }
+
+    /// Bars of the latest bar's session, including extended hours. None for daily and
+    /// longer charts.
+    pub fn intraday_session(&self) -> Option<&[Bar]> {
+        if matches!(self.timeframe.as_str(), "1Day" | "1Week" | "1Month") {
+            return None;
+        }
+        let date = self.session_date(self.bars.last()?);
+        let start = self
+            .bars
+            .iter()
+            .rposition(|bar| self.session_date(bar) != date)
+            .map_or(0, |index| index + 1);
+        Some(&self.bars[start..])
+    }
+
+    /// High, low and close of the session (or ISO week) before the latest bar's, for pivot
+    /// levels on intraday charts. None unless the whole period is loaded.
+    pub fn prior_period(&self, weekly: bool) -> Option<(f64, f64, f64)> {
+        if matches!(self.timeframe.as_str(), "1Day" | "1Week" | "1Month") {
+            return None;
+        }
+        let period = |bar: &Bar| {
+            let date = self.session_date(bar);
+            if weekly {
+                let week = date.iso_week();
+                (week.year(), week.week())
+            } else {
+                (date.year(), date.ordinal())
+            }
+        };
+        let current = period(self.bars.last()?);
+        let end = self.bars.iter().rposition(|bar| period(bar) != current)?;
+        let prior = period(&self.bars[end]);
+        // The period must start after the first loaded bar, or it may be cut short
+        let start = self.bars[..end]
+            .iter()
+            .rposition(|bar| period(bar) != prior)?
+            + 1;
+        let bars = &self.bars[start..=end];
+
+        Some((
+            bars.iter()
+                .map(|bar| bar.high)
+                .fold(f64::NEG_INFINITY, f64::max),
+            bars.iter().map(|bar| bar.low).fold(f64::INFINITY, f64::min),
+            bars[bars.len() - 1].close,
+        ))
+    }
+
+    /// Open, high and low of the latest bar's session and the close before it. Intraday
+    /// sessions include extended-hours bars. None for weekly and monthly charts.
+    pub fn session_stats(&self) -> Option<SessionStats> {
+        if self.bars.is_empty() {
+            return None;
+        }
+        let session = match self.timeframe.as_str() {
+            "1Week" | "1Month" => return None,
+            "1Day" => &self.bars[self.bars.len() - 1..],
+            _ => self.intraday_session()?,
+        };
+        let first = session.first()?;
+        let before = self.bars.len() - session.len();
+
+        Some(SessionStats {
+            open: first.open,
+            high: session
+                .iter()
+                .map(|bar| bar.high)
+                .fold(f64::NEG_INFINITY, f64::max),
+            low: session
+                .iter()
+                .map(|bar| bar.low)
+                .fold(f64::INFINITY, f64::min),
+            prev_close: before.checked_sub(1).map(|index| self.bars[index].close),
+        })
+    }
+
+    /// Range of bars scrolled into view
+    pub fn visible_range(&self) -> (usize, usize) {
+        let start_index =
+            (self.chart_scroll_offset as usize).min(self.bars.len().saturating_sub(1));
+        let end_index = (start_index + self.bars_per_screen).min(self.bars.len());
+        // Ensure we don't have an empty range
+        if end_index > start_index {
+            (start_index, end_index)
+        } else {
+            (0, end_index)
+        }
+    }
+
+    /// Index of the bar under the mouse, from the last cursor position in the chart area
+    pub fn bar_under_mouse(&self) -> Option<usize> {
+        let position = self.mouse_position?;
+        let (width, _) = self.chart_bounds?;
+        let (start_index, end_index) = self.visible_range();
+        let x: f32 = position.x.into();
+        let x_percent = x / width * 100.0;
+        let slot = (x_percent - 5.0) / 90.0 * (end_index - start_index) as f32;
+        (slot >= 0.0)
+            .then(|| start_index + slot as usize)
+            .filter(|index| *index < end_index)
+    }
+
+    pub fn new(symbol: String, timeframe: String, bar_limit: u32) -> Self {
         Self {
             symbol: symbol.clone(),
-            symbol_input: symbol,
+            symbol_input: TextInput::new(symbol, InputMode::Symbol),
             timeframe,
             bars: Vec::new(),
             loading: true,
+            fetch_generation: 0,
+            retry: None,
             error: None,
-            input_focused: false,
             market_data_connected: false,
             last_bar_time: None,
             last_bar_symbol: None,
@@ -55,12 +278,140 @@ impl Chart {
             mouse_position: None,
             show_crosshair: false,
             chart_bounds: None,
-            bar_limit: "100".to_string(),
-            bar_limit_focused: false,
+            bar_limit: TextInput::new(bar_limit.to_string(), InputMode::Integer),
             chart_scroll_offset: 0.0,
             bars_per_screen: 100,
         }
     }
+
+    /// Merge bars fetched to fill a stream gap. Candles already on the chart are replaced
+    /// (the REST bar is complete), new ones are inserted in order. Returns how many were added.
+    pub fn merge_backfill(&mut self, backfill: Vec<Bar>) -> usize {
+        // Keep following the latest bar if the view was at the end
+        let at_end = self.chart_scroll_offset as usize
+            >= self.bars.len().saturating_sub(self.bars_per_screen);
+        let mut added = 0;
+        for bar in backfill {
+            let aligned = align_timestamp_to_timeframe(bar.timestamp, &self.timeframe);
+            let index = self.bars.partition_point(|existing| {
+                align_timestamp_to_timeframe(existing.timestamp, &self.timeframe) < aligned
+            });
+            let same_candle = self.bars.get(index).is_some_and(|existing| {
+                align_timestamp_to_timeframe(existing.timestamp, &self.timeframe) == aligned
+            });
+            if same_candle {
+                self.bars[index] = bar;
+            } else {
+                self.bars.insert(index, bar);
+                added += 1;
+            }
+        }
+        if at_end {
+            self.chart_scroll_offset = self.bars.len().saturating_sub(self.bars_per_screen) as f32;
+        }
+        added
+    }
+
+    /// Merge a streamed bar into the chart: extend the last candle when it falls in the
+    /// same timeframe period, otherwise start a new one
+    pub fn apply_bar_update(&mut self, bar_update: &BarUpdate) {
+        // Convert BarUpdate to Bar struct
+        match convert_bar_update_to_bar(bar_update) {
+            Ok(new_bar) => {
+                if self.bars.is_empty() {
+                    // No existing bars, just add the new one
+                    self.bars.push(new_bar);
+                    info!("✅ Added first bar to chart");
+                } else {
+                    // Align the incoming bar timestamp to the chart's timeframe
+                    let aligned_timestamp =
+                        align_timestamp_to_timeframe(new_bar.timestamp, &self.timeframe);
+
+                    // Get the last bar's timestamp before taking mutable reference
+                    let last_bar_timestamp = self.bars.last().unwrap().timestamp;
+                    let last_bar_aligned =
+                        align_timestamp_to_timeframe(last_bar_timestamp, &self.timeframe);
+
+                    if aligned_timestamp == last_bar_aligned {
+                        // Get mutable reference after calculating timestamps
+                        let last_bar = self.bars.last_mut().unwrap();
+                        // This bar update belongs to the same timeframe candle as the last bar
+                        // Update the last bar by aggregating the data
+                        debug!(
+                            "🔄 Updating existing {} candle (period: {})",
+                            self.timeframe,
+                            aligned_timestamp.format("%Y-%m-%d %H:%M:%S")
+                        );
+
+                        // Keep the open from the existing bar (first price of the period)
+                        // Update high to be the maximum
+                        last_bar.high = last_bar.high.max(new_bar.high);
+                        // Update low to be the minimum
+                        last_bar.low = last_bar.low.min(new_bar.low);
+                        // Update close to the latest close
+                        last_bar.close = new_bar.close;
+                        // Add the volume
+                        last_bar.volume += new_bar.volume;
+                        // Update timestamp to the latest
+                        last_bar.timestamp = new_bar.timestamp;
+                        // Update optional fields
+                        if let (Some(existing_tc), Some(new_tc)) =
+                            (last_bar.trade_count, new_bar.trade_count)
+                        {
+                            last_bar.trade_count = Some(existing_tc + new_tc);
+                        }
+
+                        debug!(
+                            "✅ Updated current {} bar: O:{:.2} H:{:.2} L:{:.2} C:{:.2} V:{}",
+                            self.timeframe,
+                            last_bar.open,
+                            last_bar.high,
+                            last_bar.low,
+                            last_bar.close,
+                            last_bar.volume
+                        );
+                    } else if aligned_timestamp > last_bar_aligned {
+                        // Get mutable reference is not needed here, just push
+                        // This is a new timeframe period - append a new bar
+                        debug!(
+                            "➕ New {} candle period started: {}",
+                            self.timeframe,
+                            aligned_timestamp.format("%Y-%m-%d %H:%M:%S")
+                        );
+                        self.bars.push(new_bar);
+                        debug!(
+                            "✅ Added new {} bar to chart (total: {})",
+                            self.timeframe,
+                            self.bars.len()
+                        );
+
+                        // Auto-scroll to show the latest bar
+                        if self.bars.len() > self.bars_per_screen {
+                            self.chart_scroll_offset =
+                                (self.bars.len() - self.bars_per_screen) as f32;
+                        }
+                    } else {
+                        debug!("⚠️ Received bar with older timeframe period, ignoring");
+                    }
+                }
+            }
+            Err(e) => {
+                error!("❌ Failed to convert bar update: {}", e);
+            }
+        }
+    }
+}
+
+/// Quiet time after a symbol or timeframe change before bars are requested, so clicking
+/// through several timeframes only fetches the last one
+pub const FETCH_DEBOUNCE_MS: u64 = 300;
+
+/// Bar timeframes offered by the chart
+pub const TIMEFRAMES: [&str; 7] = ["1Min", "5Min", "15Min", "1Hour", "1Day", "1Week", "1Month"];
+
+/// Crypto pairs are written with a slash (e.g. BTC/USD); equities never are
+pub fn is_crypto_symbol(symbol: &str) -> bool {
+    symbol.contains('/')
 }
 
 /// Calculate nice round grid values for price display
@@ -205,31 +556,31 @@ pub fn align_timestamp_to_timeframe(
 }
 
 /// Convert a bar update from the stream to a Bar struct
-pub fn convert_bar_update_to_bar(bar_update: &crate::stream::BarUpdate) -> Result<Bar, String> {
+pub fn convert_bar_update_to_bar(bar_update: &BarUpdate) -> Result<Bar, TerminalError> {
     let timestamp = chrono::DateTime::parse_from_rfc3339(&bar_update.timestamp)
-        .map_err(|e| format!("Failed to parse timestamp: {}", e))?
+        .map_err(|e| TerminalError::Parse(format!("timestamp: {}", e)))?
         .with_timezone(&chrono::Utc);
 
     let open = bar_update
         .open
         .parse::<f64>()
-        .map_err(|e| format!("Failed to parse open: {}", e))?;
+        .map_err(|e| TerminalError::Parse(format!("open: {}", e)))?;
     let high = bar_update
         .high
         .parse::<f64>()
-        .map_err(|e| format!("Failed to parse high: {}", e))?;
+        .map_err(|e| TerminalError::Parse(format!("high: {}", e)))?;
     let low = bar_update
         .low
         .parse::<f64>()
-        .map_err(|e| format!("Failed to parse low: {}", e))?;
+        .map_err(|e| TerminalError::Parse(format!("low: {}", e)))?;
     let close = bar_update
         .close
         .parse::<f64>()
-        .map_err(|e| format!("Failed to parse close: {}", e))?;
+        .map_err(|e| TerminalError::Parse(format!("close: {}", e)))?;
     let volume = bar_update
         .volume
         .parse::<u64>()
-        .map_err(|e| format!("Failed to parse volume: {}", e))?;
+        .map_err(|e| TerminalError::Parse(format!("volume: {}", e)))?;
 
     Ok(Bar {
         timestamp,
@@ -242,3 +593,12 @@ pub fn convert_bar_update_to_bar(bar_update: &crate::stream::BarUpdate) -> Resul
         vwap: bar_update.vwap.as_ref().and_then(|v| v.parse::<f64>().ok()),
     })
 }
+
+/// Bar an anchored drawing starts on: the first bar at or after `at`, as long as bars
+/// before it are loaded too (a VWAP would start late otherwise)
+pub fn anchor_index(bars: &[Bar], at: chrono::DateTime<chrono::Utc>) -> Option<usize> {
+    if bars.first()?.timestamp > at {
+        return None;
+    }
+    bars.iter().position(|bar| bar.timestamp >= at)
+}
@@ -0,0 +1,92 @@
+// Chart window module: extra top-level windows, each with its own chart and order ticket,
+// sharing the main window's streams and account
+
+use crate::chart::Chart;
+use crate::hotkeys::HotkeySide;
+use crate::overlays::DonchianSettings;
+use crate::text_input::{InputMode, TextInput};
+use crate::{TradingTerminal, keymap};
+use gpui::{
+    Context, Entity, FocusHandle, IntoElement, Render, Window, WindowHandle, div, prelude::*,
+};
+
+/// State of one chart window, owned by the terminal so stream updates reach it
+pub struct ChartSlot {
+    pub id: usize,
+    pub chart: Chart,
+    pub donchian: DonchianSettings,
+    // Order ticket: market orders for the window's symbol
+    pub side: HotkeySide,
+    pub quantity: TextInput,
+    pub handle: Option<WindowHandle<ChartWindow>>,
+}
+
+impl ChartSlot {
+    pub fn new(id: usize, chart: Chart, donchian: DonchianSettings) -> Self {
+        Self {
+            id,
+            chart,
+            donchian,
+            side: HotkeySide::Buy,
+            quantity: TextInput::new("1", InputMode::Decimal),
+            handle: None,
+        }
+    }
+}
+
+/// Window view for a chart slot; renders and forwards keystrokes through the terminal
+pub struct ChartWindow {
+    terminal: Entity<TradingTerminal>,
+    id: usize,
+    focus_handle: FocusHandle,
+}
+
+impl ChartWindow {
+    pub fn new(
+        terminal: Entity<TradingTerminal>,
+        id: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        cx.observe(&terminal, |_, _, cx| cx.notify()).detach();
+
+        // Closing the window drops its chart and order ticket
+        let close_terminal = terminal.downgrade();
+        window.on_window_should_close(cx, move |_window, cx| {
+            let _ = close_terminal.update(cx, |terminal, cx| terminal.close_chart_window(id, cx));
+            true
+        });
+
+        let focus_handle = cx.focus_handle();
+        window.focus(&focus_handle);
+        Self {
+            terminal,
+            id,
+            focus_handle,
+        }
+    }
+}
+
+impl Render for ChartWindow {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let id = self.id;
+        let content = self.terminal.update(cx, |terminal, cx| {
+            terminal.render_chart_window(id, cx).into_any_element()
+        });
+
+        div()
+            .size_full()
+            .track_focus(&self.focus_handle)
+            .on_key_down(cx.listener(|this, event: &gpui::KeyDownEvent, _, cx| {
+                this.terminal
+                    .update(cx, |terminal, cx| terminal.handle_key_down(event, cx));
+            }))
+            .on_action(cx.listener(|this, _: &keymap::RefreshData, _, cx| {
+                let id = this.id;
+                this.terminal.update(cx, |terminal, cx| {
+                    terminal.fetch_chart_bars(crate::chart::ChartId::Window(id), cx)
+                });
+            }))
+            .child(content)
+    }
+}
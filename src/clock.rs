@@ -0,0 +1,150 @@
+// Clock module for market open/closed status and session countdowns
+
+use crate::error::TerminalError;
+use alpaca_markets::TradingClient;
+use chrono::{DateTime, Utc};
+
+/// How often the clock is re-queried (the countdown itself ticks locally)
+pub const CLOCK_REFRESH_SECS: i64 = 60;
+
+/// Market clock snapshot
+#[derive(Clone)]
+pub struct MarketClock {
+    pub is_open: bool,
+    pub next_open: DateTime<Utc>,
+    pub next_close: DateTime<Utc>,
+    pub fetched_at: DateTime<Utc>,
+}
+
+impl MarketClock {
+    /// The next session transition (close while open, open while closed)
+    pub fn next_transition(&self) -> DateTime<Utc> {
+        if self.is_open {
+            self.next_close
+        } else {
+            self.next_open
+        }
+    }
+
+    /// Whether the clock is stale: refresh interval elapsed or a transition has passed
+    pub fn needs_refresh(&self, now: DateTime<Utc>) -> bool {
+        (now - self.fetched_at).num_seconds() >= CLOCK_REFRESH_SECS || now >= self.next_transition()
+    }
+
+    /// Countdown to the next transition, formatted as "1d 02:03:04" or "02:03:04"
+    pub fn countdown(&self, now: DateTime<Utc>) -> String {
+        let remaining = (self.next_transition() - now).num_seconds().max(0);
+        let days = remaining / 86_400;
+        let hours = (remaining % 86_400) / 3_600;
+        let minutes = (remaining % 3_600) / 60;
+        let seconds = remaining % 60;
+
+        if days > 0 {
+            format!("{}d {:02}:{:02}:{:02}", days, hours, minutes, seconds)
+        } else {
+            format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+        }
+    }
+}
+
+/// Fetch the market clock
+pub fn fetch_clock_sync() -> Result<MarketClock, TerminalError> {
+    crate::api::block_on(async {
+        let config = crate::credentials::alpaca_config()?;
+
+        let client = TradingClient::new(config);
+
+        let result = client.get_clock().await;
+
+        match result {
+            Ok(clock) => Ok(MarketClock {
+                is_open: clock.is_open,
+                next_open: clock.next_open.with_timezone(&Utc),
+                next_close: clock.next_close.with_timezone(&Utc),
+                fetched_at: Utc::now(),
+            }),
+            Err(e) => Err(TerminalError::api(e)),
+        }
+    })
+}
+
+/// Days of the trading calendar shown in the calendar panel
+pub const CALENDAR_LOOKAHEAD_DAYS: i64 = 90;
+
+/// Regular session close, used to detect early-close days
+const REGULAR_CLOSE: &str = "16:00";
+
+/// A notable day in the upcoming trading calendar
+#[derive(Clone)]
+pub enum CalendarEvent {
+    /// Weekday with no trading session
+    Holiday(chrono::NaiveDate),
+    /// Session that closes before 16:00 ET
+    EarlyClose {
+        date: chrono::NaiveDate,
+        close: String,
+    },
+}
+
+impl CalendarEvent {
+    pub fn date(&self) -> chrono::NaiveDate {
+        match self {
+            CalendarEvent::Holiday(date) => *date,
+            CalendarEvent::EarlyClose { date, .. } => *date,
+        }
+    }
+}
+
+/// Fetch upcoming market holidays and early-close days
+pub fn fetch_calendar_events_sync() -> Result<Vec<CalendarEvent>, TerminalError> {
+    crate::api::block_on(async {
+        let config = crate::credentials::alpaca_config()?;
+
+        let client = TradingClient::new(config);
+
+        let start = Utc::now().date_naive();
+        let end = start + chrono::Duration::days(CALENDAR_LOOKAHEAD_DAYS);
+
+        let result = client
+            .get_calendar(Some(start.to_string()), Some(end.to_string()))
+            .await;
+
+        match result {
+            Ok(days) => {
+                let mut events = Vec::new();
+                let mut session_dates = std::collections::BTreeSet::new();
+
+                for day in days {
+                    let Ok(date) = chrono::NaiveDate::parse_from_str(&day.date, "%Y-%m-%d") else {
+                        continue;
+                    };
+                    session_dates.insert(date);
+                    if day.close.as_str() < REGULAR_CLOSE {
+                        events.push(CalendarEvent::EarlyClose {
+                            date,
+                            close: day.close,
+                        });
+                    }
+                }
+
+                // Weekdays without a session are holidays (only up to the last returned session)
+                let last_session = session_dates.last().copied().unwrap_or(start).min(end);
+                let mut date = start;
+                while date <= last_session {
+                    let is_weekday = !matches!(
+                        chrono::Datelike::weekday(&date),
+                        chrono::Weekday::Sat | chrono::Weekday::Sun
+                    );
+                    if is_weekday && !session_dates.contains(&date) {
+                        events.push(CalendarEvent::Holiday(date));
+                    }
+                    date += chrono::Duration::days(1);
+                }
+
+                events.sort_by_key(|event| event.date());
+                Ok(events)
+            }
+            Err(e) => Err(TerminalError::api(e)),
+        }
+    })
+}
@@ -0,0 +1,179 @@
+// Columns module for configurable footer table layouts (visibility, order, widths)
+
+use serde::{Deserialize, Serialize};
+
+/// Minimum and maximum width a column can be dragged to
+pub const MIN_COLUMN_WIDTH: f32 = 40.0;
+pub const MAX_COLUMN_WIDTH: f32 = 400.0;
+
+/// Columns available in the positions table
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum PositionColumn {
+    Symbol,
+    Qty,
+    AvgEntry,
+    Current,
+    MarketValue,
+    UnrealizedPl,
+    UnrealizedPlpc,
+    Realized,
+    Action,
+}
+
+impl PositionColumn {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PositionColumn::Symbol => "Symbol",
+            PositionColumn::Qty => "Qty",
+            PositionColumn::AvgEntry => "Avg Entry",
+            PositionColumn::Current => "Current",
+            PositionColumn::MarketValue => "Market Value",
+            PositionColumn::UnrealizedPl => "P&L",
+            PositionColumn::UnrealizedPlpc => "P&L %",
+            PositionColumn::Realized => "Realized",
+            PositionColumn::Action => "Action",
+        }
+    }
+}
+
+/// Columns available in the orders table
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum OrderColumn {
+    Symbol,
+    Side,
+    Qty,
+    FilledQty,
+    AvgFillPrice,
+    Type,
+    LimitPrice,
+    Status,
+    CreatedAt,
+    Action,
+}
+
+impl OrderColumn {
+    pub fn label(&self) -> &'static str {
+        match self {
+            OrderColumn::Symbol => "Symbol",
+            OrderColumn::Side => "Side",
+            OrderColumn::Qty => "Qty",
+            OrderColumn::FilledQty => "Filled Qty",
+            OrderColumn::AvgFillPrice => "Avg Fill",
+            OrderColumn::Type => "Type",
+            OrderColumn::LimitPrice => "Limit Price",
+            OrderColumn::Status => "Status",
+            OrderColumn::CreatedAt => "Created At",
+            OrderColumn::Action => "Action",
+        }
+    }
+}
+
+/// Width and visibility of a single column
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ColumnSetting<C> {
+    pub column: C,
+    pub width: f32,
+    pub visible: bool,
+}
+
+/// Ordered column layout for a table
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TableColumns<C> {
+    pub columns: Vec<ColumnSetting<C>>,
+}
+
+impl<C: Copy + PartialEq> TableColumns<C> {
+    fn new(columns: &[(C, f32, bool)]) -> Self {
+        Self {
+            columns: columns
+                .iter()
+                .map(|&(column, width, visible)| ColumnSetting {
+                    column,
+                    width,
+                    visible,
+                })
+                .collect(),
+        }
+    }
+
+    /// Visible columns with their widths, in display order
+    pub fn visible(&self) -> Vec<(usize, C, f32)> {
+        self.columns
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.visible)
+            .map(|(index, c)| (index, c.column, c.width))
+            .collect()
+    }
+
+    /// Show or hide a column (at least one column always stays visible)
+    pub fn toggle(&mut self, column: C) {
+        let visible_count = self.columns.iter().filter(|c| c.visible).count();
+        if let Some(setting) = self.columns.iter_mut().find(|c| c.column == column) {
+            if setting.visible && visible_count <= 1 {
+                return;
+            }
+            setting.visible = !setting.visible;
+        }
+    }
+
+    /// Set a column's width by index, clamped to the allowed range
+    pub fn set_width(&mut self, index: usize, width: f32) {
+        if let Some(setting) = self.columns.get_mut(index) {
+            setting.width = width.clamp(MIN_COLUMN_WIDTH, MAX_COLUMN_WIDTH);
+        }
+    }
+
+    pub fn width(&self, index: usize) -> f32 {
+        self.columns.get(index).map(|c| c.width).unwrap_or(80.0)
+    }
+}
+
+impl Default for TableColumns<PositionColumn> {
+    fn default() -> Self {
+        Self::new(&[
+            (PositionColumn::Symbol, 80.0, true),
+            (PositionColumn::Qty, 80.0, true),
+            (PositionColumn::AvgEntry, 100.0, true),
+            (PositionColumn::Current, 100.0, true),
+            (PositionColumn::MarketValue, 120.0, true),
+            (PositionColumn::UnrealizedPl, 100.0, true),
+            (PositionColumn::UnrealizedPlpc, 80.0, true),
+            (PositionColumn::Realized, 100.0, true),
+            (PositionColumn::Action, 80.0, true),
+        ])
+    }
+}
+
+impl Default for TableColumns<OrderColumn> {
+    fn default() -> Self {
+        Self::new(&[
+            (OrderColumn::Symbol, 80.0, true),
+            (OrderColumn::Side, 60.0, true),
+            (OrderColumn::Qty, 80.0, true),
+            (OrderColumn::FilledQty, 80.0, false),
+            (OrderColumn::AvgFillPrice, 100.0, false),
+            (OrderColumn::Type, 80.0, true),
+            (OrderColumn::LimitPrice, 100.0, true),
+            (OrderColumn::Status, 100.0, true),
+            (OrderColumn::CreatedAt, 150.0, true),
+            (OrderColumn::Action, 80.0, true),
+        ])
+    }
+}
+
+/// Which table a column drag or menu belongs to
+#[derive(Clone, Copy, PartialEq)]
+pub enum ColumnTable {
+    Positions,
+    Orders,
+}
+
+/// In-progress drag of a column boundary
+#[derive(Clone, Copy)]
+pub struct ColumnDrag {
+    pub table: ColumnTable,
+    pub index: usize,
+    pub start_x: f32,
+    pub start_width: f32,
+}
@@ -0,0 +1,704 @@
+// Config module for the hand-edited `config.toml` (startup defaults and behavior)
+
+use crate::error::TerminalError;
+use crate::numbers::NumberFormat;
+use alpaca_markets::AlpacaConfig;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::SystemTime;
+use tracing::{info, warn};
+
+/// How often the config file is checked for changes
+pub const RELOAD_INTERVAL_SECS: u64 = 2;
+
+/// Written on first run so the available options are discoverable
+const DEFAULT_CONFIG: &str = r##"# Alpaca terminal configuration. Changes are picked up while the terminal runs.
+
+[chart]
+# Symbol and timeframe shown on first start (later starts restore the last session)
+symbol = "AAPL"
+# 1Min, 5Min, 15Min, 1Hour, 1Day, 1Week or 1Month
+timeframe = "1Day"
+bar_limit = 100
+
+[market_data]
+# Stock data feed: "iex" (free) or "sip" (all exchanges, needs a paid plan)
+feed = "iex"
+
+[appearance]
+theme = "dark"
+
+[retry]
+# Attempts for chart, account, position and order fetches that fail with a network error
+attempts = 3
+# Delay before the first retry (ms), doubled for each later one up to max_backoff_ms
+backoff_ms = 500
+max_backoff_ms = 8000
+# Vary each delay by up to this fraction (0.0 - 1.0) so retries don't line up
+jitter = 0.2
+
+[numbers]
+# Thousands and decimal separators: "auto" follows the system locale (LC_NUMERIC / LANG),
+# or name one such as "en_US" or "de_DE"
+locale = "auto"
+# Decimal places for prices (account values always use two)
+price_decimals = 2
+# Show volumes as 12.3M instead of 12,345,678
+compact_volume = true
+
+[chart_style]
+# Candle and chart colors as "#rrggbb". For a red/green colorblind-friendly scheme try
+# bullish = "#1f77b4" and bearish = "#ff7f0e".
+bullish = "#00cc66"
+bearish = "#ff4444"
+background = "#1a1a1a"
+grid = "#2a2a2a"
+# Wick width in pixels (the live candle's wick is drawn one pixel wider)
+wick_width = 1.0
+
+[heatmap]
+# Symbols to map: "sp100", "watchlist" (favorites) or "custom" (the symbols list below)
+universe = "sp100"
+# symbols = ["AAPL", "MSFT", "NVDA"]
+# Tile area: "dollar_volume" (value traded today), "position" (market value held) or "equal".
+# Alpaca's market data has no market caps, so traded value stands in for company size.
+size_by = "dollar_volume"
+# Seconds between snapshot refreshes while the Heatmap tab is open (minimum 15)
+refresh_secs = 60
+
+[screener]
+# Symbols ranked in the Movers tab: "sp100", "watchlist" or "custom" (the symbols list below)
+universe = "sp100"
+# symbols = ["AAPL", "MSFT", "NVDA"]
+# Rows shown per ranking
+limit = 25
+# Seconds between snapshot refreshes while the Movers tab is open (minimum 15)
+refresh_secs = 60
+
+[performance]
+# Symbol overlaid on the Performance tab's equity curve as a buy-and-hold comparison
+benchmark = "SPY"
+
+[backtest]
+# Simulated account the Backtest tab starts each run with (USD)
+initial_capital = 10000.0
+# Fill price worse than the bar's open by this many basis points (1 bp = 0.01%)
+slippage_bps = 5.0
+# Charged on every simulated order (USD)
+commission = 0.0
+
+[simulator]
+# Cash the forward-test broker starts (and resets) with (USD)
+starting_cash = 100000.0
+
+# One-key buy/sell orders. Entries left commented out keep the value set in the sidebar.
+[hotkeys]
+# enabled = false
+# quantity = "1"
+
+[confirmations]
+# Ask before submitting a hotkey order
+# hotkey_orders = true
+# Live-account orders worth at least this much (USD) require typing the symbol to confirm
+live_order_notional = 1000.0
+
+# Orders over these limits are blocked before they reach the API. Leave commented out for no limit.
+[risk]
+# Largest single order as a percent of account equity
+# max_order_equity_percent = 10.0
+# Largest single order in shares
+# max_order_shares = 500
+# Once today's loss reaches this much (USD), order entry and hotkeys are disabled until the
+# next session unless overridden from the order form
+# max_daily_loss = 500.0
+
+# Rhai strategy scripts in the scripts folder next to this file
+[scripting]
+# Let scripts submit orders on a live account (by default their orders are only sent on paper)
+allow_live = false
+# Steps a script may run before it's stopped, so a runaway loop can't hang it
+max_operations = 1000000
+"##;
+
+/// Stock market data feed
+#[derive(Clone, Copy, PartialEq, Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DataFeed {
+    #[default]
+    Iex,
+    Sip,
+}
+
+impl DataFeed {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DataFeed::Iex => "IEX",
+            DataFeed::Sip => "SIP",
+        }
+    }
+
+    /// Point a REST client config at this feed (without an override the API serves SIP)
+    pub fn configure(&self, config: AlpacaConfig) -> AlpacaConfig {
+        match self {
+            DataFeed::Iex => config.with_iex_feed(),
+            DataFeed::Sip => config,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ChartConfig {
+    pub symbol: String,
+    pub timeframe: String,
+    pub bar_limit: u32,
+}
+
+impl Default for ChartConfig {
+    fn default() -> Self {
+        Self {
+            symbol: "AAPL".to_string(),
+            timeframe: "1Day".to_string(),
+            bar_limit: 100,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Default, Deserialize)]
+#[serde(default)]
+pub struct MarketDataConfig {
+    pub feed: DataFeed,
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct AppearanceConfig {
+    // Only "dark" is available for now
+    pub theme: String,
+}
+
+impl Default for AppearanceConfig {
+    fn default() -> Self {
+        Self {
+            theme: "dark".to_string(),
+        }
+    }
+}
+
+/// Retry policy for REST fetches that fail transiently
+#[derive(Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct RetryConfig {
+    pub attempts: u32,
+    pub backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    pub jitter: f64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            backoff_ms: 500,
+            max_backoff_ms: 8000,
+            jitter: 0.2,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct NumbersConfig {
+    pub locale: String,
+    pub price_decimals: usize,
+    pub compact_volume: bool,
+    // Resolved from the fields above when the config is loaded
+    #[serde(skip)]
+    pub format: NumberFormat,
+}
+
+impl Default for NumbersConfig {
+    fn default() -> Self {
+        let mut config = Self {
+            locale: "auto".to_string(),
+            price_decimals: 2,
+            compact_volume: true,
+            format: NumberFormat::default(),
+        };
+        config.resolve();
+        config
+    }
+}
+
+impl NumbersConfig {
+    /// Build the number format for the configured (or system) locale
+    fn resolve(&mut self) {
+        let locale = if self.locale.eq_ignore_ascii_case("auto") {
+            crate::numbers::system_locale()
+        } else {
+            self.locale.clone()
+        };
+        self.format = NumberFormat {
+            price_decimals: self.price_decimals.min(8),
+            compact_volume: self.compact_volume,
+            ..NumberFormat::for_locale(&locale)
+        };
+    }
+}
+
+/// Color written as "#rrggbb" in the config file
+#[derive(Clone, Copy, PartialEq, Debug, Deserialize)]
+#[serde(try_from = "String")]
+pub struct HexColor(pub u32);
+
+impl TryFrom<String> for HexColor {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let hex = value.trim().trim_start_matches('#');
+        if hex.len() != 6 {
+            return Err(format!(
+                "expected a color like \"#00cc66\", got {:?}",
+                value
+            ));
+        }
+        u32::from_str_radix(hex, 16)
+            .map(HexColor)
+            .map_err(|_| format!("expected a color like \"#00cc66\", got {:?}", value))
+    }
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ChartStyleConfig {
+    pub bullish: HexColor,
+    pub bearish: HexColor,
+    pub background: HexColor,
+    pub grid: HexColor,
+    pub wick_width: f32,
+}
+
+impl Default for ChartStyleConfig {
+    fn default() -> Self {
+        Self {
+            bullish: HexColor(0x00cc66),
+            bearish: HexColor(0xff4444),
+            background: HexColor(0x1a1a1a),
+            grid: HexColor(0x2a2a2a),
+            wick_width: 1.0,
+        }
+    }
+}
+
+impl ChartStyleConfig {
+    /// Candle color for a bar's direction
+    pub fn candle_color(&self, is_bullish: bool) -> u32 {
+        if is_bullish {
+            self.bullish.0
+        } else {
+            self.bearish.0
+        }
+    }
+}
+
+/// Symbol set for market-wide views
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Universe {
+    #[default]
+    Sp100,
+    Watchlist,
+    Custom,
+}
+
+impl Universe {
+    pub const ALL: [Universe; 3] = [Universe::Sp100, Universe::Watchlist, Universe::Custom];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Universe::Sp100 => "S&P 100",
+            Universe::Watchlist => "Watchlist",
+            Universe::Custom => "Custom",
+        }
+    }
+}
+
+/// What a heatmap tile's area stands for
+#[derive(Clone, Copy, PartialEq, Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TileSize {
+    #[default]
+    DollarVolume,
+    Position,
+    Equal,
+}
+
+impl TileSize {
+    pub const ALL: [TileSize; 3] = [TileSize::DollarVolume, TileSize::Position, TileSize::Equal];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TileSize::DollarVolume => "$ Volume",
+            TileSize::Position => "Position",
+            TileSize::Equal => "Equal",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct HeatmapConfig {
+    pub universe: Universe,
+    // Used by the custom universe
+    pub symbols: Vec<String>,
+    pub size_by: TileSize,
+    pub refresh_secs: u64,
+}
+
+impl Default for HeatmapConfig {
+    fn default() -> Self {
+        Self {
+            universe: Universe::Sp100,
+            symbols: Vec::new(),
+            size_by: TileSize::DollarVolume,
+            refresh_secs: 60,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ScreenerConfig {
+    pub universe: Universe,
+    // Used by the custom universe
+    pub symbols: Vec<String>,
+    pub limit: usize,
+    pub refresh_secs: u64,
+}
+
+impl Default for ScreenerConfig {
+    fn default() -> Self {
+        Self {
+            universe: Universe::Sp100,
+            symbols: Vec::new(),
+            limit: 25,
+            refresh_secs: 60,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct PerformanceConfig {
+    pub benchmark: String,
+}
+
+impl Default for PerformanceConfig {
+    fn default() -> Self {
+        Self {
+            benchmark: "SPY".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ScriptingConfig {
+    pub allow_live: bool,
+    pub max_operations: u64,
+}
+
+impl Default for ScriptingConfig {
+    fn default() -> Self {
+        Self {
+            allow_live: false,
+            max_operations: 1_000_000,
+        }
+    }
+}
+
+/// Simulated account for the Backtest tab
+#[derive(Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct BacktestConfig {
+    pub initial_capital: f64,
+    pub slippage_bps: f64,
+    pub commission: f64,
+}
+
+impl Default for BacktestConfig {
+    fn default() -> Self {
+        Self {
+            initial_capital: 10_000.0,
+            slippage_bps: 5.0,
+            commission: 0.0,
+        }
+    }
+}
+
+/// Forward-test broker in the Simulator tab
+#[derive(Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct SimulatorConfig {
+    pub starting_cash: f64,
+}
+
+impl Default for SimulatorConfig {
+    fn default() -> Self {
+        Self {
+            starting_cash: 100_000.0,
+        }
+    }
+}
+
+/// Overrides for the sidebar's hotkey settings (None keeps the saved value)
+#[derive(Clone, PartialEq, Default, Deserialize)]
+#[serde(default)]
+pub struct HotkeysConfig {
+    pub enabled: Option<bool>,
+    pub quantity: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ConfirmationsConfig {
+    pub hotkey_orders: Option<bool>,
+    pub live_order_notional: f64,
+}
+
+impl Default for ConfirmationsConfig {
+    fn default() -> Self {
+        Self {
+            hotkey_orders: None,
+            live_order_notional: 1000.0,
+        }
+    }
+}
+
+/// Order size and daily loss limits (None means unlimited)
+#[derive(Clone, PartialEq, Default, Deserialize)]
+#[serde(default)]
+pub struct RiskConfig {
+    pub max_order_equity_percent: Option<f64>,
+    pub max_order_shares: Option<f64>,
+    pub max_daily_loss: Option<f64>,
+}
+
+/// Contents of `<config dir>/alpaca_terminal/config.toml`
+#[derive(Clone, PartialEq, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub chart: ChartConfig,
+    pub market_data: MarketDataConfig,
+    pub appearance: AppearanceConfig,
+    pub retry: RetryConfig,
+    pub chart_style: ChartStyleConfig,
+    pub numbers: NumbersConfig,
+    pub hotkeys: HotkeysConfig,
+    pub confirmations: ConfirmationsConfig,
+    pub risk: RiskConfig,
+    pub heatmap: HeatmapConfig,
+    pub screener: ScreenerConfig,
+    pub performance: PerformanceConfig,
+    pub backtest: BacktestConfig,
+    pub simulator: SimulatorConfig,
+    pub scripting: ScriptingConfig,
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    crate::settings::config_dir().map(|dir| dir.join("config.toml"))
+}
+
+/// Modification time of the config file, used to detect edits
+pub fn modified_at() -> Option<SystemTime> {
+    config_path()
+        .and_then(|path| std::fs::metadata(path).ok())
+        .and_then(|metadata| metadata.modified().ok())
+}
+
+impl Config {
+    /// Load the config, writing the commented default file on first run.
+    /// Falls back to defaults if the file can't be read or parsed.
+    pub fn load() -> Self {
+        let Some(path) = config_path() else {
+            return Self::default();
+        };
+
+        if !path.exists() {
+            let result = path
+                .parent()
+                .map(std::fs::create_dir_all)
+                .unwrap_or(Ok(()))
+                .and_then(|_| std::fs::write(&path, DEFAULT_CONFIG));
+            match result {
+                Ok(_) => info!("📝 Wrote default config to {}", path.display()),
+                Err(e) => warn!(
+                    "⚠️  Failed to write default config {}: {}",
+                    path.display(),
+                    e
+                ),
+            }
+            return Self::default();
+        }
+
+        Self::reload().unwrap_or_else(|e| {
+            warn!("⚠️  {}", e);
+            Self::default()
+        })
+    }
+
+    /// Re-read the config file, reporting parse errors so the caller can keep the old config
+    pub fn reload() -> Result<Self, TerminalError> {
+        let path = config_path()
+            .ok_or_else(|| TerminalError::Config("No config directory".to_string()))?;
+        let contents = std::fs::read_to_string(&path).map_err(|e| {
+            TerminalError::Config(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        let mut config: Config = toml::from_str(&contents).map_err(|e| {
+            TerminalError::Config(format!("Invalid config {}: {}", path.display(), e))
+        })?;
+        config.validate();
+        info!("✅ Config loaded from {}", path.display());
+        Ok(config)
+    }
+
+    /// Replace out-of-range values with defaults
+    fn validate(&mut self) {
+        let defaults = ChartConfig::default();
+        self.chart.symbol = self.chart.symbol.trim().to_uppercase();
+        if self.chart.symbol.is_empty() {
+            self.chart.symbol = defaults.symbol;
+        }
+        if !crate::chart::TIMEFRAMES.contains(&self.chart.timeframe.as_str()) {
+            warn!(
+                "⚠️  Unknown timeframe {:?} in config, using {}",
+                self.chart.timeframe, defaults.timeframe
+            );
+            self.chart.timeframe = defaults.timeframe;
+        }
+        if self.chart.bar_limit == 0 {
+            self.chart.bar_limit = defaults.bar_limit;
+        }
+        if self.appearance.theme != "dark" {
+            warn!(
+                "⚠️  Unknown theme {:?} in config, using dark",
+                self.appearance.theme
+            );
+            self.appearance = AppearanceConfig::default();
+        }
+        if !(1..=10).contains(&self.retry.attempts) {
+            warn!(
+                "⚠️  Retry attempts {} in config is out of range, using {}",
+                self.retry.attempts,
+                self.retry.attempts.clamp(1, 10)
+            );
+            self.retry.attempts = self.retry.attempts.clamp(1, 10);
+        }
+        self.retry.max_backoff_ms = self.retry.max_backoff_ms.max(self.retry.backoff_ms);
+        self.retry.jitter = self.retry.jitter.clamp(0.0, 1.0);
+        self.numbers.resolve();
+        if !(0.5..=10.0).contains(&self.chart_style.wick_width) {
+            warn!(
+                "⚠️  Wick width {} in config is out of range, using 1",
+                self.chart_style.wick_width
+            );
+            self.chart_style.wick_width = ChartStyleConfig::default().wick_width;
+        }
+        self.heatmap.symbols = self
+            .heatmap
+            .symbols
+            .iter()
+            .map(|symbol| symbol.trim().to_uppercase())
+            .filter(|symbol| !symbol.is_empty())
+            .collect();
+        if self.heatmap.universe == Universe::Custom && self.heatmap.symbols.is_empty() {
+            warn!("⚠️  Custom heatmap universe has no symbols, using the S&P 100");
+            self.heatmap.universe = Universe::Sp100;
+        }
+        self.heatmap.refresh_secs = self.heatmap.refresh_secs.max(15);
+        self.screener.symbols = self
+            .screener
+            .symbols
+            .iter()
+            .map(|symbol| symbol.trim().to_uppercase())
+            .filter(|symbol| !symbol.is_empty())
+            .collect();
+        if self.screener.universe == Universe::Custom && self.screener.symbols.is_empty() {
+            warn!("⚠️  Custom screener universe has no symbols, using the S&P 100");
+            self.screener.universe = Universe::Sp100;
+        }
+        if self.screener.limit == 0 {
+            self.screener.limit = ScreenerConfig::default().limit;
+        }
+        self.screener.refresh_secs = self.screener.refresh_secs.max(15);
+        self.performance.benchmark = self.performance.benchmark.trim().to_uppercase();
+        if self.performance.benchmark.is_empty() {
+            self.performance.benchmark = PerformanceConfig::default().benchmark;
+        }
+        if let Some(percent) = self
+            .risk
+            .max_order_equity_percent
+            .filter(|percent| *percent <= 0.0)
+        {
+            warn!(
+                "⚠️  Max order size {}% of equity in config must be positive, ignoring it",
+                percent
+            );
+            self.risk.max_order_equity_percent = None;
+        }
+        if let Some(shares) = self.risk.max_order_shares.filter(|shares| *shares <= 0.0) {
+            warn!(
+                "⚠️  Max order size {} shares in config must be positive, ignoring it",
+                shares
+            );
+            self.risk.max_order_shares = None;
+        }
+        if let Some(loss) = self.risk.max_daily_loss.filter(|loss| *loss <= 0.0) {
+            warn!(
+                "⚠️  Max daily loss {} in config must be positive, ignoring it",
+                loss
+            );
+            self.risk.max_daily_loss = None;
+        }
+        let backtest = BacktestConfig::default();
+        if self.backtest.initial_capital <= 0.0 {
+            warn!(
+                "⚠️  Backtest capital {} in config must be positive, using {}",
+                self.backtest.initial_capital, backtest.initial_capital
+            );
+            self.backtest.initial_capital = backtest.initial_capital;
+        }
+        if self.backtest.slippage_bps < 0.0 {
+            warn!("⚠️  Negative backtest slippage in config, using none");
+            self.backtest.slippage_bps = 0.0;
+        }
+        if self.backtest.commission < 0.0 {
+            warn!("⚠️  Negative backtest commission in config, using none");
+            self.backtest.commission = 0.0;
+        }
+        if self.simulator.starting_cash <= 0.0 {
+            let starting_cash = SimulatorConfig::default().starting_cash;
+            warn!(
+                "⚠️  Simulator cash {} in config must be positive, using {}",
+                self.simulator.starting_cash, starting_cash
+            );
+            self.simulator.starting_cash = starting_cash;
+        }
+        if self.scripting.max_operations == 0 {
+            self.scripting.max_operations = ScriptingConfig::default().max_operations;
+        }
+    }
+
+    /// Apply the hotkey and confirmation overrides on top of saved settings
+    pub fn apply_overrides(&self, settings: &mut crate::settings::Settings) {
+        if let Some(enabled) = self.hotkeys.enabled {
+            settings.hotkeys_enabled = enabled;
+        }
+        if let Some(quantity) = &self.hotkeys.quantity {
+            settings.hotkey_quantity = quantity.clone();
+        }
+        if let Some(confirm) = self.confirmations.hotkey_orders {
+            settings.hotkey_skip_confirmation = !confirm;
+        }
+    }
+}
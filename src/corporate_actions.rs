@@ -0,0 +1,111 @@
+// Corporate actions module for splits and dividends shown on daily charts
+
+use crate::error::TerminalError;
+use alpaca_markets::MarketDataClient;
+use chrono::{NaiveDate, Utc};
+
+/// How far back corporate actions are requested
+pub const CORPORATE_ACTIONS_LOOKBACK_DAYS: i64 = 5 * 365;
+
+/// Split or cash dividend, keyed by its ex-date
+#[derive(Clone)]
+pub enum CorporateAction {
+    Split {
+        ex_date: NaiveDate,
+        old_rate: f64,
+        new_rate: f64,
+    },
+    Dividend {
+        ex_date: NaiveDate,
+        rate: f64,
+    },
+}
+
+impl CorporateAction {
+    pub fn ex_date(&self) -> NaiveDate {
+        match self {
+            CorporateAction::Split { ex_date, .. } => *ex_date,
+            CorporateAction::Dividend { ex_date, .. } => *ex_date,
+        }
+    }
+
+    /// Marker letter on the chart
+    pub fn marker(&self) -> &'static str {
+        match self {
+            CorporateAction::Split { .. } => "S",
+            CorporateAction::Dividend { .. } => "D",
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            CorporateAction::Split {
+                old_rate, new_rate, ..
+            } => {
+                let kind = if new_rate >= old_rate {
+                    "Split"
+                } else {
+                    "Reverse split"
+                };
+                format!("{} {}:{}", kind, new_rate, old_rate)
+            }
+            CorporateAction::Dividend { rate, .. } => format!("Dividend ${:.4}/share", rate),
+        }
+    }
+}
+
+/// Fetch splits and cash dividends for the symbol, oldest first
+pub fn fetch_corporate_actions_sync(symbol: String) -> Result<Vec<CorporateAction>, TerminalError> {
+    crate::api::block_on(async {
+        let config = crate::credentials::alpaca_config()?;
+
+        let client = MarketDataClient::new(config);
+
+        let end = Utc::now().date_naive();
+        let start = end - chrono::Duration::days(CORPORATE_ACTIONS_LOOKBACK_DAYS);
+
+        let result = client
+            .get_corporate_actions(
+                &symbol,
+                Some("forward_split,reverse_split,cash_dividend".to_string()),
+                Some(start.to_string()),
+                Some(end.to_string()),
+            )
+            .await;
+
+        let parse_date = |date: &str| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+
+        match result {
+            Ok(response) => {
+                let actions = response.corporate_actions;
+                let mut events = Vec::new();
+
+                for split in actions
+                    .forward_splits
+                    .into_iter()
+                    .chain(actions.reverse_splits)
+                {
+                    if let Some(ex_date) = parse_date(&split.ex_date) {
+                        events.push(CorporateAction::Split {
+                            ex_date,
+                            old_rate: split.old_rate,
+                            new_rate: split.new_rate,
+                        });
+                    }
+                }
+                for dividend in actions.cash_dividends {
+                    if let Some(ex_date) = parse_date(&dividend.ex_date) {
+                        events.push(CorporateAction::Dividend {
+                            ex_date,
+                            rate: dividend.rate,
+                        });
+                    }
+                }
+
+                events.sort_by_key(|event| event.ex_date());
+                Ok(events)
+            }
+            Err(e) => Err(TerminalError::for_symbol(e, &symbol)),
+        }
+    })
+}
@@ -0,0 +1,269 @@
+// Credentials module: named account profiles saved from the setup dialog, falling back to
+// environment variables
+
+use crate::error::TerminalError;
+use crate::text_input::{InputMode, TextInput};
+use alpaca_markets::{AlpacaConfig, TradingClient};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tracing::{info, warn};
+
+/// Named API key pair and environment
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub key_id: String,
+    pub secret_key: String,
+    pub paper: bool,
+}
+
+impl Profile {
+    pub fn to_config(&self) -> AlpacaConfig {
+        AlpacaConfig::new(self.key_id.clone(), self.secret_key.clone(), self.paper)
+    }
+
+    pub fn environment(&self) -> Environment {
+        if self.paper {
+            Environment::Paper
+        } else {
+            Environment::Live
+        }
+    }
+
+    /// Trading API the profile connects to
+    pub fn base_url(&self) -> &'static str {
+        if self.paper {
+            "https://paper-api.alpaca.markets"
+        } else {
+            "https://api.alpaca.markets"
+        }
+    }
+}
+
+/// Trading environment the terminal places orders in
+#[derive(Clone, Copy, PartialEq)]
+pub enum Environment {
+    Paper,
+    Live,
+}
+
+impl Environment {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Environment::Paper => "PAPER",
+            Environment::Live => "LIVE",
+        }
+    }
+}
+
+/// Profiles saved to `<config dir>/alpaca_terminal/credentials.json`
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileStore {
+    pub active: Option<String>,
+    pub profiles: Vec<Profile>,
+}
+
+// Profile REST calls use: read from disk on the first call, then kept in memory and
+// replaced whenever the profiles are saved (switch, add, remove)
+static ACTIVE: Mutex<Option<Option<Profile>>> = Mutex::new(None);
+
+fn credentials_path() -> Option<PathBuf> {
+    crate::settings::config_dir().map(|dir| dir.join("credentials.json"))
+}
+
+impl ProfileStore {
+    /// Load profiles from disk. A single key pair saved before profiles existed
+    /// becomes a "default" profile.
+    pub fn load() -> Self {
+        let Some(path) = credentials_path() else {
+            return Self::default();
+        };
+        let Ok(contents) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+
+        if let Ok(store) = serde_json::from_str::<ProfileStore>(&contents) {
+            return store;
+        }
+
+        #[derive(Deserialize)]
+        struct LegacyCredentials {
+            key_id: String,
+            secret_key: String,
+            paper: bool,
+        }
+        match serde_json::from_str::<LegacyCredentials>(&contents) {
+            Ok(legacy) => Self {
+                active: Some("default".to_string()),
+                profiles: vec![Profile {
+                    name: "default".to_string(),
+                    key_id: legacy.key_id,
+                    secret_key: legacy.secret_key,
+                    paper: legacy.paper,
+                }],
+            },
+            Err(e) => {
+                warn!("⚠️  Invalid credentials file: {}", e);
+                // Kept aside so saving a new key pair doesn't destroy the old ones
+                crate::persist::back_up(&path);
+                Self::default()
+            }
+        }
+    }
+
+    /// Make the active profile the one REST calls use, then write profiles to disk,
+    /// readable only by the current user
+    pub fn save(&self) -> Result<(), TerminalError> {
+        // A switch takes effect even if the file can't be written
+        *ACTIVE.lock().unwrap_or_else(|e| e.into_inner()) = Some(self.active_profile().cloned());
+
+        let path = credentials_path()
+            .ok_or_else(|| TerminalError::Config("No config directory".to_string()))?;
+        let json =
+            serde_json::to_string_pretty(self).map_err(|e| TerminalError::Config(e.to_string()))?;
+        crate::persist::write_private(&path, json.as_bytes()).map_err(|e| {
+            TerminalError::Config(format!("Failed to write {}: {}", path.display(), e))
+        })?;
+
+        info!("🔑 API credentials saved to {}", path.display());
+        Ok(())
+    }
+
+    /// Active profile, or the first one if the active name is stale
+    pub fn active_profile(&self) -> Option<&Profile> {
+        self.active
+            .as_ref()
+            .and_then(|name| self.profiles.iter().find(|p| &p.name == name))
+            .or(self.profiles.first())
+    }
+
+    pub fn active_name(&self) -> Option<&str> {
+        self.active_profile().map(|p| p.name.as_str())
+    }
+
+    /// Environment of the active profile. Environment-variable keys count as live unless
+    /// APCA_API_BASE_URL points at the paper API, erring on the side of caution.
+    pub fn environment(&self) -> Environment {
+        if let Some(profile) = self.active_profile() {
+            return profile.environment();
+        }
+        if std::env::var("APCA_API_BASE_URL").is_ok_and(|url| url.contains("paper")) {
+            Environment::Paper
+        } else {
+            Environment::Live
+        }
+    }
+
+    /// Add a profile or replace the one with the same name, and make it active
+    pub fn upsert(&mut self, profile: Profile) {
+        self.active = Some(profile.name.clone());
+        match self.profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => self.profiles.push(profile),
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.profiles.retain(|p| p.name != name);
+        if self.active.as_deref() == Some(name) {
+            self.active = self.profiles.first().map(|p| p.name.clone());
+        }
+    }
+}
+
+/// Client config from the active profile, else APCA_API_KEY_ID / APCA_API_SECRET_KEY
+pub fn alpaca_config() -> Result<AlpacaConfig, TerminalError> {
+    let active = ACTIVE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_or_insert_with(|| ProfileStore::load().active_profile().cloned())
+        .clone();
+    if let Some(profile) = active {
+        return Ok(profile.to_config());
+    }
+    AlpacaConfig::from_env().map_err(|_| TerminalError::MissingCredentials)
+}
+
+pub fn has_credentials() -> bool {
+    alpaca_config().is_ok()
+}
+
+/// Check the keys against the trading API, returning the account number
+pub fn test_connection_sync(profile: Profile) -> Result<String, TerminalError> {
+    crate::api::block_on(async {
+        let client = TradingClient::new(profile.to_config());
+        match client.get_account().await {
+            Ok(account) => Ok(account.account_number),
+            Err(e) => Err(TerminalError::api(e)),
+        }
+    })
+}
+
+/// Profile editor, shown on first run and from the header's profile menu
+pub struct SetupDialog {
+    pub open: bool,
+    pub name: TextInput,
+    pub key_id: TextInput,
+    pub secret_key: TextInput,
+    pub paper: bool,
+    pub testing: bool,
+    // Outcome of the last connection test: account number or error
+    pub status: Option<Result<String, TerminalError>>,
+}
+
+impl SetupDialog {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            name: TextInput::new("", InputMode::Text),
+            key_id: TextInput::new("", InputMode::ApiKey),
+            secret_key: TextInput::new("", InputMode::ApiKey).masked(),
+            paper: true,
+            testing: false,
+            status: None,
+        }
+    }
+
+    /// Open the dialog for a saved profile, or blank for a new one
+    pub fn show(&mut self, profile: Option<&Profile>) {
+        let default_name = if profile.is_none() && ProfileStore::load().profiles.is_empty() {
+            "paper"
+        } else {
+            ""
+        };
+        self.name.set_text(
+            profile
+                .map(|p| p.name.clone())
+                .unwrap_or(default_name.to_string()),
+        );
+        self.key_id
+            .set_text(profile.map(|p| p.key_id.clone()).unwrap_or_default());
+        self.secret_key
+            .set_text(profile.map(|p| p.secret_key.clone()).unwrap_or_default());
+        self.paper = profile.is_none_or(|p| p.paper);
+        self.testing = false;
+        self.status = None;
+        self.open = true;
+    }
+
+    /// Profile from the form, or an error if a field is empty
+    pub fn profile(&self) -> Result<Profile, TerminalError> {
+        let name = self.name.text().trim();
+        if name.is_empty() {
+            return Err(TerminalError::Input("Enter a profile name".to_string()));
+        }
+        if self.key_id.is_empty() || self.secret_key.is_empty() {
+            return Err(TerminalError::Input(
+                "Enter both the API key ID and secret key".to_string(),
+            ));
+        }
+        Ok(Profile {
+            name: name.to_string(),
+            key_id: self.key_id.text().to_string(),
+            secret_key: self.secret_key.text().to_string(),
+            paper: self.paper,
+        })
+    }
+}
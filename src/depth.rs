@@ -0,0 +1,113 @@
+// Depth module: bid/ask sizes for the main chart's symbol from the streamed NBBO. The feed
+// only carries the best bid and offer, so levels the top of book moved through in the last
+// few seconds stand in for a ladder until they're overtaken or go stale.
+
+use crate::stream::QuoteTick;
+use std::time::{Duration, Instant};
+
+/// How long a level that's no longer the best bid/offer stays in the ladder
+const LEVEL_WINDOW: Duration = Duration::from_secs(5);
+
+/// Levels shown per side
+pub const MAX_LEVELS: usize = 5;
+
+#[derive(Clone, Copy)]
+pub struct Level {
+    pub price: f64,
+    pub size: f64,
+    seen_at: Instant,
+}
+
+#[derive(Default)]
+pub struct DepthBook {
+    pub symbol: String,
+    // Best first: bids high to low, asks low to high
+    bids: Vec<Level>,
+    asks: Vec<Level>,
+    pub quotes: u64,
+}
+
+impl DepthBook {
+    /// Start over for a new symbol
+    pub fn reset(&mut self, symbol: &str) {
+        *self = Self {
+            symbol: symbol.to_string(),
+            ..Self::default()
+        };
+    }
+
+    /// Take a quote for the book's symbol; false if it's for another symbol or unusable
+    pub fn apply(&mut self, quote: &QuoteTick) -> bool {
+        if quote.symbol != self.symbol {
+            return false;
+        }
+        let number = |text: &str| text.parse::<f64>().ok().filter(|value| *value > 0.0);
+        let now = Instant::now();
+        let (Some(bid), Some(ask)) = (number(&quote.bid_price), number(&quote.ask_price)) else {
+            return false;
+        };
+        let bid_size = number(&quote.bid_size).unwrap_or(0.0);
+        let ask_size = number(&quote.ask_size).unwrap_or(0.0);
+
+        // A new best bid replaces the levels above it; the same for asks below
+        self.bids.retain(|level| level.price < bid);
+        self.bids.insert(
+            0,
+            Level {
+                price: bid,
+                size: bid_size,
+                seen_at: now,
+            },
+        );
+        self.asks.retain(|level| level.price > ask);
+        self.asks.insert(
+            0,
+            Level {
+                price: ask,
+                size: ask_size,
+                seen_at: now,
+            },
+        );
+        for levels in [&mut self.bids, &mut self.asks] {
+            let best = levels[0];
+            levels.retain(|level| {
+                level.price == best.price || now.duration_since(level.seen_at) < LEVEL_WINDOW
+            });
+            levels.truncate(MAX_LEVELS);
+        }
+        self.quotes += 1;
+        true
+    }
+
+    pub fn bids(&self) -> &[Level] {
+        &self.bids
+    }
+
+    pub fn asks(&self) -> &[Level] {
+        &self.asks
+    }
+
+    /// Spread in dollars and basis points of the midpoint
+    pub fn spread(&self) -> Option<(f64, f64)> {
+        let bid = self.bids.first()?.price;
+        let ask = self.asks.first()?.price;
+        let spread = ask - bid;
+        Some((spread, spread / ((ask + bid) / 2.0) * 10_000.0))
+    }
+
+    /// Share of the top-of-book size on the bid, 0 to 1
+    pub fn imbalance(&self) -> Option<f64> {
+        let bid = self.bids.first()?.size;
+        let ask = self.asks.first()?.size;
+        (bid + ask > 0.0).then(|| bid / (bid + ask))
+    }
+
+    /// Largest size shown, which the bars are scaled to
+    pub fn max_size(&self) -> f64 {
+        self.bids
+            .iter()
+            .chain(self.asks.iter())
+            .map(|level| level.size)
+            .fold(0.0, f64::max)
+    }
+}
@@ -0,0 +1,99 @@
+// Drawings module: tools placed on a symbol's chart by clicking a bar, persisted between
+// sessions. Anchored VWAPs are drawn from their anchor bar forward.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing::{error, info};
+
+/// Line colors, cycled through as anchors are added
+pub const VWAP_COLORS: [u32; 4] = [0xf0883e, 0xa371f7, 0x39c5cf, 0xdb61a2];
+
+/// VWAP accumulated from the bar starting at `at`
+#[derive(Clone, Serialize, Deserialize)]
+pub struct VwapAnchor {
+    pub id: u64,
+    pub symbol: String,
+    pub at: DateTime<Utc>,
+    pub color: u32,
+}
+
+/// Drawings saved to `<config dir>/alpaca_terminal/drawings.json`
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DrawingStore {
+    pub vwap_anchors: Vec<VwapAnchor>,
+    next_id: u64,
+    // The next click on a chart places an anchored VWAP (not saved)
+    #[serde(skip)]
+    pub placing_vwap: bool,
+}
+
+fn drawings_path() -> Option<PathBuf> {
+    crate::settings::config_dir().map(|dir| dir.join("drawings.json"))
+}
+
+impl DrawingStore {
+    /// Anchor a VWAP to a symbol's bar, unless one is already there
+    pub fn add_vwap(&mut self, symbol: &str, at: DateTime<Utc>) -> bool {
+        if self
+            .vwap_anchors
+            .iter()
+            .any(|anchor| anchor.symbol == symbol && anchor.at == at)
+        {
+            return false;
+        }
+        self.next_id += 1;
+        let color = VWAP_COLORS[self.vwap_anchors_for(symbol).count() % VWAP_COLORS.len()];
+        self.vwap_anchors.push(VwapAnchor {
+            id: self.next_id,
+            symbol: symbol.to_string(),
+            at,
+            color,
+        });
+        info!("⚓ Anchored VWAP on {} at {}", symbol, at);
+        true
+    }
+
+    pub fn remove_vwap(&mut self, id: u64) {
+        self.vwap_anchors.retain(|anchor| anchor.id != id);
+    }
+
+    pub fn vwap_anchors_for<'a>(&'a self, symbol: &'a str) -> impl Iterator<Item = &'a VwapAnchor> {
+        self.vwap_anchors
+            .iter()
+            .filter(move |anchor| anchor.symbol == symbol)
+    }
+
+    /// Load drawings from disk, or start empty if missing or invalid
+    pub fn load() -> Self {
+        let Some(path) = drawings_path() else {
+            return Self::default();
+        };
+
+        match crate::persist::load::<DrawingStore>(&path, "drawings") {
+            Some(store) => {
+                info!(
+                    "✅ Loaded {} anchored VWAPs from {}",
+                    store.vwap_anchors.len(),
+                    path.display()
+                );
+                store
+            }
+            None => Self::default(),
+        }
+    }
+
+    /// Write drawings to disk
+    pub fn save(&self) {
+        let Some(path) = drawings_path() else {
+            return;
+        };
+
+        let result = crate::persist::save(&path, self);
+
+        if let Err(e) = result {
+            error!("✗ Error saving drawings to {}: {}", path.display(), e);
+        }
+    }
+}
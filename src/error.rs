@@ -0,0 +1,176 @@
+// Error module: what can go wrong talking to Alpaca or reading local files, with a hint
+// the UI can show next to the message
+
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TerminalError {
+    // config.toml, settings or profile files, or the API runtime failed to start
+    Config(String),
+    // No profile and no APCA_* environment variables
+    MissingCredentials,
+    // Request never got a response (DNS, connection, timeout)
+    Network(String),
+    // HTTP 429
+    RateLimited,
+    // HTTP 401: keys are wrong or for the other environment
+    Unauthorized,
+    // The API doesn't know the symbol
+    InvalidSymbol(String),
+    // HTTP 404 for anything else (order, position)
+    NotFound(String),
+    // The API refused the request (buying power, shorting, PDT, market hours, bad quantity)
+    Rejected(String),
+    // Response or stream data we couldn't read
+    Parse(String),
+    // A form field the terminal can't use
+    Input(String),
+}
+
+impl TerminalError {
+    /// Classify an error from the alpaca_markets client by its HTTP status. The client
+    /// only exposes the status through its Debug output, so it's read from the status
+    /// field there (or from Alpaca's error code, which starts with it); digits elsewhere in
+    /// the text, such as a price or order id, never count. 403 is how Alpaca refuses
+    /// buying power, shorting and PDT violations, so it's a rejection, not bad keys.
+    pub fn api(error: impl fmt::Debug) -> Self {
+        let message = format!("{:?}", error);
+        match http_status(&message) {
+            Some(429) => TerminalError::RateLimited,
+            Some(401) => TerminalError::Unauthorized,
+            Some(404) => TerminalError::NotFound(api_message(&message)),
+            Some(_) => TerminalError::Rejected(api_message(&message)),
+            None => {
+                // No response: the request never reached Alpaca, or its body didn't parse
+                let lower = message.to_lowercase();
+                if lower.contains("timed out")
+                    || lower.contains("timeout")
+                    || lower.contains("connect")
+                    || lower.contains("dns")
+                {
+                    TerminalError::Network(message)
+                } else if lower.contains("decode") || lower.contains("deserializ") {
+                    TerminalError::Parse(message)
+                } else {
+                    TerminalError::Rejected(message)
+                }
+            }
+        }
+    }
+
+    /// API error for a request about one symbol; unknown-symbol responses become
+    /// InvalidSymbol
+    pub fn for_symbol(error: impl fmt::Debug, symbol: &str) -> Self {
+        match Self::api(error) {
+            TerminalError::NotFound(_) => TerminalError::InvalidSymbol(symbol.to_string()),
+            TerminalError::Rejected(message)
+                if message.to_lowercase().contains("invalid symbol") =>
+            {
+                TerminalError::InvalidSymbol(symbol.to_string())
+            }
+            other => other,
+        }
+    }
+
+    /// What the user can do about it
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            TerminalError::Config(_) => Some("Check the file's contents and permissions"),
+            TerminalError::MissingCredentials => Some(
+                "Add keys from the profile menu or set APCA_API_KEY_ID and APCA_API_SECRET_KEY",
+            ),
+            TerminalError::Network(_) => Some("Check your internet connection and retry"),
+            TerminalError::RateLimited => {
+                Some("Too many requests; wait a few seconds before retrying")
+            }
+            TerminalError::Unauthorized => {
+                Some("Check the profile's API keys and that they match paper or live")
+            }
+            TerminalError::InvalidSymbol(_) => {
+                Some("Check the ticker spelling (crypto pairs look like BTC/USD)")
+            }
+            TerminalError::NotFound(_) => {
+                Some("It may have been filled, canceled or closed already")
+            }
+            TerminalError::Rejected(_) => None,
+            TerminalError::Parse(_) => None,
+            TerminalError::Input(_) => None,
+        }
+    }
+
+    /// Likely to succeed if the same request is sent again after a short wait
+    pub fn is_transient(&self) -> bool {
+        matches!(self, TerminalError::Network(_) | TerminalError::RateLimited)
+    }
+
+    /// Worth offering a Retry button (retrying won't fix bad keys or a bad symbol)
+    pub fn is_retryable(&self) -> bool {
+        !matches!(
+            self,
+            TerminalError::Config(_)
+                | TerminalError::MissingCredentials
+                | TerminalError::Unauthorized
+                | TerminalError::InvalidSymbol(_)
+                | TerminalError::NotFound(_)
+                | TerminalError::Input(_)
+        )
+    }
+}
+
+/// HTTP status in the client's Debug text: the number after a `status` field (`status: 403`,
+/// `status: Some(429)`, `StatusCode(404)`), else the first three digits of an Alpaca error
+/// code (`"code":40310000`)
+fn http_status(message: &str) -> Option<u16> {
+    let lower = message.to_lowercase();
+    let after = |key: &str| {
+        lower.match_indices(key).find_map(|(index, _)| {
+            let rest = lower[index + key.len()..]
+                .trim_start_matches(|c: char| matches!(c, ' ' | ':' | '=' | '"' | '\\' | '('))
+                .trim_start_matches("some(");
+            let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+            (!digits.is_empty()).then_some(digits)
+        })
+    };
+    if let Some(digits) = after("statuscode").or_else(|| after("status")) {
+        return digits
+            .parse()
+            .ok()
+            .filter(|status| (100..600).contains(status));
+    }
+    after("\"code")
+        .filter(|digits| digits.len() == 8)
+        .and_then(|digits| digits[..3].parse().ok())
+}
+
+/// Alpaca's `message` from the response body, or the whole text when there isn't one
+fn api_message(message: &str) -> String {
+    // Quotes inside the body are escaped in Debug output (\"message\":\"...\")
+    let Some(index) = message.find("\"message") else {
+        return message.to_string();
+    };
+    let rest = message[index + "\"message".len()..]
+        .trim_start_matches(|c: char| matches!(c, ' ' | ':' | '"' | '\\'));
+    match rest.find(['"', '\\']) {
+        Some(end) if end > 0 => rest[..end].to_string(),
+        _ => message.to_string(),
+    }
+}
+
+impl fmt::Display for TerminalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TerminalError::Config(message) => write!(f, "{}", message),
+            TerminalError::MissingCredentials => write!(f, "No API credentials"),
+            TerminalError::Network(message) => write!(f, "Network error: {}", message),
+            TerminalError::RateLimited => write!(f, "Rate limited by Alpaca"),
+            TerminalError::Unauthorized => write!(f, "API keys were rejected"),
+            TerminalError::InvalidSymbol(symbol) => write!(f, "Unknown symbol {}", symbol),
+            TerminalError::NotFound(message) => write!(f, "Not found: {}", message),
+            TerminalError::Rejected(message) => write!(f, "Rejected: {}", message),
+            TerminalError::Parse(message) => write!(f, "Unexpected data: {}", message),
+            TerminalError::Input(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for TerminalError {}
@@ -0,0 +1,162 @@
+// Export module: CSV files of orders, positions and tax lots for record-keeping outside the
+// terminal
+
+use crate::account::{Order, Position};
+use crate::error::TerminalError;
+use crate::tax::TaxLot;
+use chrono::Local;
+use std::path::PathBuf;
+
+const ORDER_HEADER: [&str; 14] = [
+    "id",
+    "parent_id",
+    "symbol",
+    "side",
+    "type",
+    "qty",
+    "limit_price",
+    "status",
+    "filled_qty",
+    "filled_avg_price",
+    "created_at",
+    "submitted_at",
+    "filled_at",
+    "updated_at",
+];
+
+const POSITION_HEADER: [&str; 7] = [
+    "symbol",
+    "qty",
+    "avg_entry_price",
+    "current_price",
+    "market_value",
+    "unrealized_pl",
+    "unrealized_plpc",
+];
+
+const TAX_LOT_HEADER: [&str; 10] = [
+    "symbol",
+    "side",
+    "qty",
+    "opened_at",
+    "closed_at",
+    "cost_basis",
+    "proceeds",
+    "gain",
+    "holding_days",
+    "term",
+];
+
+/// Downloads folder, falling back to the home directory
+fn export_dir() -> PathBuf {
+    dirs::download_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// Quote a field when it holds a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Write rows under a header to `<name>_<timestamp>.csv` in the export folder
+fn write_csv(name: &str, header: &[&str], rows: &[Vec<String>]) -> Result<PathBuf, TerminalError> {
+    let mut contents = header.join(",");
+    contents.push('\n');
+    for row in rows {
+        let fields: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+        contents.push_str(&fields.join(","));
+        contents.push('\n');
+    }
+
+    let path = export_dir().join(format!(
+        "{}_{}.csv",
+        name,
+        Local::now().format("%Y%m%d_%H%M%S")
+    ));
+    std::fs::write(&path, contents)
+        .map_err(|e| TerminalError::Config(format!("Failed to write {}: {}", path.display(), e)))?;
+    Ok(path)
+}
+
+fn order_row(order: &Order, parent_id: Option<&str>) -> Vec<String> {
+    let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+    vec![
+        order.id.clone(),
+        parent_id.unwrap_or_default().to_string(),
+        order.symbol.clone(),
+        order.side.clone(),
+        order.order_type.clone(),
+        order.qty.clone(),
+        optional(&order.limit_price),
+        order.status.clone(),
+        order.filled_qty.clone(),
+        optional(&order.filled_avg_price),
+        order.created_at.clone(),
+        optional(&order.submitted_at),
+        optional(&order.filled_at),
+        optional(&order.updated_at),
+    ]
+}
+
+/// Write orders, each followed by its bracket/OCO legs, and return the file's path
+pub fn export_orders(orders: &[Order]) -> Result<PathBuf, TerminalError> {
+    let rows: Vec<Vec<String>> = orders
+        .iter()
+        .flat_map(|order| {
+            std::iter::once(order_row(order, None)).chain(
+                order
+                    .legs
+                    .iter()
+                    .map(|leg| order_row(leg, Some(order.id.as_str()))),
+            )
+        })
+        .collect();
+    write_csv("orders", &ORDER_HEADER, &rows)
+}
+
+/// Write a snapshot of the open positions and return the file's path
+pub fn export_positions(positions: &[Position]) -> Result<PathBuf, TerminalError> {
+    let rows: Vec<Vec<String>> = positions
+        .iter()
+        .map(|position| {
+            vec![
+                position.symbol.clone(),
+                position.qty.clone(),
+                position.avg_entry_price.clone(),
+                position.current_price.clone(),
+                position.market_value.clone(),
+                position.unrealized_pl.clone(),
+                position.unrealized_plpc.clone(),
+            ]
+        })
+        .collect();
+    write_csv("positions", &POSITION_HEADER, &rows)
+}
+
+/// Write the tax lot report (open lots have no close date or gain) and return the file's path
+pub fn export_tax_lots(lots: &[TaxLot]) -> Result<PathBuf, TerminalError> {
+    let amount = |value: Option<f64>| value.map(|v| format!("{:.2}", v)).unwrap_or_default();
+    let rows: Vec<Vec<String>> = lots
+        .iter()
+        .map(|lot| {
+            vec![
+                lot.symbol.clone(),
+                if lot.is_long { "long" } else { "short" }.to_string(),
+                lot.qty.to_string(),
+                lot.opened_at.to_rfc3339(),
+                lot.closed_at.map(|at| at.to_rfc3339()).unwrap_or_default(),
+                amount(lot.cost_basis),
+                amount(lot.proceeds),
+                amount(lot.gain()),
+                lot.holding_days().to_string(),
+                lot.term().label().to_string(),
+            ]
+        })
+        .collect();
+    write_csv("tax_lots", &TAX_LOT_HEADER, &rows)
+}
@@ -0,0 +1,143 @@
+// Exposure module: long/short, gross and net exposure plus a SPY beta-weighted delta for
+// the Analytics tab. Betas come from regressions of daily returns against SPY.
+
+use crate::account::Position;
+use alpaca_markets::Bar;
+use chrono::{Local, NaiveDate};
+use std::collections::HashMap;
+
+/// Index the betas are measured against
+pub const BENCHMARK: &str = "SPY";
+/// Daily bars per regression (about six months of sessions)
+pub const BETA_BARS: u32 = 126;
+/// Fewer shared daily returns than this and the beta is left unknown
+const MIN_RETURNS: usize = 20;
+
+/// Dollar exposure of the open positions (short is positive)
+pub struct Exposure {
+    pub long: f64,
+    pub short: f64,
+    pub net: f64,
+    pub gross: f64,
+}
+
+impl Exposure {
+    pub fn from_positions(positions: &[Position]) -> Self {
+        let values = positions
+            .iter()
+            .filter_map(|position| position.market_value.parse::<f64>().ok());
+        let (long, short) = values.fold((0.0, 0.0), |(long, short), value| {
+            if value >= 0.0 {
+                (long + value, short)
+            } else {
+                (long, short - value)
+            }
+        });
+        Self {
+            long,
+            short,
+            net: long - short,
+            gross: long + short,
+        }
+    }
+}
+
+/// Beta of a symbol's daily close-to-close returns against the benchmark's, over the dates
+/// both have bars for
+pub fn beta(bars: &[Bar], benchmark: &[Bar]) -> Option<f64> {
+    let benchmark: HashMap<NaiveDate, f64> = benchmark
+        .iter()
+        .map(|bar| (bar.timestamp.date_naive(), bar.close))
+        .collect();
+    let closes: Vec<(f64, f64)> = bars
+        .iter()
+        .filter_map(|bar| Some((bar.close, *benchmark.get(&bar.timestamp.date_naive())?)))
+        .collect();
+    let returns: Vec<(f64, f64)> = closes
+        .windows(2)
+        .filter(|pair| pair[0].0 > 0.0 && pair[0].1 > 0.0)
+        .map(|pair| (pair[1].0 / pair[0].0 - 1.0, pair[1].1 / pair[0].1 - 1.0))
+        .collect();
+    if returns.len() < MIN_RETURNS {
+        return None;
+    }
+
+    let count = returns.len() as f64;
+    let mean_symbol = returns.iter().map(|(symbol, _)| symbol).sum::<f64>() / count;
+    let mean_index = returns.iter().map(|(_, index)| index).sum::<f64>() / count;
+    let (covariance, variance) =
+        returns
+            .iter()
+            .fold((0.0, 0.0), |(covariance, variance), (symbol, index)| {
+                let index_deviation = index - mean_index;
+                (
+                    covariance + (symbol - mean_symbol) * index_deviation,
+                    variance + index_deviation * index_deviation,
+                )
+            });
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+/// Analytics tab state
+pub struct Analytics {
+    // Beta per symbol, for the day it was computed on (None when there wasn't enough data)
+    pub betas: HashMap<String, (NaiveDate, Option<f64>)>,
+    // Latest benchmark close, to express the beta-weighted delta in shares
+    pub benchmark_price: Option<f64>,
+    pub loading: bool,
+    pub error: Option<String>,
+}
+
+impl Analytics {
+    pub fn new() -> Self {
+        Self {
+            betas: HashMap::new(),
+            benchmark_price: None,
+            loading: false,
+            error: None,
+        }
+    }
+
+    /// Today's beta for a symbol (the benchmark's own is 1)
+    pub fn beta(&self, symbol: &str) -> Option<f64> {
+        if symbol == BENCHMARK {
+            return Some(1.0);
+        }
+        let today = Local::now().date_naive();
+        self.betas
+            .get(symbol)
+            .filter(|(date, _)| *date == today)
+            .and_then(|(_, beta)| *beta)
+    }
+
+    /// Position symbols not regressed yet today
+    pub fn missing(&self, positions: &[Position]) -> Vec<String> {
+        let today = Local::now().date_naive();
+        positions
+            .iter()
+            .map(|position| &position.symbol)
+            .filter(|symbol| *symbol != BENCHMARK)
+            .filter(|symbol| {
+                self.betas
+                    .get(*symbol)
+                    .is_none_or(|(date, _)| *date != today)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Sum of each position's value times its beta, in benchmark dollars, and the number
+    /// of positions left out for lack of a beta
+    pub fn beta_weighted_delta(&self, positions: &[Position]) -> (f64, usize) {
+        positions
+            .iter()
+            .filter_map(|position| {
+                let value = position.market_value.parse::<f64>().ok()?;
+                Some((value, self.beta(&position.symbol)))
+            })
+            .fold((0.0, 0), |(delta, missing), (value, beta)| match beta {
+                Some(beta) => (delta + value * beta, missing),
+                None => (delta, missing + 1),
+            })
+    }
+}
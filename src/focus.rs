@@ -0,0 +1,157 @@
+// Focus module: which text input receives keystrokes, and Tab order between inputs
+
+use crate::automation::AutomationField;
+use crate::chart::ChartId;
+use crate::options::StrategyField;
+use crate::screener::FilterField;
+
+/// Text inputs that can hold keyboard focus
+#[derive(Clone, Copy, PartialEq)]
+pub enum InputField {
+    Symbol,
+    BarLimit,
+    PositionsFilter,
+    OrdersSearch,
+    HistorySymbol,
+    OrderQuantity,
+    LimitPrice,
+    HotkeyQuantity,
+    // Percent offsets of auto-attached exits
+    AutoExitTakeProfit,
+    AutoExitStopLoss,
+    // Preset order size of the scalp pad
+    ScalpSize,
+    // ATR period and multiplier of the SuperTrend overlay
+    SuperTrendPeriod,
+    SuperTrendMultiplier,
+    // Acceleration step and maximum of the Parabolic SAR overlay
+    SarStep,
+    SarMax,
+    // EMA/ATR period and ATR multiplier of the Keltner Channels overlay
+    KeltnerPeriod,
+    KeltnerMultiplier,
+    // Period of the ADX/DMI sub-panel
+    AdxPeriod,
+    AlertPrice,
+    // Dollar amount of a new recurring buy
+    RecurringAmount,
+    Strategy(StrategyField),
+    Automation(AutomationField),
+    ProfileName,
+    ApiKeyId,
+    ApiSecretKey,
+    LiveConfirmation,
+    WorkspaceName,
+    // HH:MM Eastern for a scheduled order
+    ScheduleTime,
+    ScreenFilter(FilterField),
+    // Symbol and order quantity of a chart window
+    ChartSymbol(usize),
+    ChartQuantity(usize),
+    // Donchian Channels period of the main chart or a chart window
+    DonchianPeriod(ChartId),
+}
+
+impl InputField {
+    /// Whether a typed character is allowed in a type-and-backspace field
+    /// (`TextInput` fields filter by their own input mode)
+    pub fn accepts(&self, c: char) -> bool {
+        match self {
+            InputField::Symbol
+            | InputField::BarLimit
+            | InputField::OrderQuantity
+            | InputField::LimitPrice
+            | InputField::ProfileName
+            | InputField::ApiKeyId
+            | InputField::ApiSecretKey
+            | InputField::LiveConfirmation
+            | InputField::WorkspaceName
+            | InputField::ChartSymbol(_)
+            | InputField::ChartQuantity(_) => false,
+            InputField::PositionsFilter | InputField::OrdersSearch | InputField::HistorySymbol => {
+                c.is_alphanumeric()
+            }
+            InputField::HotkeyQuantity
+            | InputField::AutoExitTakeProfit
+            | InputField::AutoExitStopLoss
+            | InputField::ScalpSize
+            | InputField::SuperTrendMultiplier
+            | InputField::SarStep
+            | InputField::SarMax
+            | InputField::KeltnerMultiplier
+            | InputField::AlertPrice
+            | InputField::RecurringAmount => c.is_numeric() || c == '.',
+            InputField::SuperTrendPeriod
+            | InputField::KeltnerPeriod
+            | InputField::AdxPeriod
+            | InputField::DonchianPeriod(_) => c.is_numeric(),
+            InputField::ScheduleTime => c.is_numeric() || c == ':',
+            // '-' for dates and credit (negative) net prices
+            InputField::Strategy(_) => c.is_numeric() || c == '.' || c == '-',
+            InputField::Automation(_) => c.is_numeric() || c == '.',
+            InputField::ScreenFilter(FilterField::Name) => !c.is_control(),
+            // '-' for falling change bounds
+            InputField::ScreenFilter(_) => c.is_numeric() || c == '.' || c == '-',
+        }
+    }
+
+    /// Chart window the input belongs to
+    pub fn chart_window(&self) -> Option<usize> {
+        match self {
+            InputField::ChartSymbol(id)
+            | InputField::ChartQuantity(id)
+            | InputField::DonchianPeriod(ChartId::Window(id)) => Some(*id),
+            _ => None,
+        }
+    }
+
+    /// Symbol filters are stored upper-case as they're typed
+    pub fn uppercases(&self) -> bool {
+        matches!(
+            self,
+            InputField::PositionsFilter | InputField::OrdersSearch | InputField::HistorySymbol
+        )
+    }
+}
+
+/// Single owner of text input focus, so at most one input is focused at a time
+pub struct FocusManager {
+    focused: Option<InputField>,
+}
+
+impl FocusManager {
+    pub fn new() -> Self {
+        Self { focused: None }
+    }
+
+    pub fn focused(&self) -> Option<InputField> {
+        self.focused
+    }
+
+    pub fn is_focused(&self, field: InputField) -> bool {
+        self.focused == Some(field)
+    }
+
+    pub fn set(&mut self, field: Option<InputField>) {
+        self.focused = field;
+    }
+
+    /// Field after (or before) the focused one in `order`, wrapping around. Starts from
+    /// the first (or last) field when nothing in `order` is focused.
+    pub fn next(&self, order: &[InputField], forward: bool) -> Option<InputField> {
+        if order.is_empty() {
+            return None;
+        }
+        let count = order.len();
+        let index = match self
+            .focused
+            .and_then(|f| order.iter().position(|o| *o == f))
+        {
+            Some(index) if forward => (index + 1) % count,
+            Some(index) => (index + count - 1) % count,
+            None if forward => 0,
+            None => count - 1,
+        };
+        Some(order[index])
+    }
+}
@@ -0,0 +1,183 @@
+// Heatmap module: day % change across a universe of symbols, drawn as a treemap whose tile
+// areas follow traded value or position size
+
+use crate::config::{TileSize, Universe};
+use crate::snapshot::SymbolSnapshot;
+use chrono::{DateTime, Local};
+use std::collections::HashMap;
+
+/// Width:height of the map area, used to keep tiles close to square
+pub const MAP_ASPECT: f64 = 3.0;
+/// A percent change this large or more gets the strongest color
+const FULL_COLOR_PERCENT: f64 = 3.0;
+/// Smallest tile, as a fraction of the largest, so unsized symbols stay clickable
+const MIN_WEIGHT_FRACTION: f64 = 0.02;
+
+pub struct HeatmapTile {
+    pub symbol: String,
+    pub price: Option<f64>,
+    pub change_percent: Option<f64>,
+    weight: f64,
+}
+
+/// Tile position and size as fractions of the map
+pub struct TileRect {
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+}
+
+/// Heatmap tab state
+pub struct Heatmap {
+    pub universe: Universe,
+    pub size_by: TileSize,
+    pub snapshots: Vec<SymbolSnapshot>,
+    pub loading: bool,
+    pub error: Option<String>,
+    pub updated_at: Option<DateTime<Local>>,
+    // Symbols the loaded snapshots were requested for
+    pub symbols: Vec<String>,
+}
+
+impl Heatmap {
+    pub fn new(universe: Universe, size_by: TileSize) -> Self {
+        Self {
+            universe,
+            size_by,
+            snapshots: Vec::new(),
+            loading: false,
+            error: None,
+            updated_at: None,
+            symbols: Vec::new(),
+        }
+    }
+
+    /// Whether the snapshots are older than the refresh interval
+    pub fn needs_refresh(&self, refresh_secs: u64) -> bool {
+        self.updated_at
+            .is_none_or(|at| (Local::now() - at).num_seconds() >= refresh_secs as i64)
+    }
+
+    /// Tiles largest first. `positions` maps symbols to the market value held.
+    pub fn tiles(&self, positions: &HashMap<String, f64>) -> Vec<HeatmapTile> {
+        let mut tiles: Vec<HeatmapTile> = self
+            .snapshots
+            .iter()
+            .map(|snapshot| HeatmapTile {
+                symbol: snapshot.symbol.clone(),
+                price: snapshot.price(),
+                change_percent: snapshot.day_change_percent(),
+                weight: match self.size_by {
+                    TileSize::DollarVolume => snapshot.dollar_volume().unwrap_or(0.0),
+                    TileSize::Position => positions
+                        .get(&snapshot.symbol)
+                        .map_or(0.0, |value| value.abs()),
+                    TileSize::Equal => 1.0,
+                },
+            })
+            .collect();
+
+        let largest = tiles.iter().map(|tile| tile.weight).fold(0.0, f64::max);
+        let floor = if largest > 0.0 {
+            largest * MIN_WEIGHT_FRACTION
+        } else {
+            1.0
+        };
+        for tile in &mut tiles {
+            tile.weight = tile.weight.max(floor);
+        }
+        tiles.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+        tiles
+    }
+}
+
+/// Background for a day change: gray when flat, deepening green or red with the move
+pub fn change_color(percent: Option<f64>) -> u32 {
+    const FLAT: u32 = 0x30363d;
+    let Some(percent) = percent else {
+        return 0x21262d;
+    };
+    let target = if percent >= 0.0 { 0x2ea043 } else { 0xda3633 };
+    let t = (percent.abs() / FULL_COLOR_PERCENT).min(1.0);
+    let channel = |shift: u32| {
+        let from = ((FLAT >> shift) & 0xff) as f64;
+        let to = ((target >> shift) & 0xff) as f64;
+        ((from + (to - from) * t).round() as u32) << shift
+    };
+    channel(16) | channel(8) | channel(0)
+}
+
+/// Squarified treemap layout for tiles sorted largest first, on a map `aspect` times wider
+/// than it is tall. Rows are laid along the shorter side of the space left, growing while
+/// that keeps the row's tiles closer to square.
+pub fn layout(tiles: &[HeatmapTile], aspect: f64) -> Vec<TileRect> {
+    let total: f64 = tiles.iter().map(|tile| tile.weight).sum();
+    if total <= 0.0 {
+        return Vec::new();
+    }
+    // Areas in a space `aspect` wide and 1 tall
+    let areas: Vec<f64> = tiles
+        .iter()
+        .map(|tile| tile.weight / total * aspect)
+        .collect();
+
+    let (mut x, mut y, mut width, mut height) = (0.0, 0.0, aspect, 1.0);
+    let mut rects = Vec::with_capacity(areas.len());
+    let mut start = 0;
+    while start < areas.len() {
+        let side = width.min(height);
+        let mut end = start + 1;
+        while end < areas.len()
+            && worst_ratio(&areas[start..=end], side) <= worst_ratio(&areas[start..end], side)
+        {
+            end += 1;
+        }
+
+        let row = &areas[start..end];
+        let row_area: f64 = row.iter().sum();
+        if width >= height {
+            // Column on the left, tiles stacked downwards
+            let column = row_area / height;
+            let mut top = y;
+            for area in row {
+                let tile_height = area / column;
+                rects.push(rect(x, top, column, tile_height, aspect));
+                top += tile_height;
+            }
+            x += column;
+            width -= column;
+        } else {
+            // Row along the top, tiles left to right
+            let band = row_area / width;
+            let mut left = x;
+            for area in row {
+                let tile_width = area / band;
+                rects.push(rect(left, y, tile_width, band, aspect));
+                left += tile_width;
+            }
+            y += band;
+            height -= band;
+        }
+        start = end;
+    }
+    rects
+}
+
+/// Largest width:height (or height:width) ratio among a row's tiles laid along `side`
+fn worst_ratio(row: &[f64], side: f64) -> f64 {
+    let sum: f64 = row.iter().sum();
+    let largest = row.iter().copied().fold(f64::MIN, f64::max);
+    let smallest = row.iter().copied().fold(f64::MAX, f64::min);
+    let (side, sum) = (side * side, sum * sum);
+    (side * largest / sum).max(sum / (side * smallest))
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64, aspect: f64) -> TileRect {
+    TileRect {
+        x: (x / aspect) as f32,
+        y: y as f32,
+        w: (w / aspect) as f32,
+        h: h as f32,
+    }
+}
@@ -0,0 +1,43 @@
+// Hotkeys module for one-key market orders on the charted symbol
+
+use alpaca_markets::models::OrderSide;
+use serde::{Deserialize, Serialize};
+
+/// Side of a hotkey order
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum HotkeySide {
+    Buy,
+    Sell,
+}
+
+impl HotkeySide {
+    pub fn label(&self) -> &'static str {
+        match self {
+            HotkeySide::Buy => "Buy",
+            HotkeySide::Sell => "Sell",
+        }
+    }
+
+    pub fn order_side(&self) -> OrderSide {
+        match self {
+            HotkeySide::Buy => OrderSide::Buy,
+            HotkeySide::Sell => OrderSide::Sell,
+        }
+    }
+}
+
+/// Sidebar panel state (the enable flag, size and confirmation live in settings)
+pub struct Hotkeys {
+    pub open: bool,
+    // Hotkey order waiting for confirmation
+    pub pending: Option<HotkeySide>,
+}
+
+impl Hotkeys {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            pending: None,
+        }
+    }
+}
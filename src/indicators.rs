@@ -0,0 +1,433 @@
+// Indicators module: technical indicator series computed from bar closes (or whole bars).
+// Each series is aligned with its input, with None until enough values have been seen.
+
+use alpaca_markets::Bar;
+
+/// Simple moving average
+pub fn sma(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut series = vec![None; values.len()];
+    if period == 0 {
+        return series;
+    }
+    let mut sum = 0.0;
+    for (index, value) in values.iter().enumerate() {
+        sum += value;
+        if index >= period {
+            sum -= values[index - period];
+        }
+        if index + 1 >= period {
+            series[index] = Some(sum / period as f64);
+        }
+    }
+    series
+}
+
+/// Exponential moving average, seeded with the simple average of the first `period` values
+pub fn ema(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut series = vec![None; values.len()];
+    if period == 0 || values.len() < period {
+        return series;
+    }
+    let alpha = 2.0 / (period as f64 + 1.0);
+    let mut average = values[..period].iter().sum::<f64>() / period as f64;
+    series[period - 1] = Some(average);
+    for (index, value) in values.iter().enumerate().skip(period) {
+        average += alpha * (value - average);
+        series[index] = Some(average);
+    }
+    series
+}
+
+/// Relative strength index (0 - 100) with Wilder's smoothing
+pub fn rsi(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut series = vec![None; values.len()];
+    if period == 0 || values.len() <= period {
+        return series;
+    }
+    let changes: Vec<f64> = values.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    let mut gain = changes[..period].iter().map(|c| c.max(0.0)).sum::<f64>() / period as f64;
+    let mut loss = changes[..period].iter().map(|c| (-c).max(0.0)).sum::<f64>() / period as f64;
+    let index_of = |gain: f64, loss: f64| {
+        if loss == 0.0 {
+            100.0
+        } else {
+            100.0 - 100.0 / (1.0 + gain / loss)
+        }
+    };
+    series[period] = Some(index_of(gain, loss));
+    for (index, change) in changes.iter().enumerate().skip(period) {
+        gain = (gain * (period - 1) as f64 + change.max(0.0)) / period as f64;
+        loss = (loss * (period - 1) as f64 + (-change).max(0.0)) / period as f64;
+        series[index + 1] = Some(index_of(gain, loss));
+    }
+    series
+}
+
+/// Latest value of a series
+pub fn last(series: &[Option<f64>]) -> Option<f64> {
+    series.last().copied().flatten()
+}
+
+/// Share of the volume the value area holds
+pub const VALUE_AREA_SHARE: f64 = 0.7;
+
+/// Volume traded at each price bin between `low` and `high`, with the point of control
+/// (busiest bin) and the value area (bins around it holding 70% of the volume)
+pub struct VolumeProfile {
+    pub low: f64,
+    pub bin_size: f64,
+    // Lowest price bin first
+    pub volumes: Vec<f64>,
+    pub poc: usize,
+    pub value_area: std::ops::RangeInclusive<usize>,
+}
+
+impl VolumeProfile {
+    /// Price at the middle of a bin
+    pub fn price(&self, bin: usize) -> f64 {
+        self.low + (bin as f64 + 0.5) * self.bin_size
+    }
+}
+
+/// Volume profile of the bars over `bins` rows. Each bar's volume is spread evenly over
+/// its high-low range; None without volume in the range.
+pub fn volume_profile(bars: &[Bar], low: f64, high: f64, bins: usize) -> Option<VolumeProfile> {
+    if bins == 0 || high <= low {
+        return None;
+    }
+    let bin_size = (high - low) / bins as f64;
+    let bin_of = |price: f64| (((price - low) / bin_size) as usize).min(bins - 1);
+    let mut volumes = vec![0.0; bins];
+    for bar in bars {
+        let (bar_low, bar_high) = (bar.low.max(low), bar.high.min(high));
+        if bar_high < bar_low || bar.volume == 0 {
+            continue;
+        }
+        let volume = bar.volume as f64;
+        if bar_high == bar_low {
+            volumes[bin_of(bar_low)] += volume;
+            continue;
+        }
+        for (bin, slot) in volumes
+            .iter_mut()
+            .enumerate()
+            .take(bin_of(bar_high) + 1)
+            .skip(bin_of(bar_low))
+        {
+            let bin_low = low + bin as f64 * bin_size;
+            let overlap = (bin_low + bin_size).min(bar_high) - bin_low.max(bar_low);
+            *slot += volume * overlap.max(0.0) / (bar_high - bar_low);
+        }
+    }
+
+    let total: f64 = volumes.iter().sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let poc = volumes
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map(|(bin, _)| bin)?;
+
+    // Grow from the point of control towards the busier neighbour until the share is held
+    let (mut first, mut last) = (poc, poc);
+    let mut held = volumes[poc];
+    while held < total * VALUE_AREA_SHARE && (first > 0 || last + 1 < bins) {
+        let below = (first > 0).then(|| volumes[first - 1]);
+        let above = (last + 1 < bins).then(|| volumes[last + 1]);
+        if above.unwrap_or(-1.0) >= below.unwrap_or(-1.0) {
+            last += 1;
+            held += volumes[last];
+        } else {
+            first -= 1;
+            held += volumes[first];
+        }
+    }
+
+    Some(VolumeProfile {
+        low,
+        bin_size,
+        volumes,
+        poc,
+        value_area: first..=last,
+    })
+}
+
+/// Average true range, smoothed the way RSI is. The first bar's true range is its
+/// high-low range.
+pub fn atr(bars: &[Bar], period: usize) -> Vec<Option<f64>> {
+    let mut series = vec![None; bars.len()];
+    if period == 0 || bars.len() < period {
+        return series;
+    }
+    let mut average = (0..period)
+        .map(|index| true_range(bars, index))
+        .sum::<f64>()
+        / period as f64;
+    series[period - 1] = Some(average);
+    for (index, slot) in series.iter_mut().enumerate().skip(period) {
+        average = (average * (period - 1) as f64 + true_range(bars, index)) / period as f64;
+        *slot = Some(average);
+    }
+    series
+}
+
+/// Largest of the bar's range and its distances from the previous close
+fn true_range(bars: &[Bar], index: usize) -> f64 {
+    let bar = &bars[index];
+    match index.checked_sub(1).map(|prev| bars[prev].close) {
+        Some(prev_close) => (bar.high - bar.low)
+            .max((bar.high - prev_close).abs())
+            .max((bar.low - prev_close).abs()),
+        None => bar.high - bar.low,
+    }
+}
+
+/// Directional movement: ADX with the +DI and -DI lines it comes from, as (adx, plus_di,
+/// minus_di) series on a 0-100 scale. The DIs start after `period` bars and ADX, their
+/// smoothed spread, after `period` more.
+pub fn dmi(bars: &[Bar], period: usize) -> (Vec<Option<f64>>, Vec<Option<f64>>, Vec<Option<f64>>) {
+    if period == 0 || bars.len() <= period {
+        let empty = vec![None; bars.len()];
+        return (empty.clone(), empty.clone(), empty);
+    }
+    let mut adx = vec![None; period];
+    let mut plus_di = vec![None; period];
+    let mut minus_di = vec![None; period];
+    // True range and +DM/-DM of each bar against the one before
+    let moves = |index: usize| {
+        let up = bars[index].high - bars[index - 1].high;
+        let down = bars[index - 1].low - bars[index].low;
+        (
+            true_range(bars, index),
+            if up > down && up > 0.0 { up } else { 0.0 },
+            if down > up && down > 0.0 { down } else { 0.0 },
+        )
+    };
+
+    // Running Wilder sums, seeded with the first `period` moves
+    let (mut range, mut plus, mut minus) = (1..=period)
+        .map(moves)
+        .fold((0.0, 0.0, 0.0), |(range, plus, minus), (r, p, m)| {
+            (range + r, plus + p, minus + m)
+        });
+    let mut first_dx = Vec::with_capacity(period);
+    let mut average: Option<f64> = None;
+    for index in period..bars.len() {
+        if index > period {
+            let (r, p, m) = moves(index);
+            let decay = 1.0 - 1.0 / period as f64;
+            range = range * decay + r;
+            plus = plus * decay + p;
+            minus = minus * decay + m;
+        }
+        let (plus_value, minus_value) = if range > 0.0 {
+            (100.0 * plus / range, 100.0 * minus / range)
+        } else {
+            (0.0, 0.0)
+        };
+        plus_di.push(Some(plus_value));
+        minus_di.push(Some(minus_value));
+
+        let spread = plus_value + minus_value;
+        let dx = if spread > 0.0 {
+            100.0 * (plus_value - minus_value).abs() / spread
+        } else {
+            0.0
+        };
+        average = match average {
+            Some(average) => Some((average * (period - 1) as f64 + dx) / period as f64),
+            None => {
+                first_dx.push(dx);
+                (first_dx.len() == period).then(|| first_dx.iter().sum::<f64>() / period as f64)
+            }
+        };
+        adx.push(average);
+    }
+    (adx, plus_di, minus_di)
+}
+
+/// SuperTrend: a band `multiplier` ATRs from the bar midpoint that trails price, below it
+/// while the trend is up and above it while down. Each value comes with whether the trend
+/// is up.
+pub fn supertrend(bars: &[Bar], period: usize, multiplier: f64) -> Vec<Option<(f64, bool)>> {
+    let mut series = vec![None; bars.len()];
+    // Final upper and lower bands and the trend of the previous bar
+    let mut previous: Option<(f64, f64, bool)> = None;
+    for (index, range) in atr(bars, period).into_iter().enumerate() {
+        let Some(range) = range else {
+            continue;
+        };
+        let bar = &bars[index];
+        let middle = (bar.high + bar.low) / 2.0;
+        let (mut upper, mut lower) = (middle + multiplier * range, middle - multiplier * range);
+        let mut up = true;
+        if let Some((prev_upper, prev_lower, prev_up)) = previous {
+            let prev_close = bars[index - 1].close;
+            // Bands only tighten until price closes through them
+            if upper > prev_upper && prev_close <= prev_upper {
+                upper = prev_upper;
+            }
+            if lower < prev_lower && prev_close >= prev_lower {
+                lower = prev_lower;
+            }
+            up = if prev_up {
+                bar.close >= lower
+            } else {
+                bar.close > upper
+            };
+        }
+        series[index] = Some((if up { lower } else { upper }, up));
+        previous = Some((upper, lower, up));
+    }
+    series
+}
+
+/// Parabolic SAR: a stop that starts at the prior extreme and accelerates towards price by
+/// `step` each time the trend makes a new extreme, up to `max`, flipping sides when price
+/// crosses it. Each value comes with whether the trend is up (the stop below price).
+pub fn parabolic_sar(bars: &[Bar], step: f64, max: f64) -> Vec<Option<(f64, bool)>> {
+    let mut series = vec![None; bars.len()];
+    if bars.len() < 2 {
+        return series;
+    }
+    let mut up = bars[1].close >= bars[0].close;
+    let (mut sar, mut extreme) = if up {
+        (bars[0].low, bars[1].high)
+    } else {
+        (bars[0].high, bars[1].low)
+    };
+    let mut acceleration = step;
+    series[1] = Some((sar, up));
+
+    for (index, bar) in bars.iter().enumerate().skip(2) {
+        let (prev, prev2) = (&bars[index - 1], &bars[index - 2]);
+        sar += acceleration * (extreme - sar);
+        if up {
+            // The stop never moves into the last two bars' range
+            sar = sar.min(prev.low).min(prev2.low);
+            if bar.low < sar {
+                (up, sar, extreme, acceleration) = (false, extreme, bar.low, step);
+            } else if bar.high > extreme {
+                extreme = bar.high;
+                acceleration = (acceleration + step).min(max);
+            }
+        } else {
+            sar = sar.max(prev.high).max(prev2.high);
+            if bar.high > sar {
+                (up, sar, extreme, acceleration) = (true, extreme, bar.high, step);
+            } else if bar.low < extreme {
+                extreme = bar.low;
+                acceleration = (acceleration + step).min(max);
+            }
+        }
+        series[index] = Some((sar, up));
+    }
+    series
+}
+
+/// Keltner Channels: the EMA of the closes with upper and lower bands `multiplier` ATRs
+/// away, as (middle, upper, lower) series
+pub fn keltner(
+    bars: &[Bar],
+    period: usize,
+    multiplier: f64,
+) -> (Vec<Option<f64>>, Vec<Option<f64>>, Vec<Option<f64>>) {
+    let closes: Vec<f64> = bars.iter().map(|bar| bar.close).collect();
+    let middle = ema(&closes, period);
+    let range = atr(bars, period);
+    let band = |sign: f64| -> Vec<Option<f64>> {
+        middle
+            .iter()
+            .zip(&range)
+            .map(|(middle, range)| Some(middle.as_ref()? + sign * multiplier * range.as_ref()?))
+            .collect()
+    };
+    let (upper, lower) = (band(1.0), band(-1.0));
+    (middle, upper, lower)
+}
+
+/// Donchian Channels: the highest high and lowest low of the last `period` bars and the
+/// midpoint between them, as (middle, upper, lower) series
+pub fn donchian(
+    bars: &[Bar],
+    period: usize,
+) -> (Vec<Option<f64>>, Vec<Option<f64>>, Vec<Option<f64>>) {
+    let mut middle = vec![None; bars.len()];
+    let mut upper = vec![None; bars.len()];
+    let mut lower = vec![None; bars.len()];
+    if period == 0 {
+        return (middle, upper, lower);
+    }
+    for (index, window) in bars.windows(period).enumerate() {
+        let high = window
+            .iter()
+            .map(|bar| bar.high)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let low = window
+            .iter()
+            .map(|bar| bar.low)
+            .fold(f64::INFINITY, f64::min);
+        let index = index + period - 1;
+        middle[index] = Some((high + low) / 2.0);
+        upper[index] = Some(high);
+        lower[index] = Some(low);
+    }
+    (middle, upper, lower)
+}
+
+/// Classic floor-trader pivot and its three resistance and support levels
+pub struct Pivots {
+    pub pivot: f64,
+    pub resistance: [f64; 3],
+    pub support: [f64; 3],
+}
+
+impl Pivots {
+    /// Levels with their labels, highest first
+    pub fn levels(&self) -> [(&'static str, f64); 7] {
+        [
+            ("R3", self.resistance[2]),
+            ("R2", self.resistance[1]),
+            ("R1", self.resistance[0]),
+            ("P", self.pivot),
+            ("S1", self.support[0]),
+            ("S2", self.support[1]),
+            ("S3", self.support[2]),
+        ]
+    }
+}
+
+/// Pivot levels from a period's high, low and close
+pub fn pivot_points(high: f64, low: f64, close: f64) -> Pivots {
+    let pivot = (high + low + close) / 3.0;
+    let range = high - low;
+    Pivots {
+        pivot,
+        resistance: [2.0 * pivot - low, pivot + range, high + 2.0 * (pivot - low)],
+        support: [
+            2.0 * pivot - high,
+            pivot - range,
+            low - 2.0 * (high - pivot),
+        ],
+    }
+}
+
+/// Volume-weighted average of the typical price (high + low + close) / 3, accumulated from
+/// the bar at `anchor` on
+pub fn anchored_vwap(bars: &[Bar], anchor: usize) -> Vec<Option<f64>> {
+    let mut series = vec![None; bars.len()];
+    let (mut value, mut volume) = (0.0, 0.0);
+    for (index, bar) in bars.iter().enumerate().skip(anchor) {
+        let typical = (bar.high + bar.low + bar.close) / 3.0;
+        value += typical * bar.volume as f64;
+        volume += bar.volume as f64;
+        series[index] = if volume > 0.0 {
+            Some(value / volume)
+        } else {
+            Some(typical)
+        };
+    }
+    series
+}
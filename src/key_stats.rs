@@ -0,0 +1,52 @@
+// Key stats module: 52-week range and average volume for the charted symbol, computed from a
+// year of daily bars (today's open/high/low and the previous close come from the snapshot)
+
+use alpaca_markets::Bar;
+use chrono::{Duration, Utc};
+
+/// Daily bars requested for the 52-week range (crypto trades every day of the year)
+pub const YEAR_BARS: u32 = 366;
+/// Completed sessions averaged for the volume figure
+pub const AVG_VOLUME_SESSIONS: usize = 30;
+
+#[derive(Clone)]
+pub struct KeyStats {
+    pub symbol: String,
+    pub year_high: Option<f64>,
+    pub year_low: Option<f64>,
+    pub avg_volume: Option<f64>,
+}
+
+impl KeyStats {
+    /// Stats from daily bars, oldest first
+    pub fn from_daily_bars(symbol: String, bars: &[Bar]) -> Self {
+        let year_ago = Utc::now() - Duration::days(365);
+        let year = bars.iter().filter(|bar| bar.timestamp >= year_ago);
+        let year_high = year.clone().map(|bar| bar.high).reduce(f64::max);
+        let year_low = year.map(|bar| bar.low).reduce(f64::min);
+
+        // Today's bar is still filling in, so it would drag the average down
+        let today = Utc::now().date_naive();
+        let completed: Vec<&Bar> = bars
+            .iter()
+            .filter(|bar| bar.timestamp.date_naive() < today)
+            .collect();
+        let recent = &completed[completed.len().saturating_sub(AVG_VOLUME_SESSIONS)..];
+        let avg_volume = (!recent.is_empty())
+            .then(|| recent.iter().map(|bar| bar.volume as f64).sum::<f64>() / recent.len() as f64);
+
+        Self {
+            symbol,
+            year_high,
+            year_low,
+            avg_volume,
+        }
+    }
+
+    /// 52-week range widened by today's high and low, which the daily bars may not have yet
+    pub fn year_range(&self, today: Option<&Bar>) -> Option<(f64, f64)> {
+        let high = self.year_high.into_iter().chain(today.map(|bar| bar.high));
+        let low = self.year_low.into_iter().chain(today.map(|bar| bar.low));
+        Some((high.reduce(f64::max)?, low.reduce(f64::min)?))
+    }
+}
@@ -0,0 +1,145 @@
+// Keymap module: user-configurable key chords for terminal actions
+
+use gpui::{App, KeyBinding, Keystroke, actions};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+actions!(
+    terminal,
+    [
+        Quit,
+        RefreshData,
+        SubmitOrder,
+        NextTab,
+        PreviousTab,
+        Flatten,
+        FocusSymbol,
+        HotkeyBuy,
+        HotkeySell
+    ]
+);
+
+/// Key chord per action, saved to `<config dir>/alpaca_terminal/keymap.json`.
+/// Chords use GPUI syntax (e.g. "ctrl-shift-r", "secondary-enter" for cmd on macOS and
+/// ctrl elsewhere); an empty string leaves the action unbound.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Keymap {
+    pub quit: String,
+    pub refresh_data: String,
+    pub submit_order: String,
+    pub next_tab: String,
+    pub previous_tab: String,
+    pub flatten: String,
+    pub focus_symbol: String,
+    // Only active while no text input has focus
+    pub hotkey_buy: String,
+    pub hotkey_sell: String,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            quit: "secondary-q".to_string(),
+            refresh_data: "secondary-r".to_string(),
+            submit_order: "secondary-enter".to_string(),
+            next_tab: "ctrl-tab".to_string(),
+            previous_tab: "ctrl-shift-tab".to_string(),
+            flatten: "secondary-shift-x".to_string(),
+            focus_symbol: "secondary-k".to_string(),
+            hotkey_buy: "shift-b".to_string(),
+            hotkey_sell: "shift-s".to_string(),
+        }
+    }
+}
+
+/// Key context set on the terminal while no text input has focus, so plain-letter
+/// hotkeys don't swallow typing
+pub const TRADING_CONTEXT: &str = "Trading";
+
+fn keymap_path() -> Option<PathBuf> {
+    crate::settings::config_dir().map(|dir| dir.join("keymap.json"))
+}
+
+/// Check that every keystroke in a (space separated) chord parses
+fn is_valid_chord(chord: &str) -> bool {
+    chord
+        .split_whitespace()
+        .all(|keystroke| Keystroke::parse(keystroke).is_ok())
+}
+
+impl Keymap {
+    /// Load the keymap from disk, writing the defaults on first run (or after an unreadable
+    /// file was moved aside) so they can be edited
+    pub fn load() -> Self {
+        let Some(path) = keymap_path() else {
+            return Self::default();
+        };
+
+        match crate::persist::load::<Keymap>(&path, "keymap") {
+            Some(keymap) => {
+                info!("✅ Keymap loaded from {}", path.display());
+                keymap
+            }
+            None => {
+                let keymap = Self::default();
+                keymap.save(&path);
+                keymap
+            }
+        }
+    }
+
+    fn save(&self, path: &PathBuf) {
+        let result = crate::persist::save(path, self);
+
+        if let Err(e) = result {
+            warn!(
+                "⚠️  Failed to write default keymap to {}: {}",
+                path.display(),
+                e
+            );
+        }
+    }
+
+    /// Register the chords with GPUI, skipping unbound and invalid entries
+    pub fn bind(&self, cx: &mut App) {
+        let mut bindings = Vec::new();
+        let mut add = |chord: &str, binding: fn(&str) -> KeyBinding| {
+            if chord.trim().is_empty() {
+                return;
+            }
+            if is_valid_chord(chord) {
+                bindings.push(binding(chord));
+            } else {
+                warn!("⚠️  Ignoring invalid key chord in keymap: {:?}", chord);
+            }
+        };
+
+        add(&self.quit, |chord| KeyBinding::new(chord, Quit, None));
+        add(&self.refresh_data, |chord| {
+            KeyBinding::new(chord, RefreshData, None)
+        });
+        add(&self.submit_order, |chord| {
+            KeyBinding::new(chord, SubmitOrder, None)
+        });
+        add(&self.next_tab, |chord| {
+            KeyBinding::new(chord, NextTab, None)
+        });
+        add(&self.previous_tab, |chord| {
+            KeyBinding::new(chord, PreviousTab, None)
+        });
+        add(&self.flatten, |chord| KeyBinding::new(chord, Flatten, None));
+        add(&self.focus_symbol, |chord| {
+            KeyBinding::new(chord, FocusSymbol, None)
+        });
+        add(&self.hotkey_buy, |chord| {
+            KeyBinding::new(chord, HotkeyBuy, Some(TRADING_CONTEXT))
+        });
+        add(&self.hotkey_sell, |chord| {
+            KeyBinding::new(chord, HotkeySell, Some(TRADING_CONTEXT))
+        });
+
+        cx.bind_keys(bindings);
+    }
+}
@@ -0,0 +1,39 @@
+// Layout module: responsive breakpoints for the main window
+
+/// Below this width the order sidebar collapses into a drawer
+const COMPACT_WIDTH: f32 = 1280.0;
+/// Below this width header controls stack and padding shrinks (laptop half-screen)
+const NARROW_WIDTH: f32 = 900.0;
+/// Width of the order drawer when it's open
+pub const DRAWER_WIDTH: f32 = 340.0;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LayoutMode {
+    // Sidebar always visible next to the chart
+    Wide,
+    // Sidebar is a toggleable drawer over the chart
+    Compact,
+    // Drawer, plus stacked header controls and tighter spacing
+    Narrow,
+}
+
+impl LayoutMode {
+    pub fn for_width(width: f32) -> Self {
+        if width >= COMPACT_WIDTH {
+            LayoutMode::Wide
+        } else if width >= NARROW_WIDTH {
+            LayoutMode::Compact
+        } else {
+            LayoutMode::Narrow
+        }
+    }
+
+    /// Whether the order sidebar is shown as a drawer instead of a column
+    pub fn has_drawer(&self) -> bool {
+        !matches!(self, LayoutMode::Wide)
+    }
+
+    pub fn is_narrow(&self) -> bool {
+        matches!(self, LayoutMode::Narrow)
+    }
+}
@@ -0,0 +1,201 @@
+// Logging module: tracing output to stdout, a rotating log file and the in-app log viewer
+
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber, info, warn};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::layer::{self, Layer};
+use tracing_subscriber::{EnvFilter, fmt, prelude::*};
+
+/// Filter used when RUST_LOG isn't set. Chart calibration output is opt-in with
+/// `RUST_LOG=info,chart_calibration=debug`.
+const DEFAULT_FILTER: &str = "info";
+
+/// Daily log files kept before the oldest is deleted
+const MAX_LOG_FILES: usize = 7;
+
+/// Events kept in memory for the log viewer (oldest are dropped)
+const MAX_LOG_ENTRIES: usize = 2000;
+
+/// Most rows the log viewer renders at once
+pub const MAX_VISIBLE_LOG_ENTRIES: usize = 500;
+
+/// Levels offered by the log viewer filter, most severe first
+pub const LOG_LEVELS: [Level; 4] = [Level::ERROR, Level::WARN, Level::INFO, Level::DEBUG];
+
+static LOG_BUFFER: Mutex<VecDeque<LogEntry>> = Mutex::new(VecDeque::new());
+
+#[derive(Clone)]
+pub struct LogEntry {
+    pub timestamp: DateTime<Local>,
+    pub level: Level,
+    // Event target, normally the module path (e.g. `alpaca_markets_terminal::stream`)
+    pub target: &'static str,
+    pub message: String,
+}
+
+pub fn level_label(level: Level) -> &'static str {
+    match level {
+        Level::ERROR => "Error",
+        Level::WARN => "Warn",
+        Level::INFO => "Info",
+        Level::DEBUG => "Debug",
+        Level::TRACE => "Trace",
+    }
+}
+
+/// Short module name for a target (crate prefix stripped, the crate root shown as "main")
+pub fn module_label(target: &'static str) -> &'static str {
+    let crate_name = env!("CARGO_CRATE_NAME");
+    if target == crate_name {
+        return "main";
+    }
+    target
+        .strip_prefix(crate_name)
+        .and_then(|rest| rest.strip_prefix("::"))
+        .unwrap_or(target)
+}
+
+/// Log viewer filter: minimum severity and an optional module
+pub struct LogFilter {
+    pub level: Level,
+    pub module: Option<&'static str>,
+}
+
+impl LogFilter {
+    pub fn new() -> Self {
+        Self {
+            level: Level::INFO,
+            module: None,
+        }
+    }
+
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        // More verbose levels compare greater (ERROR < WARN < INFO < DEBUG)
+        entry.level <= self.level
+            && self
+                .module
+                .is_none_or(|module| module_label(entry.target) == module)
+    }
+}
+
+/// Buffered events matching the filter, newest first
+pub fn recent_entries(filter: &LogFilter) -> Vec<LogEntry> {
+    let Ok(buffer) = LOG_BUFFER.lock() else {
+        return Vec::new();
+    };
+    buffer
+        .iter()
+        .rev()
+        .filter(|entry| filter.matches(entry))
+        .take(MAX_VISIBLE_LOG_ENTRIES)
+        .cloned()
+        .collect()
+}
+
+/// Distinct module labels seen in the buffer, sorted
+pub fn logged_modules() -> Vec<&'static str> {
+    let Ok(buffer) = LOG_BUFFER.lock() else {
+        return Vec::new();
+    };
+    let mut modules: Vec<&'static str> = buffer
+        .iter()
+        .map(|entry| module_label(entry.target))
+        .collect();
+    modules.sort_unstable();
+    modules.dedup();
+    modules
+}
+
+pub fn clear_entries() {
+    if let Ok(mut buffer) = LOG_BUFFER.lock() {
+        buffer.clear();
+    }
+}
+
+/// Formats an event's message followed by any extra fields as `key=value`
+struct MessageVisitor<'a>(&'a mut String);
+
+impl Visit for MessageVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.0, "{:?}", value);
+        } else {
+            let _ = write!(self.0, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// Layer copying events into the in-memory buffer read by the log viewer
+struct BufferLayer;
+
+impl<S: Subscriber> Layer<S> for BufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: layer::Context<'_, S>) {
+        let mut message = String::new();
+        event.record(&mut MessageVisitor(&mut message));
+
+        let metadata = event.metadata();
+        if let Ok(mut buffer) = LOG_BUFFER.lock() {
+            buffer.push_back(LogEntry {
+                timestamp: Local::now(),
+                level: *metadata.level(),
+                target: metadata.target(),
+                message,
+            });
+            if buffer.len() > MAX_LOG_ENTRIES {
+                buffer.pop_front();
+            }
+        }
+    }
+}
+
+/// Directory holding the log files (`<config dir>/alpaca_terminal/logs`)
+pub fn log_dir() -> Option<PathBuf> {
+    crate::settings::config_dir().map(|dir| dir.join("logs"))
+}
+
+/// Install the global subscriber. The returned guard flushes the file writer on drop,
+/// so keep it alive until the app exits.
+pub fn init() -> Option<WorkerGuard> {
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+
+    let appender = log_dir().map(|dir| {
+        RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix("terminal")
+            .filename_suffix("log")
+            .max_log_files(MAX_LOG_FILES)
+            .build(dir)
+    });
+
+    let (file_layer, guard, file_error) = match appender {
+        Some(Ok(appender)) => {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            let layer = fmt::layer().with_ansi(false).with_writer(writer);
+            (Some(layer), Some(guard), None)
+        }
+        Some(Err(e)) => (None, None, Some(e)),
+        None => (None, None, None),
+    };
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer())
+        .with(file_layer)
+        .with(BufferLayer)
+        .init();
+
+    match (file_error, log_dir()) {
+        (Some(e), _) => warn!("⚠️  Failed to open log file, logging to stdout only: {}", e),
+        (None, Some(dir)) if guard.is_some() => info!("📝 Logging to {}", dir.display()),
+        _ => {}
+    }
+
+    guard
+}
@@ -1,23 +1,89 @@
 use alpaca_markets::{
-    Adjustment, AlpacaConfig, Bar, MarketDataClient, Sort,
+    Adjustment, Bar, MarketDataClient, Sort,
     models::{OrderSide, OrderTimeInForce, OrderType},
 };
 use chrono::{Duration, Utc};
 use gpui::{
-    App, Application, Context, ElementId, FocusHandle, FontWeight, IntoElement, Render, Window,
-    WindowOptions, actions, div, prelude::*, px, rgb,
+    AnyElement, App, Application, Bounds, ClipboardItem, Context, Div, ElementId, FocusHandle,
+    FontWeight, IntoElement, Render, ScrollHandle, Window, WindowBounds, WindowOptions, div, point,
+    prelude::*, px, rgb, size,
 };
+use tracing::{debug, error, info, warn};
 
 mod account;
+mod account_config;
+mod alerts;
+mod api;
+mod asset;
+mod auto_exit;
+mod automation;
+mod backtest;
+mod bar_cache;
 mod chart;
+mod chart_window;
+mod clock;
+mod columns;
+mod config;
+mod corporate_actions;
+mod credentials;
+mod depth;
+mod drawings;
+mod error;
+mod export;
+mod exposure;
+mod focus;
+mod heatmap;
+mod hotkeys;
+mod indicators;
+mod key_stats;
+mod keymap;
+mod layout;
+mod logging;
+mod news;
+mod notifications;
+mod numbers;
+mod options;
+mod order_guard;
+mod order_window;
+mod overlays;
+mod performance;
+mod persist;
+mod pnl;
+mod rate_limit;
+mod recurring;
+mod risk;
+mod scalp;
+mod schedule;
+mod screener;
+mod scripting;
+mod service;
+mod settings;
+mod simulator;
+mod snapshot;
+mod sound;
 mod stream;
+mod stream_health;
+mod tax;
+mod text_input;
+mod toast;
+mod universe;
+mod workspace;
 
 use account::Account;
 use chart::Chart;
+use columns::{OrderColumn, PositionColumn};
 use stream::{StreamManager, StreamUpdate};
+use text_input::TextInput;
 use tokio::sync::mpsc;
 
-actions!(app, [Quit, RefreshData]);
+/// Suffix of the main window's title
+const APP_TITLE: &str = "Alpaca Terminal";
+
+/// Max height of the footer tables before they scroll
+const FOOTER_TABLE_MAX_HEIGHT: f32 = 220.0;
+
+/// Most bars fetched to fill a stream gap (a long outage on 1Min can exceed this)
+const BACKFILL_LIMIT: u32 = 10_000;
 
 struct TradingTerminal {
     // Chart state
@@ -25,2762 +91,17114 @@ struct TradingTerminal {
     focus_handle: FocusHandle,
     // Account state
     account: Account,
-    // WebSocket stream
+    // Scroll state shared by the footer tables (only one tab is visible at a time)
+    footer_scroll: ScrollHandle,
+    // Asset info for the traded symbol
+    asset: Option<asset::AssetInfo>,
+    // Latest trade, quote and daily bars for the charted symbol (header quote)
+    snapshot: Option<snapshot::SymbolSnapshot>,
+    // 52-week range and average volume for the charted symbol
+    key_stats: Option<key_stats::KeyStats>,
+    // Main window, and the title last set on it
+    window_handle: gpui::AnyWindowHandle,
+    window_title: String,
+    asset_loading: bool,
+    // Market clock (open/closed and next session transition)
+    market_clock: Option<clock::MarketClock>,
+    clock_loading: bool,
+    // The ticker retries failed clock fetches every second, so only the first failure is shown
+    clock_failed: bool,
+    // Upcoming holidays / early closes (loaded when the calendar panel is first opened)
+    calendar_events: Vec<clock::CalendarEvent>,
+    calendar_loading: bool,
+    // Recurring buys wait for the calendar; while it fails only the first failure is shown
+    calendar_loaded: bool,
+    calendar_failed: bool,
+    calendar_open: bool,
+    // Account configuration (trading restrictions), edited from the Account tab
+    account_config: Option<account_config::AccountConfig>,
+    account_config_saving: bool,
+    account_config_message: Option<String>,
+    // Persisted UI preferences (table column layouts)
+    settings: settings::Settings,
+    // Hand-edited config.toml and its last seen modification time (for live reload)
+    config: config::Config,
+    config_modified: Option<std::time::SystemTime>,
+    // API key setup dialog; data is only fetched once credentials exist
+    setup: credentials::SetupDialog,
+    data_loaded: bool,
+    // Saved account profiles and the header menu for switching between them
+    profiles: credentials::ProfileStore,
+    profile_menu_open: bool,
+    // Named layouts menu in the header
+    workspaces: workspace::WorkspaceMenu,
+    column_drag: Option<columns::ColumnDrag>,
+    recent_symbols_open: bool,
+    // Latest close/trade seen on the market data stream, per symbol (feeds the favorites bar)
+    last_prices: std::collections::HashMap<String, String>,
+    // A positions refetch is queued after a fill
+    positions_refresh_scheduled: bool,
+    // Which symbols the chart, chart windows, watchlist and alerts need streamed;
+    // bumping the generation retires the streams' updates after a restart
+    subscriptions: stream::SubscriptionManager,
+    market_data_generation: u64,
+    // Position in the recent symbols list while cycling with up/down
+    recent_symbol_index: Option<usize>,
+    columns_menu_open: Option<columns::ColumnTable>,
+    // Multi-leg option strategy builder (sidebar)
+    strategy: options::StrategyBuilder,
+    // Headlines for the charted symbol or the watchlist (News tab)
+    news: news::News,
+    // Day change across a symbol universe (Heatmap tab)
+    heatmap: heatmap::Heatmap,
+    // Top gainers, losers, gaps and relative volume (Movers tab)
+    screener: screener::Screener,
+    // Exposure and SPY betas for the positions (Analytics tab)
+    analytics: exposure::Analytics,
+    // Period shown in the Performance tab
+    performance: performance::Performance,
+    // Backtest tab: strategy preset, bar count and the last report
+    backtest: backtest::Backtest,
+    // Level / module filter for the Logs tab
+    log_filter: logging::LogFilter,
+    // Extra chart windows, each with its own chart and order ticket
+    chart_windows: Vec<chart_window::ChartSlot>,
+    next_chart_window_id: usize,
+    // Order form popped out into its own window
+    order_window: Option<gpui::WindowHandle<order_window::OrderEntryWindow>>,
+    // Typed confirmation for large live-account orders
+    order_guard: order_guard::OrderGuard,
+    // Halts order entry once today's loss reaches the configured limit
+    loss_limit: risk::LossLimit,
+    // Orders held locally until their submit time
+    scheduler: schedule::Scheduler,
+    // Background worker for REST calls (bars, account, orders)
+    service: service::DataService,
+    // One-key buy/sell panel and pending confirmation
+    hotkeys: hotkeys::Hotkeys,
+    // Text input with keyboard focus (Tab cycles, Escape clears)
+    focus: focus::FocusManager,
+    // Splits / dividends for the charted symbol (markers on daily charts)
+    corporate_actions: Vec<corporate_actions::CorporateAction>,
+    corporate_actions_symbol: Option<String>,
+    // Bar index of the corporate action marker under the mouse
+    hovered_corporate_action: Option<usize>,
+    // Fills, order events and stream errors (notification center)
+    notifications: notifications::NotificationCenter,
+    // Websocket telemetry behind the connection status popover
+    stream_health: stream_health::StreamHealth,
+    // Whether the window has focus (desktop notifications are sent only when it doesn't)
+    window_active: bool,
+    // Breakpoint for the current window width; below wide the sidebar becomes a drawer
+    layout: layout::LayoutMode,
+    sidebar_open: bool,
+    // Transient messages in the bottom-right corner (order feedback and lifecycle events)
+    toasts: toast::Toasts,
+    // Price alerts (persisted) and the sidebar form for adding them
+    alerts: alerts::AlertStore,
+    // Anchored VWAPs placed on charts (persisted)
+    drawings: drawings::DrawingStore,
+    alert_form: alerts::AlertForm,
+    // Recurring notional buys (persisted), run by the scheduler, and the form for adding them
+    recurring: recurring::RecurringStore,
+    recurring_form: recurring::RecurringForm,
+    // Price-cross order rules (persisted) with their master switch and log, and the rule form
+    automation: automation::AutomationStore,
+    automation_form: automation::AutomationForm,
+    // Manual entries waiting to fill and the OCO exits attached to them
+    auto_exits: auto_exit::AutoExits,
+    // Reverse entry waiting for the scalp pad's closing order to flatten the position
+    scalp: scalp::Scalp,
+    // Streamed top-of-book sizes for the depth panel beside the main chart
+    depth: depth::DepthBook,
+    // Rhai strategy scripts from the scripts folder and their output
+    scripts: scripting::Scripts,
+    // Forward-test broker (persisted) that takes orders instead of Alpaca while enabled
+    simulator: simulator::SimBroker,
+    // WebSocket stream; updates from streams started before the current generation are dropped
+    trading_stream_generation: u64,
     stream_connected: bool,
     stream_status: String,
 }
 
 impl TradingTerminal {
-    fn new(cx: &mut Context<Self>) -> Self {
+    fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        cx.observe_window_activation(window, |terminal, window, _cx| {
+            terminal.window_active = window.is_window_active();
+        })
+        .detach();
+
+        // Track the window size so it can be restored next time, and switch layouts at
+        // the breakpoints
+        cx.observe_window_bounds(window, |terminal, window, cx| {
+            let size = window.bounds().size;
+            terminal.settings.session.window_size = Some((size.width.into(), size.height.into()));
+
+            let layout = layout::LayoutMode::for_width(window.viewport_size().width.into());
+            if layout != terminal.layout {
+                terminal.layout = layout;
+                terminal.sidebar_open = false;
+                cx.notify();
+            }
+        })
+        .detach();
+
+        // Save the session on quit and when the window is closed
+        cx.on_app_quit(|terminal, _cx| {
+            terminal.save_session();
+            async {}
+        })
+        .detach();
+        let terminal_handle = cx.entity().downgrade();
+        window.on_window_should_close(cx, move |_window, cx| {
+            let _ = terminal_handle.update(cx, |terminal, _cx| terminal.save_session());
+            true
+        });
+
+        let config = config::Config::load();
+        let mut settings = settings::Settings::load();
+        config.apply_overrides(&mut settings);
+        let heatmap = heatmap::Heatmap::new(config.heatmap.universe, config.heatmap.size_by);
+        let screener = screener::Screener::new(config.screener.universe);
+        let simulator = simulator::SimBroker::load(config.simulator.starting_cash);
+
+        // The last session wins over the configured startup defaults
+        let session = settings.session.clone();
+        let timeframe = session
+            .timeframe
+            .filter(|timeframe| chart::TIMEFRAMES.contains(&timeframe.as_str()))
+            .unwrap_or(config.chart.timeframe.clone());
+        let mut account = Account::new();
+        if let Some(tab) = session.footer_tab {
+            account.active_footer_tab = tab;
+        }
+
+        // REST calls go through the data service; its responses are applied as they arrive
+        let (service_sender, mut service_receiver) =
+            tokio::sync::mpsc::unbounded_channel::<service::ServiceResponse>();
+        cx.spawn(async move |this, cx| {
+            while let Some(response) = service_receiver.recv().await {
+                if this
+                    .update(cx, |terminal, cx| {
+                        terminal.handle_service_response(response, cx)
+                    })
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        })
+        .detach();
+        let service = service::DataService::start(service_sender, config.retry.clone());
+
         let mut terminal = Self {
-            chart: Chart::new("AAPL".to_string(), "1Day".to_string()),
+            chart: Chart::new(
+                session.symbol.unwrap_or(config.chart.symbol.clone()),
+                timeframe,
+                session
+                    .bar_limit
+                    .filter(|limit| *limit > 0)
+                    .unwrap_or(config.chart.bar_limit),
+            ),
             focus_handle: cx.focus_handle(),
-            account: Account::new(),
+            account,
+            footer_scroll: ScrollHandle::new(),
+            asset: None,
+            snapshot: None,
+            key_stats: None,
+            window_handle: window.window_handle(),
+            window_title: APP_TITLE.to_string(),
+            asset_loading: false,
+            market_clock: None,
+            clock_loading: false,
+            clock_failed: false,
+            calendar_events: Vec::new(),
+            calendar_loading: false,
+            calendar_loaded: false,
+            calendar_failed: false,
+            calendar_open: false,
+            account_config: None,
+            account_config_saving: false,
+            account_config_message: None,
+            settings,
+            config,
+            config_modified: config::modified_at(),
+            setup: credentials::SetupDialog::new(),
+            data_loaded: false,
+            profiles: credentials::ProfileStore::load(),
+            profile_menu_open: false,
+            workspaces: workspace::WorkspaceMenu::new(),
+            column_drag: None,
+            recent_symbols_open: false,
+            last_prices: std::collections::HashMap::new(),
+            positions_refresh_scheduled: false,
+            subscriptions: stream::SubscriptionManager::new(),
+            market_data_generation: 0,
+            recent_symbol_index: None,
+            columns_menu_open: None,
+            strategy: options::StrategyBuilder::new(),
+            news: news::News::new(),
+            heatmap,
+            screener,
+            analytics: exposure::Analytics::new(),
+            performance: performance::Performance::new(),
+            backtest: backtest::Backtest::new(),
+            log_filter: logging::LogFilter::new(),
+            hotkeys: hotkeys::Hotkeys::new(),
+            order_guard: order_guard::OrderGuard::new(),
+            loss_limit: risk::LossLimit::new(),
+            scheduler: schedule::Scheduler::new(),
+            service,
+            order_window: None,
+            chart_windows: Vec::new(),
+            next_chart_window_id: 0,
+            focus: focus::FocusManager::new(),
+            corporate_actions: Vec::new(),
+            corporate_actions_symbol: None,
+            hovered_corporate_action: None,
+            notifications: notifications::NotificationCenter::new(),
+            stream_health: stream_health::StreamHealth::new(),
+            window_active: true,
+            layout: layout::LayoutMode::for_width(window.viewport_size().width.into()),
+            sidebar_open: false,
+            toasts: toast::Toasts::new(),
+            alerts: alerts::AlertStore::load(),
+            drawings: drawings::DrawingStore::load(),
+            alert_form: alerts::AlertForm::new(),
+            recurring: recurring::RecurringStore::load(),
+            recurring_form: recurring::RecurringForm::new(),
+            automation: automation::AutomationStore::load(),
+            automation_form: automation::AutomationForm::new(),
+            auto_exits: auto_exit::AutoExits::default(),
+            scalp: scalp::Scalp::default(),
+            depth: depth::DepthBook::default(),
+            scripts: scripting::Scripts::new(),
+            simulator,
 
+            trading_stream_generation: 0,
             stream_connected: false,
             stream_status: "Disconnected".to_string(),
         };
 
-        // Fetch data on startup
-        terminal.fetch_bars(cx);
-        terminal.fetch_account(cx);
-        terminal.fetch_positions(cx);
-        terminal.start_websocket_stream(cx);
-        terminal.start_market_data_stream(cx);
-        terminal.fetch_orders(cx);
+        terminal.start_clock_ticker(cx);
+        terminal.start_config_watcher(cx);
+
+        // Without credentials, ask for them before fetching anything
+        if credentials::has_credentials() {
+            terminal.connect(cx);
+        } else {
+            info!("🔑 No API credentials found, showing setup dialog");
+            terminal.setup.show(None);
+            terminal.set_input_focus(Some(focus::InputField::ProfileName));
+        }
+
+        // Focus the terminal so keymap shortcuts work before anything is clicked
+        window.focus(&terminal.focus_handle);
         terminal
     }
 
-    fn handle_input(&mut self, text: &str, cx: &mut Context<Self>) {
-        if !self.chart.input_focused {
-            return;
+    /// Fetch data and start the streams (on startup, or once keys are entered)
+    fn connect(&mut self, cx: &mut Context<Self>) {
+        self.data_loaded = true;
+        self.load_account_data(cx);
+        self.check_alerts_since_last_session(cx);
+    }
+
+    /// Fetch everything tied to the active credentials and (re)start the streams
+    fn load_account_data(&mut self, cx: &mut Context<Self>) {
+        self.fetch_bars(cx);
+        self.fetch_snapshot();
+        self.fetch_key_stats(cx);
+        self.fetch_asset(cx);
+        self.fetch_corporate_actions();
+        self.fetch_clock();
+        // Recurring buys skip market holidays, so they need the calendar
+        if !self.recurring.plans.is_empty() {
+            self.fetch_calendar(cx);
         }
+        self.fetch_account(cx);
+        self.fetch_account_config();
+        self.fetch_positions(cx);
+        self.fetch_activities(cx);
+        self.start_websocket_stream(cx);
+        self.start_market_data_stream(cx);
+        self.fetch_orders(cx);
+        self.fetch_news(cx);
+        // History is fetched lazily, so a restored History tab needs it now
+        if self.account.active_footer_tab == account::FooterTab::History {
+            self.fetch_order_history(cx);
+        }
+    }
 
-        self.chart.symbol_input.push_str(text);
-        cx.notify();
+    /// Record where the terminal was left and write it to the settings file
+    fn save_session(&mut self) {
+        let session = &mut self.settings.session;
+        session.symbol = Some(self.chart.symbol.clone());
+        session.timeframe = Some(self.chart.timeframe.clone());
+        session.bar_limit = self.chart.bar_limit.text().trim().parse().ok();
+        session.footer_tab = Some(self.account.active_footer_tab.clone());
+        self.settings.save();
+        info!("💾 Session saved");
     }
 
-    fn handle_backspace(&mut self, cx: &mut Context<Self>) {
-        if !self.chart.input_focused {
+    /// Make a saved profile active: drop the previous account's data, restart the
+    /// streams with the new keys and refetch
+    fn switch_profile(&mut self, name: String, cx: &mut Context<Self>) {
+        self.profile_menu_open = false;
+        if self.profiles.active_name() == Some(name.as_str()) {
+            cx.notify();
             return;
         }
 
-        self.chart.symbol_input.pop();
-        cx.notify();
-    }
-
-    fn submit_symbol(&mut self, cx: &mut Context<Self>) {
-        if !self.chart.symbol_input.is_empty() {
-            self.chart.symbol = self.chart.symbol_input.clone().to_uppercase();
-            self.chart.input_focused = false;
-            self.fetch_bars(cx);
+        self.profiles.active = Some(name.clone());
+        if let Err(e) = self.profiles.save() {
+            self.show_failure("Failed to save profiles", &e, None, cx);
         }
+        self.reconnect(cx);
+        info!("🔁 Switched to profile {}", name);
+        self.show_toast(
+            toast::ToastLevel::Info,
+            format!("Switched to profile {}", name),
+            cx,
+        );
     }
 
-    fn fetch_account(&mut self, cx: &mut Context<Self>) {
-        self.account.account_loading = true;
-        cx.notify();
+    /// Reload everything for the active profile (streams from the old keys are retired)
+    fn reconnect(&mut self, cx: &mut Context<Self>) {
+        self.account.clear_data();
+        self.account_config = None;
+        self.hotkeys.pending = None;
+        // Force the news stream to restart even though its symbols are unchanged
+        self.news.stream_symbols.clear();
 
-        cx.spawn(async move |this, cx| {
-            let result = cx
-                .background_executor()
-                .spawn(async move { account::fetch_account_sync() })
-                .await;
+        if self.data_loaded {
+            self.load_account_data(cx);
+        } else {
+            self.connect(cx);
+        }
+    }
 
-            let _ = this.update(cx, |terminal, cx| {
-                match result {
-                    Ok(account_data) => {
-                        terminal.account.account_number = Some(account_data.0);
-                        terminal.account.account_status = Some(account_data.1);
-                        terminal.account.buying_power = Some(account_data.2);
-                        terminal.account.cash = Some(account_data.3);
-                        terminal.account.portfolio_value = Some(account_data.4);
-                        terminal.account.equity = Some(account_data.5);
-                        println!("✓ Successfully loaded account information");
-                    }
-                    Err(error) => {
-                        eprintln!("✗ Error fetching account: {}", error);
-                        terminal.account.account_status = Some("Error".to_string());
-                    }
-                }
-                terminal.account.account_loading = false;
+    /// Check the keys in the setup dialog against the trading API
+    fn test_credentials(&mut self, cx: &mut Context<Self>) {
+        let profile = match self.setup.profile() {
+            Ok(profile) => profile,
+            Err(e) => {
+                self.setup.status = Some(Err(e));
                 cx.notify();
-            });
-        })
-        .detach();
-    }
+                return;
+            }
+        };
 
-    fn fetch_positions(&mut self, cx: &mut Context<Self>) {
-        self.account.positions_loading = true;
+        self.setup.testing = true;
+        self.setup.status = None;
         cx.notify();
 
         cx.spawn(async move |this, cx| {
             let result = cx
                 .background_executor()
-                .spawn(async move { account::fetch_positions_sync() })
+                .spawn(async move { credentials::test_connection_sync(profile) })
                 .await;
 
             let _ = this.update(cx, |terminal, cx| {
-                match result {
-                    Ok(positions) => {
-                        terminal.account.positions = positions;
-                        println!(
-                            "✓ Successfully loaded {} positions",
-                            terminal.account.positions.len()
-                        );
-                    }
-                    Err(error) => {
-                        eprintln!("✗ Error fetching positions: {}", error);
-                        terminal.account.positions.clear();
-                    }
+                match &result {
+                    Ok(account_number) => info!("✅ Credentials valid for {}", account_number),
+                    Err(e) => warn!("⚠️  Credential test failed: {}", e),
                 }
-                terminal.account.positions_loading = false;
+                terminal.setup.testing = false;
+                terminal.setup.status = Some(result);
                 cx.notify();
             });
         })
         .detach();
     }
 
-    fn fetch_orders(&mut self, cx: &mut Context<Self>) {
-        self.account.orders_loading = true;
-        cx.notify();
+    /// Save the profile from the setup dialog, make it active and load data with it
+    fn save_profile(&mut self, cx: &mut Context<Self>) {
+        let profile = match self.setup.profile() {
+            Ok(profile) => profile,
+            Err(e) => {
+                self.setup.status = Some(Err(e));
+                cx.notify();
+                return;
+            }
+        };
+        let name = profile.name.clone();
 
-        cx.spawn(async move |this, cx| {
-            let result = cx
-                .background_executor()
-                .spawn(async move { account::fetch_orders_sync() })
-                .await;
+        self.profiles.upsert(profile);
+        if let Err(e) = self.profiles.save() {
+            self.setup.status = Some(Err(e));
+            cx.notify();
+            return;
+        }
 
-            let _ = this.update(cx, |terminal, cx| {
-                match result {
-                    Ok(mut orders) => {
-                        // Filter out terminal state orders (filled, canceled, expired, rejected)
-                        orders.retain(|order| {
-                            !matches!(
-                                order.status.as_str(),
-                                "filled" | "canceled" | "expired" | "rejected"
-                            )
-                        });
-                        terminal.account.orders = orders;
-                        println!(
-                            "✓ Successfully loaded {} active orders",
-                            terminal.account.orders.len()
-                        );
-                    }
-                    Err(error) => {
-                        eprintln!("✗ Error fetching orders: {}", error);
-                        terminal.account.orders.clear();
-                    }
-                }
-                terminal.account.orders_loading = false;
-                cx.notify();
-            });
-        })
-        .detach();
+        self.setup.open = false;
+        self.set_input_focus(None);
+        self.reconnect(cx);
+        self.show_toast(
+            toast::ToastLevel::Success,
+            format!("Profile {} saved", name),
+            cx,
+        );
     }
 
-    fn cancel_order(&mut self, order_id: String, cx: &mut Context<Self>) {
-        cx.spawn(async move |this, cx| {
-            let result = cx
-                .background_executor()
-                .spawn(async move { account::cancel_order_sync(order_id) })
-                .await;
+    /// Delete a saved profile; the next one becomes active
+    fn remove_profile(&mut self, name: String, cx: &mut Context<Self>) {
+        let was_active = self.profiles.active_name() == Some(name.as_str());
+        self.profiles.remove(&name);
+        if let Err(e) = self.profiles.save() {
+            self.show_failure("Failed to save profiles", &e, None, cx);
+        }
 
-            let _ = this.update(cx, |_terminal, cx| {
-                match result {
-                    Ok(_) => {
-                        println!("✓ Order canceled successfully");
-                        // WebSocket will handle the order update automatically
-                        cx.notify();
-                    }
-                    Err(error) => {
-                        eprintln!("✗ Error canceling order: {}", error);
-                    }
-                }
-            });
-        })
-        .detach();
+        if !was_active {
+            cx.notify();
+        } else if credentials::has_credentials() {
+            self.reconnect(cx);
+        } else {
+            self.profile_menu_open = false;
+            self.setup.show(None);
+            self.set_input_focus(Some(focus::InputField::ProfileName));
+            cx.notify();
+        }
     }
 
-    fn close_position(&mut self, symbol: String, cx: &mut Context<Self>) {
-        cx.spawn(async move |this, cx| {
-            let result = cx
-                .background_executor()
-                .spawn(async move { account::close_position_sync(symbol) })
-                .await;
+    /// Save the current layout under the name typed in the workspace menu
+    fn save_workspace(&mut self, cx: &mut Context<Self>) {
+        let name = self.workspaces.name.text().trim().to_string();
+        if name.is_empty() {
+            self.show_toast(
+                toast::ToastLevel::Warning,
+                "Enter a workspace name".to_string(),
+                cx,
+            );
+            return;
+        }
 
-            let _ = this.update(cx, |terminal, cx| {
-                match result {
-                    Ok(_) => {
-                        println!("✓ Position closed successfully");
-                        // Refresh positions list (WebSocket handles order updates)
-                        terminal.fetch_positions(cx);
-                    }
-                    Err(error) => {
-                        eprintln!("✗ Error closing position: {}", error);
-                    }
-                }
-            });
-        })
-        .detach();
+        self.settings.upsert_workspace(workspace::Workspace {
+            name: name.clone(),
+            symbol: self.chart.symbol.clone(),
+            timeframe: self.chart.timeframe.clone(),
+            bar_limit: self
+                .chart
+                .bar_limit
+                .text()
+                .trim()
+                .parse()
+                .unwrap_or(self.config.chart.bar_limit),
+            footer_tab: self.account.active_footer_tab.clone(),
+            favorite_symbols: self.settings.favorite_symbols.clone(),
+            positions_columns: self.settings.positions_columns.clone(),
+            orders_columns: self.settings.orders_columns.clone(),
+        });
+        self.settings.save();
+        self.workspaces.name.clear();
+        self.set_input_focus(None);
+        info!("🗂️  Saved workspace {}", name);
+        self.show_toast(
+            toast::ToastLevel::Success,
+            format!("Workspace {} saved", name),
+            cx,
+        );
     }
 
-    fn submit_order(&mut self, cx: &mut Context<Self>) {
-        // Validate inputs
-        if self.account.order_quantity.trim().is_empty() {
-            self.account.order_message = Some("Error: Quantity cannot be empty".to_string());
+    /// Restore a saved layout and load its symbol
+    fn switch_workspace(&mut self, name: String, cx: &mut Context<Self>) {
+        self.workspaces.open = false;
+        let Some(workspace) = self
+            .settings
+            .workspaces
+            .iter()
+            .find(|w| w.name == name)
+            .cloned()
+        else {
             cx.notify();
             return;
-        }
-
-        let qty = match self.account.order_quantity.parse::<f64>() {
-            Ok(q) if q > 0.0 => q,
-            _ => {
-                self.account.order_message = Some("Error: Invalid quantity".to_string());
-                cx.notify();
-                return;
-            }
         };
 
-        if matches!(self.account.order_type, OrderType::Limit)
-            && self.account.order_limit_price.trim().is_empty()
-        {
-            self.account.order_message =
-                Some("Error: Limit price required for limit orders".to_string());
-            cx.notify();
+        self.settings.active_workspace = Some(name.clone());
+        self.settings.favorite_symbols = workspace.favorite_symbols;
+        self.settings.positions_columns = workspace.positions_columns;
+        self.settings.orders_columns = workspace.orders_columns;
+        self.chart.timeframe = workspace.timeframe;
+        self.chart
+            .bar_limit
+            .set_text(workspace.bar_limit.to_string());
+        self.select_footer_tab(workspace.footer_tab, cx);
+        // Loads the symbol and saves the settings
+        self.chart.symbol_input.set_text(workspace.symbol);
+        self.submit_symbol(cx);
+
+        info!("🗂️  Switched to workspace {}", name);
+        self.show_toast(
+            toast::ToastLevel::Info,
+            format!("Switched to workspace {}", name),
+            cx,
+        );
+    }
+
+    fn remove_workspace(&mut self, name: String, cx: &mut Context<Self>) {
+        self.settings.remove_workspace(&name);
+        self.settings.save();
+        cx.notify();
+    }
+
+    /// Keystrokes for the focused input and pending confirmations. The main window and
+    /// the popped-out order window both route their key events here.
+    fn handle_key_down(&mut self, event: &gpui::KeyDownEvent, cx: &mut Context<Self>) {
+        // Confirm or cancel a pending hotkey order
+        if let Some(side) = self.hotkeys.pending {
+            match event.keystroke.key.as_str() {
+                "enter" => self.submit_hotkey_order(side, cx),
+                "escape" => {
+                    self.hotkeys.pending = None;
+                    cx.notify();
+                }
+                _ => {}
+            }
             return;
         }
 
-        let limit_price = if matches!(self.account.order_type, OrderType::Limit) {
-            match self.account.order_limit_price.parse::<f64>() {
-                Ok(p) if p > 0.0 => Some(p),
-                _ => {
-                    self.account.order_message = Some("Error: Invalid limit price".to_string());
+        // Confirm or cancel trading past the daily loss limit
+        if self.loss_limit.confirming {
+            match event.keystroke.key.as_str() {
+                "enter" => self.override_loss_limit(cx),
+                "escape" => {
+                    self.loss_limit.confirming = false;
                     cx.notify();
-                    return;
                 }
+                _ => {}
             }
-        } else {
-            None
-        };
-
-        self.account.order_submitting = true;
-        self.account.order_message = None;
-        cx.notify();
-
-        let symbol = self.chart.symbol.clone();
-        let side = match self.account.order_side {
-            OrderSide::Buy => OrderSide::Buy,
-            OrderSide::Sell => OrderSide::Sell,
-        };
-        let order_type = match self.account.order_type {
-            OrderType::Market => OrderType::Market,
-            OrderType::Limit => OrderType::Limit,
-            _ => OrderType::Market,
-        };
-        let time_in_force = match self.account.order_time_in_force {
-            OrderTimeInForce::Day => OrderTimeInForce::Day,
-            OrderTimeInForce::Gtc => OrderTimeInForce::Gtc,
-            _ => OrderTimeInForce::Day,
-        };
-
-        cx.spawn(async move |this, cx| {
-            let result = cx
-                .background_executor()
-                .spawn(async move {
-                    account::submit_order_sync(
-                        symbol,
-                        side,
-                        order_type,
-                        qty,
-                        limit_price,
-                        time_in_force,
-                    )
-                })
-                .await;
+            return;
+        }
 
-            let _ = this.update(cx, |terminal, cx| {
-                match result {
-                    Ok(order_id) => {
-                        terminal.account.order_message =
-                            Some(format!("✓ Order submitted successfully! ID: {}", order_id));
-                        terminal.account.order_quantity = "".to_string();
-                        terminal.account.order_limit_price = "".to_string();
-                        // WebSocket will handle the order update automatically
-                    }
-                    Err(error) => {
-                        terminal.account.order_message = Some(format!("✗ Error: {}", error));
-                    }
-                }
-                terminal.account.order_submitting = false;
-                cx.notify();
-            });
-        })
-        .detach();
-    }
+        let key = event.keystroke.key.as_str();
 
-    fn start_websocket_stream(&mut self, cx: &mut Context<Self>) {
-        println!("🚀 Starting WebSocket stream connection...");
+        // Tab / Shift+Tab cycle through the visible inputs
+        if key == "tab" && !event.keystroke.modifiers.control {
+            let order = self.tab_order();
+            let next = self.focus.next(&order, !event.keystroke.modifiers.shift);
+            self.set_input_focus(next);
+            cx.notify();
+            return;
+        }
 
-        self.stream_status = "Connecting...".to_string();
-        cx.notify();
+        // Escape closes the order drawer when no input has focus
+        if key == "escape" && self.focus.focused().is_none() && self.sidebar_open {
+            self.sidebar_open = false;
+            cx.notify();
+            return;
+        }
 
-        // Create a channel for receiving updates from the WebSocket
-        let (sender, mut receiver) = mpsc::unbounded_channel::<StreamUpdate>();
+        let Some(field) = self.focus.focused() else {
+            return;
+        };
 
-        // Start the WebSocket stream in a background task
-        StreamManager::start_stream(sender);
+        // Escape cancels a held live order
+        if key == "escape" && self.order_guard.pending.is_some() {
+            self.cancel_live_order(cx);
+            return;
+        }
 
-        // Spawn a task to listen for updates and apply them to the UI
-        cx.spawn(async move |this, cx| {
-            while let Some(update) = receiver.recv().await {
-                let _ = this.update(cx, |chart, cx| {
-                    chart.handle_stream_update(update, cx);
-                });
-            }
-        })
-        .detach();
-    }
+        // Escape leaves any input
+        if key == "escape" {
+            self.set_input_focus(None);
+            cx.notify();
+            return;
+        }
 
-    fn handle_stream_update(&mut self, update: StreamUpdate, cx: &mut Context<Self>) {
-        match update {
-            StreamUpdate::Connected => {
-                println!("✅ WebSocket connected!");
-                self.stream_connected = true;
-                self.stream_status = "Connected".to_string();
-                cx.notify();
-            }
-            StreamUpdate::Disconnected => {
-                println!("❌ WebSocket disconnected");
-                self.stream_connected = false;
-                self.stream_status = "Disconnected".to_string();
-                cx.notify();
-            }
-            StreamUpdate::TradeUpdate(order_update) => {
-                println!("📦 Received order update for: {}", order_update.symbol);
-                self.account.update_order_from_stream(order_update);
-                cx.notify();
+        match (field, key) {
+            (focus::InputField::Symbol, "enter") => self.submit_symbol(cx),
+            (focus::InputField::Symbol, "up") => self.cycle_recent_symbol(true, cx),
+            (focus::InputField::Symbol, "down") => self.cycle_recent_symbol(false, cx),
+            (focus::InputField::BarLimit, "enter") => self.fetch_bars(cx),
+            (focus::InputField::AlertPrice, "enter") => self.add_alert(cx),
+            (focus::InputField::RecurringAmount, "enter") => self.add_recurring_plan(cx),
+            (focus::InputField::Automation(_), "enter") => self.add_automation_rule(cx),
+            (focus::InputField::WorkspaceName, "enter") => self.save_workspace(cx),
+            (focus::InputField::ScreenFilter(screener::FilterField::Name), "enter") => {
+                self.save_screen(cx)
             }
-            StreamUpdate::AccountUpdate(account_info) => {
-                println!("💰 Received account update");
-                self.account.update_from_stream(account_info);
-                cx.notify();
+            (focus::InputField::ScreenFilter(_), "enter") => self.apply_screen_filter(cx),
+            (focus::InputField::ChartSymbol(id), "enter") => {
+                self.submit_chart_window_symbol(id, cx)
             }
-            StreamUpdate::Error(error) => {
-                eprintln!("❌ Stream error: {}", error);
-                self.stream_status = format!("Error: {}", error);
-                cx.notify();
+            (focus::InputField::ChartQuantity(id), "enter") => {
+                self.submit_chart_window_order(id, cx)
             }
-            StreamUpdate::MarketDataConnected => {
-                println!("✅ Market Data WebSocket connected!");
-                self.chart.market_data_connected = true;
+            (focus::InputField::LiveConfirmation, "enter") => self.confirm_live_order(cx),
+            (focus::InputField::ProfileName, "enter") => {
+                self.set_input_focus(Some(focus::InputField::ApiKeyId));
                 cx.notify();
             }
-            StreamUpdate::MarketDataDisconnected => {
-                println!("❌ Market Data WebSocket disconnected");
-                self.chart.market_data_connected = false;
+            (focus::InputField::ApiKeyId, "enter") => {
+                self.set_input_focus(Some(focus::InputField::ApiSecretKey));
                 cx.notify();
             }
-            StreamUpdate::BarUpdate(bar_update) => {
-                println!("📊 Received bar update for: {}", bar_update.symbol);
-                self.update_bars_from_stream(bar_update, cx);
+            (focus::InputField::ApiSecretKey, "enter") => self.save_profile(cx),
+            (_, "enter") => {
+                self.set_input_focus(None);
                 cx.notify();
             }
+            _ => {
+                if let Some(input) = self.text_input_mut(field) {
+                    let outcome = input.handle_key(&event.keystroke, cx);
+                    let mode = input.mode();
+                    match outcome {
+                        text_input::KeyOutcome::Handled => cx.notify(),
+                        text_input::KeyOutcome::PasteRejected => self.show_toast(
+                            toast::ToastLevel::Warning,
+                            format!("Clipboard doesn't contain a valid {}", mode.describe()),
+                            cx,
+                        ),
+                        text_input::KeyOutcome::Ignored => {}
+                    }
+                    return;
+                }
+
+                let Some(text) = self.input_text_mut(field) else {
+                    return;
+                };
+                if key == "backspace" {
+                    text.pop();
+                    cx.notify();
+                } else if let Some(key_char) = &event.keystroke.key_char {
+                    if key_char.chars().count() == 1 && key_char.chars().all(|c| field.accepts(c)) {
+                        if field.uppercases() {
+                            text.push_str(&key_char.to_uppercase());
+                        } else {
+                            text.push_str(key_char);
+                        }
+                        cx.notify();
+                    }
+                }
+            }
         }
     }
 
-    fn start_market_data_stream(&mut self, cx: &mut Context<Self>) {
-        println!("🚀 Starting Market Data WebSocket stream connection...");
+    /// Move keyboard focus to a text input (None leaves all inputs), running the
+    /// previous input's blur effects
+    fn set_input_focus(&mut self, field: Option<focus::InputField>) {
+        match self.focus.focused() {
+            Some(focus::InputField::Symbol) => self.recent_symbols_open = false,
+            // The hotkey size is a setting; persist it once editing ends
+            Some(focus::InputField::HotkeyQuantity)
+            | Some(focus::InputField::AutoExitTakeProfit)
+            | Some(focus::InputField::AutoExitStopLoss)
+            | Some(focus::InputField::ScalpSize)
+            | Some(focus::InputField::SuperTrendPeriod)
+            | Some(focus::InputField::SuperTrendMultiplier)
+            | Some(focus::InputField::SarStep)
+            | Some(focus::InputField::SarMax)
+            | Some(focus::InputField::KeltnerPeriod)
+            | Some(focus::InputField::KeltnerMultiplier)
+            | Some(focus::InputField::AdxPeriod)
+            | Some(focus::InputField::DonchianPeriod(chart::ChartId::Main)) => self.settings.save(),
+            _ => {}
+        }
+        self.focus.set(field);
+    }
 
-        // Create a channel for receiving updates from the WebSocket
-        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel::<stream::StreamUpdate>();
+    /// Inputs with full editing (cursor, selection, clipboard)
+    fn text_input_mut(&mut self, field: focus::InputField) -> Option<&mut TextInput> {
+        match field {
+            focus::InputField::Symbol => Some(&mut self.chart.symbol_input),
+            focus::InputField::BarLimit => Some(&mut self.chart.bar_limit),
+            focus::InputField::OrderQuantity => Some(&mut self.account.order_quantity),
+            focus::InputField::LimitPrice => Some(&mut self.account.order_limit_price),
+            focus::InputField::ProfileName => Some(&mut self.setup.name),
+            focus::InputField::ApiKeyId => Some(&mut self.setup.key_id),
+            focus::InputField::ApiSecretKey => Some(&mut self.setup.secret_key),
+            focus::InputField::LiveConfirmation => Some(&mut self.order_guard.input),
+            focus::InputField::WorkspaceName => Some(&mut self.workspaces.name),
+            focus::InputField::ChartSymbol(id) => self
+                .chart_slot_mut(id)
+                .map(|slot| &mut slot.chart.symbol_input),
+            focus::InputField::ChartQuantity(id) => {
+                self.chart_slot_mut(id).map(|slot| &mut slot.quantity)
+            }
+            _ => None,
+        }
+    }
 
-        // Get the current symbol to subscribe to
-        let symbol = self.chart.symbol.clone();
+    /// Text behind the remaining (type-and-backspace) inputs
+    fn input_text_mut(&mut self, field: focus::InputField) -> Option<&mut String> {
+        match field {
+            focus::InputField::PositionsFilter => Some(&mut self.account.positions_filter),
+            focus::InputField::OrdersSearch => Some(&mut self.account.orders_search),
+            focus::InputField::HistorySymbol => Some(&mut self.account.history_symbol_filter),
+            focus::InputField::HotkeyQuantity => Some(&mut self.settings.hotkey_quantity),
+            focus::InputField::AutoExitTakeProfit => {
+                Some(&mut self.settings.auto_exits.take_profit_percent)
+            }
+            focus::InputField::AutoExitStopLoss => {
+                Some(&mut self.settings.auto_exits.stop_loss_percent)
+            }
+            focus::InputField::ScalpSize => Some(&mut self.settings.scalp.size),
+            focus::InputField::SuperTrendPeriod => {
+                Some(&mut self.settings.overlays.supertrend.period)
+            }
+            focus::InputField::SuperTrendMultiplier => {
+                Some(&mut self.settings.overlays.supertrend.multiplier)
+            }
+            focus::InputField::SarStep => Some(&mut self.settings.overlays.parabolic_sar.step),
+            focus::InputField::SarMax => Some(&mut self.settings.overlays.parabolic_sar.max),
+            focus::InputField::KeltnerPeriod => Some(&mut self.settings.overlays.keltner.period),
+            focus::InputField::KeltnerMultiplier => {
+                Some(&mut self.settings.overlays.keltner.multiplier)
+            }
+            focus::InputField::AdxPeriod => Some(&mut self.settings.overlays.adx.period),
+            focus::InputField::DonchianPeriod(id) => {
+                self.donchian_mut(id).map(|donchian| &mut donchian.period)
+            }
+            focus::InputField::ScheduleTime => Some(&mut self.scheduler.time),
+            focus::InputField::AlertPrice => Some(&mut self.alert_form.price),
+            focus::InputField::RecurringAmount => Some(&mut self.recurring_form.amount),
+            focus::InputField::Strategy(field) => Some(self.strategy.field_mut(field)),
+            focus::InputField::Automation(field) => Some(self.automation_form.field_mut(field)),
+            focus::InputField::ScreenFilter(field) => Some(self.screener.form.field_mut(field)),
+            _ => None,
+        }
+    }
 
-        // Start the market data WebSocket stream in a background task
-        stream::MarketDataStreamManager::start_stream(sender, vec![symbol]);
+    /// Inputs currently on screen, in Tab order (header, footer tab, then sidebar)
+    fn tab_order(&self) -> Vec<focus::InputField> {
+        // Dialogs are modal
+        if self.order_guard.pending.is_some() {
+            return vec![focus::InputField::LiveConfirmation];
+        }
+        // Tab stays within the focused chart window's inputs
+        if let Some(id) = self.focus.focused().and_then(|field| field.chart_window()) {
+            let mut order = vec![
+                focus::InputField::ChartSymbol(id),
+                focus::InputField::ChartQuantity(id),
+            ];
+            let chart_id = chart::ChartId::Window(id);
+            if self
+                .donchian(chart_id)
+                .is_some_and(|donchian| donchian.enabled)
+            {
+                order.push(focus::InputField::DonchianPeriod(chart_id));
+            }
+            return order;
+        }
+        if self.setup.open {
+            return vec![
+                focus::InputField::ProfileName,
+                focus::InputField::ApiKeyId,
+                focus::InputField::ApiSecretKey,
+            ];
+        }
 
-        // Spawn a task to listen for updates and apply them to the UI
-        cx.spawn(async move |this, cx| {
-            while let Some(update) = receiver.recv().await {
-                let _ = this.update(cx, |chart, cx| {
-                    chart.handle_stream_update(update, cx);
-                });
+        let mut order = vec![focus::InputField::Symbol, focus::InputField::BarLimit];
+        if self.settings.overlays.donchian.enabled {
+            order.push(focus::InputField::DonchianPeriod(chart::ChartId::Main));
+        }
+        if self.settings.overlays.supertrend.enabled {
+            order.extend([
+                focus::InputField::SuperTrendPeriod,
+                focus::InputField::SuperTrendMultiplier,
+            ]);
+        }
+        if self.settings.overlays.parabolic_sar.enabled {
+            order.extend([focus::InputField::SarStep, focus::InputField::SarMax]);
+        }
+        if self.settings.overlays.keltner.enabled {
+            order.extend([
+                focus::InputField::KeltnerPeriod,
+                focus::InputField::KeltnerMultiplier,
+            ]);
+        }
+        if self.settings.overlays.adx.enabled {
+            order.push(focus::InputField::AdxPeriod);
+        }
+
+        match self.account.active_footer_tab {
+            account::FooterTab::Positions => order.push(focus::InputField::PositionsFilter),
+            account::FooterTab::Orders => order.push(focus::InputField::OrdersSearch),
+            account::FooterTab::History => order.push(focus::InputField::HistorySymbol),
+            account::FooterTab::Movers if self.screener.form.open => {
+                for field in screener::FilterField::NUMERIC {
+                    order.push(focus::InputField::ScreenFilter(field));
+                }
+                order.push(focus::InputField::ScreenFilter(screener::FilterField::Name));
             }
-        })
-        .detach();
+            _ => {}
+        }
+
+        // A closed drawer hides the sidebar; the popped-out order form stays reachable
+        let sidebar_visible = !self.layout.has_drawer() || self.sidebar_open;
+        // The scalp pad replaces the sidebar's form and panels
+        if sidebar_visible && self.settings.scalp.enabled {
+            order.push(focus::InputField::ScalpSize);
+            if self.order_window.is_none() {
+                return order;
+            }
+        }
+        if (sidebar_visible && !self.settings.scalp.enabled) || self.order_window.is_some() {
+            order.push(focus::InputField::OrderQuantity);
+            if matches!(self.account.order_type, OrderType::Limit) {
+                order.push(focus::InputField::LimitPrice);
+            }
+            if self.scheduler.mode == schedule::ScheduleMode::Time {
+                order.push(focus::InputField::ScheduleTime);
+            }
+        }
+        if !sidebar_visible {
+            return order;
+        }
+        if self.hotkeys.open {
+            order.push(focus::InputField::HotkeyQuantity);
+        }
+        if self.auto_exits.open {
+            order.push(focus::InputField::AutoExitTakeProfit);
+            order.push(focus::InputField::AutoExitStopLoss);
+        }
+        if self.alert_form.open {
+            order.push(focus::InputField::AlertPrice);
+        }
+        if self.recurring_form.open {
+            order.push(focus::InputField::RecurringAmount);
+        }
+        if self.automation_form.open {
+            for field in [
+                automation::AutomationField::Level,
+                automation::AutomationField::Quantity,
+                automation::AutomationField::LimitPrice,
+            ] {
+                order.push(focus::InputField::Automation(field));
+            }
+        }
+        if self.strategy.open {
+            order.push(focus::InputField::Strategy(
+                options::StrategyField::Expiration,
+            ));
+            for index in 0..self.strategy.kind.strike_labels().len() {
+                order.push(focus::InputField::Strategy(options::StrategyField::Strike(
+                    index,
+                )));
+            }
+            order.push(focus::InputField::Strategy(
+                options::StrategyField::Quantity,
+            ));
+            order.push(focus::InputField::Strategy(
+                options::StrategyField::NetPrice,
+            ));
+        }
+
+        order
     }
 
-    fn update_bars_from_stream(&mut self, bar_update: stream::BarUpdate, cx: &mut Context<Self>) {
-        // Store the bar update information for display
-        self.chart.last_bar_time = Some(bar_update.timestamp.clone());
-        self.chart.last_bar_symbol = Some(bar_update.symbol.clone());
-        self.chart.last_bar_open = Some(bar_update.open.clone());
-        self.chart.last_bar_high = Some(bar_update.high.clone());
-        self.chart.last_bar_low = Some(bar_update.low.clone());
-        self.chart.last_bar_close = Some(bar_update.close.clone());
-        self.chart.last_bar_volume = Some(bar_update.volume.clone());
+    fn submit_symbol(&mut self, cx: &mut Context<Self>) {
+        if !self.chart.symbol_input.is_empty() {
+            self.chart.symbol = self.chart.symbol_input.text().to_uppercase();
+            self.set_input_focus(None);
+            self.recent_symbol_index = None;
+            self.settings.remember_symbol(&self.chart.symbol);
+            self.settings.save();
+            // Crypto orders don't support DAY time in force
+            if self.chart.is_crypto()
+                && matches!(self.account.order_time_in_force, OrderTimeInForce::Day)
+            {
+                self.account.order_time_in_force = OrderTimeInForce::Gtc;
+            }
+            self.fetch_chart_bars_debounced(chart::ChartId::Main, cx);
 
-        println!(
-            "📊 Bar Update: {} @ {} - O:{} H:{} L:{} C:{} V:{}",
-            bar_update.symbol,
-            bar_update.timestamp,
-            bar_update.open,
-            bar_update.high,
-            bar_update.low,
-            bar_update.close,
-            bar_update.volume,
-        );
+            // Details load after the same pause, and only if no other symbol was picked since
+            let symbol = self.chart.symbol.clone();
+            cx.spawn(async move |this, cx| {
+                cx.background_executor()
+                    .timer(std::time::Duration::from_millis(chart::FETCH_DEBOUNCE_MS))
+                    .await;
+                let _ = this.update(cx, |terminal, cx| {
+                    if terminal.chart.symbol == symbol {
+                        terminal.load_symbol_details(cx);
+                    }
+                });
+            })
+            .detach();
+        }
+    }
 
-        // Only update chart if the bar is for the current symbol
-        if bar_update.symbol == self.chart.symbol {
-            // Convert BarUpdate to Bar struct
-            match chart::convert_bar_update_to_bar(&bar_update) {
-                Ok(new_bar) => {
-                    if self.chart.bars.is_empty() {
-                        // No existing bars, just add the new one
-                        self.chart.bars.push(new_bar);
-                        println!("✅ Added first bar to chart");
-                    } else {
-                        // Align the incoming bar timestamp to the chart's timeframe
-                        let aligned_timestamp = chart::align_timestamp_to_timeframe(
-                            new_bar.timestamp,
-                            &self.chart.timeframe,
-                        );
+    /// Quote, asset info, corporate actions, news and streaming for the charted symbol
+    fn load_symbol_details(&mut self, cx: &mut Context<Self>) {
+        self.fetch_snapshot();
+        self.fetch_key_stats(cx);
+        self.fetch_asset(cx);
+        self.fetch_corporate_actions();
+        if self.news_symbols() != self.news.symbols {
+            self.fetch_news(cx);
+        }
+        // Subscribe the new symbol (equity or crypto feed) and drop the old one if unused
+        self.sync_market_data();
+    }
 
-                        // Get the last bar's timestamp before taking mutable reference
-                        let last_bar_timestamp = self.chart.bars.last().unwrap().timestamp;
-                        let last_bar_aligned = chart::align_timestamp_to_timeframe(
-                            last_bar_timestamp,
-                            &self.chart.timeframe,
-                        );
+    fn fetch_corporate_actions(&mut self) {
+        // Corporate actions only change with the symbol
+        if self.corporate_actions_symbol.as_ref() == Some(&self.chart.symbol) {
+            return;
+        }
 
-                        if aligned_timestamp == last_bar_aligned {
-                            // Get mutable reference after calculating timestamps
-                            let last_bar = self.chart.bars.last_mut().unwrap();
-                            // This bar update belongs to the same timeframe candle as the last bar
-                            // Update the last bar by aggregating the data
-                            println!(
-                                "🔄 Updating existing {} candle (period: {})",
-                                self.chart.timeframe,
-                                aligned_timestamp.format("%Y-%m-%d %H:%M:%S")
-                            );
+        self.corporate_actions.clear();
+        self.hovered_corporate_action = None;
+        self.corporate_actions_symbol = Some(self.chart.symbol.clone());
 
-                            // Keep the open from the existing bar (first price of the period)
-                            // Update high to be the maximum
-                            last_bar.high = last_bar.high.max(new_bar.high);
-                            // Update low to be the minimum
-                            last_bar.low = last_bar.low.min(new_bar.low);
-                            // Update close to the latest close
-                            last_bar.close = new_bar.close;
-                            // Add the volume
-                            last_bar.volume += new_bar.volume;
-                            // Update timestamp to the latest
-                            last_bar.timestamp = new_bar.timestamp;
-                            // Update optional fields
-                            if let (Some(existing_tc), Some(new_tc)) =
-                                (last_bar.trade_count, new_bar.trade_count)
-                            {
-                                last_bar.trade_count = Some(existing_tc + new_tc);
-                            }
+        // Crypto pairs have no splits or dividends
+        if self.chart.is_crypto() {
+            return;
+        }
 
-                            println!(
-                                "✅ Updated current {} bar: O:{:.2} H:{:.2} L:{:.2} C:{:.2} V:{}",
-                                self.chart.timeframe,
-                                last_bar.open,
-                                last_bar.high,
-                                last_bar.low,
-                                last_bar.close,
-                                last_bar.volume
-                            );
-                        } else if aligned_timestamp > last_bar_aligned {
-                            // Get mutable reference is not needed here, just push
-                            // This is a new timeframe period - append a new bar
-                            println!(
-                                "➕ New {} candle period started: {}",
-                                self.chart.timeframe,
-                                aligned_timestamp.format("%Y-%m-%d %H:%M:%S")
-                            );
-                            self.chart.bars.push(new_bar);
-                            println!(
-                                "✅ Added new {} bar to chart (total: {})",
-                                self.chart.timeframe,
-                                self.chart.bars.len()
-                            );
+        self.service.send(service::ServiceRequest::CorporateActions(
+            self.chart.symbol.clone(),
+        ));
+    }
 
-                            // Auto-scroll to show the latest bar
-                            if self.chart.bars.len() > self.chart.bars_per_screen {
-                                self.chart.chart_scroll_offset =
-                                    (self.chart.bars.len() - self.chart.bars_per_screen) as f32;
-                            }
-                        } else {
-                            println!("⚠️ Received bar with older timeframe period, ignoring");
-                        }
-                    }
-                }
-                Err(e) => {
-                    eprintln!("❌ Failed to convert bar update: {}", e);
-                }
-            }
+    /// Chart a symbol picked from the recent list
+    fn select_recent_symbol(&mut self, symbol: String, cx: &mut Context<Self>) {
+        self.chart.symbol_input.set_text(symbol);
+        self.submit_symbol(cx);
+    }
+
+    /// Step through recent symbols in the symbol input (up = older, down = newer)
+    fn cycle_recent_symbol(&mut self, older: bool, cx: &mut Context<Self>) {
+        let count = self.settings.recent_symbols.len();
+        if count == 0 {
+            return;
         }
 
-        // Notify to update the UI
+        let index = match (self.recent_symbol_index, older) {
+            (None, true) => Some(0),
+            (None, false) => None,
+            (Some(i), true) => Some((i + 1).min(count - 1)),
+            (Some(0), false) => None,
+            (Some(i), false) => Some(i - 1),
+        };
+
+        self.recent_symbol_index = index;
+        let symbol = index
+            .and_then(|i| self.settings.recent_symbols.get(i).cloned())
+            .unwrap_or_default();
+        self.chart.symbol_input.set_text(symbol);
         cx.notify();
     }
 
-    fn fetch_bars(&mut self, cx: &mut Context<Self>) {
-        self.chart.loading = true;
-        self.chart.error = None;
-        cx.notify();
+    /// Price, quote and daily bars for the header right away, before any streamed bar
+    fn fetch_snapshot(&mut self) {
+        if self
+            .snapshot
+            .as_ref()
+            .is_some_and(|snapshot| snapshot.symbol != self.chart.symbol)
+        {
+            self.snapshot = None;
+        }
+
+        self.service.send(service::ServiceRequest::Snapshot {
+            symbol: self.chart.symbol.clone(),
+            feed: self.config.market_data.feed,
+        });
+    }
+
+    /// Load a year of daily bars for the key stats strip
+    fn fetch_key_stats(&mut self, cx: &mut Context<Self>) {
+        // Daily stats barely move within a session, so they only reload with the symbol
+        if self
+            .key_stats
+            .as_ref()
+            .is_some_and(|stats| stats.symbol == self.chart.symbol)
+        {
+            return;
+        }
+        self.key_stats = None;
 
         let symbol = self.chart.symbol.clone();
-        let timeframe = self.chart.timeframe.clone();
-        let limit = self.chart.bar_limit.parse::<u32>().unwrap_or(100);
+        let data_feed = self.config.market_data.feed;
 
-        // Modern GPUI async pattern with AsyncApp::update()
         cx.spawn(async move |this, cx| {
-            // Run the blocking API call in a background thread
+            let request_symbol = symbol.clone();
             let result = cx
                 .background_executor()
-                .spawn(async move { fetch_bars_sync(&symbol, &timeframe, limit) })
+                .spawn(async move {
+                    fetch_bars_sync(&request_symbol, "1Day", key_stats::YEAR_BARS, data_feed)
+                })
                 .await;
 
-            // Update UI using AsyncApp::update()
             let _ = this.update(cx, |terminal, cx| {
+                // Ignore stale responses for a previously selected symbol
+                if terminal.chart.symbol != symbol {
+                    return;
+                }
                 match result {
                     Ok(bars) => {
-                        terminal.chart.bars = bars;
-                        terminal.chart.error = None;
-                        // Set scroll offset to show most recent bars by default
-                        terminal.chart.chart_scroll_offset = terminal
-                            .chart
-                            .bars
-                            .len()
-                            .saturating_sub(terminal.chart.bars_per_screen)
-                            as f32;
-                        println!(
-                            "✓ Successfully loaded {} bars for {} ({})",
-                            terminal.chart.bars.len(),
-                            terminal.chart.symbol,
-                            terminal.chart.timeframe
+                        info!(
+                            "✓ Loaded {} daily bars for {} key stats",
+                            bars.len(),
+                            symbol
                         );
-                        // Debug: Show first and last bar prices with timestamps
-                        if !terminal.chart.bars.is_empty() {
-                            let first = &terminal.chart.bars[0];
-                            let last = &terminal.chart.bars[terminal.chart.bars.len() - 1];
-                            println!(
-                                "  First bar: O:{:.2} H:{:.2} L:{:.2} C:{:.2} ({})",
-                                first.open,
-                                first.high,
-                                first.low,
-                                first.close,
-                                first.timestamp.format("%Y-%m-%d %H:%M")
-                            );
-                            println!(
-                                "  Last bar:  O:{:.2} H:{:.2} L:{:.2} C:{:.2} ({})",
-                                last.open,
-                                last.high,
-                                last.low,
-                                last.close,
-                                last.timestamp.format("%Y-%m-%d %H:%M")
-                            );
-                        }
-                    }
-                    Err(error) => {
-                        terminal.chart.error = Some(error.clone());
-                        terminal.chart.bars = generate_mock_data();
-                        eprintln!("✗ Error fetching bars: {}. Using mock data.", error);
+                        terminal.key_stats =
+                            Some(key_stats::KeyStats::from_daily_bars(symbol, &bars));
                     }
+                    Err(error) => warn!("⚠️  Failed to load key stats: {}", error),
                 }
-                terminal.chart.loading = false;
                 cx.notify();
             });
         })
         .detach();
     }
 
-    fn render_candlesticks(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
-        if self.chart.bars.is_empty() {
-            let message = if self.chart.loading {
-                "Loading data from Alpaca Markets...".to_string()
-            } else if let Some(ref error) = self.chart.error {
-                error.clone()
-            } else {
-                "No data available.".to_string()
-            };
+    /// Seed the header and the last-price map from a snapshot
+    fn apply_snapshot(&mut self, snapshot: snapshot::SymbolSnapshot) {
+        if let Some(price) = snapshot.last_price {
+            self.last_prices
+                .insert(snapshot.symbol.clone(), price.to_string());
+        }
+        // The market data indicator shows the latest bar; start it from the snapshot's
+        if let Some(bar) = snapshot.minute_bar.as_ref().or(snapshot.daily_bar.as_ref()) {
+            self.chart.last_bar_symbol = Some(snapshot.symbol.clone());
+            self.chart.last_bar_time = Some(bar.timestamp.to_rfc3339());
+            self.chart.last_bar_open = Some(bar.open.to_string());
+            self.chart.last_bar_high = Some(bar.high.to_string());
+            self.chart.last_bar_low = Some(bar.low.to_string());
+            self.chart.last_bar_close = Some(bar.close.to_string());
+            self.chart.last_bar_volume = Some(bar.volume.to_string());
+        }
+        self.snapshot = Some(snapshot);
+    }
 
-            return div()
-                .grid()
-                .items_center()
-                .justify_center()
-                .size_full()
-                .child(div().text_color(rgb(0x808080)).child(message));
+    fn fetch_asset(&mut self, cx: &mut Context<Self>) {
+        // Asset details only change with the symbol
+        if self
+            .asset
+            .as_ref()
+            .is_some_and(|asset| asset.symbol == self.chart.symbol)
+        {
+            return;
         }
 
-        // Calculate visible range of bars (windowing for scrolling)
-        let bars_per_screen = self.chart.bars_per_screen;
-        // Clamp start_index to valid range
-        let start_index =
-            (self.chart.chart_scroll_offset as usize).min(self.chart.bars.len().saturating_sub(1));
-        let end_index = (start_index + bars_per_screen).min(self.chart.bars.len());
-        // Ensure we don't have an empty range
-        let start_index = if end_index > start_index {
-            start_index
-        } else {
-            0
-        };
-        let visible_bars = &self.chart.bars[start_index..end_index];
+        self.asset_loading = true;
+        self.asset = None;
+        cx.notify();
 
-        // Calculate price range for visible bars only
-        let max_price = visible_bars
-            .iter()
-            .map(|b| b.close)
-            .fold(f64::NEG_INFINITY, f64::max);
-        let min_price = visible_bars
-            .iter()
-            .map(|b| b.close)
-            .fold(f64::INFINITY, f64::min);
+        self.service
+            .send(service::ServiceRequest::Asset(self.chart.symbol.clone()));
+    }
 
-        let price_range = max_price - min_price;
-        let price_padding = price_range * 0.1;
-        let adjusted_max = max_price + price_padding;
-        let adjusted_min = min_price - price_padding;
-        let adjusted_range = adjusted_max - adjusted_min;
+    /// Scroll the footer table so the scrollbar thumb follows the pointer's Y position
+    fn scroll_footer_to(&mut self, y: gpui::Pixels, cx: &mut Context<Self>) {
+        let bounds = self.footer_scroll.bounds();
+        let max_offset = self.footer_scroll.max_offset();
+        let height: f32 = bounds.size.height.into();
+        if height <= 0.0 {
+            return;
+        }
 
-        // Calculate bar width based on visible bars with padding
-        let padding_left_percent = 5.0; // 5% left padding
-        let padding_right_percent = 5.0; // 5% right padding
-        let usable_width_percent = 100.0 - padding_left_percent - padding_right_percent;
+        let y_in_track: f32 = (y - bounds.origin.y).into();
+        let ratio = (y_in_track / height).clamp(0.0, 1.0);
+        self.footer_scroll
+            .set_offset(point(px(0.0), -(max_offset.height * ratio)));
+        cx.notify();
+    }
 
-        let visible_bar_count = visible_bars.len() as f32;
-        let bar_spacing_ratio = 0.2; // 20% spacing between bars
-        let bar_width_percent =
-            (usable_width_percent / visible_bar_count) * (1.0 - bar_spacing_ratio);
-        let total_bar_width_percent = usable_width_percent / visible_bar_count;
+    /// Resize the column being dragged to follow the mouse
+    fn drag_column(&mut self, x: gpui::Pixels, cx: &mut Context<Self>) {
+        let Some(drag) = self.column_drag else {
+            return;
+        };
 
-        div()
-            .flex()
-            .flex_col()
-            .gap_4()
-            .size_full()
-            .child(
-                // Chart container - expands to fill available space
-                div()
-                    .id("chart-container")
-                    .relative()
-                    .flex_1()
-                    .w_full()
-                    .bg(rgb(0x1a1a1a))
-                    .border_2()
-                    .border_color(rgb(0x404040))
-                    // Inner div with relative positioning for accurate mouse tracking
-                    .child(
-                        div()
-                            .relative()
-                            .size_full()
-                            .overflow_hidden()
-                            .on_mouse_move(cx.listener(
-                                |this, event: &gpui::MouseMoveEvent, window, cx| {
-                                    // CALIBRATION GUIDE for offset_y:
-                                    // 1. Hover at the VERY TOP of the chart (where price is highest)
-                                    // 2. If crosshair price is HIGHER than expected: INCREASE offset_y
-                                    // 3. If crosshair price is LOWER than expected: DECREASE offset_y
-                                    let offset_x = px(66.0);
-                                    let offset_y = px(212.0); // Adjust this if top of chart is wrong
+        let x: f32 = x.into();
+        let width = drag.start_width + (x - drag.start_x);
+        match drag.table {
+            columns::ColumnTable::Positions => {
+                self.settings.positions_columns.set_width(drag.index, width)
+            }
+            columns::ColumnTable::Orders => {
+                self.settings.orders_columns.set_width(drag.index, width)
+            }
+        }
+        cx.notify();
+    }
 
-                                    let relative_x = event.position.x - offset_x;
-                                    let relative_y = event.position.y - offset_y;
+    /// Finish a column drag and persist the new width
+    fn end_column_drag(&mut self, cx: &mut Context<Self>) {
+        if self.column_drag.take().is_some() {
+            self.settings.save();
+            cx.notify();
+        }
+    }
 
-                                    this.chart.mouse_position = Some(gpui::Point {
-                                        x: relative_x,
-                                        y: relative_y,
-                                    });
+    /// Whether the pending Sell order would exceed the held long quantity and open a short
+    fn sell_would_open_short(&self) -> bool {
+        if !matches!(self.account.order_side, OrderSide::Sell) {
+            return false;
+        }
 
-                                    // Calculate chart bounds from window size
-                                    let window_bounds = window.bounds();
-                                    let window_width: f32 = window_bounds.size.width.into();
-                                    let window_height: f32 = window_bounds.size.height.into();
+        let order_qty = self
+            .account
+            .order_quantity
+            .text()
+            .parse::<f64>()
+            .unwrap_or(0.0);
+        let held_qty = self
+            .account
+            .positions
+            .iter()
+            .find(|p| p.symbol == self.chart.symbol)
+            .and_then(|p| p.qty.parse::<f64>().ok())
+            .unwrap_or(0.0);
 
-                                    // Chart width calculation
-                                    let chart_width = window_width * 0.875 - 100.0;
+        // Selling with no long position (or more than held) opens or extends a short
+        held_qty <= 0.0 || order_qty > held_qty
+    }
 
-                                    // FIXED-PIXEL APPROACH: Chart height = window height - all fixed UI elements
-                                    // This works regardless of window size because we subtract absolute pixels
-                                    //
-                                    // CALIBRATION: Adjust bottom_offset if prices don't match grid
-                                    // - If crosshair shows LOWER price than grid: INCREASE bottom_offset
-                                    // - If crosshair shows HIGHER price than grid: DECREASE bottom_offset
-                                    //
-                                    // Components below the chart (approximate values):
-                                    // - Scroll controls: ~50px
-                                    // - Gap before footer: ~24px
-                                    // - Footer: ~280px
-                                    // - Window bottom padding: ~40px
-                                    let bottom_offset = 414.0; // Tune this value
+    fn fetch_account(&mut self, cx: &mut Context<Self>) {
+        self.account.account_loading = true;
+        cx.notify();
 
-                                    let offset_y_f32: f32 = offset_y.into();
-                                    let chart_height = window_height - offset_y_f32 - bottom_offset;
+        self.service.send(service::ServiceRequest::Account);
+    }
 
-                                    // Debug: Print calibration info (comment out after calibration)
-                                    println!("Window H: {:.0}px, Chart H: {:.0}px (= {:.0} - {:.0} - {:.0}), Mouse Y: {:.0}px",
-                                             window_height, chart_height, window_height, offset_y_f32, bottom_offset, relative_y);
+    fn fetch_clock(&mut self) {
+        self.clock_loading = true;
+        self.service.send(service::ServiceRequest::Clock);
+    }
 
-                                    this.chart.chart_bounds = Some((chart_width, chart_height));
-                                    this.chart.show_crosshair = true;
-                                    cx.notify();
-                                },
-                            ))
-                            .on_scroll_wheel(cx.listener(
-                                |this, event: &gpui::ScrollWheelEvent, _window, cx| {
-                                    let pixel_delta = event.delta.pixel_delta(px(1.0));
-                                    let scroll_amount: f32 = pixel_delta.y.into();
+    fn fetch_calendar(&mut self, cx: &mut Context<Self>) {
+        self.calendar_loading = true;
+        cx.notify();
 
-                                    // Check if Ctrl is pressed for zoom
-                                    if event.modifiers.control {
-                                        // Zoom: adjust bars_per_screen
-                                        let zoom_amount = (scroll_amount * 2.0) as i32;
+        self.service.send(service::ServiceRequest::Calendar);
+    }
 
-                                        if zoom_amount > 0 {
-                                            // Zoom out (show more bars)
-                                            this.chart.bars_per_screen = (this.chart.bars_per_screen
-                                                + zoom_amount as usize)
-                                                .min(this.chart.bars.len());
-                                        } else {
-                                            // Zoom in (show fewer bars)
-                                            this.chart.bars_per_screen =
-                                                (this.chart.bars_per_screen as i32 + zoom_amount).max(10)
-                                                    as usize;
-                                        }
+    fn toggle_calendar(&mut self, cx: &mut Context<Self>) {
+        self.calendar_open = !self.calendar_open;
+        if self.calendar_open && self.calendar_events.is_empty() && !self.calendar_loading {
+            self.fetch_calendar(cx);
+        }
+        cx.notify();
+    }
 
-                                        // Adjust scroll offset to keep it in bounds
-                                        let max_offset =
-                                            this.chart.bars.len().saturating_sub(this.chart.bars_per_screen)
-                                                as f32;
-                                        this.chart.chart_scroll_offset =
-                                            this.chart.chart_scroll_offset.min(max_offset);
-                                    } else {
-                                        // Normal scroll: move through bars
-                                        let max_offset =
-                                            this.chart.bars.len().saturating_sub(this.chart.bars_per_screen)
-                                                as f32;
-                                        let scroll_amount = scroll_amount * 0.5; // Adjust sensitivity
+    /// Re-render every second for the countdown and re-query the clock when it goes stale
+    fn start_clock_ticker(&mut self, cx: &mut Context<Self>) {
+        cx.spawn(async move |this, cx| {
+            loop {
+                cx.background_executor()
+                    .timer(std::time::Duration::from_secs(1))
+                    .await;
 
-                                        if scroll_amount > 0.0 {
-                                            // Scroll forward (show older bars)
-                                            this.chart.chart_scroll_offset = (this.chart.chart_scroll_offset
-                                                + scroll_amount)
-                                                .min(max_offset);
-                                        } else {
-                                            // Scroll backward (show newer bars)
-                                            this.chart.chart_scroll_offset =
-                                                (this.chart.chart_scroll_offset + scroll_amount).max(0.0);
-                                        }
-                                    }
+                let result = this.update(cx, |terminal, cx| {
+                    let is_stale = terminal
+                        .market_clock
+                        .as_ref()
+                        .is_none_or(|clock| clock.needs_refresh(Utc::now()));
+                    // Nothing to fetch with until API keys are set up
+                    if is_stale && !terminal.clock_loading && terminal.data_loaded {
+                        terminal.fetch_clock();
+                    }
+                    if terminal.account.active_footer_tab == account::FooterTab::Heatmap
+                        && terminal.data_loaded
+                        && !terminal.heatmap.loading
+                        && terminal
+                            .heatmap
+                            .needs_refresh(terminal.config.heatmap.refresh_secs)
+                    {
+                        terminal.fetch_heatmap(cx);
+                    }
+                    if terminal.account.active_footer_tab == account::FooterTab::Movers
+                        && terminal.data_loaded
+                        && !terminal.screener.loading
+                        && terminal
+                            .screener
+                            .needs_refresh(terminal.config.screener.refresh_secs)
+                    {
+                        terminal.fetch_screener(cx);
+                    }
+                    // New positions get their betas while the tab is open
+                    if terminal.account.active_footer_tab == account::FooterTab::Analytics
+                        && terminal.data_loaded
+                        && !terminal.analytics.loading
+                        && terminal.analytics.error.is_none()
+                        && !terminal
+                            .analytics
+                            .missing(&terminal.account.positions)
+                            .is_empty()
+                    {
+                        terminal.fetch_analytics(cx);
+                    }
+                    // Scanning alerts run whichever tab is open
+                    if terminal.data_loaded
+                        && !terminal.screener.scanning
+                        && terminal
+                            .settings
+                            .saved_screens
+                            .iter()
+                            .any(|screen| screen.scan)
+                        && terminal
+                            .screener
+                            .needs_scan(terminal.config.screener.refresh_secs)
+                    {
+                        terminal.scan_screens(cx);
+                    }
+                    terminal.submit_due_orders(cx);
+                    terminal.run_recurring_plans(cx);
+                    // Kept current here too, since a minimized window may not render
+                    terminal.refresh_window_title(cx);
+                    cx.notify();
+                });
 
-                                    cx.notify();
-                                },
-                            ))
-                            // Price grid lines with round values (adaptive to zoom level)
-                            .children({
-                                // Adjust grid line count based on zoom level
-                                let grid_count = if self.chart.bars_per_screen <= 20 {
-                                    12 // Very zoomed in - show many grid lines
-                                } else if self.chart.bars_per_screen <= 50 {
-                                    10 // Moderately zoomed in
-                                } else if self.chart.bars_per_screen <= 100 {
-                                    8 // Default zoom
-                                } else if self.chart.bars_per_screen <= 200 {
-                                    6 // Zoomed out
-                                } else if self.chart.bars_per_screen <= 500 {
-                                    5 // More zoomed out
-                                } else {
-                                    4 // Very zoomed out - show fewer grid lines
-                                };
+                // View was dropped
+                if result.is_err() {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
 
-                                let grid_values = chart::calculate_round_grid_values(
-                                    adjusted_min,
-                                    adjusted_max,
-                                    grid_count,
-                                );
-                                grid_values.into_iter().map(|price| {
-                                    // Calculate Y position as percentage
-                                    let y_percent =
-                                        ((adjusted_max - price) / adjusted_range) as f32 * 100.0;
+    /// Poll the config file's modification time and reload it when it changes
+    fn start_config_watcher(&mut self, cx: &mut Context<Self>) {
+        cx.spawn(async move |this, cx| {
+            loop {
+                cx.background_executor()
+                    .timer(std::time::Duration::from_secs(config::RELOAD_INTERVAL_SECS))
+                    .await;
 
-                                    div()
-                                        .absolute()
-                                        .left_0()
-                                        .top(gpui::relative(y_percent / 100.0))
-                                        .w_full()
-                                        .h(px(1.0))
-                                        .bg(rgb(0x2a2a2a))
-                                        .child(
-                                            div()
-                                                .absolute()
-                                                .left(px(5.0))
-                                                .top(px(-8.0))
-                                                .text_xs()
-                                                .text_color(rgb(0x808080))
-                                                .child(format!("${:.2}", price)),
-                                        )
-                                })
-                            })
-                            // Candlestick wicks
-                            .children(visible_bars.iter().enumerate().map(|(i, bar)| {
-                                // Calculate positions as percentages with padding
-                                let x_percent =
-                                    padding_left_percent + i as f32 * total_bar_width_percent;
+                let modified = config::modified_at();
+                let result = this.update(cx, |terminal, cx| {
+                    // A deleted file keeps the current config
+                    if modified.is_some() && modified != terminal.config_modified {
+                        terminal.config_modified = modified;
+                        terminal.reload_config(cx);
+                    }
+                });
 
-                                // Calculate Y positions as percentages with padding
-                                let padding_top_percent = 5.0;
-                                let padding_bottom_percent = 5.0;
-                                let usable_height_percent =
-                                    100.0 - padding_top_percent - padding_bottom_percent;
+                // View was dropped
+                if result.is_err() {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
 
-                                let high_y_percent = padding_top_percent
-                                    + ((adjusted_max - bar.high) / adjusted_range) as f32
-                                        * usable_height_percent;
-                                let low_y_percent = padding_top_percent
-                                    + ((adjusted_max - bar.low) / adjusted_range) as f32
-                                        * usable_height_percent;
+    /// Apply an edited config file. Chart defaults only take effect on the next start;
+    /// the feed and hotkey/confirmation overrides apply right away.
+    fn reload_config(&mut self, cx: &mut Context<Self>) {
+        match config::Config::reload() {
+            Ok(config) => {
+                if config == self.config {
+                    return;
+                }
+                let feed_changed = config.market_data.feed != self.config.market_data.feed;
+                if config.retry != self.config.retry {
+                    self.service.set_retry_config(config.retry.clone());
+                }
+                config.apply_overrides(&mut self.settings);
+                if config.heatmap != self.config.heatmap {
+                    // Reloaded by the clock ticker if the tab is open
+                    self.heatmap.universe = config.heatmap.universe;
+                    self.heatmap.size_by = config.heatmap.size_by;
+                    self.heatmap.updated_at = None;
+                }
+                if config.screener != self.config.screener {
+                    self.screener.universe = config.screener.universe;
+                    self.screener.updated_at = None;
+                }
+                self.config = config;
 
-                                let wick_height_percent = low_y_percent - high_y_percent;
+                if feed_changed {
+                    self.start_market_data_stream(cx);
+                    self.fetch_bars(cx);
+                }
+                self.show_toast(
+                    toast::ToastLevel::Info,
+                    "Configuration reloaded".to_string(),
+                    cx,
+                );
+            }
+            Err(e) => {
+                warn!("⚠️  {}", e);
+                self.show_failure("Config not reloaded", &e, None, cx);
+            }
+        }
+    }
 
-                                // Determine if bullish or bearish
-                                let is_bullish = bar.close >= bar.open;
+    fn fetch_account_config(&mut self) {
+        self.service.send(service::ServiceRequest::AccountConfig);
+    }
 
-                                // Check if this is the most recent bar (live updating)
-                                let is_latest_bar = i == visible_bars.len() - 1 &&
-                                    end_index == self.chart.bars.len();
+    /// Apply a change to the account configuration and save it to the server
+    fn update_account_config(
+        &mut self,
+        update: impl FnOnce(&mut account_config::AccountConfig),
+        cx: &mut Context<Self>,
+    ) {
+        if self.account_config_saving {
+            return;
+        }
+        let Some(mut config) = self.account_config.clone() else {
+            return;
+        };
+        update(&mut config);
+        if Some(&config) == self.account_config.as_ref() {
+            return;
+        }
 
-                                let color = if is_bullish {
-                                    rgb(0x00cc66)
-                                } else {
-                                    rgb(0xff4444)
-                                };
+        // Show the change immediately; the server response replaces it
+        let previous = self.account_config.replace(config.clone());
+        self.account_config_saving = true;
+        self.account_config_message = None;
+        cx.notify();
 
-                                // High-Low wick (thin line)
-                                div()
-                                    .absolute()
-                                    .left(gpui::relative(
-                                        (x_percent + bar_width_percent / 2.0) / 100.0,
-                                    ))
-                                    .top(gpui::relative(high_y_percent / 100.0))
-                                    .w(if is_latest_bar { px(2.0) } else { px(1.0) })
-                                    .h(gpui::relative(wick_height_percent / 100.0))
-                                    .bg(color)
-                            }))
-                            // Candlestick bodies
-                            .children(visible_bars.iter().enumerate().map(|(i, bar)| {
-                                // Calculate positions as percentages with padding
-                                let x_percent =
-                                    padding_left_percent + i as f32 * total_bar_width_percent;
+        self.service
+            .send(service::ServiceRequest::UpdateAccountConfig { config, previous });
+    }
 
-                                // Calculate Y positions as percentages with padding
-                                let padding_top_percent = 5.0;
-                                let padding_bottom_percent = 5.0;
-                                let usable_height_percent =
-                                    100.0 - padding_top_percent - padding_bottom_percent;
+    fn fetch_positions(&mut self, cx: &mut Context<Self>) {
+        self.account.positions_loading = true;
+        cx.notify();
 
-                                let open_y_percent = padding_top_percent
-                                    + ((adjusted_max - bar.open) / adjusted_range) as f32
-                                        * usable_height_percent;
-                                let close_y_percent = padding_top_percent
-                                    + ((adjusted_max - bar.close) / adjusted_range) as f32
-                                        * usable_height_percent;
+        self.service.send(service::ServiceRequest::Positions);
+    }
 
-                                let body_top_percent = open_y_percent.min(close_y_percent);
-                                let body_height_percent =
-                                    (open_y_percent - close_y_percent).abs().max(0.1);
+    /// Re-read positions shortly after fills so the server's numbers replace the local
+    /// estimate. Fills arriving within the delay share one request.
+    fn schedule_positions_refresh(&mut self, cx: &mut Context<Self>) {
+        if self.positions_refresh_scheduled {
+            return;
+        }
+        self.positions_refresh_scheduled = true;
+        cx.spawn(async move |this, cx| {
+            cx.background_executor()
+                .timer(std::time::Duration::from_millis(
+                    account::FILL_REFRESH_DELAY_MS,
+                ))
+                .await;
+            let _ = this.update(cx, |terminal, _cx| {
+                terminal.positions_refresh_scheduled = false;
+                // No loading state: the table keeps showing the local update meanwhile
+                terminal.service.send(service::ServiceRequest::Positions);
+            });
+        })
+        .detach();
+    }
 
-                                // Determine if bullish or bearish
-                                let is_bullish = bar.close >= bar.open;
+    fn fetch_orders(&mut self, cx: &mut Context<Self>) {
+        self.account.orders_loading = true;
+        cx.notify();
 
-                                // Check if this is the most recent bar (live updating)
-                                let is_latest_bar = i == visible_bars.len() - 1 &&
-                                    end_index == self.chart.bars.len();
+        self.service
+            .send(service::ServiceRequest::Orders { until: None });
+    }
 
-                                let (color, fill_color) = if is_bullish {
-                                    (rgb(0x00cc66), rgb(0x00cc66))
-                                } else {
-                                    (rgb(0xff4444), rgb(0xff4444))
-                                };
+    /// Fetch the next (older) page of open orders and append it
+    fn load_more_orders(&mut self, cx: &mut Context<Self>) {
+        let Some(until) = self.account.orders_next_until else {
+            return;
+        };
+        if self.account.orders_loading_more {
+            return;
+        }
 
-                                // Open-Close body (thicker rectangle)
-                                let mut body_div = div()
-                                    .absolute()
-                                    .left(gpui::relative(x_percent / 100.0))
-                                    .top(gpui::relative(body_top_percent / 100.0))
-                                    .w(gpui::relative(bar_width_percent / 100.0))
-                                    .h(gpui::relative(body_height_percent / 100.0))
-                                    .bg(fill_color);
+        self.account.orders_loading_more = true;
+        cx.notify();
 
-                                // Add thicker border and glow effect for the latest bar
-                                if is_latest_bar {
-                                    body_div = body_div
-                                        .border_2()
-                                        .border_color(color)
-                                        .shadow_lg();
-                                } else {
-                                    body_div = body_div
-                                        .border_1()
-                                        .border_color(color);
-                                }
+        self.service
+            .send(service::ServiceRequest::Orders { until: Some(until) });
+    }
 
-                                body_div
-                            }))
-                            // Crosshair overlay
-                            .children(if self.chart.show_crosshair && self.chart.mouse_position.is_some() {
-                                let mouse_pos = self.chart.mouse_position.unwrap();
+    fn fetch_order_history(&mut self, cx: &mut Context<Self>) {
+        self.account.order_history_loading = true;
+        cx.notify();
 
-                                // Calculate price from mouse Y position
-                                // Grid lines use full height (0-100%) without padding
-                                let mouse_y_f32: f32 = mouse_pos.y.into();
-                                let chart_height =
-                                    self.chart.chart_bounds.map(|(_, h)| h).unwrap_or(400.0);
+        let since = self.account.history_range.start_time();
 
-                                // Account for 2px border on chart container
-                                let border_offset = 2.0;
-                                let adjusted_mouse_y = mouse_y_f32 - border_offset;
-                                let adjusted_chart_height = chart_height - (border_offset * 2.0);
+        self.service
+            .send(service::ServiceRequest::OrderHistory { since });
+    }
 
-                                let y_percent = (adjusted_mouse_y / adjusted_chart_height) * 100.0;
+    /// Write the loaded order history, fills included, to a CSV file
+    fn export_order_history(&mut self, cx: &mut Context<Self>) {
+        if self.account.order_history.is_empty() {
+            self.show_toast(
+                toast::ToastLevel::Info,
+                "No orders to export".to_string(),
+                cx,
+            );
+            return;
+        }
 
-                                // Convert Y position to price (matches grid line calculation)
-                                // Grid formula: y_percent = ((adjusted_max - price) / adjusted_range) * 100.0
-                                // Inverse: price = adjusted_max - (y_percent / 100.0 * adjusted_range)
-                                let price_at_cursor =
-                                    adjusted_max - ((y_percent / 100.0) as f64 * adjusted_range);
+        match export::export_orders(&self.account.order_history) {
+            Ok(path) => {
+                info!(
+                    "💾 Exported {} orders to {}",
+                    self.account.order_history.len(),
+                    path.display()
+                );
+                self.show_toast(
+                    toast::ToastLevel::Success,
+                    format!("Orders exported to {}", path.display()),
+                    cx,
+                );
+            }
+            Err(e) => {
+                error!("✗ Order export failed: {}", e);
+                self.show_toast(toast::ToastLevel::Error, e.to_string(), cx);
+            }
+        }
+    }
 
-                                // Debug: Print price calculation for calibration
-                                println!("Y%%: {:.1}, Price: ${:.2}, Range: ${:.2}-${:.2}",
-                                         y_percent, price_at_cursor, adjusted_min, adjusted_max);
-                                println!(">>> If crosshair shows LOWER than grid: INCREASE bottom_offset (line 879)");
-                                println!(">>> If crosshair shows HIGHER than grid: DECREASE bottom_offset (line 879)");
-                                println!(">>> Current bottom_offset: 394.0 - Adjust by 5-10px increments");
+    /// Write the current positions to a timestamped CSV file
+    fn export_positions(&mut self, cx: &mut Context<Self>) {
+        match export::export_positions(&self.account.positions) {
+            Ok(path) => {
+                info!(
+                    "💾 Exported {} positions to {}",
+                    self.account.positions.len(),
+                    path.display()
+                );
+                self.show_toast(
+                    toast::ToastLevel::Success,
+                    format!("Positions exported to {}", path.display()),
+                    cx,
+                );
+            }
+            Err(e) => {
+                error!("✗ Position export failed: {}", e);
+                self.show_toast(toast::ToastLevel::Error, e.to_string(), cx);
+            }
+        }
+    }
 
-                                // Calculate bar index from mouse X position
-                                let mouse_x_f32: f32 = mouse_pos.x.into();
-                                let chart_width =
-                                    self.chart.chart_bounds.map(|(w, _)| w).unwrap_or(800.0);
-                                let x_percent = (mouse_x_f32 / chart_width) * 100.0;
-
-                                let padding_left_percent = 5.0;
-                                let usable_width_percent = 100.0 - padding_left_percent - 5.0;
-                                let bar_index = ((x_percent - padding_left_percent)
-                                    / usable_width_percent
-                                    * visible_bar_count)
-                                    as usize;
+    /// Write the tax lot report to a CSV file
+    fn export_tax_lots(&mut self, cx: &mut Context<Self>) {
+        // A report missing older fills would carry wrong cost basis, dates and terms
+        if !self.account.activities_complete {
+            self.show_toast(
+                toast::ToastLevel::Error,
+                "Tax lots can't be exported until the full activity history has loaded".to_string(),
+                cx,
+            );
+            return;
+        }
+        let lots = tax::report(&self.account.lot_ledger);
+        if lots.is_empty() {
+            self.show_toast(toast::ToastLevel::Info, "No lots to export".to_string(), cx);
+            return;
+        }
 
-                                // Get the timestamp if valid bar index
-                                let timestamp_opt = if bar_index < visible_bars.len() {
-                                    Some(visible_bars[bar_index].timestamp)
-                                } else {
-                                    None
-                                };
+        match export::export_tax_lots(&lots) {
+            Ok(path) => {
+                info!("💾 Exported {} tax lots to {}", lots.len(), path.display());
+                self.show_toast(
+                    toast::ToastLevel::Success,
+                    format!("Tax lots exported to {}", path.display()),
+                    cx,
+                );
+            }
+            Err(e) => {
+                error!("✗ Tax lot export failed: {}", e);
+                self.show_toast(toast::ToastLevel::Error, e.to_string(), cx);
+            }
+        }
+    }
 
-                                let mut elements = vec![
-                                    // Vertical crosshair line
-                                    div()
-                                        .absolute()
-                                        .left(mouse_pos.x)
-                                        .top(px(0.0))
-                                        .w(px(1.0))
-                                        .h(gpui::relative(1.0))
-                                        .bg(gpui::rgba(0xFFFFFF40))
-                                        .into_any_element(),
-                                    // Horizontal crosshair line
-                                    div()
-                                        .absolute()
-                                        .left(px(0.0))
-                                        .top(mouse_pos.y)
-                                        .w(gpui::relative(1.0))
-                                        .h(px(1.0))
-                                        .bg(gpui::rgba(0xFFFFFF40))
-                                        .into_any_element(),
-                                ];
+    /// Symbols covered by the news panel: the charted symbol, plus favorites in watchlist scope
+    fn news_symbols(&self) -> Vec<String> {
+        let mut symbols = vec![self.chart.symbol.clone()];
+        if self.news.scope == news::NewsScope::Watchlist {
+            for symbol in &self.settings.favorite_symbols {
+                if !symbols.contains(symbol) {
+                    symbols.push(symbol.clone());
+                }
+            }
+        }
+        symbols
+    }
 
-                                // Price label on Y-axis (right side)
-                                // Always show price label for calibration (removed bounds check)
-                                elements.push(
-                                    div()
-                                        .absolute()
-                                        .right(px(5.0))
-                                        .top(mouse_pos.y - px(10.0))
-                                        .px_2()
-                                        .py_1()
-                                        .bg(rgb(0x1f6feb))
-                                        .border_1()
-                                        .border_color(rgb(0x388bfd))
-                                        .rounded_sm()
-                                        .text_xs()
-                                        .font_weight(FontWeight::SEMIBOLD)
-                                        .text_color(rgb(0xffffff))
-                                        .child(format!("${:.2}", price_at_cursor))
-                                        .into_any_element(),
-                                );
+    fn fetch_news(&mut self, cx: &mut Context<Self>) {
+        let symbols = self.news_symbols();
 
-                                // Timestamp label on X-axis (bottom)
-                                if let Some(timestamp) = timestamp_opt {
-                                    // Format timestamp for display (MM-DD HH:MM)
-                                    let display_time = timestamp.format("%m-%d %H:%M").to_string();
+        self.news.loading = true;
+        self.news.error = None;
+        self.news.symbols = symbols.clone();
+        cx.notify();
 
-                                    elements.push(
-                                        div()
-                                            .absolute()
-                                            .left(mouse_pos.x - px(40.0))
-                                            .bottom(px(5.0))
-                                            .px_2()
-                                            .py_1()
-                                            .bg(rgb(0x1f6feb))
-                                            .border_1()
-                                            .border_color(rgb(0x388bfd))
-                                            .rounded_sm()
-                                            .text_xs()
-                                            .font_weight(FontWeight::SEMIBOLD)
-                                            .text_color(rgb(0xffffff))
-                                            .child(display_time)
-                                            .into_any_element(),
-                                    );
-                                }
+        if self.news.stream_symbols != symbols {
+            self.start_news_stream(cx);
+        }
 
-                                elements
-                            } else {
-                                vec![]
-                            }),
-                    ),
-            )
-            .child(
-                // Scroll controls
-                div()
-                    .flex()
-                    .flex_row()
-                    .gap_2()
-                    .items_center()
-                    .justify_center()
-                    .p_2()
-                    .on_mouse_move(cx.listener(|this, _event, _window, cx| {
-                        // Hide crosshair when mouse is over scroll controls
-                        this.chart.show_crosshair = false;
-                        cx.notify();
-                    }))
-                    .child(
-                        div()
-                            .px_3()
-                            .py_1()
-                            .bg(rgb(0x2a2a2a))
-                            .border_1()
-                            .border_color(rgb(0x404040))
-                            .rounded_md()
-                            .cursor_pointer()
-                            .hover(|style| style.bg(rgb(0x3a3a3a)))
-                            .on_mouse_down(
-                                gpui::MouseButton::Left,
-                                cx.listener(|this, _event: &gpui::MouseDownEvent, _window, cx| {
-                                    if this.chart.chart_scroll_offset > 0.0 {
-                                        this.chart.chart_scroll_offset =
-                                            (this.chart.chart_scroll_offset - 50.0).max(0.0);
-                                        cx.notify();
-                                    }
-                                }),
-                            )
-                            .child("← Previous 50"),
-                    )
-                    .child(
-                        div()
-                            .flex()
-                            .flex_row()
-                            .gap_2()
-                            .items_center()
-                            .text_sm()
-                            .text_color(rgb(0x808080))
-                            .child(format!(
-                                "Showing bars {}-{} of {} | Zoom: {} bars",
-                                start_index + 1,
-                                end_index,
-                                self.chart.bars.len(),
-                                self.chart.bars_per_screen
-                            ))
-                            .when(end_index == self.chart.bars.len() && self.chart.market_data_connected, |this| {
-                                this.child(
-                                    div()
-                                        .px_2()
-                                        .py_0p5()
-                                        .bg(rgb(0x238636))
-                                        .rounded_sm()
-                                        .text_xs()
-                                        .font_weight(FontWeight::BOLD)
-                                        .text_color(rgb(0xffffff))
-                                        .child("● LIVE")
-                                )
-                            })
-                    )
-                    .child(
-                        div()
-                            .px_3()
-                            .py_1()
-                            .bg(rgb(0x2a2a2a))
-                            .border_1()
-                            .border_color(rgb(0x404040))
-                            .rounded_md()
-                            .cursor_pointer()
-                            .hover(|style| style.bg(rgb(0x3a3a3a)))
-                            .on_mouse_down(
-                                gpui::MouseButton::Left,
-                                cx.listener(|this, _event: &gpui::MouseDownEvent, _window, cx| {
-                                    let max_offset =
-                                        this.chart.bars.len().saturating_sub(this.chart.bars_per_screen) as f32;
-                                    if this.chart.chart_scroll_offset < max_offset {
-                                        this.chart.chart_scroll_offset =
-                                            (this.chart.chart_scroll_offset + 50.0).min(max_offset);
-                                        cx.notify();
-                                    }
-                                }),
-                            )
-                            .child("Next 50 →"),
-                    )
-                    .child(
-                        div()
-                            .px_3()
-                            .py_1()
-                            .bg(rgb(0x1f6feb))
-                            .border_1()
-                            .border_color(rgb(0x404040))
-                            .rounded_md()
-                            .cursor_pointer()
-                            .hover(|style| style.bg(rgb(0x2a7ffc)))
-                            .on_mouse_down(
-                                gpui::MouseButton::Left,
-                                cx.listener(|this, _event: &gpui::MouseDownEvent, _window, cx| {
-                                    // Show most recent bars
-                                    this.chart.chart_scroll_offset =
-                                        this.chart.bars.len().saturating_sub(this.chart.bars_per_screen) as f32;
-                                    cx.notify();
-                                }),
-                            )
-                            .child("Show Latest →→"),
-                    ),
-            )
-            .child(
-                // Price statistics
-                div()
-                    .flex()
-                    .gap_6()
-                    .text_sm()
-                    .text_color(rgb(0xcccccc))
-                    .child(div().child(format!("High: ${:.2}", max_price)))
-                    .child(div().child(format!("Low: ${:.2}", min_price)))
-                    .child(div().child(format!("Range: ${:.2}", price_range)))
-                    .child(div().child(format!("Bars: {}", self.chart.bars.len())))
-                    .when_some(self.chart.bars.last(), |this, last_bar| {
-                        let is_bullish = last_bar.close >= last_bar.open;
-                        let color = if is_bullish {
-                            rgb(0x00cc66)
-                        } else {
-                            rgb(0xff4444)
-                        };
-                        this.child(
-                            div()
-                                .text_color(color)
-                                .child(format!("Last Close: ${:.2}", last_bar.close)),
-                        )
-                    }),
-            )
+        self.service.send(service::ServiceRequest::News(symbols));
     }
-}
-
-impl Render for TradingTerminal {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let timeframe_display = match self.chart.timeframe.as_str() {
-            "1Min" => "1 Minute",
-            "5Min" => "5 Minutes",
-            "15Min" => "15 Minutes",
-            "1Hour" => "1 Hour",
-            "1Day" => "Daily",
-            "1Week" => "Weekly",
-            "1Month" => "Monthly",
-            _ => &self.chart.timeframe,
-        };
 
-        div()
-            .grid()
-            .grid_cols(8)
-            .grid_rows(1)
-            .bg(rgb(0x0d1117))
-            .size_full()
-            .min_w(px(1024.0))
-            .gap_4()
-            .child(
-                // Main content area (left column) - flex layout for header/chart/footer
-                div()
-                    .col_span(7)
-                    .flex()
-                    .flex_col()
-                    .p_8()
-                    .gap_6()
-                    .track_focus(&self.focus_handle)
-                    .on_key_down(cx.listener(|this, event: &gpui::KeyDownEvent, _, cx| {
-                        // Handle symbol input
-                        if this.chart.input_focused {
-                            let key = event.keystroke.key.as_str();
-
-                            if key == "enter" {
-                                this.submit_symbol(cx);
-                            } else if key == "backspace" {
-                                this.handle_backspace(cx);
-                            } else if key == "escape" {
-                                this.chart.input_focused = false;
-                                cx.notify();
-                            } else if let Some(key_char) = &event.keystroke.key_char {
-                                if key_char.len() == 1
-                                    && key_char.chars().all(|c| c.is_alphanumeric())
-                                {
-                                    this.handle_input(key_char, cx);
-                                }
-                            }
-                            return;
-                        }
+    /// (Re)start the news stream for the panel's symbols
+    fn start_news_stream(&mut self, cx: &mut Context<Self>) {
+        info!("🚀 Starting News WebSocket stream connection...");
 
-                        // Handle quantity input
-                        if this.account.quantity_focused {
-                            let key = event.keystroke.key.as_str();
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel::<stream::StreamUpdate>();
 
-                            if key == "enter" {
-                                this.account.quantity_focused = false;
-                                cx.notify();
-                            } else if key == "backspace" {
-                                this.account.order_quantity.pop();
-                                cx.notify();
-                            } else if key == "escape" {
-                                this.account.quantity_focused = false;
-                                cx.notify();
-                            } else if let Some(key_char) = &event.keystroke.key_char {
-                                if key_char.len() == 1
-                                    && (key_char.chars().all(|c| c.is_numeric()) || key_char == ".")
-                                {
-                                    this.account.order_quantity.push_str(key_char);
-                                    cx.notify();
-                                }
-                            }
-                            return;
-                        }
+        self.news.stream_symbols = self.news.symbols.clone();
+        stream::MarketDataStreamManager::start_news_stream(
+            sender,
+            self.news.stream_symbols.clone(),
+        );
 
-                        // Handle price input
-                        if this.account.price_focused {
-                            let key = event.keystroke.key.as_str();
+        // Same retirement scheme as the market data streams
+        self.news.stream_generation += 1;
+        let generation = self.news.stream_generation;
 
-                            if key == "enter" {
-                                this.account.price_focused = false;
-                                cx.notify();
-                            } else if key == "backspace" {
-                                this.account.order_limit_price.pop();
-                                cx.notify();
-                            } else if key == "escape" {
-                                this.account.price_focused = false;
-                                cx.notify();
-                            } else if let Some(key_char) = &event.keystroke.key_char {
-                                if key_char.len() == 1
-                                    && (key_char.chars().all(|c| c.is_numeric()) || key_char == ".")
-                                {
-                                    this.account.order_limit_price.push_str(key_char);
-                                    cx.notify();
+        cx.spawn(async move |this, cx| {
+            while let Some(update) = receiver.recv().await {
+                let is_current = this.update(cx, |terminal, cx| {
+                    if terminal.news.stream_generation != generation {
+                        return false;
+                    }
+                    terminal.handle_stream_update(update, cx);
+                    true
+                });
+                if !matches!(is_current, Ok(true)) {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
+
+    /// Reload the data behind the active footer tab
+    fn refresh_footer_tab(&mut self, cx: &mut Context<Self>) {
+        match self.account.active_footer_tab {
+            account::FooterTab::Account => {
+                self.fetch_account(cx);
+                self.fetch_account_config();
+            }
+            account::FooterTab::Positions => self.fetch_positions(cx),
+            account::FooterTab::Orders => self.fetch_orders(cx),
+            account::FooterTab::History => self.fetch_order_history(cx),
+            account::FooterTab::Activities | account::FooterTab::TaxLots => {
+                self.fetch_activities(cx)
+            }
+            account::FooterTab::Performance => {
+                self.performance.benchmark_loaded_on = None;
+                self.fetch_activities(cx);
+                self.fetch_benchmark(cx);
+            }
+            account::FooterTab::Backtest => self.run_backtest(cx),
+            // Simulated state is local; just re-render
+            account::FooterTab::Simulator => cx.notify(),
+            account::FooterTab::News => self.fetch_news(cx),
+            account::FooterTab::Heatmap => self.fetch_heatmap(cx),
+            account::FooterTab::Movers => self.fetch_screener(cx),
+            account::FooterTab::Analytics => {
+                self.analytics.betas.clear();
+                self.analytics.benchmark_price = None;
+                self.fetch_positions(cx);
+                self.fetch_analytics(cx);
+            }
+            // The log buffer is live; just re-render
+            account::FooterTab::Logs => cx.notify(),
+        }
+    }
+
+    /// Symbols in the heatmap's universe
+    fn heatmap_symbols(&self) -> Vec<String> {
+        universe::symbols(
+            self.heatmap.universe,
+            &self.config.heatmap.symbols,
+            &self.settings.favorite_symbols,
+        )
+    }
+
+    fn fetch_heatmap(&mut self, cx: &mut Context<Self>) {
+        let symbols = self.heatmap_symbols();
+
+        self.heatmap.loading = true;
+        self.heatmap.error = None;
+        self.heatmap.symbols = symbols.clone();
+        cx.notify();
+
+        let data_feed = self.config.market_data.feed;
+        cx.spawn(async move |this, cx| {
+            let request_symbols = symbols.clone();
+            let result = cx
+                .background_executor()
+                .spawn(async move { snapshot::fetch_snapshots_sync(&request_symbols, data_feed) })
+                .await;
+
+            let _ = this.update(cx, |terminal, cx| {
+                // Ignore stale responses for a previous universe
+                if terminal.heatmap.symbols != symbols {
+                    return;
+                }
+                match result {
+                    Ok(snapshots) => {
+                        info!("✓ Loaded {} heatmap snapshots", snapshots.len());
+                        terminal.heatmap.snapshots = snapshots;
+                    }
+                    // Refreshed periodically, so the error is shown in the tab rather than
+                    // as a toast each time
+                    Err(error) => {
+                        error!("✗ Error fetching heatmap snapshots: {}", error);
+                        terminal.heatmap.error = Some(error.to_string());
+                    }
+                }
+                terminal.heatmap.updated_at = Some(chrono::Local::now());
+                terminal.heatmap.loading = false;
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Symbols in the screener's universe
+    fn screener_symbols(&self) -> Vec<String> {
+        universe::symbols(
+            self.screener.universe,
+            &self.config.screener.symbols,
+            &self.settings.favorite_symbols,
+        )
+    }
+
+    fn fetch_screener(&mut self, cx: &mut Context<Self>) {
+        let symbols = self.screener_symbols();
+
+        self.screener.loading = true;
+        self.screener.error = None;
+        self.screener.symbols = symbols.clone();
+        cx.notify();
+
+        let data_feed = self.config.market_data.feed;
+        cx.spawn(async move |this, cx| {
+            let request_symbols = symbols.clone();
+            let result = cx
+                .background_executor()
+                .spawn(async move { snapshot::fetch_snapshots_sync(&request_symbols, data_feed) })
+                .await;
+
+            let _ = this.update(cx, |terminal, cx| {
+                // Ignore stale responses for a previous universe
+                if terminal.screener.symbols != symbols {
+                    return;
+                }
+                match result {
+                    Ok(snapshots) => {
+                        info!("✓ Loaded {} screener snapshots", snapshots.len());
+                        terminal.screener.snapshots = snapshots;
+                        terminal.fetch_screener_smas(cx);
+                    }
+                    Err(error) => {
+                        error!("✗ Error fetching screener snapshots: {}", error);
+                        terminal.screener.error = Some(error.to_string());
+                    }
+                }
+                terminal.screener.updated_at = Some(chrono::Local::now());
+                terminal.screener.loading = false;
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Filter the movers list by the form's bounds
+    fn apply_screen_filter(&mut self, cx: &mut Context<Self>) {
+        match self
+            .screener
+            .form
+            .filter(self.screener.universe, self.screener.sort)
+        {
+            Ok(filter) => {
+                self.screener.filter = Some(filter);
+                self.set_input_focus(None);
+                self.fetch_screener_smas(cx);
+            }
+            Err(message) => self.show_toast(toast::ToastLevel::Warning, message, cx),
+        }
+        cx.notify();
+    }
+
+    fn clear_screen_filter(&mut self, cx: &mut Context<Self>) {
+        self.screener.filter = None;
+        self.screener.form.clear();
+        self.set_input_focus(None);
+        cx.notify();
+    }
+
+    /// Save the form as a named screen and apply it
+    fn save_screen(&mut self, cx: &mut Context<Self>) {
+        if self.screener.form.name.trim().is_empty() {
+            self.show_toast(
+                toast::ToastLevel::Warning,
+                "Enter a screen name".to_string(),
+                cx,
+            );
+            return;
+        }
+        self.apply_screen_filter(cx);
+        let Some(mut filter) = self.screener.filter.clone() else {
+            return;
+        };
+        let name = filter.name.clone();
+        // Overwriting a screen keeps its alert settings; its matches start over
+        if let Some(existing) = self
+            .settings
+            .saved_screens
+            .iter()
+            .find(|screen| screen.name == name)
+        {
+            filter.scan = existing.scan;
+            filter.add_to_watchlist = existing.add_to_watchlist;
+        }
+        self.screener.reset_scan(&name);
+        self.settings.upsert_screen(filter);
+        self.settings.save();
+        info!("🔎 Saved screen {}", name);
+        self.show_toast(
+            toast::ToastLevel::Success,
+            format!("Screen {} saved", name),
+            cx,
+        );
+    }
+
+    /// Apply a saved screen, switching to its universe and ranking
+    fn load_screen(&mut self, name: &str, cx: &mut Context<Self>) {
+        let Some(filter) = self
+            .settings
+            .saved_screens
+            .iter()
+            .find(|screen| screen.name == name)
+            .cloned()
+        else {
+            return;
+        };
+        self.screener.form.load(&filter);
+        self.screener.sort = filter.sort;
+        let universe_changed = self.screener.universe != filter.universe;
+        self.screener.universe = filter.universe;
+        self.screener.filter = Some(filter);
+        if universe_changed {
+            // SMAs are fetched once the new universe's snapshots are in
+            self.fetch_screener(cx);
+        } else {
+            self.fetch_screener_smas(cx);
+        }
+        cx.notify();
+    }
+
+    fn remove_screen(&mut self, name: &str, cx: &mut Context<Self>) {
+        self.settings.remove_screen(name);
+        self.screener.reset_scan(name);
+        self.settings.save();
+        cx.notify();
+    }
+
+    /// Turn a saved screen's scanning alert on or off
+    fn toggle_screen_scan(&mut self, name: &str, cx: &mut Context<Self>) {
+        if let Some(screen) = self
+            .settings
+            .saved_screens
+            .iter_mut()
+            .find(|screen| screen.name == name)
+        {
+            screen.scan = !screen.scan;
+            info!(
+                "🔎 Scanning alert for {} {}",
+                name,
+                if screen.scan { "on" } else { "off" }
+            );
+        }
+        self.screener.reset_scan(name);
+        self.settings.save();
+        // Scan right away so the baseline is recorded
+        self.screener.scanned_at = None;
+        cx.notify();
+    }
+
+    /// Whether a scanning screen stars the symbols it finds
+    fn toggle_screen_watchlist(&mut self, name: &str, cx: &mut Context<Self>) {
+        if let Some(screen) = self
+            .settings
+            .saved_screens
+            .iter_mut()
+            .find(|screen| screen.name == name)
+        {
+            screen.add_to_watchlist = !screen.add_to_watchlist;
+        }
+        self.settings.save();
+        cx.notify();
+    }
+
+    /// Fetch snapshots for every scanning screen's universe, then alert on new matches
+    fn scan_screens(&mut self, cx: &mut Context<Self>) {
+        let mut symbols: Vec<String> = self
+            .settings
+            .saved_screens
+            .iter()
+            .filter(|screen| screen.scan)
+            .flat_map(|screen| {
+                universe::symbols(
+                    screen.universe,
+                    &self.config.screener.symbols,
+                    &self.settings.favorite_symbols,
+                )
+            })
+            .collect();
+        symbols.sort();
+        symbols.dedup();
+        if symbols.is_empty() {
+            return;
+        }
+        self.screener.scanning = true;
+
+        let data_feed = self.config.market_data.feed;
+        cx.spawn(async move |this, cx| {
+            let result = cx
+                .background_executor()
+                .spawn(async move { snapshot::fetch_snapshots_sync(&symbols, data_feed) })
+                .await;
+
+            let _ = this.update(cx, |terminal, cx| {
+                terminal.screener.scanning = false;
+                terminal.screener.scanned_at = Some(chrono::Local::now());
+                match result {
+                    Ok(snapshots) => {
+                        debug!("🔎 Scanned {} symbols for screen alerts", snapshots.len());
+                        terminal.screener.scan_snapshots = snapshots;
+                        terminal.check_screen_alerts(cx);
+                    }
+                    // Tried again on the next scan
+                    Err(error) => warn!("⚠️  Screen alert scan failed: {}", error),
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Notify about symbols that newly pass a scanning screen, starring them if asked
+    fn check_screen_alerts(&mut self, cx: &mut Context<Self>) {
+        let screens: Vec<screener::ScreenFilter> = self
+            .settings
+            .saved_screens
+            .iter()
+            .filter(|screen| screen.scan)
+            .cloned()
+            .collect();
+
+        let mut fired = false;
+        let mut starred = false;
+        for screen in screens {
+            let universe = universe::symbols(
+                screen.universe,
+                &self.config.screener.symbols,
+                &self.settings.favorite_symbols,
+            );
+            let Some(added) = self.screener.new_matches(&screen, &universe) else {
+                continue;
+            };
+            for symbol in added {
+                let detail = self
+                    .screener
+                    .scan_snapshots
+                    .iter()
+                    .find(|snapshot| snapshot.symbol == symbol)
+                    .and_then(|snapshot| {
+                        let price = snapshot.price()?;
+                        Some(match snapshot.day_change_percent() {
+                            Some(percent) => format!("Last ${:.2} ({:+.2}%)", price, percent),
+                            None => format!("Last ${:.2}", price),
+                        })
+                    })
+                    .unwrap_or_default();
+                info!("🔎 {} now matches screen {}", symbol, screen.name);
+                self.notify(
+                    notifications::NotificationKind::Alert,
+                    format!("{} matches {}", symbol, screen.name),
+                    detail,
+                );
+                fired = true;
+                if screen.add_to_watchlist && !self.settings.is_favorite(&symbol) {
+                    self.settings.toggle_favorite(&symbol);
+                    starred = true;
+                }
+            }
+        }
+
+        if fired {
+            self.play_sound(sound::SoundCue::Alert);
+        }
+        if starred {
+            self.settings.save();
+            self.sync_market_data();
+        }
+        // Screens with an SMA condition are checked once their daily bars are in
+        self.fetch_screener_smas(cx);
+    }
+
+    /// Load 20-day SMAs for the symbols an SMA filter still needs. Daily bars are fetched
+    /// one symbol at a time (the rate limiter spaces them out) and cached for the day.
+    fn fetch_screener_smas(&mut self, cx: &mut Context<Self>) {
+        if self.screener.sma_loading {
+            return;
+        }
+        let symbols = self.screener.missing_smas(&self.settings.saved_screens);
+        if symbols.is_empty() {
+            return;
+        }
+        self.screener.sma_loading = true;
+
+        let data_feed = self.config.market_data.feed;
+        cx.spawn(async move |this, cx| {
+            let smas = cx
+                .background_executor()
+                .spawn(async move {
+                    symbols
+                        .into_iter()
+                        .filter_map(|symbol| {
+                            let period = screener::SMA_PERIOD;
+                            match fetch_bars_sync(&symbol, "1Day", period as u32, data_feed) {
+                                Ok(bars) => screener::sma(&bars, period).map(|sma| (symbol, sma)),
+                                Err(error) => {
+                                    warn!(
+                                        "⚠️  Failed to load daily bars for {}: {}",
+                                        symbol, error
+                                    );
+                                    None
                                 }
                             }
-                            return;
-                        }
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .await;
 
-                        // Handle bar limit input
-                        if this.chart.bar_limit_focused {
-                            let key = event.keystroke.key.as_str();
+            let _ = this.update(cx, |terminal, cx| {
+                info!("✓ Loaded {} 20-day SMAs for the screener", smas.len());
+                let today = chrono::Local::now().date_naive();
+                terminal
+                    .screener
+                    .sma_cache
+                    .extend(smas.into_iter().map(|(symbol, sma)| (symbol, (today, sma))));
+                terminal.screener.sma_loading = false;
+                cx.notify();
+            });
+        })
+        .detach();
+    }
 
-                            if key == "enter" {
-                                this.fetch_bars(cx);
-                            } else if key == "backspace" {
-                                this.chart.bar_limit.pop();
-                                cx.notify();
-                            } else if key == "escape" {
-                                this.chart.bar_limit_focused = false;
-                                cx.notify();
-                            } else if let Some(key_char) = &event.keystroke.key_char {
-                                if key_char.len() == 1 && key_char.chars().all(|c| c.is_numeric()) {
-                                    this.chart.bar_limit.push_str(key_char);
-                                    cx.notify();
+    /// Regress the positions' daily returns against SPY for betas not computed today
+    fn fetch_analytics(&mut self, cx: &mut Context<Self>) {
+        if self.analytics.loading {
+            return;
+        }
+        let symbols = self.analytics.missing(&self.account.positions);
+        if symbols.is_empty() && self.analytics.benchmark_price.is_some() {
+            return;
+        }
+        self.analytics.loading = true;
+        self.analytics.error = None;
+        cx.notify();
+
+        let data_feed = self.config.market_data.feed;
+        cx.spawn(async move |this, cx| {
+            let result = cx
+                .background_executor()
+                .spawn(async move {
+                    let benchmark = fetch_bars_sync(
+                        exposure::BENCHMARK,
+                        "1Day",
+                        exposure::BETA_BARS,
+                        data_feed,
+                    )?;
+                    // One request per symbol; the rate limiter spaces them out
+                    let betas = symbols
+                        .into_iter()
+                        .map(|symbol| {
+                            let beta = match fetch_bars_sync(
+                                &symbol,
+                                "1Day",
+                                exposure::BETA_BARS,
+                                data_feed,
+                            ) {
+                                Ok(bars) => exposure::beta(&bars, &benchmark),
+                                Err(error) => {
+                                    warn!(
+                                        "⚠️  Failed to load daily bars for {}: {}",
+                                        symbol, error
+                                    );
+                                    None
                                 }
-                            }
-                            return;
-                        }
-                    }))
-                    .child(
-                        // Header
-                        div()
-                            .flex()
-                            .flex_shrink_0()
-                            .items_center()
-                            .justify_between()
-                            .on_mouse_move(cx.listener(|this, _event, _window, cx| {
-                                // Hide crosshair when mouse is over header
-                                this.chart.show_crosshair = false;
-                                cx.notify();
-                            }))
-                            .child(
-                                // Controls: Symbol input and Timeframe selector
-                                div()
-                                    .flex()
-                                    .gap_4()
-                                    .items_end()
-                                    .child(
-                                        // Symbol input
-                                        div()
-                                            .flex()
-                                            .flex_col()
-                                            .gap_2()
-                                            .child(
-                                                div()
-                                                    .text_sm()
-                                                    .font_weight(FontWeight::SEMIBOLD)
-                                                    .text_color(rgb(0xffffff))
-                                                    .child("Symbol:"),
-                                            )
-                                            .child(
-                                                div()
-                                                    .flex()
-                                                    .gap_2()
-                                                    .child(
-                                                        div()
-                                                            .id("symbol-input")
-                                                            .px_4()
-                                                            .py_2()
-                                                            .bg(if self.chart.input_focused {
-                                                                rgb(0x1f2937)
-                                                            } else {
-                                                                rgb(0x161b22)
-                                                            })
-                                                            .border_1()
-                                                            .border_color(if self.chart.input_focused {
-                                                                rgb(0x1f6feb)
-                                                            } else {
-                                                                rgb(0x30363d)
-                                                            })
-                                                            .rounded_lg()
-                                                            .text_color(rgb(0xffffff))
-                                                            .min_w(px(120.0))
-                                                            .cursor_text()
-                                                            .child(if self.chart.input_focused {
-                                                                format!("{}|", self.chart.symbol_input)
-                                                            } else if self.chart.symbol_input.is_empty() {
-                                                                "Enter symbol...".to_string()
-                                                            } else {
-                                                                self.chart.symbol_input.clone()
-                                                            })
-                                                            .on_click(cx.listener(
-                                                                |this, _, _window, cx| {
-                                                                    this.chart.input_focused = true;
-                                                                    _window
-                                                                        .focus(&this.focus_handle);
-                                                                    cx.notify();
-                                                                },
-                                                            )),
-                                                    )
-                                                    .child(
-                                                        div()
-                                                            .id("update-symbol-button")
-                                                            .px_4()
-                                                            .py_2()
-                                                            .bg(rgb(0x1f6feb))
-                                                            .rounded_lg()
-                                                            .text_color(rgb(0xffffff))
-                                                            .font_weight(FontWeight::SEMIBOLD)
-                                                            .cursor_pointer()
-                                                            .hover(|style| style.bg(rgb(0x388bfd)))
-                                                            .child("Update")
-                                                            .on_click(cx.listener(
-                                                                |this, _, _, cx| {
-                                                                    this.submit_symbol(cx);
-                                                                },
-                                                            )),
-                                                    ),
-                                            ),
+                            };
+                            (symbol, beta)
+                        })
+                        .collect::<Vec<_>>();
+                    Ok::<_, error::TerminalError>((benchmark.last().map(|bar| bar.close), betas))
+                })
+                .await;
+
+            let _ = this.update(cx, |terminal, cx| {
+                match result {
+                    Ok((benchmark_price, betas)) => {
+                        info!("✓ Computed {} position betas", betas.len());
+                        let today = chrono::Local::now().date_naive();
+                        terminal.analytics.benchmark_price = benchmark_price;
+                        terminal.analytics.betas.extend(
+                            betas
+                                .into_iter()
+                                .map(|(symbol, beta)| (symbol, (today, beta))),
+                        );
+                    }
+                    Err(error) => {
+                        error!("✗ Error fetching {} bars: {}", exposure::BENCHMARK, error);
+                        terminal.analytics.error = Some(error.to_string());
+                    }
+                }
+                terminal.analytics.loading = false;
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Backtest the selected strategy on the main chart's symbol and timeframe, with bars
+    /// from the bar cache
+    fn run_backtest(&mut self, cx: &mut Context<Self>) {
+        if self.backtest.running {
+            return;
+        }
+        self.backtest.running = true;
+        self.backtest.error = None;
+        cx.notify();
+
+        let symbol = self.chart.symbol.clone();
+        let timeframe = self.chart.timeframe.clone();
+        let bar_count = self.backtest.bar_count;
+        let strategy = self.backtest.strategy;
+        let config = self.config.backtest.clone();
+        let data_feed = self.config.market_data.feed;
+        cx.spawn(async move |this, cx| {
+            let result = cx
+                .background_executor()
+                .spawn(async move {
+                    let bars = bar_cache::load_bars(&symbol, &timeframe, bar_count, data_feed)
+                        .map_err(|error| error.to_string())?;
+                    backtest::run(&symbol, &bars, strategy, &config)
+                })
+                .await;
+
+            let _ = this.update(cx, |terminal, cx| {
+                match result {
+                    Ok(report) => {
+                        info!(
+                            "✓ Backtested {} on {}: {} trades, {:+.2}%",
+                            report.strategy.label(),
+                            report.symbol,
+                            report.trades.len(),
+                            report.return_percent()
+                        );
+                        terminal.backtest.report = Some(report);
+                    }
+                    Err(error) => {
+                        error!("✗ Backtest failed: {}", error);
+                        terminal.backtest.error = Some(error);
+                    }
+                }
+                terminal.backtest.running = false;
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Daily bars of the configured benchmark for the Performance tab's overlay, once a day
+    fn fetch_benchmark(&mut self, cx: &mut Context<Self>) {
+        let symbol = self.config.performance.benchmark.clone();
+        if self.performance.benchmark_loading || !self.performance.benchmark_stale(&symbol) {
+            return;
+        }
+        self.performance.benchmark_loading = true;
+        self.performance.benchmark_error = None;
+        cx.notify();
+
+        let data_feed = self.config.market_data.feed;
+        cx.spawn(async move |this, cx| {
+            let request_symbol = symbol.clone();
+            let result = cx
+                .background_executor()
+                .spawn(async move {
+                    fetch_bars_sync(
+                        &request_symbol,
+                        "1Day",
+                        performance::BENCHMARK_BARS,
+                        data_feed,
+                    )
+                })
+                .await;
+
+            let _ = this.update(cx, |terminal, cx| {
+                match result {
+                    Ok(bars) => {
+                        info!(
+                            "✓ Loaded {} daily {} bars for the benchmark",
+                            bars.len(),
+                            symbol
+                        );
+                        terminal.performance.benchmark_bars = bars;
+                        terminal.performance.benchmark_symbol = symbol;
+                        terminal.performance.benchmark_loaded_on =
+                            Some(chrono::Local::now().date_naive());
+                    }
+                    Err(error) => {
+                        error!("✗ Error fetching benchmark {} bars: {}", symbol, error);
+                        terminal.performance.benchmark_error = Some(error.to_string());
+                    }
+                }
+                terminal.performance.benchmark_loading = false;
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Whether any text input currently takes keystrokes
+    fn text_input_focused(&self) -> bool {
+        self.focus.focused().is_some()
+    }
+
+    /// Buy/sell hotkey pressed: submit right away or ask for confirmation
+    fn hotkey_order(&mut self, side: hotkeys::HotkeySide, cx: &mut Context<Self>) {
+        if !self.settings.hotkeys_enabled {
+            self.show_toast(
+                toast::ToastLevel::Info,
+                "Trading hotkeys are disabled".to_string(),
+                cx,
+            );
+            return;
+        }
+        if self.trading_halted() {
+            self.show_toast(
+                toast::ToastLevel::Error,
+                "Trading hotkeys are disabled: daily loss limit reached".to_string(),
+                cx,
+            );
+            return;
+        }
+
+        if self.settings.hotkey_skip_confirmation {
+            self.submit_hotkey_order(side, cx);
+        } else {
+            self.hotkeys.pending = Some(side);
+            cx.notify();
+        }
+    }
+
+    /// Submit a market order of the configured hotkey size for the charted symbol
+    fn submit_hotkey_order(&mut self, side: hotkeys::HotkeySide, cx: &mut Context<Self>) {
+        self.hotkeys.pending = None;
+
+        let qty = match self.settings.hotkey_quantity.trim().parse::<f64>() {
+            Ok(q) if q > 0.0 => q,
+            _ => {
+                self.show_toast(
+                    toast::ToastLevel::Error,
+                    "Set a hotkey quantity first".to_string(),
+                    cx,
+                );
+                return;
+            }
+        };
+
+        let description = format!("{} {} {} market", side.label(), qty, self.chart.symbol);
+        if !self.check_risk_limits(chart::ChartId::Main, qty, None, cx) {
+            return;
+        }
+
+        let symbol = self.chart.symbol.clone();
+        info!("⌨️  Hotkey {} {} {}", side.label(), qty, symbol);
+        self.guard_live_order(
+            order_guard::GuardedOrder::Hotkey(side),
+            qty,
+            None,
+            description,
+            Self::market_order(symbol, side, qty),
+            cx,
+        );
+    }
+
+    /// Send an order to Alpaca, or in forward-test mode to the simulated broker. Simulated
+    /// orders are answered right away like a submitted one and fill on later trade ticks.
+    fn submit_ticket(
+        &mut self,
+        origin: service::OrderOrigin,
+        ticket: service::OrderTicket,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.simulator.enabled {
+            self.service
+                .send(service::ServiceRequest::SubmitOrder { origin, ticket });
+            return;
+        }
+
+        let price = self
+            .last_prices
+            .get(&ticket.symbol)
+            .and_then(|price| price.parse::<f64>().ok());
+        let result = self
+            .simulator
+            .place(&ticket, origin.label(), price)
+            .map(|id| format!("SIM-{}", id))
+            .map_err(error::TerminalError::Rejected);
+        self.simulator.save();
+        // Trades of the order's symbol are needed to fill it
+        self.sync_market_data();
+        self.handle_service_response(
+            service::ServiceResponse::OrderSubmitted {
+                origin,
+                symbol: ticket.symbol,
+                qty: ticket.qty,
+                result,
+            },
+            cx,
+        );
+    }
+
+    /// Fill the simulated orders a trade reaches
+    fn run_simulator(&mut self, symbol: &str, price: f64, cx: &mut Context<Self>) {
+        let events = self.simulator.on_trade(symbol, price);
+        if events.is_empty() {
+            return;
+        }
+
+        for event in events {
+            match event {
+                simulator::SimEvent::Filled(fill) => {
+                    let message = format!(
+                        "{} {} {} @ ${:.2}",
+                        fill.side.label(),
+                        fill.qty,
+                        fill.symbol,
+                        fill.price
+                    );
+                    self.notify(
+                        notifications::NotificationKind::Fill,
+                        "Simulated fill".to_string(),
+                        format!("{} ({})", message, fill.source),
+                    );
+                    self.show_toast(
+                        toast::ToastLevel::Success,
+                        format!("🧪 Simulated fill: {}", message),
+                        cx,
+                    );
+                }
+                simulator::SimEvent::Rejected { order, reason } => self.show_toast(
+                    toast::ToastLevel::Error,
+                    format!("🧪 Simulated {} rejected: {}", order.describe(), reason),
+                    cx,
+                ),
+            }
+        }
+        self.simulator.save();
+        // Filled orders may leave symbols nothing else needs
+        self.sync_market_data();
+        cx.notify();
+    }
+
+    fn toggle_simulator(&mut self, cx: &mut Context<Self>) {
+        self.simulator.enabled = !self.simulator.enabled;
+        self.simulator.save();
+        info!(
+            "🧪 Forward-test mode {}",
+            if self.simulator.enabled { "on" } else { "off" }
+        );
+        self.show_toast(
+            toast::ToastLevel::Info,
+            if self.simulator.enabled {
+                "Forward-test mode on: new orders fill in the simulator".to_string()
+            } else {
+                "Forward-test mode off: orders go to Alpaca again".to_string()
+            },
+            cx,
+        );
+        cx.notify();
+    }
+
+    fn reset_simulator(&mut self, cx: &mut Context<Self>) {
+        self.simulator.reset(self.config.simulator.starting_cash);
+        self.simulator.save();
+        self.sync_market_data();
+        cx.notify();
+    }
+
+    fn cancel_simulated_order(&mut self, id: u64, cx: &mut Context<Self>) {
+        if self.simulator.cancel(id) {
+            info!("🧪 Canceled simulated order #{}", id);
+            self.simulator.save();
+            self.sync_market_data();
+            cx.notify();
+        }
+    }
+
+    /// Flatten a simulated position at the next trade (works with forward-test mode off)
+    fn close_simulated_position(&mut self, symbol: String, cx: &mut Context<Self>) {
+        let Some(position) = self
+            .simulator
+            .positions
+            .iter()
+            .find(|position| position.symbol == symbol)
+        else {
+            return;
+        };
+        let ticket = service::OrderTicket {
+            side: if position.qty > 0.0 {
+                OrderSide::Sell
+            } else {
+                OrderSide::Buy
+            },
+            qty: position.qty.abs(),
+            symbol,
+            order_type: OrderType::Market,
+            notional: None,
+            limit_price: None,
+            time_in_force: OrderTimeInForce::Gtc,
+        };
+        // Covering a short is paid from cash, so it's checked like any buy
+        let price = self
+            .last_prices
+            .get(&ticket.symbol)
+            .and_then(|price| price.parse::<f64>().ok());
+        if let Err(error) = self.simulator.place(&ticket, "Close".to_string(), price) {
+            self.show_toast(
+                toast::ToastLevel::Error,
+                format!("🧪 Can't close {}: {}", ticket.symbol, error),
+                cx,
+            );
+        }
+        self.simulator.save();
+        self.sync_market_data();
+        cx.notify();
+    }
+
+    /// Watch a manual entry so an exit is attached when it fills (simulated orders don't
+    /// stream fills, so they're left alone)
+    fn watch_auto_exit(&mut self, order_id: String, cx: &mut Context<Self>) {
+        if !self.settings.auto_exits.enabled || self.simulator.enabled {
+            return;
+        }
+        if let Some(update) = self.auto_exits.watch(order_id) {
+            self.attach_auto_exit(&update, cx);
+        }
+    }
+
+    /// Submit the OCO exit for a filled manual entry. Orders that reduced or flipped a
+    /// position aren't entries and get no exit.
+    fn attach_auto_exit(&mut self, update: &stream::OrderUpdate, cx: &mut Context<Self>) {
+        let number = |text: &str| text.parse::<f64>().unwrap_or(0.0);
+        let qty = number(&update.filled_qty);
+        let price = update
+            .filled_avg_price
+            .as_deref()
+            .map(number)
+            .unwrap_or(0.0);
+        if qty <= 0.0 || price <= 0.0 {
+            return;
+        }
+        let side = if update.side == "sell" {
+            hotkeys::HotkeySide::Sell
+        } else {
+            hotkeys::HotkeySide::Buy
+        };
+        let held = self
+            .account
+            .positions
+            .iter()
+            .find(|position| position.symbol == update.symbol)
+            .map(|position| number(&position.qty))
+            .unwrap_or(0.0);
+        let is_entry = match side {
+            hotkeys::HotkeySide::Buy => held + 1e-9 >= qty,
+            hotkeys::HotkeySide::Sell => -held + 1e-9 >= qty,
+        };
+        if !is_entry {
+            info!(
+                "🎯 No auto exit for order {}: it reduced the {} position",
+                update.id, update.symbol
+            );
+            return;
+        }
+
+        let skipped = if chart::is_crypto_symbol(&update.symbol) {
+            Some("crypto doesn't support OCO orders".to_string())
+        } else if qty.fract() != 0.0 {
+            Some("OCO orders need whole shares".to_string())
+        } else {
+            None
+        };
+        let offsets = match (skipped, self.settings.auto_exits.offsets()) {
+            (None, Ok(offsets)) => offsets,
+            (Some(reason), _) | (None, Err(reason)) => {
+                warn!("🎯 No auto exit for {}: {}", update.symbol, reason);
+                self.show_toast(
+                    toast::ToastLevel::Warning,
+                    format!("No exit attached to {}: {}", update.symbol, reason),
+                    cx,
+                );
+                return;
+            }
+        };
+
+        let exit = auto_exit::ExitOrder::new(
+            update.id.clone(),
+            update.symbol.clone(),
+            side,
+            qty,
+            price,
+            offsets,
+        );
+        info!(
+            "🎯 Attaching exit to order {}: {}",
+            update.id,
+            exit.describe()
+        );
+        self.service.send(service::ServiceRequest::SubmitExit(exit));
+    }
+
+    /// Submit a market order in the background and toast the result (hotkeys and chart
+    /// window tickets)
+    fn send_market_order(
+        &mut self,
+        symbol: String,
+        side: hotkeys::HotkeySide,
+        qty: f64,
+        cx: &mut Context<Self>,
+    ) {
+        self.run_order_action(Self::market_order(symbol, side, qty), cx);
+    }
+
+    /// Market order from a hotkey, chart window ticket or the scalp pad
+    fn market_order(
+        symbol: String,
+        side: hotkeys::HotkeySide,
+        qty: f64,
+    ) -> order_guard::OrderAction {
+        // Crypto orders don't support DAY time in force
+        let time_in_force = if chart::is_crypto_symbol(&symbol) {
+            OrderTimeInForce::Gtc
+        } else {
+            OrderTimeInForce::Day
+        };
+        order_guard::OrderAction::Submit {
+            origin: service::OrderOrigin::Market { side },
+            ticket: service::OrderTicket {
+                symbol,
+                side: side.order_side(),
+                order_type: OrderType::Market,
+                qty,
+                notional: None,
+                limit_price: None,
+                time_in_force,
+            },
+        }
+    }
+
+    /// Signed quantity and average price of the charted symbol's position, from the
+    /// simulated account in forward-test mode
+    fn scalp_position(&self) -> Option<(f64, f64)> {
+        let symbol = &self.chart.symbol;
+        if self.simulator.enabled {
+            return self
+                .simulator
+                .positions
+                .iter()
+                .find(|position| &position.symbol == symbol)
+                .map(|position| (position.qty, position.avg_price));
+        }
+        let number = |text: &str| text.parse::<f64>().unwrap_or(0.0);
+        self.account
+            .positions
+            .iter()
+            .find(|position| &position.symbol == symbol)
+            .map(|position| (number(&position.qty), number(&position.avg_entry_price)))
+            .filter(|(qty, _)| *qty != 0.0)
+    }
+
+    /// Scalp pad buy, sell or reverse: a market order on the charted symbol, sent without
+    /// confirmation apart from the risk limits and the live-order guard
+    fn scalp_order(&mut self, action: scalp::ScalpAction, cx: &mut Context<Self>) {
+        if self.trading_halted() {
+            self.show_toast(
+                toast::ToastLevel::Error,
+                "Scalping is disabled: daily loss limit reached".to_string(),
+                cx,
+            );
+            return;
+        }
+        let symbol = self.chart.symbol.clone();
+        let (side, qty) = match action {
+            scalp::ScalpAction::Buy | scalp::ScalpAction::Sell => {
+                let Some(qty) = self.settings.scalp.quantity() else {
+                    self.show_toast(
+                        toast::ToastLevel::Error,
+                        "Set a scalp size first".to_string(),
+                        cx,
+                    );
+                    return;
+                };
+                let side = if action == scalp::ScalpAction::Buy {
+                    hotkeys::HotkeySide::Buy
+                } else {
+                    hotkeys::HotkeySide::Sell
+                };
+                (side, qty)
+            }
+            scalp::ScalpAction::Reverse => {
+                let Some((qty, _)) = self.scalp_position() else {
+                    self.show_toast(
+                        toast::ToastLevel::Info,
+                        format!("No {} position to reverse", symbol),
+                        cx,
+                    );
+                    return;
+                };
+                let side = if qty > 0.0 {
+                    hotkeys::HotkeySide::Sell
+                } else {
+                    hotkeys::HotkeySide::Buy
+                };
+                (side, qty.abs())
+            }
+        };
+
+        let description = match action {
+            scalp::ScalpAction::Reverse => {
+                format!("Reverse {} into {} {}", symbol, side.label(), qty)
+            }
+            _ => format!("{} {} {} market", side.label(), qty, symbol),
+        };
+        if !self.check_risk_limits(chart::ChartId::Main, qty, None, cx) {
+            return;
+        }
+
+        info!("⚡ Scalp: {}", description);
+        let order_action = if action != scalp::ScalpAction::Reverse {
+            Self::market_order(symbol, side, qty)
+        } else if self.simulator.enabled {
+            // The simulated broker flips a position with one order
+            Self::market_order(symbol, side, qty * 2.0)
+        } else {
+            order_guard::OrderAction::Reverse(scalp::PendingReverse { symbol, side, qty })
+        };
+        self.guard_live_order(
+            order_guard::GuardedOrder::Scalp(action),
+            qty,
+            None,
+            description,
+            order_action,
+            cx,
+        );
+    }
+
+    /// Send a reverse's opposite entry once the stream shows its symbol flat
+    fn send_pending_reverse(&mut self, symbol: &str, cx: &mut Context<Self>) {
+        let position_qty = self
+            .account
+            .positions
+            .iter()
+            .find(|position| position.symbol == symbol)
+            .map(|position| position.qty.parse::<f64>().unwrap_or(0.0))
+            .unwrap_or(0.0);
+        if let Some(reverse) = self.scalp.take_reverse(symbol, position_qty) {
+            info!(
+                "⚡ Scalp reverse: {} {} {} after close",
+                reverse.side.label(),
+                reverse.qty,
+                reverse.symbol
+            );
+            self.send_market_order(reverse.symbol, reverse.side, reverse.qty, cx);
+        }
+    }
+
+    /// Close the charted symbol's position from the scalp pad
+    fn scalp_flatten(&mut self, cx: &mut Context<Self>) {
+        let symbol = self.chart.symbol.clone();
+        if self.scalp_position().is_none() {
+            self.show_toast(
+                toast::ToastLevel::Info,
+                format!("No {} position to flatten", symbol),
+                cx,
+            );
+            return;
+        }
+        self.scalp.pending_reverse = None;
+        info!("⚡ Scalp: flatten {}", symbol);
+        if self.simulator.enabled {
+            self.close_simulated_position(symbol, cx);
+        } else {
+            self.close_position(symbol);
+        }
+        cx.notify();
+    }
+
+    /// Submit scheduled orders whose time has come. Orders are dropped, not sent, while
+    /// the daily loss limit halts trading.
+    fn submit_due_orders(&mut self, cx: &mut Context<Self>) {
+        for order in self.scheduler.take_due(Utc::now()) {
+            if self.trading_halted() {
+                warn!(
+                    "🚫 Dropped scheduled {}: daily loss limit reached",
+                    order.description
+                );
+                self.show_toast(
+                    toast::ToastLevel::Error,
+                    format!(
+                        "Scheduled {} dropped: daily loss limit reached",
+                        order.description
+                    ),
+                    cx,
+                );
+                continue;
+            }
+
+            info!("⏱️  Submitting scheduled {}", order.description);
+            // Crypto orders don't support DAY time in force
+            let time_in_force = if chart::is_crypto_symbol(&order.symbol) {
+                OrderTimeInForce::Gtc
+            } else {
+                OrderTimeInForce::Day
+            };
+            self.submit_ticket(
+                service::OrderOrigin::Scheduled { side: order.side },
+                service::OrderTicket {
+                    symbol: order.symbol,
+                    side: order.side.order_side(),
+                    order_type: if order.limit_price.is_some() {
+                        OrderType::Limit
+                    } else {
+                        OrderType::Market
+                    },
+                    qty: order.qty,
+                    notional: None,
+                    limit_price: order.limit_price,
+                    time_in_force,
+                },
+                cx,
+            );
+        }
+    }
+
+    /// Submit the recurring buys due at this open. Holidays, halted trading and runs
+    /// missed while the terminal was closed are recorded in the plan's history instead.
+    fn run_recurring_plans(&mut self, cx: &mut Context<Self>) {
+        let now = Utc::now();
+        // Equity for the size limit comes with the first account load
+        if !self.data_loaded || !self.recurring.plans.iter().any(|plan| plan.next_at <= now) {
+            return;
+        }
+        // Hold due runs until the calendar is in, so a holiday can't pass for a trading day
+        if !self.calendar_loaded {
+            if !self.calendar_loading {
+                self.fetch_calendar(cx);
+            }
+            return;
+        }
+
+        let calendar = &self.calendar_events;
+        let is_holiday = |at: chrono::DateTime<Utc>| {
+            let date = at.with_timezone(&chrono_tz::America::New_York).date_naive();
+            calendar.iter().any(
+                |event| matches!(event, clock::CalendarEvent::Holiday(holiday) if *holiday == date),
+            )
+        };
+        let halted = self.trading_halted();
+        let due = self.recurring.take_due(now, is_holiday, halted);
+        self.recurring.save();
+
+        for run in due {
+            self.submit_recurring_run(run, cx);
+        }
+        cx.notify();
+    }
+
+    /// Send a recurring buy through the order size limit and, on a live account, the typed
+    /// confirmation; a blocked run is recorded on its plan
+    fn submit_recurring_run(&mut self, run: recurring::DueRun, cx: &mut Context<Self>) {
+        if let Some(reason) = risk::order_size_violation(
+            &self.config.risk,
+            0.0,
+            Some(run.notional),
+            self.account.equity,
+        ) {
+            warn!("🚫 Recurring buy of {} blocked: {}", run.symbol, reason);
+            self.show_toast(
+                toast::ToastLevel::Error,
+                format!("Recurring buy of {} blocked: {}", run.symbol, reason),
+                cx,
+            );
+            self.recurring
+                .resolve(run.plan, recurring::Outcome::Blocked { reason });
+            self.recurring.save();
+            return;
+        }
+        let description = format!("Recurring buy of ${:.2} {}", run.notional, run.symbol);
+
+        info!(
+            "🔁 Submitting recurring buy of ${:.2} {} due {}",
+            run.notional, run.symbol, run.at
+        );
+        self.guard_live_order(
+            order_guard::GuardedOrder::Recurring(run.plan),
+            1.0,
+            Some(run.notional),
+            description,
+            order_guard::OrderAction::Submit {
+                origin: service::OrderOrigin::Recurring { plan: run.plan },
+                ticket: service::OrderTicket {
+                    symbol: run.symbol,
+                    side: OrderSide::Buy,
+                    order_type: OrderType::Market,
+                    qty: 0.0,
+                    notional: Some(run.notional),
+                    limit_price: None,
+                    time_in_force: OrderTimeInForce::Day,
+                },
+            },
+            cx,
+        );
+    }
+
+    fn add_recurring_plan(&mut self, cx: &mut Context<Self>) {
+        let notional = match self.recurring_form.amount.trim().parse::<f64>() {
+            Ok(value) if value > 0.0 => value,
+            _ => return,
+        };
+
+        self.recurring.add(
+            self.chart.symbol.clone(),
+            notional,
+            self.recurring_form.weekday,
+        );
+        self.recurring.save();
+        self.recurring_form.amount.clear();
+        self.set_input_focus(None);
+
+        if self.calendar_events.is_empty() && !self.calendar_loading {
+            self.fetch_calendar(cx);
+        }
+        cx.notify();
+    }
+
+    /// Close every open position (Flatten shortcut)
+    fn flatten_positions(&mut self, cx: &mut Context<Self>) {
+        let symbols: Vec<String> = self
+            .account
+            .positions
+            .iter()
+            .map(|position| position.symbol.clone())
+            .collect();
+        if symbols.is_empty() {
+            self.show_toast(
+                toast::ToastLevel::Info,
+                "No positions to flatten".to_string(),
+                cx,
+            );
+            return;
+        }
+
+        info!("🧹 Flattening {} positions", symbols.len());
+        self.show_toast(
+            toast::ToastLevel::Warning,
+            format!("Flattening {} positions", symbols.len()),
+            cx,
+        );
+        for symbol in symbols {
+            self.close_position(symbol);
+        }
+    }
+
+    fn select_footer_tab(&mut self, tab: account::FooterTab, cx: &mut Context<Self>) {
+        self.account.active_footer_tab = tab;
+        self.footer_scroll.set_offset(point(px(0.0), px(0.0)));
+
+        // Tabs backed by extra endpoints are fetched lazily on first visit
+        match self.account.active_footer_tab {
+            account::FooterTab::History => {
+                if self.account.order_history.is_empty() && !self.account.order_history_loading {
+                    self.fetch_order_history(cx);
+                }
+            }
+            account::FooterTab::Activities
+            | account::FooterTab::TaxLots
+            | account::FooterTab::Performance => {
+                if self.account.activities.is_empty() && !self.account.activities_loading {
+                    self.fetch_activities(cx);
+                }
+                if self.account.active_footer_tab == account::FooterTab::Performance {
+                    self.fetch_benchmark(cx);
+                }
+            }
+            account::FooterTab::News => {
+                if self.news.symbols != self.news_symbols() && !self.news.loading {
+                    self.fetch_news(cx);
+                }
+            }
+            account::FooterTab::Heatmap => {
+                let stale = self.heatmap.symbols != self.heatmap_symbols()
+                    || self.heatmap.needs_refresh(self.config.heatmap.refresh_secs);
+                if stale && !self.heatmap.loading {
+                    self.fetch_heatmap(cx);
+                }
+            }
+            account::FooterTab::Analytics => self.fetch_analytics(cx),
+            account::FooterTab::Movers => {
+                let stale = self.screener.symbols != self.screener_symbols()
+                    || self
+                        .screener
+                        .needs_refresh(self.config.screener.refresh_secs);
+                if stale && !self.screener.loading {
+                    self.fetch_screener(cx);
+                }
+            }
+            _ => {}
+        }
+
+        cx.notify();
+    }
+
+    fn fetch_activities(&mut self, cx: &mut Context<Self>) {
+        self.account.activities_loading = true;
+        cx.notify();
+
+        self.service.send(service::ServiceRequest::Activities);
+    }
+
+    fn cancel_order(&mut self, order_id: String) {
+        self.service
+            .send(service::ServiceRequest::CancelOrder(order_id));
+    }
+
+    fn close_position(&mut self, symbol: String) {
+        self.service
+            .send(service::ServiceRequest::ClosePosition(symbol));
+    }
+
+    fn submit_order(&mut self, cx: &mut Context<Self>) {
+        // Validate inputs
+        if self.account.order_quantity.text().trim().is_empty() {
+            self.show_toast(
+                toast::ToastLevel::Error,
+                "Quantity cannot be empty".to_string(),
+                cx,
+            );
+            cx.notify();
+            return;
+        }
+
+        let qty = match self.account.order_quantity.text().parse::<f64>() {
+            Ok(q) if q > 0.0 => q,
+            _ => {
+                self.show_toast(toast::ToastLevel::Error, "Invalid quantity".to_string(), cx);
+                cx.notify();
+                return;
+            }
+        };
+
+        if matches!(self.account.order_type, OrderType::Limit)
+            && self.account.order_limit_price.text().trim().is_empty()
+        {
+            self.show_toast(
+                toast::ToastLevel::Error,
+                "Limit price required for limit orders".to_string(),
+                cx,
+            );
+            cx.notify();
+            return;
+        }
+
+        if self.chart.is_crypto()
+            && !matches!(
+                self.account.order_time_in_force,
+                OrderTimeInForce::Gtc | OrderTimeInForce::Ioc
+            )
+        {
+            self.show_toast(
+                toast::ToastLevel::Error,
+                "Crypto orders require GTC or IOC time in force".to_string(),
+                cx,
+            );
+            cx.notify();
+            return;
+        }
+
+        let limit_price = if matches!(self.account.order_type, OrderType::Limit) {
+            match self.account.order_limit_price.text().parse::<f64>() {
+                Ok(p) if p > 0.0 => Some(p),
+                _ => {
+                    self.show_toast(
+                        toast::ToastLevel::Error,
+                        "Invalid limit price".to_string(),
+                        cx,
+                    );
+                    cx.notify();
+                    return;
+                }
+            }
+        } else {
+            None
+        };
+
+        let description = format!(
+            "{} {} {} {}{}",
+            if matches!(self.account.order_side, OrderSide::Buy) {
+                "Buy"
+            } else {
+                "Sell"
+            },
+            qty,
+            self.chart.symbol,
+            if limit_price.is_some() {
+                "limit"
+            } else {
+                "market"
+            },
+            limit_price
+                .map(|price| format!(" @ ${:.2}", price))
+                .unwrap_or_default(),
+        );
+        let scheduled_at = if self.scheduler.mode == schedule::ScheduleMode::Now {
+            None
+        } else {
+            match self.scheduler.submit_time(
+                self.market_clock.as_ref(),
+                &self.calendar_events,
+                Utc::now(),
+            ) {
+                Ok(at) => Some(at),
+                Err(message) => {
+                    self.show_toast(toast::ToastLevel::Error, message, cx);
+                    return;
+                }
+            }
+        };
+        if !self.check_risk_limits(chart::ChartId::Main, qty, limit_price, cx) {
+            return;
+        }
+
+        let symbol = self.chart.symbol.clone();
+        let action = if let Some(at) = scheduled_at {
+            let side = if matches!(self.account.order_side, OrderSide::Buy) {
+                hotkeys::HotkeySide::Buy
+            } else {
+                hotkeys::HotkeySide::Sell
+            };
+            order_guard::OrderAction::Stage(schedule::ScheduledOrder {
+                id: 0,
+                symbol,
+                side,
+                qty,
+                limit_price,
+                at,
+                description: description.clone(),
+            })
+        } else {
+            let side = match self.account.order_side {
+                OrderSide::Buy => OrderSide::Buy,
+                OrderSide::Sell => OrderSide::Sell,
+            };
+            let order_type = match self.account.order_type {
+                OrderType::Market => OrderType::Market,
+                OrderType::Limit => OrderType::Limit,
+                _ => OrderType::Market,
+            };
+            let time_in_force = match self.account.order_time_in_force {
+                OrderTimeInForce::Day => OrderTimeInForce::Day,
+                OrderTimeInForce::Gtc => OrderTimeInForce::Gtc,
+                OrderTimeInForce::Ioc => OrderTimeInForce::Ioc,
+                _ => OrderTimeInForce::Day,
+            };
+            order_guard::OrderAction::Submit {
+                origin: service::OrderOrigin::Form,
+                ticket: service::OrderTicket {
+                    symbol,
+                    side,
+                    order_type,
+                    qty,
+                    notional: None,
+                    limit_price,
+                    time_in_force,
+                },
+            }
+        };
+        self.guard_live_order(
+            order_guard::GuardedOrder::Form,
+            qty,
+            limit_price,
+            description,
+            action,
+            cx,
+        );
+    }
+
+    /// Submit the strategy builder's legs as one multi-leg order
+    fn submit_strategy(&mut self, cx: &mut Context<Self>) {
+        if self.trading_halted() {
+            warn!("🚫 Strategy order blocked: daily loss limit reached");
+            self.strategy.message = Some("✗ Trading halted: daily loss limit reached".to_string());
+            cx.notify();
+            return;
+        }
+        if self.chart.is_crypto() {
+            self.strategy.message = Some("✗ Options are not available for crypto".to_string());
+            cx.notify();
+            return;
+        }
+
+        let legs = match self.strategy.legs(&self.chart.symbol) {
+            Ok(legs) => legs,
+            Err(error) => {
+                self.strategy.message = Some(format!("✗ {}", error));
+                cx.notify();
+                return;
+            }
+        };
+
+        let quantity = match self.strategy.quantity.trim().parse::<u32>() {
+            Ok(q) if q > 0 => q,
+            _ => {
+                self.strategy.message =
+                    Some("✗ Quantity must be a whole number of contracts".to_string());
+                cx.notify();
+                return;
+            }
+        };
+
+        let net_price = match self.strategy.net_price.trim().parse::<f64>() {
+            Ok(p) if p != 0.0 => p,
+            _ => {
+                self.strategy.message =
+                    Some("✗ Net price required (negative for credit)".to_string());
+                cx.notify();
+                return;
+            }
+        };
+
+        // Notional is the net price per share across 100-share contracts
+        let description = format!(
+            "{} contract {} strategy at {:.2} net",
+            quantity, self.chart.symbol, net_price
+        );
+        self.guard_live_order(
+            order_guard::GuardedOrder::Strategy,
+            quantity as f64,
+            Some(net_price.abs() * 100.0),
+            description,
+            order_guard::OrderAction::Strategy {
+                underlying: self.chart.symbol.clone(),
+                legs,
+                quantity,
+                net_price,
+            },
+            cx,
+        );
+    }
+
+    /// Send a multi-leg options order that passed the guards
+    fn send_strategy(
+        &mut self,
+        legs: Vec<options::OptionLeg>,
+        quantity: u32,
+        net_price: f64,
+        cx: &mut Context<Self>,
+    ) {
+        self.strategy.submitting = true;
+        self.strategy.message = None;
+        cx.notify();
+
+        self.service.send(service::ServiceRequest::SubmitStrategy {
+            legs,
+            quantity,
+            net_price,
+        });
+    }
+
+    fn start_websocket_stream(&mut self, cx: &mut Context<Self>) {
+        info!("🚀 Starting WebSocket stream connection...");
+
+        self.stream_status = "Connecting...".to_string();
+        cx.notify();
+
+        // Create a channel for receiving updates from the WebSocket
+        let (sender, mut receiver) = mpsc::unbounded_channel::<StreamUpdate>();
+
+        // Start the WebSocket stream in a background task
+        StreamManager::start_stream(sender);
+
+        // Same retirement scheme as the market data streams (profile switches)
+        self.trading_stream_generation += 1;
+        let generation = self.trading_stream_generation;
+
+        // Spawn a task to listen for updates and apply them to the UI
+        cx.spawn(async move |this, cx| {
+            while let Some(update) = receiver.recv().await {
+                let is_current = this.update(cx, |chart, cx| {
+                    if chart.trading_stream_generation != generation {
+                        return false;
+                    }
+                    chart.handle_stream_update(update, cx);
+                    true
+                });
+                if !matches!(is_current, Ok(true)) {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
+
+    /// Record a notification; fills and alerts also raise a desktop notification while the window is in the background
+    fn notify(&mut self, kind: notifications::NotificationKind, title: String, message: String) {
+        if matches!(
+            kind,
+            notifications::NotificationKind::Fill | notifications::NotificationKind::Alert
+        ) && !self.window_active
+        {
+            notifications::send_desktop_notification(&title, &message);
+        }
+        self.notifications.push(kind, title, message);
+    }
+
+    /// Show a toast and dismiss it after a few seconds
+    fn show_toast(&mut self, level: toast::ToastLevel, message: String, cx: &mut Context<Self>) {
+        let id = self.toasts.push(level, message, None, None);
+        cx.notify();
+        self.dismiss_toast_later(id, cx);
+    }
+
+    fn dismiss_toast_later(&mut self, id: u64, cx: &mut Context<Self>) {
+        cx.spawn(async move |this, cx| {
+            cx.background_executor()
+                .timer(std::time::Duration::from_secs(toast::TOAST_DURATION_SECS))
+                .await;
+            let _ = this.update(cx, |terminal, cx| {
+                terminal.toasts.dismiss(id);
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Surface a background failure. Errors with a retry action stay until dismissed or
+    /// retried; repeats of an error already on screen are dropped.
+    fn show_error(
+        &mut self,
+        message: String,
+        action: Option<toast::RetryAction>,
+        cx: &mut Context<Self>,
+    ) {
+        self.push_error(message, None, action, cx);
+    }
+
+    /// Surface a failed API call or file operation with its recovery hint. Retry is
+    /// dropped for errors that retrying won't fix (bad keys, unknown symbol).
+    fn show_failure(
+        &mut self,
+        context: &str,
+        error: &error::TerminalError,
+        action: Option<toast::RetryAction>,
+        cx: &mut Context<Self>,
+    ) {
+        let action = action.filter(|_| error.is_retryable());
+        self.push_error(format!("{}: {}", context, error), error.hint(), action, cx);
+    }
+
+    fn push_error(
+        &mut self,
+        message: String,
+        hint: Option<&'static str>,
+        action: Option<toast::RetryAction>,
+        cx: &mut Context<Self>,
+    ) {
+        if self.toasts.is_showing(&message) {
+            return;
+        }
+        let sticky = action.is_some();
+        let id = self
+            .toasts
+            .push(toast::ToastLevel::Error, message, hint, action);
+        cx.notify();
+        if !sticky {
+            self.dismiss_toast_later(id, cx);
+        }
+    }
+
+    /// Re-run the operation behind a failed toast
+    fn retry(&mut self, action: toast::RetryAction, cx: &mut Context<Self>) {
+        match action {
+            toast::RetryAction::Bars => self.fetch_bars(cx),
+            toast::RetryAction::Asset => self.fetch_asset(cx),
+            toast::RetryAction::CorporateActions => self.fetch_corporate_actions(),
+            toast::RetryAction::Account => self.fetch_account(cx),
+            toast::RetryAction::AccountConfig => self.fetch_account_config(),
+            toast::RetryAction::Calendar => self.fetch_calendar(cx),
+            toast::RetryAction::Positions => self.fetch_positions(cx),
+            toast::RetryAction::Orders => self.fetch_orders(cx),
+            toast::RetryAction::MoreOrders => self.load_more_orders(cx),
+            toast::RetryAction::OrderHistory => self.fetch_order_history(cx),
+            toast::RetryAction::Activities => self.fetch_activities(cx),
+            toast::RetryAction::News => self.fetch_news(cx),
+            toast::RetryAction::Alerts => self.check_alerts_since_last_session(cx),
+            toast::RetryAction::CancelOrder(order_id) => self.cancel_order(order_id),
+            toast::RetryAction::ClosePosition(symbol) => self.close_position(symbol),
+        }
+    }
+
+    /// Fire any alerts on the symbol whose condition the price meets
+    fn check_alerts(&mut self, symbol: &str, price: f64) {
+        let triggered = self.alerts.check(symbol, price);
+        if triggered.is_empty() {
+            return;
+        }
+
+        for fired in triggered {
+            info!(
+                "🔔 Alert: {} {} (last ${:.2})",
+                fired.alert.symbol,
+                fired.alert.condition.describe(),
+                fired.price
+            );
+            self.notify(
+                notifications::NotificationKind::Alert,
+                format!(
+                    "{} {}",
+                    fired.alert.symbol,
+                    fired.alert.condition.describe()
+                ),
+                format!("Last price ${:.2}", fired.price),
+            );
+        }
+        self.play_sound(sound::SoundCue::Alert);
+        self.alerts.save();
+    }
+
+    /// Submit the orders of automation rules whose level the price crossed. Rules blocked
+    /// by the loss limit or the order size limits are logged instead.
+    fn run_automation(&mut self, symbol: &str, price: f64, cx: &mut Context<Self>) {
+        let fired = self.automation.check(symbol, price);
+        if fired.is_empty() {
+            return;
+        }
+
+        for automation::FiredRule { rule, price } in fired {
+            info!("🤖 Rule fired at ${:.2}: {}", price, rule.describe());
+            let notional = Some(rule.limit_price.unwrap_or(price) * rule.qty);
+            // Simulated orders never reach the account, so rules forward test on live keys
+            let blocked = if self.profiles.environment() == credentials::Environment::Live
+                && !rule.allow_live
+                && !self.simulator.enabled
+            {
+                Some("rule isn't allowed to trade live (turn on live for it)".to_string())
+            } else if self.trading_halted() {
+                Some("daily loss limit reached".to_string())
+            } else {
+                risk::order_size_violation(
+                    &self.config.risk,
+                    rule.qty,
+                    notional,
+                    self.account.equity,
+                )
+            };
+            if let Some(reason) = blocked {
+                warn!("🚫 Rule order blocked: {}", reason);
+                self.automation.record(
+                    format!("{} at ${:.2}: blocked, {}", rule.describe(), price, reason),
+                    true,
+                );
+                self.show_toast(
+                    toast::ToastLevel::Error,
+                    format!("Automation order blocked: {}", reason),
+                    cx,
+                );
+                continue;
+            }
+
+            self.automation.record(
+                format!("{} at ${:.2}: fired", rule.describe(), price),
+                false,
+            );
+            self.notify(
+                notifications::NotificationKind::Order,
+                format!("Rule fired: {} {}", rule.symbol, rule.trigger.describe()),
+                format!("Last price ${:.2}", price),
+            );
+            // Crypto orders don't support DAY time in force
+            let time_in_force = if chart::is_crypto_symbol(&rule.symbol) {
+                OrderTimeInForce::Gtc
+            } else {
+                OrderTimeInForce::Day
+            };
+            let description = rule.describe();
+            self.guard_live_order(
+                order_guard::GuardedOrder::Automation(rule.id),
+                rule.qty,
+                Some(rule.limit_price.unwrap_or(price)),
+                description,
+                order_guard::OrderAction::Submit {
+                    origin: service::OrderOrigin::Automation { rule: rule.id },
+                    ticket: service::OrderTicket {
+                        symbol: rule.symbol,
+                        side: rule.side.order_side(),
+                        order_type: if rule.limit_price.is_some() {
+                            OrderType::Limit
+                        } else {
+                            OrderType::Market
+                        },
+                        qty: rule.qty,
+                        notional: None,
+                        limit_price: rule.limit_price,
+                        time_in_force,
+                    },
+                },
+                cx,
+            );
+        }
+        self.automation.save();
+        // Fired rules disable themselves, so their symbols may no longer be needed
+        self.sync_market_data();
+    }
+
+    fn add_automation_rule(&mut self, cx: &mut Context<Self>) {
+        let (trigger, qty, limit_price) = match self.automation_form.parse() {
+            Ok(parsed) => parsed,
+            Err(message) => {
+                self.automation_form.message = Some(message);
+                cx.notify();
+                return;
+            }
+        };
+
+        self.automation.add(
+            self.chart.symbol.clone(),
+            trigger,
+            self.automation_form.side,
+            qty,
+            limit_price,
+        );
+        self.automation.save();
+        self.automation_form.level.clear();
+        self.automation_form.quantity.clear();
+        self.automation_form.limit_price.clear();
+        self.automation_form.message = None;
+        self.set_input_focus(None);
+
+        // Rules are evaluated on streamed prices, so make sure the symbol is streamed
+        self.sync_market_data();
+        cx.notify();
+    }
+
+    /// Flip the automation master switch
+    fn toggle_automation_armed(&mut self, cx: &mut Context<Self>) {
+        self.automation.armed = !self.automation.armed;
+        let message = if self.automation.armed {
+            "Automation armed"
+        } else {
+            "Automation disarmed"
+        };
+        info!("🤖 {}", message);
+        self.automation.record(message.to_string(), false);
+        self.automation.save();
+
+        // Rule orders skip the live order confirmation, so say so when arming a live account
+        if self.automation.armed && self.profiles.environment() == credentials::Environment::Live {
+            self.show_toast(
+                toast::ToastLevel::Warning,
+                "Automation armed: rules will place LIVE orders without confirmation".to_string(),
+                cx,
+            );
+        } else {
+            self.show_toast(toast::ToastLevel::Info, message.to_string(), cx);
+        }
+    }
+
+    /// Re-read the script names from the scripts folder
+    fn refresh_scripts(&mut self) {
+        match scripting::list_scripts() {
+            Ok(names) => {
+                self.scripts.auto.retain(|name| names.contains(name));
+                self.scripts.names = names;
+            }
+            Err(error) => {
+                self.scripts.push_output("scripts", error.to_string(), true);
+            }
+        }
+    }
+
+    /// Run a script in the background against the main chart, then submit its orders
+    fn run_script(&mut self, name: String, cx: &mut Context<Self>) {
+        // Auto runs on fast streams are skipped while the previous one is still going
+        if self.scripts.running.contains(&name) {
+            return;
+        }
+        let source = match scripting::read_script(&name) {
+            Ok(source) => source,
+            Err(error) => {
+                self.scripts.push_output(&name, error.to_string(), true);
+                cx.notify();
+                return;
+            }
+        };
+
+        let input = scripting::ScriptInput {
+            symbol: self.chart.symbol.clone(),
+            bars: self
+                .chart
+                .bars
+                .iter()
+                .map(scripting::ScriptBar::from)
+                .collect(),
+            positions: self
+                .account
+                .positions
+                .iter()
+                .filter_map(|position| {
+                    Some((position.symbol.clone(), position.qty.parse::<f64>().ok()?))
+                })
+                .collect(),
+            equity: self.account.equity,
+            max_operations: self.config.scripting.max_operations,
+        };
+        self.scripts.running.insert(name.clone());
+        cx.notify();
+
+        cx.spawn(async move |this, cx| {
+            let run = cx
+                .background_executor()
+                .spawn(async move { scripting::run(&source, input) })
+                .await;
+
+            let _ = this.update(cx, |terminal, cx| {
+                terminal.scripts.running.remove(&name);
+                for line in run.logs {
+                    terminal.scripts.push_output(&name, line, false);
+                }
+                if let Some(error) = run.error {
+                    terminal.scripts.push_output(&name, error, true);
+                }
+                for order in run.orders {
+                    terminal.submit_script_order(&name, order, cx);
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Send an order a script asked for. Live accounts are off limits unless the config
+    /// allows them, and the loss and size limits apply as for manual orders.
+    fn submit_script_order(
+        &mut self,
+        name: &str,
+        order: scripting::ScriptOrder,
+        cx: &mut Context<Self>,
+    ) {
+        let price = order.limit_price.or_else(|| {
+            self.last_prices
+                .get(&order.symbol)
+                .and_then(|price| price.parse::<f64>().ok())
+        });
+        // The script's numbers are checked first. Simulated orders never reach the account,
+        // so forward tests run on live keys too
+        let blocked = if let Err(reason) = order.validate() {
+            Some(reason)
+        } else if self.profiles.environment() == credentials::Environment::Live
+            && !self.config.scripting.allow_live
+            && !self.simulator.enabled
+        {
+            Some("scripts only trade on paper (set scripting.allow_live to change)".to_string())
+        } else if self.trading_halted() {
+            Some("daily loss limit reached".to_string())
+        } else {
+            risk::order_size_violation(
+                &self.config.risk,
+                order.qty,
+                price.map(|price| price * order.qty),
+                self.account.equity,
+            )
+        };
+        if let Some(reason) = blocked {
+            self.scripts.push_output(
+                name,
+                format!("{} blocked: {}", order.describe(), reason),
+                true,
+            );
+            self.show_toast(
+                toast::ToastLevel::Error,
+                format!("Script {} order blocked: {}", name, reason),
+                cx,
+            );
+            return;
+        }
+
+        self.scripts
+            .push_output(name, format!("Submitting {}", order.describe()), false);
+        // Crypto orders don't support DAY time in force
+        let time_in_force = if chart::is_crypto_symbol(&order.symbol) {
+            OrderTimeInForce::Gtc
+        } else {
+            OrderTimeInForce::Day
+        };
+        self.submit_ticket(
+            service::OrderOrigin::Script {
+                name: name.to_string(),
+            },
+            service::OrderTicket {
+                symbol: order.symbol,
+                side: order.side.order_side(),
+                order_type: if order.limit_price.is_some() {
+                    OrderType::Limit
+                } else {
+                    OrderType::Market
+                },
+                qty: order.qty,
+                notional: None,
+                limit_price: order.limit_price,
+                time_in_force,
+            },
+            cx,
+        );
+    }
+
+    /// Re-check saved alerts against the latest prices (catches alerts that triggered while closed)
+    fn check_alerts_since_last_session(&mut self, cx: &mut Context<Self>) {
+        let symbols = self.alerts.symbols();
+        if symbols.is_empty() {
+            return;
+        }
+        let data_feed = self.config.market_data.feed;
+
+        cx.spawn(async move |this, cx| {
+            let result = cx
+                .background_executor()
+                .spawn(async move { alerts::fetch_latest_prices_sync(symbols, data_feed) })
+                .await;
+
+            let _ = this.update(cx, |terminal, cx| {
+                match result {
+                    Ok(prices) => {
+                        for (symbol, price) in prices {
+                            terminal.check_alerts(&symbol, price);
+                        }
+                    }
+                    Err(error) => {
+                        error!("✗ Error checking alerts: {}", error);
+                        terminal.show_failure(
+                            "Failed to check price alerts",
+                            &error,
+                            Some(toast::RetryAction::Alerts),
+                            cx,
+                        );
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    fn add_alert(&mut self, cx: &mut Context<Self>) {
+        let value = match self.alert_form.price.trim().parse::<f64>() {
+            Ok(value) if value > 0.0 => value,
+            _ => return,
+        };
+        // Percent and trailing alerts are anchored to the current price
+        let Some(current_price) = self.current_price() else {
+            return;
+        };
+        let condition = self.alert_form.kind.condition(value, current_price);
+
+        self.alerts.add(
+            self.chart.symbol.clone(),
+            condition,
+            self.alert_form.repeating,
+        );
+        self.alerts.save();
+        self.alert_form.price.clear();
+        self.set_input_focus(None);
+
+        // Alerts are evaluated on streamed prices, so make sure the symbol is streamed
+        self.sync_market_data();
+        cx.notify();
+    }
+
+    /// Open another top-level window charting the current symbol
+    fn open_chart_window(&mut self, cx: &mut Context<Self>) {
+        let id = self.next_chart_window_id;
+        self.next_chart_window_id += 1;
+        let bar_limit = self
+            .chart
+            .bar_limit
+            .text()
+            .trim()
+            .parse()
+            .unwrap_or(self.config.chart.bar_limit);
+        let symbol = self.chart.symbol.clone();
+        self.chart_windows.push(chart_window::ChartSlot::new(
+            id,
+            Chart::new(symbol.clone(), self.chart.timeframe.clone(), bar_limit),
+            self.settings.overlays.donchian.clone(),
+        ));
+        self.fetch_chart_bars(chart::ChartId::Window(id), cx);
+        self.sync_market_data();
+
+        let terminal = cx.entity();
+        // Opened once this update finishes, since the new window renders from the terminal
+        cx.defer(move |cx| {
+            let options = WindowOptions {
+                window_bounds: Some(WindowBounds::Windowed(Bounds::centered(
+                    None,
+                    size(px(900.0), px(640.0)),
+                    cx,
+                ))),
+                titlebar: Some(gpui::TitlebarOptions {
+                    title: Some(format!("{} Chart", symbol).into()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            };
+            let window_terminal = terminal.clone();
+            let result = cx.open_window(options, |window, cx| {
+                cx.new(|cx| chart_window::ChartWindow::new(window_terminal, id, window, cx))
+            });
+
+            terminal.update(cx, |terminal, cx| {
+                match result {
+                    Ok(handle) => {
+                        info!("🪟 Opened chart window for {}", symbol);
+                        if let Some(slot) = terminal.chart_slot_mut(id) {
+                            slot.handle = Some(handle);
+                        }
+                    }
+                    Err(e) => {
+                        error!("✗ Failed to open chart window: {}", e);
+                        terminal.chart_windows.retain(|slot| slot.id != id);
+                        terminal.show_error(
+                            format!("Failed to open chart window: {}", e),
+                            None,
+                            cx,
+                        );
+                    }
+                }
+                cx.notify();
+            });
+        });
+    }
+
+    /// Drop a closed chart window's state
+    fn close_chart_window(&mut self, id: usize, cx: &mut Context<Self>) {
+        self.chart_windows.retain(|slot| slot.id != id);
+        self.sync_market_data();
+        if self.focus.focused().and_then(|field| field.chart_window()) == Some(id) {
+            self.set_input_focus(None);
+        }
+        if self
+            .order_guard
+            .pending
+            .as_ref()
+            .is_some_and(|pending| pending.order == order_guard::GuardedOrder::ChartWindow(id))
+        {
+            self.order_guard.cancel();
+        }
+        info!("🪟 Closed chart window");
+        cx.notify();
+    }
+
+    /// Chart the symbol typed into a chart window
+    fn submit_chart_window_symbol(&mut self, id: usize, cx: &mut Context<Self>) {
+        let Some(slot) = self.chart_slot_mut(id) else {
+            return;
+        };
+        if slot.chart.symbol_input.is_empty() {
+            return;
+        }
+        let symbol = slot.chart.symbol_input.text().to_uppercase();
+        slot.chart.symbol = symbol.clone();
+        if let Some(handle) = slot.handle {
+            // Deferred: the window is busy dispatching the keystroke that got us here
+            let title = format!("{} Chart", symbol);
+            cx.defer(move |cx| {
+                let _ = handle.update(cx, |_, window, _| window.set_window_title(&title));
+            });
+        }
+
+        self.set_input_focus(None);
+        self.fetch_chart_bars_debounced(chart::ChartId::Window(id), cx);
+        // Shares the market data streams with the main chart
+        self.sync_market_data();
+    }
+
+    /// Submit a chart window's order ticket as a market order
+    fn submit_chart_window_order(&mut self, id: usize, cx: &mut Context<Self>) {
+        let Some(slot) = self.chart_slot_mut(id) else {
+            return;
+        };
+        let side = slot.side;
+        let symbol = slot.chart.symbol.clone();
+        let qty = match slot.quantity.text().trim().parse::<f64>() {
+            Ok(q) if q > 0.0 => q,
+            _ => {
+                self.show_toast(
+                    toast::ToastLevel::Error,
+                    "Enter an order quantity".to_string(),
+                    cx,
+                );
+                return;
+            }
+        };
+
+        let description = format!("{} {} {} market", side.label(), qty, symbol);
+        if !self.check_risk_limits(chart::ChartId::Window(id), qty, None, cx) {
+            return;
+        }
+
+        self.set_input_focus(None);
+        info!("🪟 Chart window {} {} {}", side.label(), qty, symbol);
+        self.guard_live_order(
+            order_guard::GuardedOrder::ChartWindow(id),
+            qty,
+            None,
+            description,
+            Self::market_order(symbol, side, qty),
+            cx,
+        );
+    }
+
+    /// Move the order form into a small always-on-top window
+    fn pop_out_order_form(&mut self, cx: &mut Context<Self>) {
+        if self.order_window.is_some() {
+            return;
+        }
+
+        let terminal = cx.entity();
+        // Opened once this update finishes, since the new window renders from the terminal
+        cx.defer(move |cx| {
+            let options = WindowOptions {
+                window_bounds: Some(WindowBounds::Windowed(Bounds::centered(
+                    None,
+                    size(px(340.0), px(720.0)),
+                    cx,
+                ))),
+                titlebar: Some(gpui::TitlebarOptions {
+                    title: Some("Order Entry".into()),
+                    ..Default::default()
+                }),
+                // Pop-up windows float above regular ones
+                kind: gpui::WindowKind::PopUp,
+                ..Default::default()
+            };
+            let window_terminal = terminal.clone();
+            let result = cx.open_window(options, |window, cx| {
+                cx.new(|cx| order_window::OrderEntryWindow::new(window_terminal, window, cx))
+            });
+
+            terminal.update(cx, |terminal, cx| {
+                match result {
+                    Ok(handle) => {
+                        info!("🪟 Order form popped out");
+                        terminal.order_window = Some(handle);
+                    }
+                    Err(e) => {
+                        error!("✗ Failed to open order window: {}", e);
+                        terminal.show_error(
+                            format!("Failed to open order window: {}", e),
+                            None,
+                            cx,
+                        );
+                    }
+                }
+                cx.notify();
+            });
+        });
+    }
+
+    /// Close the order window and show the form in the sidebar again
+    fn dock_order_form(&mut self, cx: &mut Context<Self>) {
+        if let Some(handle) = self.order_window.take() {
+            // Deferred: the Dock button may be in the window being closed
+            cx.defer(move |cx| {
+                let _ = handle.update(cx, |_, window, _| window.remove_window());
+            });
+            info!("🪟 Order form docked");
+        }
+        cx.notify();
+    }
+
+    /// Block an order over the configured size limits or past the daily loss limit,
+    /// saying which one it breaks. Returns whether the order may go ahead.
+    fn check_risk_limits(
+        &mut self,
+        chart_id: chart::ChartId,
+        qty: f64,
+        price: Option<f64>,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        if self.trading_halted() {
+            warn!("🚫 Order blocked: daily loss limit reached");
+            self.show_toast(
+                toast::ToastLevel::Error,
+                "Order blocked: daily loss limit reached".to_string(),
+                cx,
+            );
+            return false;
+        }
+
+        let notional = price
+            .or_else(|| self.chart_price(chart_id))
+            .map(|p| p * qty);
+        let Some(message) =
+            risk::order_size_violation(&self.config.risk, qty, notional, self.account.equity)
+        else {
+            return true;
+        };
+
+        warn!("🚫 {}", message);
+        self.show_toast(toast::ToastLevel::Error, message, cx);
+        false
+    }
+
+    /// Whether today's loss has reached the limit and trading wasn't resumed
+    fn trading_halted(&self) -> bool {
+        risk::LossLimit::breached(
+            &self.config.risk,
+            self.account.daily_pnl().map(|(change, _)| change),
+        ) && !self.loss_limit.is_overridden()
+    }
+
+    /// Re-enable trading for the rest of the day after the loss limit was hit
+    fn override_loss_limit(&mut self, cx: &mut Context<Self>) {
+        self.loss_limit.override_today();
+        warn!("⚠️  Daily loss limit overridden, trading re-enabled for today");
+        self.show_toast(
+            toast::ToastLevel::Warning,
+            "Trading re-enabled past the daily loss limit for today".to_string(),
+            cx,
+        );
+    }
+
+    /// Carry out an order, or on a live account hold it for typed confirmation when its
+    /// notional reaches the configured limit
+    fn guard_live_order(
+        &mut self,
+        order: order_guard::GuardedOrder,
+        qty: f64,
+        price: Option<f64>,
+        description: String,
+        action: order_guard::OrderAction,
+        cx: &mut Context<Self>,
+    ) {
+        // Simulated orders never reach the live account; options strategies always go to
+        // Alpaca, the simulator doesn't take them
+        let simulated = self.simulator.enabled && order != order_guard::GuardedOrder::Strategy;
+        if self.profiles.environment() != credentials::Environment::Live || simulated {
+            self.run_order_action(action, cx);
+            return;
+        }
+
+        let notional = price
+            .or_else(|| order.chart().and_then(|id| self.chart_price(id)))
+            .map(|p| p * qty);
+        let threshold = self.config.confirmations.live_order_notional;
+        if !order_guard::OrderGuard::requires_confirmation(notional, threshold) {
+            self.run_order_action(action, cx);
+            return;
+        }
+
+        info!("🛡️  Holding live order for confirmation: {}", description);
+        // Only one order is held at a time
+        self.release_held_order();
+        self.order_guard.hold(order_guard::PendingLiveOrder {
+            order,
+            symbol: action.symbol().to_string(),
+            description,
+            notional,
+            action,
+        });
+        self.set_input_focus(Some(focus::InputField::LiveConfirmation));
+        cx.notify();
+    }
+
+    /// Send the held live order, exactly as it was built, once its symbol has been typed
+    fn confirm_live_order(&mut self, cx: &mut Context<Self>) {
+        if !self.order_guard.is_typed() {
+            self.show_toast(
+                toast::ToastLevel::Warning,
+                "Type the symbol exactly to confirm the live order".to_string(),
+                cx,
+            );
+            return;
+        }
+        let Some(pending) = self.order_guard.pending.take() else {
+            return;
+        };
+
+        self.order_guard.cancel();
+        self.set_input_focus(None);
+        info!("🛡️  Live order confirmed: {}", pending.description);
+        self.run_order_action(pending.action, cx);
+    }
+
+    /// Send, stage or start an order that passed the guards
+    fn run_order_action(&mut self, action: order_guard::OrderAction, cx: &mut Context<Self>) {
+        match action {
+            order_guard::OrderAction::Submit { origin, ticket } => {
+                if matches!(origin, service::OrderOrigin::Form) {
+                    self.account.order_submitting = true;
+                }
+                self.submit_ticket(origin, ticket, cx);
+            }
+            order_guard::OrderAction::Stage(order) => {
+                info!("⏱️  Scheduled {} for {}", order.description, order.at);
+                self.show_toast(
+                    toast::ToastLevel::Info,
+                    format!(
+                        "{} scheduled for {}",
+                        order.description,
+                        order
+                            .at
+                            .with_timezone(&chrono::Local)
+                            .format("%a %b %-d %H:%M")
+                    ),
+                    cx,
+                );
+                self.scheduler.stage(order);
+                self.account.order_quantity.clear();
+                self.account.order_limit_price.clear();
+            }
+            order_guard::OrderAction::Reverse(reverse) => {
+                // Alpaca won't take an order that crosses from long to short, so the position
+                // is closed first and the opposite entry follows once it's flat
+                let symbol = reverse.symbol.clone();
+                self.scalp.pending_reverse = Some(reverse);
+                self.close_position(symbol);
+            }
+            order_guard::OrderAction::Strategy {
+                legs,
+                quantity,
+                net_price,
+                ..
+            } => self.send_strategy(legs, quantity, net_price, cx),
+        }
+        cx.notify();
+    }
+
+    fn cancel_live_order(&mut self, cx: &mut Context<Self>) {
+        self.release_held_order();
+        self.set_input_focus(None);
+        cx.notify();
+    }
+
+    /// Drop the held live order; a held recurring run or rule order is recorded as not
+    /// confirmed
+    fn release_held_order(&mut self) {
+        let held = self
+            .order_guard
+            .pending
+            .as_ref()
+            .map(|pending| pending.order);
+        match held {
+            Some(order_guard::GuardedOrder::Recurring(plan)) => {
+                self.recurring.resolve(
+                    plan,
+                    recurring::Outcome::Blocked {
+                        reason: "live confirmation not given".to_string(),
+                    },
+                );
+                self.recurring.save();
+            }
+            Some(order_guard::GuardedOrder::Automation(rule)) => {
+                self.automation
+                    .record(format!("Rule #{}: live confirmation not given", rule), true);
+                self.automation.save();
+            }
+            _ => {}
+        }
+        self.order_guard.cancel();
+    }
+
+    /// Latest streamed price for the charted symbol, falling back to the last bar close
+    fn current_price(&self) -> Option<f64> {
+        self.chart_price(chart::ChartId::Main)
+    }
+
+    /// Latest streamed price for a chart's symbol, falling back to its last bar close
+    fn chart_price(&self, id: chart::ChartId) -> Option<f64> {
+        let chart = self.chart_by_id(id)?;
+        self.last_prices
+            .get(&chart.symbol)
+            .and_then(|price| price.parse::<f64>().ok())
+            .or_else(|| chart.bars.last().map(|bar| bar.close))
+    }
+
+    fn play_sound(&self, cue: sound::SoundCue) {
+        if self.settings.sound_cue_enabled(cue) {
+            sound::play(cue);
+        }
+    }
+
+    /// Apply a result from the data service
+    fn handle_service_response(
+        &mut self,
+        response: service::ServiceResponse,
+        cx: &mut Context<Self>,
+    ) {
+        // A final response ends the retry status shown for its tab
+        if let Some(tab) = response.footer_tab() {
+            if matches!(&self.account.retrying, Some((retrying, _, _)) if *retrying == tab) {
+                self.account.retrying = None;
+            }
+        }
+        match response {
+            service::ServiceResponse::Retrying {
+                target,
+                attempt,
+                attempts,
+            } => match target {
+                service::RetryTarget::Chart { chart, generation } => {
+                    if let Some(chart) = self.chart_mut(chart) {
+                        if chart.fetch_generation == generation {
+                            chart.retry = Some((attempt, attempts));
+                        }
+                    }
+                }
+                service::RetryTarget::Tab(tab) => {
+                    self.account.retrying = Some((tab, attempt, attempts));
+                }
+            },
+            service::ServiceResponse::Bars {
+                chart,
+                generation,
+                result,
+            } => {
+                self.apply_chart_bars(chart, generation, result, cx);
+            }
+            service::ServiceResponse::Backfill {
+                chart,
+                generation,
+                result,
+            } => {
+                self.apply_backfill(chart, generation, result);
+            }
+            service::ServiceResponse::Account(result) => {
+                match result {
+                    Ok(snapshot) => {
+                        self.account.apply_snapshot(snapshot);
+                        info!("✓ Successfully loaded account information");
+                    }
+                    Err(error) => {
+                        error!("✗ Error fetching account: {}", error);
+                        self.account.account_status = Some("Error".to_string());
+                        self.show_failure(
+                            "Failed to load account",
+                            &error,
+                            Some(toast::RetryAction::Account),
+                            cx,
+                        );
+                    }
+                }
+                self.account.account_loading = false;
+            }
+            service::ServiceResponse::Positions(result) => {
+                match result {
+                    Ok(positions) => {
+                        self.account.positions = positions;
+                        info!(
+                            "✓ Successfully loaded {} positions",
+                            self.account.positions.len()
+                        );
+                    }
+                    Err(error) => {
+                        error!("✗ Error fetching positions: {}", error);
+                        self.account.positions.clear();
+                        self.show_failure(
+                            "Failed to load positions",
+                            &error,
+                            Some(toast::RetryAction::Positions),
+                            cx,
+                        );
+                    }
+                }
+                self.account.positions_loading = false;
+            }
+            service::ServiceResponse::Orders {
+                more: false,
+                result,
+            } => {
+                match result {
+                    Ok(page) => {
+                        self.account.orders = active_orders(page.orders);
+                        self.account.orders_next_until = page.next_until;
+                        info!(
+                            "✓ Successfully loaded {} active orders",
+                            self.account.orders.len()
+                        );
+                    }
+                    Err(error) => {
+                        error!("✗ Error fetching orders: {}", error);
+                        self.account.orders.clear();
+                        self.account.orders_next_until = None;
+                        self.show_failure(
+                            "Failed to load orders",
+                            &error,
+                            Some(toast::RetryAction::Orders),
+                            cx,
+                        );
+                    }
+                }
+                self.account.orders_loading = false;
+            }
+            service::ServiceResponse::Orders { more: true, result } => {
+                match result {
+                    Ok(page) => {
+                        // Skip orders already loaded (the cursor timestamp may be shared)
+                        let new_orders: Vec<_> = active_orders(page.orders)
+                            .into_iter()
+                            .filter(|order| !self.account.orders.iter().any(|o| o.id == order.id))
+                            .collect();
+                        info!("✓ Loaded {} more active orders", new_orders.len());
+                        self.account.orders.extend(new_orders);
+                        self.account.orders_next_until = page.next_until;
+                    }
+                    Err(error) => {
+                        error!("✗ Error fetching more orders: {}", error);
+                        self.show_failure(
+                            "Failed to load more orders",
+                            &error,
+                            Some(toast::RetryAction::MoreOrders),
+                            cx,
+                        );
+                    }
+                }
+                self.account.orders_loading_more = false;
+            }
+            service::ServiceResponse::OrderHistory(result) => {
+                match result {
+                    Ok(orders) => {
+                        self.account.order_history = orders;
+                        info!(
+                            "✓ Successfully loaded {} closed orders",
+                            self.account.order_history.len()
+                        );
+                    }
+                    Err(error) => {
+                        error!("✗ Error fetching order history: {}", error);
+                        self.account.order_history.clear();
+                        self.show_failure(
+                            "Failed to load order history",
+                            &error,
+                            Some(toast::RetryAction::OrderHistory),
+                            cx,
+                        );
+                    }
+                }
+                self.account.order_history_loading = false;
+            }
+            service::ServiceResponse::Activities(result) => {
+                match result {
+                    Ok(history) => {
+                        self.account.activities = history.activities;
+                        self.account.activities_complete = history.complete;
+                        self.account.lot_ledger =
+                            pnl::LotLedger::from_activities(&self.account.activities);
+                        info!(
+                            "✓ Successfully loaded {} account activities{}",
+                            self.account.activities.len(),
+                            if history.complete { "" } else { " (partial)" }
+                        );
+                    }
+                    Err(error) => {
+                        error!("✗ Error fetching activities: {}", error);
+                        self.account.activities.clear();
+                        self.account.activities_complete = false;
+                        self.show_failure(
+                            "Failed to load account activities",
+                            &error,
+                            Some(toast::RetryAction::Activities),
+                            cx,
+                        );
+                    }
+                }
+                self.account.activities_loading = false;
+            }
+            service::ServiceResponse::OrderSubmitted {
+                origin: service::OrderOrigin::Form,
+                result,
+                ..
+            } => {
+                match result {
+                    Ok(order_id) => {
+                        self.show_toast(
+                            toast::ToastLevel::Success,
+                            format!("Order submitted (ID: {})", order_id),
+                            cx,
+                        );
+                        self.account.order_quantity.clear();
+                        self.account.order_limit_price.clear();
+                        self.watch_auto_exit(order_id, cx);
+                        // WebSocket will handle the order update automatically
+                    }
+                    Err(error) => self.show_failure("Order failed", &error, None, cx),
+                }
+                self.account.order_submitting = false;
+            }
+            service::ServiceResponse::OrderSubmitted {
+                origin:
+                    service::OrderOrigin::Market { side } | service::OrderOrigin::Scheduled { side },
+                symbol,
+                qty,
+                result,
+            } => match result {
+                Ok(order_id) => {
+                    self.show_toast(
+                        toast::ToastLevel::Success,
+                        format!(
+                            "{} {} {} submitted (ID: {})",
+                            side.label(),
+                            qty,
+                            symbol,
+                            order_id
+                        ),
+                        cx,
+                    );
+                    self.watch_auto_exit(order_id, cx);
+                }
+                Err(error) => {
+                    error!("✗ Error submitting market order: {}", error);
+                    self.show_failure("Order failed", &error, None, cx);
+                }
+            },
+            service::ServiceResponse::OrderSubmitted {
+                origin: service::OrderOrigin::Recurring { plan },
+                symbol,
+                result,
+                ..
+            } => {
+                let outcome = match result {
+                    Ok(order_id) => {
+                        self.show_toast(
+                            toast::ToastLevel::Success,
+                            format!("Recurring buy of {} submitted (ID: {})", symbol, order_id),
+                            cx,
+                        );
+                        recurring::Outcome::Submitted { order_id }
+                    }
+                    Err(error) => {
+                        error!("✗ Error submitting recurring buy: {}", error);
+                        self.show_failure(
+                            &format!("Recurring buy of {} failed", symbol),
+                            &error,
+                            None,
+                            cx,
+                        );
+                        recurring::Outcome::Failed {
+                            error: error.to_string(),
+                        }
+                    }
+                };
+                self.recurring.resolve(plan, outcome);
+                self.recurring.save();
+            }
+            service::ServiceResponse::OrderSubmitted {
+                origin: service::OrderOrigin::Automation { rule },
+                symbol,
+                qty,
+                result,
+            } => {
+                match result {
+                    Ok(order_id) => {
+                        self.automation.record(
+                            format!(
+                                "Rule #{}: {} {} submitted (ID: {})",
+                                rule, qty, symbol, order_id
+                            ),
+                            false,
+                        );
+                        self.show_toast(
+                            toast::ToastLevel::Success,
+                            format!(
+                                "Automation order {} {} submitted (ID: {})",
+                                qty, symbol, order_id
+                            ),
+                            cx,
+                        );
+                    }
+                    Err(error) => {
+                        error!("✗ Error submitting automation order: {}", error);
+                        self.automation.record(
+                            format!("Rule #{}: {} {} failed: {}", rule, qty, symbol, error),
+                            true,
+                        );
+                        self.show_failure("Automation order failed", &error, None, cx);
+                    }
+                }
+                self.automation.save();
+            }
+            service::ServiceResponse::OrderSubmitted {
+                origin: service::OrderOrigin::Script { name },
+                symbol,
+                qty,
+                result,
+            } => match result {
+                Ok(order_id) => {
+                    self.scripts.push_output(
+                        &name,
+                        format!("{} {} submitted (ID: {})", qty, symbol, order_id),
+                        false,
+                    );
+                    self.show_toast(
+                        toast::ToastLevel::Success,
+                        format!(
+                            "Script {}: {} {} submitted (ID: {})",
+                            name, qty, symbol, order_id
+                        ),
+                        cx,
+                    );
+                }
+                Err(error) => {
+                    error!("✗ Error submitting script order: {}", error);
+                    self.scripts.push_output(
+                        &name,
+                        format!("{} {} failed: {}", qty, symbol, error),
+                        true,
+                    );
+                    self.show_failure(&format!("Script {} order failed", name), &error, None, cx);
+                }
+            },
+            service::ServiceResponse::ExitSubmitted { exit, result } => match result {
+                Ok(order_id) => {
+                    info!(
+                        "✓ Auto exit submitted: {} (ID: {})",
+                        exit.describe(),
+                        order_id
+                    );
+                    self.auto_exits.exit_ids.insert(order_id);
+                    self.show_toast(
+                        toast::ToastLevel::Success,
+                        format!("Exit attached: {}", exit.describe()),
+                        cx,
+                    );
+                }
+                Err(error) => {
+                    error!(
+                        "✗ Error submitting auto exit for order {}: {}",
+                        exit.entry_id, error
+                    );
+                    self.show_failure(
+                        &format!("Exit for {} failed", exit.symbol),
+                        &error,
+                        None,
+                        cx,
+                    );
+                }
+            },
+            service::ServiceResponse::OrderCanceled { order_id, result } => match result {
+                Ok(_) => {
+                    info!("✓ Order canceled successfully");
+                    // WebSocket will handle the order update automatically
+                }
+                Err(error) => {
+                    error!("✗ Error canceling order: {}", error);
+                    self.show_failure(
+                        "Failed to cancel order",
+                        &error,
+                        Some(toast::RetryAction::CancelOrder(order_id)),
+                        cx,
+                    );
+                }
+            },
+            service::ServiceResponse::PositionClosed { symbol, result } => match result {
+                Ok(_) => {
+                    info!("✓ Position closed successfully");
+                    // Refresh positions list (WebSocket handles order updates)
+                    self.fetch_positions(cx);
+                }
+                Err(error) => {
+                    error!("✗ Error closing position: {}", error);
+                    // A reverse whose close failed must not open the other side
+                    if self
+                        .scalp
+                        .pending_reverse
+                        .as_ref()
+                        .is_some_and(|reverse| reverse.symbol == symbol)
+                    {
+                        self.scalp.pending_reverse = None;
+                    }
+                    self.show_failure(
+                        &format!("Failed to close {}", symbol),
+                        &error,
+                        Some(toast::RetryAction::ClosePosition(symbol)),
+                        cx,
+                    );
+                }
+            },
+            service::ServiceResponse::StrategySubmitted(result) => {
+                match result {
+                    Ok(order_id) => {
+                        info!("✓ Strategy order submitted: {}", order_id);
+                        self.strategy.message =
+                            Some(format!("✓ Strategy submitted! ID: {}", order_id));
+                    }
+                    Err(error) => {
+                        error!("✗ Error submitting strategy: {}", error);
+                        self.show_failure("Failed to submit strategy", &error, None, cx);
+                        self.strategy.message = Some(format!("✗ Error: {}", error));
+                    }
+                }
+                self.strategy.submitting = false;
+            }
+            // Ignore stale responses for a previously selected symbol
+            service::ServiceResponse::CorporateActions { symbol, .. }
+                if self.chart.symbol != symbol => {}
+            service::ServiceResponse::CorporateActions { symbol, result } => match result {
+                Ok(actions) => {
+                    info!(
+                        "✓ Loaded {} corporate actions for {}",
+                        actions.len(),
+                        symbol
+                    );
+                    self.corporate_actions = actions;
+                }
+                Err(error) => {
+                    error!("✗ Error fetching corporate actions: {}", error);
+                    // Allow a retry for the same symbol
+                    self.corporate_actions_symbol = None;
+                    self.show_failure(
+                        "Failed to load corporate actions",
+                        &error,
+                        Some(toast::RetryAction::CorporateActions),
+                        cx,
+                    );
+                }
+            },
+            service::ServiceResponse::Snapshot(result) => match result {
+                // Ignore stale responses for a previously selected symbol
+                Ok(snapshot) if snapshot.symbol == self.chart.symbol => {
+                    info!(
+                        "✓ Loaded snapshot for {} (last {:?})",
+                        snapshot.symbol, snapshot.last_price
+                    );
+                    self.apply_snapshot(snapshot);
+                }
+                Ok(_) => {}
+                // The header falls back to streamed prices
+                Err(error) => warn!("⚠️  Failed to load snapshot: {}", error),
+            },
+            service::ServiceResponse::Asset(result) => {
+                match result {
+                    Ok(asset_info) => {
+                        // Ignore stale responses for a previously selected symbol
+                        if asset_info.symbol == self.chart.symbol {
+                            info!(
+                                "✓ Loaded asset {} (shortable: {}, ETB: {}, marginable: {})",
+                                asset_info.symbol,
+                                asset_info.shortable,
+                                asset_info.easy_to_borrow,
+                                asset_info.marginable
+                            );
+                            self.asset = Some(asset_info);
+                        }
+                    }
+                    Err(error) => {
+                        error!("✗ Error fetching asset: {}", error);
+                        self.show_failure(
+                            "Failed to load asset details",
+                            &error,
+                            Some(toast::RetryAction::Asset),
+                            cx,
+                        );
+                    }
+                }
+                self.asset_loading = false;
+            }
+            service::ServiceResponse::Clock(result) => {
+                match result {
+                    Ok(market_clock) => {
+                        self.market_clock = Some(market_clock);
+                        self.clock_failed = false;
+                    }
+                    Err(error) => {
+                        error!("✗ Error fetching market clock: {}", error);
+                        if !self.clock_failed {
+                            self.clock_failed = true;
+                            self.show_failure("Failed to load market clock", &error, None, cx);
+                        }
+                    }
+                }
+                self.clock_loading = false;
+            }
+            service::ServiceResponse::Calendar(result) => {
+                match result {
+                    Ok(events) => {
+                        info!("✓ Loaded {} upcoming calendar events", events.len());
+                        self.calendar_events = events;
+                        self.calendar_loaded = true;
+                        self.calendar_failed = false;
+                    }
+                    Err(error) => {
+                        error!("✗ Error fetching calendar: {}", error);
+                        if !self.calendar_failed {
+                            self.calendar_failed = true;
+                            self.show_failure(
+                                "Failed to load market calendar",
+                                &error,
+                                Some(toast::RetryAction::Calendar),
+                                cx,
+                            );
+                        }
+                    }
+                }
+                self.calendar_loading = false;
+            }
+            service::ServiceResponse::AccountConfig(result) => match result {
+                Ok(config) => {
+                    info!("✓ Loaded account configuration");
+                    self.account_config = Some(config);
+                }
+                Err(error) => {
+                    error!("✗ Error fetching account configuration: {}", error);
+                    self.show_failure(
+                        "Failed to load account configuration",
+                        &error,
+                        Some(toast::RetryAction::AccountConfig),
+                        cx,
+                    );
+                }
+            },
+            service::ServiceResponse::AccountConfigUpdated { previous, result } => {
+                match result {
+                    Ok(config) => {
+                        info!("✓ Account configuration updated");
+                        self.account_config = Some(config);
+                        self.account_config_message = Some("✓ Configuration saved".to_string());
+                    }
+                    Err(error) => {
+                        error!("✗ Error updating account configuration: {}", error);
+                        self.account_config = previous;
+                        self.account_config_message = Some(format!("✗ {}", error));
+                        self.show_failure("Failed to save account configuration", &error, None, cx);
+                    }
+                }
+                self.account_config_saving = false;
+            }
+            // Ignore stale responses for a previous symbol or scope
+            service::ServiceResponse::News { symbols, .. } if self.news.symbols != symbols => {}
+            service::ServiceResponse::News { symbols, result } => {
+                match result {
+                    Ok(articles) => {
+                        info!(
+                            "✓ Loaded {} news articles for {}",
+                            articles.len(),
+                            symbols.join(",")
+                        );
+                        self.news.articles = articles;
+                    }
+                    Err(error) => {
+                        error!("✗ Error fetching news: {}", error);
+                        self.news.articles.clear();
+                        self.show_failure(
+                            "Failed to load news",
+                            &error,
+                            Some(toast::RetryAction::News),
+                            cx,
+                        );
+                        self.news.error = Some(error.to_string());
+                    }
+                }
+                self.news.loading = false;
+            }
+        }
+        cx.notify();
+    }
+
+    /// Place the armed drawing on the bar under the mouse
+    fn place_drawing(&mut self, id: chart::ChartId, cx: &mut Context<Self>) {
+        if !self.drawings.placing_vwap {
+            return;
+        }
+        let Some(chart) = self.chart_by_id(id) else {
+            return;
+        };
+        let Some(bar) = chart.bar_under_mouse().map(|index| &chart.bars[index]) else {
+            return;
+        };
+        let (symbol, at) = (chart.symbol.clone(), bar.timestamp);
+        self.drawings.placing_vwap = false;
+        if self.drawings.add_vwap(&symbol, at) {
+            self.drawings.save();
+        }
+        cx.notify();
+    }
+
+    fn toggle_depth(&mut self, cx: &mut Context<Self>) {
+        self.settings.depth_open = !self.settings.depth_open;
+        self.settings.save();
+        // Start over so levels from before the panel closed don't linger
+        self.depth.reset(&self.chart.symbol);
+        self.sync_market_data();
+        cx.notify();
+    }
+
+    /// Show loaded bars on a chart, or mock data if loading failed
+    fn apply_chart_bars(
+        &mut self,
+        id: chart::ChartId,
+        generation: u64,
+        result: Result<Vec<Bar>, error::TerminalError>,
+        cx: &mut Context<Self>,
+    ) {
+        // The chart window may have closed while loading
+        let Some(chart) = self.chart_mut(id) else {
+            return;
+        };
+        // The symbol or timeframe changed since this fetch was sent
+        if chart.fetch_generation != generation {
+            debug!(
+                "⏭️  Dropping stale bars for {:?} (fetch {}, latest {})",
+                id, generation, chart.fetch_generation
+            );
+            return;
+        }
+        chart.loading = false;
+        chart.retry = None;
+        match result {
+            Ok(bars) => {
+                chart.bars = bars;
+                chart.error = None;
+                // Set scroll offset to show most recent bars by default
+                chart.chart_scroll_offset =
+                    chart.bars.len().saturating_sub(chart.bars_per_screen) as f32;
+                info!(
+                    "✓ Successfully loaded {} bars for {} ({})",
+                    chart.bars.len(),
+                    chart.symbol,
+                    chart.timeframe
+                );
+                // Debug: Show first and last bar prices with timestamps
+                if let (Some(first), Some(last)) = (chart.bars.first(), chart.bars.last()) {
+                    debug!(
+                        "  First bar: O:{:.2} H:{:.2} L:{:.2} C:{:.2} ({})",
+                        first.open,
+                        first.high,
+                        first.low,
+                        first.close,
+                        first.timestamp.format("%Y-%m-%d %H:%M")
+                    );
+                    debug!(
+                        "  Last bar:  O:{:.2} H:{:.2} L:{:.2} C:{:.2} ({})",
+                        last.open,
+                        last.high,
+                        last.low,
+                        last.close,
+                        last.timestamp.format("%Y-%m-%d %H:%M")
+                    );
+                }
+            }
+            Err(error) => {
+                chart.error = Some(error.to_string());
+                chart.bars = generate_mock_data();
+                error!("✗ Error fetching bars: {}. Using mock data.", error);
+                // Retry is only offered for the main chart
+                let retry = (id == chart::ChartId::Main).then_some(toast::RetryAction::Bars);
+                self.show_failure("Failed to load bars (showing mock data)", &error, retry, cx);
+            }
+        }
+    }
+
+    fn handle_stream_update(&mut self, update: StreamUpdate, cx: &mut Context<Self>) {
+        self.stream_health.record(&update);
+        match update {
+            // Only counted; the clock ticker re-renders the popover
+            StreamUpdate::Heartbeat(_) | StreamUpdate::Handshake { .. } => {}
+            StreamUpdate::Connected | StreamUpdate::Reconnected => {
+                info!("✅ WebSocket connected!");
+                self.stream_connected = true;
+                self.stream_status = "Connected".to_string();
+                cx.notify();
+            }
+            StreamUpdate::Disconnected => {
+                warn!("❌ WebSocket disconnected");
+                self.stream_connected = false;
+                self.stream_status = "Disconnected".to_string();
+                cx.notify();
+            }
+            StreamUpdate::TradeUpdate(order_update) => {
+                info!("📦 Received order update for: {}", order_update.symbol);
+                if let Some((kind, title, message)) =
+                    notifications::order_event_notification(&order_update)
+                {
+                    self.notify(kind, title, message);
+                }
+                if let Some(cue) = sound::SoundCue::for_order_event(&order_update.event) {
+                    self.play_sound(cue);
+                }
+                if let Some((level, message)) = toast::order_event_toast(&order_update) {
+                    self.show_toast(level, message, cx);
+                }
+                if self.account.apply_fill(&order_update) {
+                    self.schedule_positions_refresh(cx);
+                    self.send_pending_reverse(&order_update.symbol, cx);
+                }
+                // After the fill moved the local position, which tells entries from exits
+                if self.settings.auto_exits.enabled && self.auto_exits.take_fill(&order_update) {
+                    self.attach_auto_exit(&order_update, cx);
+                }
+                self.account.update_order_from_stream(order_update);
+                cx.notify();
+            }
+            StreamUpdate::AccountUpdate(account_info) => {
+                debug!("💰 Received account update");
+                self.account.update_from_stream(account_info);
+                cx.notify();
+            }
+            StreamUpdate::Error(error) => {
+                error!("❌ Stream error: {}", error);
+                self.notify(
+                    notifications::NotificationKind::Error,
+                    "Stream error".to_string(),
+                    error.clone(),
+                );
+                // Streams reconnect on their own, so there's nothing to retry
+                self.show_error(format!("Stream error: {}", error), None, cx);
+                self.stream_status = format!("Error: {}", error);
+                cx.notify();
+            }
+            StreamUpdate::MarketDataConnected => {
+                info!("✅ Market Data WebSocket connected!");
+                for chart in self.charts_mut() {
+                    chart.market_data_connected = true;
+                }
+                cx.notify();
+            }
+            StreamUpdate::MarketDataReconnected => {
+                info!("✅ Market Data WebSocket reconnected!");
+                for chart in self.charts_mut() {
+                    chart.market_data_connected = true;
+                }
+                self.backfill_charts();
+                cx.notify();
+            }
+            StreamUpdate::MarketDataDisconnected => {
+                warn!("❌ Market Data WebSocket disconnected");
+                for chart in self.charts_mut() {
+                    chart.market_data_connected = false;
+                }
+                cx.notify();
+            }
+            StreamUpdate::TradeTick(trade_tick) => {
+                if let Ok(price) = trade_tick.price.parse::<f64>() {
+                    self.check_alerts(&trade_tick.symbol, price);
+                    self.run_automation(&trade_tick.symbol, price, cx);
+                    self.run_simulator(&trade_tick.symbol, price, cx);
+                }
+                self.last_prices.insert(trade_tick.symbol, trade_tick.price);
+                cx.notify();
+            }
+            StreamUpdate::Quote(quote) => {
+                if self.settings.depth_open && self.depth.apply(&quote) {
+                    cx.notify();
+                }
+            }
+            StreamUpdate::News(headline) => {
+                debug!("📰 Received headline for: {}", headline.symbols.join(","));
+                if self.news.push_live(headline.into()) {
+                    cx.notify();
+                }
+            }
+            StreamUpdate::NewsConnected => {
+                info!("✅ News WebSocket connected!");
+                self.news.stream_connected = true;
+                cx.notify();
+            }
+            StreamUpdate::NewsDisconnected => {
+                warn!("❌ News WebSocket disconnected");
+                self.news.stream_connected = false;
+                cx.notify();
+            }
+            StreamUpdate::BarUpdate(bar_update) => {
+                debug!("📊 Received bar update for: {}", bar_update.symbol);
+                self.update_bars_from_stream(bar_update, cx);
+                cx.notify();
+            }
+        }
+    }
+
+    /// Record which symbols each part of the UI needs streamed
+    fn update_subscription_sources(&mut self) {
+        self.subscriptions
+            .set(stream::SymbolSource::MainChart, [self.chart.symbol.clone()]);
+        self.subscriptions.set(
+            stream::SymbolSource::ChartWindows,
+            self.chart_windows
+                .iter()
+                .map(|slot| slot.chart.symbol.clone()),
+        );
+        // Favorites show live prices on their chips
+        self.subscriptions.set(
+            stream::SymbolSource::Watchlist,
+            self.settings.favorite_symbols.clone(),
+        );
+        self.subscriptions
+            .set(stream::SymbolSource::Alerts, self.alerts.symbols());
+        self.subscriptions
+            .set(stream::SymbolSource::Automation, self.automation.symbols());
+        self.subscriptions
+            .set(stream::SymbolSource::Simulator, self.simulator.symbols());
+        // Quotes only for the main chart, and only while the depth panel shows them
+        if self.depth.symbol != self.chart.symbol {
+            self.depth.reset(&self.chart.symbol);
+        }
+        self.subscriptions
+            .set_quotes(self.settings.depth_open.then(|| self.chart.symbol.clone()));
+    }
+
+    /// Subscribe and unsubscribe the running streams to match what the UI needs
+    fn sync_market_data(&mut self) {
+        self.update_subscription_sources();
+        self.subscriptions.sync();
+    }
+
+    /// Start the market data streams from scratch (startup, or the data feed changed)
+    fn start_market_data_stream(&mut self, cx: &mut Context<Self>) {
+        info!("🚀 Starting Market Data WebSocket stream connection...");
+
+        // Crypto pairs stream from the crypto feed, everything else from the configured stock feed
+        self.update_subscription_sources();
+        let mut receiver = self.subscriptions.restart(self.config.market_data.feed);
+
+        // Updates from streams started before this one are dropped; those stream threads
+        // exit once their command channels close
+        self.market_data_generation += 1;
+        let generation = self.market_data_generation;
+
+        // Spawn a task to listen for updates and apply them to the UI
+        cx.spawn(async move |this, cx| {
+            while let Some(update) = receiver.recv().await {
+                let is_current = this.update(cx, |chart, cx| {
+                    if chart.market_data_generation != generation {
+                        return false;
+                    }
+                    chart.handle_stream_update(update, cx);
+                    true
+                });
+                if !matches!(is_current, Ok(true)) {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
+
+    fn update_bars_from_stream(&mut self, bar_update: stream::BarUpdate, cx: &mut Context<Self>) {
+        // Store the bar update information for display
+        self.chart.last_bar_time = Some(bar_update.timestamp.clone());
+        self.chart.last_bar_symbol = Some(bar_update.symbol.clone());
+        self.chart.last_bar_open = Some(bar_update.open.clone());
+        self.chart.last_bar_high = Some(bar_update.high.clone());
+        self.chart.last_bar_low = Some(bar_update.low.clone());
+        self.chart.last_bar_close = Some(bar_update.close.clone());
+        self.chart.last_bar_volume = Some(bar_update.volume.clone());
+        self.last_prices
+            .insert(bar_update.symbol.clone(), bar_update.close.clone());
+        if let Ok(close) = bar_update.close.parse::<f64>() {
+            self.check_alerts(&bar_update.symbol, close);
+            self.run_automation(&bar_update.symbol, close, cx);
+        }
+        let is_main_symbol = bar_update.symbol == self.chart.symbol;
+
+        debug!(
+            "📊 Bar Update: {} @ {} - O:{} H:{} L:{} C:{} V:{}",
+            bar_update.symbol,
+            bar_update.timestamp,
+            bar_update.open,
+            bar_update.high,
+            bar_update.low,
+            bar_update.close,
+            bar_update.volume,
+        );
+
+        // Every chart showing this symbol (main and chart windows) takes the update
+        for chart in self.charts_mut() {
+            if chart.symbol == bar_update.symbol {
+                chart.apply_bar_update(&bar_update);
+            }
+        }
+
+        // Auto scripts see the main chart's bars, including this one
+        if is_main_symbol {
+            let auto: Vec<String> = self.scripts.auto.iter().cloned().collect();
+            for name in auto {
+                self.run_script(name, cx);
+            }
+        }
+
+        // Notify to update the UI
+        cx.notify();
+    }
+
+    fn fetch_bars(&mut self, cx: &mut Context<Self>) {
+        self.fetch_chart_bars(chart::ChartId::Main, cx);
+    }
+
+    /// Load bars for the main chart or a chart window
+    fn fetch_chart_bars(&mut self, id: chart::ChartId, cx: &mut Context<Self>) {
+        if let Some(generation) = self.start_bars_fetch(id, cx) {
+            self.send_bars_request(id, generation);
+        }
+    }
+
+    /// Load bars once the symbol or timeframe has stopped changing. Each call supersedes
+    /// the previous one, so only the last selection is requested.
+    fn fetch_chart_bars_debounced(&mut self, id: chart::ChartId, cx: &mut Context<Self>) {
+        let Some(generation) = self.start_bars_fetch(id, cx) else {
+            return;
+        };
+        cx.spawn(async move |this, cx| {
+            cx.background_executor()
+                .timer(std::time::Duration::from_millis(chart::FETCH_DEBOUNCE_MS))
+                .await;
+            let _ = this.update(cx, |terminal, _cx| {
+                terminal.send_bars_request(id, generation);
+            });
+        })
+        .detach();
+    }
+
+    /// Show the chart as loading and start a new fetch generation
+    fn start_bars_fetch(&mut self, id: chart::ChartId, cx: &mut Context<Self>) -> Option<u64> {
+        let chart = self.chart_mut(id)?;
+        chart.loading = true;
+        chart.retry = None;
+        chart.error = None;
+        chart.fetch_generation += 1;
+        let generation = chart.fetch_generation;
+        cx.notify();
+        Some(generation)
+    }
+
+    /// Request bars for a fetch generation, unless a newer fetch has started since
+    fn send_bars_request(&mut self, id: chart::ChartId, generation: u64) {
+        let default_limit = self.config.chart.bar_limit;
+        let data_feed = self.config.market_data.feed;
+        let Some(chart) = self.chart_by_id(id) else {
+            return;
+        };
+        if chart.fetch_generation != generation {
+            return;
+        }
+
+        let symbol = chart.symbol.clone();
+        let timeframe = chart.timeframe.clone();
+        let limit = chart
+            .bar_limit
+            .text()
+            .parse::<u32>()
+            .unwrap_or(default_limit);
+
+        self.service.send(service::ServiceRequest::Bars {
+            chart: id,
+            generation,
+            symbol,
+            timeframe,
+            limit,
+            feed: data_feed,
+        });
+    }
+
+    /// After a market data reconnect, fetch the bars each streamed chart missed
+    fn backfill_charts(&mut self) {
+        let feed = self.config.market_data.feed;
+        let ids: Vec<chart::ChartId> = std::iter::once(chart::ChartId::Main)
+            .chain(
+                self.chart_windows
+                    .iter()
+                    .map(|slot| chart::ChartId::Window(slot.id)),
+            )
+            .collect();
+        for id in ids {
+            let Some(chart) = self.chart_by_id(id) else {
+                continue;
+            };
+            // A chart still loading will get current bars anyway
+            if chart.loading || !self.subscriptions.is_subscribed(&chart.symbol) {
+                continue;
+            }
+            let Some(last) = chart.bars.last() else {
+                continue;
+            };
+            // From the start of the last candle, so a partial candle is completed too
+            let since = chart::align_timestamp_to_timeframe(last.timestamp, &chart.timeframe);
+            info!(
+                "🩹 Backfilling {} {} bars since {}",
+                chart.symbol,
+                chart.timeframe,
+                since.format("%Y-%m-%d %H:%M")
+            );
+            self.service.send(service::ServiceRequest::Backfill {
+                chart: id,
+                generation: chart.fetch_generation,
+                symbol: chart.symbol.clone(),
+                timeframe: chart.timeframe.clone(),
+                since,
+                feed,
+            });
+        }
+    }
+
+    /// Merge backfilled bars into a chart, unless it has been reloaded since
+    fn apply_backfill(
+        &mut self,
+        id: chart::ChartId,
+        generation: u64,
+        result: Result<Vec<Bar>, error::TerminalError>,
+    ) {
+        let Some(chart) = self.chart_mut(id) else {
+            return;
+        };
+        if chart.fetch_generation != generation {
+            return;
+        }
+        chart.retry = None;
+        match result {
+            Ok(bars) => {
+                let added = chart.merge_backfill(bars);
+                info!(
+                    "✓ Backfilled {} missed bars for {} ({})",
+                    added, chart.symbol, chart.timeframe
+                );
+            }
+            // The chart keeps its bars; live updates carry on from here
+            Err(error) => warn!("⚠️  Backfill for {} failed: {}", chart.symbol, error),
+        }
+    }
+
+    /// Chart by id (None once a chart window has closed)
+    fn chart_by_id(&self, id: chart::ChartId) -> Option<&Chart> {
+        match id {
+            chart::ChartId::Main => Some(&self.chart),
+            chart::ChartId::Window(id) => self
+                .chart_windows
+                .iter()
+                .find(|slot| slot.id == id)
+                .map(|slot| &slot.chart),
+        }
+    }
+
+    fn chart_mut(&mut self, id: chart::ChartId) -> Option<&mut Chart> {
+        match id {
+            chart::ChartId::Main => Some(&mut self.chart),
+            chart::ChartId::Window(id) => self.chart_slot_mut(id).map(|slot| &mut slot.chart),
+        }
+    }
+
+    /// Donchian settings of a chart: the saved ones for the main chart, the window's own copy
+    /// for a chart window
+    fn donchian(&self, id: chart::ChartId) -> Option<&overlays::DonchianSettings> {
+        match id {
+            chart::ChartId::Main => Some(&self.settings.overlays.donchian),
+            chart::ChartId::Window(id) => self
+                .chart_windows
+                .iter()
+                .find(|slot| slot.id == id)
+                .map(|slot| &slot.donchian),
+        }
+    }
+
+    fn donchian_mut(&mut self, id: chart::ChartId) -> Option<&mut overlays::DonchianSettings> {
+        match id {
+            chart::ChartId::Main => Some(&mut self.settings.overlays.donchian),
+            chart::ChartId::Window(id) => self.chart_slot_mut(id).map(|slot| &mut slot.donchian),
+        }
+    }
+
+    fn chart_slot_mut(&mut self, id: usize) -> Option<&mut chart_window::ChartSlot> {
+        self.chart_windows.iter_mut().find(|slot| slot.id == id)
+    }
+
+    /// The main chart followed by every chart window's
+    fn charts_mut(&mut self) -> impl Iterator<Item = &mut Chart> {
+        std::iter::once(&mut self.chart)
+            .chain(self.chart_windows.iter_mut().map(|slot| &mut slot.chart))
+    }
+
+    /// Corporate actions grouped by the visible bar on their ex-date (daily charts only)
+    fn corporate_action_markers(
+        &self,
+        start_index: usize,
+        end_index: usize,
+    ) -> Vec<(usize, Vec<corporate_actions::CorporateAction>)> {
+        if self.chart.timeframe != "1Day" || self.corporate_actions.is_empty() {
+            return Vec::new();
+        }
+
+        (start_index..end_index)
+            .filter_map(|index| {
+                let date = self.chart.bars[index].timestamp.date_naive();
+                let actions: Vec<_> = self
+                    .corporate_actions
+                    .iter()
+                    .filter(|action| action.ex_date() == date)
+                    .cloned()
+                    .collect();
+                (!actions.is_empty()).then_some((index, actions))
+            })
+            .collect()
+    }
+
+    fn render_candlesticks(
+        &mut self,
+        id: chart::ChartId,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let Some(chart) = self.chart_by_id(id) else {
+            return div();
+        };
+        let style = &self.config.chart_style;
+        let numbers = &self.config.numbers.format;
+        if chart.bars.is_empty() {
+            let message = if let Some((attempt, attempts)) = chart.retry {
+                format!(
+                    "Connection problem, retrying (attempt {} of {})...",
+                    attempt, attempts
+                )
+            } else if chart.loading {
+                "Loading data from Alpaca Markets...".to_string()
+            } else if let Some(ref error) = chart.error {
+                error.clone()
+            } else {
+                "No data available.".to_string()
+            };
+
+            return div()
+                .grid()
+                .items_center()
+                .justify_center()
+                .size_full()
+                .child(div().text_color(rgb(0x808080)).child(message));
+        }
+
+        // Calculate visible range of bars (windowing for scrolling)
+        let (start_index, end_index) = chart.visible_range();
+        let visible_bars = &chart.bars[start_index..end_index];
+
+        // Calculate price range for visible bars only
+        let max_price = visible_bars
+            .iter()
+            .map(|b| b.close)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let min_price = visible_bars
+            .iter()
+            .map(|b| b.close)
+            .fold(f64::INFINITY, f64::min);
+
+        let price_range = max_price - min_price;
+
+        // Session figures come from all loaded bars, so they don't move with the scroll
+        let session = chart.session_stats().map(|mut session| {
+            // The main chart's snapshot has the regular-session close, which intraday bars
+            // with after-hours trades don't
+            if id == chart::ChartId::Main
+                && let Some(close) = self
+                    .chart_snapshot()
+                    .and_then(|snapshot| snapshot.previous_close())
+            {
+                session.prev_close = Some(close);
+            }
+            session
+        });
+        let price_padding = price_range * 0.1;
+        let adjusted_max = max_price + price_padding;
+        let adjusted_min = min_price - price_padding;
+        let adjusted_range = adjusted_max - adjusted_min;
+
+        // Calculate bar width based on visible bars with padding
+        let padding_left_percent = 5.0; // 5% left padding
+        let padding_right_percent = 5.0; // 5% right padding
+        let usable_width_percent = 100.0 - padding_left_percent - padding_right_percent;
+
+        let visible_bar_count = visible_bars.len() as f32;
+        let bar_spacing_ratio = 0.2; // 20% spacing between bars
+        let bar_width_percent =
+            (usable_width_percent / visible_bar_count) * (1.0 - bar_spacing_ratio);
+        let total_bar_width_percent = usable_width_percent / visible_bar_count;
+        let plot = chart::PlotArea {
+            start_index,
+            end_index,
+            min_price: adjusted_min,
+            max_price: adjusted_max,
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .size_full()
+            .child(
+                // Chart container - expands to fill available space
+                div()
+                    .id("chart-container")
+                    .relative()
+                    .flex_1()
+                    .w_full()
+                    .bg(rgb(style.background.0))
+                    .border_2()
+                    .border_color(rgb(0x404040))
+                    // Inner div with relative positioning for accurate mouse tracking
+                    .child(
+                        div()
+                            .relative()
+                            .size_full()
+                            .overflow_hidden()
+                            .on_mouse_move(cx.listener(
+                                move |this, event: &gpui::MouseMoveEvent, window, cx| {
+                                    // The ADX sub-panel (and the gap above it) takes from the chart
+                                    let sub_panel_height = if this.settings.overlays.adx.enabled {
+                                        chart::SUB_PANEL_HEIGHT + 16.0
+                                    } else {
+                                        0.0
+                                    };
+                                    let Some(chart) = this.chart_mut(id) else {
+                                        return;
+                                    };
+                                    // CALIBRATION GUIDE for offset_y:
+                                    // 1. Hover at the VERY TOP of the chart (where price is highest)
+                                    // 2. If crosshair price is HIGHER than expected: INCREASE offset_y
+                                    // 3. If crosshair price is LOWER than expected: DECREASE offset_y
+                                    // Offsets are tuned per window in ChartId::crosshair_layout
+                                    let layout = id.crosshair_layout();
+                                    let offset_x = px(layout.offset_x);
+                                    let offset_y = px(layout.offset_y);
+
+                                    let relative_x = event.position.x - offset_x;
+                                    let relative_y = event.position.y - offset_y;
+
+                                    chart.mouse_position = Some(gpui::Point {
+                                        x: relative_x,
+                                        y: relative_y,
+                                    });
+
+                                    // Calculate chart bounds from window size
+                                    let window_bounds = window.bounds();
+                                    let window_width: f32 = window_bounds.size.width.into();
+                                    let window_height: f32 = window_bounds.size.height.into();
+
+                                    // Chart width calculation
+                                    let chart_width =
+                                        window_width * layout.width_fraction - layout.width_inset;
+
+                                    // FIXED-PIXEL APPROACH: Chart height = window height - all fixed UI elements
+                                    // This works regardless of window size because we subtract absolute pixels
+                                    //
+                                    // CALIBRATION: Adjust bottom_offset if prices don't match grid
+                                    // - If crosshair shows LOWER price than grid: INCREASE bottom_offset
+                                    // - If crosshair shows HIGHER price than grid: DECREASE bottom_offset
+                                    //
+                                    // Components below the chart (approximate values):
+                                    // - Scroll controls: ~50px
+                                    // - Gap before footer: ~24px
+                                    // - Footer: ~280px
+                                    // - Window bottom padding: ~40px
+                                    let bottom_offset = layout.bottom_offset + sub_panel_height;
+
+                                    let offset_y_f32: f32 = offset_y.into();
+                                    let chart_height = window_height - offset_y_f32 - bottom_offset;
+
+                                    // Calibration info (enable with RUST_LOG=chart_calibration=debug)
+                                    debug!(target: "chart_calibration", "Window H: {:.0}px, Chart H: {:.0}px (= {:.0} - {:.0} - {:.0}), Mouse Y: {:.0}px",
+                                             window_height, chart_height, window_height, offset_y_f32, bottom_offset, relative_y);
+
+                                    chart.chart_bounds = Some((chart_width, chart_height));
+                                    chart.show_crosshair = true;
+                                    cx.notify();
+                                },
+                            ))
+                            // Clicking a bar places the armed drawing tool
+                            .on_mouse_down(
+                                gpui::MouseButton::Left,
+                                cx.listener(move |this, _event: &gpui::MouseDownEvent, _window, cx| {
+                                    this.place_drawing(id, cx);
+                                }),
+                            )
+                            .on_scroll_wheel(cx.listener(
+                                move |this, event: &gpui::ScrollWheelEvent, _window, cx| {
+                                    let Some(chart) = this.chart_mut(id) else {
+                                        return;
+                                    };
+                                    let pixel_delta = event.delta.pixel_delta(px(1.0));
+                                    let scroll_amount: f32 = pixel_delta.y.into();
+
+                                    // Check if Ctrl is pressed for zoom
+                                    if event.modifiers.control {
+                                        // Zoom: adjust bars_per_screen
+                                        let zoom_amount = (scroll_amount * 2.0) as i32;
+
+                                        if zoom_amount > 0 {
+                                            // Zoom out (show more bars)
+                                            chart.bars_per_screen = (chart.bars_per_screen
+                                                + zoom_amount as usize)
+                                                .min(chart.bars.len());
+                                        } else {
+                                            // Zoom in (show fewer bars)
+                                            chart.bars_per_screen =
+                                                (chart.bars_per_screen as i32 + zoom_amount).max(10)
+                                                    as usize;
+                                        }
+
+                                        // Adjust scroll offset to keep it in bounds
+                                        let max_offset =
+                                            chart.bars.len().saturating_sub(chart.bars_per_screen)
+                                                as f32;
+                                        chart.chart_scroll_offset =
+                                            chart.chart_scroll_offset.min(max_offset);
+                                    } else {
+                                        // Normal scroll: move through bars
+                                        let max_offset =
+                                            chart.bars.len().saturating_sub(chart.bars_per_screen)
+                                                as f32;
+                                        let scroll_amount = scroll_amount * 0.5; // Adjust sensitivity
+
+                                        if scroll_amount > 0.0 {
+                                            // Scroll forward (show older bars)
+                                            chart.chart_scroll_offset = (chart.chart_scroll_offset
+                                                + scroll_amount)
+                                                .min(max_offset);
+                                        } else {
+                                            // Scroll backward (show newer bars)
+                                            chart.chart_scroll_offset =
+                                                (chart.chart_scroll_offset + scroll_amount).max(0.0);
+                                        }
+                                    }
+
+                                    cx.notify();
+                                },
+                            ))
+                            // Price grid lines with round values (adaptive to zoom level)
+                            .children({
+                                // Adjust grid line count based on zoom level
+                                let grid_count = if chart.bars_per_screen <= 20 {
+                                    12 // Very zoomed in - show many grid lines
+                                } else if chart.bars_per_screen <= 50 {
+                                    10 // Moderately zoomed in
+                                } else if chart.bars_per_screen <= 100 {
+                                    8 // Default zoom
+                                } else if chart.bars_per_screen <= 200 {
+                                    6 // Zoomed out
+                                } else if chart.bars_per_screen <= 500 {
+                                    5 // More zoomed out
+                                } else {
+                                    4 // Very zoomed out - show fewer grid lines
+                                };
+
+                                let grid_values = chart::calculate_round_grid_values(
+                                    adjusted_min,
+                                    adjusted_max,
+                                    grid_count,
+                                );
+                                grid_values.into_iter().map(|price| {
+                                    // Calculate Y position as percentage
+                                    let y_percent =
+                                        ((adjusted_max - price) / adjusted_range) as f32 * 100.0;
+
+                                    div()
+                                        .absolute()
+                                        .left_0()
+                                        .top(gpui::relative(y_percent / 100.0))
+                                        .w_full()
+                                        .h(px(1.0))
+                                        .bg(rgb(style.grid.0))
+                                        .child(
+                                            div()
+                                                .absolute()
+                                                .left(px(5.0))
+                                                .top(px(-8.0))
+                                                .text_xs()
+                                                .text_color(rgb(0x808080))
+                                                .child(numbers.price(price)),
+                                        )
+                                })
+                            })
+                            // Candlestick wicks
+                            .children(visible_bars.iter().enumerate().map(|(i, bar)| {
+                                // Calculate positions as percentages with padding
+                                let x_percent =
+                                    padding_left_percent + i as f32 * total_bar_width_percent;
+
+                                // Calculate Y positions as percentages with padding
+                                let padding_top_percent = 5.0;
+                                let padding_bottom_percent = 5.0;
+                                let usable_height_percent =
+                                    100.0 - padding_top_percent - padding_bottom_percent;
+
+                                let high_y_percent = padding_top_percent
+                                    + ((adjusted_max - bar.high) / adjusted_range) as f32
+                                        * usable_height_percent;
+                                let low_y_percent = padding_top_percent
+                                    + ((adjusted_max - bar.low) / adjusted_range) as f32
+                                        * usable_height_percent;
+
+                                let wick_height_percent = low_y_percent - high_y_percent;
+
+                                // Determine if bullish or bearish
+                                let is_bullish = bar.close >= bar.open;
+
+                                // Check if this is the most recent bar (live updating)
+                                let is_latest_bar = i == visible_bars.len() - 1 &&
+                                    end_index == chart.bars.len();
+
+                                let color = rgb(style.candle_color(is_bullish));
+                                let wick_width = if is_latest_bar {
+                                    style.wick_width + 1.0
+                                } else {
+                                    style.wick_width
+                                };
+
+                                // High-Low wick (thin line)
+                                div()
+                                    .absolute()
+                                    .left(gpui::relative(
+                                        (x_percent + bar_width_percent / 2.0) / 100.0,
+                                    ))
+                                    .top(gpui::relative(high_y_percent / 100.0))
+                                    .w(px(wick_width))
+                                    .h(gpui::relative(wick_height_percent / 100.0))
+                                    .bg(color)
+                            }))
+                            // Candlestick bodies
+                            .children(visible_bars.iter().enumerate().map(|(i, bar)| {
+                                // Calculate positions as percentages with padding
+                                let x_percent =
+                                    padding_left_percent + i as f32 * total_bar_width_percent;
+
+                                // Calculate Y positions as percentages with padding
+                                let padding_top_percent = 5.0;
+                                let padding_bottom_percent = 5.0;
+                                let usable_height_percent =
+                                    100.0 - padding_top_percent - padding_bottom_percent;
+
+                                let open_y_percent = padding_top_percent
+                                    + ((adjusted_max - bar.open) / adjusted_range) as f32
+                                        * usable_height_percent;
+                                let close_y_percent = padding_top_percent
+                                    + ((adjusted_max - bar.close) / adjusted_range) as f32
+                                        * usable_height_percent;
+
+                                let body_top_percent = open_y_percent.min(close_y_percent);
+                                let body_height_percent =
+                                    (open_y_percent - close_y_percent).abs().max(0.1);
+
+                                // Determine if bullish or bearish
+                                let is_bullish = bar.close >= bar.open;
+
+                                // Check if this is the most recent bar (live updating)
+                                let is_latest_bar = i == visible_bars.len() - 1 &&
+                                    end_index == chart.bars.len();
+
+                                let color = rgb(style.candle_color(is_bullish));
+                                let fill_color = color;
+
+                                // Open-Close body (thicker rectangle)
+                                let mut body_div = div()
+                                    .absolute()
+                                    .left(gpui::relative(x_percent / 100.0))
+                                    .top(gpui::relative(body_top_percent / 100.0))
+                                    .w(gpui::relative(bar_width_percent / 100.0))
+                                    .h(gpui::relative(body_height_percent / 100.0))
+                                    .bg(fill_color);
+
+                                // Add thicker border and glow effect for the latest bar
+                                if is_latest_bar {
+                                    body_div = body_div
+                                        .border_2()
+                                        .border_color(color)
+                                        .shadow_lg();
+                                } else {
+                                    body_div = body_div
+                                        .border_1()
+                                        .border_color(color);
+                                }
+
+                                body_div
+                            }))
+                            // Volume-at-price histogram along the right edge
+                            .children(self.render_volume_profile(
+                                chart,
+                                visible_bars,
+                                adjusted_min,
+                                adjusted_max,
+                            ))
+                            // Anchored VWAPs of the symbol
+                            .children(self.render_anchored_vwaps(chart, &plot))
+                            // Prior day's or week's pivot levels
+                            .children(self.render_pivots(chart, &plot))
+                            .children(self.render_supertrend(chart, &plot))
+                            .children(self.render_parabolic_sar(chart, &plot))
+                            .children(self.render_keltner(chart, &plot))
+                            .children(self.render_donchian(id, chart, &plot))
+                            // News markers along the x-axis; hover shows the headlines
+                            .children(
+                                if id == chart::ChartId::Main {
+                                    news::news_markers(&self.news.articles, &chart.symbol, &chart.bars)
+                                } else {
+                                    Vec::new()
+                                }
+                                .into_iter()
+                                .filter(|(index, _)| (start_index..end_index).contains(index))
+                                .map(|(index, articles)| {
+                                    let x_percent = padding_left_percent
+                                        + (index - start_index) as f32 * total_bar_width_percent
+                                        + bar_width_percent / 2.0;
+                                    let is_hovered = self.news.hovered_marker == Some(index);
+
+                                    div()
+                                        .absolute()
+                                        .left(gpui::relative(x_percent / 100.0))
+                                        .bottom(px(4.0))
+                                        .child(
+                                            div()
+                                                .id(ElementId::Name(
+                                                    format!("news-marker-{}", index).into(),
+                                                ))
+                                                .ml(px(-7.0))
+                                                .size(px(14.0))
+                                                .rounded_full()
+                                                .flex()
+                                                .items_center()
+                                                .justify_center()
+                                                .bg(if is_hovered {
+                                                    rgb(0xf2cc60)
+                                                } else {
+                                                    rgb(0xbb8009)
+                                                })
+                                                .text_color(rgb(0x0d1117))
+                                                .text_size(px(9.0))
+                                                .font_weight(FontWeight::BOLD)
+                                                .cursor_pointer()
+                                                .child(if articles.len() > 1 {
+                                                    articles.len().to_string()
+                                                } else {
+                                                    "N".to_string()
+                                                })
+                                                .on_hover(cx.listener(
+                                                    move |this, hovered: &bool, _, cx| {
+                                                        if *hovered {
+                                                            this.news.hovered_marker = Some(index);
+                                                        } else if this.news.hovered_marker
+                                                            == Some(index)
+                                                        {
+                                                            this.news.hovered_marker = None;
+                                                        }
+                                                        cx.notify();
+                                                    },
+                                                )),
+                                        )
+                                        .when(is_hovered, |this| {
+                                            this.child(
+                                                div()
+                                                    .absolute()
+                                                    .bottom(px(20.0))
+                                                    .left(px(-140.0))
+                                                    .w(px(280.0))
+                                                    .p_2()
+                                                    .flex()
+                                                    .flex_col()
+                                                    .gap_1()
+                                                    .bg(rgb(0x161b22))
+                                                    .border_1()
+                                                    .border_color(rgb(0x30363d))
+                                                    .rounded_md()
+                                                    .shadow_lg()
+                                                    .text_xs()
+                                                    .children(articles.into_iter().map(|article| {
+                                                        div()
+                                                            .flex()
+                                                            .flex_col()
+                                                            .child(
+                                                                div()
+                                                                    .text_color(rgb(0x8b949e))
+                                                                    .child(format!(
+                                                                        "{} · {}",
+                                                                        article
+                                                                            .created_at
+                                                                            .with_timezone(
+                                                                                &chrono::Local
+                                                                            )
+                                                                            .format("%m-%d %H:%M"),
+                                                                        article.source
+                                                                    )),
+                                                            )
+                                                            .child(
+                                                                div()
+                                                                    .text_color(rgb(0xffffff))
+                                                                    .child(article.headline.clone()),
+                                                            )
+                                                    })),
+                                            )
+                                        })
+                                }),
+                            )
+                            // Split / ex-dividend markers along the top (daily charts only)
+                            .children(
+                                if id == chart::ChartId::Main {
+                                    self.corporate_action_markers(start_index, end_index)
+                                } else {
+                                    Vec::new()
+                                }
+                                    .into_iter()
+                                    .map(|(index, actions)| {
+                                        let x_percent = padding_left_percent
+                                            + (index - start_index) as f32 * total_bar_width_percent
+                                            + bar_width_percent / 2.0;
+                                        let is_hovered = self.hovered_corporate_action == Some(index);
+                                        let has_split = actions.iter().any(|action| {
+                                            matches!(action, corporate_actions::CorporateAction::Split { .. })
+                                        });
+
+                                        div()
+                                            .absolute()
+                                            .left(gpui::relative(x_percent / 100.0))
+                                            .top(px(4.0))
+                                            .child(
+                                                div()
+                                                    .id(ElementId::Name(
+                                                        format!("corporate-action-{}", index).into(),
+                                                    ))
+                                                    .ml(px(-7.0))
+                                                    .size(px(14.0))
+                                                    .rounded_sm()
+                                                    .flex()
+                                                    .items_center()
+                                                    .justify_center()
+                                                    .bg(if has_split {
+                                                        rgb(0x8957e5)
+                                                    } else {
+                                                        rgb(0x1f6feb)
+                                                    })
+                                                    .border_1()
+                                                    .border_color(if is_hovered {
+                                                        rgb(0xffffff)
+                                                    } else {
+                                                        rgb(0x30363d)
+                                                    })
+                                                    .text_color(rgb(0xffffff))
+                                                    .text_size(px(9.0))
+                                                    .font_weight(FontWeight::BOLD)
+                                                    .child(if has_split { "S" } else { "D" })
+                                                    .on_hover(cx.listener(
+                                                        move |this, hovered: &bool, _, cx| {
+                                                            if *hovered {
+                                                                this.hovered_corporate_action = Some(index);
+                                                            } else if this.hovered_corporate_action
+                                                                == Some(index)
+                                                            {
+                                                                this.hovered_corporate_action = None;
+                                                            }
+                                                            cx.notify();
+                                                        },
+                                                    )),
+                                            )
+                                            .when(is_hovered, |this| {
+                                                this.child(
+                                                    div()
+                                                        .absolute()
+                                                        .top(px(20.0))
+                                                        .left(px(-110.0))
+                                                        .w(px(220.0))
+                                                        .p_2()
+                                                        .flex()
+                                                        .flex_col()
+                                                        .gap_1()
+                                                        .bg(rgb(0x161b22))
+                                                        .border_1()
+                                                        .border_color(rgb(0x30363d))
+                                                        .rounded_md()
+                                                        .shadow_lg()
+                                                        .text_xs()
+                                                        .children(actions.into_iter().map(|action| {
+                                                            div()
+                                                                .flex()
+                                                                .justify_between()
+                                                                .child(
+                                                                    div()
+                                                                        .text_color(rgb(0x8b949e))
+                                                                        .child(format!("Ex {}", action.ex_date())),
+                                                                )
+                                                                .child(
+                                                                    div()
+                                                                        .text_color(rgb(0xffffff))
+                                                                        .child(action.description()),
+                                                                )
+                                                        })),
+                                                )
+                                            })
+                                    }),
+                            )
+                            // Crosshair overlay
+                            .children(if chart.show_crosshair && chart.mouse_position.is_some() {
+                                let mouse_pos = chart.mouse_position.unwrap();
+
+                                // Calculate price from mouse Y position
+                                // Grid lines use full height (0-100%) without padding
+                                let mouse_y_f32: f32 = mouse_pos.y.into();
+                                let chart_height =
+                                    chart.chart_bounds.map(|(_, h)| h).unwrap_or(400.0);
+
+                                // Account for 2px border on chart container
+                                let border_offset = 2.0;
+                                let adjusted_mouse_y = mouse_y_f32 - border_offset;
+                                let adjusted_chart_height = chart_height - (border_offset * 2.0);
+
+                                let y_percent = (adjusted_mouse_y / adjusted_chart_height) * 100.0;
+
+                                // Convert Y position to price (matches grid line calculation)
+                                // Grid formula: y_percent = ((adjusted_max - price) / adjusted_range) * 100.0
+                                // Inverse: price = adjusted_max - (y_percent / 100.0 * adjusted_range)
+                                let price_at_cursor =
+                                    adjusted_max - ((y_percent / 100.0) as f64 * adjusted_range);
+
+                                // Price calculation for calibration (chart_calibration target)
+                                debug!(target: "chart_calibration", "Y%%: {:.1}, Price: ${:.2}, Range: ${:.2}-${:.2}",
+                                         y_percent, price_at_cursor, adjusted_min, adjusted_max);
+                                debug!(target: "chart_calibration", ">>> If crosshair shows LOWER than grid: INCREASE bottom_offset (line 879)");
+                                debug!(target: "chart_calibration", ">>> If crosshair shows HIGHER than grid: DECREASE bottom_offset (line 879)");
+                                debug!(target: "chart_calibration", ">>> Current bottom_offset: 394.0 - Adjust by 5-10px increments");
+
+                                // Calculate bar index from mouse X position
+                                let mouse_x_f32: f32 = mouse_pos.x.into();
+                                let chart_width =
+                                    chart.chart_bounds.map(|(w, _)| w).unwrap_or(800.0);
+                                let x_percent = (mouse_x_f32 / chart_width) * 100.0;
+
+                                let padding_left_percent = 5.0;
+                                let usable_width_percent = 100.0 - padding_left_percent - 5.0;
+                                let bar_index = ((x_percent - padding_left_percent)
+                                    / usable_width_percent
+                                    * visible_bar_count)
+                                    as usize;
+
+                                // Get the timestamp if valid bar index
+                                let timestamp_opt = if bar_index < visible_bars.len() {
+                                    Some(visible_bars[bar_index].timestamp)
+                                } else {
+                                    None
+                                };
+
+                                let mut elements = vec![
+                                    // Vertical crosshair line
+                                    div()
+                                        .absolute()
+                                        .left(mouse_pos.x)
+                                        .top(px(0.0))
+                                        .w(px(1.0))
+                                        .h(gpui::relative(1.0))
+                                        .bg(gpui::rgba(0xFFFFFF40))
+                                        .into_any_element(),
+                                    // Horizontal crosshair line
+                                    div()
+                                        .absolute()
+                                        .left(px(0.0))
+                                        .top(mouse_pos.y)
+                                        .w(gpui::relative(1.0))
+                                        .h(px(1.0))
+                                        .bg(gpui::rgba(0xFFFFFF40))
+                                        .into_any_element(),
+                                ];
+
+                                // Price label on Y-axis (right side)
+                                // Always show price label for calibration (removed bounds check)
+                                elements.push(
+                                    div()
+                                        .absolute()
+                                        .right(px(5.0))
+                                        .top(mouse_pos.y - px(10.0))
+                                        .px_2()
+                                        .py_1()
+                                        .bg(rgb(0x1f6feb))
+                                        .border_1()
+                                        .border_color(rgb(0x388bfd))
+                                        .rounded_sm()
+                                        .text_xs()
+                                        .font_weight(FontWeight::SEMIBOLD)
+                                        .text_color(rgb(0xffffff))
+                                        .child(numbers.price(price_at_cursor))
+                                        .into_any_element(),
+                                );
+
+                                // Timestamp label on X-axis (bottom)
+                                if let Some(timestamp) = timestamp_opt {
+                                    // Format timestamp for display (MM-DD HH:MM)
+                                    let display_time = timestamp.format("%m-%d %H:%M").to_string();
+
+                                    elements.push(
+                                        div()
+                                            .absolute()
+                                            .left(mouse_pos.x - px(40.0))
+                                            .bottom(px(5.0))
+                                            .px_2()
+                                            .py_1()
+                                            .bg(rgb(0x1f6feb))
+                                            .border_1()
+                                            .border_color(rgb(0x388bfd))
+                                            .rounded_sm()
+                                            .text_xs()
+                                            .font_weight(FontWeight::SEMIBOLD)
+                                            .text_color(rgb(0xffffff))
+                                            .child(display_time)
+                                            .into_any_element(),
+                                    );
+                                }
+
+                                elements
+                            } else {
+                                vec![]
+                            }),
+                    ),
+            )
+            .children(self.render_adx_panel(id, chart, &plot, cx))
+            .child(
+                // Scroll controls
+                div()
+                    .flex()
+                    .flex_row()
+                    .gap_2()
+                    .items_center()
+                    .justify_center()
+                    .p_2()
+                    .on_mouse_move(cx.listener(move |this, _event, _window, cx| {
+                        let Some(chart) = this.chart_mut(id) else {
+                            return;
+                        };
+                        // Hide crosshair when mouse is over scroll controls
+                        chart.show_crosshair = false;
+                        cx.notify();
+                    }))
+                    .child(
+                        div()
+                            .px_3()
+                            .py_1()
+                            .bg(rgb(0x2a2a2a))
+                            .border_1()
+                            .border_color(rgb(0x404040))
+                            .rounded_md()
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x3a3a3a)))
+                            .on_mouse_down(
+                                gpui::MouseButton::Left,
+                                cx.listener(move |this, _event: &gpui::MouseDownEvent, _window, cx| {
+                                    let Some(chart) = this.chart_mut(id) else {
+                                        return;
+                                    };
+                                    if chart.chart_scroll_offset > 0.0 {
+                                        chart.chart_scroll_offset =
+                                            (chart.chart_scroll_offset - 50.0).max(0.0);
+                                        cx.notify();
+                                    }
+                                }),
+                            )
+                            .child("← Previous 50"),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .flex_row()
+                            .gap_2()
+                            .items_center()
+                            .text_sm()
+                            .text_color(rgb(0x808080))
+                            .child(format!(
+                                "Showing bars {}-{} of {} | Zoom: {} bars",
+                                start_index + 1,
+                                end_index,
+                                chart.bars.len(),
+                                chart.bars_per_screen
+                            ))
+                            .when(end_index == chart.bars.len() && chart.market_data_connected, |this| {
+                                this.child(
+                                    div()
+                                        .px_2()
+                                        .py_0p5()
+                                        .bg(rgb(0x238636))
+                                        .rounded_sm()
+                                        .text_xs()
+                                        .font_weight(FontWeight::BOLD)
+                                        .text_color(rgb(0xffffff))
+                                        .child("● LIVE")
+                                )
+                            })
+                    )
+                    .child(
+                        div()
+                            .px_3()
+                            .py_1()
+                            .bg(rgb(0x2a2a2a))
+                            .border_1()
+                            .border_color(rgb(0x404040))
+                            .rounded_md()
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x3a3a3a)))
+                            .on_mouse_down(
+                                gpui::MouseButton::Left,
+                                cx.listener(move |this, _event: &gpui::MouseDownEvent, _window, cx| {
+                                    let Some(chart) = this.chart_mut(id) else {
+                                        return;
+                                    };
+                                    let max_offset =
+                                        chart.bars.len().saturating_sub(chart.bars_per_screen) as f32;
+                                    if chart.chart_scroll_offset < max_offset {
+                                        chart.chart_scroll_offset =
+                                            (chart.chart_scroll_offset + 50.0).min(max_offset);
+                                        cx.notify();
+                                    }
+                                }),
+                            )
+                            .child("Next 50 →"),
+                    )
+                    .child(
+                        div()
+                            .px_3()
+                            .py_1()
+                            .bg(rgb(0x1f6feb))
+                            .border_1()
+                            .border_color(rgb(0x404040))
+                            .rounded_md()
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x2a7ffc)))
+                            .on_mouse_down(
+                                gpui::MouseButton::Left,
+                                cx.listener(move |this, _event: &gpui::MouseDownEvent, _window, cx| {
+                                    let Some(chart) = this.chart_mut(id) else {
+                                        return;
+                                    };
+                                    // Show most recent bars
+                                    chart.chart_scroll_offset =
+                                        chart.bars.len().saturating_sub(chart.bars_per_screen) as f32;
+                                    cx.notify();
+                                }),
+                            )
+                            .child("Show Latest →→"),
+                    ),
+            )
+            .when(id == chart::ChartId::Main, |this| {
+                this.child(self.render_indicator_bar(cx))
+            })
+            .child(self.render_donchian_control(id, cx))
+            .child(
+                // Price statistics
+                div()
+                    .flex()
+                    .gap_6()
+                    .text_sm()
+                    .text_color(rgb(0xcccccc))
+                    .child(div().child(format!("High: {}", numbers.price(max_price))))
+                    .child(div().child(format!("Low: {}", numbers.price(min_price))))
+                    .child(div().child(format!("Range: {}", numbers.price(price_range))))
+                    .child(div().child(format!("Bars: {}", chart.bars.len())))
+                    .when_some(chart.bars.last(), |this, last_bar| {
+                        let is_bullish = last_bar.close >= last_bar.open;
+                        let color = rgb(style.candle_color(is_bullish));
+                        this.child(
+                            div()
+                                .text_color(color)
+                                .child(format!("Last Close: {}", numbers.price(last_bar.close))),
+                        )
+                    })
+                    .when_some(session, |this, session| {
+                        let change = chart.bars.last().and_then(|bar| session.change(bar.close));
+                        let color = change.map_or(rgb(0x8b949e), |(change, _)| pnl_color(change));
+                        this.child(div().child(format!("Open: {}", numbers.price(session.open))))
+                            .child(
+                                div()
+                                    .text_color(color)
+                                    .child(match change {
+                                        Some((_, percent)) => format!("Day: {:+.2}%", percent),
+                                        None => "Day: --".to_string(),
+                                    }),
+                            )
+                            .child(div().child(format!(
+                                "Day Range: {} – {}",
+                                numbers.price(session.low),
+                                numbers.price(session.high)
+                            )))
+                    }),
+            )
+    }
+}
+
+impl Render for TradingTerminal {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let timeframe_display = match self.chart.timeframe.as_str() {
+            "1Min" => "1 Minute",
+            "5Min" => "5 Minutes",
+            "15Min" => "15 Minutes",
+            "1Hour" => "1 Hour",
+            "1Day" => "Daily",
+            "1Week" => "Weekly",
+            "1Month" => "Monthly",
+            _ => &self.chart.timeframe,
+        };
+
+        div()
+            .relative()
+            .grid()
+            .grid_cols(8)
+            .on_action(cx.listener(|this, _: &keymap::RefreshData, _, cx| {
+                this.fetch_bars(cx);
+                this.refresh_footer_tab(cx);
+            }))
+            .on_action(cx.listener(|this, _: &keymap::SubmitOrder, _, cx| {
+                this.submit_order(cx);
+            }))
+            .on_action(cx.listener(|this, _: &keymap::NextTab, _, cx| {
+                let tab = this.account.active_footer_tab.cycled(true);
+                this.select_footer_tab(tab, cx);
+            }))
+            .on_action(cx.listener(|this, _: &keymap::PreviousTab, _, cx| {
+                let tab = this.account.active_footer_tab.cycled(false);
+                this.select_footer_tab(tab, cx);
+            }))
+            .on_action(cx.listener(|this, _: &keymap::Flatten, _, cx| {
+                this.flatten_positions(cx);
+            }))
+            .when(!self.text_input_focused() && !self.setup.open, |this| {
+                this.key_context(keymap::TRADING_CONTEXT)
+            })
+            .on_action(cx.listener(|this, _: &keymap::HotkeyBuy, _, cx| {
+                this.hotkey_order(hotkeys::HotkeySide::Buy, cx);
+            }))
+            .on_action(cx.listener(|this, _: &keymap::HotkeySell, _, cx| {
+                this.hotkey_order(hotkeys::HotkeySide::Sell, cx);
+            }))
+            .on_action(cx.listener(|this, _: &keymap::FocusSymbol, window, cx| {
+                this.set_input_focus(Some(focus::InputField::Symbol));
+                window.focus(&this.focus_handle);
+                cx.notify();
+            }))
+            .grid_rows(1)
+            .bg(rgb(0x0d1117))
+            // Window frame in the environment's color, so live trading is never mistaken for paper
+            .border_2()
+            .border_color(environment_color(self.profiles.environment()))
+            .size_full()
+            .min_w(px(640.0))
+            .gap_4()
+            .child(
+                // Main content area (left column) - flex layout for header/chart/footer.
+                // Takes the full width when the sidebar is a drawer.
+                div()
+                    .col_span(if self.layout.has_drawer() { 8 } else { 7 })
+                    .flex()
+                    .flex_col()
+                    .when(self.layout.is_narrow(), |this| this.p_4().gap_4())
+                    .when(!self.layout.is_narrow(), |this| this.p_8().gap_6())
+                    .track_focus(&self.focus_handle)
+                    .on_key_down(cx.listener(|this, event: &gpui::KeyDownEvent, _, cx| {
+                        this.handle_key_down(event, cx);
+                    }))
+                    .child(
+                        // Header (stacked on narrow windows)
+                        div()
+                            .flex()
+                            .flex_shrink_0()
+                            .when(self.layout.is_narrow(), |this| {
+                                this.flex_col().items_start().gap_4()
+                            })
+                            .when(!self.layout.is_narrow(), |this| this.items_center())
+                            .justify_between()
+                            .on_mouse_move(cx.listener(|this, _event, _window, cx| {
+                                // Hide crosshair when mouse is over header
+                                this.chart.show_crosshair = false;
+                                cx.notify();
+                            }))
+                            .child(
+                                // Controls: Symbol input and Timeframe selector
+                                div()
+                                    .flex()
+                                    .flex_wrap()
+                                    .gap_4()
+                                    .items_end()
+                                    .child(
+                                        // Symbol input
+                                        div()
+                                            .flex()
+                                            .flex_col()
+                                            .gap_2()
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .font_weight(FontWeight::SEMIBOLD)
+                                                    .text_color(rgb(0xffffff))
+                                                    .child("Symbol:"),
+                                            )
+                                            .child(
+                                                div()
+                                                    .flex()
+                                                    .gap_2()
+                                                    .child(
+                                                        div()
+                                                            .id("symbol-input")
+                                                            .px_4()
+                                                            .py_2()
+                                                            .bg(if self.focus.is_focused(focus::InputField::Symbol) {
+                                                                rgb(0x1f2937)
+                                                            } else {
+                                                                rgb(0x161b22)
+                                                            })
+                                                            .border_1()
+                                                            .border_color(if self.focus.is_focused(focus::InputField::Symbol) {
+                                                                rgb(0x1f6feb)
+                                                            } else {
+                                                                rgb(0x30363d)
+                                                            })
+                                                            .rounded_lg()
+                                                            .text_color(rgb(0xffffff))
+                                                            .min_w(px(120.0))
+                                                            .cursor_text()
+                                                            .child(self.chart.symbol_input.render(
+                                                                self.focus.is_focused(focus::InputField::Symbol),
+                                                                "Enter symbol...",
+                                                            ))
+                                                            .on_click(cx.listener(
+                                                                |this, _, _window, cx| {
+                                                                    this.set_input_focus(Some(focus::InputField::Symbol));
+                                                                    _window
+                                                                        .focus(&this.focus_handle);
+                                                                    cx.notify();
+                                                                },
+                                                            )),
+                                                    )
+                                                    .child(
+                                                        div()
+                                                            .id("update-symbol-button")
+                                                            .px_4()
+                                                            .py_2()
+                                                            .bg(rgb(0x1f6feb))
+                                                            .rounded_lg()
+                                                            .text_color(rgb(0xffffff))
+                                                            .font_weight(FontWeight::SEMIBOLD)
+                                                            .cursor_pointer()
+                                                            .hover(|style| style.bg(rgb(0x388bfd)))
+                                                            .child("Update")
+                                                            .on_click(cx.listener(
+                                                                |this, _, _, cx| {
+                                                                    this.submit_symbol(cx);
+                                                                },
+                                                            )),
+                                                    )
+                                                    .child(self.render_recent_symbols(cx)),
+                                            ),
+                                    )
+                                    .child(
+                                        // Timeframe selector
+                                        div()
+                                            .flex()
+                                            .flex_col()
+                                            .gap_2()
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .font_weight(FontWeight::SEMIBOLD)
+                                                    .text_color(rgb(0xffffff))
+                                                    .child("Timeframe:"),
+                                            )
+                                            .child(
+                                                div()
+                                                    .flex()
+                                                    .gap_2()
+                                                    .child(
+                                                        self.render_timeframe_button(
+                                                            "1Min", "1m", cx,
+                                                        ),
+                                                    )
+                                                    .child(
+                                                        self.render_timeframe_button(
+                                                            "5Min", "5m", cx,
+                                                        ),
+                                                    )
+                                                    .child(self.render_timeframe_button(
+                                                        "15Min", "15m", cx,
+                                                    ))
+                                                    .child(
+                                                        self.render_timeframe_button(
+                                                            "1Hour", "1h", cx,
+                                                        ),
+                                                    )
+                                                    .child(
+                                                        self.render_timeframe_button(
+                                                            "1Day", "1D", cx,
+                                                        ),
+                                                    )
+                                                    .child(
+                                                        self.render_timeframe_button(
+                                                            "1Week", "1W", cx,
+                                                        ),
+                                                    )
+                                                    .child(self.render_timeframe_button(
+                                                        "1Month", "1M", cx,
+                                                    )),
+                                            ),
+                                    )
+                                    .child(
+                                        // Bar limit input
+                                        div()
+                                            .flex()
+                                            .flex_col()
+                                            .gap_2()
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .font_weight(FontWeight::SEMIBOLD)
+                                                    .text_color(rgb(0xffffff))
+                                                    .child("Bars:"),
+                                            )
+                                            .child(
+                                                div()
+                                                    .id("bar-limit-input")
+                                                    .px_4()
+                                                    .py_2()
+                                                    .bg(if self.focus.is_focused(focus::InputField::BarLimit) {
+                                                        rgb(0x1f2937)
+                                                    } else {
+                                                        rgb(0x161b22)
+                                                    })
+                                                    .border_1()
+                                                    .border_color(if self.focus.is_focused(focus::InputField::BarLimit) {
+                                                        rgb(0x1f6feb)
+                                                    } else {
+                                                        rgb(0x30363d)
+                                                    })
+                                                    .rounded_lg()
+                                                    .text_color(rgb(0xffffff))
+                                                    .min_w(px(80.0))
+                                                    .cursor_text()
+                                                    .child(self.chart.bar_limit.render(
+                                                        self.focus.is_focused(focus::InputField::BarLimit),
+                                                        "100",
+                                                    ))
+                                                    .on_click(cx.listener(
+                                                        |this, _, _window, cx| {
+                                                            this.set_input_focus(Some(focus::InputField::BarLimit));
+                                                            _window.focus(&this.focus_handle);
+                                                            cx.notify();
+                                                        },
+                                                    )),
+                                            ),
+                                    ),
+                            )
+                            .child(
+                                // Title section
+                                div()
+                                    .flex()
+                                    .flex_col()
+                                    .gap_1()
+                                    .child(
+                                        div()
+                                            .text_2xl()
+                                            .font_weight(FontWeight::BOLD)
+                                            .text_color(rgb(0xffffff))
+                                            .child(format!("{} Stock Chart", self.chart.symbol)),
+                                    )
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .items_center()
+                                            .gap_3()
+                                            .child(div().text_sm().text_color(rgb(0x808080)).child(
+                                                format!(
+                                                    "{} candlestick chart powered by Alpaca Markets",
+                                                    timeframe_display
+                                                ),
+                                            ))
+                                            .child(self.render_depth_toggle(cx)),
+                                    ),
+                            )
+                            .child(
+                                // Status and controls section (a wrapping row on narrow windows)
+                                div()
+                                    .flex()
+                                    .when(self.layout.is_narrow(), |this| this.flex_wrap())
+                                    .when(!self.layout.is_narrow(), |this| this.flex_col())
+                                    .gap_3()
+                                    .when(self.layout.has_drawer(), |this| {
+                                        this.child(self.render_sidebar_toggle(cx))
+                                    })
+                                    .child(
+                                        // Market open/closed with countdown (click for calendar)
+                                        self.render_market_clock(cx),
+                                    )
+                                    .when(self.calendar_open, |this| {
+                                        this.child(self.render_calendar())
+                                    })
+                                    .child(
+                                        // Active account profile (click to switch)
+                                        self.render_profile_button(cx),
+                                    )
+                                    .when(self.profile_menu_open, |this| {
+                                        this.child(self.render_profile_menu(cx))
+                                    })
+                                    .child(
+                                        // Named layouts (click to switch or save)
+                                        self.render_workspace_button(cx),
+                                    )
+                                    .when(self.workspaces.open, |this| {
+                                        this.child(self.render_workspace_menu(cx))
+                                    })
+                                    .child(
+                                        // Notification center toggle with unread count
+                                        self.render_notification_button(cx),
+                                    )
+                                    .when(self.notifications.open, |this| {
+                                        this.child(self.render_notification_center(cx))
+                                    })
+                                    .child(
+                                        // Charted symbol's last price and change
+                                        self.render_symbol_quote(),
+                                    )
+                                    .child(
+                                        // 52-week range, average volume and today's session
+                                        self.render_key_stats(),
+                                    )
+                                    .child(
+                                        // Today's P&L
+                                        self.render_daily_pnl(),
+                                    )
+                                    .child(
+                                        // WebSocket Status Indicator (click for stream health)
+                                        div()
+                                            .id("stream-status-indicator")
+                                            .cursor_pointer()
+                                            .on_click(cx.listener(|this, _, _, cx| {
+                                                this.stream_health.open = !this.stream_health.open;
+                                                cx.notify();
+                                            }))
+                                            .flex()
+                                            .items_center()
+                                            .gap_2()
+                                            .px_4()
+                                            .py_3()
+                                            .rounded_lg()
+                                            .bg(if self.stream_connected {
+                                                rgb(0x238636)
+                                            } else {
+                                                rgb(0x6e7681)
+                                            })
+                                            .child(
+                                                div().text_sm().text_color(rgb(0xffffff)).child(
+                                                    if self.stream_connected {
+                                                        "🟢 Live Updates"
+                                                    } else {
+                                                        "⭕ Disconnected"
+                                                    },
+                                                ),
+                                            ),
+                                    )
+                                    .child(
+                                        // Market Data WebSocket Status Indicator
+                                        div()
+                                            .id("market-data-status-indicator")
+                                            .cursor_pointer()
+                                            .on_click(cx.listener(|this, _, _, cx| {
+                                                this.stream_health.open = !this.stream_health.open;
+                                                cx.notify();
+                                            }))
+                                            .flex()
+                                            .items_center()
+                                            .gap_2()
+                                            .px_4()
+                                            .py_3()
+                                            .rounded_lg()
+                                            .bg(if self.chart.market_data_connected {
+                                                rgb(0x1f6feb)
+                                            } else {
+                                                rgb(0x6e7681)
+                                            })
+                                            .child(
+                                                div()
+                                                    .flex()
+                                                    .flex_col()
+                                                    .gap_1()
+                                                    .child(
+                                                        div().text_sm().font_weight(FontWeight::SEMIBOLD).text_color(rgb(0xffffff)).child(
+                                                            if self.stream_health.market_data.is_stale() {
+                                                                "📊 Market Data (no data)"
+                                                            } else if self.chart.market_data_connected {
+                                                                "📊 Market Data Stream"
+                                                            } else {
+                                                                "📊 No Market Data"
+                                                            }
+                                                        )
+                                                    )
+                                                    .when(self.chart.market_data_connected && self.chart.last_bar_symbol.is_some(), |this| {
+                                                        this.child(
+                                                            div()
+                                                                .flex()
+                                                                .flex_col()
+                                                                .gap_1()
+                                                                .text_xs()
+                                                                .text_color(rgb(0xcccccc))
+                                                                .child(
+                                                                    div().child(format!(
+                                                                        "Symbol: {} | Time: {}",
+                                                                        self.chart.last_bar_symbol.as_ref().unwrap(),
+                                                                        self.chart.last_bar_time.as_ref().map(|t| {
+                                                                            if t.len() >= 19 {
+                                                                                &t[11..19] // HH:MM:SS
+                                                                            } else {
+                                                                                t.as_str()
+                                                                            }
+                                                                        }).unwrap_or("--:--:--")
+                                                                    ))
+                                                                )
+                                                                .child(
+                                                                    div().child(format!(
+                                                                        "O: {} | H: {} | L: {} | C: {}",
+                                                                        self.chart.last_bar_open.as_ref().unwrap_or(&"--".to_string()),
+                                                                        self.chart.last_bar_high.as_ref().unwrap_or(&"--".to_string()),
+                                                                        self.chart.last_bar_low.as_ref().unwrap_or(&"--".to_string()),
+                                                                        self.chart.last_bar_close.as_ref().unwrap_or(&"--".to_string()),
+                                                                    ))
+                                                                )
+                                                                .child(
+                                                                    div().child(format!(
+                                                                        "Volume: {}",
+                                                                        self.chart.last_bar_volume
+                                                                            .as_ref()
+                                                                            .map(|v| self.config.numbers.format.volume_text(v))
+                                                                            .unwrap_or("--".to_string()),
+                                                                    ))
+                                                                )
+                                                        )
+                                                    }),
+                                            ),
+                                    )
+                                    .when(self.stream_health.open, |this| {
+                                        this.child(self.render_stream_health(cx))
+                                    }),
+                            )
+                            .child(
+                                // Refresh button
+                                div()
+                                    .id("refresh-button")
+                                    .px_6()
+                                    .py_3()
+                                    .bg(rgb(0x238636))
+                                    .rounded_lg()
+                                    .text_color(rgb(0xffffff))
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(0x2ea043)))
+                                    .child(match self.chart.retry {
+                                        Some((attempt, attempts)) => {
+                                            format!("⟳ Retrying {}/{}...", attempt, attempts)
+                                        }
+                                        None if self.chart.loading => "⟳ Loading...".to_string(),
+                                        None => "↻ Refresh Data".to_string(),
+                                    })
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.fetch_bars(cx);
+                                    })),
+                            ),
+                    )
+                    .child(
+                        // Favorite symbols (quick-switch chips)
+                        self.render_favorites_bar(cx),
+                    )
+                    .child(
+                        // Spacer div between header and chart to catch mouse events in the gap
+                        div().h(px(24.0)).w_full().on_mouse_move(cx.listener(
+                            |this, _event, _window, cx| {
+                                this.chart.show_crosshair = false;
+                                cx.notify();
+                            },
+                        )),
+                    )
+                    .child(
+                        // Chart area wrapper with side padding to catch mouse events
+                        div()
+                            .flex_1()
+                            .flex()
+                            .flex_row()
+                            .min_h(px(400.0))
+                            .child(
+                                // Left padding area to catch mouse events
+                                div().w(px(32.0)).h_full().on_mouse_move(cx.listener(
+                                    |this, _event, _window, cx| {
+                                        this.chart.show_crosshair = false;
+                                        cx.notify();
+                                    },
+                                )),
+                            )
+                            .child(
+                                // Actual chart
+                                div()
+                                    .flex_1()
+                                    .grid()
+                                    .items_center()
+                                    .justify_center()
+                                    .child(self.render_candlesticks(chart::ChartId::Main, cx)),
+                            )
+                            .when(self.settings.depth_open, |this| {
+                                this.child(self.render_depth_panel(cx))
+                            })
+                            .child(
+                                // Right padding area to catch mouse events
+                                div().w(px(32.0)).h_full().on_mouse_move(cx.listener(
+                                    |this, _event, _window, cx| {
+                                        this.chart.show_crosshair = false;
+                                        cx.notify();
+                                    },
+                                )),
+                            ),
+                    )
+                    .child(
+                        // Spacer div between chart and footer to catch mouse events in the gap
+                        div().h(px(24.0)).w_full().on_mouse_move(cx.listener(
+                            |this, _event, _window, cx| {
+                                this.chart.show_crosshair = false;
+                                cx.notify();
+                            },
+                        )),
+                    )
+                    .child(
+                        // Tabbed Footer
+                        div()
+                            .flex_shrink_0()
+                            .grid()
+                            .grid_cols(1)
+                            .gap_3()
+                            .p_4()
+                            .bg(rgb(0x161b22))
+                            .rounded_lg()
+                            .border_1()
+                            .border_color(rgb(0x30363d))
+                            .on_mouse_move(cx.listener(|this, _event, _window, cx| {
+                                // Hide crosshair when mouse is over footer
+                                this.chart.show_crosshair = false;
+                                cx.notify();
+                            }))
+                            .child(
+                                // Tab buttons and refresh button
+                                div()
+                                    .flex()
+                                    .items_center()
+                                    .justify_between()
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .flex_wrap()
+                                            .gap_2()
+                                            .child(self.render_footer_tab_button(
+                                                "tab-account",
+                                                "Account Information",
+                                                account::FooterTab::Account,
+                                                cx,
+                                            ))
+                                            .child(self.render_footer_tab_button(
+                                                "tab-positions",
+                                                "Active Positions",
+                                                account::FooterTab::Positions,
+                                                cx,
+                                            ))
+                                            .child(self.render_footer_tab_button(
+                                                "tab-orders",
+                                                "Active Orders",
+                                                account::FooterTab::Orders,
+                                                cx,
+                                            ))
+                                            .child(self.render_footer_tab_button(
+                                                "tab-history",
+                                                "Order History",
+                                                account::FooterTab::History,
+                                                cx,
+                                            ))
+                                            .child(self.render_footer_tab_button(
+                                                "tab-activities",
+                                                "Activities",
+                                                account::FooterTab::Activities,
+                                                cx,
+                                            ))
+                                            .child(self.render_footer_tab_button(
+                                                "tab-tax-lots",
+                                                "Tax Lots",
+                                                account::FooterTab::TaxLots,
+                                                cx,
+                                            ))
+                                            .child(self.render_footer_tab_button(
+                                                "tab-performance",
+                                                "Performance",
+                                                account::FooterTab::Performance,
+                                                cx,
+                                            ))
+                                            .child(self.render_footer_tab_button(
+                                                "tab-backtest",
+                                                "Backtest",
+                                                account::FooterTab::Backtest,
+                                                cx,
+                                            ))
+                                            .child(self.render_footer_tab_button(
+                                                "tab-simulator",
+                                                "Simulator",
+                                                account::FooterTab::Simulator,
+                                                cx,
+                                            ))
+                                            .child(self.render_footer_tab_button(
+                                                "tab-news",
+                                                "News",
+                                                account::FooterTab::News,
+                                                cx,
+                                            ))
+                                            .child(self.render_footer_tab_button(
+                                                "tab-heatmap",
+                                                "Heatmap",
+                                                account::FooterTab::Heatmap,
+                                                cx,
+                                            ))
+                                            .child(self.render_footer_tab_button(
+                                                "tab-movers",
+                                                "Movers",
+                                                account::FooterTab::Movers,
+                                                cx,
+                                            ))
+                                            .child(self.render_footer_tab_button(
+                                                "tab-analytics",
+                                                "Analytics",
+                                                account::FooterTab::Analytics,
+                                                cx,
+                                            ))
+                                            .child(self.render_footer_tab_button(
+                                                "tab-logs",
+                                                "Logs",
+                                                account::FooterTab::Logs,
+                                                cx,
+                                            )),
+                                    )
+                                    .child(
+                                        div()
+                                            .id("refresh-footer-button")
+                                            .px_3()
+                                            .py_1()
+                                            .bg(rgb(0x238636))
+                                            .rounded_md()
+                                            .text_xs()
+                                            .text_color(rgb(0xffffff))
+                                            .font_weight(FontWeight::SEMIBOLD)
+                                            .cursor_pointer()
+                                            .hover(|style| style.bg(rgb(0x2ea043)))
+                                            .child(
+                                                if (self.account.active_footer_tab == account::FooterTab::Account
+                                                    && self.account.account_loading)
+                                                    || (self.account.active_footer_tab
+                                                        == account::FooterTab::Positions
+                                                        && self.account.positions_loading)
+                                                    || (self.account.active_footer_tab == account::FooterTab::Orders
+                                                        && self.account.orders_loading)
+                                                    || (self.account.active_footer_tab == account::FooterTab::History
+                                                        && self.account.order_history_loading)
+                                                    || (matches!(
+                                                        self.account.active_footer_tab,
+                                                        account::FooterTab::Activities
+                                                            | account::FooterTab::TaxLots
+                                                            | account::FooterTab::Performance
+                                                    ) && self.account.activities_loading)
+                                                    || (self.account.active_footer_tab == account::FooterTab::Performance
+                                                        && self.performance.benchmark_loading)
+                                                    || (self.account.active_footer_tab == account::FooterTab::Backtest
+                                                        && self.backtest.running)
+                                                    || (self.account.active_footer_tab == account::FooterTab::News
+                                                        && self.news.loading)
+                                                    || (self.account.active_footer_tab == account::FooterTab::Heatmap
+                                                        && self.heatmap.loading)
+                                                    || (self.account.active_footer_tab == account::FooterTab::Movers
+                                                        && self.screener.loading)
+                                                    || (self.account.active_footer_tab == account::FooterTab::Analytics
+                                                        && self.analytics.loading)
+                                                {
+                                                    match &self.account.retrying {
+                                                        Some((tab, attempt, attempts))
+                                                            if *tab
+                                                                == self.account.active_footer_tab =>
+                                                        {
+                                                            format!(
+                                                                "⟳ Retrying {}/{}...",
+                                                                attempt, attempts
+                                                            )
+                                                        }
+                                                        _ => "⟳ Loading...".to_string(),
+                                                    }
+                                                } else {
+                                                    "↻ Refresh".to_string()
+                                                },
+                                            )
+                                            .on_click(cx.listener(|this, _, _, cx| {
+                                                this.refresh_footer_tab(cx);
+                                            })),
+                                    ),
+                            )
+                            .when(self.account.active_footer_tab == account::FooterTab::Account, |div| {
+                                div.child(self.render_account_tab(cx))
+                            })
+                            .when(self.account.active_footer_tab == account::FooterTab::Positions, |div| {
+                                div.child(self.render_positions_tab(cx))
+                            })
+                            .when(self.account.active_footer_tab == account::FooterTab::Orders, |div| {
+                                div.child(self.render_orders_tab(cx))
+                            })
+                            .when(self.account.active_footer_tab == account::FooterTab::History, |div| {
+                                div.child(self.render_history_tab(cx))
+                            })
+                            .when(self.account.active_footer_tab == account::FooterTab::Activities, |div| {
+                                div.child(self.render_activities_tab(cx))
+                            })
+                            .when(self.account.active_footer_tab == account::FooterTab::TaxLots, |div| {
+                                div.child(self.render_tax_lots_tab(cx))
+                            })
+                            .when(self.account.active_footer_tab == account::FooterTab::Performance, |div| {
+                                div.child(self.render_performance_tab(cx))
+                            })
+                            .when(self.account.active_footer_tab == account::FooterTab::Backtest, |div| {
+                                div.child(self.render_backtest_tab(cx))
+                            })
+                            .when(self.account.active_footer_tab == account::FooterTab::Simulator, |div| {
+                                div.child(self.render_simulator_tab(cx))
+                            })
+                            .when(self.account.active_footer_tab == account::FooterTab::News, |div| {
+                                div.child(self.render_news_tab(cx))
+                            })
+                            .when(self.account.active_footer_tab == account::FooterTab::Heatmap, |div| {
+                                div.child(self.render_heatmap_tab(cx))
+                            })
+                            .when(self.account.active_footer_tab == account::FooterTab::Movers, |div| {
+                                div.child(self.render_movers_tab(cx))
+                            })
+                            .when(self.account.active_footer_tab == account::FooterTab::Analytics, |div| {
+                                div.child(self.render_analytics_tab(cx))
+                            })
+                            .when(self.account.active_footer_tab == account::FooterTab::Logs, |div| {
+                                div.child(self.render_log_tab(cx))
+                            }),
+                    ),
+            ) // Close main content .child()
+            .when(!self.layout.has_drawer(), |this| {
+                // Right sidebar - Order form
+                this.child(self.render_sidebar(cx).col_span(1).border_l_1())
+            })
+            .when(self.layout.has_drawer() && self.sidebar_open, |this| {
+                // Same sidebar as a drawer over the right edge of the chart
+                this.child(
+                    self.render_sidebar(cx)
+                        .id("sidebar-drawer")
+                        .absolute()
+                        .top_0()
+                        .right_0()
+                        .h_full()
+                        .w(px(layout::DRAWER_WIDTH))
+                        .overflow_y_scroll()
+                        .border_l_2()
+                        .shadow_lg(),
+                )
+            })
+            // Toasts float above the layout in the bottom-right corner
+            .child(self.render_toasts(cx))
+            .when_some(self.hotkeys.pending, |this, side| {
+                this.child(self.render_hotkey_confirmation(side, cx))
+            })
+            .when(self.loss_limit.confirming, |this| {
+                this.child(self.render_loss_limit_confirmation(cx))
+            })
+            .when(self.setup.open, |this| {
+                this.child(self.render_setup_dialog(cx))
+            })
+            .when_some(self.order_guard.pending.as_ref(), |this, pending| {
+                this.child(self.render_live_order_confirmation(pending, cx))
+            })
+    }
+}
+
+impl TradingTerminal {
+    /// Order form, hotkeys, alerts and strategies. A grid column on wide windows, a
+    /// drawer toggled from the header otherwise.
+    fn render_sidebar(&self, cx: &mut Context<Self>) -> Div {
+        let sidebar = div()
+            .bg(rgb(0x161b22))
+            .border_color(rgb(0x30363d))
+            .p_6()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .on_mouse_move(cx.listener(|this, _event, _window, cx| {
+                // Hide crosshair when mouse is over sidebar
+                this.chart.show_crosshair = false;
+                cx.notify();
+            }))
+            .child(self.render_environment_banner());
+
+        // Scalp mode keeps the sidebar to the pad alone
+        if self.settings.scalp.enabled {
+            return sidebar.child(self.render_scalp_pad(cx));
+        }
+
+        sidebar
+            .when(self.order_window.is_some(), |this| {
+                this.child(self.render_docked_placeholder(cx))
+            })
+            .when(self.order_window.is_none(), |this| {
+                this.child(self.render_order_form(cx))
+            })
+            .child(
+                // One-key buy/sell hotkeys (collapsible)
+                self.render_hotkeys_panel(cx),
+            )
+            .child(
+                // Take-profit/stop-loss exits for manual entries (collapsible)
+                self.render_auto_exit_panel(cx),
+            )
+            .child(
+                // Price alerts (collapsible)
+                self.render_alerts_panel(cx),
+            )
+            .child(
+                // Recurring notional buys (collapsible)
+                self.render_recurring_panel(cx),
+            )
+            .child(
+                // Price-cross order rules (collapsible)
+                self.render_automation_panel(cx),
+            )
+            .child(
+                // Rhai strategy scripts (collapsible)
+                self.render_scripts_panel(cx),
+            )
+            .child(
+                // Multi-leg option strategies (collapsible)
+                self.render_strategy_builder(cx),
+            )
+    }
+
+    /// Header button that opens or closes the sidebar drawer
+    fn render_sidebar_toggle(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .id("sidebar-toggle")
+            .px_4()
+            .py_2()
+            .rounded_lg()
+            .bg(if self.sidebar_open {
+                rgb(0x1f6feb)
+            } else {
+                rgb(0x21262d)
+            })
+            .border_1()
+            .border_color(rgb(0x30363d))
+            .text_sm()
+            .text_color(rgb(0xffffff))
+            .cursor_pointer()
+            .hover(|style| style.bg(rgb(0x30363d)))
+            .child(if self.sidebar_open {
+                "✕ Close Order Panel"
+            } else {
+                "☰ Order Panel"
+            })
+            .on_click(cx.listener(|this, _, _, cx| {
+                this.sidebar_open = !this.sidebar_open;
+                cx.notify();
+            }))
+    }
+
+    /// Order ticket: symbol, side, type, quantity, prices and the submit button. Rendered
+    /// in the sidebar, or in its own window when popped out.
+    fn render_order_form(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let halted = self.trading_halted();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .when(halted, |this| this.opacity(0.6))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .text_lg()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(rgb(0xffffff))
+                            .child("Place Order"),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_1()
+                            .child(self.render_scalp_toggle(cx))
+                            .child(self.render_order_window_toggle(cx)),
+                    ),
+            )
+            .child(
+                // Current symbol display
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(rgb(0xffffff))
+                            .child("Trading Symbol"),
+                    )
+                    .child(
+                        div()
+                            .px_3()
+                            .py_2()
+                            .bg(rgb(0x0d1117))
+                            .border_1()
+                            .border_color(rgb(0x1f6feb))
+                            .rounded_md()
+                            .text_color(rgb(0x58a6ff))
+                            .font_weight(FontWeight::BOLD)
+                            .child(self.chart.symbol.clone()),
+                    ),
+            )
+            .child(
+                // Exchange, class and tradability / borrow flags for the traded symbol
+                self.render_asset_details(),
+            )
+            .child(
+                // Outside regular hours: orders queue for the next open
+                self.render_session_hint(),
+            )
+            .child(
+                // Order side (Buy/Sell)
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(rgb(0xffffff))
+                            .child("Side"),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .id("order-side-buy-btn")
+                                    .flex_1()
+                                    .px_3()
+                                    .py_2()
+                                    .rounded_md()
+                                    .text_center()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .cursor_pointer()
+                                    .bg(if matches!(self.account.order_side, OrderSide::Buy) {
+                                        rgb(0x3fb950)
+                                    } else {
+                                        rgb(0x21262d)
+                                    })
+                                    .text_color(rgb(0xffffff))
+                                    .hover(|style| {
+                                        if matches!(self.account.order_side, OrderSide::Buy) {
+                                            style.bg(rgb(0x4fc55d))
+                                        } else {
+                                            style.bg(rgb(0x30363d))
+                                        }
+                                    })
+                                    .child("Buy")
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.account.order_side = OrderSide::Buy;
+                                        cx.notify();
+                                    })),
+                            )
+                            .child(
+                                div()
+                                    .id("order-side-sell-btn")
+                                    .flex_1()
+                                    .px_3()
+                                    .py_2()
+                                    .rounded_md()
+                                    .text_center()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .cursor_pointer()
+                                    .bg(if matches!(self.account.order_side, OrderSide::Sell) {
+                                        rgb(0xff4444)
+                                    } else {
+                                        rgb(0x21262d)
+                                    })
+                                    .text_color(rgb(0xffffff))
+                                    .hover(|style| {
+                                        if matches!(self.account.order_side, OrderSide::Sell) {
+                                            style.bg(rgb(0xff5555))
+                                        } else {
+                                            style.bg(rgb(0x30363d))
+                                        }
+                                    })
+                                    .child("Sell")
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.account.order_side = OrderSide::Sell;
+                                        cx.notify();
+                                    })),
+                            ),
+                    ),
+            )
+            .child(
+                // Order type (Market/Limit)
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(rgb(0xffffff))
+                            .child("Order Type"),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .id("order-type-market-btn")
+                                    .flex_1()
+                                    .px_3()
+                                    .py_2()
+                                    .rounded_md()
+                                    .text_center()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .cursor_pointer()
+                                    .bg(if matches!(self.account.order_type, OrderType::Market) {
+                                        rgb(0x1f6feb)
+                                    } else {
+                                        rgb(0x21262d)
+                                    })
+                                    .text_color(rgb(0xffffff))
+                                    .hover(|style| {
+                                        if matches!(self.account.order_type, OrderType::Market) {
+                                            style.bg(rgb(0x388bfd))
+                                        } else {
+                                            style.bg(rgb(0x30363d))
+                                        }
+                                    })
+                                    .child("Market")
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.account.order_type = OrderType::Market;
+                                        cx.notify();
+                                    })),
+                            )
+                            .child(
+                                div()
+                                    .id("order-type-limit-btn")
+                                    .flex_1()
+                                    .px_3()
+                                    .py_2()
+                                    .rounded_md()
+                                    .text_center()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .cursor_pointer()
+                                    .bg(if matches!(self.account.order_type, OrderType::Limit) {
+                                        rgb(0x1f6feb)
+                                    } else {
+                                        rgb(0x21262d)
+                                    })
+                                    .text_color(rgb(0xffffff))
+                                    .hover(|style| {
+                                        if matches!(self.account.order_type, OrderType::Limit) {
+                                            style.bg(rgb(0x388bfd))
+                                        } else {
+                                            style.bg(rgb(0x30363d))
+                                        }
+                                    })
+                                    .child("Limit")
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.account.order_type = OrderType::Limit;
+                                        cx.notify();
+                                    })),
+                            ),
+                    ),
+            )
+            .child(
+                // Quantity input
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(rgb(0xffffff))
+                            .child("Quantity"),
+                    )
+                    .child(
+                        div()
+                            .id("order-quantity-input")
+                            .px_3()
+                            .py_2()
+                            .bg(if self.focus.is_focused(focus::InputField::OrderQuantity) {
+                                rgb(0x1f2937)
+                            } else {
+                                rgb(0x0d1117)
+                            })
+                            .border_1()
+                            .border_color(
+                                if self.focus.is_focused(focus::InputField::OrderQuantity) {
+                                    rgb(0x1f6feb)
+                                } else {
+                                    rgb(0x30363d)
+                                },
+                            )
+                            .rounded_md()
+                            .text_color(rgb(0xffffff))
+                            .cursor_text()
+                            .child(self.account.order_quantity.render(
+                                self.focus.is_focused(focus::InputField::OrderQuantity),
+                                "Enter quantity...",
+                            ))
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.set_input_focus(Some(focus::InputField::OrderQuantity));
+                                // A popped-out form keeps focus in its own window
+                                if this.order_window.is_none() {
+                                    _window.focus(&this.focus_handle);
+                                }
+                                cx.notify();
+                            })),
+                    ),
+            )
+            .child(
+                // Limit price input (shown only for limit orders)
+                self.render_limit_price_input(cx),
+            )
+            .child(
+                // Time in Force (shown only for limit orders)
+                self.render_time_in_force(cx),
+            )
+            .child(
+                // Submit now, at the next open or at a set time
+                self.render_schedule_selector(cx),
+            )
+            .when(halted, |this| this.child(self.render_loss_limit_notice(cx)))
+            .when(!halted, |this| {
+                this.child(
+                    // Submit button
+                    div()
+                        .id("submit-order-button")
+                        .px_4()
+                        .py_3()
+                        .mt_4()
+                        .bg(if matches!(self.account.order_side, OrderSide::Buy) {
+                            rgb(0x238636)
+                        } else {
+                            rgb(0xda3633)
+                        })
+                        .rounded_md()
+                        .text_center()
+                        .text_color(rgb(0xffffff))
+                        .font_weight(FontWeight::BOLD)
+                        .cursor_pointer()
+                        .hover(|style| {
+                            if matches!(self.account.order_side, OrderSide::Buy) {
+                                style.bg(rgb(0x2ea043))
+                            } else {
+                                style.bg(rgb(0xff4444))
+                            }
+                        })
+                        .child(if self.account.order_submitting {
+                            "Submitting...".to_string()
+                        } else {
+                            format!(
+                                "{}{} {}",
+                                if self.scheduler.mode == schedule::ScheduleMode::Now {
+                                    ""
+                                } else {
+                                    "Schedule "
+                                },
+                                if matches!(self.account.order_side, OrderSide::Buy) {
+                                    "Buy"
+                                } else {
+                                    "Sell"
+                                },
+                                self.chart.symbol
+                            )
+                        })
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            if !this.account.order_submitting {
+                                this.submit_order(cx);
+                            }
+                        })),
+                )
+            })
+            .when(!self.scheduler.orders.is_empty(), |this| {
+                this.child(self.render_scheduled_orders(cx))
+            })
+    }
+
+    /// Pop the order form out into its own window, or dock it back into the sidebar
+    fn render_order_window_toggle(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let popped_out = self.order_window.is_some();
+
+        div()
+            .id("order-window-toggle")
+            .px_2()
+            .py_1()
+            .rounded_md()
+            .text_xs()
+            .text_color(rgb(0x58a6ff))
+            .cursor_pointer()
+            .hover(|style| style.bg(rgb(0x21262d)).text_color(rgb(0xffffff)))
+            .child(if popped_out {
+                "⇲ Dock"
+            } else {
+                "⧉ Pop Out"
+            })
+            .on_click(cx.listener(move |this, _, _, cx| {
+                if popped_out {
+                    this.dock_order_form(cx);
+                } else {
+                    this.pop_out_order_form(cx);
+                }
+            }))
+    }
+
+    /// Chips switching the chart overlays
+    fn render_indicator_bar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let overlays = &self.settings.overlays;
+
+        div()
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .justify_center()
+            .gap_1()
+            .child(
+                div()
+                    .mr_1()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child("Volume profile"),
+            )
+            .children(overlays::VolumeProfileMode::ALL.iter().map(|&mode| {
+                self.render_filter_chip(
+                    format!("overlay-volume-profile-{}", mode.label()),
+                    mode.label(),
+                    overlays.volume_profile == mode,
+                    cx,
+                    move |this, _| {
+                        this.settings.overlays.volume_profile = mode;
+                        this.settings.save();
+                    },
+                )
+            }))
+            .child(
+                div()
+                    .ml_3()
+                    .mr_1()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child("Pivots"),
+            )
+            .children(overlays::PivotMode::ALL.iter().map(|&mode| {
+                self.render_filter_chip(
+                    format!("overlay-pivots-{}", mode.label()),
+                    mode.label(),
+                    overlays.pivots == mode,
+                    cx,
+                    move |this, _| {
+                        this.settings.overlays.pivots = mode;
+                        this.settings.save();
+                    },
+                )
+            }))
+            .child(
+                div()
+                    .ml_3()
+                    .mr_1()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child("SuperTrend"),
+            )
+            .child(self.render_filter_chip(
+                "overlay-supertrend".to_string(),
+                if overlays.supertrend.enabled {
+                    "On"
+                } else {
+                    "Off"
+                },
+                overlays.supertrend.enabled,
+                cx,
+                |this, _| {
+                    let supertrend = &mut this.settings.overlays.supertrend;
+                    supertrend.enabled = !supertrend.enabled;
+                    if matches!(
+                        this.focus.focused(),
+                        Some(focus::InputField::SuperTrendPeriod)
+                            | Some(focus::InputField::SuperTrendMultiplier)
+                    ) {
+                        this.set_input_focus(None);
+                    }
+                    this.settings.save();
+                },
+            ))
+            .when(overlays.supertrend.enabled, |bar| {
+                bar.child(self.render_overlay_input(
+                    "overlay-supertrend-period".to_string(),
+                    focus::InputField::SuperTrendPeriod,
+                    &overlays.supertrend.period,
+                    cx,
+                ))
+                .child(div().text_xs().text_color(rgb(0x8b949e)).child("×"))
+                .child(self.render_overlay_input(
+                    "overlay-supertrend-multiplier".to_string(),
+                    focus::InputField::SuperTrendMultiplier,
+                    &overlays.supertrend.multiplier,
+                    cx,
+                ))
+            })
+            .child(
+                div()
+                    .ml_3()
+                    .mr_1()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child("SAR"),
+            )
+            .child(self.render_filter_chip(
+                "overlay-parabolic-sar".to_string(),
+                if overlays.parabolic_sar.enabled {
+                    "On"
+                } else {
+                    "Off"
+                },
+                overlays.parabolic_sar.enabled,
+                cx,
+                |this, _| {
+                    let sar = &mut this.settings.overlays.parabolic_sar;
+                    sar.enabled = !sar.enabled;
+                    if matches!(
+                        this.focus.focused(),
+                        Some(focus::InputField::SarStep) | Some(focus::InputField::SarMax)
+                    ) {
+                        this.set_input_focus(None);
+                    }
+                    this.settings.save();
+                },
+            ))
+            .when(overlays.parabolic_sar.enabled, |bar| {
+                bar.child(self.render_overlay_input(
+                    "overlay-sar-step".to_string(),
+                    focus::InputField::SarStep,
+                    &overlays.parabolic_sar.step,
+                    cx,
+                ))
+                .child(div().text_xs().text_color(rgb(0x8b949e)).child("max"))
+                .child(self.render_overlay_input(
+                    "overlay-sar-max".to_string(),
+                    focus::InputField::SarMax,
+                    &overlays.parabolic_sar.max,
+                    cx,
+                ))
+            })
+            .child(
+                div()
+                    .ml_3()
+                    .mr_1()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child("Keltner"),
+            )
+            .child(self.render_filter_chip(
+                "overlay-keltner".to_string(),
+                if overlays.keltner.enabled {
+                    "On"
+                } else {
+                    "Off"
+                },
+                overlays.keltner.enabled,
+                cx,
+                |this, _| {
+                    let keltner = &mut this.settings.overlays.keltner;
+                    keltner.enabled = !keltner.enabled;
+                    if matches!(
+                        this.focus.focused(),
+                        Some(focus::InputField::KeltnerPeriod)
+                            | Some(focus::InputField::KeltnerMultiplier)
+                    ) {
+                        this.set_input_focus(None);
+                    }
+                    this.settings.save();
+                },
+            ))
+            .when(overlays.keltner.enabled, |bar| {
+                bar.child(self.render_overlay_input(
+                    "overlay-keltner-period".to_string(),
+                    focus::InputField::KeltnerPeriod,
+                    &overlays.keltner.period,
+                    cx,
+                ))
+                .child(div().text_xs().text_color(rgb(0x8b949e)).child("×"))
+                .child(self.render_overlay_input(
+                    "overlay-keltner-multiplier".to_string(),
+                    focus::InputField::KeltnerMultiplier,
+                    &overlays.keltner.multiplier,
+                    cx,
+                ))
+            })
+            .child(
+                div()
+                    .ml_3()
+                    .mr_1()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child("ADX"),
+            )
+            .child(self.render_filter_chip(
+                "overlay-adx".to_string(),
+                if overlays.adx.enabled { "On" } else { "Off" },
+                overlays.adx.enabled,
+                cx,
+                |this, _| {
+                    this.settings.overlays.adx.enabled = !this.settings.overlays.adx.enabled;
+                    if this.focus.is_focused(focus::InputField::AdxPeriod) {
+                        this.set_input_focus(None);
+                    }
+                    this.settings.save();
+                },
+            ))
+            .when(overlays.adx.enabled, |bar| {
+                bar.child(self.render_overlay_input(
+                    "overlay-adx-period".to_string(),
+                    focus::InputField::AdxPeriod,
+                    &overlays.adx.period,
+                    cx,
+                ))
+            })
+            .child(
+                div()
+                    .ml_3()
+                    .mr_1()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child("Anchored VWAP"),
+            )
+            .child(self.render_filter_chip(
+                "overlay-vwap-anchor".to_string(),
+                if self.drawings.placing_vwap {
+                    "Click a bar..."
+                } else {
+                    "⚓ Add"
+                },
+                self.drawings.placing_vwap,
+                cx,
+                |this, _| this.drawings.placing_vwap = !this.drawings.placing_vwap,
+            ))
+            .children(
+                self.drawings
+                    .vwap_anchors_for(&self.chart.symbol)
+                    .map(|anchor| {
+                        let id = anchor.id;
+                        let loaded = chart::anchor_index(&self.chart.bars, anchor.at).is_some();
+                        div()
+                            .id(ElementId::Name(format!("vwap-anchor-{}", id).into()))
+                            .flex()
+                            .items_center()
+                            .gap_1()
+                            .px_2()
+                            .py_1()
+                            .rounded_md()
+                            .bg(rgb(0x21262d))
+                            .text_xs()
+                            .text_color(rgb(anchor.color))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x30363d)))
+                            .child(format!(
+                                "{}{}",
+                                anchor
+                                    .at
+                                    .with_timezone(&chrono::Local)
+                                    .format("%m-%d %H:%M"),
+                                if loaded { "" } else { " (not loaded)" }
+                            ))
+                            .child(div().text_color(rgb(0x8b949e)).child("✕"))
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.drawings.remove_vwap(id);
+                                this.drawings.save();
+                                cx.notify();
+                            }))
+                    })
+                    .collect::<Vec<_>>(),
+            )
+    }
+
+    /// A series as short level segments across each visible bar's slot, labelled at its
+    /// latest visible point. Values outside the price range are left out.
+    fn render_line_overlay(
+        &self,
+        series: &[Option<f64>],
+        plot: &chart::PlotArea,
+        color: u32,
+        label: Option<String>,
+    ) -> Vec<AnyElement> {
+        let points: Vec<(usize, f64)> = (plot.start_index..plot.end_index)
+            .filter_map(|index| {
+                series
+                    .get(index)
+                    .copied()
+                    .flatten()
+                    .map(|value| (index, value))
+            })
+            .filter(|(_, value)| plot.contains(*value))
+            .collect();
+        let mut elements: Vec<AnyElement> = points
+            .iter()
+            .map(|&(index, value)| {
+                div()
+                    .absolute()
+                    .left(gpui::relative(plot.x(index) / 100.0))
+                    .top(gpui::relative(plot.y(value) / 100.0))
+                    .w(gpui::relative(plot.bar_step() / 100.0))
+                    .h(px(2.0))
+                    .bg(rgb(color))
+                    .into_any_element()
+            })
+            .collect();
+        if let (Some(&(index, value)), Some(label)) = (points.last(), label) {
+            elements.push(
+                div()
+                    .absolute()
+                    .left(gpui::relative((plot.x(index) + plot.bar_step()) / 100.0))
+                    .top(gpui::relative(plot.y(value) / 100.0))
+                    .mt(px(-8.0))
+                    .ml_1()
+                    .text_xs()
+                    .text_color(rgb(color))
+                    .child(label)
+                    .into_any_element(),
+            );
+        }
+        elements
+    }
+
+    /// VWAP lines from each of the symbol's anchors that falls within the loaded bars
+    fn render_anchored_vwaps(
+        &self,
+        chart: &chart::Chart,
+        plot: &chart::PlotArea,
+    ) -> Vec<AnyElement> {
+        self.drawings
+            .vwap_anchors_for(&chart.symbol)
+            .filter_map(|anchor| {
+                let anchor_index = chart::anchor_index(&chart.bars, anchor.at)?;
+                let series = indicators::anchored_vwap(&chart.bars, anchor_index);
+                let label = format!(
+                    "AVWAP {}",
+                    self.config.numbers.format.price(indicators::last(&series)?)
+                );
+                Some(self.render_line_overlay(&series, plot, anchor.color, Some(label)))
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Volume profile bars against the right edge, sized by volume, with the value area
+    /// highlighted and a line at the point of control. Prices map like the candles.
+    /// SuperTrend line, green under price in an uptrend and red over it in a downtrend,
+    /// labelled in the colour of the latest visible trend
+    fn render_supertrend(&self, chart: &chart::Chart, plot: &chart::PlotArea) -> Vec<AnyElement> {
+        let settings = &self.settings.overlays.supertrend;
+        let Some((period, multiplier)) = settings.params().filter(|_| settings.enabled) else {
+            return Vec::new();
+        };
+        let series = indicators::supertrend(&chart.bars, period, multiplier);
+        let latest = series[plot.start_index..plot.end_index.min(series.len())]
+            .iter()
+            .rev()
+            .find_map(|point| *point);
+        let label = |up: bool| {
+            latest
+                .filter(|(_, latest_up)| *latest_up == up)
+                .map(|(value, _)| format!("ST {}", self.config.numbers.format.price(value)))
+        };
+        let side = |up: bool| -> Vec<Option<f64>> {
+            series
+                .iter()
+                .map(|point| {
+                    point
+                        .filter(|(_, point_up)| *point_up == up)
+                        .map(|(value, _)| value)
+                })
+                .collect()
+        };
+
+        let mut elements = self.render_line_overlay(&side(true), plot, 0x3fb950, label(true));
+        elements.extend(self.render_line_overlay(&side(false), plot, 0xff4444, label(false)));
+        elements
+    }
+
+    /// Parabolic SAR as a dot per bar, green below price in an uptrend and red above it in
+    /// a downtrend
+    fn render_parabolic_sar(
+        &self,
+        chart: &chart::Chart,
+        plot: &chart::PlotArea,
+    ) -> Vec<AnyElement> {
+        let settings = &self.settings.overlays.parabolic_sar;
+        let Some((step, max)) = settings.params().filter(|_| settings.enabled) else {
+            return Vec::new();
+        };
+        let series = indicators::parabolic_sar(&chart.bars, step, max);
+
+        (plot.start_index..plot.end_index.min(series.len()))
+            .filter_map(|index| series[index].map(|(sar, up)| (index, sar, up)))
+            .filter(|(_, sar, _)| plot.contains(*sar))
+            .map(|(index, sar, up)| {
+                div()
+                    .absolute()
+                    .left(gpui::relative(
+                        (plot.x(index) + plot.bar_step() / 2.0) / 100.0,
+                    ))
+                    .top(gpui::relative(plot.y(sar) / 100.0))
+                    .ml(px(-2.0))
+                    .mt(px(-2.0))
+                    .size(px(4.0))
+                    .rounded_full()
+                    .bg(rgb(if up { 0x3fb950 } else { 0xff4444 }))
+                    .into_any_element()
+            })
+            .collect()
+    }
+
+    /// Keltner Channels: the EMA labelled with its latest value, between its ATR bands
+    fn render_keltner(&self, chart: &chart::Chart, plot: &chart::PlotArea) -> Vec<AnyElement> {
+        const COLOR: u32 = 0x39c5cf;
+        let settings = &self.settings.overlays.keltner;
+        let Some((period, multiplier)) = settings.params().filter(|_| settings.enabled) else {
+            return Vec::new();
+        };
+        let (middle, upper, lower) = indicators::keltner(&chart.bars, period, multiplier);
+        let label = indicators::last(&middle)
+            .map(|value| format!("KC {}", self.config.numbers.format.price(value)));
+
+        let mut elements = self.render_line_overlay(&middle, plot, COLOR, label);
+        elements.extend(self.render_line_overlay(&upper, plot, COLOR, None));
+        elements.extend(self.render_line_overlay(&lower, plot, COLOR, None));
+        elements
+    }
+
+    /// ADX with +DI and -DI in a panel under the chart, lined up with its bars, with the
+    /// 25 trend-strength line and the latest values
+    fn render_adx_panel(
+        &self,
+        id: chart::ChartId,
+        chart: &chart::Chart,
+        plot: &chart::PlotArea,
+        cx: &mut Context<Self>,
+    ) -> Option<AnyElement> {
+        let settings = &self.settings.overlays.adx;
+        let period = settings.period().filter(|_| settings.enabled)?;
+        let (adx, plus_di, minus_di) = indicators::dmi(&chart.bars, period);
+        let panel = chart::PlotArea {
+            start_index: plot.start_index,
+            end_index: plot.end_index,
+            min_price: 0.0,
+            max_price: 100.0,
+        };
+        let value = |series: &[Option<f64>]| {
+            indicators::last(series).map_or("--".to_string(), |value| format!("{:.1}", value))
+        };
+
+        Some(
+            div()
+                .relative()
+                .w_full()
+                .h(px(chart::SUB_PANEL_HEIGHT))
+                .flex_none()
+                .bg(rgb(self.config.chart_style.background.0))
+                .border_2()
+                .border_color(rgb(0x404040))
+                .overflow_hidden()
+                .on_mouse_move(cx.listener(move |this, _event, _window, cx| {
+                    let Some(chart) = this.chart_mut(id) else {
+                        return;
+                    };
+                    chart.show_crosshair = false;
+                    cx.notify();
+                }))
+                .child(
+                    div()
+                        .absolute()
+                        .left_0()
+                        .top(gpui::relative(panel.y(25.0) / 100.0))
+                        .w_full()
+                        .h(px(1.0))
+                        .bg(rgb(0x30363d)),
+                )
+                .children(self.render_line_overlay(&plus_di, &panel, 0x3fb950, None))
+                .children(self.render_line_overlay(&minus_di, &panel, 0xff4444, None))
+                .children(self.render_line_overlay(&adx, &panel, 0xf2cc60, None))
+                .child(
+                    div()
+                        .absolute()
+                        .top(px(2.0))
+                        .left(px(6.0))
+                        .flex()
+                        .gap_2()
+                        .text_xs()
+                        .child(
+                            div()
+                                .text_color(rgb(0x8b949e))
+                                .child(format!("ADX({})", period)),
+                        )
+                        .child(div().text_color(rgb(0xf2cc60)).child(value(&adx)))
+                        .child(
+                            div()
+                                .text_color(rgb(0x3fb950))
+                                .child(format!("+DI {}", value(&plus_di))),
+                        )
+                        .child(
+                            div()
+                                .text_color(rgb(0xff4444))
+                                .child(format!("−DI {}", value(&minus_di))),
+                        ),
+                )
+                .into_any_element(),
+        )
+    }
+
+    /// Donchian Channels: labelled upper and lower bands with the midpoint between them
+    fn render_donchian(
+        &self,
+        id: chart::ChartId,
+        chart: &chart::Chart,
+        plot: &chart::PlotArea,
+    ) -> Vec<AnyElement> {
+        const COLOR: u32 = 0x58a6ff;
+        let Some(period) = self
+            .donchian(id)
+            .filter(|donchian| donchian.enabled)
+            .and_then(|donchian| donchian.period())
+        else {
+            return Vec::new();
+        };
+        let (middle, upper, lower) = indicators::donchian(&chart.bars, period);
+        let label = |prefix: &str, series: &[Option<f64>]| {
+            indicators::last(series)
+                .map(|value| format!("{} {}", prefix, self.config.numbers.format.price(value)))
+        };
+
+        let mut elements = self.render_line_overlay(&upper, plot, COLOR, label("DC ↑", &upper));
+        elements.extend(self.render_line_overlay(&lower, plot, COLOR, label("DC ↓", &lower)));
+        elements.extend(self.render_line_overlay(&middle, plot, 0x30363d, None));
+        elements
+    }
+
+    /// Per-chart Donchian Channels switch and period
+    fn render_donchian_control(
+        &self,
+        id: chart::ChartId,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let field = focus::InputField::DonchianPeriod(id);
+        let (enabled, period) = self
+            .donchian(id)
+            .map(|donchian| (donchian.enabled, donchian.period.clone()))
+            .unwrap_or_default();
+
+        div()
+            .flex()
+            .items_center()
+            .justify_center()
+            .gap_1()
+            .child(
+                div()
+                    .mr_1()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child("Donchian"),
+            )
+            .child(self.render_filter_chip(
+                format!("donchian-toggle-{:?}", id),
+                if enabled { "On" } else { "Off" },
+                enabled,
+                cx,
+                move |this, _| {
+                    let Some(donchian) = this.donchian_mut(id) else {
+                        return;
+                    };
+                    donchian.enabled = !donchian.enabled;
+                    if this.focus.is_focused(field) {
+                        this.set_input_focus(None);
+                    }
+                    if id == chart::ChartId::Main {
+                        this.settings.save();
+                    }
+                },
+            ))
+            .when(enabled, |this| {
+                this.child(self.render_overlay_input(
+                    format!("donchian-period-{:?}", id),
+                    field,
+                    &period,
+                    cx,
+                ))
+            })
+    }
+
+    /// Small numeric input for an indicator setting
+    fn render_overlay_input(
+        &self,
+        id: String,
+        field: focus::InputField,
+        text: &str,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let focused = self.focus.is_focused(field);
+
+        div()
+            .id(ElementId::Name(id.into()))
+            .w(px(36.0))
+            .px_1()
+            .py_1()
+            .bg(if focused {
+                rgb(0x1f2937)
+            } else {
+                rgb(0x0d1117)
+            })
+            .border_1()
+            .border_color(if focused {
+                rgb(0x1f6feb)
+            } else {
+                rgb(0x30363d)
+            })
+            .rounded_md()
+            .text_xs()
+            .text_color(rgb(0xffffff))
+            .cursor_text()
+            .child(if focused {
+                format!("{}|", text)
+            } else {
+                text.to_string()
+            })
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.set_input_focus(Some(field));
+                // Chart windows route their own keystrokes here
+                if field.chart_window().is_none() {
+                    window.focus(&this.focus_handle);
+                }
+                cx.notify();
+            }))
+    }
+
+    /// Pivot levels as labelled horizontal lines across the chart, for the levels inside
+    /// the price range
+    fn render_pivots(&self, chart: &chart::Chart, plot: &chart::PlotArea) -> Vec<AnyElement> {
+        let weekly = match self.settings.overlays.pivots {
+            overlays::PivotMode::Off => return Vec::new(),
+            overlays::PivotMode::Daily => false,
+            overlays::PivotMode::Weekly => true,
+        };
+        let Some((high, low, close)) = chart.prior_period(weekly) else {
+            return Vec::new();
+        };
+
+        indicators::pivot_points(high, low, close)
+            .levels()
+            .into_iter()
+            .filter(|(_, price)| plot.contains(*price))
+            .map(|(label, price)| {
+                let color = match label.as_bytes()[0] {
+                    b'R' => 0x3fb950,
+                    b'S' => 0xff4444,
+                    _ => 0xf2cc60,
+                };
+                div()
+                    .absolute()
+                    .left_0()
+                    .top(gpui::relative(plot.y(price) / 100.0))
+                    .w_full()
+                    .h(px(1.0))
+                    .bg(gpui::rgba(color << 8 | 0x66))
+                    .child(
+                        div()
+                            .absolute()
+                            .left(px(4.0))
+                            .top(px(-14.0))
+                            .text_xs()
+                            .text_color(rgb(color))
+                            .child(format!(
+                                "{} {}",
+                                label,
+                                self.config.numbers.format.price(price)
+                            )),
+                    )
+                    .into_any_element()
+            })
+            .collect()
+    }
+
+    fn render_volume_profile(
+        &self,
+        chart: &chart::Chart,
+        visible_bars: &[Bar],
+        min_price: f64,
+        max_price: f64,
+    ) -> Vec<AnyElement> {
+        const BINS: usize = 24;
+        let bars = match self.settings.overlays.volume_profile {
+            overlays::VolumeProfileMode::Off => return Vec::new(),
+            overlays::VolumeProfileMode::Visible => visible_bars,
+            overlays::VolumeProfileMode::Session => {
+                chart.intraday_session().unwrap_or(visible_bars)
+            }
+        };
+        let Some(profile) = indicators::volume_profile(bars, min_price, max_price, BINS) else {
+            return Vec::new();
+        };
+
+        let range = max_price - min_price;
+        let y_percent = |price: f64| 5.0 + ((max_price - price) / range) as f32 * 90.0;
+        let bin_height = (profile.bin_size / range) as f32 * 90.0;
+        let largest = profile.volumes[profile.poc];
+
+        let mut elements: Vec<AnyElement> = profile
+            .volumes
+            .iter()
+            .enumerate()
+            .filter(|(_, volume)| **volume > 0.0)
+            .map(|(bin, volume)| {
+                let color = if bin == profile.poc {
+                    gpui::rgba(0xf2cc6099)
+                } else if profile.value_area.contains(&bin) {
+                    gpui::rgba(0x1f6feb66)
+                } else {
+                    gpui::rgba(0x8b949e40)
+                };
+                let top = y_percent(profile.price(bin)) - bin_height / 2.0;
+                div()
+                    .absolute()
+                    .right_0()
+                    .top(gpui::relative(top / 100.0))
+                    .h(gpui::relative(bin_height * 0.9 / 100.0))
+                    .w(gpui::relative((volume / largest) as f32 * 0.25))
+                    .bg(color)
+                    .into_any_element()
+            })
+            .collect();
+
+        let poc_price = profile.price(profile.poc);
+        elements.push(
+            div()
+                .absolute()
+                .left_0()
+                .top(gpui::relative(y_percent(poc_price) / 100.0))
+                .w_full()
+                .h(px(1.0))
+                .bg(gpui::rgba(0xf2cc6080))
+                .child(
+                    div()
+                        .absolute()
+                        .right(px(4.0))
+                        .top(px(-14.0))
+                        .text_xs()
+                        .text_color(rgb(0xf2cc60))
+                        .child(format!(
+                            "POC {}",
+                            self.config.numbers.format.price(poc_price)
+                        )),
+                )
+                .into_any_element(),
+        );
+        elements
+    }
+
+    fn render_depth_toggle(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let open = self.settings.depth_open;
+
+        div()
+            .id("depth-toggle")
+            .px_2()
+            .py_1()
+            .rounded_md()
+            .text_xs()
+            .bg(if open { rgb(0x1f6feb) } else { rgb(0x21262d) })
+            .text_color(if open { rgb(0xffffff) } else { rgb(0x8b949e) })
+            .cursor_pointer()
+            .hover(|style| style.bg(rgb(0x30363d)))
+            .child("📶 Depth")
+            .on_click(cx.listener(|this, _, _, cx| {
+                this.toggle_depth(cx);
+            }))
+    }
+
+    /// Bid/ask size bars from the streamed NBBO: asks above the spread (best nearest it),
+    /// bids below, and the top-of-book imbalance
+    fn render_depth_panel(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let book = &self.depth;
+        let max_size = book.max_size();
+        let level_row = |level: &depth::Level, color: u32, tint: u32| {
+            let fraction = if max_size > 0.0 {
+                (level.size / max_size) as f32
+            } else {
+                0.0
+            };
+            div()
+                .relative()
+                .flex()
+                .justify_between()
+                .px_2()
+                .py(px(2.0))
+                .text_xs()
+                .child(
+                    div()
+                        .absolute()
+                        .top_0()
+                        .bottom_0()
+                        .right_0()
+                        .w(gpui::relative(fraction))
+                        .bg(rgb(tint)),
+                )
+                .child(
+                    div()
+                        .relative()
+                        .text_color(rgb(color))
+                        .child(format!("{:.2}", level.price)),
+                )
+                .child(
+                    div()
+                        .relative()
+                        .text_color(rgb(0xffffff))
+                        .child(format!("{}", level.size)),
+                )
+        };
+
+        div()
+            .w(px(200.0))
+            .flex_shrink_0()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .p_3()
+            .ml_2()
+            .bg(rgb(0x161b22))
+            .border_1()
+            .border_color(rgb(0x30363d))
+            .rounded_md()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(rgb(0xffffff))
+                            .child(format!("Depth {}", book.symbol)),
+                    )
+                    .child(
+                        div()
+                            .id("depth-close")
+                            .px_1()
+                            .text_sm()
+                            .text_color(rgb(0x8b949e))
+                            .cursor_pointer()
+                            .hover(|style| style.text_color(rgb(0xffffff)))
+                            .child("✕")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.toggle_depth(cx);
+                            })),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .justify_between()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child("Price")
+                    .child("Size"),
+            )
+            .when(book.quotes == 0, |this| {
+                this.child(
+                    div()
+                        .py_4()
+                        .text_xs()
+                        .text_color(rgb(0x8b949e))
+                        .child("Waiting for quotes..."),
+                )
+            })
+            .when(book.quotes > 0, |this| {
+                let spread = book
+                    .spread()
+                    .map(|(dollars, bps)| format!("Spread ${:.2} ({:.1} bps)", dollars, bps))
+                    .unwrap_or_default();
+                let imbalance = book.imbalance().unwrap_or(0.5);
+                this.children(
+                    book.asks()
+                        .iter()
+                        .rev()
+                        .map(|level| level_row(level, 0xff4444, 0x3d1f24)),
+                )
+                .child(
+                    div()
+                        .py_1()
+                        .text_xs()
+                        .text_color(rgb(0x8b949e))
+                        .border_t_1()
+                        .border_b_1()
+                        .border_color(rgb(0x30363d))
+                        .child(spread),
+                )
+                .children(
+                    book.bids()
+                        .iter()
+                        .map(|level| level_row(level, 0x3fb950, 0x1a3324)),
+                )
+                .child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .pt_2()
+                        .child(
+                            div()
+                                .flex()
+                                .h(px(6.0))
+                                .rounded_sm()
+                                .overflow_hidden()
+                                .bg(rgb(0xff4444))
+                                .child(
+                                    div()
+                                        .h_full()
+                                        .w(gpui::relative(imbalance as f32))
+                                        .bg(rgb(0x3fb950)),
+                                ),
+                        )
+                        .child(
+                            div()
+                                .flex()
+                                .justify_between()
+                                .text_xs()
+                                .text_color(rgb(0x8b949e))
+                                .child(format!("Bid {:.0}%", imbalance * 100.0))
+                                .child(format!("Ask {:.0}%", (1.0 - imbalance) * 100.0)),
+                        ),
+                )
+            })
+            .child(
+                div()
+                    .pt_1()
+                    .text_xs()
+                    .text_color(rgb(0x6e7681))
+                    .child("Best bid/offer with levels seen in the last few seconds"),
+            )
+    }
+
+    /// Switches the sidebar between the full order form and the scalp pad
+    fn render_scalp_toggle(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let enabled = self.settings.scalp.enabled;
+
+        div()
+            .id("scalp-toggle")
+            .px_2()
+            .py_1()
+            .rounded_md()
+            .text_xs()
+            .text_color(rgb(0x58a6ff))
+            .cursor_pointer()
+            .hover(|style| style.bg(rgb(0x21262d)).text_color(rgb(0xffffff)))
+            .child(if enabled {
+                "☰ Full Form"
+            } else {
+                "⚡ Scalp"
+            })
+            .on_click(cx.listener(|this, _, _, cx| {
+                this.settings.scalp.enabled = !this.settings.scalp.enabled;
+                if this.focus.is_focused(focus::InputField::ScalpSize) {
+                    this.set_input_focus(None);
+                }
+                this.settings.save();
+                cx.notify();
+            }))
+    }
+
+    /// Compact pad for the charted symbol: preset size, large buy/sell buttons, flatten,
+    /// reverse and the position with its unrealized P&L at the live price
+    fn render_scalp_pad(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let halted = self.trading_halted();
+        let focused = self.focus.is_focused(focus::InputField::ScalpSize);
+        let size = self.settings.scalp.size.clone();
+        let last = self.current_price();
+        let position = self.scalp_position();
+
+        let big_button = |id: &'static str, label: String, color: u32, hover: u32| {
+            div()
+                .id(id)
+                .flex_1()
+                .flex()
+                .items_center()
+                .justify_center()
+                .h(px(64.0))
+                .rounded_lg()
+                .bg(rgb(color))
+                .text_xl()
+                .font_weight(FontWeight::BOLD)
+                .text_color(rgb(0xffffff))
+                .cursor_pointer()
+                .hover(move |style| style.bg(rgb(hover)))
+                .child(label)
+        };
+        let small_button = |id: &'static str, label: &'static str| {
+            div()
+                .id(id)
+                .flex_1()
+                .flex()
+                .items_center()
+                .justify_center()
+                .py_2()
+                .rounded_md()
+                .bg(rgb(0x21262d))
+                .border_1()
+                .border_color(rgb(0x30363d))
+                .text_sm()
+                .font_weight(FontWeight::SEMIBOLD)
+                .text_color(rgb(0xffffff))
+                .cursor_pointer()
+                .hover(|style| style.bg(rgb(0x30363d)))
+                .child(label)
+        };
+
+        let (position_text, pnl_text, pnl_color) = match (position, last) {
+            (Some((qty, avg)), Some(last)) => {
+                let pnl = qty * (last - avg);
+                let percent = if avg > 0.0 {
+                    (last / avg - 1.0) * qty.signum() * 100.0
+                } else {
+                    0.0
+                };
+                (
+                    format!(
+                        "{} {} @ ${:.2}",
+                        if qty > 0.0 { "Long" } else { "Short" },
+                        qty.abs(),
+                        avg
+                    ),
+                    format!(
+                        "{}${:.2} ({:+.2}%)",
+                        if pnl < 0.0 { "-" } else { "+" },
+                        pnl.abs(),
+                        percent
+                    ),
+                    if pnl < 0.0 {
+                        rgb(0xff4444)
+                    } else {
+                        rgb(0x3fb950)
+                    },
+                )
+            }
+            (Some((qty, avg)), None) => (
+                format!(
+                    "{} {} @ ${:.2}",
+                    if qty > 0.0 { "Long" } else { "Short" },
+                    qty.abs(),
+                    avg
+                ),
+                "—".to_string(),
+                rgb(0x8b949e),
+            ),
+            (None, _) => ("Flat".to_string(), "—".to_string(), rgb(0x8b949e)),
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .when(halted, |this| this.opacity(0.6))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .text_lg()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(rgb(0xffffff))
+                            .child("⚡ Scalp"),
+                    )
+                    .child(self.render_scalp_toggle(cx)),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .px_3()
+                    .py_2()
+                    .bg(rgb(0x0d1117))
+                    .border_1()
+                    .border_color(rgb(0x1f6feb))
+                    .rounded_md()
+                    .child(
+                        div()
+                            .text_color(rgb(0x58a6ff))
+                            .font_weight(FontWeight::BOLD)
+                            .child(self.chart.symbol.clone()),
+                    )
+                    .child(
+                        div()
+                            .text_lg()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(rgb(0xffffff))
+                            .child(
+                                last.map(|price| format!("${:.2}", price))
+                                    .unwrap_or("—".to_string()),
+                            ),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().text_xs().text_color(rgb(0x8b949e)).child("Size"))
+                    .child(
+                        div()
+                            .id("scalp-size-input")
+                            .w(px(80.0))
+                            .px_3()
+                            .py_1()
+                            .bg(if focused {
+                                rgb(0x1f2937)
+                            } else {
+                                rgb(0x0d1117)
+                            })
+                            .border_1()
+                            .border_color(if focused {
+                                rgb(0x1f6feb)
+                            } else {
+                                rgb(0x30363d)
+                            })
+                            .rounded_md()
+                            .text_sm()
+                            .text_color(rgb(0xffffff))
+                            .cursor_text()
+                            .child(if focused {
+                                format!("{}|", size)
+                            } else if size.is_empty() {
+                                "Qty...".to_string()
+                            } else {
+                                size.clone()
+                            })
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.set_input_focus(Some(focus::InputField::ScalpSize));
+                                window.focus(&this.focus_handle);
+                                cx.notify();
+                            })),
+                    )
+                    .children(scalp::SIZE_PRESETS.iter().map(|&preset| {
+                        self.render_filter_chip(
+                            format!("scalp-size-{}", preset),
+                            preset,
+                            size.trim() == preset,
+                            cx,
+                            move |this, _| {
+                                this.settings.scalp.size = preset.to_string();
+                                this.settings.save();
+                            },
+                        )
+                    })),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(
+                        big_button(
+                            "scalp-buy",
+                            format!("BUY {}", size.trim()),
+                            0x238636,
+                            0x2ea043,
+                        )
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.scalp_order(scalp::ScalpAction::Buy, cx);
+                        })),
+                    )
+                    .child(
+                        big_button(
+                            "scalp-sell",
+                            format!("SELL {}", size.trim()),
+                            0xda3633,
+                            0xff4444,
+                        )
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.scalp_order(scalp::ScalpAction::Sell, cx);
+                        })),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(
+                        small_button("scalp-flatten", "Flatten").on_click(cx.listener(
+                            |this, _, _, cx| {
+                                this.scalp_flatten(cx);
+                            },
+                        )),
+                    )
+                    .child(
+                        small_button("scalp-reverse", "Reverse").on_click(cx.listener(
+                            |this, _, _, cx| {
+                                this.scalp_order(scalp::ScalpAction::Reverse, cx);
+                            },
+                        )),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .justify_between()
+                    .p_3()
+                    .bg(rgb(0x0d1117))
+                    .border_1()
+                    .border_color(rgb(0x30363d))
+                    .rounded_md()
+                    .child(self.render_account_stat(
+                        "Position".to_string(),
+                        position_text,
+                        rgb(0xffffff),
+                    ))
+                    .child(self.render_account_stat(
+                        "Unrealized P&L".to_string(),
+                        pnl_text,
+                        pnl_color,
+                    )),
+            )
+            .when_some(self.scalp.pending_reverse.as_ref(), |this, reverse| {
+                this.child(div().text_xs().text_color(rgb(0xf2cc60)).child(format!(
+                    "Reversing: {} {} {} once the close fills",
+                    reverse.side.label(),
+                    reverse.qty,
+                    reverse.symbol
+                )))
+            })
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child(if self.simulator.enabled {
+                        "Market orders go to the simulated account without confirmation"
+                    } else {
+                        "Market orders are sent on click without confirmation"
+                    }),
+            )
+    }
+
+    /// Sidebar stand-in while the order form is in its own window
+    fn render_docked_placeholder(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .p_4()
+            .rounded_md()
+            .bg(rgb(0x0d1117))
+            .border_1()
+            .border_color(rgb(0x30363d))
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(rgb(0x8b949e))
+                    .child("Order entry is open in its own window"),
+            )
+            .child(self.render_order_window_toggle(cx))
+    }
+
+    /// Contents of a chart window: symbol and timeframe, the chart, and an order ticket
+    fn render_chart_window(&mut self, id: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let chart_id = chart::ChartId::Window(id);
+        let Some(slot) = self.chart_windows.iter().find(|slot| slot.id == id) else {
+            return div();
+        };
+        let symbol_field = focus::InputField::ChartSymbol(id);
+        let quantity_field = focus::InputField::ChartQuantity(id);
+        let symbol_focused = self.focus.is_focused(symbol_field);
+        let quantity_focused = self.focus.is_focused(quantity_field);
+        let symbol = slot.chart.symbol.clone();
+        let timeframe = slot.chart.timeframe.clone();
+        let side = slot.side;
+        let symbol_input = slot.chart.symbol_input.render(symbol_focused, "Symbol");
+        let quantity_input = slot.quantity.render(quantity_focused, "Qty");
+        let quantity = slot.quantity.text().to_string();
+        let position = self
+            .account
+            .positions
+            .iter()
+            .find(|position| position.symbol == symbol)
+            .map(|position| position.qty.clone());
+        let price = self.chart_price(chart_id);
+        let candlesticks = self.render_candlesticks(chart_id, cx).into_any_element();
+        let pending = self
+            .order_guard
+            .pending
+            .as_ref()
+            .filter(|pending| pending.order == order_guard::GuardedOrder::ChartWindow(id));
+
+        let timeframe_chips: Vec<_> = chart::TIMEFRAMES
+            .iter()
+            .map(|&tf| {
+                self.render_filter_chip(
+                    format!("chart-window-{}-timeframe-{}", id, tf),
+                    tf,
+                    timeframe == tf,
+                    cx,
+                    move |this, cx| {
+                        if let Some(chart) = this.chart_mut(chart_id) {
+                            chart.timeframe = tf.to_string();
+                        }
+                        this.fetch_chart_bars_debounced(chart_id, cx);
+                    },
+                )
+            })
+            .collect();
+        let side_chips: Vec<_> = [hotkeys::HotkeySide::Buy, hotkeys::HotkeySide::Sell]
+            .into_iter()
+            .map(|chip_side| {
+                self.render_filter_chip(
+                    format!("chart-window-{}-side-{}", id, chip_side.label()),
+                    chip_side.label(),
+                    side == chip_side,
+                    cx,
+                    move |this, cx| {
+                        if let Some(slot) = this.chart_slot_mut(id) {
+                            slot.side = chip_side;
+                        }
+                        cx.notify();
+                    },
+                )
+            })
+            .collect();
+        let side_color = match side {
+            hotkeys::HotkeySide::Buy => rgb(0x238636),
+            hotkeys::HotkeySide::Sell => rgb(0xda3633),
+        };
+
+        div()
+            .relative()
+            .size_full()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .p_6()
+            .bg(rgb(0x0d1117))
+            .border_2()
+            .border_color(environment_color(self.profiles.environment()))
+            .child(
+                // Symbol, last price and timeframe
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_4()
+                    .child(
+                        div()
+                            .id(ElementId::Name(
+                                format!("chart-window-{}-symbol", id).into(),
+                            ))
+                            .px_3()
+                            .py_2()
+                            .min_w(px(100.0))
+                            .bg(if symbol_focused {
+                                rgb(0x1f2937)
+                            } else {
+                                rgb(0x161b22)
+                            })
+                            .border_1()
+                            .border_color(if symbol_focused {
+                                rgb(0x1f6feb)
+                            } else {
+                                rgb(0x30363d)
+                            })
+                            .rounded_md()
+                            .text_color(rgb(0xffffff))
+                            .font_weight(FontWeight::BOLD)
+                            .cursor_text()
+                            .child(symbol_input)
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.set_input_focus(Some(symbol_field));
+                                cx.notify();
+                            })),
+                    )
+                    .child(div().text_color(rgb(0x8b949e)).child(match price {
+                        Some(price) => self.config.numbers.format.price(price),
+                        None => "—".to_string(),
+                    }))
+                    .child(div().flex().gap_1().children(timeframe_chips)),
+            )
+            .child(div().flex_1().child(candlesticks))
+            .child(
+                // Order ticket: market orders for this window's symbol
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_3()
+                    .p_3()
+                    .rounded_md()
+                    .bg(rgb(0x161b22))
+                    .border_1()
+                    .border_color(rgb(0x30363d))
+                    .child(div().flex().gap_1().children(side_chips))
+                    .child(
+                        div()
+                            .id(ElementId::Name(format!("chart-window-{}-qty", id).into()))
+                            .px_3()
+                            .py_1()
+                            .min_w(px(80.0))
+                            .bg(if quantity_focused {
+                                rgb(0x1f2937)
+                            } else {
+                                rgb(0x0d1117)
+                            })
+                            .border_1()
+                            .border_color(if quantity_focused {
+                                rgb(0x1f6feb)
+                            } else {
+                                rgb(0x30363d)
+                            })
+                            .rounded_md()
+                            .text_sm()
+                            .text_color(rgb(0xffffff))
+                            .cursor_text()
+                            .child(quantity_input)
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.set_input_focus(Some(quantity_field));
+                                cx.notify();
+                            })),
+                    )
+                    .child(
+                        div()
+                            .id(ElementId::Name(
+                                format!("chart-window-{}-submit", id).into(),
+                            ))
+                            .px_4()
+                            .py_1()
+                            .rounded_md()
+                            .bg(side_color)
+                            .text_sm()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(rgb(0xffffff))
+                            .cursor_pointer()
+                            .hover(|style| style.opacity(0.9))
+                            .child(format!("{} {} {} @ MKT", side.label(), quantity, symbol))
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.submit_chart_window_order(id, cx);
+                            })),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(rgb(0x8b949e))
+                            .child(match position {
+                                Some(qty) => format!("Position: {}", qty),
+                                None => "No position".to_string(),
+                            }),
+                    ),
+            )
+            .when_some(pending, |this, pending| {
+                this.child(self.render_live_order_confirmation(pending, cx))
+            })
+    }
+
+    fn render_favorites_bar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_favorite = self.settings.is_favorite(&self.chart.symbol);
+
+        div()
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .gap_2()
+            .mt_4()
+            .child(
+                div()
+                    .id("favorite-toggle")
+                    .px_3()
+                    .py_1()
+                    .rounded_md()
+                    .text_sm()
+                    .bg(rgb(0x161b22))
+                    .border_1()
+                    .border_color(rgb(0x30363d))
+                    .text_color(if is_favorite {
+                        rgb(0xf2cc60)
+                    } else {
+                        rgb(0x8b949e)
+                    })
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgb(0x21262d)))
+                    .child(format!(
+                        "{} {}",
+                        if is_favorite { "★" } else { "☆" },
+                        self.chart.symbol
+                    ))
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        let symbol = this.chart.symbol.clone();
+                        this.settings.toggle_favorite(&symbol);
+                        this.settings.save();
+                        // Favorite chips show live prices
+                        this.sync_market_data();
+                        cx.notify();
+                    })),
+            )
+            .child(
+                div()
+                    .id("open-chart-window")
+                    .px_3()
+                    .py_1()
+                    .rounded_md()
+                    .text_sm()
+                    .bg(rgb(0x161b22))
+                    .border_1()
+                    .border_color(rgb(0x30363d))
+                    .text_color(rgb(0x58a6ff))
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgb(0x21262d)))
+                    .child("⧉ New Chart Window")
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.open_chart_window(cx);
+                    })),
+            )
+            .children(self.settings.favorite_symbols.iter().map(|symbol| {
+                let is_current = *symbol == self.chart.symbol;
+                // Stream price if we have one, else the last loaded bar for the charted symbol
+                let price = self.last_prices.get(symbol).cloned().or_else(|| {
+                    is_current
+                        .then(|| {
+                            self.chart
+                                .bars
+                                .last()
+                                .map(|bar| format!("{:.2}", bar.close))
+                        })
+                        .flatten()
+                });
+
+                div()
+                    .id(ElementId::Name(format!("favorite-{}", symbol).into()))
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .px_3()
+                    .py_1()
+                    .rounded_md()
+                    .text_sm()
+                    .bg(if is_current {
+                        rgb(0x1f6feb)
+                    } else {
+                        rgb(0x21262d)
+                    })
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgb(0x30363d)))
+                    .child(
+                        div()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(rgb(0xffffff))
+                            .child(symbol.clone()),
+                    )
+                    .when_some(price, |this, price| {
+                        this.child(
+                            div()
+                                .text_xs()
+                                .text_color(if is_current {
+                                    rgb(0xffffff)
+                                } else {
+                                    rgb(0x8b949e)
+                                })
+                                .child(self.config.numbers.format.price_text(&price)),
+                        )
+                    })
+                    .on_click({
+                        let symbol = symbol.clone();
+                        cx.listener(move |this, _, _, cx| {
+                            this.select_recent_symbol(symbol.clone(), cx);
+                        })
+                    })
+            }))
+    }
+
+    fn render_recent_symbols(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .relative()
+            .child(
+                div()
+                    .id("recent-symbols-button")
+                    .px_3()
+                    .py_2()
+                    .bg(if self.recent_symbols_open {
+                        rgb(0x1f2937)
+                    } else {
+                        rgb(0x161b22)
+                    })
+                    .border_1()
+                    .border_color(rgb(0x30363d))
+                    .rounded_lg()
+                    .text_color(rgb(0x8b949e))
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgb(0x21262d)))
+                    .child("Recent ▾")
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.recent_symbols_open = !this.recent_symbols_open;
+                        cx.notify();
+                    })),
+            )
+            .when(self.recent_symbols_open, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .top(px(40.0))
+                        .left_0()
+                        .min_w(px(120.0))
+                        .flex()
+                        .flex_col()
+                        .bg(rgb(0x161b22))
+                        .border_1()
+                        .border_color(rgb(0x30363d))
+                        .rounded_md()
+                        .shadow_lg()
+                        .when(self.settings.recent_symbols.is_empty(), |this| {
+                            this.child(
+                                div()
+                                    .px_3()
+                                    .py_1()
+                                    .text_xs()
+                                    .text_color(rgb(0x8b949e))
+                                    .child("No recent symbols"),
+                            )
+                        })
+                        .children(self.settings.recent_symbols.iter().map(|symbol| {
+                            div()
+                                .id(ElementId::Name(format!("recent-symbol-{}", symbol).into()))
+                                .px_3()
+                                .py_1()
+                                .text_sm()
+                                .text_color(if *symbol == self.chart.symbol {
+                                    rgb(0x58a6ff)
+                                } else {
+                                    rgb(0xffffff)
+                                })
+                                .cursor_pointer()
+                                .hover(|style| style.bg(rgb(0x30363d)))
+                                .child(symbol.clone())
+                                .on_click({
+                                    let symbol = symbol.clone();
+                                    cx.listener(move |this, _, _, cx| {
+                                        this.select_recent_symbol(symbol.clone(), cx);
+                                    })
+                                })
+                        })),
+                )
+            })
+    }
+
+    fn render_market_clock(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let (status_text, status_color, countdown_text) = match self.market_clock.as_ref() {
+            Some(clock) => (
+                if clock.is_open {
+                    "Market Open"
+                } else {
+                    "Market Closed"
+                },
+                if clock.is_open {
+                    rgb(0x3fb950)
+                } else {
+                    rgb(0xff4444)
+                },
+                format!(
+                    "{} in {}",
+                    if clock.is_open { "Closes" } else { "Opens" },
+                    clock.countdown(Utc::now())
+                ),
+            ),
+            None => ("Market --", rgb(0x8b949e), "Loading clock...".to_string()),
+        };
+
+        div()
+            .id("market-clock")
+            .flex()
+            .items_center()
+            .justify_between()
+            .gap_3()
+            .px_4()
+            .py_2()
+            .rounded_lg()
+            .bg(rgb(0x161b22))
+            .border_1()
+            .border_color(if self.calendar_open {
+                rgb(0x1f6feb)
+            } else {
+                rgb(0x30363d)
+            })
+            .cursor_pointer()
+            .hover(|style| style.border_color(rgb(0x1f6feb)))
+            .on_click(cx.listener(|this, _, _, cx| {
+                this.toggle_calendar(cx);
+            }))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().w(px(8.0)).h(px(8.0)).rounded_full().bg(status_color))
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(status_color)
+                            .child(status_text),
+                    ),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child(countdown_text),
+            )
+    }
+
+    fn render_calendar(&self) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .px_4()
+            .py_2()
+            .rounded_lg()
+            .bg(rgb(0x161b22))
+            .border_1()
+            .border_color(rgb(0x30363d))
+            .child(
+                div()
+                    .text_xs()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0x8b949e))
+                    .child(format!(
+                        "Holidays & early closes (next {} days)",
+                        clock::CALENDAR_LOOKAHEAD_DAYS
+                    )),
+            )
+            .when(self.calendar_loading, |this| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(rgb(0x8b949e))
+                        .child("Loading calendar..."),
+                )
+            })
+            .when(
+                !self.calendar_loading && self.calendar_events.is_empty(),
+                |this| {
+                    this.child(
+                        div()
+                            .text_xs()
+                            .text_color(rgb(0x8b949e))
+                            .child("No holidays or early closes coming up"),
+                    )
+                },
+            )
+            .children(self.calendar_events.iter().map(|event| {
+                let (label, color) = match event {
+                    clock::CalendarEvent::Holiday(_) => ("Closed".to_string(), rgb(0xff4444)),
+                    clock::CalendarEvent::EarlyClose { close, .. } => {
+                        (format!("Closes {} ET", close), rgb(0xf2cc60))
+                    }
+                };
+
+                div()
+                    .flex()
+                    .justify_between()
+                    .gap_4()
+                    .text_xs()
+                    .child(
+                        div()
+                            .text_color(rgb(0xffffff))
+                            .child(event.date().format("%a %b %-d").to_string()),
+                    )
+                    .child(div().text_color(color).child(label))
+            }))
+    }
+
+    /// Paper/live banner above the order form (simulated while forward testing)
+    fn render_environment_banner(&self) -> impl IntoElement {
+        let environment = self.profiles.environment();
+        if self.simulator.enabled {
+            return div()
+                .px_3()
+                .py_2()
+                .rounded_md()
+                .bg(rgb(0x6e40c9))
+                .text_sm()
+                .font_weight(FontWeight::BOLD)
+                .text_color(rgb(0xffffff))
+                .child("🧪 SIMULATED: ORDERS STAY IN THE TERMINAL");
+        }
+
+        div()
+            .px_3()
+            .py_2()
+            .rounded_md()
+            .bg(environment_color(environment))
+            .text_sm()
+            .font_weight(FontWeight::BOLD)
+            .text_color(rgb(0xffffff))
+            .child(match environment {
+                credentials::Environment::Paper => "📝 PAPER TRADING",
+                credentials::Environment::Live => "⚠️ LIVE TRADING: REAL MONEY",
+            })
+    }
+
+    fn render_profile_button(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let label = self
+            .profiles
+            .active_profile()
+            .map(|profile| profile.name.clone())
+            .unwrap_or("Environment keys".to_string());
+
+        div()
+            .id("profile-menu-toggle")
+            .flex()
+            .items_center()
+            .gap_2()
+            .px_4()
+            .py_2()
+            .rounded_lg()
+            .bg(rgb(0x161b22))
+            .border_1()
+            .border_color(if self.profile_menu_open {
+                rgb(0x1f6feb)
+            } else {
+                rgb(0x30363d)
+            })
+            .cursor_pointer()
+            .hover(|style| style.border_color(rgb(0x1f6feb)))
+            .on_click(cx.listener(|this, _, _, cx| {
+                this.profile_menu_open = !this.profile_menu_open;
+                cx.notify();
+            }))
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0xffffff))
+                    .child(format!("🔑 {} ▾", label)),
+            )
+            .child(
+                div()
+                    .px_1()
+                    .rounded_sm()
+                    .bg(environment_color(self.profiles.environment()))
+                    .text_xs()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(rgb(0xffffff))
+                    .child(self.profiles.environment().label()),
+            )
+    }
+
+    fn render_profile_menu(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let active = self.profiles.active_name().map(str::to_string);
+        let action = |id: &'static str, label: &'static str| {
+            div()
+                .id(id)
+                .text_xs()
+                .text_color(rgb(0x58a6ff))
+                .cursor_pointer()
+                .hover(|style| style.text_color(rgb(0xffffff)))
+                .child(label)
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .px_4()
+            .py_2()
+            .min_w(px(260.0))
+            .rounded_lg()
+            .bg(rgb(0x161b22))
+            .border_1()
+            .border_color(rgb(0x30363d))
+            .child(
+                div()
+                    .text_xs()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0x8b949e))
+                    .child("Profiles"),
+            )
+            .when(self.profiles.profiles.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(rgb(0x8b949e))
+                        .child("Using APCA_API_KEY_ID / APCA_API_SECRET_KEY"),
+                )
+            })
+            .children(
+                self.profiles
+                    .profiles
+                    .iter()
+                    .enumerate()
+                    .map(|(index, profile)| {
+                        let is_active = active.as_deref() == Some(profile.name.as_str());
+                        let environment = profile.environment();
+                        let switch_name = profile.name.clone();
+                        let remove_name = profile.name.clone();
+
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .py_1()
+                            .child(
+                                div()
+                                    .id(ElementId::Name(format!("profile-{}", index).into()))
+                                    .flex()
+                                    .flex_1()
+                                    .items_center()
+                                    .gap_2()
+                                    .cursor_pointer()
+                                    .child(
+                                        div()
+                                            .text_sm()
+                                            .text_color(if is_active {
+                                                rgb(0xffffff)
+                                            } else {
+                                                rgb(0x8b949e)
+                                            })
+                                            .when(is_active, |this| {
+                                                this.font_weight(FontWeight::SEMIBOLD)
+                                            })
+                                            .child(format!(
+                                                "{} {}",
+                                                if is_active { "●" } else { "○" },
+                                                profile.name
+                                            )),
+                                    )
+                                    .child(
+                                        div()
+                                            .px_1()
+                                            .rounded_sm()
+                                            .text_xs()
+                                            .text_color(environment_color(environment))
+                                            .child(environment.label()),
+                                    )
+                                    .child(
+                                        div()
+                                            .text_xs()
+                                            .text_color(rgb(0x8b949e))
+                                            .child(profile.base_url()),
+                                    )
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.switch_profile(switch_name.clone(), cx);
+                                    })),
+                            )
+                            .child(
+                                div()
+                                    .id(ElementId::Name(format!("profile-remove-{}", index).into()))
+                                    .px_1()
+                                    .text_xs()
+                                    .text_color(rgb(0x8b949e))
+                                    .cursor_pointer()
+                                    .hover(|style| style.text_color(rgb(0xff4444)))
+                                    .child("✕")
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.remove_profile(remove_name.clone(), cx);
+                                    })),
+                            )
+                    }),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_4()
+                    .pt_1()
+                    .border_t_1()
+                    .border_color(rgb(0x30363d))
+                    .when(!self.profiles.profiles.is_empty(), |this| {
+                        this.child(action("profile-edit", "Edit Active").on_click(cx.listener(
+                            |this, _, _, cx| {
+                                let profile = this.profiles.active_profile().cloned();
+                                this.profile_menu_open = false;
+                                this.setup.show(profile.as_ref());
+                                this.set_input_focus(Some(focus::InputField::ApiKeyId));
+                                cx.notify();
+                            },
+                        )))
+                    })
+                    .child(action("profile-add", "+ Add Profile").on_click(cx.listener(
+                        |this, _, _, cx| {
+                            this.profile_menu_open = false;
+                            this.setup.show(None);
+                            this.set_input_focus(Some(focus::InputField::ProfileName));
+                            cx.notify();
+                        },
+                    ))),
+            )
+    }
+
+    fn render_workspace_button(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let label = self
+            .settings
+            .active_workspace
+            .clone()
+            .unwrap_or("Workspace".to_string());
+
+        div()
+            .id("workspace-menu-toggle")
+            .flex()
+            .items_center()
+            .px_4()
+            .py_2()
+            .rounded_lg()
+            .bg(rgb(0x161b22))
+            .border_1()
+            .border_color(if self.workspaces.open {
+                rgb(0x1f6feb)
+            } else {
+                rgb(0x30363d)
+            })
+            .cursor_pointer()
+            .hover(|style| style.border_color(rgb(0x1f6feb)))
+            .on_click(cx.listener(|this, _, _, cx| {
+                this.workspaces.open = !this.workspaces.open;
+                if !this.workspaces.open && this.focus.is_focused(focus::InputField::WorkspaceName)
+                {
+                    this.set_input_focus(None);
+                }
+                cx.notify();
+            }))
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0xffffff))
+                    .child(format!("🗂️ {} ▾", label)),
+            )
+    }
+
+    fn render_workspace_menu(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let name_focused = self.focus.is_focused(focus::InputField::WorkspaceName);
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .px_4()
+            .py_2()
+            .min_w(px(260.0))
+            .rounded_lg()
+            .bg(rgb(0x161b22))
+            .border_1()
+            .border_color(rgb(0x30363d))
+            .child(
+                div()
+                    .text_xs()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0x8b949e))
+                    .child("Workspaces"),
+            )
+            .when(self.settings.workspaces.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(rgb(0x8b949e))
+                        .child("No saved workspaces"),
+                )
+            })
+            .children(
+                self.settings
+                    .workspaces
+                    .iter()
+                    .enumerate()
+                    .map(|(index, workspace)| {
+                        let is_active =
+                            self.settings.active_workspace.as_deref() == Some(&workspace.name);
+                        let switch_name = workspace.name.clone();
+                        let remove_name = workspace.name.clone();
+
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .py_1()
+                            .child(
+                                div()
+                                    .id(ElementId::Name(format!("workspace-{}", index).into()))
+                                    .flex()
+                                    .flex_1()
+                                    .items_center()
+                                    .gap_2()
+                                    .cursor_pointer()
+                                    .child(
+                                        div()
+                                            .text_sm()
+                                            .text_color(if is_active {
+                                                rgb(0xffffff)
+                                            } else {
+                                                rgb(0x8b949e)
+                                            })
+                                            .when(is_active, |this| {
+                                                this.font_weight(FontWeight::SEMIBOLD)
+                                            })
+                                            .child(format!(
+                                                "{} {}",
+                                                if is_active { "●" } else { "○" },
+                                                workspace.name
+                                            )),
+                                    )
+                                    .child(div().text_xs().text_color(rgb(0x8b949e)).child(
+                                        format!("{} · {}", workspace.symbol, workspace.timeframe),
+                                    ))
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.switch_workspace(switch_name.clone(), cx);
+                                    })),
+                            )
+                            .child(
+                                div()
+                                    .id(ElementId::Name(
+                                        format!("workspace-remove-{}", index).into(),
+                                    ))
+                                    .px_1()
+                                    .text_xs()
+                                    .text_color(rgb(0x8b949e))
+                                    .cursor_pointer()
+                                    .hover(|style| style.text_color(rgb(0xff4444)))
+                                    .child("✕")
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.remove_workspace(remove_name.clone(), cx);
+                                    })),
+                            )
+                    }),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .pt_2()
+                    .border_t_1()
+                    .border_color(rgb(0x30363d))
+                    .child(
+                        div()
+                            .id("workspace-name-input")
+                            .flex_1()
+                            .px_2()
+                            .py_1()
+                            .bg(if name_focused {
+                                rgb(0x1f2937)
+                            } else {
+                                rgb(0x0d1117)
+                            })
+                            .border_1()
+                            .border_color(if name_focused {
+                                rgb(0x1f6feb)
+                            } else {
+                                rgb(0x30363d)
+                            })
+                            .rounded_md()
+                            .text_xs()
+                            .text_color(rgb(0xffffff))
+                            .cursor_text()
+                            .child(self.workspaces.name.render(name_focused, "Workspace name"))
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.set_input_focus(Some(focus::InputField::WorkspaceName));
+                                cx.notify();
+                            })),
+                    )
+                    .child(
+                        div()
+                            .id("workspace-save")
+                            .text_xs()
+                            .text_color(rgb(0x58a6ff))
+                            .cursor_pointer()
+                            .hover(|style| style.text_color(rgb(0xffffff)))
+                            .child("Save Current")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.save_workspace(cx);
+                            })),
+                    ),
+            )
+    }
+
+    /// Popover with per-websocket telemetry: handshake time, message latency, last message and
+    /// reconnects
+    fn render_stream_health(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let row = |name: &'static str, health: &stream_health::ChannelHealth| {
+            let (status, color) = if health.is_stale() {
+                ("No data", rgb(0xf2cc60))
+            } else if health.connected {
+                ("Connected", rgb(0x3fb950))
+            } else {
+                ("Disconnected", rgb(0x8b949e))
+            };
+            let handshake = health
+                .handshake_ms
+                .map(|ms| format!("{}ms", ms))
+                .unwrap_or("--".to_string());
+            let last_message = health
+                .idle()
+                .map(|idle| format!("{} ago", stream_health::format_age(idle)))
+                .unwrap_or("never".to_string());
+            let uptime = health
+                .connected_at
+                .map(|at| stream_health::format_age(at.elapsed()))
+                .unwrap_or("--".to_string());
+
+            div()
+                .flex()
+                .flex_col()
+                .gap_1()
+                .py_1()
+                .child(
+                    div()
+                        .flex()
+                        .justify_between()
+                        .gap_4()
+                        .child(
+                            div()
+                                .text_sm()
+                                .font_weight(FontWeight::SEMIBOLD)
+                                .text_color(rgb(0xffffff))
+                                .child(name),
+                        )
+                        .child(div().text_xs().text_color(color).child(status)),
+                )
+                .child(div().text_xs().text_color(rgb(0x8b949e)).child(format!(
+                    "Handshake time {} · Last message {} · Up {}",
+                    handshake, last_message, uptime
+                )))
+                // Only market data messages carry an exchange timestamp
+                .when_some(health.latency_ms, |this, latency| {
+                    this.child(
+                        div()
+                            .text_xs()
+                            .text_color(rgb(0x8b949e))
+                            .child(format!("Latency {}ms (exchange time to receipt)", latency)),
+                    )
+                })
+                .child(div().text_xs().text_color(rgb(0x8b949e)).child(format!(
+                    "{} messages · {} reconnects",
+                    health.messages, health.reconnects
+                )))
+        };
+
+        div()
+            .id("stream-health-popover")
+            .flex()
+            .flex_col()
+            .gap_1()
+            .px_4()
+            .py_2()
+            .min_w(px(300.0))
+            .rounded_lg()
+            .bg(rgb(0x161b22))
+            .border_1()
+            .border_color(rgb(0x30363d))
+            .on_click(cx.listener(|this, _, _, cx| {
+                this.stream_health.open = false;
+                cx.notify();
+            }))
+            .child(
+                div()
+                    .text_xs()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0x8b949e))
+                    .child("Stream Health"),
+            )
+            .child(row("Trading", &self.stream_health.trading))
+            .child(row("Market Data", &self.stream_health.market_data))
+            .child(row("News", &self.stream_health.news))
+    }
+
+    fn render_notification_button(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let unread = self.notifications.unread_count();
+
+        div()
+            .id("notification-center-toggle")
+            .flex()
+            .items_center()
+            .justify_between()
+            .gap_3()
+            .px_4()
+            .py_2()
+            .rounded_lg()
+            .bg(rgb(0x161b22))
+            .border_1()
+            .border_color(if self.notifications.open {
+                rgb(0x1f6feb)
+            } else {
+                rgb(0x30363d)
+            })
+            .cursor_pointer()
+            .hover(|style| style.border_color(rgb(0x1f6feb)))
+            .on_click(cx.listener(|this, _, _, cx| {
+                this.notifications.open = !this.notifications.open;
+                cx.notify();
+            }))
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0xffffff))
+                    .child("🔔 Notifications"),
+            )
+            .when(unread > 0, |this| {
+                this.child(
+                    div()
+                        .px_2()
+                        .rounded_full()
+                        .bg(rgb(0xda3633))
+                        .text_xs()
+                        .font_weight(FontWeight::BOLD)
+                        .text_color(rgb(0xffffff))
+                        .child(unread.to_string()),
+                )
+            })
+    }
+
+    fn render_notification_center(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let header_action = |id: &'static str, label: &'static str| {
+            div()
+                .id(id)
+                .text_xs()
+                .text_color(rgb(0x58a6ff))
+                .cursor_pointer()
+                .hover(|style| style.text_color(rgb(0xffffff)))
+                .child(label)
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .px_4()
+            .py_2()
+            .max_w(px(360.0))
+            .rounded_lg()
+            .bg(rgb(0x161b22))
+            .border_1()
+            .border_color(rgb(0x30363d))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_4()
+                    .child(
+                        div()
+                            .text_xs()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(rgb(0x8b949e))
+                            .child("Notifications"),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .gap_3()
+                            .child(
+                                header_action(
+                                    "notifications-mute",
+                                    if self.settings.sound_muted {
+                                        "🔇 Muted"
+                                    } else {
+                                        "🔊 Sound"
+                                    },
+                                )
+                                .on_click(cx.listener(
+                                    |this, _, _, cx| {
+                                        this.settings.sound_muted = !this.settings.sound_muted;
+                                        this.settings.save();
+                                        cx.notify();
+                                    },
+                                )),
+                            )
+                            .child(
+                                header_action("notifications-mark-read", "Mark all read").on_click(
+                                    cx.listener(|this, _, _, cx| {
+                                        this.notifications.mark_all_read();
+                                        cx.notify();
+                                    }),
+                                ),
+                            )
+                            .child(header_action("notifications-clear", "Clear").on_click(
+                                cx.listener(|this, _, _, cx| {
+                                    this.notifications.clear();
+                                    cx.notify();
+                                }),
+                            )),
+                    ),
+            )
+            .when(!self.settings.sound_muted, |this| {
+                this.child(
+                    // Per-cue toggles (highlighted = plays)
+                    div().flex().items_center().gap_1().children(
+                        sound::SoundCue::ALL
+                            .iter()
+                            .map(|&cue| {
+                                self.render_filter_chip(
+                                    format!("sound-cue-{}", cue.label()),
+                                    cue.label(),
+                                    self.settings.sound_cue_enabled(cue),
+                                    cx,
+                                    move |this, _| {
+                                        this.settings.toggle_sound_cue(cue);
+                                        this.settings.save();
+                                    },
+                                )
+                            })
+                            .collect::<Vec<_>>(),
+                    ),
+                )
+            })
+            .when(self.notifications.notifications.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(rgb(0x8b949e))
+                        .child("No notifications"),
+                )
+            })
+            .child(
+                div()
+                    .id("notification-list")
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .max_h(px(FOOTER_TABLE_MAX_HEIGHT))
+                    .overflow_y_scroll()
+                    .children(self.notifications.notifications.iter().map(|notification| {
+                        let id = notification.id;
+                        let kind_color = match notification.kind {
+                            notifications::NotificationKind::Alert => rgb(0x58a6ff),
+                            notifications::NotificationKind::Fill => rgb(0x3fb950),
+                            notifications::NotificationKind::Order => rgb(0xf2cc60),
+                            notifications::NotificationKind::Error => rgb(0xff4444),
+                        };
+
+                        div()
+                            .id(ElementId::Name(format!("notification-{}", id).into()))
+                            .flex()
+                            .gap_2()
+                            .py_1()
+                            .text_xs()
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x21262d)))
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.notifications.mark_read(id);
+                                cx.notify();
+                            }))
+                            .child(
+                                // Unread dot
+                                div()
+                                    .mt(px(4.0))
+                                    .w(px(6.0))
+                                    .h(px(6.0))
+                                    .flex_shrink_0()
+                                    .rounded_full()
+                                    .when(!notification.read, |this| this.bg(rgb(0x1f6feb))),
+                            )
+                            .child(
+                                div()
+                                    .flex()
+                                    .flex_col()
+                                    .flex_1()
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .justify_between()
+                                            .gap_2()
+                                            .child(
+                                                div()
+                                                    .font_weight(if notification.read {
+                                                        FontWeight::NORMAL
+                                                    } else {
+                                                        FontWeight::SEMIBOLD
+                                                    })
+                                                    .text_color(kind_color)
+                                                    .child(format!(
+                                                        "{} · {}",
+                                                        notification.kind.label(),
+                                                        notification.title
+                                                    )),
+                                            )
+                                            .child(
+                                                div().text_color(rgb(0x8b949e)).child(
+                                                    notification
+                                                        .timestamp
+                                                        .format("%H:%M:%S")
+                                                        .to_string(),
+                                                ),
+                                            ),
+                                    )
+                                    .child(
+                                        div()
+                                            .text_color(if notification.read {
+                                                rgb(0x8b949e)
+                                            } else {
+                                                rgb(0xffffff)
+                                            })
+                                            .child(notification.message.clone()),
+                                    ),
+                            )
+                    })),
+            )
+    }
+
+    /// Snapshot for the charted symbol, if it has loaded
+    fn chart_snapshot(&self) -> Option<&snapshot::SymbolSnapshot> {
+        self.snapshot
+            .as_ref()
+            .filter(|snapshot| snapshot.symbol == self.chart.symbol)
+    }
+
+    /// Last price of the charted symbol (streamed, or from the snapshot) and its change and
+    /// percent change against the previous close
+    fn symbol_quote(&self) -> (Option<f64>, Option<(f64, f64)>) {
+        let snapshot = self.chart_snapshot();
+        let price = self
+            .last_prices
+            .get(&self.chart.symbol)
+            .and_then(|price| price.parse::<f64>().ok())
+            .or_else(|| snapshot.and_then(|snapshot| snapshot.last_price));
+        let change = price.and_then(|price| snapshot.and_then(|snapshot| snapshot.change(price)));
+        (price, change)
+    }
+
+    /// OS window title, e.g. "AAPL 229.41 ▲1.2% — Alpaca Terminal", for watching the price
+    /// from the taskbar
+    fn window_title(&self) -> String {
+        let (price, change) = self.symbol_quote();
+        let Some(price) = price else {
+            return format!("{} — {}", self.chart.symbol, APP_TITLE);
+        };
+        let price = self.config.numbers.format.price(price).replacen('$', "", 1);
+        match change {
+            Some((change, percent)) => format!(
+                "{} {} {}{:.1}% — {}",
+                self.chart.symbol,
+                price,
+                if change >= 0.0 { "▲" } else { "▼" },
+                percent.abs(),
+                APP_TITLE
+            ),
+            None => format!("{} {} — {}", self.chart.symbol, price, APP_TITLE),
+        }
+    }
+
+    /// Set the OS window title if the price or symbol changed since it was last set
+    fn refresh_window_title(&mut self, cx: &mut Context<Self>) {
+        let title = self.window_title();
+        if title == self.window_title {
+            return;
+        }
+        self.window_title = title.clone();
+        let handle = self.window_handle;
+        // Deferred: the window may be mid-update when this runs
+        cx.defer(move |cx| {
+            let _ = handle.update(cx, |_, window, _| window.set_window_title(&title));
+        });
+    }
+
+    /// Last price of the charted symbol (streamed, or from the snapshot) with the change
+    /// against the previous close and the current bid/ask
+    fn render_symbol_quote(&self) -> impl IntoElement {
+        let numbers = &self.config.numbers.format;
+        let snapshot = self.chart_snapshot();
+        let (price, change) = self.symbol_quote();
+
+        // While the market is closed the headline is the regular-session close, with the
+        // pre-market or after-hours price beside it
+        let market_closed = self
+            .market_clock
+            .as_ref()
+            .is_some_and(|clock| !clock.is_open);
+        let extended = price
+            .filter(|_| market_closed && !chart::is_crypto_symbol(&self.chart.symbol))
+            .and_then(|price| snapshot.and_then(|snapshot| snapshot.extended_quote(price)));
+        let (price, change) = match &extended {
+            Some(extended) => (Some(extended.regular_close), extended.regular_change),
+            None => (price, change),
+        };
+
+        let change_label = |change: Option<(f64, f64)>| match change {
+            Some((change, percent)) => (
+                format!("{} {:+.2}%", if change >= 0.0 { "▲" } else { "▼" }, percent),
+                pnl_color(change),
+            ),
+            None => ("--".to_string(), rgb(0x8b949e)),
+        };
+        let (change_text, color) = change_label(change);
+        let spread = snapshot.and_then(|snapshot| match (snapshot.bid, snapshot.ask) {
+            (Some(bid), Some(ask)) if bid > 0.0 && ask > 0.0 => {
+                Some(format!("{} × {}", numbers.price(bid), numbers.price(ask)))
+            }
+            _ => None,
+        });
+
+        div()
+            .flex()
+            .items_center()
+            .gap_3()
+            .px_4()
+            .py_2()
+            .rounded_lg()
+            .bg(rgb(0x161b22))
+            .border_1()
+            .border_color(rgb(0x30363d))
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child(self.chart.symbol.clone()),
+            )
+            .child(
+                div()
+                    .text_lg()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(rgb(0xffffff))
+                    .child(
+                        price
+                            .map(|price| numbers.price(price))
+                            .unwrap_or("--".to_string()),
+                    ),
+            )
+            .child(div().text_sm().text_color(color).child(change_text))
+            .when_some(extended, |this, extended| {
+                let (change_text, color) = change_label(Some(extended.change));
+                this.child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .pl_3()
+                        .border_l_1()
+                        .border_color(rgb(0x30363d))
+                        .child(
+                            div()
+                                .text_xs()
+                                .text_color(rgb(0x8b949e))
+                                .child(extended.session.label()),
+                        )
+                        .child(
+                            div()
+                                .text_sm()
+                                .text_color(rgb(0xffffff))
+                                .child(numbers.price(extended.price)),
+                        )
+                        .child(div().text_xs().text_color(color).child(change_text)),
+                )
+            })
+            .when_some(spread, |this, spread| {
+                this.child(div().text_xs().text_color(rgb(0x8b949e)).child(spread))
+            })
+    }
+
+    /// Compact stats for the charted symbol: 52-week range, average volume, today's
+    /// open/high/low and the previous close
+    fn render_key_stats(&self) -> impl IntoElement {
+        let numbers = &self.config.numbers.format;
+        let snapshot = self.chart_snapshot();
+        let stats = self
+            .key_stats
+            .as_ref()
+            .filter(|stats| stats.symbol == self.chart.symbol);
+        let today = snapshot.and_then(|snapshot| snapshot.daily_bar.as_ref());
+
+        let price = |value: Option<f64>| value.map(|value| numbers.price(value));
+        let range = |range: Option<(f64, f64)>| {
+            range.map(|(high, low)| format!("{} – {}", numbers.price(low), numbers.price(high)))
+        };
+        let rows = [
+            (
+                "52W Range",
+                range(stats.and_then(|stats| stats.year_range(today))),
+            ),
+            (
+                "Avg Volume",
+                stats
+                    .and_then(|stats| stats.avg_volume)
+                    .map(|volume| numbers.volume(volume)),
+            ),
+            ("Open", price(today.map(|bar| bar.open))),
+            ("Day Range", range(today.map(|bar| (bar.high, bar.low)))),
+            (
+                "Prev Close",
+                price(snapshot.and_then(|snapshot| snapshot.previous_close())),
+            ),
+        ];
+
+        div()
+            .flex()
+            .flex_wrap()
+            .gap_x_4()
+            .gap_y_1()
+            .px_4()
+            .py_2()
+            .rounded_lg()
+            .bg(rgb(0x161b22))
+            .border_1()
+            .border_color(rgb(0x30363d))
+            .children(rows.into_iter().map(|(label, value)| {
+                div()
+                    .flex()
+                    .gap_1()
+                    .text_xs()
+                    .child(div().text_color(rgb(0x8b949e)).child(label))
+                    .child(
+                        div()
+                            .text_color(rgb(0xffffff))
+                            .child(value.unwrap_or("--".to_string())),
+                    )
+            }))
+    }
+
+    fn render_daily_pnl(&self) -> impl IntoElement {
+        let (value_text, percent_text, color) = match self.account.daily_pnl() {
+            Some((change, percent)) => (
+                self.config.numbers.format.signed_money(change),
+                format!("{:+.2}%", percent),
+                pnl_color(change),
+            ),
+            None => ("--".to_string(), "--".to_string(), rgb(0x8b949e)),
+        };
+
+        div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .gap_3()
+            .px_4()
+            .py_2()
+            .rounded_lg()
+            .bg(rgb(0x161b22))
+            .border_1()
+            .border_color(rgb(0x30363d))
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child("Today's P&L"),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .items_baseline()
+                    .text_color(color)
+                    .font_weight(FontWeight::BOLD)
+                    .child(div().text_lg().child(value_text))
+                    .child(div().text_sm().child(percent_text)),
+            )
+    }
+
+    fn render_account_tab(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let numbers = &self.config.numbers.format;
+        let money = |value: Option<f64>| value.map(|v| numbers.money(v)).unwrap_or("-".to_string());
+
+        let balances = div()
+            .flex()
+            .flex_wrap()
+            .gap_6()
+            .text_sm()
+            .child(
+                self.render_account_stat(
+                    "Account Number".to_string(),
+                    self.account
+                        .account_number
+                        .clone()
+                        .unwrap_or("Loading...".to_string()),
+                    rgb(0xa371f7),
+                ),
+            )
+            .child(
+                self.render_account_stat(
+                    "Account Status".to_string(),
+                    self.account
+                        .account_status
+                        .clone()
+                        .unwrap_or("Loading...".to_string()),
+                    rgb(0x58a6ff),
+                ),
+            )
+            .child(self.render_account_stat(
+                "Portfolio Value".to_string(),
+                numbers.money(self.account.portfolio_value.unwrap_or(0.0)),
+                rgb(0x3fb950),
+            ))
+            .child(self.render_account_stat(
+                "Equity".to_string(),
+                numbers.money(self.account.equity.unwrap_or(0.0)),
+                rgb(0x3fb950),
+            ))
+            .child(self.render_account_stat(
+                "Cash".to_string(),
+                numbers.money(self.account.cash.unwrap_or(0.0)),
+                rgb(0xf2cc60),
+            ))
+            .child(self.render_account_stat(
+                "Buying Power".to_string(),
+                numbers.money(self.account.buying_power.unwrap_or(0.0)),
+                rgb(0xf2cc60),
+            ));
+
+        // Margin, exposure and pattern day trader details
+        let daytrade_count = self.account.daytrade_count.unwrap_or(0);
+        let is_pdt = self.account.pattern_day_trader.unwrap_or(false);
+        let margin = div()
+            .flex()
+            .flex_wrap()
+            .gap_6()
+            .text_sm()
+            .child(
+                self.render_account_stat(
+                    "Day Trades (5d)".to_string(),
+                    self.account
+                        .daytrade_count
+                        .map(|count| count.to_string())
+                        .unwrap_or("-".to_string()),
+                    if daytrade_count >= 3 {
+                        rgb(0xff4444)
+                    } else {
+                        rgb(0xffffff)
+                    },
+                ),
+            )
+            .child(self.render_account_stat(
+                "PDT".to_string(),
+                match self.account.pattern_day_trader {
+                    Some(true) => "Yes".to_string(),
+                    Some(false) => "No".to_string(),
+                    None => "-".to_string(),
+                },
+                if is_pdt { rgb(0xf2cc60) } else { rgb(0xffffff) },
+            ))
+            .child(self.render_account_stat(
+                "Initial Margin".to_string(),
+                money(self.account.initial_margin),
+                rgb(0xffffff),
+            ))
+            .child(self.render_account_stat(
+                "Maintenance Margin".to_string(),
+                money(self.account.maintenance_margin),
+                rgb(0xffffff),
+            ))
+            .child(self.render_account_stat(
+                "Long Market Value".to_string(),
+                money(self.account.long_market_value),
+                rgb(0x3fb950),
+            ))
+            .child(self.render_account_stat(
+                "Short Market Value".to_string(),
+                money(self.account.short_market_value),
+                rgb(0xff4444),
+            ))
+            .child(self.render_account_stat(
+                "SMA".to_string(),
+                money(self.account.sma),
+                rgb(0xffffff),
+            ))
+            .child(
+                self.render_account_stat(
+                    "Multiplier".to_string(),
+                    self.account
+                        .multiplier
+                        .map(|m| format!("{}x", m))
+                        .unwrap_or("-".to_string()),
+                    rgb(0xffffff),
+                ),
+            );
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .child(balances)
+            .child(margin)
+            .child(self.render_account_config(cx))
+    }
+
+    fn render_account_config(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let section = div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .pt_3()
+            .border_t_1()
+            .border_color(rgb(0x30363d))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_3()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(rgb(0xffffff))
+                            .child("Account Configuration"),
+                    )
+                    .when(self.account_config_saving, |this| {
+                        this.child(div().text_xs().text_color(rgb(0x8b949e)).child("Saving..."))
+                    })
+                    .when_some(self.account_config_message.clone(), |this, message| {
+                        this.child(
+                            div()
+                                .text_xs()
+                                .text_color(if message.starts_with("✓") {
+                                    rgb(0x3fb950)
+                                } else {
+                                    rgb(0xff4444)
+                                })
+                                .child(message),
+                        )
+                    }),
+            );
+
+        let Some(config) = self.account_config.as_ref() else {
+            return section.child(
+                div()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child("Loading configuration..."),
+            );
+        };
+
+        let config_label = |label: &'static str| {
+            div()
+                .w(px(160.0))
+                .text_xs()
+                .text_color(rgb(0x8b949e))
+                .child(label)
+        };
+
+        section
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(config_label("Restrictions"))
+                    .child(self.render_filter_chip(
+                        "account-config-no-shorting".to_string(),
+                        "No Shorting",
+                        config.no_shorting,
+                        cx,
+                        |this, cx| {
+                            this.update_account_config(|c| c.no_shorting = !c.no_shorting, cx)
+                        },
+                    ))
+                    .child(self.render_filter_chip(
+                        "account-config-suspend-trade".to_string(),
+                        "Suspend Trading",
+                        config.suspend_trade,
+                        cx,
+                        |this, cx| {
+                            this.update_account_config(|c| c.suspend_trade = !c.suspend_trade, cx)
+                        },
+                    )),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(config_label("Trade Confirmation Emails"))
+                    .children(
+                        account_config::TRADE_CONFIRM_EMAIL_OPTIONS
+                            .iter()
+                            .map(|option| {
+                                let option = *option;
+                                self.render_filter_chip(
+                                    format!("account-config-email-{}", option),
+                                    option,
+                                    config.trade_confirm_email == option,
+                                    cx,
+                                    move |this, cx| {
+                                        this.update_account_config(
+                                            |c| c.trade_confirm_email = option.to_string(),
+                                            cx,
+                                        )
+                                    },
+                                )
+                            }),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(config_label("Day Trading BP Check"))
+                    .children(account_config::DTBP_CHECK_OPTIONS.iter().map(|option| {
+                        let option = *option;
+                        self.render_filter_chip(
+                            format!("account-config-dtbp-{}", option),
+                            option,
+                            config.dtbp_check == option,
+                            cx,
+                            move |this, cx| {
+                                this.update_account_config(
+                                    |c| c.dtbp_check = option.to_string(),
+                                    cx,
+                                )
+                            },
+                        )
+                    })),
+            )
+    }
+
+    fn render_positions_tab(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.account.positions_loading {
+            return div()
+                .grid()
+                .items_center()
+                .justify_center()
+                .p_6()
+                .text_color(rgb(0x8b949e))
+                .child("Loading positions...");
+        }
+
+        if self.account.positions.is_empty() {
+            return div()
+                .grid()
+                .items_center()
+                .justify_center()
+                .p_6()
+                .text_color(rgb(0x8b949e))
+                .child("No active positions");
+        }
+
+        let total_unrealized: f64 = self
+            .account
+            .positions
+            .iter()
+            .filter_map(|p| p.unrealized_pl.parse::<f64>().ok())
+            .sum();
+        let total_realized = self.account.lot_ledger.total_realized();
+        let visible_positions = self.account.visible_positions();
+        let position_columns = self.settings.positions_columns.visible();
+
+        div()
+            .grid()
+            .grid_cols(1)
+            .gap_2()
+            .on_mouse_move(
+                cx.listener(|this, event: &gpui::MouseMoveEvent, _window, cx| {
+                    this.drag_column(event.position.x, cx);
+                }),
+            )
+            .on_mouse_up(
+                gpui::MouseButton::Left,
+                cx.listener(|this, _event: &gpui::MouseUpEvent, _window, cx| {
+                    this.end_column_drag(cx);
+                }),
+            )
+            .on_mouse_up_out(
+                gpui::MouseButton::Left,
+                cx.listener(|this, _event: &gpui::MouseUpEvent, _window, cx| {
+                    this.end_column_drag(cx);
+                }),
+            )
+            .child(
+                // P&L summary (realized P&L is matched FIFO from the loaded fill activities)
+                div()
+                    .flex()
+                    .gap_6()
+                    .text_sm()
+                    .child(self.render_account_stat(
+                        "Unrealized P&L".to_string(),
+                        self.config.numbers.format.money(total_unrealized),
+                        pnl_color(total_unrealized),
+                    ))
+                    .child(self.render_account_stat(
+                        "Realized P&L (recent fills)".to_string(),
+                        self.config.numbers.format.money(total_realized),
+                        pnl_color(total_realized),
+                    ))
+                    .child(
+                        self.render_account_stat(
+                            "Total P&L".to_string(),
+                            self.config
+                                .numbers
+                                .format
+                                .money(total_unrealized + total_realized),
+                            pnl_color(total_unrealized + total_realized),
+                        ),
+                    )
+                    .child(
+                        // Quick symbol filter
+                        div()
+                            .id("positions-filter-input")
+                            .ml_auto()
+                            .px_3()
+                            .py_1()
+                            .min_w(px(120.0))
+                            .h(px(28.0))
+                            .bg(
+                                if self.focus.is_focused(focus::InputField::PositionsFilter) {
+                                    rgb(0x1f2937)
+                                } else {
+                                    rgb(0x0d1117)
+                                },
+                            )
+                            .border_1()
+                            .border_color(
+                                if self.focus.is_focused(focus::InputField::PositionsFilter) {
+                                    rgb(0x1f6feb)
+                                } else {
+                                    rgb(0x30363d)
+                                },
+                            )
+                            .rounded_md()
+                            .text_xs()
+                            .text_color(rgb(0xffffff))
+                            .cursor_text()
+                            .child(
+                                if self.focus.is_focused(focus::InputField::PositionsFilter) {
+                                    format!("{}|", self.account.positions_filter)
+                                } else if self.account.positions_filter.is_empty() {
+                                    "Filter symbol...".to_string()
+                                } else {
+                                    self.account.positions_filter.clone()
+                                },
+                            )
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.set_input_focus(Some(focus::InputField::PositionsFilter));
+                                window.focus(&this.focus_handle);
+                                cx.notify();
+                            })),
+                    )
+                    .child(
+                        div()
+                            .id("positions-export")
+                            .px_2()
+                            .py_1()
+                            .rounded_md()
+                            .bg(rgb(0x21262d))
+                            .text_xs()
+                            .text_color(rgb(0x58a6ff))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x30363d)).text_color(rgb(0xffffff)))
+                            .child("⇩ Export positions")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.export_positions(cx);
+                            })),
+                    )
+                    .child(self.render_columns_menu_button(columns::ColumnTable::Positions, cx)),
+            )
+            .when(
+                self.columns_menu_open == Some(columns::ColumnTable::Positions),
+                |this| this.child(self.render_position_columns_menu(cx)),
+            )
+            .child(
+                // Table header (drag a column's right edge to resize it)
+                div()
+                    .flex()
+                    .gap_4()
+                    .pb_2()
+                    .border_b_1()
+                    .border_color(rgb(0x30363d))
+                    .children(position_columns.iter().map(|&(index, column, width)| {
+                        div()
+                            .relative()
+                            .flex_shrink_0()
+                            .child(self.render_position_header_cell(column, width, cx))
+                            .child(self.render_column_resize_handle(
+                                columns::ColumnTable::Positions,
+                                index,
+                                width,
+                                cx,
+                            ))
+                    })),
+            )
+            .when(visible_positions.is_empty(), |this| {
+                this.child(
+                    div()
+                        .p_4()
+                        .text_sm()
+                        .text_color(rgb(0x8b949e))
+                        .child("No positions match the filter"),
+                )
+            })
+            .child(
+                self.render_scrollable_rows(
+                    "positions-rows",
+                    visible_positions
+                        .into_iter()
+                        .enumerate()
+                        .map(|(idx, pos)| {
+                            let is_selected =
+                                self.account.selected_position.as_deref() == Some(&pos.symbol);
+
+                            div()
+                                .id(ElementId::Name(format!("position-row-{}", idx).into()))
+                                .flex()
+                                .gap_4()
+                                .py_2()
+                                .rounded_md()
+                                .cursor_pointer()
+                                .when(is_selected, |this| this.bg(rgb(0x21262d)))
+                                .hover(|style| style.bg(rgb(0x21262d)))
+                                .on_click({
+                                    let symbol = pos.symbol.clone();
+                                    cx.listener(move |this, _, _, cx| {
+                                        this.account.selected_position =
+                                            if this.account.selected_position.as_ref()
+                                                == Some(&symbol)
+                                            {
+                                                None
+                                            } else {
+                                                Some(symbol.clone())
+                                            };
+                                        cx.notify();
+                                    })
+                                })
+                                .children(position_columns.iter().map(|&(_, column, width)| {
+                                    self.render_position_cell(column, width, idx, pos, cx)
+                                }))
+                        })
+                        .collect::<Vec<_>>(),
+                    cx,
+                ),
+            )
+            .when_some(
+                self.account
+                    .selected_position
+                    .as_ref()
+                    .and_then(|symbol| self.account.positions.iter().find(|p| &p.symbol == symbol)),
+                |this, pos| this.child(self.render_position_detail(pos, cx)),
+            )
+    }
+
+    fn render_orders_tab(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.account.orders_loading {
+            return div()
+                .grid()
+                .items_center()
+                .justify_center()
+                .p_6()
+                .text_color(rgb(0x8b949e))
+                .child("Loading orders...");
+        }
+
+        if self.account.orders.is_empty() {
+            return div()
+                .grid()
+                .items_center()
+                .justify_center()
+                .p_6()
+                .text_color(rgb(0x8b949e))
+                .child("No active orders");
+        }
+
+        let visible_orders = self.account.visible_orders();
+        let order_columns = self.settings.orders_columns.visible();
+
+        div()
+            .grid()
+            .grid_cols(1)
+            .gap_2()
+            .on_mouse_move(
+                cx.listener(|this, event: &gpui::MouseMoveEvent, _window, cx| {
+                    this.drag_column(event.position.x, cx);
+                }),
+            )
+            .on_mouse_up(
+                gpui::MouseButton::Left,
+                cx.listener(|this, _event: &gpui::MouseUpEvent, _window, cx| {
+                    this.end_column_drag(cx);
+                }),
+            )
+            .on_mouse_up_out(
+                gpui::MouseButton::Left,
+                cx.listener(|this, _event: &gpui::MouseUpEvent, _window, cx| {
+                    this.end_column_drag(cx);
+                }),
+            )
+            .child(
+                // Search box and side/type filters
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_4()
+                    .child(
+                        div()
+                            .id("orders-search-input")
+                            .px_3()
+                            .py_1()
+                            .min_w(px(120.0))
+                            .h(px(28.0))
+                            .bg(if self.focus.is_focused(focus::InputField::OrdersSearch) {
+                                rgb(0x1f2937)
+                            } else {
+                                rgb(0x0d1117)
+                            })
+                            .border_1()
+                            .border_color(
+                                if self.focus.is_focused(focus::InputField::OrdersSearch) {
+                                    rgb(0x1f6feb)
+                                } else {
+                                    rgb(0x30363d)
+                                },
+                            )
+                            .rounded_md()
+                            .text_xs()
+                            .text_color(rgb(0xffffff))
+                            .cursor_text()
+                            .child(if self.focus.is_focused(focus::InputField::OrdersSearch) {
+                                format!("{}|", self.account.orders_search)
+                            } else if self.account.orders_search.is_empty() {
+                                "Search symbol...".to_string()
+                            } else {
+                                self.account.orders_search.clone()
+                            })
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.set_input_focus(Some(focus::InputField::OrdersSearch));
+                                window.focus(&this.focus_handle);
+                                cx.notify();
+                            })),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .gap_1()
+                            .children(account::OrderSideFilter::ALL.iter().map(|filter| {
+                                let filter = *filter;
+                                self.render_filter_chip(
+                                    format!("orders-side-{}", filter.label()),
+                                    filter.label(),
+                                    self.account.orders_side_filter == filter,
+                                    cx,
+                                    move |this, _| this.account.orders_side_filter = filter,
+                                )
+                            })),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .gap_1()
+                            .children(account::OrderTypeFilter::ALL.iter().map(|filter| {
+                                let filter = *filter;
+                                self.render_filter_chip(
+                                    format!("orders-type-{}", filter.label()),
+                                    filter.label(),
+                                    self.account.orders_type_filter == filter,
+                                    cx,
+                                    move |this, _| this.account.orders_type_filter = filter,
+                                )
+                            })),
+                    )
+                    .child(div().text_xs().text_color(rgb(0x8b949e)).child(format!(
+                        "{} of {}{} orders",
+                        visible_orders.len(),
+                        self.account.orders.len(),
+                        if self.account.orders_next_until.is_some() {
+                            "+"
+                        } else {
+                            ""
+                        }
+                    )))
+                    .child(
+                        div().ml_auto().child(
+                            self.render_columns_menu_button(columns::ColumnTable::Orders, cx),
+                        ),
+                    ),
+            )
+            .when(
+                self.columns_menu_open == Some(columns::ColumnTable::Orders),
+                |this| this.child(self.render_order_columns_menu(cx)),
+            )
+            .child(
+                // Table header (drag a column's right edge to resize it)
+                div()
+                    .flex()
+                    .gap_4()
+                    .pb_2()
+                    .border_b_1()
+                    .border_color(rgb(0x30363d))
+                    .children(order_columns.iter().map(|&(index, column, width)| {
+                        div()
+                            .relative()
+                            .flex_shrink_0()
+                            .child(self.render_order_header_cell(column, width, cx))
+                            .child(self.render_column_resize_handle(
+                                columns::ColumnTable::Orders,
+                                index,
+                                width,
+                                cx,
+                            ))
+                    })),
+            )
+            .child(
+                self.render_scrollable_rows(
+                    "orders-rows",
+                    visible_orders
+                        .into_iter()
+                        .enumerate()
+                        .flat_map(|(idx, order)| {
+                            // Bracket/OCO legs are listed indented under their parent
+                            let legs = order
+                                .legs
+                                .iter()
+                                .enumerate()
+                                .map(move |(leg, order)| (idx, Some(leg), order));
+                            std::iter::once((idx, None, order)).chain(legs)
+                        })
+                        .map(|(idx, leg, order)| {
+                            self.render_order_row(idx, leg, order, &order_columns, cx)
+                        })
+                        .collect::<Vec<_>>(),
+                    cx,
+                ),
+            )
+            .when(self.account.orders_next_until.is_some(), |this| {
+                this.child(
+                    div().flex().justify_center().child(
+                        div()
+                            .id("orders-load-more")
+                            .px_3()
+                            .py_1()
+                            .rounded_md()
+                            .text_xs()
+                            .bg(rgb(0x21262d))
+                            .text_color(rgb(0x58a6ff))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x30363d)))
+                            .child(if self.account.orders_loading_more {
+                                "Loading...".to_string()
+                            } else {
+                                format!("Load more ({} loaded)", self.account.orders.len())
+                            })
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.load_more_orders(cx);
+                            })),
+                    ),
+                )
+            })
+            .when_some(self.account.selected_order(), |this, order| {
+                this.child(self.render_order_detail(order, cx))
+            })
+    }
+
+    fn render_news_tab(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let scope_chips = news::NewsScope::ALL
+            .iter()
+            .map(|&scope| {
+                self.render_filter_chip(
+                    format!("news-scope-{}", scope.label()),
+                    scope.label(),
+                    self.news.scope == scope,
+                    cx,
+                    move |this, cx| {
+                        if this.news.scope != scope {
+                            this.news.scope = scope;
+                            this.fetch_news(cx);
+                        }
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        let controls = div().flex().items_center().gap_1().children(scope_chips);
+
+        let body = if self.news.loading {
+            div()
+                .grid()
+                .items_center()
+                .justify_center()
+                .p_6()
+                .text_color(rgb(0x8b949e))
+                .child("Loading news...")
+        } else if let Some(error) = &self.news.error {
+            div()
+                .grid()
+                .items_center()
+                .justify_center()
+                .p_6()
+                .text_color(rgb(0xff4444))
+                .child(error.clone())
+        } else if self.news.articles.is_empty() {
+            div()
+                .grid()
+                .items_center()
+                .justify_center()
+                .p_6()
+                .text_color(rgb(0x8b949e))
+                .child("No news")
+        } else {
+            div().child(
+                self.render_scrollable_rows(
+                    "news-rows",
+                    self.news
+                        .articles
+                        .iter()
+                        .map(|article| {
+                            let url = article.url.clone();
+                            div()
+                                .id(ElementId::Name(format!("news-{}", article.id).into()))
+                                .flex()
+                                .gap_4()
+                                .py_2()
+                                .text_sm()
+                                .text_color(rgb(0x8b949e))
+                                .when(url.is_some(), |this| {
+                                    this.cursor_pointer().hover(|style| style.bg(rgb(0x21262d)))
+                                })
+                                .child(
+                                    div().w(px(130.0)).flex_shrink_0().child(
+                                        article
+                                            .created_at
+                                            .with_timezone(&chrono::Local)
+                                            .format("%Y-%m-%d %H:%M")
+                                            .to_string(),
+                                    ),
+                                )
+                                .child(
+                                    div()
+                                        .w(px(90.0))
+                                        .flex_shrink_0()
+                                        .text_color(rgb(0x58a6ff))
+                                        .child(article.source.clone()),
+                                )
+                                .child(
+                                    div()
+                                        .w(px(120.0))
+                                        .flex_shrink_0()
+                                        .overflow_hidden()
+                                        .child(article.symbols.join(", ")),
+                                )
+                                .child(
+                                    div()
+                                        .flex_1()
+                                        .text_color(rgb(0xffffff))
+                                        .child(article.headline.clone()),
+                                )
+                                .on_click(cx.listener(move |_, _, _, cx| {
+                                    if let Some(url) = &url {
+                                        cx.open_url(url);
+                                    }
+                                }))
+                        })
+                        .collect(),
+                    cx,
+                ),
+            )
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(controls)
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_3()
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(if self.news.stream_connected {
+                                        rgb(0x3fb950)
+                                    } else {
+                                        rgb(0x8b949e)
+                                    })
+                                    .child(if self.news.stream_connected {
+                                        "● Live"
+                                    } else {
+                                        "○ Offline"
+                                    }),
+                            )
+                            .child(div().text_xs().text_color(rgb(0x8b949e)).child(format!(
+                                "{} headlines · {}",
+                                self.news.articles.len(),
+                                self.news.symbols.join(", ")
+                            ))),
+                    ),
+            )
+            .child(body)
+    }
+
+    fn render_heatmap_tab(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let universe_chips = config::Universe::ALL
+            .iter()
+            .map(|&universe| {
+                self.render_filter_chip(
+                    format!("heatmap-universe-{}", universe.label()),
+                    universe.label(),
+                    self.heatmap.universe == universe,
+                    cx,
+                    move |this, cx| {
+                        if this.heatmap.universe != universe {
+                            this.heatmap.universe = universe;
+                            this.fetch_heatmap(cx);
+                        }
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        let size_chips = config::TileSize::ALL
+            .iter()
+            .map(|&size_by| {
+                self.render_filter_chip(
+                    format!("heatmap-size-{}", size_by.label()),
+                    size_by.label(),
+                    self.heatmap.size_by == size_by,
+                    cx,
+                    move |this, cx| {
+                        this.heatmap.size_by = size_by;
+                        cx.notify();
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        let controls = div()
+            .flex()
+            .items_center()
+            .gap_1()
+            .children(universe_chips)
+            .child(div().w(px(12.0)))
+            .children(size_chips);
+
+        let positions: std::collections::HashMap<String, f64> = self
+            .account
+            .positions
+            .iter()
+            .filter_map(|position| {
+                let value = position.market_value.parse::<f64>().ok()?;
+                Some((position.symbol.clone(), value))
+            })
+            .collect();
+        let tiles = self.heatmap.tiles(&positions);
+        let rects = heatmap::layout(&tiles, heatmap::MAP_ASPECT);
+        let numbers = &self.config.numbers.format;
+
+        let body = if tiles.is_empty() {
+            let message = if self.heatmap.loading {
+                "Loading snapshots...".to_string()
+            } else if let Some(error) = &self.heatmap.error {
+                error.clone()
+            } else if self.heatmap.universe == config::Universe::Watchlist {
+                "Add favorites to map your watchlist".to_string()
+            } else {
+                "No data".to_string()
+            };
+            div()
+                .grid()
+                .items_center()
+                .justify_center()
+                .p_6()
+                .text_color(if self.heatmap.error.is_some() {
+                    rgb(0xff4444)
+                } else {
+                    rgb(0x8b949e)
+                })
+                .child(message)
+        } else {
+            div()
+                .relative()
+                .w_full()
+                .h(px(FOOTER_TABLE_MAX_HEIGHT))
+                .children(tiles.iter().zip(rects).map(|(tile, rect)| {
+                    // Labels only fit on tiles of a reasonable size
+                    let show_label = rect.w >= 0.05 && rect.h >= 0.22;
+                    let symbol = tile.symbol.clone();
+                    div()
+                        .id(ElementId::Name(format!("heatmap-{}", tile.symbol).into()))
+                        .absolute()
+                        .left(gpui::relative(rect.x))
+                        .top(gpui::relative(rect.y))
+                        .w(gpui::relative(rect.w))
+                        .h(gpui::relative(rect.h))
+                        .overflow_hidden()
+                        .flex()
+                        .flex_col()
+                        .items_center()
+                        .justify_center()
+                        .bg(rgb(heatmap::change_color(tile.change_percent)))
+                        .border_1()
+                        .border_color(rgb(0x0d1117))
+                        .text_color(rgb(0xffffff))
+                        .cursor_pointer()
+                        .hover(|style| style.border_color(rgb(0x58a6ff)))
+                        .when(show_label, |this| {
+                            this.child(
+                                div()
+                                    .text_xs()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .child(tile.symbol.clone()),
+                            )
+                            .child(div().text_xs().child(match tile.change_percent {
+                                Some(percent) => format!("{:+.2}%", percent),
+                                None => "--".to_string(),
+                            }))
+                            .when_some(tile.price, |this, price| {
+                                this.child(
+                                    div()
+                                        .text_xs()
+                                        .text_color(rgb(0xc9d1d9))
+                                        .child(numbers.price(price)),
+                                )
+                            })
+                        })
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.select_recent_symbol(symbol.clone(), cx);
+                        }))
+                }))
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(controls)
+                    .child(div().text_xs().text_color(rgb(0x8b949e)).child(format!(
+                        "{} symbols · updated {}",
+                        tiles.len(),
+                        self.heatmap
+                            .updated_at
+                            .map(|at| at.format("%H:%M:%S").to_string())
+                            .unwrap_or("--".to_string())
+                    ))),
+            )
+            .child(body)
+    }
+
+    fn render_movers_tab(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let universe_chips = config::Universe::ALL
+            .iter()
+            .map(|&universe| {
+                self.render_filter_chip(
+                    format!("movers-universe-{}", universe.label()),
+                    universe.label(),
+                    self.screener.universe == universe,
+                    cx,
+                    move |this, cx| {
+                        if this.screener.universe != universe {
+                            this.screener.universe = universe;
+                            this.fetch_screener(cx);
+                        }
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        let sort_chips = screener::ScreenerSort::ALL
+            .iter()
+            .map(|&sort| {
+                self.render_filter_chip(
+                    format!("movers-sort-{}", sort.label()),
+                    sort.label(),
+                    self.screener.sort == sort,
+                    cx,
+                    move |this, cx| {
+                        this.screener.sort = sort;
+                        cx.notify();
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        let filter_toggle = self.render_filter_chip(
+            "movers-filters".to_string(),
+            if self.screener.filter.is_some() {
+                "Filters ●"
+            } else {
+                "Filters"
+            },
+            self.screener.form.open,
+            cx,
+            |this, cx| {
+                this.screener.form.open = !this.screener.form.open;
+                if !this.screener.form.open
+                    && matches!(
+                        this.focus.focused(),
+                        Some(focus::InputField::ScreenFilter(_))
+                    )
+                {
+                    this.set_input_focus(None);
+                }
+                cx.notify();
+            },
+        );
+        let controls = div()
+            .flex()
+            .items_center()
+            .gap_1()
+            .children(sort_chips)
+            .child(div().w(px(12.0)))
+            .children(universe_chips)
+            .child(div().w(px(12.0)))
+            .child(filter_toggle);
+
+        let rows = self.screener.rows(self.config.screener.limit);
+        let numbers = &self.config.numbers.format;
+        let percent = |value: Option<f64>| {
+            value
+                .map(|value| format!("{:+.2}%", value))
+                .unwrap_or("--".to_string())
+        };
+
+        let body = if rows.is_empty() {
+            let message = if self.screener.loading {
+                "Loading snapshots...".to_string()
+            } else if let Some(error) = &self.screener.error {
+                error.clone()
+            } else if self.screener.universe == config::Universe::Watchlist
+                && self.settings.favorite_symbols.is_empty()
+            {
+                "Add favorites to screen your watchlist".to_string()
+            } else if self.screener.sma_loading {
+                "Loading 20-day SMAs...".to_string()
+            } else if self.screener.filter.is_some() {
+                "No symbols match the screen".to_string()
+            } else {
+                "No data".to_string()
+            };
+            div()
+                .grid()
+                .items_center()
+                .justify_center()
+                .p_6()
+                .text_color(if self.screener.error.is_some() {
+                    rgb(0xff4444)
+                } else {
+                    rgb(0x8b949e)
+                })
+                .child(message)
+        } else {
+            let header_cell = |label: &'static str, width: f32| {
+                div()
+                    .w(px(width))
+                    .text_xs()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0x8b949e))
+                    .child(label)
+            };
+
+            div()
+                .grid()
+                .grid_cols(1)
+                .gap_2()
+                .child(
+                    // Table header
+                    div()
+                        .flex()
+                        .gap_4()
+                        .pb_2()
+                        .border_b_1()
+                        .border_color(rgb(0x30363d))
+                        .child(header_cell("#", 30.0))
+                        .child(header_cell("Symbol", 80.0))
+                        .child(header_cell("Price", 100.0))
+                        .child(header_cell("Change", 80.0))
+                        .child(header_cell("Gap", 80.0))
+                        .child(header_cell("Rel Vol", 80.0))
+                        .child(header_cell("Volume", 100.0)),
+                )
+                .child(
+                    self.render_scrollable_rows(
+                        "movers-rows",
+                        rows.iter()
+                            .enumerate()
+                            .map(|(rank, row)| {
+                                let symbol = row.symbol.clone();
+                                let change_color =
+                                    row.change_percent.map_or(rgb(0x8b949e), pnl_color);
+                                let gap_color = row.gap_percent.map_or(rgb(0x8b949e), pnl_color);
+                                div()
+                                    .id(ElementId::Name(format!("movers-{}", row.symbol).into()))
+                                    .flex()
+                                    .gap_4()
+                                    .py_1()
+                                    .text_sm()
+                                    .text_color(rgb(0xffffff))
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(0x21262d)))
+                                    .child(
+                                        div()
+                                            .w(px(30.0))
+                                            .text_color(rgb(0x8b949e))
+                                            .child((rank + 1).to_string()),
+                                    )
+                                    .child(
+                                        div()
+                                            .w(px(80.0))
+                                            .font_weight(FontWeight::SEMIBOLD)
+                                            .child(row.symbol.clone()),
+                                    )
+                                    .child(
+                                        div().w(px(100.0)).child(
+                                            row.price
+                                                .map(|price| numbers.price(price))
+                                                .unwrap_or("--".to_string()),
+                                        ),
+                                    )
+                                    .child(
+                                        div()
+                                            .w(px(80.0))
+                                            .text_color(change_color)
+                                            .child(percent(row.change_percent)),
+                                    )
+                                    .child(
+                                        div()
+                                            .w(px(80.0))
+                                            .text_color(gap_color)
+                                            .child(percent(row.gap_percent)),
+                                    )
+                                    .child(
+                                        div().w(px(80.0)).child(
+                                            row.relative_volume
+                                                .map(|ratio| format!("{:.2}×", ratio))
+                                                .unwrap_or("--".to_string()),
+                                        ),
+                                    )
+                                    .child(
+                                        div().w(px(100.0)).text_color(rgb(0x8b949e)).child(
+                                            row.volume
+                                                .map(|volume| numbers.volume(volume))
+                                                .unwrap_or("--".to_string()),
+                                        ),
+                                    )
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.select_recent_symbol(symbol.clone(), cx);
+                                    }))
+                            })
+                            .collect(),
+                        cx,
+                    ),
+                )
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(controls)
+                    .child(div().text_xs().text_color(rgb(0x8b949e)).child(format!(
+                        "{}{} of {} symbols · updated {}",
+                        match &self.screener.filter {
+                            Some(filter) if !filter.name.is_empty() => format!("{}: ", filter.name),
+                            _ => String::new(),
+                        },
+                        rows.len(),
+                        self.screener.snapshots.len(),
+                        self.screener
+                            .updated_at
+                            .map(|at| at.format("%H:%M:%S").to_string())
+                            .unwrap_or("--".to_string())
+                    ))),
+            )
+            .when(self.screener.form.open, |this| {
+                this.child(self.render_screen_filter_form(cx))
+            })
+            .child(body)
+    }
+
+    /// Screen bounds, SMA position and saved screens for the Movers tab
+    fn render_screen_filter_form(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let sma_chips = screener::SmaFilter::ALL
+            .iter()
+            .map(|&sma| {
+                self.render_filter_chip(
+                    format!("movers-sma-{}", sma.label()),
+                    sma.label(),
+                    self.screener.form.sma == sma,
+                    cx,
+                    move |this, cx| {
+                        this.screener.form.sma = sma;
+                        cx.notify();
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        let action = |id: &'static str, label: &'static str| {
+            div()
+                .id(id)
+                .text_xs()
+                .text_color(rgb(0x58a6ff))
+                .cursor_pointer()
+                .hover(|style| style.text_color(rgb(0xffffff)))
+                .child(label)
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .p_3()
+            .rounded_md()
+            .bg(rgb(0x0d1117))
+            .border_1()
+            .border_color(rgb(0x30363d))
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .items_end()
+                    .gap_3()
+                    .children(
+                        screener::FilterField::NUMERIC
+                            .iter()
+                            .map(|&field| self.render_screen_filter_input(field, 90.0, cx))
+                            .collect::<Vec<_>>(),
+                    )
+                    .child(div().flex().items_center().gap_1().children(sma_chips))
+                    .child(action("movers-filter-apply", "Apply").on_click(cx.listener(
+                        |this, _, _, cx| {
+                            this.apply_screen_filter(cx);
+                        },
+                    )))
+                    .child(action("movers-filter-clear", "Clear").on_click(cx.listener(
+                        |this, _, _, cx| {
+                            this.clear_screen_filter(cx);
+                        },
+                    ))),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .items_end()
+                    .gap_3()
+                    .child(self.render_screen_filter_input(screener::FilterField::Name, 160.0, cx))
+                    .child(
+                        action("movers-screen-save", "Save Screen").on_click(cx.listener(
+                            |this, _, _, cx| {
+                                this.save_screen(cx);
+                            },
+                        )),
+                    )
+                    .children(self.settings.saved_screens.iter().enumerate().map(
+                        |(index, screen)| {
+                            let is_active = self
+                                .screener
+                                .filter
+                                .as_ref()
+                                .is_some_and(|filter| filter.name == screen.name);
+                            let load_name = screen.name.clone();
+                            let scan_name = screen.name.clone();
+                            let watchlist_name = screen.name.clone();
+                            let remove_name = screen.name.clone();
+                            div()
+                                .flex()
+                                .items_center()
+                                .gap_1()
+                                .px_2()
+                                .py_1()
+                                .rounded_md()
+                                .text_xs()
+                                .bg(if is_active {
+                                    rgb(0x1f6feb)
+                                } else {
+                                    rgb(0x21262d)
+                                })
+                                .child(
+                                    div()
+                                        .id(ElementId::Name(format!("screen-{}", index).into()))
+                                        .text_color(rgb(0xffffff))
+                                        .cursor_pointer()
+                                        .child(screen.name.clone())
+                                        .on_click(cx.listener(move |this, _, _, cx| {
+                                            this.load_screen(&load_name, cx);
+                                        })),
+                                )
+                                .child(
+                                    // Scanning alert toggle
+                                    div()
+                                        .id(ElementId::Name(
+                                            format!("screen-scan-{}", index).into(),
+                                        ))
+                                        .text_color(if screen.scan {
+                                            rgb(0xf2cc60)
+                                        } else {
+                                            rgb(0x8b949e)
+                                        })
+                                        .cursor_pointer()
+                                        .hover(|style| style.text_color(rgb(0xffffff)))
+                                        .child(if screen.scan { "🔔" } else { "🔕" })
+                                        .on_click(cx.listener(move |this, _, _, cx| {
+                                            this.toggle_screen_scan(&scan_name, cx);
+                                        })),
+                                )
+                                .when(screen.scan, |this| {
+                                    // Star new matches
+                                    this.child(
+                                        div()
+                                            .id(ElementId::Name(
+                                                format!("screen-watchlist-{}", index).into(),
+                                            ))
+                                            .text_color(if screen.add_to_watchlist {
+                                                rgb(0xf2cc60)
+                                            } else {
+                                                rgb(0x8b949e)
+                                            })
+                                            .cursor_pointer()
+                                            .hover(|style| style.text_color(rgb(0xffffff)))
+                                            .child(if screen.add_to_watchlist {
+                                                "★"
+                                            } else {
+                                                "☆"
+                                            })
+                                            .on_click(cx.listener(move |this, _, _, cx| {
+                                                this.toggle_screen_watchlist(&watchlist_name, cx);
+                                            })),
                                     )
+                                })
+                                .child(
+                                    div()
+                                        .id(ElementId::Name(
+                                            format!("screen-remove-{}", index).into(),
+                                        ))
+                                        .text_color(rgb(0x8b949e))
+                                        .cursor_pointer()
+                                        .hover(|style| style.text_color(rgb(0xff4444)))
+                                        .child("✕")
+                                        .on_click(cx.listener(move |this, _, _, cx| {
+                                            this.remove_screen(&remove_name, cx);
+                                        })),
+                                )
+                        },
+                    )),
+            )
+    }
+
+    fn render_screen_filter_input(
+        &self,
+        field: screener::FilterField,
+        width: f32,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let focused = self
+            .focus
+            .is_focused(focus::InputField::ScreenFilter(field));
+        let value = self.screener.form.field(field);
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child(field.label()),
+            )
+            .child(
+                div()
+                    .id(ElementId::Name(
+                        format!("screen-filter-{}", field.label()).into(),
+                    ))
+                    .w(px(width))
+                    .px_2()
+                    .py_1()
+                    .bg(if focused {
+                        rgb(0x1f2937)
+                    } else {
+                        rgb(0x0d1117)
+                    })
+                    .border_1()
+                    .border_color(if focused {
+                        rgb(0x1f6feb)
+                    } else {
+                        rgb(0x30363d)
+                    })
+                    .rounded_md()
+                    .text_xs()
+                    .text_color(rgb(0xffffff))
+                    .cursor_text()
+                    .child(if focused {
+                        format!("{}|", value)
+                    } else if value.is_empty() {
+                        "—".to_string()
+                    } else {
+                        value.to_string()
+                    })
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.set_input_focus(Some(focus::InputField::ScreenFilter(field)));
+                        window.focus(&this.focus_handle);
+                        cx.notify();
+                    })),
+            )
+    }
+
+    /// Long/short, gross and net exposure with the SPY beta-weighted delta, and each
+    /// position's beta
+    fn render_analytics_tab(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let numbers = &self.config.numbers.format;
+        let positions = &self.account.positions;
+        let exposure = exposure::Exposure::from_positions(positions);
+        let (delta, unweighted) = self.analytics.beta_weighted_delta(positions);
+        let of_equity = |value: f64| match self.account.equity.filter(|equity| *equity > 0.0) {
+            Some(equity) => format!("{} ({:.0}%)", numbers.money(value), value / equity * 100.0),
+            None => numbers.money(value),
+        };
+        let delta_text = match self.analytics.benchmark_price.filter(|price| *price > 0.0) {
+            Some(price) => format!(
+                "{} (≈{:.0} {})",
+                numbers.signed_money(delta),
+                delta / price,
+                exposure::BENCHMARK
+            ),
+            None => numbers.signed_money(delta),
+        };
+
+        let summary = div()
+            .flex()
+            .flex_wrap()
+            .gap_6()
+            .text_sm()
+            .child(self.render_account_stat(
+                "Long".to_string(),
+                of_equity(exposure.long),
+                rgb(0x3fb950),
+            ))
+            .child(self.render_account_stat(
+                "Short".to_string(),
+                of_equity(exposure.short),
+                rgb(0xff4444),
+            ))
+            .child(self.render_account_stat(
+                "Net".to_string(),
+                of_equity(exposure.net),
+                pnl_color(exposure.net),
+            ))
+            .child(self.render_account_stat(
+                "Gross".to_string(),
+                of_equity(exposure.gross),
+                rgb(0x58a6ff),
+            ))
+            .child(self.render_account_stat(
+                format!("β-Weighted Δ ({})", exposure::BENCHMARK),
+                delta_text,
+                pnl_color(delta),
+            ))
+            .when(unweighted > 0, |this| {
+                this.child(self.render_account_stat(
+                    "Without Beta".to_string(),
+                    format!("{} positions", unweighted),
+                    rgb(0x8b949e),
+                ))
+            });
+
+        let body = if positions.is_empty() {
+            div()
+                .grid()
+                .items_center()
+                .justify_center()
+                .p_6()
+                .text_color(rgb(0x8b949e))
+                .child("No open positions")
+        } else if let Some(error) = &self.analytics.error {
+            div()
+                .grid()
+                .items_center()
+                .justify_center()
+                .p_6()
+                .text_color(rgb(0xff4444))
+                .child(error.clone())
+        } else {
+            let header_cell = |label: &'static str, width: f32| {
+                div()
+                    .w(px(width))
+                    .text_xs()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0x8b949e))
+                    .child(label)
+            };
+
+            div()
+                .grid()
+                .grid_cols(1)
+                .gap_2()
+                .child(
+                    // Table header
+                    div()
+                        .flex()
+                        .gap_4()
+                        .pb_2()
+                        .border_b_1()
+                        .border_color(rgb(0x30363d))
+                        .child(header_cell("Symbol", 80.0))
+                        .child(header_cell("Market Value", 120.0))
+                        .child(header_cell("Beta", 80.0))
+                        .child(header_cell("β-Weighted", 120.0)),
+                )
+                .child(
+                    self.render_scrollable_rows(
+                        "analytics-rows",
+                        positions
+                            .iter()
+                            .map(|position| {
+                                let value = position.market_value.parse::<f64>().ok();
+                                let beta = self.analytics.beta(&position.symbol);
+                                div()
+                                    .flex()
+                                    .gap_4()
+                                    .py_1()
+                                    .text_sm()
+                                    .text_color(rgb(0xffffff))
                                     .child(
-                                        // Timeframe selector
                                         div()
-                                            .flex()
-                                            .flex_col()
-                                            .gap_2()
-                                            .child(
-                                                div()
-                                                    .text_sm()
-                                                    .font_weight(FontWeight::SEMIBOLD)
-                                                    .text_color(rgb(0xffffff))
-                                                    .child("Timeframe:"),
-                                            )
-                                            .child(
-                                                div()
-                                                    .flex()
-                                                    .gap_2()
-                                                    .child(
-                                                        self.render_timeframe_button(
-                                                            "1Min", "1m", cx,
-                                                        ),
-                                                    )
-                                                    .child(
-                                                        self.render_timeframe_button(
-                                                            "5Min", "5m", cx,
-                                                        ),
-                                                    )
-                                                    .child(self.render_timeframe_button(
-                                                        "15Min", "15m", cx,
-                                                    ))
-                                                    .child(
-                                                        self.render_timeframe_button(
-                                                            "1Hour", "1h", cx,
-                                                        ),
-                                                    )
-                                                    .child(
-                                                        self.render_timeframe_button(
-                                                            "1Day", "1D", cx,
-                                                        ),
-                                                    )
-                                                    .child(
-                                                        self.render_timeframe_button(
-                                                            "1Week", "1W", cx,
-                                                        ),
-                                                    )
-                                                    .child(self.render_timeframe_button(
-                                                        "1Month", "1M", cx,
-                                                    )),
-                                            ),
+                                            .w(px(80.0))
+                                            .font_weight(FontWeight::SEMIBOLD)
+                                            .child(position.symbol.clone()),
+                                    )
+                                    .child(div().w(px(120.0)).child(
+                                        value.map(|v| numbers.money(v)).unwrap_or("-".to_string()),
+                                    ))
+                                    .child(div().w(px(80.0)).child(match beta {
+                                        Some(beta) => format!("{:.2}", beta),
+                                        None if self.analytics.loading => "...".to_string(),
+                                        None => "-".to_string(),
+                                    }))
+                                    .child(
+                                        div().w(px(120.0)).child(
+                                            value
+                                                .zip(beta)
+                                                .map(|(value, beta)| {
+                                                    numbers.signed_money(value * beta)
+                                                })
+                                                .unwrap_or("-".to_string()),
+                                        ),
+                                    )
+                            })
+                            .collect(),
+                        cx,
+                    ),
+                )
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(summary)
+            .child(div().text_xs().text_color(rgb(0x8b949e)).child(format!(
+                "Betas from {} daily returns against {}",
+                exposure::BETA_BARS - 1,
+                exposure::BENCHMARK
+            )))
+            .child(body)
+    }
+
+    fn render_log_tab(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let entries = logging::recent_entries(&self.log_filter);
+
+        let level_chips = logging::LOG_LEVELS
+            .iter()
+            .map(|&level| {
+                self.render_filter_chip(
+                    format!("log-level-{}", level),
+                    logging::level_label(level),
+                    self.log_filter.level == level,
+                    cx,
+                    move |this, _| this.log_filter.level = level,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let module_chips = std::iter::once(None)
+            .chain(logging::logged_modules().into_iter().map(Some))
+            .map(|module| {
+                self.render_filter_chip(
+                    format!("log-module-{}", module.unwrap_or("all")),
+                    module.unwrap_or("All"),
+                    self.log_filter.module == module,
+                    cx,
+                    move |this, _| this.log_filter.module = module,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let controls = div()
+            .flex()
+            .items_center()
+            .gap_4()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(rgb(0x8b949e))
+                            .mr_1()
+                            .child("Level"),
+                    )
+                    .children(level_chips),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .items_center()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(rgb(0x8b949e))
+                            .mr_1()
+                            .child("Module"),
+                    )
+                    .children(module_chips),
+            );
+
+        let body = if entries.is_empty() {
+            div()
+                .grid()
+                .items_center()
+                .justify_center()
+                .p_6()
+                .text_color(rgb(0x8b949e))
+                .child("No log entries")
+        } else {
+            div().child(
+                self.render_scrollable_rows(
+                    "log-rows",
+                    entries
+                        .iter()
+                        .map(|entry| {
+                            let level_color = match entry.level {
+                                tracing::Level::ERROR => rgb(0xff4444),
+                                tracing::Level::WARN => rgb(0xf2cc60),
+                                tracing::Level::INFO => rgb(0x58a6ff),
+                                _ => rgb(0x8b949e),
+                            };
+                            div()
+                                .flex()
+                                .gap_4()
+                                .py_1()
+                                .text_xs()
+                                .text_color(rgb(0x8b949e))
+                                .child(
+                                    div()
+                                        .w(px(70.0))
+                                        .flex_shrink_0()
+                                        .child(entry.timestamp.format("%H:%M:%S").to_string()),
+                                )
+                                .child(
+                                    div()
+                                        .w(px(50.0))
+                                        .flex_shrink_0()
+                                        .text_color(level_color)
+                                        .child(logging::level_label(entry.level)),
+                                )
+                                .child(
+                                    div()
+                                        .w(px(130.0))
+                                        .flex_shrink_0()
+                                        .overflow_hidden()
+                                        .child(logging::module_label(entry.target)),
+                                )
+                                .child(
+                                    div()
+                                        .flex_1()
+                                        .text_color(rgb(0xffffff))
+                                        .child(entry.message.clone()),
+                                )
+                        })
+                        .collect(),
+                    cx,
+                ),
+            )
+        };
+
+        let log_location = logging::log_dir()
+            .map(|dir| format!("Log files: {}", dir.display()))
+            .unwrap_or_else(|| "Log files unavailable".to_string());
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(controls)
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_3()
+                            .child(div().text_xs().text_color(rgb(0x8b949e)).child(format!(
+                                "{} entries · {}",
+                                entries.len(),
+                                log_location
+                            )))
+                            .child(
+                                div()
+                                    .id("log-clear")
+                                    .px_2()
+                                    .py_1()
+                                    .bg(rgb(0x21262d))
+                                    .rounded_md()
+                                    .text_xs()
+                                    .text_color(rgb(0x8b949e))
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(0x30363d)))
+                                    .child("Clear")
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        logging::clear_entries();
+                                        this.log_filter.module = None;
+                                        cx.notify();
+                                    })),
+                            ),
+                    ),
+            )
+            .child(body)
+    }
+
+    fn render_history_tab(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let filtered_orders = self.account.filtered_order_history();
+
+        let controls = div()
+            .flex()
+            .items_center()
+            .gap_4()
+            .child(
+                // Status filter dropdown
+                div()
+                    .relative()
+                    .child(
+                        div()
+                            .id("history-status-dropdown")
+                            .px_3()
+                            .py_1()
+                            .min_w(px(120.0))
+                            .bg(rgb(0x0d1117))
+                            .border_1()
+                            .border_color(rgb(0x30363d))
+                            .rounded_md()
+                            .text_xs()
+                            .text_color(rgb(0xffffff))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x21262d)))
+                            .child(format!(
+                                "Status: {} ▾",
+                                self.account.history_status_filter.label()
+                            ))
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.account.history_status_dropdown_open =
+                                    !this.account.history_status_dropdown_open;
+                                cx.notify();
+                            })),
+                    )
+                    .when(self.account.history_status_dropdown_open, |this| {
+                        this.child(
+                            div()
+                                .absolute()
+                                .top(px(28.0))
+                                .left_0()
+                                .min_w(px(120.0))
+                                .flex()
+                                .flex_col()
+                                .bg(rgb(0x161b22))
+                                .border_1()
+                                .border_color(rgb(0x30363d))
+                                .rounded_md()
+                                .shadow_lg()
+                                .children(account::HistoryStatusFilter::ALL.iter().map(|filter| {
+                                    let filter = *filter;
+                                    div()
+                                        .id(ElementId::Name(
+                                            format!("history-status-{}", filter.label()).into(),
+                                        ))
+                                        .px_3()
+                                        .py_1()
+                                        .text_xs()
+                                        .text_color(
+                                            if self.account.history_status_filter == filter {
+                                                rgb(0x58a6ff)
+                                            } else {
+                                                rgb(0xffffff)
+                                            },
+                                        )
+                                        .cursor_pointer()
+                                        .hover(|style| style.bg(rgb(0x30363d)))
+                                        .child(filter.label())
+                                        .on_click(cx.listener(move |this, _, _, cx| {
+                                            this.account.history_status_filter = filter;
+                                            this.account.history_status_dropdown_open = false;
+                                            cx.notify();
+                                        }))
+                                })),
+                        )
+                    }),
+            )
+            .child(
+                // Date range selector
+                div()
+                    .flex()
+                    .gap_1()
+                    .children(account::HistoryRange::ALL.iter().map(|range| {
+                        let range = *range;
+                        let is_selected = self.account.history_range == range;
+                        div()
+                            .id(ElementId::Name(
+                                format!("history-range-{}", range.label()).into(),
+                            ))
+                            .px_2()
+                            .py_1()
+                            .rounded_md()
+                            .text_xs()
+                            .cursor_pointer()
+                            .bg(if is_selected {
+                                rgb(0x1f6feb)
+                            } else {
+                                rgb(0x21262d)
+                            })
+                            .text_color(if is_selected {
+                                rgb(0xffffff)
+                            } else {
+                                rgb(0x8b949e)
+                            })
+                            .hover(|style| style.bg(rgb(0x30363d)))
+                            .child(range.label())
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                if this.account.history_range != range {
+                                    this.account.history_range = range;
+                                    this.fetch_order_history(cx);
+                                }
+                            }))
+                    })),
+            )
+            .child(
+                // Symbol filter input
+                div()
+                    .id("history-symbol-filter")
+                    .px_3()
+                    .py_1()
+                    .min_w(px(100.0))
+                    .bg(if self.focus.is_focused(focus::InputField::HistorySymbol) {
+                        rgb(0x1f2937)
+                    } else {
+                        rgb(0x0d1117)
+                    })
+                    .border_1()
+                    .border_color(if self.focus.is_focused(focus::InputField::HistorySymbol) {
+                        rgb(0x1f6feb)
+                    } else {
+                        rgb(0x30363d)
+                    })
+                    .rounded_md()
+                    .text_xs()
+                    .text_color(rgb(0xffffff))
+                    .cursor_text()
+                    .child(if self.focus.is_focused(focus::InputField::HistorySymbol) {
+                        format!("{}|", self.account.history_symbol_filter)
+                    } else if self.account.history_symbol_filter.is_empty() {
+                        "Filter symbol...".to_string()
+                    } else {
+                        self.account.history_symbol_filter.clone()
+                    })
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.set_input_focus(Some(focus::InputField::HistorySymbol));
+                        window.focus(&this.focus_handle);
+                        cx.notify();
+                    })),
+            )
+            .child(div().text_xs().text_color(rgb(0x8b949e)).child(format!(
+                "{} of {} orders",
+                filtered_orders.len(),
+                self.account.order_history.len()
+            )))
+            .child(
+                div()
+                    .id("history-export")
+                    .px_2()
+                    .py_1()
+                    .rounded_md()
+                    .bg(rgb(0x21262d))
+                    .text_xs()
+                    .text_color(rgb(0x58a6ff))
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgb(0x30363d)).text_color(rgb(0xffffff)))
+                    .child("⇩ Export CSV")
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.export_order_history(cx);
+                    })),
+            );
+
+        let body = if self.account.order_history_loading {
+            div()
+                .grid()
+                .items_center()
+                .justify_center()
+                .p_6()
+                .text_color(rgb(0x8b949e))
+                .child("Loading order history...")
+        } else if filtered_orders.is_empty() {
+            div()
+                .grid()
+                .items_center()
+                .justify_center()
+                .p_6()
+                .text_color(rgb(0x8b949e))
+                .child("No orders match the selected filters")
+        } else {
+            let header_cell = |label: &'static str, width: f32| {
+                div()
+                    .w(px(width))
+                    .text_xs()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0x8b949e))
+                    .child(label)
+            };
+
+            div()
+                .grid()
+                .grid_cols(1)
+                .gap_2()
+                .child(
+                    // Table header
+                    div()
+                        .flex()
+                        .gap_4()
+                        .pb_2()
+                        .border_b_1()
+                        .border_color(rgb(0x30363d))
+                        .child(header_cell("Symbol", 80.0))
+                        .child(header_cell("Side", 60.0))
+                        .child(header_cell("Qty", 80.0))
+                        .child(header_cell("Filled", 80.0))
+                        .child(header_cell("Avg Fill", 100.0))
+                        .child(header_cell("Type", 80.0))
+                        .child(header_cell("Status", 100.0))
+                        .child(header_cell("Created At", 150.0)),
+                )
+                .child(
+                    self.render_scrollable_rows(
+                        "history-rows",
+                        filtered_orders
+                            .into_iter()
+                            .enumerate()
+                            .map(|(idx, order)| {
+                                let side_color = if order.side.to_lowercase().contains("buy") {
+                                    rgb(0x3fb950)
+                                } else {
+                                    rgb(0xff4444)
+                                };
+
+                                let status_color = match order.status.to_lowercase().as_str() {
+                                    s if s.contains("filled") => rgb(0x3fb950),
+                                    s if s.contains("canceled") || s.contains("rejected") => {
+                                        rgb(0xff4444)
+                                    }
+                                    _ => rgb(0x8b949e),
+                                };
+                                let is_selected =
+                                    self.account.selected_order_id.as_deref() == Some(&order.id);
+
+                                div()
+                                    .id(ElementId::Name(format!("history-row-{}", idx).into()))
+                                    .flex()
+                                    .gap_4()
+                                    .py_2()
+                                    .text_sm()
+                                    .rounded_md()
+                                    .cursor_pointer()
+                                    .when(is_selected, |this| this.bg(rgb(0x21262d)))
+                                    .hover(|style| style.bg(rgb(0x21262d)))
+                                    .on_click({
+                                        let order_id = order.id.clone();
+                                        cx.listener(move |this, _, _, cx| {
+                                            this.account.toggle_selected_order(&order_id);
+                                            cx.notify();
+                                        })
+                                    })
+                                    .child(
+                                        div()
+                                            .w(px(80.0))
+                                            .text_color(rgb(0xffffff))
+                                            .child(order.symbol.clone()),
+                                    )
+                                    .child(
+                                        div()
+                                            .w(px(60.0))
+                                            .font_weight(FontWeight::SEMIBOLD)
+                                            .text_color(side_color)
+                                            .child(order.side.clone()),
+                                    )
+                                    .child(
+                                        div()
+                                            .w(px(80.0))
+                                            .text_color(rgb(0x8b949e))
+                                            .child(order.qty.clone()),
+                                    )
+                                    .child(
+                                        div()
+                                            .w(px(80.0))
+                                            .text_color(rgb(0x8b949e))
+                                            .child(order.filled_qty.clone()),
+                                    )
+                                    .child(
+                                        div().w(px(100.0)).text_color(rgb(0x8b949e)).child(
+                                            order
+                                                .filled_avg_price
+                                                .as_ref()
+                                                .map(|p| self.config.numbers.format.price_text(p))
+                                                .unwrap_or("-".to_string()),
+                                        ),
+                                    )
+                                    .child(
+                                        div()
+                                            .w(px(80.0))
+                                            .text_color(rgb(0x8b949e))
+                                            .child(order.order_type.clone()),
+                                    )
+                                    .child(
+                                        div()
+                                            .w(px(100.0))
+                                            .font_weight(FontWeight::SEMIBOLD)
+                                            .text_color(status_color)
+                                            .child(order.status.clone()),
+                                    )
+                                    .child(
+                                        div()
+                                            .w(px(150.0))
+                                            .text_color(rgb(0x8b949e))
+                                            .child(order.created_at.clone()),
+                                    )
+                            })
+                            .collect::<Vec<_>>(),
+                        cx,
+                    ),
+                )
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(controls)
+            .child(body)
+            .when_some(self.account.selected_order(), |this, order| {
+                this.child(self.render_order_detail(order, cx))
+            })
+    }
+
+    fn render_position_detail(
+        &self,
+        pos: &account::Position,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let lots = self.account.lot_ledger.lots_for(&pos.symbol);
+        let closed_trades = self.account.lot_ledger.closed_trades_for(&pos.symbol);
+        let current_price = pos.current_price.parse::<f64>().unwrap_or(0.0);
+        let lot_qty: f64 = lots.iter().map(|lot| lot.qty).sum();
+        let lot_cost_basis: f64 = lots.iter().map(|lot| lot.cost_basis()).sum();
+        let realized_value = self.account.lot_ledger.realized_pnl(&pos.symbol);
+        let position_qty = pos.qty.parse::<f64>().unwrap_or(0.0);
+        let numbers = &self.config.numbers.format;
+
+        let header_cell = |label: &'static str, width: f32| {
+            div()
+                .w(px(width))
+                .text_xs()
+                .font_weight(FontWeight::SEMIBOLD)
+                .text_color(rgb(0x8b949e))
+                .child(label)
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .p_3()
+            .bg(rgb(0x0d1117))
+            .border_1()
+            .border_color(rgb(0x30363d))
+            .rounded_md()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_3()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(rgb(0xffffff))
+                            .child(format!("{} cost-basis lots", pos.symbol)),
+                    )
+                    .child(self.render_copy_button(
+                        "position-detail-copy",
+                        "Copy Summary",
+                        pos.summary(),
+                        "Position summary",
+                        cx,
+                    ))
+                    .child(
+                        div()
+                            .id("position-detail-close")
+                            .ml_auto()
+                            .px_2()
+                            .text_sm()
+                            .text_color(rgb(0x8b949e))
+                            .cursor_pointer()
+                            .hover(|style| style.text_color(rgb(0xffffff)))
+                            .child("✕")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.account.selected_position = None;
+                                cx.notify();
+                            })),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .gap_6()
+                    .text_sm()
+                    .child(self.render_account_stat(
+                        "Cost Basis".to_string(),
+                        numbers.money(lot_cost_basis),
+                        rgb(0xffffff),
+                    ))
+                    .child(self.render_account_stat(
+                        "Avg Entry".to_string(),
+                        numbers.price_text(&pos.avg_entry_price),
+                        rgb(0xffffff),
+                    ))
+                    .child(self.render_account_stat(
+                        "Unrealized P&L".to_string(),
+                        numbers.money_text(&pos.unrealized_pl),
+                        pnl_color(pos.unrealized_pl.parse::<f64>().unwrap_or(0.0)),
+                    ))
+                    .child(self.render_account_stat(
+                        "Realized P&L".to_string(),
+                        numbers.money(realized_value),
+                        pnl_color(realized_value),
+                    )),
+            )
+            .when(!self.account.activities_complete, |this| {
+                this.child(self.render_ledger_source("Lots"))
+            })
+            .when((lot_qty - position_qty).abs() > 1e-6, |this| {
+                // Transfers and partial history leave shares without a matched fill
+                this.child(div().text_xs().text_color(rgb(0xf2cc60)).child(format!(
+                    "⚠️ Lots from the loaded fills cover {} of {} shares",
+                    lot_qty, pos.qty
+                )))
+            })
+            .child(
+                div()
+                    .flex()
+                    .gap_4()
+                    .pb_1()
+                    .border_b_1()
+                    .border_color(rgb(0x30363d))
+                    .child(header_cell("Opened", 140.0))
+                    .child(header_cell("Qty", 80.0))
+                    .child(header_cell("Price", 100.0))
+                    .child(header_cell("Cost Basis", 100.0))
+                    .child(header_cell("Held", 80.0))
+                    .child(header_cell("Unrealized", 100.0)),
+            )
+            .when(lots.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(rgb(0x8b949e))
+                        .child("No open lots found in recent fills"),
+                )
+            })
+            .children(lots.iter().map(|lot| {
+                let unrealized = (current_price - lot.price) * lot.qty;
+                let held = lot.holding_period();
+
+                div()
+                    .flex()
+                    .gap_4()
+                    .text_xs()
+                    .text_color(rgb(0xffffff))
+                    .child(
+                        div()
+                            .w(px(140.0))
+                            .text_color(rgb(0x8b949e))
+                            .child(lot.opened_at.format("%Y-%m-%d %H:%M").to_string()),
+                    )
+                    .child(div().w(px(80.0)).child(format!("{}", lot.qty)))
+                    .child(div().w(px(100.0)).child(numbers.price(lot.price)))
+                    .child(div().w(px(100.0)).child(numbers.money(lot.cost_basis())))
+                    .child(div().w(px(80.0)).text_color(rgb(0x8b949e)).child(
+                        if held.num_days() > 0 {
+                            format!("{}d", held.num_days())
+                        } else {
+                            format!("{}h", held.num_hours())
+                        },
+                    ))
+                    .child(
+                        div()
+                            .w(px(100.0))
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(pnl_color(unrealized))
+                            .child(numbers.money(unrealized)),
+                    )
+            }))
+            .when(!closed_trades.is_empty(), |this| {
+                this.child(
+                    div()
+                        .pt_2()
+                        .text_xs()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .text_color(rgb(0x8b949e))
+                        .child("Closed Lots"),
+                )
+                .children(closed_trades.iter().map(|trade| {
+                    div()
+                        .flex()
+                        .gap_4()
+                        .text_xs()
+                        .text_color(rgb(0xffffff))
+                        .child(
+                            div()
+                                .w(px(140.0))
+                                .text_color(rgb(0x8b949e))
+                                .child(trade.closed_at.format("%Y-%m-%d %H:%M").to_string()),
+                        )
+                        .child(div().w(px(80.0)).child(format!(
+                            "{} {}",
+                            if trade.is_long { "Long" } else { "Short" },
+                            trade.qty
+                        )))
+                        .child(div().w(px(100.0)).child(numbers.price(trade.entry_price)))
+                        .child(div().w(px(100.0)).child(numbers.price(trade.exit_price)))
+                        .child(div().w(px(80.0)).text_color(rgb(0x8b949e)).child(format!(
+                            "{}d",
+                            (trade.closed_at - trade.opened_at).num_days()
+                        )))
+                        .child(
+                            div()
+                                .w(px(100.0))
+                                .font_weight(FontWeight::SEMIBOLD)
+                                .text_color(pnl_color(trade.pnl))
+                                .child(numbers.money(trade.pnl)),
+                        )
+                }))
+            })
+    }
+
+    fn render_order_detail(
+        &self,
+        order: &account::Order,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let events = self
+            .account
+            .order_events
+            .get(&order.id)
+            .map(|events| events.as_slice())
+            .unwrap_or(&[]);
+        let time_or_dash = |t: &Option<String>| t.clone().unwrap_or("-".to_string());
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .p_3()
+            .bg(rgb(0x0d1117))
+            .border_1()
+            .border_color(rgb(0x30363d))
+            .rounded_md()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_3()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(rgb(0xffffff))
+                            .child(format!(
+                                "{} {} {} {}",
+                                order.side, order.qty, order.symbol, order.order_type
+                            )),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(rgb(0x8b949e))
+                            .child(order.id.clone()),
+                    )
+                    .when(self.auto_exits.exit_ids.contains(&order.id), |this| {
+                        this.child(
+                            div()
+                                .px_1()
+                                .rounded_sm()
+                                .bg(rgb(0x1f6feb))
+                                .text_xs()
+                                .text_color(rgb(0xffffff))
+                                .child("🎯 Auto exit"),
+                        )
+                    })
+                    .child(self.render_copy_button(
+                        "order-detail-copy-id",
+                        "Copy ID",
+                        order.id.clone(),
+                        "Order ID",
+                        cx,
+                    ))
+                    .child(self.render_copy_button(
+                        "order-detail-copy-summary",
+                        "Copy Summary",
+                        order.summary(),
+                        "Order summary",
+                        cx,
+                    ))
+                    .child(
+                        div()
+                            .id("order-detail-close")
+                            .ml_auto()
+                            .px_2()
+                            .text_sm()
+                            .text_color(rgb(0x8b949e))
+                            .cursor_pointer()
+                            .hover(|style| style.text_color(rgb(0xffffff)))
+                            .child("✕")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.account.selected_order_id = None;
+                                cx.notify();
+                            })),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .gap_6()
+                    .text_sm()
+                    .child(self.render_account_stat(
+                        "Status".to_string(),
+                        order.status.clone(),
+                        rgb(0xffffff),
+                    ))
+                    .child(self.render_account_stat(
+                        "Filled Qty".to_string(),
+                        format!("{} / {}", order.filled_qty, order.qty),
+                        rgb(0xffffff),
+                    ))
+                    .child(
+                        self.render_account_stat(
+                            "Avg Fill Price".to_string(),
+                            order
+                                .filled_avg_price
+                                .as_ref()
+                                .map(|p| self.config.numbers.format.price_text(p))
+                                .unwrap_or("-".to_string()),
+                            rgb(0xffffff),
+                        ),
+                    )
+                    .child(
+                        self.render_account_stat(
+                            "Limit Price".to_string(),
+                            order
+                                .limit_price
+                                .as_ref()
+                                .map(|p| self.config.numbers.format.price_text(p))
+                                .unwrap_or("-".to_string()),
+                            rgb(0xffffff),
+                        ),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .gap_6()
+                    .text_sm()
+                    .child(self.render_account_stat(
+                        "Created".to_string(),
+                        order.created_at.clone(),
+                        rgb(0x8b949e),
+                    ))
+                    .child(self.render_account_stat(
+                        "Submitted".to_string(),
+                        time_or_dash(&order.submitted_at),
+                        rgb(0x8b949e),
+                    ))
+                    .child(self.render_account_stat(
+                        "Updated".to_string(),
+                        time_or_dash(&order.updated_at),
+                        rgb(0x8b949e),
+                    ))
+                    .child(self.render_account_stat(
+                        "Filled".to_string(),
+                        time_or_dash(&order.filled_at),
+                        rgb(0x8b949e),
+                    )),
+            )
+            .when(!order.legs.is_empty(), |this| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .child(
+                            div()
+                                .text_xs()
+                                .font_weight(FontWeight::SEMIBOLD)
+                                .text_color(rgb(0x8b949e))
+                                .child("Legs"),
+                        )
+                        .children(order.legs.iter().map(|leg| {
+                            div()
+                                .flex()
+                                .gap_4()
+                                .text_xs()
+                                .text_color(rgb(0xffffff))
+                                .child(div().w(px(60.0)).child(leg.side.clone()))
+                                .child(div().w(px(80.0)).child(leg.order_type.clone()))
+                                .child(div().w(px(80.0)).child(leg.qty.clone()))
+                                .child(
+                                    div()
+                                        .w(px(100.0))
+                                        .child(leg.limit_price.clone().unwrap_or("-".to_string())),
+                                )
+                                .child(
+                                    div().w(px(100.0)).child(
+                                        leg.stop_price
+                                            .as_ref()
+                                            .map(|stop| format!("stop {}", stop))
+                                            .unwrap_or("-".to_string()),
+                                    ),
+                                )
+                                .child(div().text_color(rgb(0x8b949e)).child(leg.status.clone()))
+                        })),
+                )
+            })
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_xs()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(rgb(0x8b949e))
+                            .child("Stream Events"),
+                    )
+                    .when(events.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .text_xs()
+                                .text_color(rgb(0x8b949e))
+                                .child("No trade updates received this session"),
+                        )
+                    })
+                    .children(events.iter().map(|event| {
+                        div()
+                            .flex()
+                            .gap_4()
+                            .text_xs()
+                            .child(
+                                div()
+                                    .w(px(80.0))
+                                    .text_color(rgb(0x8b949e))
+                                    .child(event.received_at.format("%H:%M:%S").to_string()),
+                            )
+                            .child(
+                                div()
+                                    .w(px(120.0))
+                                    .text_color(rgb(0x58a6ff))
+                                    .child(event.event.clone()),
+                            )
+                            .child(
+                                div()
+                                    .w(px(100.0))
+                                    .text_color(rgb(0xffffff))
+                                    .child(event.status.clone()),
+                            )
+                            .child(div().text_color(rgb(0x8b949e)).child(format!(
+                                "filled {} @ {}",
+                                event.filled_qty,
+                                event.filled_avg_price.clone().unwrap_or("-".to_string())
+                            )))
+                    })),
+            )
+    }
+
+    /// Lots matched FIFO from the loaded fills: cost basis, proceeds, gain and holding
+    /// period class, closed lots first
+    fn render_tax_lots_tab(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let numbers = &self.config.numbers.format;
+        let lots = tax::report(&self.account.lot_ledger);
+        let realized = |term: tax::Term| -> f64 {
+            lots.iter()
+                .filter(|lot| lot.term() == term)
+                .filter_map(|lot| lot.gain())
+                .sum()
+        };
+        let short_term = realized(tax::Term::Short);
+        let long_term = realized(tax::Term::Long);
+        let exportable = self.account.activities_complete;
+
+        let summary = div()
+            .flex()
+            .items_center()
+            .gap_6()
+            .text_sm()
+            .child(self.render_account_stat(
+                "Short-term Gain".to_string(),
+                numbers.signed_money(short_term),
+                pnl_color(short_term),
+            ))
+            .child(self.render_account_stat(
+                "Long-term Gain".to_string(),
+                numbers.signed_money(long_term),
+                pnl_color(long_term),
+            ))
+            .child(
+                div()
+                    .id("tax-lots-export")
+                    .ml_auto()
+                    .px_2()
+                    .py_1()
+                    .rounded_md()
+                    .bg(rgb(0x21262d))
+                    .text_xs()
+                    .when(exportable, |this| {
+                        this.text_color(rgb(0x58a6ff))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x30363d)).text_color(rgb(0xffffff)))
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.export_tax_lots(cx);
+                            }))
+                    })
+                    .when(!exportable, |this| this.text_color(rgb(0x6e7681)))
+                    .child("⇩ Export CSV"),
+            );
+
+        let body = if self.account.activities_loading {
+            div()
+                .grid()
+                .items_center()
+                .justify_center()
+                .p_6()
+                .text_color(rgb(0x8b949e))
+                .child("Loading fills...")
+        } else if lots.is_empty() {
+            div()
+                .grid()
+                .items_center()
+                .justify_center()
+                .p_6()
+                .text_color(rgb(0x8b949e))
+                .child("No fills in the loaded activity history")
+        } else {
+            let header_cell = |label: &'static str, width: f32| {
+                div()
+                    .w(px(width))
+                    .text_xs()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0x8b949e))
+                    .child(label)
+            };
+            let amount = |value: Option<f64>| {
+                value
+                    .map(|value| numbers.money(value))
+                    .unwrap_or("-".to_string())
+            };
+
+            div()
+                .grid()
+                .grid_cols(1)
+                .gap_2()
+                .child(
+                    // Table header
+                    div()
+                        .flex()
+                        .gap_4()
+                        .pb_2()
+                        .border_b_1()
+                        .border_color(rgb(0x30363d))
+                        .child(header_cell("Symbol", 80.0))
+                        .child(header_cell("Side", 60.0))
+                        .child(header_cell("Qty", 80.0))
+                        .child(header_cell("Opened", 100.0))
+                        .child(header_cell("Closed", 100.0))
+                        .child(header_cell("Cost Basis", 110.0))
+                        .child(header_cell("Proceeds", 110.0))
+                        .child(header_cell("Gain", 110.0))
+                        .child(header_cell("Held", 70.0))
+                        .child(header_cell("Term", 90.0)),
+                )
+                .child(
+                    self.render_scrollable_rows(
+                        "tax-lot-rows",
+                        lots.iter()
+                            .map(|lot| {
+                                let gain = lot.gain();
+                                div()
+                                    .flex()
+                                    .gap_4()
+                                    .py_1()
+                                    .text_sm()
+                                    .text_color(rgb(0x8b949e))
+                                    .child(
+                                        div()
+                                            .w(px(80.0))
+                                            .font_weight(FontWeight::SEMIBOLD)
+                                            .text_color(rgb(0xffffff))
+                                            .child(lot.symbol.clone()),
                                     )
+                                    .child(div().w(px(60.0)).child(if lot.is_long {
+                                        "Long"
+                                    } else {
+                                        "Short"
+                                    }))
+                                    .child(div().w(px(80.0)).child(lot.qty.to_string()))
                                     .child(
-                                        // Bar limit input
                                         div()
-                                            .flex()
-                                            .flex_col()
-                                            .gap_2()
-                                            .child(
-                                                div()
-                                                    .text_sm()
-                                                    .font_weight(FontWeight::SEMIBOLD)
-                                                    .text_color(rgb(0xffffff))
-                                                    .child("Bars:"),
+                                            .w(px(100.0))
+                                            .child(lot.opened_at.format("%Y-%m-%d").to_string()),
+                                    )
+                                    .child(div().w(px(100.0)).child(match lot.closed_at {
+                                        Some(at) => at.format("%Y-%m-%d").to_string(),
+                                        None => "Open".to_string(),
+                                    }))
+                                    .child(div().w(px(110.0)).child(amount(lot.cost_basis)))
+                                    .child(div().w(px(110.0)).child(amount(lot.proceeds)))
+                                    .child(
+                                        div()
+                                            .w(px(110.0))
+                                            .text_color(
+                                                gain.map(pnl_color).unwrap_or(rgb(0x8b949e)),
                                             )
                                             .child(
-                                                div()
-                                                    .id("bar-limit-input")
-                                                    .px_4()
-                                                    .py_2()
-                                                    .bg(if self.chart.bar_limit_focused {
-                                                        rgb(0x1f2937)
-                                                    } else {
-                                                        rgb(0x161b22)
-                                                    })
-                                                    .border_1()
-                                                    .border_color(if self.chart.bar_limit_focused {
-                                                        rgb(0x1f6feb)
-                                                    } else {
-                                                        rgb(0x30363d)
-                                                    })
-                                                    .rounded_lg()
-                                                    .text_color(rgb(0xffffff))
-                                                    .min_w(px(80.0))
-                                                    .cursor_text()
-                                                    .child(if self.chart.bar_limit_focused {
-                                                        format!("{}|", self.chart.bar_limit)
-                                                    } else if self.chart.bar_limit.is_empty() {
-                                                        "100".to_string()
-                                                    } else {
-                                                        self.chart.bar_limit.clone()
-                                                    })
-                                                    .on_click(cx.listener(
-                                                        |this, _, _window, cx| {
-                                                            this.chart.bar_limit_focused = true;
-                                                            this.chart.input_focused = false;
-                                                            this.account.quantity_focused = false;
-                                                            this.account.price_focused = false;
-                                                            _window.focus(&this.focus_handle);
-                                                            cx.notify();
-                                                        },
-                                                    )),
+                                                gain.map(|gain| numbers.signed_money(gain))
+                                                    .unwrap_or("-".to_string()),
+                                            ),
+                                    )
+                                    .child(
+                                        div().w(px(70.0)).child(format!("{}d", lot.holding_days())),
+                                    )
+                                    .child(div().w(px(90.0)).child(lot.term().label()))
+                            })
+                            .collect(),
+                        cx,
+                    ),
+                )
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(summary)
+            .child(self.render_ledger_source("Lots"))
+            .child(body)
+    }
+
+    /// Win rate, average win/loss, profit factor, expectancy and drawdown of the trades
+    /// closed in the selected period
+    fn render_performance_tab(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let numbers = &self.config.numbers.format;
+        let stats = performance::PerformanceStats::from_trades(
+            &self.account.lot_ledger.closed_trades,
+            self.performance.range,
+        );
+        let curve = performance::equity_curve(
+            &self.account.lot_ledger.closed_trades,
+            self.performance.range,
+        );
+        let money = |value: Option<f64>| {
+            value
+                .map(|value| numbers.signed_money(value))
+                .unwrap_or("-".to_string())
+        };
+        let color = |value: Option<f64>| value.map(pnl_color).unwrap_or(rgb(0x8b949e));
+
+        let controls =
+            div()
+                .flex()
+                .items_center()
+                .gap_1()
+                .children(account::HistoryRange::ALL.iter().map(|range| {
+                    let range = *range;
+                    let is_selected = self.performance.range == range;
+                    div()
+                        .id(ElementId::Name(
+                            format!("performance-range-{}", range.label()).into(),
+                        ))
+                        .px_2()
+                        .py_1()
+                        .rounded_md()
+                        .text_xs()
+                        .cursor_pointer()
+                        .bg(if is_selected {
+                            rgb(0x1f6feb)
+                        } else {
+                            rgb(0x21262d)
+                        })
+                        .text_color(if is_selected {
+                            rgb(0xffffff)
+                        } else {
+                            rgb(0x8b949e)
+                        })
+                        .hover(|style| style.bg(rgb(0x30363d)))
+                        .child(range.label())
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.performance.range = range;
+                            cx.notify();
+                        }))
+                }));
+
+        let body = if self.account.activities_loading {
+            div()
+                .p_6()
+                .text_color(rgb(0x8b949e))
+                .child("Loading fills...")
+        } else if stats.trades == 0 {
+            div()
+                .p_6()
+                .text_color(rgb(0x8b949e))
+                .child("No trades closed in this period")
+        } else {
+            div()
+                .flex()
+                .flex_wrap()
+                .gap_6()
+                .text_sm()
+                .child(self.render_account_stat(
+                    "Trades".to_string(),
+                    format!("{} ({}W / {}L)", stats.trades, stats.wins, stats.losses),
+                    rgb(0xffffff),
+                ))
+                .child(
+                    self.render_account_stat(
+                        "Win Rate".to_string(),
+                        stats
+                            .win_rate()
+                            .map(|rate| format!("{:.1}%", rate))
+                            .unwrap_or("-".to_string()),
+                        rgb(0xffffff),
+                    ),
+                )
+                .child(self.render_account_stat(
+                    "Net P&L".to_string(),
+                    numbers.signed_money(stats.net),
+                    pnl_color(stats.net),
+                ))
+                .child(self.render_account_stat(
+                    "Average Win".to_string(),
+                    money(stats.average_win()),
+                    color(stats.average_win()),
+                ))
+                .child(self.render_account_stat(
+                    "Average Loss".to_string(),
+                    money(stats.average_loss()),
+                    color(stats.average_loss()),
+                ))
+                .child(self.render_account_stat(
+                    "Profit Factor".to_string(),
+                    match stats.profit_factor() {
+                        Some(factor) => format!("{:.2}", factor),
+                        None if stats.wins > 0 => "∞".to_string(),
+                        None => "-".to_string(),
+                    },
+                    rgb(0xffffff),
+                ))
+                .child(self.render_account_stat(
+                    "Expectancy".to_string(),
+                    money(stats.expectancy()),
+                    color(stats.expectancy()),
+                ))
+                .child(self.render_account_stat(
+                    "Max Drawdown".to_string(),
+                    numbers.money(stats.max_drawdown),
+                    if stats.max_drawdown > 0.0 {
+                        rgb(0xff4444)
+                    } else {
+                        rgb(0x8b949e)
+                    },
+                ))
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(controls)
+            .child(body)
+            .when(
+                curve.len() >= 2 && !self.account.activities_loading,
+                |this| this.child(self.render_equity_curve(&curve)),
+            )
+            .child(self.render_ledger_source("Round trips"))
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child("Drawdown is measured on cumulative realized P&L"),
+            )
+    }
+
+    /// Where lots and round trips come from, with a warning while the activity history
+    /// is partial (positions opened before it have no opening lot)
+    fn render_ledger_source(&self, subject: &str) -> impl IntoElement {
+        let count = self.account.activities.len();
+        if self.account.activities_complete {
+            div().text_xs().text_color(rgb(0x8b949e)).child(format!(
+                "{} matched first-in, first-out from all {} account activities",
+                subject, count
+            ))
+        } else {
+            div().text_xs().text_color(rgb(0xf2cc60)).child(format!(
+                "⚠️ Activity history is incomplete ({} most recent activities loaded): {} opened \
+                 before it are missing, so cost basis, dates and totals may be wrong",
+                count,
+                subject.to_lowercase()
+            ))
+        }
+    }
+
+    fn render_backtest_tab(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let numbers = &self.config.numbers.format;
+        let chip = |id: String, label: String, is_selected: bool| {
+            div()
+                .id(ElementId::Name(id.into()))
+                .px_2()
+                .py_1()
+                .rounded_md()
+                .text_xs()
+                .cursor_pointer()
+                .bg(if is_selected {
+                    rgb(0x1f6feb)
+                } else {
+                    rgb(0x21262d)
+                })
+                .text_color(if is_selected {
+                    rgb(0xffffff)
+                } else {
+                    rgb(0x8b949e)
+                })
+                .hover(|style| style.bg(rgb(0x30363d)))
+                .child(label)
+        };
+
+        let controls = div()
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .gap_1()
+            .children(backtest::Strategy::PRESETS.iter().map(|strategy| {
+                let strategy = *strategy;
+                chip(
+                    format!("backtest-strategy-{}", strategy.label()),
+                    strategy.label(),
+                    self.backtest.strategy == strategy,
+                )
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    this.backtest.strategy = strategy;
+                    cx.notify();
+                }))
+            }))
+            .child(div().w(px(12.0)))
+            .children(backtest::BAR_COUNTS.iter().map(|count| {
+                let count = *count;
+                chip(
+                    format!("backtest-bars-{}", count),
+                    format!("{} bars", count),
+                    self.backtest.bar_count == count,
+                )
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    this.backtest.bar_count = count;
+                    cx.notify();
+                }))
+            }))
+            .child(
+                div()
+                    .id("backtest-run")
+                    .ml_2()
+                    .px_3()
+                    .py_1()
+                    .rounded_md()
+                    .bg(rgb(0x238636))
+                    .text_xs()
+                    .text_color(rgb(0xffffff))
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgb(0x2ea043)))
+                    .child(format!(
+                        "▶ Run on {} {}",
+                        self.chart.symbol, self.chart.timeframe
+                    ))
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.run_backtest(cx);
+                    })),
+            );
+
+        let placeholder = |text: String| div().p_6().text_color(rgb(0x8b949e)).child(text);
+        let body = if self.backtest.running {
+            placeholder("Running backtest...".to_string())
+        } else if let Some(error) = &self.backtest.error {
+            div()
+                .p_6()
+                .text_color(rgb(0xff4444))
+                .child(format!("Backtest failed: {}", error))
+        } else if let Some(report) = &self.backtest.report {
+            let stats = performance::PerformanceStats::from_trades(
+                &report.trades,
+                account::HistoryRange::All,
+            );
+            // Marked to each close rather than per round trip
+            let max_drawdown = report
+                .curve
+                .iter()
+                .map(|point| point.drawdown)
+                .fold(0.0, f64::max);
+            let summary = div()
+                .flex()
+                .flex_wrap()
+                .gap_6()
+                .text_sm()
+                .child(self.render_account_stat(
+                    "Net P&L".to_string(),
+                    format!(
+                        "{} ({:+.2}%)",
+                        numbers.signed_money(report.net()),
+                        report.return_percent()
+                    ),
+                    pnl_color(report.net()),
+                ))
+                .child(self.render_account_stat(
+                    "Buy & Hold".to_string(),
+                    format!("{:+.2}%", report.buy_and_hold_percent()),
+                    pnl_color(report.buy_and_hold_percent()),
+                ))
+                .child(self.render_account_stat(
+                    "Trades".to_string(),
+                    format!("{} ({}W / {}L)", stats.trades, stats.wins, stats.losses),
+                    rgb(0xffffff),
+                ))
+                .child(
+                    self.render_account_stat(
+                        "Win Rate".to_string(),
+                        stats
+                            .win_rate()
+                            .map(|rate| format!("{:.1}%", rate))
+                            .unwrap_or("-".to_string()),
+                        rgb(0xffffff),
+                    ),
+                )
+                .child(self.render_account_stat(
+                    "Profit Factor".to_string(),
+                    match stats.profit_factor() {
+                        Some(factor) => format!("{:.2}", factor),
+                        None if stats.wins > 0 => "∞".to_string(),
+                        None => "-".to_string(),
+                    },
+                    rgb(0xffffff),
+                ))
+                .child(self.render_account_stat(
+                    "Max Drawdown".to_string(),
+                    numbers.money(max_drawdown),
+                    if max_drawdown > 0.0 {
+                        rgb(0xff4444)
+                    } else {
+                        rgb(0xffffff)
+                    },
+                ))
+                .child(self.render_account_stat(
+                    "Commission".to_string(),
+                    numbers.money(report.commission_paid),
+                    rgb(0x8b949e),
+                ));
+
+            let comparison = div().text_xs().text_color(rgb(0x58a6ff)).child(format!(
+                "— {} buy-and-hold of {}: {} vs. strategy {}",
+                report.symbol,
+                numbers.money(report.capital),
+                numbers.signed_money(report.buy_and_hold.last().copied().unwrap_or(0.0)),
+                numbers.signed_money(report.net())
+            ));
+
+            let header_cell = |label: &'static str, width: f32| {
+                div()
+                    .w(px(width))
+                    .text_xs()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0x8b949e))
+                    .child(label)
+            };
+            let last_index = report.trades.len().saturating_sub(1);
+            let trades = div()
+                .grid()
+                .grid_cols(1)
+                .gap_2()
+                .child(
+                    div()
+                        .flex()
+                        .gap_4()
+                        .pb_2()
+                        .border_b_1()
+                        .border_color(rgb(0x30363d))
+                        .child(header_cell("Entry", 140.0))
+                        .child(header_cell("Entry Price", 100.0))
+                        .child(header_cell("Exit", 140.0))
+                        .child(header_cell("Exit Price", 100.0))
+                        .child(header_cell("Qty", 70.0))
+                        .child(header_cell("P&L", 110.0))
+                        .child(header_cell("Return", 80.0)),
+                )
+                .child(
+                    self.render_scrollable_rows(
+                        "backtest-trade-rows",
+                        report
+                            .trades
+                            .iter()
+                            .enumerate()
+                            .rev()
+                            .map(|(index, trade)| {
+                                let still_open = report.still_open && index == last_index;
+                                div()
+                                    .flex()
+                                    .gap_4()
+                                    .py_1()
+                                    .text_sm()
+                                    .text_color(rgb(0x8b949e))
+                                    .child(div().w(px(140.0)).child(
+                                        trade.opened_at.format("%Y-%m-%d %H:%M").to_string(),
+                                    ))
+                                    .child(
+                                        div().w(px(100.0)).child(numbers.price(trade.entry_price)),
+                                    )
+                                    .child(div().w(px(140.0)).child(if still_open {
+                                        "Open".to_string()
+                                    } else {
+                                        trade.closed_at.format("%Y-%m-%d %H:%M").to_string()
+                                    }))
+                                    .child(
+                                        div().w(px(100.0)).child(numbers.price(trade.exit_price)),
+                                    )
+                                    .child(div().w(px(70.0)).child(trade.qty.to_string()))
+                                    .child(
+                                        div()
+                                            .w(px(110.0))
+                                            .text_color(pnl_color(trade.pnl))
+                                            .child(numbers.signed_money(trade.pnl)),
+                                    )
+                                    .child(
+                                        div().w(px(80.0)).text_color(pnl_color(trade.pnl)).child(
+                                            format!(
+                                                "{:+.2}%",
+                                                trade.pnl / (trade.entry_price * trade.qty) * 100.0
                                             ),
+                                        ),
+                                    )
+                            })
+                            .collect(),
+                        cx,
+                    ),
+                );
+
+            div()
+                .flex()
+                .flex_col()
+                .gap_3()
+                .child(summary)
+                .when(report.curve.len() >= 2, |this| {
+                    this.child(self.render_curve_panels(
+                        &report.curve,
+                        Some(&report.buy_and_hold),
+                        "Backtest P&L",
+                        comparison,
+                    ))
+                })
+                .child(div().text_xs().text_color(rgb(0x8b949e)).child(format!(
+                    "{} on {} from {} to {}, starting with {}",
+                    report.strategy.label(),
+                    report.symbol,
+                    report.first_at.format("%Y-%m-%d"),
+                    report.last_at.format("%Y-%m-%d"),
+                    numbers.money(report.capital)
+                )))
+                .when(!report.trades.is_empty(), |this| this.child(trades))
+        } else {
+            placeholder("Pick a strategy and run it on the charted symbol's bars".to_string())
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(controls)
+            .child(body)
+            .child(div().text_xs().text_color(rgb(0x8b949e)).child(format!(
+                "Long only, whole shares with all available cash. Signals fill at the next bar's open with {} bps slippage and {} commission per order.",
+                self.config.backtest.slippage_bps,
+                numbers.money(self.config.backtest.commission)
+            )))
+    }
+
+    fn render_simulator_tab(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let numbers = &self.config.numbers.format;
+        let broker = &self.simulator;
+        let mark = |symbol: &str| {
+            self.last_prices
+                .get(symbol)
+                .and_then(|price| price.parse::<f64>().ok())
+        };
+        let equity = broker.equity(&mark);
+        let unrealized: f64 = broker
+            .positions
+            .iter()
+            .filter_map(|position| {
+                mark(&position.symbol).map(|price| (price - position.avg_price) * position.qty)
+            })
+            .sum();
+        let net = equity - broker.starting_cash;
+
+        let action = |id: ElementId, label: &'static str| {
+            div()
+                .id(id)
+                .text_xs()
+                .text_color(rgb(0x58a6ff))
+                .cursor_pointer()
+                .hover(|style| style.text_color(rgb(0xffffff)))
+                .child(label)
+        };
+        let header_cell = |label: &'static str, width: f32| {
+            div()
+                .w(px(width))
+                .text_xs()
+                .font_weight(FontWeight::SEMIBOLD)
+                .text_color(rgb(0x8b949e))
+                .child(label)
+        };
+        let header_row = || {
+            div()
+                .flex()
+                .gap_4()
+                .pb_2()
+                .border_b_1()
+                .border_color(rgb(0x30363d))
+        };
+        let section_title = |title: String| {
+            div()
+                .text_xs()
+                .font_weight(FontWeight::SEMIBOLD)
+                .text_color(rgb(0x8b949e))
+                .child(title)
+        };
+
+        let controls = div()
+            .flex()
+            .items_center()
+            .gap_3()
+            .child(
+                div()
+                    .id("simulator-toggle")
+                    .px_3()
+                    .py_1()
+                    .rounded_md()
+                    .text_xs()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0xffffff))
+                    .cursor_pointer()
+                    .bg(if broker.enabled {
+                        rgb(0x6e40c9)
+                    } else {
+                        rgb(0x21262d)
+                    })
+                    .hover(|style| style.bg(rgb(0x30363d)))
+                    .child(if broker.enabled {
+                        "🧪 Forward-test mode: ON"
+                    } else {
+                        "🧪 Forward-test mode: OFF"
+                    })
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.toggle_simulator(cx);
+                    })),
+            )
+            .child(
+                action("simulator-reset".into(), "↺ Reset account").on_click(cx.listener(
+                    |this, _, _, cx| {
+                        this.reset_simulator(cx);
+                    },
+                )),
+            )
+            .child(div().text_xs().text_color(rgb(0x8b949e)).child(
+                if broker.enabled {
+                    "Orders from the form, hotkeys, schedules, recurring plans, rules and scripts fill here against live trades instead of going to Alpaca"
+                } else {
+                    "Turn on to send new orders to this simulated account instead of Alpaca"
+                },
+            ));
+
+        let summary = div()
+            .flex()
+            .flex_wrap()
+            .gap_6()
+            .text_sm()
+            .child(self.render_account_stat(
+                "Equity".to_string(),
+                numbers.money(equity),
+                rgb(0xffffff),
+            ))
+            .child(self.render_account_stat(
+                "Cash".to_string(),
+                numbers.money(broker.cash),
+                rgb(0xffffff),
+            ))
+            .child(self.render_account_stat(
+                "Unrealized P&L".to_string(),
+                numbers.signed_money(unrealized),
+                pnl_color(unrealized),
+            ))
+            .child(self.render_account_stat(
+                "Realized P&L".to_string(),
+                numbers.signed_money(broker.realized),
+                pnl_color(broker.realized),
+            ))
+            .child(self.render_account_stat(
+                "Since Reset".to_string(),
+                format!(
+                    "{} ({:+.2}%)",
+                    numbers.signed_money(net),
+                    net / broker.starting_cash * 100.0
+                ),
+                pnl_color(net),
+            ));
+
+        let positions = div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(section_title(format!(
+                "Positions ({})",
+                broker.positions.len()
+            )))
+            .when(broker.positions.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(rgb(0x8b949e))
+                        .child("No simulated positions"),
+                )
+            })
+            .when(!broker.positions.is_empty(), |this| {
+                this.child(
+                    header_row()
+                        .child(header_cell("Symbol", 90.0))
+                        .child(header_cell("Qty", 90.0))
+                        .child(header_cell("Avg Price", 100.0))
+                        .child(header_cell("Last", 100.0))
+                        .child(header_cell("Market Value", 120.0))
+                        .child(header_cell("Unrealized", 110.0))
+                        .child(header_cell("", 60.0)),
+                )
+                .children(broker.positions.iter().map(|position| {
+                    let last = mark(&position.symbol);
+                    let pnl = last.map(|price| (price - position.avg_price) * position.qty);
+                    let symbol = position.symbol.clone();
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_4()
+                        .text_sm()
+                        .text_color(rgb(0x8b949e))
+                        .child(
+                            div()
+                                .w(px(90.0))
+                                .font_weight(FontWeight::SEMIBOLD)
+                                .text_color(rgb(0xffffff))
+                                .child(position.symbol.clone()),
+                        )
+                        .child(div().w(px(90.0)).child(position.qty.to_string()))
+                        .child(div().w(px(100.0)).child(numbers.price(position.avg_price)))
+                        .child(
+                            div().w(px(100.0)).child(
+                                last.map(|price| numbers.price(price))
+                                    .unwrap_or("-".to_string()),
+                            ),
+                        )
+                        .child(div().w(px(120.0)).child(
+                            numbers.money(position.qty * last.unwrap_or(position.avg_price)),
+                        ))
+                        .child(
+                            div()
+                                .w(px(110.0))
+                                .text_color(pnl.map(pnl_color).unwrap_or(rgb(0x8b949e)))
+                                .child(
+                                    pnl.map(|pnl| numbers.signed_money(pnl))
+                                        .unwrap_or("-".to_string()),
+                                ),
+                        )
+                        .child(
+                            div().w(px(60.0)).child(
+                                action(
+                                    ElementId::Name(
+                                        format!("simulator-close-{}", position.symbol).into(),
                                     ),
-                            )
-                            .child(
-                                // Title section
+                                    "Close",
+                                )
+                                .on_click(cx.listener(
+                                    move |this, _, _, cx| {
+                                        this.close_simulated_position(symbol.clone(), cx);
+                                    },
+                                )),
+                            ),
+                        )
+                }))
+            });
+
+        let orders = div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(section_title(format!(
+                "Open Orders ({})",
+                broker.orders.len()
+            )))
+            .when(broker.orders.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(rgb(0x8b949e))
+                        .child("No open simulated orders"),
+                )
+            })
+            .children(broker.orders.iter().map(|order| {
+                let id = order.id;
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_4()
+                    .text_sm()
+                    .text_color(rgb(0x8b949e))
+                    .child(div().w(px(60.0)).child(format!("#{}", order.id)))
+                    .child(
+                        div()
+                            .w(px(280.0))
+                            .text_color(rgb(0xffffff))
+                            .child(order.describe()),
+                    )
+                    .child(div().w(px(120.0)).child(order.source.clone()))
+                    .child(
+                        div().w(px(140.0)).child(
+                            order
+                                .placed_at
+                                .with_timezone(&chrono::Local)
+                                .format("%b %-d %H:%M:%S")
+                                .to_string(),
+                        ),
+                    )
+                    .child(
+                        action(
+                            ElementId::Name(format!("simulator-cancel-{}", id).into()),
+                            "Cancel",
+                        )
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.cancel_simulated_order(id, cx);
+                        })),
+                    )
+            }));
+
+        let fills = div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(section_title(format!("Fills ({})", broker.fills.len())))
+            .when(!broker.fills.is_empty(), |this| {
+                this.child(
+                    header_row()
+                        .child(header_cell("Time", 140.0))
+                        .child(header_cell("Order", 60.0))
+                        .child(header_cell("Fill", 240.0))
+                        .child(header_cell("Source", 120.0))
+                        .child(header_cell("Realized", 110.0)),
+                )
+                .child(
+                    self.render_scrollable_rows(
+                        "simulator-fill-rows",
+                        broker
+                            .fills
+                            .iter()
+                            .rev()
+                            .map(|fill| {
                                 div()
                                     .flex()
-                                    .flex_col()
-                                    .gap_1()
+                                    .gap_4()
+                                    .text_sm()
+                                    .text_color(rgb(0x8b949e))
                                     .child(
-                                        div()
-                                            .text_2xl()
-                                            .font_weight(FontWeight::BOLD)
-                                            .text_color(rgb(0xffffff))
-                                            .child(format!("{} Stock Chart", self.chart.symbol)),
+                                        div().w(px(140.0)).child(
+                                            fill.at
+                                                .with_timezone(&chrono::Local)
+                                                .format("%b %-d %H:%M:%S")
+                                                .to_string(),
+                                        ),
                                     )
-                                    .child(div().text_sm().text_color(rgb(0x808080)).child(
+                                    .child(div().w(px(60.0)).child(format!("#{}", fill.order_id)))
+                                    .child(div().w(px(240.0)).text_color(rgb(0xffffff)).child(
                                         format!(
-                                            "{} candlestick chart powered by Alpaca Markets",
-                                            timeframe_display
+                                            "{} {} {} @ {}",
+                                            fill.side.label(),
+                                            fill.qty,
+                                            fill.symbol,
+                                            numbers.price(fill.price)
                                         ),
-                                    )),
-                            )
-                            .child(
-                                // Status and controls section
-                                div()
-                                    .flex()
-                                    .flex_col()
-                                    .gap_3()
+                                    ))
+                                    .child(div().w(px(120.0)).child(fill.source.clone()))
                                     .child(
-                                        // WebSocket Status Indicator
                                         div()
-                                            .flex()
-                                            .items_center()
-                                            .gap_2()
-                                            .px_4()
-                                            .py_3()
-                                            .rounded_lg()
-                                            .bg(if self.stream_connected {
-                                                rgb(0x238636)
+                                            .w(px(110.0))
+                                            .text_color(if fill.realized == 0.0 {
+                                                rgb(0x8b949e)
                                             } else {
-                                                rgb(0x6e7681)
+                                                pnl_color(fill.realized)
                                             })
-                                            .child(
-                                                div().text_sm().text_color(rgb(0xffffff)).child(
-                                                    if self.stream_connected {
-                                                        "🟢 Live Updates"
-                                                    } else {
-                                                        "⭕ Disconnected"
-                                                    },
-                                                ),
-                                            ),
-                                    )
-                                    .child(
-                                        // Market Data WebSocket Status Indicator
-                                        div()
-                                            .flex()
-                                            .items_center()
-                                            .gap_2()
-                                            .px_4()
-                                            .py_3()
-                                            .rounded_lg()
-                                            .bg(if self.chart.market_data_connected {
-                                                rgb(0x1f6feb)
+                                            .child(if fill.realized == 0.0 {
+                                                "-".to_string()
                                             } else {
-                                                rgb(0x6e7681)
-                                            })
-                                            .child(
-                                                div()
-                                                    .flex()
-                                                    .flex_col()
-                                                    .gap_1()
-                                                    .child(
-                                                        div().text_sm().font_weight(FontWeight::SEMIBOLD).text_color(rgb(0xffffff)).child(
-                                                            if self.chart.market_data_connected {
-                                                                "📊 Market Data Stream"
-                                                            } else {
-                                                                "📊 No Market Data"
-                                                            }
-                                                        )
-                                                    )
-                                                    .when(self.chart.market_data_connected && self.chart.last_bar_symbol.is_some(), |this| {
-                                                        this.child(
-                                                            div()
-                                                                .flex()
-                                                                .flex_col()
-                                                                .gap_1()
-                                                                .text_xs()
-                                                                .text_color(rgb(0xcccccc))
-                                                                .child(
-                                                                    div().child(format!(
-                                                                        "Symbol: {} | Time: {}",
-                                                                        self.chart.last_bar_symbol.as_ref().unwrap(),
-                                                                        self.chart.last_bar_time.as_ref().map(|t| {
-                                                                            if t.len() >= 19 {
-                                                                                &t[11..19] // HH:MM:SS
-                                                                            } else {
-                                                                                t.as_str()
-                                                                            }
-                                                                        }).unwrap_or("--:--:--")
-                                                                    ))
-                                                                )
-                                                                .child(
-                                                                    div().child(format!(
-                                                                        "O: {} | H: {} | L: {} | C: {}",
-                                                                        self.chart.last_bar_open.as_ref().unwrap_or(&"--".to_string()),
-                                                                        self.chart.last_bar_high.as_ref().unwrap_or(&"--".to_string()),
-                                                                        self.chart.last_bar_low.as_ref().unwrap_or(&"--".to_string()),
-                                                                        self.chart.last_bar_close.as_ref().unwrap_or(&"--".to_string()),
-                                                                    ))
-                                                                )
-                                                                .child(
-                                                                    div().child(format!(
-                                                                        "Volume: {}",
-                                                                        self.chart.last_bar_volume.as_ref().unwrap_or(&"--".to_string()),
-                                                                    ))
-                                                                )
-                                                        )
-                                                    }),
+                                                numbers.signed_money(fill.realized)
+                                            }),
+                                    )
+                            })
+                            .collect(),
+                        cx,
+                    ),
+                )
+            });
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .child(controls)
+            .child(summary)
+            .child(positions)
+            .child(orders)
+            .child(fills)
+            .child(div().text_xs().text_color(rgb(0x8b949e)).child(
+                "Market orders fill at the next streamed trade, limit orders at the first trade at or through the limit. Open orders don't expire.",
+            ))
+    }
+
+    /// Realized equity curve with the benchmark's buy-and-hold overlaid
+    fn render_equity_curve(&self, curve: &[performance::EquityPoint]) -> impl IntoElement {
+        let numbers = &self.config.numbers.format;
+        let benchmark_symbol = &self.config.performance.benchmark;
+        // Buy-and-hold of the current equity, so both curves start from the same value
+        let capital = self.account.equity.filter(|equity| *equity > 0.0);
+        let benchmark = capital
+            .filter(|_| self.performance.benchmark_symbol == *benchmark_symbol)
+            .and_then(|capital| self.performance.benchmark_curve(curve, capital));
+
+        let comparison = match (&benchmark, capital, curve.last()) {
+            (Some(benchmark), Some(capital), Some(last)) => {
+                let held = benchmark.last().copied().unwrap_or(0.0);
+                div().text_xs().text_color(rgb(0x58a6ff)).child(format!(
+                    "— {} buy-and-hold of {}: {} vs. realized {} ({} {})",
+                    benchmark_symbol,
+                    numbers.money(capital),
+                    numbers.signed_money(held),
+                    numbers.signed_money(last.equity),
+                    if last.equity >= held {
+                        "beating"
+                    } else {
+                        "trailing"
+                    },
+                    benchmark_symbol
+                ))
+            }
+            _ => div()
+                .text_xs()
+                .text_color(rgb(0x8b949e))
+                .child(if capital.is_none() {
+                    format!("{} overlay needs the account equity", benchmark_symbol)
+                } else if self.performance.benchmark_loading {
+                    format!("Loading {}...", benchmark_symbol)
+                } else if let Some(error) = &self.performance.benchmark_error {
+                    format!("{} overlay unavailable: {}", benchmark_symbol, error)
+                } else {
+                    format!("No {} bars cover this period", benchmark_symbol)
+                }),
+        };
+
+        self.render_curve_panels(curve, benchmark.as_deref(), "Realized equity", comparison)
+    }
+
+    /// Equity curve with underwater periods shaded and an optional comparison line (one value
+    /// per point), and a drawdown panel below it annotated with the deepest drawdown and its
+    /// recovery time
+    fn render_curve_panels(
+        &self,
+        curve: &[performance::EquityPoint],
+        benchmark: Option<&[f64]>,
+        title: &str,
+        comparison: Div,
+    ) -> Div {
+        let numbers = &self.config.numbers.format;
+        let span = performance::DrawdownSpan::of(curve);
+        let values = || {
+            curve
+                .iter()
+                .map(|p| p.equity)
+                .chain(benchmark.into_iter().flatten().copied())
+        };
+        let max_equity = values().fold(0.0, f64::max);
+        let min_equity = values().fold(0.0, f64::min);
+        let equity_range = (max_equity - min_equity).max(f64::EPSILON);
+        let max_drawdown = span.as_ref().map(|span| span.depth).unwrap_or(0.0);
+        let column_width = 1.0 / curve.len() as f32;
+        // Fraction of the panel height from the top, for an equity value
+        let y = |equity: f64| ((max_equity - equity) / equity_range) as f32;
+        let zero_y = y(0.0);
+
+        let annotation = match &span {
+            Some(span) => format!(
+                "Max drawdown {} on {}, {}",
+                numbers.money(span.depth),
+                span.trough_at.format("%Y-%m-%d"),
+                match span.recovery_time() {
+                    Some(time) => format!("recovered in {} days", time.num_days()),
+                    None => "not yet recovered".to_string(),
+                }
+            ),
+            None => "No drawdown in this period".to_string(),
+        };
+
+        let equity_panel = div()
+            .relative()
+            .w_full()
+            .h(px(140.0))
+            .bg(rgb(0x0d1117))
+            .border_1()
+            .border_color(rgb(0x30363d))
+            .overflow_hidden()
+            // Zero line
+            .child(
+                div()
+                    .absolute()
+                    .left_0()
+                    .w_full()
+                    .top(gpui::relative(zero_y))
+                    .h(px(1.0))
+                    .bg(rgb(0x30363d)),
+            )
+            .children(curve.iter().enumerate().map(|(index, point)| {
+                let x = index as f32 * column_width;
+                let value_y = y(point.equity);
+                let (top, height) = if value_y < zero_y {
+                    (value_y, zero_y - value_y)
+                } else {
+                    (zero_y, value_y - zero_y)
+                };
+                let is_trough = span.as_ref().is_some_and(|span| span.trough == index);
+                let benchmark_y = benchmark.map(|benchmark| y(benchmark[index]));
+                div()
+                    .absolute()
+                    .left(gpui::relative(x))
+                    .top_0()
+                    .w(gpui::relative(column_width))
+                    .h_full()
+                    // Underwater: below the running peak
+                    .when(point.drawdown > 0.0, |this| this.bg(gpui::rgba(0xff44441f)))
+                    .child(
+                        div()
+                            .absolute()
+                            .left_0()
+                            .w_full()
+                            .top(gpui::relative(top))
+                            .h(gpui::relative(height.max(0.01)))
+                            .bg(if is_trough {
+                                rgb(0xf2cc60)
+                            } else if point.equity >= 0.0 {
+                                rgb(0x3fb950)
+                            } else {
+                                rgb(0xff4444)
+                            }),
+                    )
+                    .when_some(benchmark_y, |this, benchmark_y| {
+                        this.child(
+                            div()
+                                .absolute()
+                                .left_0()
+                                .w_full()
+                                .top(gpui::relative(benchmark_y))
+                                .h(px(2.0))
+                                .bg(rgb(0x58a6ff)),
+                        )
+                    })
+            }))
+            .child(
+                div()
+                    .absolute()
+                    .top_1()
+                    .left_2()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child(format!(
+                        "{} {} to {}",
+                        title,
+                        numbers.signed_money(min_equity),
+                        numbers.signed_money(max_equity)
+                    )),
+            );
+
+        let drawdown_panel = div()
+            .relative()
+            .w_full()
+            .h(px(50.0))
+            .bg(rgb(0x0d1117))
+            .border_1()
+            .border_color(rgb(0x30363d))
+            .overflow_hidden()
+            .children(curve.iter().enumerate().map(|(index, point)| {
+                let depth = if max_drawdown > 0.0 {
+                    (point.drawdown / max_drawdown) as f32
+                } else {
+                    0.0
+                };
+                div()
+                    .absolute()
+                    .left(gpui::relative(index as f32 * column_width))
+                    .top_0()
+                    .w(gpui::relative(column_width))
+                    .h(gpui::relative(depth))
+                    .bg(rgb(0xda3633))
+            }))
+            .child(
+                div()
+                    .absolute()
+                    .bottom_1()
+                    .left_2()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child(format!("Drawdown (max {})", numbers.money(max_drawdown))),
+            );
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .child(equity_panel)
+            .child(comparison)
+            .child(drawdown_panel)
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(if span.is_some() {
+                        rgb(0xf2cc60)
+                    } else {
+                        rgb(0x8b949e)
+                    })
+                    .child(annotation),
+            )
+    }
+
+    fn render_activities_tab(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let filtered_activities = self.account.filtered_activities();
+
+        let controls =
+            div()
+                .flex()
+                .items_center()
+                .gap_1()
+                .children(account::ActivityFilter::ALL.iter().map(|filter| {
+                    let filter = *filter;
+                    let is_selected = self.account.activity_filter == filter;
+                    div()
+                        .id(ElementId::Name(
+                            format!("activity-filter-{}", filter.label()).into(),
+                        ))
+                        .px_2()
+                        .py_1()
+                        .rounded_md()
+                        .text_xs()
+                        .cursor_pointer()
+                        .bg(if is_selected {
+                            rgb(0x1f6feb)
+                        } else {
+                            rgb(0x21262d)
+                        })
+                        .text_color(if is_selected {
+                            rgb(0xffffff)
+                        } else {
+                            rgb(0x8b949e)
+                        })
+                        .hover(|style| style.bg(rgb(0x30363d)))
+                        .child(filter.label())
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.account.activity_filter = filter;
+                            cx.notify();
+                        }))
+                }));
+
+        let body =
+            if self.account.activities_loading {
+                div()
+                    .grid()
+                    .items_center()
+                    .justify_center()
+                    .p_6()
+                    .text_color(rgb(0x8b949e))
+                    .child("Loading activities...")
+            } else if filtered_activities.is_empty() {
+                div()
+                    .grid()
+                    .items_center()
+                    .justify_center()
+                    .p_6()
+                    .text_color(rgb(0x8b949e))
+                    .child("No activities")
+            } else {
+                let header_cell = |label: &'static str, width: f32| {
+                    div()
+                        .w(px(width))
+                        .text_xs()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .text_color(rgb(0x8b949e))
+                        .child(label)
+                };
+
+                div()
+                    .grid()
+                    .grid_cols(1)
+                    .gap_2()
+                    .child(
+                        // Table header
+                        div()
+                            .flex()
+                            .gap_4()
+                            .pb_2()
+                            .border_b_1()
+                            .border_color(rgb(0x30363d))
+                            .child(header_cell("Time", 130.0))
+                            .child(header_cell("Type", 80.0))
+                            .child(header_cell("Symbol", 80.0))
+                            .child(header_cell("Side", 60.0))
+                            .child(header_cell("Qty", 80.0))
+                            .child(header_cell("Price", 100.0))
+                            .child(header_cell("Amount", 110.0))
+                            .child(header_cell("Description", 200.0)),
+                    )
+                    .child(
+                        self.render_scrollable_rows(
+                            "activities-rows",
+                            filtered_activities
+                                .into_iter()
+                                .map(|activity| {
+                                    let amount = activity.amount();
+                                    let amount_color = match amount {
+                                        Some(a) if a > 0.0 => rgb(0x3fb950),
+                                        Some(a) if a < 0.0 => rgb(0xff4444),
+                                        _ => rgb(0x8b949e),
+                                    };
+
+                                    div()
+                                        .flex()
+                                        .gap_4()
+                                        .py_2()
+                                        .text_sm()
+                                        .text_color(rgb(0x8b949e))
+                                        .child(div().w(px(130.0)).child(
+                                            activity.timestamp.format("%Y-%m-%d %H:%M").to_string(),
+                                        ))
+                                        .child(
+                                            div()
+                                                .w(px(80.0))
+                                                .font_weight(FontWeight::SEMIBOLD)
+                                                .text_color(rgb(0x58a6ff))
+                                                .child(activity.activity_type.clone()),
+                                        )
+                                        .child(div().w(px(80.0)).text_color(rgb(0xffffff)).child(
+                                            activity.symbol.clone().unwrap_or("-".to_string()),
+                                        ))
+                                        .child(div().w(px(60.0)).child(
+                                            activity.side.clone().unwrap_or("-".to_string()),
+                                        ))
+                                        .child(
+                                            div().w(px(80.0)).child(
+                                                activity.qty.clone().unwrap_or("-".to_string()),
                                             ),
-                                    ),
+                                        )
+                                        .child(
+                                            div().w(px(100.0)).child(
+                                                activity
+                                                    .price
+                                                    .as_ref()
+                                                    .map(|p| {
+                                                        self.config.numbers.format.price_text(p)
+                                                    })
+                                                    .unwrap_or("-".to_string()),
+                                            ),
+                                        )
+                                        .child(
+                                            div()
+                                                .w(px(110.0))
+                                                .font_weight(FontWeight::SEMIBOLD)
+                                                .text_color(amount_color)
+                                                .child(
+                                                    amount
+                                                        .map(|a| {
+                                                            self.config.numbers.format.money(a)
+                                                        })
+                                                        .unwrap_or("-".to_string()),
+                                                ),
+                                        )
+                                        .child(div().w(px(200.0)).text_xs().child(
+                                            activity.description.clone().unwrap_or_default(),
+                                        ))
+                                })
+                                .collect::<Vec<_>>(),
+                            cx,
+                        ),
+                    )
+            };
+
+        div().flex().flex_col().gap_3().child(controls).child(body)
+    }
+
+    fn render_limit_price_input(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        if !matches!(self.account.order_type, OrderType::Limit) {
+            return div();
+        }
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0xffffff))
+                    .child("Limit Price"),
+            )
+            .child(
+                div()
+                    .id("order-limit-price-input")
+                    .px_3()
+                    .py_2()
+                    .bg(if self.focus.is_focused(focus::InputField::LimitPrice) {
+                        rgb(0x1f2937)
+                    } else {
+                        rgb(0x0d1117)
+                    })
+                    .border_1()
+                    .border_color(if self.focus.is_focused(focus::InputField::LimitPrice) {
+                        rgb(0x1f6feb)
+                    } else {
+                        rgb(0x30363d)
+                    })
+                    .rounded_md()
+                    .text_color(rgb(0xffffff))
+                    .cursor_text()
+                    .child(
+                        if self.focus.is_focused(focus::InputField::LimitPrice)
+                            || self.account.order_limit_price.is_empty()
+                        {
+                            self.account.order_limit_price.render(
+                                self.focus.is_focused(focus::InputField::LimitPrice),
+                                "Enter price...",
                             )
-                            .child(
-                                // Refresh button
-                                div()
-                                    .id("refresh-button")
-                                    .px_6()
-                                    .py_3()
-                                    .bg(rgb(0x238636))
-                                    .rounded_lg()
-                                    .text_color(rgb(0xffffff))
-                                    .font_weight(FontWeight::SEMIBOLD)
-                                    .cursor_pointer()
-                                    .hover(|style| style.bg(rgb(0x2ea043)))
-                                    .child(if self.chart.loading {
-                                        "⟳ Loading..."
+                        } else {
+                            div().child(format!("${}", self.account.order_limit_price.text()))
+                        },
+                    )
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.set_input_focus(Some(focus::InputField::LimitPrice));
+                        // A popped-out form keeps focus in its own window
+                        if this.order_window.is_none() {
+                            _window.focus(&this.focus_handle);
+                        }
+                        cx.notify();
+                    })),
+            )
+    }
+
+    fn render_schedule_selector(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let time_focused = self.focus.is_focused(focus::InputField::ScheduleTime);
+        let mode_chips: Vec<_> = schedule::ScheduleMode::ALL
+            .iter()
+            .map(|&mode| {
+                self.render_filter_chip(
+                    format!("schedule-mode-{}", mode.label()),
+                    mode.label(),
+                    self.scheduler.mode == mode,
+                    cx,
+                    move |this, _| this.scheduler.mode = mode,
+                )
+            })
+            .collect();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0xffffff))
+                    .child("Submit"),
+            )
+            .child(div().flex().gap_2().children(mode_chips))
+            .when(
+                self.scheduler.mode == schedule::ScheduleMode::Time,
+                |this| {
+                    this.child(
+                        div()
+                            .id("schedule-time-input")
+                            .px_3()
+                            .py_2()
+                            .bg(if time_focused {
+                                rgb(0x1f2937)
+                            } else {
+                                rgb(0x0d1117)
+                            })
+                            .border_1()
+                            .border_color(if time_focused {
+                                rgb(0x1f6feb)
+                            } else {
+                                rgb(0x30363d)
+                            })
+                            .rounded_md()
+                            .text_color(rgb(0xffffff))
+                            .cursor_text()
+                            .child(if time_focused {
+                                format!("{}|", self.scheduler.time)
+                            } else if self.scheduler.time.is_empty() {
+                                "HH:MM ET".to_string()
+                            } else {
+                                format!("{} ET", self.scheduler.time)
+                            })
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.set_input_focus(Some(focus::InputField::ScheduleTime));
+                                // A popped-out form keeps focus in its own window
+                                if this.order_window.is_none() {
+                                    window.focus(&this.focus_handle);
+                                }
+                                cx.notify();
+                            })),
+                    )
+                },
+            )
+            .when(
+                self.scheduler.mode == schedule::ScheduleMode::Open,
+                |this| {
+                    this.child(div().text_xs().text_color(rgb(0x8b949e)).child(
+                        match self.market_clock.as_ref() {
+                            Some(clock) => format!(
+                            "Next open {}",
+                            clock
+                                .next_open
+                                .with_timezone(&chrono::Local)
+                                .format("%a %b %-d %H:%M")
+                        ),
+                            None => "Waiting for the market clock...".to_string(),
+                        },
+                    ))
+                },
+            )
+    }
+
+    /// Orders staged for later, soonest first, each with a cancel button
+    fn render_scheduled_orders(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0xffffff))
+                    .child(format!("Scheduled ({})", self.scheduler.orders.len())),
+            )
+            .children(self.scheduler.orders.iter().map(|order| {
+                let id = order.id;
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .text_xs()
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_color(rgb(0xffffff))
+                            .child(order.description.clone()),
+                    )
+                    .child(
+                        div().text_color(rgb(0x8b949e)).child(
+                            order
+                                .at
+                                .with_timezone(&chrono::Local)
+                                .format("%a %H:%M")
+                                .to_string(),
+                        ),
+                    )
+                    .child(
+                        div()
+                            .id(ElementId::Name(format!("schedule-cancel-{}", id).into()))
+                            .px_1()
+                            .rounded_md()
+                            .text_color(rgb(0x8b949e))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x30363d)).text_color(rgb(0xff4444)))
+                            .child("✕")
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                if let Some(order) = this.scheduler.cancel(id) {
+                                    info!("⏱️  Canceled scheduled {}", order.description);
+                                }
+                                cx.notify();
+                            })),
+                    )
+            }))
+    }
+
+    fn render_time_in_force(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        if !matches!(self.account.order_type, OrderType::Limit) {
+            return div();
+        }
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0xffffff))
+                    .child("Time in Force"),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .when(!self.chart.is_crypto(), |this| {
+                        this.child(
+                            div()
+                                .id("tif-day-btn")
+                                .flex_1()
+                                .px_3()
+                                .py_2()
+                                .rounded_md()
+                                .text_center()
+                                .font_weight(FontWeight::SEMIBOLD)
+                                .cursor_pointer()
+                                .bg(
+                                    if matches!(
+                                        self.account.order_time_in_force,
+                                        OrderTimeInForce::Day
+                                    ) {
+                                        rgb(0x1f6feb)
                                     } else {
-                                        "↻ Refresh Data"
-                                    })
-                                    .on_click(cx.listener(|this, _, _, cx| {
-                                        this.fetch_bars(cx);
-                                    })),
-                            ),
+                                        rgb(0x21262d)
+                                    },
+                                )
+                                .text_color(rgb(0xffffff))
+                                .hover(|style| {
+                                    if matches!(
+                                        self.account.order_time_in_force,
+                                        OrderTimeInForce::Day
+                                    ) {
+                                        style.bg(rgb(0x388bfd))
+                                    } else {
+                                        style.bg(rgb(0x30363d))
+                                    }
+                                })
+                                .child("Day")
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.account.order_time_in_force = OrderTimeInForce::Day;
+                                    cx.notify();
+                                })),
+                        )
+                    })
+                    .child(
+                        div()
+                            .id("tif-gtc-btn")
+                            .flex_1()
+                            .px_3()
+                            .py_2()
+                            .rounded_md()
+                            .text_center()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .cursor_pointer()
+                            .bg(
+                                if matches!(self.account.order_time_in_force, OrderTimeInForce::Gtc)
+                                {
+                                    rgb(0x1f6feb)
+                                } else {
+                                    rgb(0x21262d)
+                                },
+                            )
+                            .text_color(rgb(0xffffff))
+                            .hover(|style| {
+                                if matches!(self.account.order_time_in_force, OrderTimeInForce::Gtc)
+                                {
+                                    style.bg(rgb(0x388bfd))
+                                } else {
+                                    style.bg(rgb(0x30363d))
+                                }
+                            })
+                            .child("GTC")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.account.order_time_in_force = OrderTimeInForce::Gtc;
+                                cx.notify();
+                            })),
+                    )
+                    .when(self.chart.is_crypto(), |this| {
+                        this.child(
+                            div()
+                                .id("tif-ioc-btn")
+                                .flex_1()
+                                .px_3()
+                                .py_2()
+                                .rounded_md()
+                                .text_center()
+                                .font_weight(FontWeight::SEMIBOLD)
+                                .cursor_pointer()
+                                .bg(
+                                    if matches!(
+                                        self.account.order_time_in_force,
+                                        OrderTimeInForce::Ioc
+                                    ) {
+                                        rgb(0x1f6feb)
+                                    } else {
+                                        rgb(0x21262d)
+                                    },
+                                )
+                                .text_color(rgb(0xffffff))
+                                .hover(|style| {
+                                    if matches!(
+                                        self.account.order_time_in_force,
+                                        OrderTimeInForce::Ioc
+                                    ) {
+                                        style.bg(rgb(0x388bfd))
+                                    } else {
+                                        style.bg(rgb(0x30363d))
+                                    }
+                                })
+                                .child("IOC")
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.account.order_time_in_force = OrderTimeInForce::Ioc;
+                                    cx.notify();
+                                })),
+                        )
+                    }),
+            )
+    }
+
+    fn render_asset_details(&self) -> impl IntoElement {
+        let Some(asset) = self.asset.as_ref() else {
+            return div()
+                .text_xs()
+                .text_color(rgb(0x8b949e))
+                .child(if self.asset_loading {
+                    "Loading asset details..."
+                } else {
+                    "Asset details unavailable"
+                });
+        };
+
+        let flag = |label: &str, enabled: bool| {
+            div()
+                .px_2()
+                .py_0p5()
+                .rounded_sm()
+                .text_xs()
+                .font_weight(FontWeight::SEMIBOLD)
+                .bg(if enabled {
+                    rgb(0x1a3d2a)
+                } else {
+                    rgb(0x3d1a1a)
+                })
+                .text_color(if enabled {
+                    rgb(0x3fb950)
+                } else {
+                    rgb(0xff4444)
+                })
+                .child(format!("{} {}", if enabled { "✓" } else { "✗" }, label))
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(rgb(0xffffff))
+                            .overflow_hidden()
+                            .child(asset.name.clone()),
+                    )
+                    .child(div().text_xs().text_color(rgb(0x8b949e)).child(format!(
+                        "{} · {} · {}",
+                        asset.exchange, asset.asset_class, asset.status
+                    ))),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .gap_1()
+                    .child(flag("Tradable", asset.tradable))
+                    .child(flag("Fractionable", asset.fractionable))
+                    .child(flag("Shortable", asset.shortable))
+                    .child(flag("ETB", asset.easy_to_borrow))
+                    .child(flag("Marginable", asset.marginable)),
+            )
+            .when(
+                self.sell_would_open_short() && asset.is_hard_to_borrow(),
+                |this| {
+                    this.child(
+                        div()
+                            .px_3()
+                            .py_2()
+                            .bg(rgb(0x3d2e00))
+                            .border_1()
+                            .border_color(rgb(0xf2cc60))
+                            .rounded_md()
+                            .text_xs()
+                            .text_color(rgb(0xf2cc60))
+                            .child(if asset.shortable {
+                                format!(
+                                    "⚠ This sell would open a short in {}, which is hard to borrow",
+                                    asset.symbol
+                                )
+                            } else {
+                                format!(
+                                    "⚠ This sell would open a short in {}, which is not shortable",
+                                    asset.symbol
+                                )
+                            }),
+                    )
+                },
+            )
+    }
+
+    fn render_session_hint(&self) -> impl IntoElement {
+        // Crypto trades 24/7, so the equity session doesn't apply
+        if self.chart.is_crypto() {
+            return div();
+        }
+
+        let Some(clock) = self.market_clock.as_ref().filter(|clock| !clock.is_open) else {
+            return div();
+        };
+
+        div()
+            .px_3()
+            .py_2()
+            .bg(rgb(0x21262d))
+            .border_1()
+            .border_color(rgb(0x30363d))
+            .rounded_md()
+            .text_xs()
+            .text_color(rgb(0x8b949e))
+            .child(format!(
+                "Market closed: orders queue until the next open ({}, in {})",
+                clock
+                    .next_open
+                    .with_timezone(&chrono::Local)
+                    .format("%a %b %-d %H:%M"),
+                clock.countdown(Utc::now())
+            ))
+    }
+
+    fn render_hotkeys_panel(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let header = div()
+            .id("hotkeys-panel-toggle")
+            .flex()
+            .items_center()
+            .justify_between()
+            .cursor_pointer()
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0xffffff))
+                    .child("Trading Hotkeys"),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(if self.settings.hotkeys_enabled {
+                                rgb(0x3fb950)
+                            } else {
+                                rgb(0x8b949e)
+                            })
+                            .child(if self.settings.hotkeys_enabled {
+                                "● Armed"
+                            } else {
+                                "○ Off"
+                            }),
                     )
                     .child(
-                        // Spacer div between header and chart to catch mouse events in the gap
-                        div().h(px(24.0)).w_full().on_mouse_move(cx.listener(
-                            |this, _event, _window, cx| {
-                                this.chart.show_crosshair = false;
+                        div()
+                            .text_xs()
+                            .text_color(rgb(0x8b949e))
+                            .child(if self.hotkeys.open { "▾" } else { "▸" }),
+                    ),
+            )
+            .on_click(cx.listener(|this, _, _, cx| {
+                this.hotkeys.open = !this.hotkeys.open;
+                if !this.hotkeys.open && this.focus.is_focused(focus::InputField::HotkeyQuantity) {
+                    this.set_input_focus(None);
+                }
+                cx.notify();
+            }));
+
+        if !self.hotkeys.open {
+            return div()
+                .flex()
+                .flex_col()
+                .pt_4()
+                .border_t_1()
+                .border_color(rgb(0x30363d))
+                .child(header);
+        }
+
+        let focused = self.focus.is_focused(focus::InputField::HotkeyQuantity);
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .pt_4()
+            .border_t_1()
+            .border_color(rgb(0x30363d))
+            .child(header)
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .gap_1()
+                    .child(self.render_filter_chip(
+                        "hotkeys-enabled".to_string(),
+                        "Enabled",
+                        self.settings.hotkeys_enabled,
+                        cx,
+                        |this, _| {
+                            this.settings.hotkeys_enabled = !this.settings.hotkeys_enabled;
+                            this.settings.save();
+                        },
+                    ))
+                    .child(self.render_filter_chip(
+                        "hotkeys-confirm".to_string(),
+                        "Confirm",
+                        !self.settings.hotkey_skip_confirmation,
+                        cx,
+                        |this, _| {
+                            this.settings.hotkey_skip_confirmation =
+                                !this.settings.hotkey_skip_confirmation;
+                            this.settings.save();
+                        },
+                    )),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().text_xs().text_color(rgb(0x8b949e)).child("Size"))
+                    .child(
+                        div()
+                            .id("hotkey-quantity-input")
+                            .flex_1()
+                            .px_3()
+                            .py_1()
+                            .bg(if focused {
+                                rgb(0x1f2937)
+                            } else {
+                                rgb(0x0d1117)
+                            })
+                            .border_1()
+                            .border_color(if focused {
+                                rgb(0x1f6feb)
+                            } else {
+                                rgb(0x30363d)
+                            })
+                            .rounded_md()
+                            .text_sm()
+                            .text_color(rgb(0xffffff))
+                            .cursor_text()
+                            .child(if focused {
+                                format!("{}|", self.settings.hotkey_quantity)
+                            } else if self.settings.hotkey_quantity.is_empty() {
+                                "Quantity...".to_string()
+                            } else {
+                                self.settings.hotkey_quantity.clone()
+                            })
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.set_input_focus(Some(focus::InputField::HotkeyQuantity));
+                                window.focus(&this.focus_handle);
                                 cx.notify();
-                            },
-                        )),
+                            })),
+                    ),
+            )
+            .child(div().text_xs().text_color(rgb(0x8b949e)).child(format!(
+                "Buy / sell hotkeys (Shift+B / Shift+S by default) send a market order for {}",
+                self.chart.symbol
+            )))
+    }
+
+    fn render_auto_exit_panel(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let enabled = self.settings.auto_exits.enabled;
+        let header = div()
+            .id("auto-exit-panel-toggle")
+            .flex()
+            .items_center()
+            .justify_between()
+            .cursor_pointer()
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0xffffff))
+                    .child("Auto Exits"),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(if enabled {
+                                rgb(0x3fb950)
+                            } else {
+                                rgb(0x8b949e)
+                            })
+                            .child(if enabled { "● On" } else { "○ Off" }),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(rgb(0x8b949e))
+                            .child(if self.auto_exits.open { "▾" } else { "▸" }),
+                    ),
+            )
+            .on_click(cx.listener(|this, _, _, cx| {
+                this.auto_exits.open = !this.auto_exits.open;
+                if !this.auto_exits.open
+                    && (this.focus.is_focused(focus::InputField::AutoExitTakeProfit)
+                        || this.focus.is_focused(focus::InputField::AutoExitStopLoss))
+                {
+                    this.set_input_focus(None);
+                }
+                cx.notify();
+            }));
+
+        if !self.auto_exits.open {
+            return div()
+                .flex()
+                .flex_col()
+                .pt_4()
+                .border_t_1()
+                .border_color(rgb(0x30363d))
+                .child(header);
+        }
+
+        let example = self
+            .settings
+            .auto_exits
+            .offsets()
+            .map(|(take_profit, stop_loss)| {
+                format!(
+                    "A long entry filled at $100.00 gets a sell OCO: take profit at ${:.2}, stop at ${:.2}",
+                    100.0 * (1.0 + take_profit / 100.0),
+                    100.0 * (1.0 - stop_loss / 100.0)
+                )
+            });
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .pt_4()
+            .border_t_1()
+            .border_color(rgb(0x30363d))
+            .child(header)
+            .child(div().flex().flex_wrap().gap_1().child(self.render_filter_chip(
+                "auto-exits-enabled".to_string(),
+                "Enabled",
+                enabled,
+                cx,
+                |this, _| {
+                    this.settings.auto_exits.enabled = !this.settings.auto_exits.enabled;
+                    this.settings.save();
+                },
+            )))
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(self.render_auto_exit_input(
+                        focus::InputField::AutoExitTakeProfit,
+                        "Take profit %",
+                        cx,
+                    ))
+                    .child(self.render_auto_exit_input(
+                        focus::InputField::AutoExitStopLoss,
+                        "Stop loss %",
+                        cx,
+                    )),
+            )
+            .child(match example {
+                Ok(example) => div().text_xs().text_color(rgb(0x8b949e)).child(example),
+                Err(error) => div().text_xs().text_color(rgb(0xff4444)).child(error),
+            })
+            .child(div().text_xs().text_color(rgb(0x8b949e)).child(
+                "Applies to filled form, hotkey and chart ticket entries (whole-share stocks). Exits are GTC and show in Active Orders, where canceling one cancels both legs.",
+            ))
+    }
+
+    fn render_auto_exit_input(
+        &self,
+        field: focus::InputField,
+        label: &'static str,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let focused = self.focus.is_focused(field);
+        let (id, value) = match field {
+            focus::InputField::AutoExitTakeProfit => (
+                "auto-exit-take-profit-input",
+                &self.settings.auto_exits.take_profit_percent,
+            ),
+            _ => (
+                "auto-exit-stop-loss-input",
+                &self.settings.auto_exits.stop_loss_percent,
+            ),
+        };
+
+        div()
+            .flex()
+            .flex_1()
+            .flex_col()
+            .gap_1()
+            .child(div().text_xs().text_color(rgb(0x8b949e)).child(label))
+            .child(
+                div()
+                    .id(id)
+                    .px_3()
+                    .py_1()
+                    .bg(if focused {
+                        rgb(0x1f2937)
+                    } else {
+                        rgb(0x0d1117)
+                    })
+                    .border_1()
+                    .border_color(if focused {
+                        rgb(0x1f6feb)
+                    } else {
+                        rgb(0x30363d)
+                    })
+                    .rounded_md()
+                    .text_sm()
+                    .text_color(rgb(0xffffff))
+                    .cursor_text()
+                    .child(if focused {
+                        format!("{}|", value)
+                    } else if value.is_empty() {
+                        "—".to_string()
+                    } else {
+                        value.clone()
+                    })
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.set_input_focus(Some(field));
+                        window.focus(&this.focus_handle);
+                        cx.notify();
+                    })),
+            )
+    }
+
+    /// Stands in for the submit button while trading is halted by the daily loss limit
+    fn render_loss_limit_notice(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let pnl = self.account.daily_pnl().map(|(change, _)| change);
+        let numbers = &self.config.numbers.format;
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .mt_4()
+            .child(
+                div()
+                    .px_4()
+                    .py_3()
+                    .bg(rgb(0x21262d))
+                    .rounded_md()
+                    .text_center()
+                    .text_color(rgb(0x8b949e))
+                    .font_weight(FontWeight::BOLD)
+                    .child("Trading Halted"),
+            )
+            .child(div().text_xs().text_color(rgb(0xff4444)).child(format!(
+                "Today's P&L {} reached the {} daily loss limit. Trading resumes next session.",
+                pnl.map(|pnl| numbers.signed_money(pnl))
+                    .unwrap_or("-".to_string()),
+                self.config
+                    .risk
+                    .max_daily_loss
+                    .map(|loss| numbers.money(loss))
+                    .unwrap_or_default()
+            )))
+            .child(
+                div()
+                    .id("loss-limit-override")
+                    .text_xs()
+                    .text_color(rgb(0x58a6ff))
+                    .cursor_pointer()
+                    .hover(|style| style.text_color(rgb(0xffffff)))
+                    .child("Override for today...")
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.loss_limit.confirming = true;
+                        cx.notify();
+                    })),
+            )
+    }
+
+    fn render_loss_limit_confirmation(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full()
+            .flex()
+            .items_center()
+            .justify_center()
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_4()
+                    .p_6()
+                    .w(px(360.0))
+                    .bg(rgb(0x161b22))
+                    .border_1()
+                    .border_color(rgb(0xda3633))
+                    .rounded_lg()
+                    .shadow_lg()
+                    .child(
+                        div()
+                            .text_lg()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(rgb(0xffffff))
+                            .child("Keep trading past the daily loss limit?"),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(rgb(0x8b949e))
+                            .child("Orders and hotkeys stay enabled for the rest of today. Enter to confirm, Escape to cancel"),
                     )
                     .child(
-                        // Chart area wrapper with side padding to catch mouse events
                         div()
-                            .flex_1()
                             .flex()
-                            .flex_row()
-                            .min_h(px(400.0))
+                            .justify_end()
+                            .gap_2()
                             .child(
-                                // Left padding area to catch mouse events
-                                div().w(px(32.0)).h_full().on_mouse_move(cx.listener(
-                                    |this, _event, _window, cx| {
-                                        this.chart.show_crosshair = false;
+                                div()
+                                    .id("loss-limit-cancel")
+                                    .px_4()
+                                    .py_2()
+                                    .bg(rgb(0x21262d))
+                                    .rounded_md()
+                                    .text_sm()
+                                    .text_color(rgb(0xffffff))
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(0x30363d)))
+                                    .child("Cancel")
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.loss_limit.confirming = false;
                                         cx.notify();
-                                    },
-                                )),
+                                    })),
                             )
                             .child(
-                                // Actual chart
                                 div()
-                                    .flex_1()
-                                    .grid()
-                                    .items_center()
-                                    .justify_center()
-                                    .child(self.render_candlesticks(cx)),
-                            )
-                            .child(
-                                // Right padding area to catch mouse events
-                                div().w(px(32.0)).h_full().on_mouse_move(cx.listener(
-                                    |this, _event, _window, cx| {
-                                        this.chart.show_crosshair = false;
-                                        cx.notify();
-                                    },
-                                )),
+                                    .id("loss-limit-confirm")
+                                    .px_4()
+                                    .py_2()
+                                    .bg(rgb(0xda3633))
+                                    .rounded_md()
+                                    .text_sm()
+                                    .text_color(rgb(0xffffff))
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .cursor_pointer()
+                                    .child("Resume Trading")
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.override_loss_limit(cx);
+                                    })),
                             ),
+                    ),
+            )
+    }
+
+    fn render_hotkey_confirmation(
+        &self,
+        side: hotkeys::HotkeySide,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let color = match side {
+            hotkeys::HotkeySide::Buy => rgb(0x238636),
+            hotkeys::HotkeySide::Sell => rgb(0xda3633),
+        };
+
+        div()
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full()
+            .flex()
+            .items_center()
+            .justify_center()
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_4()
+                    .p_6()
+                    .w(px(360.0))
+                    .bg(rgb(0x161b22))
+                    .border_1()
+                    .border_color(color)
+                    .rounded_lg()
+                    .shadow_lg()
+                    .child(
+                        div()
+                            .text_lg()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(rgb(0xffffff))
+                            .child(format!(
+                                "{} {} {} at market?",
+                                side.label(),
+                                self.settings.hotkey_quantity,
+                                self.chart.symbol
+                            )),
                     )
                     .child(
-                        // Spacer div between chart and footer to catch mouse events in the gap
-                        div().h(px(24.0)).w_full().on_mouse_move(cx.listener(
-                            |this, _event, _window, cx| {
-                                this.chart.show_crosshair = false;
-                                cx.notify();
-                            },
-                        )),
+                        div()
+                            .text_xs()
+                            .text_color(rgb(0x8b949e))
+                            .child("Enter to confirm, Escape to cancel"),
                     )
                     .child(
-                        // Tabbed Footer
                         div()
-                            .flex_shrink_0()
-                            .grid()
-                            .grid_cols(1)
-                            .gap_3()
-                            .p_4()
-                            .bg(rgb(0x161b22))
-                            .rounded_lg()
-                            .border_1()
-                            .border_color(rgb(0x30363d))
-                            .on_mouse_move(cx.listener(|this, _event, _window, cx| {
-                                // Hide crosshair when mouse is over footer
-                                this.chart.show_crosshair = false;
-                                cx.notify();
-                            }))
+                            .flex()
+                            .justify_end()
+                            .gap_2()
                             .child(
-                                // Tab buttons and refresh button
                                 div()
-                                    .flex()
-                                    .items_center()
-                                    .justify_between()
-                                    .child(
-                                        div()
-                                            .flex()
-                                            .gap_2()
-                                            .child(
-                                                div()
-                                                    .id("tab-account")
-                                                    .px_4()
-                                                    .py_2()
-                                                    .rounded_md()
-                                                    .text_sm()
-                                                    .font_weight(FontWeight::SEMIBOLD)
-                                                    .cursor_pointer()
-                                                    .bg(
-                                                        if self.account.active_footer_tab
-                                                            == account::FooterTab::Account
-                                                        {
-                                                            rgb(0x238636)
-                                                        } else {
-                                                            rgb(0x21262d)
-                                                        },
-                                                    )
-                                                    .text_color(rgb(0xffffff))
-                                                    .hover(|style| {
-                                                        if self.account.active_footer_tab
-                                                            == account::FooterTab::Account
-                                                        {
-                                                            style.bg(rgb(0x2ea043))
-                                                        } else {
-                                                            style.bg(rgb(0x30363d))
-                                                        }
-                                                    })
-                                                    .child("Account Information")
-                                                    .on_click(cx.listener(|this, _, _, cx| {
-                                                        this.account.active_footer_tab = account::FooterTab::Account;
-                                                        cx.notify();
-                                                    })),
-                                            )
-                                            .child(
-                                                div()
-                                                    .id("tab-positions")
-                                                    .px_4()
-                                                    .py_2()
-                                                    .rounded_md()
-                                                    .text_sm()
-                                                    .font_weight(FontWeight::SEMIBOLD)
-                                                    .cursor_pointer()
-                                                    .bg(
-                                                        if self.account.active_footer_tab
-                                                            == account::FooterTab::Positions
-                                                        {
-                                                            rgb(0x238636)
-                                                        } else {
-                                                            rgb(0x21262d)
-                                                        },
-                                                    )
-                                                    .text_color(rgb(0xffffff))
-                                                    .hover(|style| {
-                                                        if self.account.active_footer_tab
-                                                            == account::FooterTab::Positions
-                                                        {
-                                                            style.bg(rgb(0x2ea043))
-                                                        } else {
-                                                            style.bg(rgb(0x30363d))
-                                                        }
-                                                    })
-                                                    .child("Active Positions")
-                                                    .on_click(cx.listener(|this, _, _, cx| {
-                                                        this.account.active_footer_tab =
-                                                            account::FooterTab::Positions;
-                                                        cx.notify();
-                                                    })),
-                                            )
-                                            .child(
-                                                div()
-                                                    .id("tab-orders")
-                                                    .px_4()
-                                                    .py_2()
-                                                    .rounded_md()
-                                                    .text_sm()
-                                                    .font_weight(FontWeight::SEMIBOLD)
-                                                    .cursor_pointer()
-                                                    .bg(
-                                                        if self.account.active_footer_tab
-                                                            == account::FooterTab::Orders
-                                                        {
-                                                            rgb(0x238636)
-                                                        } else {
-                                                            rgb(0x21262d)
-                                                        },
-                                                    )
-                                                    .text_color(rgb(0xffffff))
-                                                    .hover(|style| {
-                                                        if self.account.active_footer_tab
-                                                            == account::FooterTab::Orders
-                                                        {
-                                                            style.bg(rgb(0x2ea043))
-                                                        } else {
-                                                            style.bg(rgb(0x30363d))
-                                                        }
-                                                    })
-                                                    .child("Active Orders")
-                                                    .on_click(cx.listener(|this, _, _, cx| {
-                                                        this.account.active_footer_tab = account::FooterTab::Orders;
-                                                        cx.notify();
-                                                    })),
-                                            ),
-                                    )
-                                    .child(
-                                        div()
-                                            .id("refresh-footer-button")
-                                            .px_3()
-                                            .py_1()
-                                            .bg(rgb(0x238636))
-                                            .rounded_md()
-                                            .text_xs()
-                                            .text_color(rgb(0xffffff))
-                                            .font_weight(FontWeight::SEMIBOLD)
-                                            .cursor_pointer()
-                                            .hover(|style| style.bg(rgb(0x2ea043)))
-                                            .child(
-                                                if (self.account.active_footer_tab == account::FooterTab::Account
-                                                    && self.account.account_loading)
-                                                    || (self.account.active_footer_tab
-                                                        == account::FooterTab::Positions
-                                                        && self.account.positions_loading)
-                                                    || (self.account.active_footer_tab == account::FooterTab::Orders
-                                                        && self.account.orders_loading)
-                                                {
-                                                    "⟳ Loading..."
-                                                } else {
-                                                    "↻ Refresh"
-                                                },
-                                            )
-                                            .on_click(cx.listener(|this, _, _, cx| {
-                                                match this.account.active_footer_tab {
-                                                    account::FooterTab::Account => this.fetch_account(cx),
-                                                    account::FooterTab::Positions => {
-                                                        this.fetch_positions(cx)
-                                                    }
-                                                    account::FooterTab::Orders => this.fetch_orders(cx),
-                                                }
-                                            })),
-                                    ),
+                                    .id("hotkey-cancel")
+                                    .px_4()
+                                    .py_2()
+                                    .bg(rgb(0x21262d))
+                                    .rounded_md()
+                                    .text_sm()
+                                    .text_color(rgb(0xffffff))
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(0x30363d)))
+                                    .child("Cancel")
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.hotkeys.pending = None;
+                                        cx.notify();
+                                    })),
                             )
-                            .when(self.account.active_footer_tab == account::FooterTab::Account, |div| {
-                                div.child(self.render_account_tab())
-                            })
-                            .when(self.account.active_footer_tab == account::FooterTab::Positions, |div| {
-                                div.child(self.render_positions_tab(cx))
-                            })
-                            .when(self.account.active_footer_tab == account::FooterTab::Orders, |div| {
-                                div.child(self.render_orders_tab(cx))
-                            }),
+                            .child(
+                                div()
+                                    .id("hotkey-confirm")
+                                    .px_4()
+                                    .py_2()
+                                    .bg(color)
+                                    .rounded_md()
+                                    .text_sm()
+                                    .text_color(rgb(0xffffff))
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .cursor_pointer()
+                                    .child(side.label())
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.submit_hotkey_order(side, cx);
+                                    })),
+                            ),
                     ),
-            ) // Close main content .child()
+            )
+    }
+
+    fn render_setup_dialog(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let input_box = |id: &'static str,
+                         field: focus::InputField,
+                         input: &TextInput,
+                         cx: &mut Context<Self>| {
+            let focused = self.focus.is_focused(field);
+            div()
+                .id(id)
+                .px_3()
+                .py_2()
+                .bg(if focused {
+                    rgb(0x1f2937)
+                } else {
+                    rgb(0x0d1117)
+                })
+                .border_1()
+                .border_color(if focused {
+                    rgb(0x1f6feb)
+                } else {
+                    rgb(0x30363d)
+                })
+                .rounded_md()
+                .text_sm()
+                .text_color(rgb(0xffffff))
+                .cursor_text()
+                .child(input.render(focused, "Paste here..."))
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    this.set_input_focus(Some(field));
+                    cx.notify();
+                }))
+        };
+        let label = |text: &'static str| div().text_xs().text_color(rgb(0x8b949e)).child(text);
+
+        let name_box = input_box(
+            "setup-profile-name",
+            focus::InputField::ProfileName,
+            &self.setup.name,
+            cx,
+        );
+        let key_id_box = input_box(
+            "setup-key-id",
+            focus::InputField::ApiKeyId,
+            &self.setup.key_id,
+            cx,
+        );
+        let secret_box = input_box(
+            "setup-secret-key",
+            focus::InputField::ApiSecretKey,
+            &self.setup.secret_key,
+            cx,
+        );
+        let environment_chips: Vec<_> = [(true, "Paper"), (false, "Live")]
+            .into_iter()
+            .map(|(paper, chip_label)| {
+                self.render_filter_chip(
+                    format!("setup-env-{}", chip_label),
+                    chip_label,
+                    self.setup.paper == paper,
+                    cx,
+                    move |this, _| this.setup.paper = paper,
+                )
+            })
+            .collect();
+        let can_cancel = credentials::has_credentials();
+
+        div()
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full()
+            .flex()
+            .items_center()
+            .justify_center()
+            .bg(gpui::rgba(0x0d1117cc))
+            .occlude()
             .child(
-                // Right sidebar - Order form
                 div()
-                    .col_span(1)
-                    .bg(rgb(0x161b22))
-                    .border_l_1()
-                    .border_color(rgb(0x30363d))
-                    .p_6()
                     .flex()
                     .flex_col()
-                    .gap_4()
-                    .on_mouse_move(cx.listener(|this, _event, _window, cx| {
-                        // Hide crosshair when mouse is over sidebar
-                        this.chart.show_crosshair = false;
-                        cx.notify();
-                    }))
+                    .gap_3()
+                    .p_6()
+                    .w(px(440.0))
+                    .bg(rgb(0x161b22))
+                    .border_1()
+                    .border_color(rgb(0x30363d))
+                    .rounded_lg()
+                    .shadow_lg()
                     .child(
                         div()
                             .text_lg()
-                            .font_weight(FontWeight::BOLD)
+                            .font_weight(FontWeight::SEMIBOLD)
                             .text_color(rgb(0xffffff))
-                            .child("Place Order"),
+                            .child("Alpaca Account Profile"),
                     )
+                    .child(div().text_xs().text_color(rgb(0x8b949e)).child(
+                        "Enter an API key pair from the Alpaca dashboard. Keys are saved to your config directory.",
+                    ))
+                    .child(label("Profile Name"))
+                    .child(name_box)
+                    .child(label("API Key ID"))
+                    .child(key_id_box)
+                    .child(label("Secret Key"))
+                    .child(secret_box)
+                    .child(label("Environment"))
+                    .child(div().flex().gap_2().children(environment_chips))
+                    .when(!self.setup.paper, |this| {
+                        this.child(
+                            div()
+                                .text_xs()
+                                .text_color(rgb(0xf2cc60))
+                                .child("⚠️ Live keys place real orders with real money"),
+                        )
+                    })
+                    .when(self.setup.testing, |this| {
+                        this.child(
+                            div()
+                                .text_xs()
+                                .text_color(rgb(0x8b949e))
+                                .child("Testing connection..."),
+                        )
+                    })
+                    .when_some(self.setup.status.clone(), |this, status| {
+                        this.child(match status {
+                            Ok(account_number) => div()
+                                .text_xs()
+                                .text_color(rgb(0x3fb950))
+                                .child(format!("✓ Connected to account {}", account_number)),
+                            Err(e) => div()
+                                .flex()
+                                .flex_col()
+                                .gap_1()
+                                .text_xs()
+                                .child(div().text_color(rgb(0xff4444)).child(e.to_string()))
+                                .when_some(e.hint(), |this, hint| {
+                                    this.child(div().text_color(rgb(0x8b949e)).child(hint))
+                                }),
+                        })
+                    })
                     .child(
-                        // Current symbol display
                         div()
                             .flex()
-                            .flex_col()
+                            .justify_end()
                             .gap_2()
+                            .when(can_cancel, |this| {
+                                this.child(
+                                    div()
+                                        .id("setup-cancel")
+                                        .px_4()
+                                        .py_2()
+                                        .bg(rgb(0x21262d))
+                                        .rounded_md()
+                                        .text_sm()
+                                        .text_color(rgb(0xffffff))
+                                        .cursor_pointer()
+                                        .hover(|style| style.bg(rgb(0x30363d)))
+                                        .child("Cancel")
+                                        .on_click(cx.listener(|this, _, _, cx| {
+                                            this.setup.open = false;
+                                            this.set_input_focus(None);
+                                            cx.notify();
+                                        })),
+                                )
+                            })
                             .child(
                                 div()
+                                    .id("setup-test")
+                                    .px_4()
+                                    .py_2()
+                                    .bg(rgb(0x21262d))
+                                    .rounded_md()
                                     .text_sm()
-                                    .font_weight(FontWeight::SEMIBOLD)
                                     .text_color(rgb(0xffffff))
-                                    .child("Trading Symbol"),
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(0x30363d)))
+                                    .child("Test Connection")
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.test_credentials(cx);
+                                    })),
                             )
                             .child(
                                 div()
-                                    .px_3()
+                                    .id("setup-save")
+                                    .px_4()
                                     .py_2()
-                                    .bg(rgb(0x0d1117))
-                                    .border_1()
-                                    .border_color(rgb(0x1f6feb))
+                                    .bg(rgb(0x238636))
                                     .rounded_md()
-                                    .text_color(rgb(0x58a6ff))
-                                    .font_weight(FontWeight::BOLD)
-                                    .child(self.chart.symbol.clone()),
+                                    .text_sm()
+                                    .text_color(rgb(0xffffff))
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(0x2ea043)))
+                                    .child("Save")
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.save_profile(cx);
+                                    })),
                             ),
+                    ),
+            )
+    }
+
+    fn render_live_order_confirmation(
+        &self,
+        pending: &order_guard::PendingLiveOrder,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let focused = self.focus.is_focused(focus::InputField::LiveConfirmation);
+        let is_typed = self.order_guard.is_typed();
+
+        div()
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full()
+            .flex()
+            .items_center()
+            .justify_center()
+            .bg(gpui::rgba(0x0d1117cc))
+            .occlude()
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_3()
+                    .p_6()
+                    .w(px(400.0))
+                    .bg(rgb(0x161b22))
+                    .border_2()
+                    .border_color(rgb(0xda3633))
+                    .rounded_lg()
+                    .shadow_lg()
+                    .child(
+                        div()
+                            .text_lg()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(rgb(0xff4444))
+                            .child("⚠️ Live Order"),
+                    )
+                    .child(
+                        div()
+                            .text_color(rgb(0xffffff))
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .child(pending.description.clone()),
+                    )
+                    .child(div().text_sm().text_color(rgb(0x8b949e)).child(
+                        match pending.notional {
+                            Some(notional) => format!("Notional ≈ ${:.2}", notional),
+                            None => "No price available to estimate the notional".to_string(),
+                        },
+                    ))
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(rgb(0x8b949e))
+                            .child(format!("Type {} to confirm", pending.symbol)),
+                    )
+                    .child(
+                        div()
+                            .id("live-order-confirmation-input")
+                            .px_3()
+                            .py_2()
+                            .bg(if focused {
+                                rgb(0x1f2937)
+                            } else {
+                                rgb(0x0d1117)
+                            })
+                            .border_1()
+                            .border_color(if is_typed {
+                                rgb(0x3fb950)
+                            } else if focused {
+                                rgb(0x1f6feb)
+                            } else {
+                                rgb(0x30363d)
+                            })
+                            .rounded_md()
+                            .text_color(rgb(0xffffff))
+                            .cursor_text()
+                            .child(self.order_guard.input.render(focused, &pending.symbol))
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.set_input_focus(Some(focus::InputField::LiveConfirmation));
+                                cx.notify();
+                            })),
                     )
                     .child(
-                        // Order side (Buy/Sell)
                         div()
                             .flex()
-                            .flex_col()
+                            .justify_end()
                             .gap_2()
                             .child(
                                 div()
+                                    .id("live-order-cancel")
+                                    .px_4()
+                                    .py_2()
+                                    .bg(rgb(0x21262d))
+                                    .rounded_md()
                                     .text_sm()
-                                    .font_weight(FontWeight::SEMIBOLD)
                                     .text_color(rgb(0xffffff))
-                                    .child("Side"),
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(0x30363d)))
+                                    .child("Cancel")
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.cancel_live_order(cx);
+                                    })),
                             )
                             .child(
                                 div()
-                                    .flex()
-                                    .gap_2()
-                                    .child(
-                                        div()
-                                            .id("order-side-buy-btn")
-                                            .flex_1()
-                                            .px_3()
-                                            .py_2()
-                                            .rounded_md()
-                                            .text_center()
-                                            .font_weight(FontWeight::SEMIBOLD)
-                                            .cursor_pointer()
-                                            .bg(if matches!(self.account.order_side, OrderSide::Buy) {
-                                                rgb(0x3fb950)
-                                            } else {
-                                                rgb(0x21262d)
-                                            })
-                                            .text_color(rgb(0xffffff))
-                                            .hover(|style| {
-                                                if matches!(self.account.order_side, OrderSide::Buy) {
-                                                    style.bg(rgb(0x4fc55d))
-                                                } else {
-                                                    style.bg(rgb(0x30363d))
-                                                }
-                                            })
-                                            .child("Buy")
-                                            .on_click(cx.listener(|this, _, _, cx| {
-                                                this.account.order_side = OrderSide::Buy;
-                                                cx.notify();
-                                            })),
-                                    )
-                                    .child(
-                                        div()
-                                            .id("order-side-sell-btn")
-                                            .flex_1()
-                                            .px_3()
-                                            .py_2()
-                                            .rounded_md()
-                                            .text_center()
-                                            .font_weight(FontWeight::SEMIBOLD)
-                                            .cursor_pointer()
-                                            .bg(if matches!(self.account.order_side, OrderSide::Sell) {
-                                                rgb(0xff4444)
-                                            } else {
-                                                rgb(0x21262d)
-                                            })
-                                            .text_color(rgb(0xffffff))
-                                            .hover(|style| {
-                                                if matches!(self.account.order_side, OrderSide::Sell) {
-                                                    style.bg(rgb(0xff5555))
-                                                } else {
-                                                    style.bg(rgb(0x30363d))
-                                                }
-                                            })
-                                            .child("Sell")
-                                            .on_click(cx.listener(|this, _, _, cx| {
-                                                this.account.order_side = OrderSide::Sell;
-                                                cx.notify();
-                                            })),
-                                    ),
+                                    .id("live-order-confirm")
+                                    .px_4()
+                                    .py_2()
+                                    .bg(if is_typed {
+                                        rgb(0xda3633)
+                                    } else {
+                                        rgb(0x21262d)
+                                    })
+                                    .rounded_md()
+                                    .text_sm()
+                                    .text_color(if is_typed {
+                                        rgb(0xffffff)
+                                    } else {
+                                        rgb(0x8b949e)
+                                    })
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .cursor_pointer()
+                                    .child("Place Live Order")
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.confirm_live_order(cx);
+                                    })),
                             ),
+                    ),
+            )
+    }
+
+    fn render_alerts_panel(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let header = div()
+            .id("alerts-panel-toggle")
+            .flex()
+            .items_center()
+            .justify_between()
+            .cursor_pointer()
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0xffffff))
+                    .child(format!("Price Alerts ({})", self.alerts.alerts.len())),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child(if self.alert_form.open { "▾" } else { "▸" }),
+            )
+            .on_click(cx.listener(|this, _, _, cx| {
+                this.alert_form.open = !this.alert_form.open;
+                if !this.alert_form.open && this.focus.is_focused(focus::InputField::AlertPrice) {
+                    this.set_input_focus(None);
+                }
+                cx.notify();
+            }));
+
+        if !self.alert_form.open {
+            return div()
+                .flex()
+                .flex_col()
+                .pt_4()
+                .border_t_1()
+                .border_color(rgb(0x30363d))
+                .child(header);
+        }
+
+        let focused = self.focus.is_focused(focus::InputField::AlertPrice);
+        let is_percent = self.alert_form.kind.is_percent();
+        let kind_chips = alerts::AlertKind::ALL
+            .iter()
+            .map(|&kind| {
+                self.render_filter_chip(
+                    format!("alert-kind-{}", kind.label()),
+                    kind.label(),
+                    self.alert_form.kind == kind,
+                    cx,
+                    move |this, _| this.alert_form.kind = kind,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .pt_4()
+            .border_t_1()
+            .border_color(rgb(0x30363d))
+            .child(header)
+            .child(div().flex().flex_wrap().gap_1().children(kind_chips).child(
+                self.render_filter_chip(
+                    "alert-repeating".to_string(),
+                    "Repeat",
+                    self.alert_form.repeating,
+                    cx,
+                    |this, _| this.alert_form.repeating = !this.alert_form.repeating,
+                ),
+            ))
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(
+                        div()
+                            .id("alert-price-input")
+                            .flex_1()
+                            .px_3()
+                            .py_1()
+                            .bg(if focused {
+                                rgb(0x1f2937)
+                            } else {
+                                rgb(0x0d1117)
+                            })
+                            .border_1()
+                            .border_color(if focused {
+                                rgb(0x1f6feb)
+                            } else {
+                                rgb(0x30363d)
+                            })
+                            .rounded_md()
+                            .text_sm()
+                            .text_color(rgb(0xffffff))
+                            .cursor_text()
+                            .child(if focused {
+                                format!("{}|", self.alert_form.price)
+                            } else if self.alert_form.price.is_empty() {
+                                if is_percent {
+                                    "Percent...".to_string()
+                                } else {
+                                    format!("{} price...", self.chart.symbol)
+                                }
+                            } else if is_percent {
+                                format!("{}%", self.alert_form.price)
+                            } else {
+                                format!("${}", self.alert_form.price)
+                            })
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.set_input_focus(Some(focus::InputField::AlertPrice));
+                                window.focus(&this.focus_handle);
+                                cx.notify();
+                            })),
                     )
                     .child(
-                        // Order type (Market/Limit)
                         div()
-                            .flex()
-                            .flex_col()
-                            .gap_2()
-                            .child(
-                                div()
-                                    .text_sm()
-                                    .font_weight(FontWeight::SEMIBOLD)
-                                    .text_color(rgb(0xffffff))
-                                    .child("Order Type"),
-                            )
-                            .child(
-                                div()
-                                    .flex()
-                                    .gap_2()
-                                    .child(
-                                        div()
-                                            .id("order-type-market-btn")
-                                            .flex_1()
-                                            .px_3()
-                                            .py_2()
-                                            .rounded_md()
-                                            .text_center()
-                                            .font_weight(FontWeight::SEMIBOLD)
-                                            .cursor_pointer()
-                                            .bg(if matches!(self.account.order_type, OrderType::Market) {
-                                                rgb(0x1f6feb)
-                                            } else {
-                                                rgb(0x21262d)
-                                            })
-                                            .text_color(rgb(0xffffff))
-                                            .hover(|style| {
-                                                if matches!(self.account.order_type, OrderType::Market) {
-                                                    style.bg(rgb(0x388bfd))
-                                                } else {
-                                                    style.bg(rgb(0x30363d))
-                                                }
-                                            })
-                                            .child("Market")
-                                            .on_click(cx.listener(|this, _, _, cx| {
-                                                this.account.order_type = OrderType::Market;
-                                                cx.notify();
-                                            })),
-                                    )
-                                    .child(
-                                        div()
-                                            .id("order-type-limit-btn")
-                                            .flex_1()
-                                            .px_3()
-                                            .py_2()
-                                            .rounded_md()
-                                            .text_center()
-                                            .font_weight(FontWeight::SEMIBOLD)
-                                            .cursor_pointer()
-                                            .bg(if matches!(self.account.order_type, OrderType::Limit) {
-                                                rgb(0x1f6feb)
-                                            } else {
-                                                rgb(0x21262d)
-                                            })
-                                            .text_color(rgb(0xffffff))
-                                            .hover(|style| {
-                                                if matches!(self.account.order_type, OrderType::Limit) {
-                                                    style.bg(rgb(0x388bfd))
-                                                } else {
-                                                    style.bg(rgb(0x30363d))
-                                                }
-                                            })
-                                            .child("Limit")
-                                            .on_click(cx.listener(|this, _, _, cx| {
-                                                this.account.order_type = OrderType::Limit;
-                                                cx.notify();
-                                            })),
-                                    ),
-                            ),
+                            .id("add-alert-button")
+                            .px_3()
+                            .py_1()
+                            .bg(rgb(0x1f6feb))
+                            .rounded_md()
+                            .text_sm()
+                            .text_color(rgb(0xffffff))
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x388bfd)))
+                            .child("Add")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.add_alert(cx);
+                            })),
+                    ),
+            )
+            .children(self.alerts.alerts.iter().map(|alert| {
+                let id = alert.id;
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_2()
+                    .text_xs()
+                    .child(
+                        div()
+                            .text_color(if alert.armed {
+                                rgb(0xffffff)
+                            } else {
+                                rgb(0x8b949e)
+                            })
+                            .child(format!(
+                                "{} {}{}",
+                                alert.symbol,
+                                alert.condition.describe(),
+                                if alert.repeating { " ↻" } else { "" }
+                            )),
+                    )
+                    .child(
+                        div()
+                            .id(ElementId::Name(format!("remove-alert-{}", id).into()))
+                            .text_color(rgb(0x8b949e))
+                            .cursor_pointer()
+                            .hover(|style| style.text_color(rgb(0xff4444)))
+                            .child("✕")
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.alerts.remove(id);
+                                this.alerts.save();
+                                this.sync_market_data();
+                                cx.notify();
+                            })),
+                    )
+            }))
+    }
+
+    fn render_recurring_panel(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let header =
+            div()
+                .id("recurring-panel-toggle")
+                .flex()
+                .items_center()
+                .justify_between()
+                .cursor_pointer()
+                .child(
+                    div()
+                        .text_sm()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .text_color(rgb(0xffffff))
+                        .child(format!("Recurring Buys ({})", self.recurring.plans.len())),
+                )
+                .child(div().text_xs().text_color(rgb(0x8b949e)).child(
+                    if self.recurring_form.open {
+                        "▾"
+                    } else {
+                        "▸"
+                    },
+                ))
+                .on_click(cx.listener(|this, _, _, cx| {
+                    this.recurring_form.open = !this.recurring_form.open;
+                    if !this.recurring_form.open
+                        && this.focus.is_focused(focus::InputField::RecurringAmount)
+                    {
+                        this.set_input_focus(None);
+                    }
+                    cx.notify();
+                }));
+
+        if !self.recurring_form.open {
+            return div()
+                .flex()
+                .flex_col()
+                .pt_4()
+                .border_t_1()
+                .border_color(rgb(0x30363d))
+                .child(header);
+        }
+
+        let focused = self.focus.is_focused(focus::InputField::RecurringAmount);
+        let weekday_chips = recurring::WEEKDAYS
+            .iter()
+            .map(|&weekday| {
+                self.render_filter_chip(
+                    format!("recurring-weekday-{}", weekday),
+                    match weekday {
+                        chrono::Weekday::Mon => "Mon",
+                        chrono::Weekday::Tue => "Tue",
+                        chrono::Weekday::Wed => "Wed",
+                        chrono::Weekday::Thu => "Thu",
+                        _ => "Fri",
+                    },
+                    self.recurring_form.weekday == weekday,
+                    cx,
+                    move |this, _| this.recurring_form.weekday = weekday,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .pt_4()
+            .border_t_1()
+            .border_color(rgb(0x30363d))
+            .child(header)
+            .child(div().flex().flex_wrap().gap_1().children(weekday_chips))
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(
+                        div()
+                            .id("recurring-amount-input")
+                            .flex_1()
+                            .px_3()
+                            .py_1()
+                            .bg(if focused {
+                                rgb(0x1f2937)
+                            } else {
+                                rgb(0x0d1117)
+                            })
+                            .border_1()
+                            .border_color(if focused {
+                                rgb(0x1f6feb)
+                            } else {
+                                rgb(0x30363d)
+                            })
+                            .rounded_md()
+                            .text_sm()
+                            .text_color(rgb(0xffffff))
+                            .cursor_text()
+                            .child(if focused {
+                                format!("${}|", self.recurring_form.amount)
+                            } else if self.recurring_form.amount.is_empty() {
+                                format!("$ of {} at open...", self.chart.symbol)
+                            } else {
+                                format!("${}", self.recurring_form.amount)
+                            })
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.set_input_focus(Some(focus::InputField::RecurringAmount));
+                                window.focus(&this.focus_handle);
+                                cx.notify();
+                            })),
                     )
                     .child(
-                        // Quantity input
+                        div()
+                            .id("add-recurring-button")
+                            .px_3()
+                            .py_1()
+                            .bg(rgb(0x1f6feb))
+                            .rounded_md()
+                            .text_sm()
+                            .text_color(rgb(0xffffff))
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x388bfd)))
+                            .child("Add")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.add_recurring_plan(cx);
+                            })),
+                    ),
+            )
+            .children(self.recurring.plans.iter().map(|plan| {
+                let id = plan.id;
+                let next = plan
+                    .next_at
+                    .with_timezone(&chrono_tz::America::New_York)
+                    .format("%a %b %-d %H:%M ET");
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .text_xs()
+                    .child(
                         div()
                             .flex()
-                            .flex_col()
-                            .gap_2()
-                            .child(
-                                div()
-                                    .text_sm()
-                                    .font_weight(FontWeight::SEMIBOLD)
-                                    .text_color(rgb(0xffffff))
-                                    .child("Quantity"),
-                            )
+                            .items_center()
+                            .justify_between()
+                            .gap_2()
+                            .child(div().text_color(rgb(0xffffff)).child(plan.describe()))
                             .child(
                                 div()
-                                    .id("order-quantity-input")
-                                    .px_3()
-                                    .py_2()
-                                    .bg(if self.account.quantity_focused {
-                                        rgb(0x1f2937)
-                                    } else {
-                                        rgb(0x0d1117)
-                                    })
-                                    .border_1()
-                                    .border_color(if self.account.quantity_focused {
-                                        rgb(0x1f6feb)
-                                    } else {
-                                        rgb(0x30363d)
-                                    })
-                                    .rounded_md()
-                                    .text_color(rgb(0xffffff))
-                                    .cursor_text()
-                                    .child(if self.account.quantity_focused {
-                                        format!("{}|", self.account.order_quantity)
-                                    } else if self.account.order_quantity.is_empty() {
-                                        "Enter quantity...".to_string()
-                                    } else {
-                                        self.account.order_quantity.clone()
-                                    })
-                                    .on_click(cx.listener(|this, _, _window, cx| {
-                                        this.account.quantity_focused = true;
-                                        this.chart.input_focused = false;
-                                        this.account.price_focused = false;
-                                        _window.focus(&this.focus_handle);
+                                    .id(ElementId::Name(format!("remove-recurring-{}", id).into()))
+                                    .text_color(rgb(0x8b949e))
+                                    .cursor_pointer()
+                                    .hover(|style| style.text_color(rgb(0xff4444)))
+                                    .child("✕")
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.recurring.remove(id);
+                                        this.recurring.save();
                                         cx.notify();
                                     })),
                             ),
                     )
                     .child(
-                        // Limit price input (shown only for limit orders)
-                        self.render_limit_price_input(cx),
-                    )
-                    .child(
-                        // Time in Force (shown only for limit orders)
-                        self.render_time_in_force(cx),
+                        div()
+                            .text_color(rgb(0x8b949e))
+                            .child(format!("Next: {}", next)),
                     )
-                    .child(
-                        // Submit button
+                    // Last few runs, newest first
+                    .children(plan.history.iter().rev().take(3).map(|occurrence| {
                         div()
-                            .id("submit-order-button")
-                            .px_4()
-                            .py_3()
-                            .mt_4()
-                            .bg(if matches!(self.account.order_side, OrderSide::Buy) {
-                                rgb(0x238636)
-                            } else {
-                                rgb(0xda3633)
-                            })
-                            .rounded_md()
-                            .text_center()
-                            .text_color(rgb(0xffffff))
-                            .font_weight(FontWeight::BOLD)
-                            .cursor_pointer()
-                            .hover(|style| {
-                                if matches!(self.account.order_side, OrderSide::Buy) {
-                                    style.bg(rgb(0x2ea043))
-                                } else {
-                                    style.bg(rgb(0xff4444))
-                                }
-                            })
-                            .child(if self.account.order_submitting {
-                                "Submitting...".to_string()
-                            } else {
-                                format!(
-                                    "{} {}",
-                                    if matches!(self.account.order_side, OrderSide::Buy) {
-                                        "Buy"
-                                    } else {
-                                        "Sell"
-                                    },
-                                    self.chart.symbol
-                                )
+                            .pl_2()
+                            .text_color(match occurrence.outcome {
+                                recurring::Outcome::Submitted { .. } => rgb(0x3fb950),
+                                recurring::Outcome::Failed { .. }
+                                | recurring::Outcome::Blocked { .. } => rgb(0xff4444),
+                                _ => rgb(0x8b949e),
                             })
-                            .on_click(cx.listener(|this, _, _, cx| {
-                                if !this.account.order_submitting {
-                                    this.submit_order(cx);
-                                }
-                            })),
-                    )
-                    .child(self.render_order_message(cx)),
-            )
-    }
-}
-
-impl TradingTerminal {
-    fn render_account_tab(&self) -> impl IntoElement {
-        div()
-            .flex()
-            .gap_6()
-            .text_sm()
-            .child(
-                self.render_account_stat(
-                    "Account Number".to_string(),
-                    self.account
-                        .account_number
-                        .clone()
-                        .unwrap_or("Loading...".to_string()),
-                    rgb(0xa371f7),
-                ),
-            )
-            .child(
-                self.render_account_stat(
-                    "Account Status".to_string(),
-                    self.account
-                        .account_status
-                        .clone()
-                        .unwrap_or("Loading...".to_string()),
-                    rgb(0x58a6ff),
-                ),
-            )
-            .child(self.render_account_stat(
-                "Portfolio Value".to_string(),
-                format!("${:.2}", self.account.portfolio_value.unwrap_or(0.0)),
-                rgb(0x3fb950),
-            ))
-            .child(self.render_account_stat(
-                "Equity".to_string(),
-                format!("${:.2}", self.account.equity.unwrap_or(0.0)),
-                rgb(0x3fb950),
-            ))
-            .child(self.render_account_stat(
-                "Cash".to_string(),
-                format!("${:.2}", self.account.cash.unwrap_or(0.0)),
-                rgb(0xf2cc60),
-            ))
-            .child(self.render_account_stat(
-                "Buying Power".to_string(),
-                format!("${:.2}", self.account.buying_power.unwrap_or(0.0)),
-                rgb(0xf2cc60),
-            ))
+                            .child(format!(
+                                "{} {}",
+                                occurrence
+                                    .at
+                                    .with_timezone(&chrono_tz::America::New_York)
+                                    .format("%b %-d"),
+                                occurrence.outcome.describe()
+                            ))
+                    }))
+            }))
     }
 
-    fn render_positions_tab(&self, cx: &mut Context<Self>) -> impl IntoElement {
-        if self.account.positions_loading {
-            return div()
-                .grid()
+    fn render_automation_panel(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let armed = self.automation.armed;
+        let header =
+            div()
+                .id("automation-panel-toggle")
+                .flex()
                 .items_center()
-                .justify_center()
-                .p_6()
-                .text_color(rgb(0x8b949e))
-                .child("Loading positions...");
-        }
+                .justify_between()
+                .cursor_pointer()
+                .child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .child(
+                            div()
+                                .text_sm()
+                                .font_weight(FontWeight::SEMIBOLD)
+                                .text_color(rgb(0xffffff))
+                                .child(format!("Automation ({})", self.automation.rules.len())),
+                        )
+                        .when(armed, |this| {
+                            this.child(
+                                div()
+                                    .text_xs()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(rgb(0x3fb950))
+                                    .child("ARMED"),
+                            )
+                        }),
+                )
+                .child(div().text_xs().text_color(rgb(0x8b949e)).child(
+                    if self.automation_form.open {
+                        "▾"
+                    } else {
+                        "▸"
+                    },
+                ))
+                .on_click(cx.listener(|this, _, _, cx| {
+                    this.automation_form.open = !this.automation_form.open;
+                    if !this.automation_form.open
+                        && matches!(this.focus.focused(), Some(focus::InputField::Automation(_)))
+                    {
+                        this.set_input_focus(None);
+                    }
+                    cx.notify();
+                }));
 
-        if self.account.positions.is_empty() {
+        if !self.automation_form.open {
             return div()
-                .grid()
-                .items_center()
-                .justify_center()
-                .p_6()
-                .text_color(rgb(0x8b949e))
-                .child("No active positions");
+                .flex()
+                .flex_col()
+                .pt_4()
+                .border_t_1()
+                .border_color(rgb(0x30363d))
+                .child(header);
         }
 
+        let kind_chips = automation::TriggerKind::ALL
+            .iter()
+            .map(|&kind| {
+                self.render_filter_chip(
+                    format!("automation-kind-{}", kind.label()),
+                    kind.label(),
+                    self.automation_form.kind == kind,
+                    cx,
+                    move |this, _| this.automation_form.kind = kind,
+                )
+            })
+            .collect::<Vec<_>>();
+        let side_chips = [hotkeys::HotkeySide::Buy, hotkeys::HotkeySide::Sell]
+            .iter()
+            .map(|&side| {
+                self.render_filter_chip(
+                    format!("automation-side-{}", side.label()),
+                    side.label(),
+                    self.automation_form.side == side,
+                    cx,
+                    move |this, _| this.automation_form.side = side,
+                )
+            })
+            .collect::<Vec<_>>();
+
         div()
-            .grid()
-            .grid_cols(1)
+            .flex()
+            .flex_col()
             .gap_2()
+            .pt_4()
+            .border_t_1()
+            .border_color(rgb(0x30363d))
+            .child(header)
+            .child(
+                div()
+                    .id("automation-arm-toggle")
+                    .px_3()
+                    .py_1()
+                    .rounded_md()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0xffffff))
+                    .cursor_pointer()
+                    .when(armed, |this| {
+                        this.bg(rgb(0x238636))
+                            .hover(|style| style.bg(rgb(0x2ea043)))
+                    })
+                    .when(!armed, |this| {
+                        this.bg(rgb(0x21262d))
+                            .border_1()
+                            .border_color(rgb(0x30363d))
+                            .hover(|style| style.bg(rgb(0x30363d)))
+                    })
+                    .child(if armed {
+                        "● Armed: click to disarm"
+                    } else {
+                        "○ Disarmed: click to arm"
+                    })
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.toggle_automation_armed(cx);
+                        cx.notify();
+                    })),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child(format!("New rule for {}", self.chart.symbol)),
+            )
+            .child(div().flex().flex_wrap().gap_1().children(kind_chips))
+            .child(div().flex().flex_wrap().gap_1().children(side_chips))
             .child(
-                // Table header
                 div()
                     .flex()
-                    .gap_4()
-                    .pb_2()
-                    .border_b_1()
-                    .border_color(rgb(0x30363d))
-                    .child(
-                        div()
-                            .w(px(80.0))
-                            .text_xs()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(rgb(0x8b949e))
-                            .child("Symbol"),
-                    )
-                    .child(
-                        div()
-                            .w(px(80.0))
-                            .text_xs()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(rgb(0x8b949e))
-                            .child("Qty"),
-                    )
+                    .gap_2()
+                    .child(div().flex_1().child(self.render_automation_input(
+                        automation::AutomationField::Level,
+                        "Trigger",
+                        cx,
+                    )))
+                    .child(div().flex_1().child(self.render_automation_input(
+                        automation::AutomationField::Quantity,
+                        "Qty",
+                        cx,
+                    )))
+                    .child(div().flex_1().child(self.render_automation_input(
+                        automation::AutomationField::LimitPrice,
+                        "Limit (blank: mkt)",
+                        cx,
+                    ))),
+            )
+            .child(
+                div()
+                    .id("add-automation-rule-button")
+                    .px_3()
+                    .py_1()
+                    .bg(rgb(0x1f6feb))
+                    .rounded_md()
+                    .text_sm()
+                    .text_color(rgb(0xffffff))
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgb(0x388bfd)))
+                    .child("Add Rule")
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.add_automation_rule(cx);
+                    })),
+            )
+            .when_some(self.automation_form.message.clone(), |this, message| {
+                this.child(div().text_xs().text_color(rgb(0xff4444)).child(message))
+            })
+            .children(self.automation.rules.iter().map(|rule| {
+                let id = rule.id;
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_2()
+                    .text_xs()
                     .child(
                         div()
-                            .w(px(100.0))
-                            .text_xs()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(rgb(0x8b949e))
-                            .child("Avg Entry"),
+                            .flex_1()
+                            .text_color(if rule.enabled {
+                                rgb(0xffffff)
+                            } else {
+                                rgb(0x8b949e)
+                            })
+                            .child(rule.describe()),
                     )
                     .child(
                         div()
-                            .w(px(100.0))
-                            .text_xs()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(rgb(0x8b949e))
-                            .child("Current"),
+                            .id(ElementId::Name(format!("toggle-rule-{}", id).into()))
+                            .text_color(if rule.enabled {
+                                rgb(0x3fb950)
+                            } else {
+                                rgb(0x8b949e)
+                            })
+                            .cursor_pointer()
+                            .child(if rule.enabled { "on" } else { "off" })
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.automation.toggle(id);
+                                this.automation.save();
+                                this.sync_market_data();
+                                cx.notify();
+                            })),
                     )
                     .child(
                         div()
-                            .w(px(120.0))
-                            .text_xs()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(rgb(0x8b949e))
-                            .child("Market Value"),
+                            .id(ElementId::Name(format!("live-rule-{}", id).into()))
+                            .text_color(if rule.allow_live {
+                                rgb(0xf2cc60)
+                            } else {
+                                rgb(0x8b949e)
+                            })
+                            .cursor_pointer()
+                            .child(if rule.allow_live { "live" } else { "paper" })
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.automation.toggle_live(id);
+                                this.automation.save();
+                                cx.notify();
+                            })),
                     )
                     .child(
                         div()
-                            .w(px(100.0))
-                            .text_xs()
-                            .font_weight(FontWeight::SEMIBOLD)
+                            .id(ElementId::Name(format!("remove-rule-{}", id).into()))
                             .text_color(rgb(0x8b949e))
-                            .child("P&L"),
+                            .cursor_pointer()
+                            .hover(|style| style.text_color(rgb(0xff4444)))
+                            .child("✕")
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.automation.remove(id);
+                                this.automation.save();
+                                this.sync_market_data();
+                                cx.notify();
+                            })),
                     )
+            }))
+            .when(!self.automation.log.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .text_color(rgb(0x8b949e))
+                        .child("Execution log"),
+                )
+                // Newest first
+                .children(self.automation.log.iter().rev().take(5).map(|entry| {
+                    div()
+                        .text_xs()
+                        .text_color(if entry.failed {
+                            rgb(0xff4444)
+                        } else {
+                            rgb(0x8b949e)
+                        })
+                        .child(format!(
+                            "{} {}",
+                            entry
+                                .at
+                                .with_timezone(&chrono::Local)
+                                .format("%m/%d %H:%M:%S"),
+                            entry.message
+                        ))
+                }))
+            })
+    }
+
+    fn render_automation_input(
+        &self,
+        field: automation::AutomationField,
+        label: &'static str,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let focused = self.focus.is_focused(focus::InputField::Automation(field));
+        let value = self.automation_form.field(field);
+        let id = match field {
+            automation::AutomationField::Level => "automation-level-input",
+            automation::AutomationField::Quantity => "automation-quantity-input",
+            automation::AutomationField::LimitPrice => "automation-limit-input",
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .child(div().text_xs().text_color(rgb(0x8b949e)).child(label))
+            .child(
+                div()
+                    .id(id)
+                    .px_3()
+                    .py_1()
+                    .bg(if focused {
+                        rgb(0x1f2937)
+                    } else {
+                        rgb(0x0d1117)
+                    })
+                    .border_1()
+                    .border_color(if focused {
+                        rgb(0x1f6feb)
+                    } else {
+                        rgb(0x30363d)
+                    })
+                    .rounded_md()
+                    .text_sm()
+                    .text_color(rgb(0xffffff))
+                    .cursor_text()
+                    .child(if focused {
+                        format!("{}|", value)
+                    } else if value.is_empty() {
+                        "—".to_string()
+                    } else {
+                        value.clone()
+                    })
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.set_input_focus(Some(focus::InputField::Automation(field)));
+                        window.focus(&this.focus_handle);
+                        cx.notify();
+                    })),
+            )
+    }
+
+    fn render_scripts_panel(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let header = div()
+            .id("scripts-panel-toggle")
+            .flex()
+            .items_center()
+            .justify_between()
+            .cursor_pointer()
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0xffffff))
+                    .child(format!("Scripts ({})", self.scripts.names.len())),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child(if self.scripts.open { "▾" } else { "▸" }),
+            )
+            .on_click(cx.listener(|this, _, _, cx| {
+                this.scripts.open = !this.scripts.open;
+                if this.scripts.open {
+                    this.refresh_scripts();
+                }
+                cx.notify();
+            }));
+
+        if !self.scripts.open {
+            return div()
+                .flex()
+                .flex_col()
+                .pt_4()
+                .border_t_1()
+                .border_color(rgb(0x30363d))
+                .child(header);
+        }
+
+        let paper_only = self.profiles.environment() == credentials::Environment::Live
+            && !self.config.scripting.allow_live;
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .pt_4()
+            .border_t_1()
+            .border_color(rgb(0x30363d))
+            .child(header)
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_2()
                     .child(
-                        div()
-                            .w(px(80.0))
-                            .text_xs()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(rgb(0x8b949e))
-                            .child("P&L %"),
+                        div().text_xs().text_color(rgb(0x8b949e)).child(
+                            scripting::scripts_dir()
+                                .map(|dir| dir.display().to_string())
+                                .unwrap_or_default(),
+                        ),
                     )
                     .child(
                         div()
-                            .w(px(80.0))
+                            .id("scripts-reload")
                             .text_xs()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(rgb(0x8b949e))
-                            .child("Action"),
+                            .text_color(rgb(0x58a6ff))
+                            .cursor_pointer()
+                            .child("↻ Reload")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.refresh_scripts();
+                                cx.notify();
+                            })),
                     ),
             )
-            .children(self.account.positions.iter().enumerate().map(|(idx, pos)| {
-                let pl_value = pos.unrealized_pl.parse::<f64>().unwrap_or(0.0);
-                let pl_color = if pl_value > 0.0 {
-                    rgb(0x3fb950)
-                } else if pl_value < 0.0 {
-                    rgb(0xff4444)
-                } else {
-                    rgb(0x8b949e)
-                };
-
+            .when(paper_only, |this| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(rgb(0xf2cc60))
+                        .child("Live account: script orders are blocked (scripting.allow_live)"),
+                )
+            })
+            .children(self.scripts.names.iter().map(|name| {
+                let running = self.scripts.running.contains(name);
+                let auto = self.scripts.auto.contains(name);
+                let run_name = name.clone();
+                let auto_name = name.clone();
                 div()
                     .flex()
-                    .gap_4()
-                    .py_2()
-                    .child(
-                        div()
-                            .w(px(80.0))
-                            .text_sm()
-                            .text_color(rgb(0xffffff))
-                            .child(pos.symbol.clone()),
-                    )
-                    .child(
-                        div()
-                            .w(px(80.0))
-                            .text_sm()
-                            .text_color(rgb(0x8b949e))
-                            .child(pos.qty.clone()),
-                    )
-                    .child(
-                        div()
-                            .w(px(100.0))
-                            .text_sm()
-                            .text_color(rgb(0x8b949e))
-                            .child(format!("${}", pos.avg_entry_price)),
-                    )
+                    .items_center()
+                    .justify_between()
+                    .gap_2()
+                    .text_xs()
+                    .child(div().flex_1().text_color(rgb(0xffffff)).child(name.clone()))
                     .child(
                         div()
-                            .w(px(100.0))
-                            .text_sm()
-                            .text_color(rgb(0x8b949e))
-                            .child(format!("${}", pos.current_price)),
+                            .id(ElementId::Name(format!("script-auto-{}", name).into()))
+                            .px_2()
+                            .rounded_md()
+                            .cursor_pointer()
+                            .when(auto, |this| {
+                                this.bg(rgb(0x1f6feb)).text_color(rgb(0xffffff))
+                            })
+                            .when(!auto, |this| this.text_color(rgb(0x8b949e)))
+                            .child("Each bar")
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                if !this.scripts.auto.remove(&auto_name) {
+                                    this.scripts.auto.insert(auto_name.clone());
+                                }
+                                cx.notify();
+                            })),
                     )
                     .child(
                         div()
-                            .w(px(120.0))
-                            .text_sm()
+                            .id(ElementId::Name(format!("script-run-{}", name).into()))
+                            .px_2()
+                            .rounded_md()
+                            .bg(rgb(0x238636))
                             .text_color(rgb(0xffffff))
-                            .child(format!("${}", pos.market_value)),
-                    )
-                    .child(
-                        div()
-                            .w(px(100.0))
-                            .text_sm()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(pl_color)
-                            .child(format!("${}", pos.unrealized_pl)),
-                    )
-                    .child(
-                        div()
-                            .w(px(80.0))
-                            .text_sm()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(pl_color)
-                            .child(format!("{}%", pos.unrealized_plpc)),
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x2ea043)))
+                            .child(if running { "Running..." } else { "▶ Run" })
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.run_script(run_name.clone(), cx);
+                            })),
                     )
-                    .child(
-                        div().w(px(80.0)).child(
+            }))
+            .when(!self.scripts.output.is_empty(), |this| {
+                this.child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .justify_between()
+                        .child(
                             div()
-                                .id(ElementId::Name(format!("close-position-{}", idx).into()))
-                                .px_3()
-                                .py_1()
-                                .bg(rgb(0xf2cc60))
-                                .rounded_md()
                                 .text_xs()
-                                .text_color(rgb(0x000000))
                                 .font_weight(FontWeight::SEMIBOLD)
+                                .text_color(rgb(0x8b949e))
+                                .child("Output"),
+                        )
+                        .child(
+                            div()
+                                .id("scripts-clear-output")
+                                .text_xs()
+                                .text_color(rgb(0x8b949e))
                                 .cursor_pointer()
-                                .hover(|style| style.bg(rgb(0xffd700)))
-                                .child("Close")
-                                .on_click({
-                                    let symbol = pos.symbol.clone();
-                                    cx.listener(move |this, _, _, cx| {
-                                        this.close_position(symbol.clone(), cx);
-                                    })
-                                }),
+                                .hover(|style| style.text_color(rgb(0xffffff)))
+                                .child("Clear")
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.scripts.output.clear();
+                                    cx.notify();
+                                })),
                         ),
-                    )
-            }))
+                )
+                // Newest first
+                .children(self.scripts.output.iter().rev().take(10).map(|line| {
+                    div()
+                        .text_xs()
+                        .text_color(if line.is_error {
+                            rgb(0xff4444)
+                        } else {
+                            rgb(0x8b949e)
+                        })
+                        .child(format!(
+                            "{} [{}] {}",
+                            line.at.format("%H:%M:%S"),
+                            line.script,
+                            line.text
+                        ))
+                }))
+            })
     }
 
-    fn render_orders_tab(&self, cx: &mut Context<Self>) -> impl IntoElement {
-        if self.account.orders_loading {
-            return div()
-                .grid()
-                .items_center()
-                .justify_center()
-                .p_6()
-                .text_color(rgb(0x8b949e))
-                .child("Loading orders...");
-        }
+    fn render_strategy_builder(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let strategy = &self.strategy;
+        let halted = self.trading_halted();
 
-        if self.account.orders.is_empty() {
+        let header = div()
+            .id("strategy-builder-toggle")
+            .flex()
+            .items_center()
+            .justify_between()
+            .cursor_pointer()
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(rgb(0xffffff))
+                    .child("Options Strategy"),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(rgb(0x8b949e))
+                    .child(if strategy.open { "▾" } else { "▸" }),
+            )
+            .on_click(cx.listener(|this, _, _, cx| {
+                this.strategy.open = !this.strategy.open;
+                if !this.strategy.open
+                    && matches!(this.focus.focused(), Some(focus::InputField::Strategy(_)))
+                {
+                    this.set_input_focus(None);
+                }
+                cx.notify();
+            }));
+
+        if !strategy.open {
             return div()
-                .grid()
-                .items_center()
-                .justify_center()
-                .p_6()
-                .text_color(rgb(0x8b949e))
-                .child("No active orders");
+                .flex()
+                .flex_col()
+                .pt_4()
+                .border_t_1()
+                .border_color(rgb(0x30363d))
+                .child(header);
         }
 
+        let kind_chips = options::StrategyKind::ALL
+            .iter()
+            .map(|&kind| {
+                self.render_filter_chip(
+                    format!("strategy-kind-{}", kind.label()),
+                    kind.label(),
+                    strategy.kind == kind,
+                    cx,
+                    move |this, _| {
+                        this.strategy.kind = kind;
+                        this.strategy.message = None;
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let strike_inputs = strategy
+            .kind
+            .strike_labels()
+            .iter()
+            .enumerate()
+            .map(|(index, label)| {
+                self.render_strategy_input(options::StrategyField::Strike(index), label, cx)
+            })
+            .collect::<Vec<_>>();
+
+        let legs = strategy.legs(&self.chart.symbol).ok();
+
         div()
-            .grid()
-            .grid_cols(1)
-            .gap_2()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .pt_4()
+            .border_t_1()
+            .border_color(rgb(0x30363d))
+            .child(header)
+            .child(div().flex().flex_wrap().gap_1().children(kind_chips))
+            .when(strategy.kind == options::StrategyKind::Vertical, |this| {
+                this.child(
+                    div()
+                        .flex()
+                        .gap_1()
+                        .child(self.render_filter_chip(
+                            "strategy-type-call".to_string(),
+                            options::OptionType::Call.label(),
+                            strategy.option_type == options::OptionType::Call,
+                            cx,
+                            |this, _| this.strategy.option_type = options::OptionType::Call,
+                        ))
+                        .child(self.render_filter_chip(
+                            "strategy-type-put".to_string(),
+                            options::OptionType::Put.label(),
+                            strategy.option_type == options::OptionType::Put,
+                            cx,
+                            |this, _| this.strategy.option_type = options::OptionType::Put,
+                        )),
+                )
+            })
+            .child(self.render_strategy_input(
+                options::StrategyField::Expiration,
+                "Expiration (YYYY-MM-DD)",
+                cx,
+            ))
+            .children(strike_inputs)
+            .child(self.render_strategy_input(options::StrategyField::Quantity, "Contracts", cx))
+            .child(self.render_strategy_input(
+                options::StrategyField::NetPrice,
+                "Net Price (debit +, credit -)",
+                cx,
+            ))
+            .when_some(legs, |this, legs| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .children(legs.into_iter().map(|leg| {
+                            div()
+                                .flex()
+                                .justify_between()
+                                .text_xs()
+                                .child(
+                                    div()
+                                        .text_color(if leg.is_buy {
+                                            rgb(0x3fb950)
+                                        } else {
+                                            rgb(0xff4444)
+                                        })
+                                        .child(if leg.is_buy { "BUY" } else { "SELL" }),
+                                )
+                                .child(div().text_color(rgb(0xc9d1d9)).child(leg.symbol))
+                        })),
+                )
+            })
+            .child(self.render_payoff_preview())
+            .when(halted, |this| this.child(self.render_loss_limit_notice(cx)))
+            .when(!halted, |this| {
+                this.child(
+                    div()
+                        .id("submit-strategy-button")
+                        .px_4()
+                        .py_2()
+                        .bg(rgb(0x1f6feb))
+                        .rounded_md()
+                        .text_center()
+                        .text_sm()
+                        .text_color(rgb(0xffffff))
+                        .font_weight(FontWeight::BOLD)
+                        .cursor_pointer()
+                        .hover(|style| style.bg(rgb(0x388bfd)))
+                        .child(if strategy.submitting {
+                            "Submitting...".to_string()
+                        } else {
+                            format!("Submit {}", strategy.kind.label())
+                        })
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            if !this.strategy.submitting {
+                                this.submit_strategy(cx);
+                            }
+                        })),
+                )
+            })
+            .when_some(strategy.message.clone(), |this, message| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(if message.starts_with("✓") {
+                            rgb(0x3fb950)
+                        } else {
+                            rgb(0xff4444)
+                        })
+                        .child(message),
+                )
+            })
+    }
+
+    fn render_strategy_input(
+        &self,
+        field: options::StrategyField,
+        label: &'static str,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let focused = self.focus.is_focused(focus::InputField::Strategy(field));
+        let value = match field {
+            options::StrategyField::Expiration => &self.strategy.expiration,
+            options::StrategyField::Strike(index) => &self.strategy.strikes[index],
+            options::StrategyField::Quantity => &self.strategy.quantity,
+            options::StrategyField::NetPrice => &self.strategy.net_price,
+        };
+        let id = match field {
+            options::StrategyField::Expiration => "strategy-expiration-input".to_string(),
+            options::StrategyField::Strike(index) => format!("strategy-strike-{}-input", index),
+            options::StrategyField::Quantity => "strategy-quantity-input".to_string(),
+            options::StrategyField::NetPrice => "strategy-net-price-input".to_string(),
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .child(div().text_xs().text_color(rgb(0x8b949e)).child(label))
             .child(
-                // Table header
                 div()
-                    .flex()
-                    .gap_4()
-                    .pb_2()
-                    .border_b_1()
-                    .border_color(rgb(0x30363d))
-                    .child(
-                        div()
-                            .w(px(80.0))
-                            .text_xs()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(rgb(0x8b949e))
-                            .child("Symbol"),
-                    )
-                    .child(
-                        div()
-                            .w(px(60.0))
-                            .text_xs()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(rgb(0x8b949e))
-                            .child("Side"),
-                    )
-                    .child(
-                        div()
-                            .w(px(80.0))
-                            .text_xs()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(rgb(0x8b949e))
-                            .child("Qty"),
-                    )
-                    .child(
-                        div()
-                            .w(px(80.0))
-                            .text_xs()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(rgb(0x8b949e))
-                            .child("Type"),
-                    )
-                    .child(
-                        div()
-                            .w(px(100.0))
-                            .text_xs()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(rgb(0x8b949e))
-                            .child("Limit Price"),
-                    )
-                    .child(
-                        div()
-                            .w(px(100.0))
-                            .text_xs()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(rgb(0x8b949e))
-                            .child("Status"),
-                    )
+                    .id(ElementId::Name(id.into()))
+                    .px_3()
+                    .py_1()
+                    .bg(if focused {
+                        rgb(0x1f2937)
+                    } else {
+                        rgb(0x0d1117)
+                    })
+                    .border_1()
+                    .border_color(if focused {
+                        rgb(0x1f6feb)
+                    } else {
+                        rgb(0x30363d)
+                    })
+                    .rounded_md()
+                    .text_sm()
+                    .text_color(rgb(0xffffff))
+                    .cursor_text()
+                    .child(if focused {
+                        format!("{}|", value)
+                    } else if value.is_empty() {
+                        "—".to_string()
+                    } else {
+                        value.clone()
+                    })
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.set_input_focus(Some(focus::InputField::Strategy(field)));
+                        window.focus(&this.focus_handle);
+                        cx.notify();
+                    })),
+            )
+    }
+
+    /// Expiration payoff for the strategy: max profit/loss, breakevens and a bar profile
+    fn render_payoff_preview(&self) -> impl IntoElement {
+        let Some(payoff) = self.strategy.payoff(&self.chart.symbol) else {
+            return div()
+                .text_xs()
+                .text_color(rgb(0x8b949e))
+                .child("Fill in the legs to preview the payoff");
+        };
+
+        let numbers = &self.config.numbers.format;
+        let format_bound = |value: Option<f64>| match value {
+            Some(v) => numbers.price(v),
+            None => "Unlimited".to_string(),
+        };
+        let breakevens = if payoff.breakevens.is_empty() {
+            "—".to_string()
+        } else {
+            payoff
+                .breakevens
+                .iter()
+                .map(|b| numbers.price(*b))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let peak = payoff
+            .samples
+            .iter()
+            .map(|(_, pnl)| pnl.abs())
+            .fold(0.0, f64::max)
+            .max(f64::EPSILON);
+        let half_height = 30.0;
+        let low = payoff.samples.first().map(|s| s.0).unwrap_or(0.0);
+        let high = payoff.samples.last().map(|s| s.0).unwrap_or(0.0);
+
+        // Each sample is a column: profit grows up from the midline, loss grows down
+        let columns = payoff.samples.iter().map(|(_, pnl)| {
+            let height = (pnl.abs() / peak * half_height) as f32;
+            div()
+                .flex_1()
+                .flex()
+                .flex_col()
+                .child(
+                    div()
+                        .h(px(half_height as f32))
+                        .flex()
+                        .flex_col()
+                        .justify_end()
+                        .border_b_1()
+                        .border_color(rgb(0x30363d))
+                        .when(*pnl > 0.0, |this| {
+                            this.child(div().h(px(height)).bg(rgb(0x238636)))
+                        }),
+                )
+                .child(div().h(px(half_height as f32)).when(*pnl < 0.0, |this| {
+                    this.child(div().h(px(height)).bg(rgb(0xda3633)))
+                }))
+        });
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .p_2()
+            .bg(rgb(0x0d1117))
+            .border_1()
+            .border_color(rgb(0x30363d))
+            .rounded_md()
+            .text_xs()
+            .child(
+                div()
+                    .flex()
+                    .justify_between()
+                    .child(div().text_color(rgb(0x8b949e)).child("Max Profit"))
                     .child(
                         div()
-                            .w(px(150.0))
-                            .text_xs()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(rgb(0x8b949e))
-                            .child("Created At"),
-                    )
+                            .text_color(rgb(0x3fb950))
+                            .child(format_bound(payoff.max_profit)),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .justify_between()
+                    .child(div().text_color(rgb(0x8b949e)).child("Max Loss"))
                     .child(
                         div()
-                            .w(px(80.0))
-                            .text_xs()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(rgb(0x8b949e))
-                            .child("Action"),
+                            .text_color(rgb(0xff4444))
+                            .child(format_bound(payoff.max_loss)),
                     ),
             )
-            .children(self.account.orders.iter().enumerate().map(|(idx, order)| {
-                let side_color = if order.side.to_lowercase().contains("buy") {
-                    rgb(0x3fb950)
-                } else {
-                    rgb(0xff4444)
-                };
+            .child(
+                div()
+                    .flex()
+                    .justify_between()
+                    .child(div().text_color(rgb(0x8b949e)).child("Breakeven"))
+                    .child(div().text_color(rgb(0xffffff)).child(breakevens)),
+            )
+            .child(div().flex().items_end().mt_1().children(columns))
+            .child(
+                div()
+                    .flex()
+                    .justify_between()
+                    .text_color(rgb(0x8b949e))
+                    .child(format!("${}", numbers.number(low, 0)))
+                    .child(format!("${}", numbers.number(high, 0))),
+            )
+    }
 
-                let status_color = match order.status.to_lowercase().as_str() {
-                    s if s.contains("filled") => rgb(0x3fb950),
-                    s if s.contains("canceled") || s.contains("rejected") => rgb(0xff4444),
-                    s if s.contains("pending") => rgb(0xf2cc60),
-                    _ => rgb(0x58a6ff),
+    fn render_toasts(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .absolute()
+            .bottom(px(24.0))
+            .right(px(24.0))
+            .flex()
+            .flex_col()
+            .gap_2()
+            .w(px(320.0))
+            .children(self.toasts.items.iter().map(|toast| {
+                let id = toast.id;
+                let color = match toast.level {
+                    toast::ToastLevel::Info => rgb(0x58a6ff),
+                    toast::ToastLevel::Success => rgb(0x3fb950),
+                    toast::ToastLevel::Warning => rgb(0xf2cc60),
+                    toast::ToastLevel::Error => rgb(0xff4444),
                 };
 
                 div()
+                    .id(ElementId::Name(format!("toast-{}", id).into()))
                     .flex()
-                    .gap_4()
-                    .py_2()
-                    .child(
-                        div()
-                            .w(px(80.0))
-                            .text_sm()
-                            .text_color(rgb(0xffffff))
-                            .child(order.symbol.clone()),
-                    )
-                    .child(
-                        div()
-                            .w(px(60.0))
-                            .text_sm()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(side_color)
-                            .child(order.side.clone()),
-                    )
-                    .child(
-                        div()
-                            .w(px(80.0))
-                            .text_sm()
-                            .text_color(rgb(0x8b949e))
-                            .child(order.qty.clone()),
-                    )
-                    .child(
-                        div()
-                            .w(px(80.0))
-                            .text_sm()
-                            .text_color(rgb(0x8b949e))
-                            .child(order.order_type.clone()),
-                    )
-                    .child(
-                        div()
-                            .w(px(100.0))
-                            .text_sm()
-                            .text_color(rgb(0x8b949e))
-                            .child(order.limit_price.clone().unwrap_or("-".to_string())),
-                    )
+                    .items_start()
+                    .justify_between()
+                    .gap_3()
+                    .px_4()
+                    .py_3()
+                    .bg(rgb(0x161b22))
+                    .border_1()
+                    .border_l_4()
+                    .border_color(color)
+                    .rounded_md()
+                    .shadow_lg()
+                    .text_sm()
+                    .cursor_pointer()
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.toasts.dismiss(id);
+                        cx.notify();
+                    }))
                     .child(
                         div()
-                            .w(px(100.0))
-                            .text_sm()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(status_color)
-                            .child(order.status.clone()),
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(div().text_color(rgb(0xffffff)).child(toast.message.clone()))
+                            .when_some(toast.hint, |this, hint| {
+                                this.child(div().text_xs().text_color(rgb(0x8b949e)).child(hint))
+                            }),
                     )
                     .child(
                         div()
-                            .w(px(150.0))
-                            .text_sm()
-                            .text_color(rgb(0x8b949e))
-                            .child(order.created_at.clone()),
-                    )
-                    .child(
-                        div().w(px(80.0)).child(
-                            div()
-                                .id(ElementId::Name(format!("cancel-order-{}", idx).into()))
-                                .px_3()
-                                .py_1()
-                                .bg(rgb(0xda3633))
-                                .rounded_md()
-                                .text_xs()
-                                .text_color(rgb(0xffffff))
-                                .font_weight(FontWeight::SEMIBOLD)
-                                .cursor_pointer()
-                                .hover(|style| style.bg(rgb(0xff4444)))
-                                .child("Cancel")
-                                .on_click({
-                                    let order_id = order.id.clone();
-                                    cx.listener(move |this, _, _, cx| {
-                                        this.cancel_order(order_id.clone(), cx);
-                                    })
-                                }),
-                        ),
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .when_some(toast.action.clone(), |this, action| {
+                                this.child(
+                                    div()
+                                        .id(ElementId::Name(format!("toast-retry-{}", id).into()))
+                                        .px_2()
+                                        .py_1()
+                                        .bg(rgb(0x21262d))
+                                        .border_1()
+                                        .border_color(rgb(0x30363d))
+                                        .rounded_md()
+                                        .text_xs()
+                                        .text_color(rgb(0x58a6ff))
+                                        .hover(|style| style.bg(rgb(0x30363d)))
+                                        .child("Retry")
+                                        .on_click(cx.listener(move |this, _, _, cx| {
+                                            cx.stop_propagation();
+                                            this.toasts.dismiss(id);
+                                            this.retry(action.clone(), cx);
+                                            cx.notify();
+                                        })),
+                                )
+                            })
+                            .child(div().text_xs().text_color(rgb(0x8b949e)).child("✕")),
                     )
             }))
     }
 
-    fn render_limit_price_input(&self, cx: &mut Context<Self>) -> impl IntoElement {
-        if !matches!(self.account.order_type, OrderType::Limit) {
-            return div();
-        }
+    fn render_scrollable_rows(
+        &self,
+        id: &'static str,
+        rows: Vec<impl IntoElement>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .relative()
+            .w_full()
+            .child(
+                div()
+                    .id(id)
+                    .max_h(px(FOOTER_TABLE_MAX_HEIGHT))
+                    .overflow_y_scroll()
+                    .track_scroll(&self.footer_scroll)
+                    .pr_4()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .on_scroll_wheel(cx.listener(|_this, _event, _window, cx| {
+                        // Re-render so the scrollbar thumb follows the wheel
+                        cx.notify();
+                    }))
+                    .children(rows),
+            )
+            .child(self.render_footer_scrollbar(cx))
+    }
+
+    fn render_footer_scrollbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let viewport_height: f32 = self.footer_scroll.bounds().size.height.into();
+        let max_scroll: f32 = self.footer_scroll.max_offset().height.into();
+
+        // Content fits: no scrollbar
+        if max_scroll <= 0.0 || viewport_height <= 0.0 {
+            return div();
+        }
+
+        let content_height = viewport_height + max_scroll;
+        let thumb_height = (viewport_height * viewport_height / content_height).max(20.0);
+        let scrolled: f32 = (-self.footer_scroll.offset().y).into();
+        let thumb_top = (scrolled / max_scroll).clamp(0.0, 1.0) * (viewport_height - thumb_height);
+
+        div()
+            .absolute()
+            .top_0()
+            .right_0()
+            .w(px(8.0))
+            .h_full()
+            .rounded_md()
+            .bg(rgb(0x0d1117))
+            .cursor_pointer()
+            .on_mouse_down(
+                gpui::MouseButton::Left,
+                cx.listener(|this, event: &gpui::MouseDownEvent, _window, cx| {
+                    this.scroll_footer_to(event.position.y, cx);
+                }),
+            )
+            .on_mouse_move(
+                cx.listener(|this, event: &gpui::MouseMoveEvent, _window, cx| {
+                    // Drag the thumb while the left button is held
+                    if event.pressed_button == Some(gpui::MouseButton::Left) {
+                        this.scroll_footer_to(event.position.y, cx);
+                    }
+                }),
+            )
+            .child(
+                div()
+                    .absolute()
+                    .left_0()
+                    .top(px(thumb_top))
+                    .w_full()
+                    .h(px(thumb_height))
+                    .rounded_md()
+                    .bg(rgb(0x484f58))
+                    .hover(|style| style.bg(rgb(0x6e7681))),
+            )
+    }
+
+    fn render_column_header(&self, label: &'static str, width: f32) -> impl IntoElement {
+        div()
+            .w(px(width))
+            .text_xs()
+            .font_weight(FontWeight::SEMIBOLD)
+            .text_color(rgb(0x8b949e))
+            .child(label)
+    }
+
+    fn render_column_resize_handle(
+        &self,
+        table: columns::ColumnTable,
+        index: usize,
+        width: f32,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let is_dragging = self
+            .column_drag
+            .is_some_and(|drag| drag.table == table && drag.index == index);
+
+        // Sits in the gap to the right of the column
+        div()
+            .absolute()
+            .top_0()
+            .right(px(-10.0))
+            .w(px(4.0))
+            .h_full()
+            .rounded_sm()
+            .bg(if is_dragging {
+                rgb(0x1f6feb)
+            } else {
+                rgb(0x21262d)
+            })
+            .cursor_col_resize()
+            .hover(|style| style.bg(rgb(0x1f6feb)))
+            .on_mouse_down(
+                gpui::MouseButton::Left,
+                cx.listener(move |this, event: &gpui::MouseDownEvent, _window, cx| {
+                    this.column_drag = Some(columns::ColumnDrag {
+                        table,
+                        index,
+                        start_x: event.position.x.into(),
+                        start_width: width,
+                    });
+                    cx.stop_propagation();
+                    cx.notify();
+                }),
+            )
+    }
+
+    fn render_columns_menu_button(
+        &self,
+        table: columns::ColumnTable,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let is_open = self.columns_menu_open == Some(table);
+
+        div()
+            .id(match table {
+                columns::ColumnTable::Positions => "positions-columns-button",
+                columns::ColumnTable::Orders => "orders-columns-button",
+            })
+            .px_2()
+            .py_1()
+            .rounded_md()
+            .text_xs()
+            .cursor_pointer()
+            .bg(if is_open {
+                rgb(0x1f6feb)
+            } else {
+                rgb(0x21262d)
+            })
+            .text_color(if is_open {
+                rgb(0xffffff)
+            } else {
+                rgb(0x8b949e)
+            })
+            .hover(|style| style.bg(rgb(0x30363d)))
+            .child("Columns ▾")
+            .on_click(cx.listener(move |this, _, _, cx| {
+                this.columns_menu_open = if this.columns_menu_open == Some(table) {
+                    None
+                } else {
+                    Some(table)
+                };
+                cx.notify();
+            }))
+    }
+
+    fn render_position_columns_menu(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_wrap()
+            .gap_1()
+            .p_2()
+            .bg(rgb(0x0d1117))
+            .border_1()
+            .border_color(rgb(0x30363d))
+            .rounded_md()
+            .children(
+                self.settings
+                    .positions_columns
+                    .columns
+                    .iter()
+                    .map(|setting| {
+                        let column = setting.column;
+                        self.render_filter_chip(
+                            format!("positions-column-{:?}", column),
+                            column.label(),
+                            setting.visible,
+                            cx,
+                            move |this, _| {
+                                this.settings.positions_columns.toggle(column);
+                                this.settings.save();
+                            },
+                        )
+                    }),
+            )
+    }
+
+    fn render_order_columns_menu(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_wrap()
+            .gap_1()
+            .p_2()
+            .bg(rgb(0x0d1117))
+            .border_1()
+            .border_color(rgb(0x30363d))
+            .rounded_md()
+            .children(self.settings.orders_columns.columns.iter().map(|setting| {
+                let column = setting.column;
+                self.render_filter_chip(
+                    format!("orders-column-{:?}", column),
+                    column.label(),
+                    setting.visible,
+                    cx,
+                    move |this, _| {
+                        this.settings.orders_columns.toggle(column);
+                        this.settings.save();
+                    },
+                )
+            }))
+    }
+
+    fn render_position_header_cell(
+        &self,
+        column: PositionColumn,
+        width: f32,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let sort = match column {
+            PositionColumn::Symbol => {
+                Some(("positions-sort-symbol", account::PositionSortColumn::Symbol))
+            }
+            PositionColumn::MarketValue => Some((
+                "positions-sort-market-value",
+                account::PositionSortColumn::MarketValue,
+            )),
+            PositionColumn::UnrealizedPl => Some((
+                "positions-sort-pl",
+                account::PositionSortColumn::UnrealizedPl,
+            )),
+            PositionColumn::UnrealizedPlpc => Some((
+                "positions-sort-plpc",
+                account::PositionSortColumn::UnrealizedPlpc,
+            )),
+            _ => None,
+        };
+
+        match sort {
+            Some((id, sort_column)) => self
+                .render_sortable_header(
+                    id,
+                    column.label(),
+                    width,
+                    self.account.positions_sort_direction(sort_column),
+                    cx,
+                    move |this, _| this.account.toggle_positions_sort(sort_column),
+                )
+                .into_any_element(),
+            None => self
+                .render_column_header(column.label(), width)
+                .into_any_element(),
+        }
+    }
+
+    fn render_position_cell(
+        &self,
+        column: PositionColumn,
+        width: f32,
+        idx: usize,
+        pos: &account::Position,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let pl_color = pnl_color(pos.unrealized_pl.parse::<f64>().unwrap_or(0.0));
+        let numbers = &self.config.numbers.format;
+        let cell = div()
+            .w(px(width))
+            .flex_shrink_0()
+            .overflow_hidden()
+            .text_sm();
+
+        match column {
+            PositionColumn::Symbol => cell.text_color(rgb(0xffffff)).child(pos.symbol.clone()),
+            PositionColumn::Qty => cell.text_color(rgb(0x8b949e)).child(pos.qty.clone()),
+            PositionColumn::AvgEntry => cell
+                .text_color(rgb(0x8b949e))
+                .child(numbers.price_text(&pos.avg_entry_price)),
+            PositionColumn::Current => cell
+                .text_color(rgb(0x8b949e))
+                .child(numbers.price_text(&pos.current_price)),
+            PositionColumn::MarketValue => cell
+                .text_color(rgb(0xffffff))
+                .child(numbers.money_text(&pos.market_value)),
+            PositionColumn::UnrealizedPl => cell
+                .font_weight(FontWeight::SEMIBOLD)
+                .text_color(pl_color)
+                .child(numbers.money_text(&pos.unrealized_pl)),
+            PositionColumn::UnrealizedPlpc => cell
+                .font_weight(FontWeight::SEMIBOLD)
+                .text_color(pl_color)
+                .child(format!("{}%", pos.unrealized_plpc)),
+            PositionColumn::Realized => {
+                let realized_value = self.account.lot_ledger.realized_pnl(&pos.symbol);
+                cell.font_weight(FontWeight::SEMIBOLD)
+                    .text_color(pnl_color(realized_value))
+                    .child(numbers.money(realized_value))
+            }
+            PositionColumn::Action => cell.child(
+                div()
+                    .id(ElementId::Name(format!("close-position-{}", idx).into()))
+                    .px_3()
+                    .py_1()
+                    .bg(rgb(0xf2cc60))
+                    .rounded_md()
+                    .text_xs()
+                    .text_color(rgb(0x000000))
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgb(0xffd700)))
+                    .child("Close")
+                    .on_click({
+                        let symbol = pos.symbol.clone();
+                        cx.listener(move |this, _, _, cx| {
+                            // Don't also toggle the row's detail panel
+                            cx.stop_propagation();
+                            this.close_position(symbol.clone());
+                        })
+                    }),
+            ),
+        }
+        .into_any_element()
+    }
+
+    fn render_order_header_cell(
+        &self,
+        column: OrderColumn,
+        width: f32,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let sort = match column {
+            OrderColumn::Symbol => Some(("orders-sort-symbol", account::OrderSortColumn::Symbol)),
+            OrderColumn::Status => Some(("orders-sort-status", account::OrderSortColumn::Status)),
+            OrderColumn::CreatedAt => {
+                Some(("orders-sort-created", account::OrderSortColumn::CreatedAt))
+            }
+            _ => None,
+        };
+
+        match sort {
+            Some((id, sort_column)) => self
+                .render_sortable_header(
+                    id,
+                    column.label(),
+                    width,
+                    self.account.orders_sort_direction(sort_column),
+                    cx,
+                    move |this, _| this.account.toggle_orders_sort(sort_column),
+                )
+                .into_any_element(),
+            None => self
+                .render_column_header(column.label(), width)
+                .into_any_element(),
+        }
+    }
+
+    /// Active Orders row; `leg` is the position of a bracket/OCO leg under row `idx`
+    fn render_order_row(
+        &self,
+        idx: usize,
+        leg: Option<usize>,
+        order: &account::Order,
+        columns: &[(usize, OrderColumn, f32)],
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let is_selected = self.account.selected_order_id.as_deref() == Some(&order.id);
+        let row_id = match leg {
+            Some(leg) => format!("order-row-{}-leg-{}", idx, leg),
+            None => format!("order-row-{}", idx),
+        };
 
         div()
+            .id(ElementId::Name(row_id.into()))
             .flex()
-            .flex_col()
-            .gap_2()
-            .child(
-                div()
-                    .text_sm()
-                    .font_weight(FontWeight::SEMIBOLD)
-                    .text_color(rgb(0xffffff))
-                    .child("Limit Price"),
-            )
-            .child(
+            .gap_4()
+            .py_2()
+            .rounded_md()
+            .cursor_pointer()
+            .when(leg.is_some(), |this| {
+                this.ml_4().pl_2().border_l_2().border_color(rgb(0x30363d))
+            })
+            .when(is_selected, |this| this.bg(rgb(0x21262d)))
+            .hover(|style| style.bg(rgb(0x21262d)))
+            .on_click({
+                let order_id = order.id.clone();
+                cx.listener(move |this, _, _, cx| {
+                    this.account.toggle_selected_order(&order_id);
+                    cx.notify();
+                })
+            })
+            .children(columns.iter().map(|&(_, column, width)| {
+                self.render_order_cell(column, width, idx, leg.is_some(), order, cx)
+            }))
+    }
+
+    fn render_order_cell(
+        &self,
+        column: OrderColumn,
+        width: f32,
+        idx: usize,
+        is_leg: bool,
+        order: &account::Order,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let cell = div()
+            .w(px(width))
+            .flex_shrink_0()
+            .overflow_hidden()
+            .text_sm();
+
+        match column {
+            // Auto-attached exits are marked so they're easy to find and cancel
+            OrderColumn::Symbol => cell.text_color(rgb(0xffffff)).child(if is_leg {
+                format!("↳ {}", order.symbol)
+            } else if self.auto_exits.exit_ids.contains(&order.id) {
+                format!("🎯 {}", order.symbol)
+            } else {
+                order.symbol.clone()
+            }),
+            OrderColumn::Side => {
+                let side_color = if order.side.to_lowercase().contains("buy") {
+                    rgb(0x3fb950)
+                } else {
+                    rgb(0xff4444)
+                };
+                cell.font_weight(FontWeight::SEMIBOLD)
+                    .text_color(side_color)
+                    .child(order.side.clone())
+            }
+            OrderColumn::Qty => cell.text_color(rgb(0x8b949e)).child(order.qty.clone()),
+            OrderColumn::FilledQty => cell
+                .text_color(rgb(0x8b949e))
+                .child(order.filled_qty.clone()),
+            OrderColumn::AvgFillPrice => cell.text_color(rgb(0x8b949e)).child(
+                order
+                    .filled_avg_price
+                    .clone()
+                    .map(|price| self.config.numbers.format.price_text(&price))
+                    .unwrap_or("-".to_string()),
+            ),
+            OrderColumn::Type => cell
+                .text_color(rgb(0x8b949e))
+                .child(order.order_type.clone()),
+            // Stop legs have no limit, so their stop is shown instead
+            OrderColumn::LimitPrice => cell.text_color(rgb(0x8b949e)).child(
+                match (&order.limit_price, &order.stop_price) {
+                    (Some(limit), _) => limit.clone(),
+                    (None, Some(stop)) => format!("stop {}", stop),
+                    (None, None) => "-".to_string(),
+                },
+            ),
+            OrderColumn::Status => {
+                let status_color = match order.status.to_lowercase().as_str() {
+                    s if s.contains("filled") => rgb(0x3fb950),
+                    s if s.contains("canceled") || s.contains("rejected") => rgb(0xff4444),
+                    s if s.contains("pending") => rgb(0xf2cc60),
+                    _ => rgb(0x58a6ff),
+                };
+                cell.font_weight(FontWeight::SEMIBOLD)
+                    .text_color(status_color)
+                    .child(order.status.clone())
+            }
+            OrderColumn::CreatedAt => cell
+                .text_color(rgb(0x8b949e))
+                .child(order.created_at.clone()),
+            // Canceling the parent cancels its legs too, so legs have no button of their own
+            OrderColumn::Action if is_leg => cell,
+            OrderColumn::Action => cell.child(
                 div()
-                    .id("order-limit-price-input")
+                    .id(ElementId::Name(format!("cancel-order-{}", idx).into()))
                     .px_3()
-                    .py_2()
-                    .bg(if self.account.price_focused {
-                        rgb(0x1f2937)
-                    } else {
-                        rgb(0x0d1117)
-                    })
-                    .border_1()
-                    .border_color(if self.account.price_focused {
-                        rgb(0x1f6feb)
-                    } else {
-                        rgb(0x30363d)
-                    })
+                    .py_1()
+                    .bg(rgb(0xda3633))
                     .rounded_md()
+                    .text_xs()
                     .text_color(rgb(0xffffff))
-                    .cursor_text()
-                    .child(if self.account.price_focused {
-                        format!("{}|", self.account.order_limit_price)
-                    } else if self.account.order_limit_price.is_empty() {
-                        "Enter price...".to_string()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgb(0xff4444)))
+                    .child(if order.legs.iter().any(|leg| leg.is_open()) {
+                        "Cancel group"
                     } else {
-                        format!("${}", self.account.order_limit_price)
+                        "Cancel"
                     })
-                    .on_click(cx.listener(|this, _, _window, cx| {
-                        this.account.price_focused = true;
-                        this.chart.input_focused = false;
-                        this.account.quantity_focused = false;
-                        _window.focus(&this.focus_handle);
-                        cx.notify();
-                    })),
-            )
+                    .on_click({
+                        let order_id = order.id.clone();
+                        cx.listener(move |this, _, _, cx| {
+                            // Don't also toggle the row's detail panel
+                            cx.stop_propagation();
+                            this.cancel_order(order_id.clone());
+                        })
+                    }),
+            ),
+        }
+        .into_any_element()
     }
 
-    fn render_time_in_force(&self, cx: &mut Context<Self>) -> impl IntoElement {
-        if !matches!(self.account.order_type, OrderType::Limit) {
-            return div();
-        }
+    /// Small button that copies `text` to the clipboard and confirms with a toast
+    fn render_copy_button(
+        &self,
+        id: &'static str,
+        label: &'static str,
+        text: String,
+        what: &'static str,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .id(id)
+            .px_2()
+            .py_1()
+            .bg(rgb(0x21262d))
+            .border_1()
+            .border_color(rgb(0x30363d))
+            .rounded_md()
+            .text_xs()
+            .text_color(rgb(0x8b949e))
+            .cursor_pointer()
+            .hover(|style| style.text_color(rgb(0xffffff)))
+            .child(label)
+            .on_click(cx.listener(move |this, _, _, cx| {
+                cx.write_to_clipboard(ClipboardItem::new_string(text.clone()));
+                this.show_toast(
+                    toast::ToastLevel::Info,
+                    format!("{} copied to clipboard", what),
+                    cx,
+                );
+            }))
+    }
 
+    fn render_sortable_header(
+        &self,
+        id: &'static str,
+        label: &'static str,
+        width: f32,
+        sort_direction: Option<bool>,
+        cx: &mut Context<Self>,
+        on_click: impl Fn(&mut Self, &mut Context<Self>) + 'static,
+    ) -> impl IntoElement {
         div()
-            .flex()
-            .flex_col()
-            .gap_2()
-            .child(
-                div()
-                    .text_sm()
-                    .font_weight(FontWeight::SEMIBOLD)
-                    .text_color(rgb(0xffffff))
-                    .child("Time in Force"),
-            )
-            .child(
-                div()
-                    .flex()
-                    .gap_2()
-                    .child(
-                        div()
-                            .id("tif-day-btn")
-                            .flex_1()
-                            .px_3()
-                            .py_2()
-                            .rounded_md()
-                            .text_center()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .cursor_pointer()
-                            .bg(
-                                if matches!(self.account.order_time_in_force, OrderTimeInForce::Day)
-                                {
-                                    rgb(0x1f6feb)
-                                } else {
-                                    rgb(0x21262d)
-                                },
-                            )
-                            .text_color(rgb(0xffffff))
-                            .hover(|style| {
-                                if matches!(self.account.order_time_in_force, OrderTimeInForce::Day)
-                                {
-                                    style.bg(rgb(0x388bfd))
-                                } else {
-                                    style.bg(rgb(0x30363d))
-                                }
-                            })
-                            .child("Day")
-                            .on_click(cx.listener(|this, _, _, cx| {
-                                this.account.order_time_in_force = OrderTimeInForce::Day;
-                                cx.notify();
-                            })),
-                    )
-                    .child(
-                        div()
-                            .id("tif-gtc-btn")
-                            .flex_1()
-                            .px_3()
-                            .py_2()
-                            .rounded_md()
-                            .text_center()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .cursor_pointer()
-                            .bg(
-                                if matches!(self.account.order_time_in_force, OrderTimeInForce::Gtc)
-                                {
-                                    rgb(0x1f6feb)
-                                } else {
-                                    rgb(0x21262d)
-                                },
-                            )
-                            .text_color(rgb(0xffffff))
-                            .hover(|style| {
-                                if matches!(self.account.order_time_in_force, OrderTimeInForce::Gtc)
-                                {
-                                    style.bg(rgb(0x388bfd))
-                                } else {
-                                    style.bg(rgb(0x30363d))
-                                }
-                            })
-                            .child("GTC")
-                            .on_click(cx.listener(|this, _, _, cx| {
-                                this.account.order_time_in_force = OrderTimeInForce::Gtc;
-                                cx.notify();
-                            })),
-                    ),
-            )
+            .id(id)
+            .w(px(width))
+            .text_xs()
+            .font_weight(FontWeight::SEMIBOLD)
+            .text_color(if sort_direction.is_some() {
+                rgb(0xffffff)
+            } else {
+                rgb(0x8b949e)
+            })
+            .cursor_pointer()
+            .hover(|style| style.text_color(rgb(0xffffff)))
+            .child(match sort_direction {
+                Some(true) => format!("{} ▲", label),
+                Some(false) => format!("{} ▼", label),
+                None => label.to_string(),
+            })
+            .on_click(cx.listener(move |this, _, _, cx| {
+                on_click(this, cx);
+                cx.notify();
+            }))
     }
 
-    fn render_order_message(&self, _cx: &mut Context<Self>) -> impl IntoElement {
-        if self.account.order_message.is_none() {
-            return div();
-        }
+    fn render_filter_chip(
+        &self,
+        id: String,
+        label: &'static str,
+        is_selected: bool,
+        cx: &mut Context<Self>,
+        on_click: impl Fn(&mut Self, &mut Context<Self>) + 'static,
+    ) -> impl IntoElement {
+        div()
+            .id(ElementId::Name(id.into()))
+            .px_2()
+            .py_1()
+            .rounded_md()
+            .text_xs()
+            .cursor_pointer()
+            .bg(if is_selected {
+                rgb(0x1f6feb)
+            } else {
+                rgb(0x21262d)
+            })
+            .text_color(if is_selected {
+                rgb(0xffffff)
+            } else {
+                rgb(0x8b949e)
+            })
+            .hover(|style| style.bg(rgb(0x30363d)))
+            .child(label)
+            .on_click(cx.listener(move |this, _, _, cx| {
+                on_click(this, cx);
+                cx.notify();
+            }))
+    }
+
+    fn render_footer_tab_button(
+        &self,
+        id: &'static str,
+        label: &'static str,
+        tab: account::FooterTab,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let is_active = self.account.active_footer_tab == tab;
 
         div()
-            .px_3()
+            .id(id)
+            .px_4()
             .py_2()
-            .bg(rgb(0x21262d))
-            .border_1()
-            .border_color(rgb(0x30363d))
             .rounded_md()
-            .text_xs()
-            .text_color(
-                if self
-                    .account
-                    .order_message
-                    .as_ref()
-                    .unwrap()
-                    .starts_with("✓")
-                {
-                    rgb(0x3fb950)
+            .text_sm()
+            .font_weight(FontWeight::SEMIBOLD)
+            .cursor_pointer()
+            .bg(if is_active {
+                rgb(0x238636)
+            } else {
+                rgb(0x21262d)
+            })
+            .text_color(rgb(0xffffff))
+            .hover(|style| {
+                if is_active {
+                    style.bg(rgb(0x2ea043))
                 } else {
-                    rgb(0xff4444)
-                },
-            )
-            .child(self.account.order_message.clone().unwrap())
+                    style.bg(rgb(0x30363d))
+                }
+            })
+            .child(label)
+            .on_click(cx.listener(move |this, _, _, cx| {
+                this.select_footer_tab(tab.clone(), cx);
+            }))
     }
 
     fn render_account_stat(
@@ -2856,32 +17274,138 @@ impl TradingTerminal {
             .child(label_owned)
             .on_click(cx.listener(move |this, _, _, cx| {
                 this.chart.timeframe = timeframe_owned.clone();
-                this.fetch_bars(cx);
+                this.fetch_chart_bars_debounced(chart::ChartId::Main, cx);
             }))
     }
 }
 
+/// Drop orders in a terminal state (filled, canceled, expired, rejected)
+fn active_orders(mut orders: Vec<account::Order>) -> Vec<account::Order> {
+    orders.retain(|order| {
+        !matches!(
+            order.status.as_str(),
+            "filled" | "canceled" | "expired" | "rejected"
+        )
+    });
+    orders
+}
+
+/// Blue for paper trading, red for live
+fn environment_color(environment: credentials::Environment) -> gpui::Rgba {
+    match environment {
+        credentials::Environment::Paper => rgb(0x1f6feb),
+        credentials::Environment::Live => rgb(0xda3633),
+    }
+}
+
+/// Green for gains, red for losses, gray for flat
+fn pnl_color(value: f64) -> gpui::Rgba {
+    if value > 0.0 {
+        rgb(0x3fb950)
+    } else if value < 0.0 {
+        rgb(0xff4444)
+    } else {
+        rgb(0x8b949e)
+    }
+}
+
+/// Split-adjusted bars from `since` up to now, oldest first (sort=asc), used to fill the
+/// gap a stream reconnect leaves
+fn fetch_bars_since_sync(
+    symbol: &str,
+    timeframe: &str,
+    since: chrono::DateTime<Utc>,
+    data_feed: config::DataFeed,
+) -> Result<Vec<Bar>, error::TerminalError> {
+    api::block_on(async {
+        let config = data_feed.configure(crate::credentials::alpaca_config()?);
+        let client = MarketDataClient::new(config);
+        let end_time = Utc::now();
+
+        // The two endpoints return different response types, so map each one
+        if chart::is_crypto_symbol(symbol) {
+            client
+                .get_crypto_bars(
+                    symbol,
+                    timeframe,
+                    Some(since),
+                    Some(end_time),
+                    Some(BACKFILL_LIMIT),
+                    Some(Sort::Asc),
+                )
+                .await
+                .map(|bars_response| bars_response.bars)
+                .map_err(|e| error::TerminalError::for_symbol(e, symbol))
+        } else {
+            client
+                .get_bars(
+                    symbol,
+                    timeframe,
+                    Some(since),
+                    Some(end_time),
+                    Some(BACKFILL_LIMIT),
+                    Some(Sort::Asc),
+                    Some(Adjustment::Split),
+                )
+                .await
+                .map(|bars_response| bars_response.bars)
+                .map_err(|e| error::TerminalError::for_symbol(e, symbol))
+        }
+    })
+}
+
 // Synchronous function to fetch account info (runs in background thread)
 
 // Synchronous function to fetch bars (runs in background thread)
 // Uses split-adjusted data with sort=desc to get most recent bars
-fn fetch_bars_sync(symbol: &str, timeframe: &str, user_limit: u32) -> Result<Vec<Bar>, String> {
-    let rt = tokio::runtime::Runtime::new().map_err(|e| format!("Runtime error: {:?}", e))?;
-
-    rt.block_on(async {
+fn fetch_bars_sync(
+    symbol: &str,
+    timeframe: &str,
+    user_limit: u32,
+    data_feed: config::DataFeed,
+) -> Result<Vec<Bar>, error::TerminalError> {
+    api::block_on(async {
         // Load configuration from environment
-        let config = match AlpacaConfig::from_env() {
-            Ok(config) => config.with_iex_feed(),
-            Err(e) => {
-                return Err(format!(
-                    "Error loading config: {:?}. Please set APCA_API_KEY_ID and APCA_API_SECRET_KEY environment variables.",
-                    e
-                ));
-            }
-        };
+        let config = data_feed.configure(crate::credentials::alpaca_config()?);
 
         let client = MarketDataClient::new(config);
 
+        // Crypto trades around the clock: bars per day are a straight division of 24h
+        if chart::is_crypto_symbol(symbol) {
+            let end_time = Utc::now();
+            let limit = user_limit as i64;
+            let start_time = match timeframe {
+                "1Min" => end_time - Duration::minutes(limit + 60),
+                "5Min" => end_time - Duration::minutes(limit * 5 + 60),
+                "15Min" => end_time - Duration::minutes(limit * 15 + 60),
+                "1Hour" => end_time - Duration::hours(limit + 2),
+                "1Day" => end_time - Duration::days(limit + 2),
+                "1Week" => end_time - Duration::weeks(limit + 2),
+                "1Month" => end_time - Duration::days(limit * 31 + 31),
+                _ => end_time - Duration::days(limit + 2),
+            };
+
+            let result = client
+                .get_crypto_bars(
+                    symbol,
+                    timeframe,
+                    Some(start_time),
+                    Some(end_time),
+                    Some(user_limit),
+                    Some(Sort::Desc),
+                )
+                .await;
+
+            return match result {
+                Ok(bars_response) => {
+                    let mut bars = bars_response.bars;
+                    bars.reverse();
+                    Ok(bars)
+                }
+                Err(e) => Err(error::TerminalError::for_symbol(e, symbol)),
+            };
+        }
+
         // Calculate time range - use generous lookback since we'll sort descending
         let end_time = Utc::now();
         let start_time = match timeframe {
@@ -2918,7 +17442,7 @@ fn fetch_bars_sync(symbol: &str, timeframe: &str, user_limit: u32) -> Result<Vec
                 bars.reverse();
                 Ok(bars)
             }
-            Err(e) => Err(format!("Error fetching data: {:?}", e)),
+            Err(e) => Err(error::TerminalError::for_symbol(e, symbol)),
         }
     })
 }
@@ -2957,12 +17481,33 @@ fn generate_mock_data() -> Vec<Bar> {
 }
 
 fn main() {
+    let _log_guard = logging::init();
+
     Application::new().run(|cx: &mut App| {
         cx.activate(true);
-        cx.on_action(|_: &Quit, cx| cx.quit());
+        cx.on_action(|_: &keymap::Quit, cx| cx.quit());
+        keymap::Keymap::load().bind(cx);
+
+        // Reopen at the size the window had when it was last closed
+        let window_options = WindowOptions {
+            window_bounds: settings::Settings::load()
+                .session
+                .window_size
+                .map(|(width, height)| {
+                    WindowBounds::Windowed(Bounds::centered(None, size(px(width), px(height)), cx))
+                }),
+            // Small enough for a laptop half-screen; the layout collapses below 1280px
+            window_min_size: Some(size(px(640.0), px(480.0))),
+            // Replaced with the charted symbol's price by the clock ticker
+            titlebar: Some(gpui::TitlebarOptions {
+                title: Some(APP_TITLE.into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
 
-        cx.open_window(WindowOptions::default(), |_, cx| {
-            cx.new(TradingTerminal::new)
+        cx.open_window(window_options, |window, cx| {
+            cx.new(|cx| TradingTerminal::new(window, cx))
         })
         .unwrap();
     });
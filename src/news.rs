@@ -0,0 +1,162 @@
+// News module for headlines from the Alpaca News API
+
+use crate::error::TerminalError;
+use alpaca_markets::{Bar, MarketDataClient, Sort};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+
+/// Number of headlines requested per fetch
+pub const NEWS_LIMIT: u32 = 50;
+
+/// News headline
+#[derive(Clone)]
+pub struct NewsArticle {
+    pub id: i64,
+    pub headline: String,
+    pub summary: String,
+    pub source: String,
+    pub url: Option<String>,
+    pub symbols: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl NewsArticle {
+    pub fn mentions_any(&self, symbols: &[String]) -> bool {
+        self.symbols.iter().any(|symbol| symbols.contains(symbol))
+    }
+}
+
+impl From<crate::stream::NewsHeadline> for NewsArticle {
+    fn from(headline: crate::stream::NewsHeadline) -> Self {
+        Self {
+            id: headline.id,
+            headline: headline.headline,
+            summary: headline.summary,
+            source: headline.source,
+            url: headline.url,
+            symbols: headline.symbols,
+            created_at: headline.created_at,
+        }
+    }
+}
+
+/// Which symbols the news panel covers
+#[derive(Clone, Copy, PartialEq)]
+pub enum NewsScope {
+    Symbol,
+    Watchlist,
+}
+
+impl NewsScope {
+    pub const ALL: [NewsScope; 2] = [NewsScope::Symbol, NewsScope::Watchlist];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            NewsScope::Symbol => "Symbol",
+            NewsScope::Watchlist => "Watchlist",
+        }
+    }
+}
+
+/// News panel state
+pub struct News {
+    pub articles: Vec<NewsArticle>,
+    pub loading: bool,
+    pub error: Option<String>,
+    pub scope: NewsScope,
+    // Symbols the loaded articles were requested for
+    pub symbols: Vec<String>,
+    // Real-time headline stream; bumping the generation retires the running one
+    pub stream_connected: bool,
+    pub stream_symbols: Vec<String>,
+    pub stream_generation: u64,
+    // Bar index of the chart news marker under the mouse
+    pub hovered_marker: Option<usize>,
+}
+
+impl News {
+    pub fn new() -> Self {
+        Self {
+            articles: Vec::new(),
+            loading: false,
+            error: None,
+            scope: NewsScope::Symbol,
+            symbols: Vec::new(),
+            stream_connected: false,
+            stream_symbols: Vec::new(),
+            stream_generation: 0,
+            hovered_marker: None,
+        }
+    }
+
+    /// Add a streamed headline to the top of the list if it matches the panel's symbols
+    pub fn push_live(&mut self, article: NewsArticle) -> bool {
+        if !article.mentions_any(&self.symbols) || self.articles.iter().any(|a| a.id == article.id)
+        {
+            return false;
+        }
+        self.articles.insert(0, article);
+        self.articles.truncate(NEWS_LIMIT as usize);
+        true
+    }
+}
+
+/// Group a symbol's headlines by the bar they were published in (keyed by bar index).
+/// Headlines published between sessions land on the preceding bar.
+pub fn news_markers<'a>(
+    articles: &'a [NewsArticle],
+    symbol: &str,
+    bars: &[Bar],
+) -> BTreeMap<usize, Vec<&'a NewsArticle>> {
+    let mut markers: BTreeMap<usize, Vec<&NewsArticle>> = BTreeMap::new();
+
+    for article in articles {
+        if !article.symbols.iter().any(|s| s == symbol) {
+            continue;
+        }
+        // Index of the last bar opened at or before the headline
+        let index = bars.partition_point(|bar| bar.timestamp <= article.created_at);
+        if index == 0 {
+            continue;
+        }
+        markers.entry(index - 1).or_default().push(article);
+    }
+
+    markers
+}
+
+/// Fetch the most recent headlines mentioning any of the symbols (newest first)
+pub fn fetch_news_sync(symbols: Vec<String>) -> Result<Vec<NewsArticle>, TerminalError> {
+    crate::api::block_on(async {
+        let config = crate::credentials::alpaca_config()?;
+
+        let client = MarketDataClient::new(config);
+
+        let result = client
+            .get_news(
+                Some(symbols.join(",")),
+                None,
+                None,
+                Some(NEWS_LIMIT),
+                Some(Sort::Desc),
+            )
+            .await;
+
+        match result {
+            Ok(response) => Ok(response
+                .news
+                .into_iter()
+                .map(|article| NewsArticle {
+                    id: article.id,
+                    headline: article.headline,
+                    summary: article.summary,
+                    source: article.source,
+                    url: article.url.filter(|url| !url.is_empty()),
+                    symbols: article.symbols,
+                    created_at: article.created_at,
+                })
+                .collect()),
+            Err(e) => Err(TerminalError::api(e)),
+        }
+    })
+}
@@ -0,0 +1,149 @@
+// Notifications module for the notification center and desktop notifications
+
+use chrono::{DateTime, Local};
+use std::thread;
+use tracing::warn;
+
+/// Oldest notifications are dropped past this count
+pub const MAX_NOTIFICATIONS: usize = 200;
+
+/// What raised a notification
+#[derive(Clone, Copy, PartialEq)]
+pub enum NotificationKind {
+    Alert,
+    Fill,
+    Order,
+    Error,
+}
+
+impl NotificationKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            NotificationKind::Alert => "Alert",
+            NotificationKind::Fill => "Fill",
+            NotificationKind::Order => "Order",
+            NotificationKind::Error => "Error",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Notification {
+    pub id: u64,
+    pub kind: NotificationKind,
+    pub title: String,
+    pub message: String,
+    pub timestamp: DateTime<Local>,
+    pub read: bool,
+}
+
+/// Notification center state (newest first)
+pub struct NotificationCenter {
+    pub notifications: Vec<Notification>,
+    pub open: bool,
+    next_id: u64,
+}
+
+impl NotificationCenter {
+    pub fn new() -> Self {
+        Self {
+            notifications: Vec::new(),
+            open: false,
+            next_id: 0,
+        }
+    }
+
+    pub fn push(&mut self, kind: NotificationKind, title: String, message: String) {
+        self.next_id += 1;
+        self.notifications.insert(
+            0,
+            Notification {
+                id: self.next_id,
+                kind,
+                title,
+                message,
+                timestamp: Local::now(),
+                read: false,
+            },
+        );
+        self.notifications.truncate(MAX_NOTIFICATIONS);
+    }
+
+    pub fn unread_count(&self) -> usize {
+        self.notifications.iter().filter(|n| !n.read).count()
+    }
+
+    pub fn mark_read(&mut self, id: u64) {
+        if let Some(notification) = self.notifications.iter_mut().find(|n| n.id == id) {
+            notification.read = true;
+        }
+    }
+
+    pub fn mark_all_read(&mut self) {
+        for notification in &mut self.notifications {
+            notification.read = true;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.notifications.clear();
+    }
+}
+
+/// Show a native desktop notification (on its own thread, as some platforms block)
+pub fn send_desktop_notification(title: &str, message: &str) {
+    let title = title.to_string();
+    let message = message.to_string();
+
+    thread::spawn(move || {
+        if let Err(e) = notify_rust::Notification::new()
+            .appname("Alpaca Markets Terminal")
+            .summary(&title)
+            .body(&message)
+            .show()
+        {
+            warn!("⚠️  Failed to show desktop notification: {}", e);
+        }
+    });
+}
+
+/// Notification for an order stream event worth surfacing (fills, partial fills,
+/// rejections, cancels, expiries); routine events like "new" return None
+pub fn order_event_notification(
+    order_update: &crate::stream::OrderUpdate,
+) -> Option<(NotificationKind, String, String)> {
+    let price = order_update
+        .filled_avg_price
+        .as_ref()
+        .map(|p| format!(" @ ${}", p))
+        .unwrap_or_default();
+
+    let (kind, title) = match order_update.event.as_str() {
+        "fill" => (NotificationKind::Fill, "Order filled"),
+        "partial_fill" => (NotificationKind::Fill, "Order partially filled"),
+        "rejected" => (NotificationKind::Order, "Order rejected"),
+        "canceled" => (NotificationKind::Order, "Order canceled"),
+        "expired" => (NotificationKind::Order, "Order expired"),
+        _ => return None,
+    };
+
+    let message = match kind {
+        NotificationKind::Fill => format!(
+            "{} {} {}/{}{}",
+            order_update.side.to_uppercase(),
+            order_update.symbol,
+            order_update.filled_qty,
+            order_update.qty,
+            price
+        ),
+        _ => format!(
+            "{} {} {} ({})",
+            order_update.side.to_uppercase(),
+            order_update.qty,
+            order_update.symbol,
+            order_update.order_type
+        ),
+    };
+
+    Some((kind, title.to_string(), message))
+}
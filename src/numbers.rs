@@ -0,0 +1,162 @@
+// Numbers module: locale-aware formatting for prices, money and volumes
+
+/// Separators and precision for displayed numbers
+#[derive(Clone, PartialEq, Debug)]
+pub struct NumberFormat {
+    pub thousands: Option<char>,
+    pub decimal: char,
+    // Decimal places for prices; account values always use two
+    pub price_decimals: usize,
+    // Volumes as 12.3M instead of 12,345,678
+    pub compact_volume: bool,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            thousands: Some(','),
+            decimal: '.',
+            price_decimals: 2,
+            compact_volume: true,
+        }
+    }
+}
+
+/// Locale from LC_ALL, LC_NUMERIC or LANG (e.g. "de_DE.UTF-8")
+pub fn system_locale() -> String {
+    ["LC_ALL", "LC_NUMERIC", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+        .unwrap_or_else(|| "en_US".to_string())
+}
+
+impl NumberFormat {
+    /// Separators for a locale such as "en_US", "de-DE" or "fr_FR.UTF-8"
+    pub fn for_locale(locale: &str) -> Self {
+        let locale = locale.split(['.', '@']).next().unwrap_or_default();
+        let mut parts = locale.split(['_', '-']);
+        let language = parts.next().unwrap_or_default().to_lowercase();
+        let region = parts.next().unwrap_or_default().to_uppercase();
+
+        let (thousands, decimal) = match (language.as_str(), region.as_str()) {
+            ("de" | "it", "CH") => (Some('\''), '.'),
+            (
+                "de" | "es" | "it" | "pt" | "nl" | "id" | "tr" | "da" | "el" | "ro" | "hr" | "sl",
+                _,
+            ) => (Some('.'), ','),
+            (
+                "fr" | "ru" | "pl" | "sv" | "nb" | "nn" | "fi" | "cs" | "sk" | "uk" | "bg" | "lt"
+                | "lv" | "et" | "hu",
+                _,
+            ) => (Some('\u{a0}'), ','),
+            _ => (Some(','), '.'),
+        };
+        Self {
+            thousands,
+            decimal,
+            ..Self::default()
+        }
+    }
+
+    /// Value with grouped thousands and a fixed number of decimals
+    pub fn number(&self, value: f64, decimals: usize) -> String {
+        let formatted = format!("{:.*}", decimals, value.abs());
+        let (integer, fraction) = match formatted.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (formatted.as_str(), None),
+        };
+
+        let mut grouped = String::new();
+        for (index, digit) in integer.chars().enumerate() {
+            if index > 0 && (integer.len() - index) % 3 == 0 {
+                if let Some(separator) = self.thousands {
+                    grouped.push(separator);
+                }
+            }
+            grouped.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            grouped.push(self.decimal);
+            grouped.push_str(fraction);
+        }
+
+        // Rounding to zero shouldn't leave a "-0.00"
+        if value < 0.0 && formatted.chars().any(|c| c.is_ascii_digit() && c != '0') {
+            format!("-{}", grouped)
+        } else {
+            grouped
+        }
+    }
+
+    /// Dollar amount with two decimals: "$1,234,567.89", "-$12.50"
+    pub fn money(&self, value: f64) -> String {
+        self.dollars(self.number(value, 2), value)
+    }
+
+    /// Money with an explicit sign, for P&L: "+$12.50"
+    pub fn signed_money(&self, value: f64) -> String {
+        let text = self.money(value);
+        if value >= 0.0 {
+            format!("+{}", text)
+        } else {
+            text
+        }
+    }
+
+    /// Price with the configured precision (sub-dollar prices get at least four decimals)
+    pub fn price(&self, value: f64) -> String {
+        let decimals = if value.abs() < 1.0 && value != 0.0 {
+            self.price_decimals.max(4)
+        } else {
+            self.price_decimals
+        };
+        self.dollars(self.number(value, decimals), value)
+    }
+
+    /// Price sent by the API as text; shown unchanged if it doesn't parse
+    pub fn price_text(&self, text: &str) -> String {
+        match text.parse::<f64>() {
+            Ok(value) => self.price(value),
+            Err(_) => format!("${}", text),
+        }
+    }
+
+    /// Money sent by the API as text
+    pub fn money_text(&self, text: &str) -> String {
+        match text.parse::<f64>() {
+            Ok(value) => self.money(value),
+            Err(_) => format!("${}", text),
+        }
+    }
+
+    /// Volume, compacted to 12.3K / 12.3M / 1.2B when enabled
+    pub fn volume(&self, value: f64) -> String {
+        if !self.compact_volume || value.abs() < 1_000.0 {
+            return self.number(value, 0);
+        }
+        let (scaled, suffix) = if value.abs() >= 1e9 {
+            (value / 1e9, "B")
+        } else if value.abs() >= 1e6 {
+            (value / 1e6, "M")
+        } else {
+            (value / 1e3, "K")
+        };
+        format!("{}{}", self.number(scaled, 1), suffix)
+    }
+
+    pub fn volume_text(&self, text: &str) -> String {
+        match text.parse::<f64>() {
+            Ok(value) => self.volume(value),
+            Err(_) => text.to_string(),
+        }
+    }
+
+    /// Put the dollar sign after any minus sign
+    fn dollars(&self, number: String, value: f64) -> String {
+        match number.strip_prefix('-') {
+            Some(rest) if value < 0.0 => format!("-${}", rest),
+            _ => format!("${}", number),
+        }
+    }
+}
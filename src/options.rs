@@ -0,0 +1,320 @@
+// Options module for building and submitting multi-leg option strategies
+
+use crate::error::TerminalError;
+use alpaca_markets::TradingClient;
+use alpaca_markets::models::{
+    MultiLegOrderRequest, OptionLegRequest, OrderSide, OrderTimeInForce, OrderType,
+};
+
+/// Shares per option contract
+pub const CONTRACT_MULTIPLIER: f64 = 100.0;
+
+/// Number of price samples in the payoff preview
+pub const PAYOFF_SAMPLES: usize = 48;
+
+/// Supported strategy templates
+#[derive(Clone, Copy, PartialEq)]
+pub enum StrategyKind {
+    Vertical,
+    Straddle,
+    IronCondor,
+}
+
+impl StrategyKind {
+    pub const ALL: [StrategyKind; 3] = [
+        StrategyKind::Vertical,
+        StrategyKind::Straddle,
+        StrategyKind::IronCondor,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StrategyKind::Vertical => "Vertical",
+            StrategyKind::Straddle => "Straddle",
+            StrategyKind::IronCondor => "Iron Condor",
+        }
+    }
+
+    /// Strike inputs used by the template, in the order they are entered
+    pub fn strike_labels(&self) -> &'static [&'static str] {
+        match self {
+            StrategyKind::Vertical => &["Long Strike", "Short Strike"],
+            StrategyKind::Straddle => &["Strike"],
+            StrategyKind::IronCondor => &["Long Put", "Short Put", "Short Call", "Long Call"],
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum OptionType {
+    Call,
+    Put,
+}
+
+impl OptionType {
+    pub fn label(&self) -> &'static str {
+        match self {
+            OptionType::Call => "Call",
+            OptionType::Put => "Put",
+        }
+    }
+
+    fn occ_code(&self) -> char {
+        match self {
+            OptionType::Call => 'C',
+            OptionType::Put => 'P',
+        }
+    }
+}
+
+/// Text field in the strategy builder that has keyboard focus
+#[derive(Clone, Copy, PartialEq)]
+pub enum StrategyField {
+    Expiration,
+    Strike(usize),
+    Quantity,
+    NetPrice,
+}
+
+/// One leg of a strategy (per unit of the strategy)
+#[derive(Clone)]
+pub struct OptionLeg {
+    pub symbol: String,
+    pub option_type: OptionType,
+    pub strike: f64,
+    pub is_buy: bool,
+    pub ratio: u32,
+}
+
+impl OptionLeg {
+    /// Value of the leg at expiration per share (negative for short legs)
+    fn value_at(&self, underlying: f64) -> f64 {
+        let intrinsic = match self.option_type {
+            OptionType::Call => (underlying - self.strike).max(0.0),
+            OptionType::Put => (self.strike - underlying).max(0.0),
+        };
+        let sign = if self.is_buy { 1.0 } else { -1.0 };
+        sign * intrinsic * self.ratio as f64
+    }
+}
+
+/// Payoff summary for the preview
+pub struct PayoffSummary {
+    /// (underlying price, P&L) samples across the preview range
+    pub samples: Vec<(f64, f64)>,
+    /// None when profit is unbounded
+    pub max_profit: Option<f64>,
+    /// None when loss is unbounded
+    pub max_loss: Option<f64>,
+    pub breakevens: Vec<f64>,
+}
+
+/// Strategy builder form state
+pub struct StrategyBuilder {
+    pub open: bool,
+    pub kind: StrategyKind,
+    // Call or put spread (verticals only)
+    pub option_type: OptionType,
+    // Expiration date as YYYY-MM-DD
+    pub expiration: String,
+    pub strikes: [String; 4],
+    pub quantity: String,
+    // Net price per strategy unit: positive = debit paid, negative = credit received
+    pub net_price: String,
+    pub submitting: bool,
+    pub message: Option<String>,
+}
+
+impl StrategyBuilder {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            kind: StrategyKind::Vertical,
+            option_type: OptionType::Call,
+            expiration: String::new(),
+            strikes: Default::default(),
+            quantity: "1".to_string(),
+            net_price: String::new(),
+            submitting: false,
+            message: None,
+        }
+    }
+
+    /// Text of a field, for editing and display
+    pub fn field_mut(&mut self, field: StrategyField) -> &mut String {
+        match field {
+            StrategyField::Expiration => &mut self.expiration,
+            StrategyField::Strike(index) => &mut self.strikes[index.min(3)],
+            StrategyField::Quantity => &mut self.quantity,
+            StrategyField::NetPrice => &mut self.net_price,
+        }
+    }
+
+    /// Build the legs for the underlying, validating the form
+    pub fn legs(&self, underlying: &str) -> Result<Vec<OptionLeg>, String> {
+        let expiration = chrono::NaiveDate::parse_from_str(self.expiration.trim(), "%Y-%m-%d")
+            .map_err(|_| "Expiration must be YYYY-MM-DD".to_string())?;
+
+        let strike_count = self.kind.strike_labels().len();
+        let strikes = self.strikes[..strike_count]
+            .iter()
+            .map(|s| match s.trim().parse::<f64>() {
+                Ok(strike) if strike > 0.0 => Ok(strike),
+                _ => Err("All strikes must be positive numbers".to_string()),
+            })
+            .collect::<Result<Vec<f64>, String>>()?;
+
+        let leg = |option_type: OptionType, strike: f64, is_buy: bool| OptionLeg {
+            symbol: occ_symbol(underlying, expiration, option_type, strike),
+            option_type,
+            strike,
+            is_buy,
+            ratio: 1,
+        };
+
+        match self.kind {
+            StrategyKind::Vertical => {
+                if strikes[0] == strikes[1] {
+                    return Err("Vertical strikes must differ".to_string());
+                }
+                Ok(vec![
+                    leg(self.option_type, strikes[0], true),
+                    leg(self.option_type, strikes[1], false),
+                ])
+            }
+            StrategyKind::Straddle => Ok(vec![
+                leg(OptionType::Call, strikes[0], true),
+                leg(OptionType::Put, strikes[0], true),
+            ]),
+            StrategyKind::IronCondor => {
+                if !strikes.windows(2).all(|w| w[0] < w[1]) {
+                    return Err("Iron condor strikes must be increasing".to_string());
+                }
+                Ok(vec![
+                    leg(OptionType::Put, strikes[0], true),
+                    leg(OptionType::Put, strikes[1], false),
+                    leg(OptionType::Call, strikes[2], false),
+                    leg(OptionType::Call, strikes[3], true),
+                ])
+            }
+        }
+    }
+
+    /// Payoff at expiration for the whole position, or None if the form is incomplete
+    pub fn payoff(&self, underlying: &str) -> Option<PayoffSummary> {
+        let legs = self.legs(underlying).ok()?;
+        let quantity = self
+            .quantity
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|q| *q > 0.0)?;
+        let net_price = self.net_price.trim().parse::<f64>().unwrap_or(0.0);
+        Some(payoff_summary(&legs, net_price, quantity))
+    }
+}
+
+/// OCC option symbol, e.g. AAPL240119C00150000
+pub fn occ_symbol(
+    underlying: &str,
+    expiration: chrono::NaiveDate,
+    option_type: OptionType,
+    strike: f64,
+) -> String {
+    format!(
+        "{}{}{}{:08}",
+        underlying.to_uppercase(),
+        expiration.format("%y%m%d"),
+        option_type.occ_code(),
+        (strike * 1000.0).round() as u64
+    )
+}
+
+/// Sample the expiration payoff and derive max profit/loss and breakevens
+pub fn payoff_summary(legs: &[OptionLeg], net_price: f64, quantity: f64) -> PayoffSummary {
+    let pnl_at = |price: f64| {
+        let value: f64 = legs.iter().map(|leg| leg.value_at(price)).sum();
+        (value - net_price) * CONTRACT_MULTIPLIER * quantity
+    };
+
+    let max_strike = legs.iter().map(|leg| leg.strike).fold(0.0, f64::max);
+    let min_strike = legs.iter().map(|leg| leg.strike).fold(f64::MAX, f64::min);
+    let low = (min_strike * 0.7).max(0.0);
+    let high = max_strike * 1.3;
+    let step = (high - low) / (PAYOFF_SAMPLES - 1) as f64;
+
+    let samples: Vec<(f64, f64)> = (0..PAYOFF_SAMPLES)
+        .map(|i| {
+            let price = low + step * i as f64;
+            (price, pnl_at(price))
+        })
+        .collect();
+
+    // Net call exposure above the top strike decides whether the upside is bounded
+    let upside_slope: i64 = legs
+        .iter()
+        .filter(|leg| leg.option_type == OptionType::Call)
+        .map(|leg| if leg.is_buy { 1 } else { -1 } * leg.ratio as i64)
+        .sum();
+
+    let sampled_max = samples.iter().map(|s| s.1).fold(f64::MIN, f64::max);
+    let sampled_min = samples.iter().map(|s| s.1).fold(f64::MAX, f64::min);
+    // The downside is bounded (price can't go below zero), so include it exactly
+    let at_zero = pnl_at(0.0);
+
+    let breakevens = samples
+        .windows(2)
+        .filter(|w| (w[0].1 < 0.0) != (w[1].1 < 0.0))
+        .map(|w| {
+            let (p0, v0) = w[0];
+            let (p1, v1) = w[1];
+            p0 + (p1 - p0) * (-v0 / (v1 - v0))
+        })
+        .collect();
+
+    PayoffSummary {
+        samples,
+        max_profit: (upside_slope <= 0).then(|| sampled_max.max(at_zero)),
+        max_loss: (upside_slope >= 0).then(|| sampled_min.min(at_zero)),
+        breakevens,
+    }
+}
+
+/// Submit the strategy as a single multi-leg (mleg) limit order
+pub fn submit_strategy_sync(
+    legs: Vec<OptionLeg>,
+    quantity: u32,
+    net_price: f64,
+) -> Result<String, TerminalError> {
+    crate::api::block_on(async {
+        let config = crate::credentials::alpaca_config()?;
+
+        let client = TradingClient::new(config);
+
+        let order_request = MultiLegOrderRequest {
+            qty: quantity.to_string(),
+            order_type: OrderType::Limit,
+            time_in_force: OrderTimeInForce::Day,
+            limit_price: Some(format!("{:.2}", net_price)),
+            legs: legs
+                .into_iter()
+                .map(|leg| OptionLegRequest {
+                    symbol: leg.symbol,
+                    side: if leg.is_buy {
+                        OrderSide::Buy
+                    } else {
+                        OrderSide::Sell
+                    },
+                    ratio_qty: leg.ratio.to_string(),
+                })
+                .collect(),
+        };
+
+        let result = client.submit_multi_leg_order(order_request).await;
+
+        match result {
+            Ok(order) => Ok(order.id),
+            Err(e) => Err(TerminalError::api(e)),
+        }
+    })
+}
@@ -0,0 +1,118 @@
+// Order guard module: typed confirmation for large orders on live accounts
+
+use crate::chart::ChartId;
+use crate::hotkeys::HotkeySide;
+use crate::options::OptionLeg;
+use crate::scalp::{PendingReverse, ScalpAction};
+use crate::schedule::ScheduledOrder;
+use crate::service::{OrderOrigin, OrderTicket};
+use crate::text_input::{InputMode, TextInput};
+
+/// Which submit path is waiting on the confirmation
+#[derive(Clone, Copy, PartialEq)]
+pub enum GuardedOrder {
+    Form,
+    Hotkey(HotkeySide),
+    // Order ticket of a chart window
+    ChartWindow(usize),
+    // Buy, sell or reverse button of the scalp pad
+    Scalp(ScalpAction),
+    // Due run of a recurring buy plan
+    Recurring(u64),
+    // Multi-leg options order from the strategy builder (on the charted underlying)
+    Strategy,
+    // Order of an automation rule that fired
+    Automation(u64),
+}
+
+impl GuardedOrder {
+    /// Chart whose symbol the order trades (None for orders not placed from a chart)
+    pub fn chart(&self) -> Option<ChartId> {
+        match self {
+            GuardedOrder::Form
+            | GuardedOrder::Hotkey(_)
+            | GuardedOrder::Scalp(_)
+            | GuardedOrder::Strategy => Some(ChartId::Main),
+            GuardedOrder::ChartWindow(id) => Some(ChartId::Window(*id)),
+            GuardedOrder::Recurring(_) | GuardedOrder::Automation(_) => None,
+        }
+    }
+}
+
+/// What a guarded order does once it may go ahead. Built before the guard runs, so a held
+/// order sends exactly what was confirmed whatever the chart and inputs show by then.
+pub enum OrderAction {
+    Submit {
+        origin: OrderOrigin,
+        ticket: OrderTicket,
+    },
+    // Form order with a later submit time
+    Stage(ScheduledOrder),
+    // Scalp reverse on Alpaca: close the position, then the opposite entry once flat
+    Reverse(PendingReverse),
+    // Multi-leg options order on `underlying`
+    Strategy {
+        underlying: String,
+        legs: Vec<OptionLeg>,
+        quantity: u32,
+        net_price: f64,
+    },
+}
+
+impl OrderAction {
+    /// Symbol typed to confirm the order
+    pub fn symbol(&self) -> &str {
+        match self {
+            OrderAction::Submit { ticket, .. } => &ticket.symbol,
+            OrderAction::Stage(order) => &order.symbol,
+            OrderAction::Reverse(reverse) => &reverse.symbol,
+            OrderAction::Strategy { underlying, .. } => underlying,
+        }
+    }
+}
+
+/// Live order held back until the symbol is typed
+pub struct PendingLiveOrder {
+    pub order: GuardedOrder,
+    pub symbol: String,
+    pub description: String,
+    // None when no price is known yet (treated as over the limit)
+    pub notional: Option<f64>,
+    pub action: OrderAction,
+}
+
+pub struct OrderGuard {
+    pub pending: Option<PendingLiveOrder>,
+    pub input: TextInput,
+}
+
+impl OrderGuard {
+    pub fn new() -> Self {
+        Self {
+            pending: None,
+            input: TextInput::new("", InputMode::Symbol),
+        }
+    }
+
+    /// Whether an order of this notional needs the typed confirmation
+    pub fn requires_confirmation(notional: Option<f64>, threshold: f64) -> bool {
+        notional.is_none_or(|value| value >= threshold)
+    }
+
+    pub fn hold(&mut self, pending: PendingLiveOrder) {
+        self.input.clear();
+        self.pending = Some(pending);
+    }
+
+    /// Whether the typed text matches the pending order's symbol
+    pub fn is_typed(&self) -> bool {
+        self.pending
+            .as_ref()
+            .is_some_and(|pending| self.input.text().eq_ignore_ascii_case(&pending.symbol))
+    }
+
+    pub fn cancel(&mut self) {
+        self.pending = None;
+        self.input.clear();
+    }
+}
@@ -0,0 +1,76 @@
+// Order window module: the order form popped out into its own always-on-top window
+
+use crate::{TradingTerminal, environment_color, keymap};
+use gpui::{Context, Entity, FocusHandle, IntoElement, Render, Window, div, prelude::*, px, rgb};
+
+/// Second window showing the terminal's order form. All state lives in the terminal;
+/// this view only re-renders the form and forwards keystrokes to it.
+pub struct OrderEntryWindow {
+    terminal: Entity<TradingTerminal>,
+    focus_handle: FocusHandle,
+}
+
+impl OrderEntryWindow {
+    pub fn new(
+        terminal: Entity<TradingTerminal>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        // Redraw whenever the terminal changes (order state, prices, environment)
+        cx.observe(&terminal, |_, _, cx| cx.notify()).detach();
+
+        // Closing the window docks the form back into the sidebar
+        let close_terminal = terminal.downgrade();
+        window.on_window_should_close(cx, move |_window, cx| {
+            let _ = close_terminal.update(cx, |terminal, cx| {
+                terminal.order_window = None;
+                cx.notify();
+            });
+            true
+        });
+
+        let focus_handle = cx.focus_handle();
+        window.focus(&focus_handle);
+        Self {
+            terminal,
+            focus_handle,
+        }
+    }
+}
+
+impl Render for OrderEntryWindow {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = self.terminal.update(cx, |terminal, cx| {
+            div()
+                .relative()
+                .size_full()
+                .flex()
+                .flex_col()
+                .gap_4()
+                .p_6()
+                .bg(rgb(0x161b22))
+                .border_2()
+                .border_color(environment_color(terminal.profiles.environment()))
+                .min_w(px(300.0))
+                .child(terminal.render_environment_banner())
+                .child(terminal.render_order_form(cx))
+                .when_some(terminal.order_guard.pending.as_ref(), |this, pending| {
+                    this.child(terminal.render_live_order_confirmation(pending, cx))
+                })
+                .into_any_element()
+        });
+
+        div()
+            .size_full()
+            .track_focus(&self.focus_handle)
+            .on_key_down(cx.listener(|this, event: &gpui::KeyDownEvent, _, cx| {
+                this.terminal
+                    .update(cx, |terminal, cx| terminal.handle_key_down(event, cx));
+            }))
+            .on_action(cx.listener(|this, _: &keymap::SubmitOrder, _, cx| {
+                this.terminal
+                    .update(cx, |terminal, cx| terminal.submit_order(cx));
+            }))
+            .child(content)
+    }
+}
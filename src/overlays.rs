@@ -0,0 +1,202 @@
+// Overlays module: which indicators are drawn over the charts, saved with the settings
+
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// A setting typed into the indicator bar, when it's a positive number
+fn positive<T: FromStr + PartialOrd + Default>(text: &str) -> Option<T> {
+    text.trim()
+        .parse::<T>()
+        .ok()
+        .filter(|value| *value > T::default())
+}
+
+/// Bars the volume profile is built from
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum VolumeProfileMode {
+    #[default]
+    Off,
+    // Bars scrolled into view
+    Visible,
+    // The latest session's bars (the visible range on daily and longer charts)
+    Session,
+}
+
+impl VolumeProfileMode {
+    pub const ALL: [VolumeProfileMode; 3] = [
+        VolumeProfileMode::Off,
+        VolumeProfileMode::Visible,
+        VolumeProfileMode::Session,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            VolumeProfileMode::Off => "Off",
+            VolumeProfileMode::Visible => "Visible",
+            VolumeProfileMode::Session => "Session",
+        }
+    }
+}
+
+/// Period whose high, low and close the pivot levels come from
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum PivotMode {
+    #[default]
+    Off,
+    Daily,
+    Weekly,
+}
+
+impl PivotMode {
+    pub const ALL: [PivotMode; 3] = [PivotMode::Off, PivotMode::Daily, PivotMode::Weekly];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PivotMode::Off => "Off",
+            PivotMode::Daily => "Daily",
+            PivotMode::Weekly => "Weekly",
+        }
+    }
+}
+
+/// SuperTrend line; the ATR period and multiplier are text so they can be edited in place
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SuperTrendSettings {
+    pub enabled: bool,
+    pub period: String,
+    pub multiplier: String,
+}
+
+impl Default for SuperTrendSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            period: "10".to_string(),
+            multiplier: "3".to_string(),
+        }
+    }
+}
+
+impl SuperTrendSettings {
+    /// Period and multiplier, or None while either isn't a positive number
+    pub fn params(&self) -> Option<(usize, f64)> {
+        Some((positive(&self.period)?, positive(&self.multiplier)?))
+    }
+}
+
+/// Parabolic SAR dots; the acceleration step and maximum are text so they can be edited
+/// in place
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ParabolicSarSettings {
+    pub enabled: bool,
+    pub step: String,
+    pub max: String,
+}
+
+impl Default for ParabolicSarSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            step: "0.02".to_string(),
+            max: "0.2".to_string(),
+        }
+    }
+}
+
+impl ParabolicSarSettings {
+    /// Step and maximum acceleration, or None unless 0 < step <= max
+    pub fn params(&self) -> Option<(f64, f64)> {
+        let step = positive(&self.step)?;
+        let max = positive(&self.max).filter(|max| *max >= step)?;
+        Some((step, max))
+    }
+}
+
+/// Keltner Channels: an EMA of the close with bands a multiple of the ATR either side
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeltnerSettings {
+    pub enabled: bool,
+    pub period: String,
+    pub multiplier: String,
+}
+
+impl Default for KeltnerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            period: "20".to_string(),
+            multiplier: "2".to_string(),
+        }
+    }
+}
+
+impl KeltnerSettings {
+    /// Period and multiplier, or None while either isn't a positive number
+    pub fn params(&self) -> Option<(usize, f64)> {
+        Some((positive(&self.period)?, positive(&self.multiplier)?))
+    }
+}
+
+/// ADX/DMI sub-panel under the chart
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdxSettings {
+    pub enabled: bool,
+    pub period: String,
+}
+
+impl Default for AdxSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            period: "14".to_string(),
+        }
+    }
+}
+
+impl AdxSettings {
+    /// Period, or None while it isn't a positive number
+    pub fn period(&self) -> Option<usize> {
+        positive(&self.period)
+    }
+}
+
+/// Donchian Channels: the highest high and lowest low of the last `period` bars. These are
+/// the main chart's; a chart window starts from a copy and keeps its own.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DonchianSettings {
+    pub enabled: bool,
+    pub period: String,
+}
+
+impl Default for DonchianSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            period: "20".to_string(),
+        }
+    }
+}
+
+impl DonchianSettings {
+    /// Period, or None while it isn't a positive number
+    pub fn period(&self) -> Option<usize> {
+        positive(&self.period)
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChartOverlays {
+    pub volume_profile: VolumeProfileMode,
+    pub pivots: PivotMode,
+    pub supertrend: SuperTrendSettings,
+    pub parabolic_sar: ParabolicSarSettings,
+    pub keltner: KeltnerSettings,
+    pub adx: AdxSettings,
+    pub donchian: DonchianSettings,
+}
@@ -0,0 +1,205 @@
+// Performance module: win rate, average win/loss, profit factor, expectancy and drawdown
+// of the round trips matched from fills, plus the realized equity curve and a benchmark
+// buy-and-hold to compare it with
+
+use crate::account::HistoryRange;
+use crate::pnl::ClosedTrade;
+use alpaca_markets::Bar;
+use chrono::{DateTime, Local, NaiveDate, Utc};
+
+/// Daily benchmark bars loaded, enough to cover the "All" range
+pub const BENCHMARK_BARS: u32 = 1000;
+
+/// Trades closed since `range` began, in closing order
+fn trades_in(trades: &[ClosedTrade], range: HistoryRange) -> Vec<&ClosedTrade> {
+    let start = range.start_time();
+    let mut trades: Vec<&ClosedTrade> = trades
+        .iter()
+        .filter(|trade| start.is_none_or(|start| trade.closed_at >= start))
+        .collect();
+    trades.sort_by_key(|trade| trade.closed_at);
+    trades
+}
+
+/// Statistics over the trades closed in a period
+pub struct PerformanceStats {
+    pub trades: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub net: f64,
+    pub gross_profit: f64,
+    // Negative (or zero)
+    pub gross_loss: f64,
+    // Largest peak-to-trough fall of cumulative realized P&L (positive)
+    pub max_drawdown: f64,
+}
+
+impl PerformanceStats {
+    /// Stats for the trades closed since `range` began, taken in closing order
+    pub fn from_trades(trades: &[ClosedTrade], range: HistoryRange) -> Self {
+        let trades = trades_in(trades, range);
+
+        let mut stats = Self {
+            trades: trades.len(),
+            wins: 0,
+            losses: 0,
+            net: 0.0,
+            gross_profit: 0.0,
+            gross_loss: 0.0,
+            max_drawdown: 0.0,
+        };
+        let mut peak: f64 = 0.0;
+        for trade in trades {
+            if trade.pnl > 0.0 {
+                stats.wins += 1;
+                stats.gross_profit += trade.pnl;
+            } else if trade.pnl < 0.0 {
+                stats.losses += 1;
+                stats.gross_loss += trade.pnl;
+            }
+            stats.net += trade.pnl;
+            peak = peak.max(stats.net);
+            stats.max_drawdown = stats.max_drawdown.max(peak - stats.net);
+        }
+        stats
+    }
+
+    /// Share of trades that made money, in percent
+    pub fn win_rate(&self) -> Option<f64> {
+        (self.trades > 0).then(|| self.wins as f64 / self.trades as f64 * 100.0)
+    }
+
+    pub fn average_win(&self) -> Option<f64> {
+        (self.wins > 0).then(|| self.gross_profit / self.wins as f64)
+    }
+
+    /// Average losing trade (negative)
+    pub fn average_loss(&self) -> Option<f64> {
+        (self.losses > 0).then(|| self.gross_loss / self.losses as f64)
+    }
+
+    /// Gross profit over gross loss; None without losing trades
+    pub fn profit_factor(&self) -> Option<f64> {
+        (self.gross_loss < 0.0).then(|| self.gross_profit / -self.gross_loss)
+    }
+
+    /// Average P&L per trade
+    pub fn expectancy(&self) -> Option<f64> {
+        (self.trades > 0).then(|| self.net / self.trades as f64)
+    }
+}
+
+/// Cumulative realized P&L after a trade and how far it sits below the prior peak
+pub struct EquityPoint {
+    pub at: DateTime<Utc>,
+    pub equity: f64,
+    pub drawdown: f64,
+}
+
+/// Realized equity curve over the period, one point per closed trade, starting from zero
+pub fn equity_curve(trades: &[ClosedTrade], range: HistoryRange) -> Vec<EquityPoint> {
+    let mut equity = 0.0;
+    let mut peak: f64 = 0.0;
+    trades_in(trades, range)
+        .into_iter()
+        .map(|trade| {
+            equity += trade.pnl;
+            peak = peak.max(equity);
+            EquityPoint {
+                at: trade.closed_at,
+                equity,
+                drawdown: peak - equity,
+            }
+        })
+        .collect()
+}
+
+/// The deepest drawdown of a curve: where it bottomed and when (if ever) the prior peak was
+/// regained
+pub struct DrawdownSpan {
+    pub depth: f64,
+    pub trough_at: DateTime<Utc>,
+    // Point index of the trough
+    pub trough: usize,
+    pub recovered_at: Option<DateTime<Utc>>,
+}
+
+impl DrawdownSpan {
+    pub fn of(curve: &[EquityPoint]) -> Option<Self> {
+        let (trough, deepest) = curve
+            .iter()
+            .enumerate()
+            .filter(|(_, point)| point.drawdown > 0.0)
+            .max_by(|(_, a), (_, b)| a.drawdown.total_cmp(&b.drawdown))?;
+        Some(Self {
+            depth: deepest.drawdown,
+            trough_at: deepest.at,
+            trough,
+            recovered_at: curve[trough..]
+                .iter()
+                .find(|point| point.drawdown <= 0.0)
+                .map(|point| point.at),
+        })
+    }
+
+    /// Time from the trough back to the prior peak
+    pub fn recovery_time(&self) -> Option<chrono::Duration> {
+        self.recovered_at.map(|at| at - self.trough_at)
+    }
+}
+
+/// Performance tab state
+pub struct Performance {
+    pub range: HistoryRange,
+    // Daily bars of the benchmark, the symbol they are for and the day they were loaded
+    pub benchmark_bars: Vec<Bar>,
+    pub benchmark_symbol: String,
+    pub benchmark_loaded_on: Option<NaiveDate>,
+    pub benchmark_loading: bool,
+    pub benchmark_error: Option<String>,
+}
+
+impl Performance {
+    pub fn new() -> Self {
+        Self {
+            range: HistoryRange::Month,
+            benchmark_bars: Vec::new(),
+            benchmark_symbol: String::new(),
+            benchmark_loaded_on: None,
+            benchmark_loading: false,
+            benchmark_error: None,
+        }
+    }
+
+    /// Whether the loaded bars are for another symbol or an earlier day
+    pub fn benchmark_stale(&self, symbol: &str) -> bool {
+        self.benchmark_symbol != symbol
+            || self.benchmark_loaded_on != Some(Local::now().date_naive())
+    }
+
+    /// Benchmark close on `date`, or on the last trading day before it
+    fn close_on(&self, date: NaiveDate) -> Option<f64> {
+        self.benchmark_bars
+            .iter()
+            .rev()
+            .find(|bar| bar.timestamp.date_naive() <= date)
+            .map(|bar| bar.close)
+    }
+
+    /// Dollar P&L of putting `capital` into the benchmark at the close of the curve's first
+    /// day, at each curve point. Starts from zero like the realized curve. None when the
+    /// bars don't reach back to that day.
+    pub fn benchmark_curve(&self, curve: &[EquityPoint], capital: f64) -> Option<Vec<f64>> {
+        let base = self.close_on(curve.first()?.at.date_naive())?;
+        if base <= 0.0 {
+            return None;
+        }
+        curve
+            .iter()
+            .map(|point| {
+                let close = self.close_on(point.at.date_naive())?;
+                Some(capital * (close / base - 1.0))
+            })
+            .collect()
+    }
+}
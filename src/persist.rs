@@ -0,0 +1,104 @@
+// Persist module: JSON state files in the config directory. Writes go to a temporary file
+// that is renamed over the old one, so a crash never leaves half a file behind, and a file
+// that can't be parsed is moved aside before the next save could overwrite it.
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::{error, warn};
+
+/// Read and parse a JSON file. None when it doesn't exist or can't be parsed; an
+/// unparseable file is renamed to `<name>.invalid-<timestamp>` so its contents survive
+/// the defaults that replace it.
+pub fn load<T: DeserializeOwned>(path: &Path, what: &str) -> Option<T> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    match serde_json::from_str::<T>(&contents) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            warn!("⚠️  Invalid {} file {}: {}", what, path.display(), e);
+            back_up(path);
+            None
+        }
+    }
+}
+
+/// Move an unreadable file out of the way, keeping it next to where it was
+pub fn back_up(path: &Path) {
+    let backup = backup_path(path);
+    match std::fs::rename(path, &backup) {
+        Ok(_) => warn!("⚠️  Kept the unreadable file as {}", backup.display()),
+        Err(e) => error!("✗ Error moving unreadable {} aside: {}", path.display(), e),
+    }
+}
+
+fn backup_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(
+        "{}.invalid-{}",
+        name,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ))
+}
+
+/// Serialize `value` as pretty JSON and write it atomically
+pub fn save<T: Serialize>(path: &Path, value: &T) -> std::io::Result<()> {
+    let json = serde_json::to_string_pretty(value).map_err(std::io::Error::other)?;
+    write(path, json.as_bytes())
+}
+
+/// Write `contents` to a temporary file beside `path`, flush it to disk and rename it into
+/// place, creating the directory first if needed
+pub fn write(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    write_file(path, contents, false)
+}
+
+/// Like `write`, but the file is created readable by the current user only, so secrets are
+/// never briefly exposed with the default permissions
+pub fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    write_file(path, contents, true)
+}
+
+fn write_file(path: &Path, contents: &[u8], private: bool) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let temp = temp_path(path);
+    // A leftover temp file would keep its old permissions
+    let _ = std::fs::remove_file(&temp);
+    let result = create(&temp, private)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .and_then(|_| std::fs::rename(&temp, path));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp);
+    }
+    result
+}
+
+fn create(path: &Path, private: bool) -> std::io::Result<std::fs::File> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    #[cfg(not(unix))]
+    let _ = private;
+    options.open(path)
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.tmp", name))
+}
@@ -0,0 +1,168 @@
+// P&L module for matching fills into lots and computing realized P&L
+
+use crate::account::Activity;
+use std::collections::{BTreeMap, VecDeque};
+
+/// An open lot (positive qty = long, negative qty = short)
+#[derive(Clone)]
+pub struct Lot {
+    pub symbol: String,
+    pub qty: f64,
+    pub price: f64,
+    pub opened_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Lot {
+    /// Cost basis of the lot (always positive)
+    pub fn cost_basis(&self) -> f64 {
+        self.qty.abs() * self.price
+    }
+
+    /// Time the lot has been held
+    pub fn holding_period(&self) -> chrono::Duration {
+        chrono::Utc::now() - self.opened_at
+    }
+}
+
+/// A closed round trip produced by matching an exit fill against an open lot
+#[derive(Clone)]
+pub struct ClosedTrade {
+    pub symbol: String,
+    pub qty: f64,
+    pub entry_price: f64,
+    pub exit_price: f64,
+    pub opened_at: chrono::DateTime<chrono::Utc>,
+    pub closed_at: chrono::DateTime<chrono::Utc>,
+    pub is_long: bool,
+    pub pnl: f64,
+}
+
+/// FIFO lot ledger built from fill activities
+#[derive(Default)]
+pub struct LotLedger {
+    pub open_lots: BTreeMap<String, VecDeque<Lot>>,
+    pub closed_trades: Vec<ClosedTrade>,
+}
+
+impl LotLedger {
+    /// Build a ledger from activities (any order); non-fill activities are ignored
+    pub fn from_activities(activities: &[Activity]) -> Self {
+        let mut fills: Vec<&Activity> = activities.iter().filter(|a| a.is_fill()).collect();
+        fills.sort_by_key(|a| a.timestamp);
+
+        let mut ledger = Self::default();
+        for fill in fills {
+            let (Some(symbol), Some(qty), Some(price)) = (
+                fill.symbol.as_ref(),
+                fill.qty.as_ref().and_then(|q| q.parse::<f64>().ok()),
+                fill.price.as_ref().and_then(|p| p.parse::<f64>().ok()),
+            ) else {
+                continue;
+            };
+
+            let signed_qty = if fill.is_buy() { qty } else { -qty };
+            ledger.apply_fill(symbol, signed_qty, price, fill.timestamp);
+        }
+        ledger
+    }
+
+    /// Apply a single fill, closing opposite-side lots first (FIFO) and opening a new lot with any remainder
+    fn apply_fill(
+        &mut self,
+        symbol: &str,
+        signed_qty: f64,
+        price: f64,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    ) {
+        let lots = self.open_lots.entry(symbol.to_string()).or_default();
+        let mut remaining = signed_qty;
+
+        while remaining.abs() > f64::EPSILON {
+            let Some(lot) = lots.front_mut() else {
+                break;
+            };
+
+            // Same direction as the open lots: nothing to close
+            if lot.qty.signum() == remaining.signum() {
+                break;
+            }
+
+            let matched = lot.qty.abs().min(remaining.abs());
+            let is_long = lot.qty > 0.0;
+            let pnl = if is_long {
+                (price - lot.price) * matched
+            } else {
+                (lot.price - price) * matched
+            };
+
+            self.closed_trades.push(ClosedTrade {
+                symbol: symbol.to_string(),
+                qty: matched,
+                entry_price: lot.price,
+                exit_price: price,
+                opened_at: lot.opened_at,
+                closed_at: timestamp,
+                is_long,
+                pnl,
+            });
+
+            lot.qty -= matched * lot.qty.signum();
+            remaining -= matched * remaining.signum();
+
+            if lot.qty.abs() <= f64::EPSILON {
+                lots.pop_front();
+            }
+        }
+
+        if remaining.abs() > f64::EPSILON {
+            lots.push_back(Lot {
+                symbol: symbol.to_string(),
+                qty: remaining,
+                price,
+                opened_at: timestamp,
+            });
+        }
+    }
+
+    /// Open lots for a symbol, oldest first
+    pub fn lots_for(&self, symbol: &str) -> Vec<&Lot> {
+        self.open_lots
+            .get(symbol)
+            .map(|lots| lots.iter().collect())
+            .unwrap_or_default()
+    }
+
+    /// Closed trades for a symbol, most recent first
+    pub fn closed_trades_for(&self, symbol: &str) -> Vec<&ClosedTrade> {
+        let mut trades: Vec<&ClosedTrade> = self
+            .closed_trades
+            .iter()
+            .filter(|t| t.symbol == symbol)
+            .collect();
+        trades.sort_by(|a, b| b.closed_at.cmp(&a.closed_at));
+        trades
+    }
+
+    /// Realized P&L for a single symbol
+    pub fn realized_pnl(&self, symbol: &str) -> f64 {
+        self.closed_trades
+            .iter()
+            .filter(|t| t.symbol == symbol)
+            .map(|t| t.pnl)
+            .sum()
+    }
+
+    /// Realized P&L per symbol
+    pub fn realized_by_symbol(&self) -> BTreeMap<String, f64> {
+        let mut by_symbol = BTreeMap::new();
+        for trade in &self.closed_trades {
+            *by_symbol.entry(trade.symbol.clone()).or_insert(0.0) += trade.pnl;
+        }
+        by_symbol
+    }
+
+    /// Total realized P&L across all symbols
+    pub fn total_realized(&self) -> f64 {
+        self.closed_trades.iter().map(|t| t.pnl).sum()
+    }
+}
@@ -0,0 +1,102 @@
+// Rate limit module: keeps REST traffic under Alpaca's per-minute request limit and pauses
+// everything after an HTTP 429
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// Alpaca allows 200 REST requests per minute per account
+pub const REQUESTS_PER_MINUTE: usize = 200;
+const WINDOW: Duration = Duration::from_secs(60);
+/// Pause after the first 429, doubled for each one that follows
+const INITIAL_PAUSE: Duration = Duration::from_secs(2);
+const MAX_PAUSE: Duration = Duration::from_secs(60);
+/// How many times a rate-limited request is sent again before the error reaches the UI
+pub const MAX_RESENDS: u32 = 5;
+
+struct State {
+    // Send times handed out within the last minute (some may still be in the future)
+    slots: VecDeque<Instant>,
+    // Nothing is sent before this after a 429
+    paused_until: Option<Instant>,
+    next_pause: Duration,
+}
+
+pub struct RateLimiter {
+    state: Mutex<State>,
+}
+
+pub static LIMITER: RateLimiter = RateLimiter::new();
+
+impl RateLimiter {
+    const fn new() -> Self {
+        Self {
+            state: Mutex::new(State {
+                slots: VecDeque::new(),
+                paused_until: None,
+                next_pause: INITIAL_PAUSE,
+            }),
+        }
+    }
+
+    /// Block until a request may be sent. Slots are handed out in call order, so excess
+    /// requests queue up behind each other instead of all waking at once.
+    pub fn acquire(&self) {
+        let slot = {
+            let Ok(mut state) = self.state.lock() else {
+                return;
+            };
+            let now = Instant::now();
+            while state
+                .slots
+                .front()
+                .is_some_and(|sent| now.duration_since(*sent) >= WINDOW)
+            {
+                state.slots.pop_front();
+            }
+
+            let mut slot = now;
+            if state.slots.len() >= REQUESTS_PER_MINUTE {
+                let oldest = state.slots[state.slots.len() - REQUESTS_PER_MINUTE];
+                slot = slot.max(oldest + WINDOW);
+            }
+            if let Some(paused_until) = state.paused_until {
+                slot = slot.max(paused_until);
+            }
+            state.slots.push_back(slot);
+            slot
+        };
+
+        let wait = slot.saturating_duration_since(Instant::now());
+        if !wait.is_zero() {
+            debug!(
+                "⏳ Request queued for {}ms by the rate limiter",
+                wait.as_millis()
+            );
+            thread::sleep(wait);
+        }
+    }
+
+    /// The API answered 429: hold all requests back for a while
+    pub fn throttled(&self) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        let pause = state.next_pause;
+        state.paused_until = Some(Instant::now() + pause);
+        state.next_pause = (pause * 2).min(MAX_PAUSE);
+        warn!(
+            "🚦 Rate limited by Alpaca, pausing requests for {}s",
+            pause.as_secs()
+        );
+    }
+
+    /// A request got through, so the next 429 starts from the shortest pause again
+    pub fn succeeded(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.next_pause = INITIAL_PAUSE;
+        }
+    }
+}
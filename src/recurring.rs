@@ -0,0 +1,240 @@
+// Recurring module: weekly notional buys (dollar-cost averaging) submitted at the market
+// open by the order scheduler, with a history of each occurrence
+
+use chrono::{DateTime, Datelike, Duration, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::America::New_York;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing::{error, info};
+
+/// Occurrences kept per plan
+const MAX_HISTORY: usize = 20;
+
+/// Weekdays a plan can run on
+pub const WEEKDAYS: [Weekday; 5] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+];
+
+/// What happened on one run of a plan
+#[derive(Clone, Serialize, Deserialize)]
+pub enum Outcome {
+    // Sent, waiting for the API's answer
+    Pending,
+    Submitted { order_id: String },
+    Failed { error: String },
+    SkippedHoliday,
+    SkippedHalted,
+    // Over the order size limit, or the live confirmation wasn't given
+    Blocked { reason: String },
+    // The terminal wasn't running that day
+    Missed,
+}
+
+impl Outcome {
+    pub fn describe(&self) -> String {
+        match self {
+            Outcome::Pending => "submitting...".to_string(),
+            Outcome::Submitted { order_id } => {
+                format!("✓ order {}", order_id.chars().take(8).collect::<String>())
+            }
+            Outcome::Failed { error } => format!("✗ {}", error),
+            Outcome::SkippedHoliday => "skipped (market holiday)".to_string(),
+            Outcome::SkippedHalted => "skipped (daily loss limit)".to_string(),
+            Outcome::Blocked { reason } => format!("🚫 {}", reason),
+            Outcome::Missed => "missed (terminal closed)".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Occurrence {
+    pub at: DateTime<Utc>,
+    pub outcome: Outcome,
+}
+
+/// Buy `notional` dollars of `symbol` at the open every `weekday`
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RecurringPlan {
+    pub id: u64,
+    pub symbol: String,
+    pub notional: f64,
+    pub weekday: Weekday,
+    pub next_at: DateTime<Utc>,
+    // Newest last
+    pub history: Vec<Occurrence>,
+}
+
+impl RecurringPlan {
+    pub fn describe(&self) -> String {
+        format!(
+            "{} ${:.2} every {} at open",
+            self.symbol, self.notional, self.weekday
+        )
+    }
+
+    fn record(&mut self, at: DateTime<Utc>, outcome: Outcome) {
+        self.history.push(Occurrence { at, outcome });
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+    }
+}
+
+/// Regular-session open (09:30 ET) on the next `weekday` after `now`
+pub fn next_open_on(weekday: Weekday, now: DateTime<Utc>) -> DateTime<Utc> {
+    let open = NaiveTime::from_hms_opt(9, 30, 0).unwrap_or_default();
+    let today = now.with_timezone(&New_York).date_naive();
+    (0..=7)
+        .map(|days| today + Duration::days(days))
+        .filter(|date| date.weekday() == weekday)
+        .filter_map(|date| {
+            New_York
+                .from_local_datetime(&date.and_time(open))
+                .earliest()
+        })
+        .map(|at| at.with_timezone(&Utc))
+        .find(|at| *at > now)
+        .unwrap_or(now + Duration::weeks(1))
+}
+
+/// A plan's run that came due
+pub struct DueRun {
+    pub plan: u64,
+    pub symbol: String,
+    pub notional: f64,
+    pub at: DateTime<Utc>,
+}
+
+/// New-plan form in the sidebar
+pub struct RecurringForm {
+    pub open: bool,
+    pub amount: String,
+    pub weekday: Weekday,
+}
+
+impl RecurringForm {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            amount: String::new(),
+            weekday: Weekday::Mon,
+        }
+    }
+}
+
+/// Plans saved to `<config dir>/alpaca_terminal/recurring.json`
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecurringStore {
+    pub plans: Vec<RecurringPlan>,
+    next_id: u64,
+}
+
+fn recurring_path() -> Option<PathBuf> {
+    crate::settings::config_dir().map(|dir| dir.join("recurring.json"))
+}
+
+impl RecurringStore {
+    pub fn add(&mut self, symbol: String, notional: f64, weekday: Weekday) {
+        self.next_id += 1;
+        self.plans.push(RecurringPlan {
+            id: self.next_id,
+            symbol,
+            notional,
+            weekday,
+            next_at: next_open_on(weekday, Utc::now()),
+            history: Vec::new(),
+        });
+    }
+
+    pub fn remove(&mut self, id: u64) {
+        self.plans.retain(|plan| plan.id != id);
+    }
+
+    /// Advance every plan whose run has come, recording holidays and halts as skipped and
+    /// runs from an earlier day as missed. Returns the runs to submit; each has a pending
+    /// occurrence awaiting `resolve`.
+    pub fn take_due(
+        &mut self,
+        now: DateTime<Utc>,
+        is_holiday: impl Fn(DateTime<Utc>) -> bool,
+        halted: bool,
+    ) -> Vec<DueRun> {
+        let mut due = Vec::new();
+        for plan in self.plans.iter_mut().filter(|plan| plan.next_at <= now) {
+            let at = plan.next_at;
+            plan.next_at = next_open_on(plan.weekday, now);
+            if is_holiday(at) {
+                plan.record(at, Outcome::SkippedHoliday);
+            } else if at.with_timezone(&New_York).date_naive()
+                != now.with_timezone(&New_York).date_naive()
+            {
+                plan.record(at, Outcome::Missed);
+            } else if halted {
+                plan.record(at, Outcome::SkippedHalted);
+            } else {
+                plan.record(at, Outcome::Pending);
+                due.push(DueRun {
+                    plan: plan.id,
+                    symbol: plan.symbol.clone(),
+                    notional: plan.notional,
+                    at,
+                });
+            }
+        }
+        due
+    }
+
+    /// Record the API's answer for a plan's pending run
+    pub fn resolve(&mut self, id: u64, outcome: Outcome) {
+        let pending = self
+            .plans
+            .iter_mut()
+            .find(|plan| plan.id == id)
+            .and_then(|plan| {
+                plan.history
+                    .iter_mut()
+                    .rev()
+                    .find(|occurrence| matches!(occurrence.outcome, Outcome::Pending))
+            });
+        if let Some(occurrence) = pending {
+            occurrence.outcome = outcome;
+        }
+    }
+
+    /// Load plans from disk, falling back to none if missing or invalid
+    pub fn load() -> Self {
+        let Some(path) = recurring_path() else {
+            return Self::default();
+        };
+
+        match crate::persist::load::<RecurringStore>(&path, "recurring buys") {
+            Some(store) => {
+                info!(
+                    "✅ Loaded {} recurring buys from {}",
+                    store.plans.len(),
+                    path.display()
+                );
+                store
+            }
+            None => Self::default(),
+        }
+    }
+
+    /// Write plans to disk
+    pub fn save(&self) {
+        let Some(path) = recurring_path() else {
+            return;
+        };
+
+        let result = crate::persist::save(&path, self);
+
+        if let Err(e) = result {
+            error!("✗ Error saving recurring buys to {}: {}", path.display(), e);
+        }
+    }
+}
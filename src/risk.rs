@@ -0,0 +1,80 @@
+// Risk module: guardrails that block orders before they are sent to the API
+
+use crate::config::RiskConfig;
+use chrono::{Local, NaiveDate};
+
+/// Why an order breaks the configured size limits, or None when it is allowed.
+/// `notional` is None when no price is known, which fails an equity-percent limit.
+pub fn order_size_violation(
+    limits: &RiskConfig,
+    qty: f64,
+    notional: Option<f64>,
+    equity: Option<f64>,
+) -> Option<String> {
+    if let Some(max_shares) = limits.max_order_shares {
+        if qty > max_shares {
+            return Some(format!(
+                "Order blocked: {} shares exceeds the {} share limit",
+                qty, max_shares
+            ));
+        }
+    }
+
+    let max_percent = limits.max_order_equity_percent?;
+    let Some(equity) = equity.filter(|equity| *equity > 0.0) else {
+        return Some(format!(
+            "Order blocked: account equity is unknown, can't check the {}% of equity limit",
+            max_percent
+        ));
+    };
+    let Some(notional) = notional else {
+        return Some(format!(
+            "Order blocked: no price to check the {}% of equity limit against",
+            max_percent
+        ));
+    };
+    let percent = notional / equity * 100.0;
+    (percent > max_percent).then(|| {
+        format!(
+            "Order blocked: ${:.2} is {:.1}% of equity, over the {}% limit",
+            notional, percent, max_percent
+        )
+    })
+}
+
+/// Trading halt once today's loss reaches the configured maximum
+pub struct LossLimit {
+    // Day the user chose to keep trading past the limit
+    overridden_on: Option<NaiveDate>,
+    // Override confirmation dialog is open
+    pub confirming: bool,
+}
+
+impl LossLimit {
+    pub fn new() -> Self {
+        Self {
+            overridden_on: None,
+            confirming: false,
+        }
+    }
+
+    /// Whether today's P&L has reached the loss limit. The P&L is measured from the
+    /// previous close, so a new session starts under the limit again.
+    pub fn breached(limits: &RiskConfig, daily_pnl: Option<f64>) -> bool {
+        limits
+            .max_daily_loss
+            .zip(daily_pnl)
+            .is_some_and(|(max_loss, pnl)| pnl <= -max_loss)
+    }
+
+    /// Whether trading was re-enabled past the limit today
+    pub fn is_overridden(&self) -> bool {
+        self.overridden_on == Some(Local::now().date_naive())
+    }
+
+    /// Keep trading for the rest of the day
+    pub fn override_today(&mut self) {
+        self.overridden_on = Some(Local::now().date_naive());
+        self.confirming = false;
+    }
+}
@@ -0,0 +1,72 @@
+// Scalp module: compact sidebar pad for fast intraday trading of the charted symbol, with
+// one-click market buys and sells of a preset size, flatten and reverse
+
+use crate::hotkeys::HotkeySide;
+use serde::{Deserialize, Serialize};
+
+/// Saved with the other settings; the size is text so it can be edited in place
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScalpSettings {
+    // The sidebar shows the scalp pad instead of the order form and panels
+    pub enabled: bool,
+    pub size: String,
+}
+
+impl Default for ScalpSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            size: "100".to_string(),
+        }
+    }
+}
+
+impl ScalpSettings {
+    /// Preset order size, or None when it isn't a positive number
+    pub fn quantity(&self) -> Option<f64> {
+        self.size
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|qty| *qty > 0.0)
+    }
+}
+
+/// Sizes offered as one-click presets
+pub const SIZE_PRESETS: [&str; 4] = ["1", "10", "100", "500"];
+
+/// Button pressed on the pad (flatten only closes, so it isn't guarded)
+#[derive(Clone, Copy, PartialEq)]
+pub enum ScalpAction {
+    Buy,
+    Sell,
+    Reverse,
+}
+
+/// Opposite entry of a reverse, sent once the closing order has left the position flat
+pub struct PendingReverse {
+    pub symbol: String,
+    pub side: HotkeySide,
+    pub qty: f64,
+}
+
+#[derive(Default)]
+pub struct Scalp {
+    pub pending_reverse: Option<PendingReverse>,
+}
+
+impl Scalp {
+    /// Take the pending reverse entry for `symbol` once `position_qty` shows it flat
+    pub fn take_reverse(&mut self, symbol: &str, position_qty: f64) -> Option<PendingReverse> {
+        let due = self
+            .pending_reverse
+            .as_ref()
+            .is_some_and(|reverse| reverse.symbol == symbol && position_qty.abs() < 1e-9);
+        if due {
+            self.pending_reverse.take()
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,123 @@
+// Schedule module: orders staged locally and submitted at a chosen time (the next market
+// open from the clock API, or a wall-clock time in New York)
+
+use crate::clock::{CalendarEvent, MarketClock};
+use crate::hotkeys::HotkeySide;
+use chrono::{DateTime, Datelike, Duration, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::America::New_York;
+
+/// When the order form submits
+#[derive(Clone, Copy, PartialEq)]
+pub enum ScheduleMode {
+    Now,
+    Open,
+    // The time typed into the form, Eastern
+    Time,
+}
+
+impl ScheduleMode {
+    pub const ALL: [ScheduleMode; 3] = [ScheduleMode::Now, ScheduleMode::Open, ScheduleMode::Time];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScheduleMode::Now => "Now",
+            ScheduleMode::Open => "At Open",
+            ScheduleMode::Time => "At Time (ET)",
+        }
+    }
+}
+
+/// An order held until its submit time
+pub struct ScheduledOrder {
+    pub id: u64,
+    pub symbol: String,
+    pub side: HotkeySide,
+    pub qty: f64,
+    pub limit_price: Option<f64>,
+    pub at: DateTime<Utc>,
+    pub description: String,
+}
+
+pub struct Scheduler {
+    pub mode: ScheduleMode,
+    // HH:MM Eastern, for ScheduleMode::Time
+    pub time: String,
+    pub orders: Vec<ScheduledOrder>,
+    next_id: u64,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self {
+            mode: ScheduleMode::Now,
+            time: "15:55".to_string(),
+            orders: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Submit time for the selected mode, or why there isn't one
+    pub fn submit_time(
+        &self,
+        clock: Option<&MarketClock>,
+        calendar: &[CalendarEvent],
+        now: DateTime<Utc>,
+    ) -> Result<DateTime<Utc>, String> {
+        match self.mode {
+            ScheduleMode::Now => Ok(now),
+            ScheduleMode::Open => clock
+                .map(|clock| clock.next_open)
+                .ok_or_else(|| "Market clock not loaded yet".to_string()),
+            ScheduleMode::Time => {
+                let time = NaiveTime::parse_from_str(self.time.trim(), "%H:%M")
+                    .map_err(|_| format!("Enter the time as HH:MM, not {:?}", self.time))?;
+                next_session_time(time, calendar, now)
+                    .ok_or_else(|| "No trading day in the next two weeks".to_string())
+            }
+        }
+    }
+
+    /// Hold an order, soonest first
+    pub fn stage(&mut self, mut order: ScheduledOrder) {
+        self.next_id += 1;
+        order.id = self.next_id;
+        let index = self.orders.partition_point(|staged| staged.at <= order.at);
+        self.orders.insert(index, order);
+    }
+
+    pub fn cancel(&mut self, id: u64) -> Option<ScheduledOrder> {
+        let index = self.orders.iter().position(|order| order.id == id)?;
+        Some(self.orders.remove(index))
+    }
+
+    /// Remove and return the orders whose time has come
+    pub fn take_due(&mut self, now: DateTime<Utc>) -> Vec<ScheduledOrder> {
+        let due = self.orders.partition_point(|order| order.at <= now);
+        self.orders.drain(..due).collect()
+    }
+}
+
+/// Next moment after `now` that `time` (Eastern) falls on a weekday that isn't a market
+/// holiday
+fn next_session_time(
+    time: NaiveTime,
+    calendar: &[CalendarEvent],
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let today = now.with_timezone(&New_York).date_naive();
+    (0..14)
+        .map(|days| today + Duration::days(days))
+        .filter(|date| !matches!(date.weekday(), Weekday::Sat | Weekday::Sun))
+        .filter(|date| {
+            !calendar
+                .iter()
+                .any(|event| matches!(event, CalendarEvent::Holiday(holiday) if holiday == date))
+        })
+        .filter_map(|date| {
+            New_York
+                .from_local_datetime(&date.and_time(time))
+                .earliest()
+        })
+        .map(|at| at.with_timezone(&Utc))
+        .find(|at| *at > now)
+}
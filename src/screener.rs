@@ -0,0 +1,452 @@
+// Screener module: ranks a symbol universe's snapshots by day change, opening gap and
+// relative volume
+
+use crate::config::Universe;
+use crate::snapshot::SymbolSnapshot;
+use alpaca_markets::Bar;
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Daily closes averaged for the SMA filter
+pub const SMA_PERIOD: usize = 20;
+
+/// What the movers list is ranked by
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ScreenerSort {
+    Gainers,
+    Losers,
+    Gap,
+    RelativeVolume,
+}
+
+impl ScreenerSort {
+    pub const ALL: [ScreenerSort; 4] = [
+        ScreenerSort::Gainers,
+        ScreenerSort::Losers,
+        ScreenerSort::Gap,
+        ScreenerSort::RelativeVolume,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScreenerSort::Gainers => "Gainers",
+            ScreenerSort::Losers => "Losers",
+            ScreenerSort::Gap => "Gap",
+            ScreenerSort::RelativeVolume => "Rel Volume",
+        }
+    }
+}
+
+/// Price position relative to the 20-day simple moving average
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum SmaFilter {
+    #[default]
+    Any,
+    Above,
+    Below,
+}
+
+impl SmaFilter {
+    pub const ALL: [SmaFilter; 3] = [SmaFilter::Any, SmaFilter::Above, SmaFilter::Below];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SmaFilter::Any => "Any SMA",
+            SmaFilter::Above => "Above SMA20",
+            SmaFilter::Below => "Below SMA20",
+        }
+    }
+}
+
+/// A user-defined screen. Unset bounds don't filter; saved by name in the settings file.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ScreenFilter {
+    pub name: String,
+    pub universe: Universe,
+    pub sort: ScreenerSort,
+    pub min_price: Option<f64>,
+    pub max_price: Option<f64>,
+    pub min_volume: Option<f64>,
+    // Day change bounds in percent
+    pub min_change: Option<f64>,
+    pub max_change: Option<f64>,
+    pub sma: SmaFilter,
+    // Scanning alert: notify when a symbol newly passes, optionally starring it
+    #[serde(default)]
+    pub scan: bool,
+    #[serde(default)]
+    pub add_to_watchlist: bool,
+}
+
+impl ScreenFilter {
+    /// Whether a row passes every bound except the SMA one, which needs daily bars
+    fn matches_snapshot(&self, row: &ScreenerRow) -> bool {
+        let within = |value: Option<f64>, min: Option<f64>, max: Option<f64>| {
+            if min.is_none() && max.is_none() {
+                return true;
+            }
+            value.is_some_and(|value| {
+                min.is_none_or(|min| value >= min) && max.is_none_or(|max| value <= max)
+            })
+        };
+        within(row.price, self.min_price, self.max_price)
+            && within(row.volume, self.min_volume, None)
+            && within(row.change_percent, self.min_change, self.max_change)
+    }
+
+    fn matches_sma(&self, row: &ScreenerRow, sma: Option<f64>) -> bool {
+        match self.sma {
+            SmaFilter::Any => true,
+            SmaFilter::Above => row.price.zip(sma).is_some_and(|(price, sma)| price > sma),
+            SmaFilter::Below => row.price.zip(sma).is_some_and(|(price, sma)| price < sma),
+        }
+    }
+}
+
+/// Text field in the screen filter form that has keyboard focus
+#[derive(Clone, Copy, PartialEq)]
+pub enum FilterField {
+    MinPrice,
+    MaxPrice,
+    MinVolume,
+    MinChange,
+    MaxChange,
+    Name,
+}
+
+impl FilterField {
+    pub const NUMERIC: [FilterField; 5] = [
+        FilterField::MinPrice,
+        FilterField::MaxPrice,
+        FilterField::MinVolume,
+        FilterField::MinChange,
+        FilterField::MaxChange,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FilterField::MinPrice => "Min Price",
+            FilterField::MaxPrice => "Max Price",
+            FilterField::MinVolume => "Min Volume",
+            FilterField::MinChange => "Min Chg %",
+            FilterField::MaxChange => "Max Chg %",
+            FilterField::Name => "Screen name",
+        }
+    }
+}
+
+/// Filter form in the Movers tab
+pub struct FilterForm {
+    pub open: bool,
+    pub min_price: String,
+    pub max_price: String,
+    pub min_volume: String,
+    pub min_change: String,
+    pub max_change: String,
+    pub sma: SmaFilter,
+    // Name the screen is saved under
+    pub name: String,
+}
+
+impl FilterForm {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            min_price: String::new(),
+            max_price: String::new(),
+            min_volume: String::new(),
+            min_change: String::new(),
+            max_change: String::new(),
+            sma: SmaFilter::Any,
+            name: String::new(),
+        }
+    }
+
+    /// Text of a field, for editing and display
+    pub fn field_mut(&mut self, field: FilterField) -> &mut String {
+        match field {
+            FilterField::MinPrice => &mut self.min_price,
+            FilterField::MaxPrice => &mut self.max_price,
+            FilterField::MinVolume => &mut self.min_volume,
+            FilterField::MinChange => &mut self.min_change,
+            FilterField::MaxChange => &mut self.max_change,
+            FilterField::Name => &mut self.name,
+        }
+    }
+
+    pub fn field(&self, field: FilterField) -> &str {
+        match field {
+            FilterField::MinPrice => &self.min_price,
+            FilterField::MaxPrice => &self.max_price,
+            FilterField::MinVolume => &self.min_volume,
+            FilterField::MinChange => &self.min_change,
+            FilterField::MaxChange => &self.max_change,
+            FilterField::Name => &self.name,
+        }
+    }
+
+    /// Build a filter from the form, validating the numbers
+    pub fn filter(&self, universe: Universe, sort: ScreenerSort) -> Result<ScreenFilter, String> {
+        let number = |field: FilterField| -> Result<Option<f64>, String> {
+            let text = self.field(field).trim();
+            if text.is_empty() {
+                return Ok(None);
+            }
+            text.parse::<f64>()
+                .map(Some)
+                .map_err(|_| format!("{} must be a number", field.label()))
+        };
+        let filter = ScreenFilter {
+            name: self.name.trim().to_string(),
+            universe,
+            sort,
+            min_price: number(FilterField::MinPrice)?,
+            max_price: number(FilterField::MaxPrice)?,
+            min_volume: number(FilterField::MinVolume)?,
+            min_change: number(FilterField::MinChange)?,
+            max_change: number(FilterField::MaxChange)?,
+            sma: self.sma,
+            scan: false,
+            add_to_watchlist: false,
+        };
+        if let (Some(min), Some(max)) = (filter.min_price, filter.max_price)
+            && min > max
+        {
+            return Err("Min price is above max price".to_string());
+        }
+        if let (Some(min), Some(max)) = (filter.min_change, filter.max_change)
+            && min > max
+        {
+            return Err("Min change is above max change".to_string());
+        }
+        Ok(filter)
+    }
+
+    /// Fill the form from a saved screen
+    pub fn load(&mut self, filter: &ScreenFilter) {
+        let text = |value: Option<f64>| value.map(|value| value.to_string()).unwrap_or_default();
+        self.min_price = text(filter.min_price);
+        self.max_price = text(filter.max_price);
+        self.min_volume = text(filter.min_volume);
+        self.min_change = text(filter.min_change);
+        self.max_change = text(filter.max_change);
+        self.sma = filter.sma;
+        self.name = filter.name.clone();
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self {
+            open: self.open,
+            ..Self::new()
+        };
+    }
+}
+
+/// Simple moving average of the last `period` closes
+pub fn sma(bars: &[Bar], period: usize) -> Option<f64> {
+    if period == 0 || bars.len() < period {
+        return None;
+    }
+    let closes = bars[bars.len() - period..].iter().map(|bar| bar.close);
+    Some(closes.sum::<f64>() / period as f64)
+}
+
+/// One symbol's figures for the movers table
+pub struct ScreenerRow {
+    pub symbol: String,
+    pub price: Option<f64>,
+    pub change_percent: Option<f64>,
+    // Today's open against the previous close
+    pub gap_percent: Option<f64>,
+    // Today's volume so far over the previous session's
+    pub relative_volume: Option<f64>,
+    pub volume: Option<f64>,
+}
+
+impl ScreenerRow {
+    fn from_snapshot(snapshot: &SymbolSnapshot) -> Self {
+        let today = snapshot.daily_bar.as_ref();
+        let previous = snapshot.prev_daily_bar.as_ref();
+        Self {
+            symbol: snapshot.symbol.clone(),
+            price: snapshot.price(),
+            change_percent: snapshot.day_change_percent(),
+            gap_percent: today
+                .and_then(|bar| snapshot.change(bar.open))
+                .map(|(_, percent)| percent),
+            relative_volume: today
+                .zip(previous.filter(|bar| bar.volume > 0))
+                .map(|(today, previous)| today.volume as f64 / previous.volume as f64),
+            volume: today.map(|bar| bar.volume as f64),
+        }
+    }
+
+    /// Ranking key, larger first. None sorts last.
+    fn rank(&self, sort: ScreenerSort) -> Option<f64> {
+        match sort {
+            ScreenerSort::Gainers => self.change_percent,
+            ScreenerSort::Losers => self.change_percent.map(|percent| -percent),
+            // Gaps either way count
+            ScreenerSort::Gap => self.gap_percent.map(f64::abs),
+            ScreenerSort::RelativeVolume => self.relative_volume,
+        }
+    }
+}
+
+/// Screener tab state
+pub struct Screener {
+    pub universe: Universe,
+    pub sort: ScreenerSort,
+    pub snapshots: Vec<SymbolSnapshot>,
+    pub loading: bool,
+    pub error: Option<String>,
+    pub updated_at: Option<DateTime<Local>>,
+    // Symbols the loaded snapshots were requested for
+    pub symbols: Vec<String>,
+    // Applied filter, if any
+    pub filter: Option<ScreenFilter>,
+    pub form: FilterForm,
+    // 20-day SMAs from daily bars, kept for the day they were computed on
+    pub sma_cache: HashMap<String, (NaiveDate, f64)>,
+    pub sma_loading: bool,
+    // Background scan for saved screens with alerts on, independent of the tab
+    pub scan_snapshots: Vec<SymbolSnapshot>,
+    pub scanning: bool,
+    pub scanned_at: Option<DateTime<Local>>,
+    // Symbols each scanning screen matched on its last scan
+    scan_matches: HashMap<String, HashSet<String>>,
+}
+
+impl Screener {
+    pub fn new(universe: Universe) -> Self {
+        Self {
+            universe,
+            sort: ScreenerSort::Gainers,
+            snapshots: Vec::new(),
+            loading: false,
+            error: None,
+            updated_at: None,
+            symbols: Vec::new(),
+            filter: None,
+            form: FilterForm::new(),
+            sma_cache: HashMap::new(),
+            sma_loading: false,
+            scan_snapshots: Vec::new(),
+            scanning: false,
+            scanned_at: None,
+            scan_matches: HashMap::new(),
+        }
+    }
+
+    /// Today's cached SMA for a symbol
+    fn sma(&self, symbol: &str) -> Option<f64> {
+        let today = Local::now().date_naive();
+        self.sma_cache
+            .get(symbol)
+            .filter(|(date, _)| *date == today)
+            .map(|(_, sma)| *sma)
+    }
+
+    /// Symbols that pass a filter's other bounds but have no SMA for today yet
+    fn missing_smas_in(&self, filter: &ScreenFilter, snapshots: &[SymbolSnapshot]) -> Vec<String> {
+        if filter.sma == SmaFilter::Any {
+            return Vec::new();
+        }
+        snapshots
+            .iter()
+            .map(ScreenerRow::from_snapshot)
+            .filter(|row| filter.matches_snapshot(row) && self.sma(&row.symbol).is_none())
+            .map(|row| row.symbol)
+            .collect()
+    }
+
+    /// SMAs still needed by the applied filter and by the scanning screens in `scans`
+    pub fn missing_smas(&self, scans: &[ScreenFilter]) -> Vec<String> {
+        let mut missing: Vec<String> = self
+            .filter
+            .iter()
+            .flat_map(|filter| self.missing_smas_in(filter, &self.snapshots))
+            .chain(
+                scans
+                    .iter()
+                    .filter(|screen| screen.scan)
+                    .flat_map(|screen| self.missing_smas_in(screen, &self.scan_snapshots)),
+            )
+            .collect();
+        missing.sort();
+        missing.dedup();
+        missing
+    }
+
+    /// Whether the background scan is due
+    pub fn needs_scan(&self, refresh_secs: u64) -> bool {
+        self.scanned_at
+            .is_none_or(|at| (Local::now() - at).num_seconds() >= refresh_secs as i64)
+    }
+
+    /// Symbols in `universe` that now pass a scanning screen but didn't on its last scan.
+    /// None while the screen's SMAs are loading, and on its first scan, which only records
+    /// what already matches.
+    pub fn new_matches(
+        &mut self,
+        screen: &ScreenFilter,
+        universe: &[String],
+    ) -> Option<Vec<String>> {
+        let snapshots: Vec<SymbolSnapshot> = self
+            .scan_snapshots
+            .iter()
+            .filter(|snapshot| universe.contains(&snapshot.symbol))
+            .cloned()
+            .collect();
+        if !self.missing_smas_in(screen, &snapshots).is_empty() {
+            return None;
+        }
+        let matches: HashSet<String> = snapshots
+            .iter()
+            .map(ScreenerRow::from_snapshot)
+            .filter(|row| {
+                screen.matches_snapshot(row) && screen.matches_sma(row, self.sma(&row.symbol))
+            })
+            .map(|row| row.symbol)
+            .collect();
+        let previous = self
+            .scan_matches
+            .insert(screen.name.clone(), matches.clone())?;
+        let mut added: Vec<String> = matches.difference(&previous).cloned().collect();
+        added.sort();
+        Some(added)
+    }
+
+    /// Forget a screen's last matches, so its next scan starts a fresh baseline
+    pub fn reset_scan(&mut self, name: &str) {
+        self.scan_matches.remove(name);
+    }
+
+    /// Whether the snapshots are older than the refresh interval
+    pub fn needs_refresh(&self, refresh_secs: u64) -> bool {
+        self.updated_at
+            .is_none_or(|at| (Local::now() - at).num_seconds() >= refresh_secs as i64)
+    }
+
+    /// The top `limit` symbols for the selected ranking
+    pub fn rows(&self, limit: usize) -> Vec<ScreenerRow> {
+        let mut rows: Vec<ScreenerRow> = self
+            .snapshots
+            .iter()
+            .map(ScreenerRow::from_snapshot)
+            .filter(|row| row.rank(self.sort).is_some())
+            .filter(|row| {
+                self.filter.as_ref().is_none_or(|filter| {
+                    filter.matches_snapshot(row) && filter.matches_sma(row, self.sma(&row.symbol))
+                })
+            })
+            .collect();
+        rows.sort_by(|a, b| {
+            let (a, b) = (a.rank(self.sort), b.rank(self.sort));
+            b.unwrap_or(f64::MIN).total_cmp(&a.unwrap_or(f64::MIN))
+        });
+        rows.truncate(limit);
+        rows
+    }
+}
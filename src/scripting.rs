@@ -0,0 +1,374 @@
+// Scripting module: user strategy scripts in Rhai, run against the chart's bars with read
+// access to positions and an order API. Scripts can't touch files or the network, are
+// stopped after a configured number of steps, and their orders are handed back to the
+// terminal (which sends them on paper accounts only unless the config allows live).
+
+use crate::error::TerminalError;
+use crate::hotkeys::HotkeySide;
+use crate::indicators;
+use alpaca_markets::Bar;
+use chrono::{DateTime, Local};
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, Scope};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::rc::Rc;
+use tracing::{info, warn};
+
+/// Output lines kept in the panel
+const MAX_OUTPUT: usize = 200;
+
+/// Written to an empty scripts folder so the API is discoverable
+const EXAMPLE_SCRIPT: &str = r#"// Example strategy: buy 1 share when the close crosses above its 20-bar average
+// and sell it again when the close falls back below.
+//
+// Available to scripts:
+//   symbol                      the charted symbol
+//   bars                        array of #{time, open, high, low, close, volume}, oldest first
+//   equity                      account equity (() until loaded)
+//   sma(n), ema(n), rsi(n)      latest indicator value on the closes (() without enough bars)
+//   position(sym)               shares held (negative when short, 0.0 when flat)
+//   buy(sym, qty), sell(sym, qty)
+//   buy_limit(sym, qty, price), sell_limit(sym, qty, price)
+//   print(text)                 write to the Scripts panel
+
+let average = sma(20);
+if average == () {
+    print("Not enough bars yet");
+    return;
+}
+
+let close = bars[bars.len() - 1].close;
+let held = position(symbol);
+print(`${symbol} close ${close}, SMA(20) ${average}, holding ${held}`);
+
+if close > average && held == 0.0 {
+    buy(symbol, 1);
+} else if close < average && held > 0.0 {
+    sell(symbol, held);
+}
+"#;
+
+/// Order a script asked for
+pub struct ScriptOrder {
+    pub symbol: String,
+    pub side: HotkeySide,
+    pub qty: f64,
+    pub limit_price: Option<f64>,
+}
+
+impl ScriptOrder {
+    pub fn describe(&self) -> String {
+        match self.limit_price {
+            Some(limit) => format!(
+                "{} {} {} limit ${:.2}",
+                self.side.label(),
+                self.qty,
+                self.symbol,
+                limit
+            ),
+            None => format!("{} {} {} market", self.side.label(), self.qty, self.symbol),
+        }
+    }
+
+    /// What's wrong with the numbers a script passed, checked before any order guard since
+    /// a NaN or negative value would slip through the notional checks
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.qty.is_finite() || self.qty <= 0.0 {
+            return Err(format!(
+                "quantity must be a positive number, got {}",
+                self.qty
+            ));
+        }
+        match self.limit_price {
+            Some(limit) if !limit.is_finite() || limit <= 0.0 => Err(format!(
+                "limit price must be a positive number, got {}",
+                limit
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Copy of a chart bar handed to the script thread
+pub struct ScriptBar {
+    time: String,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64,
+}
+
+impl From<&Bar> for ScriptBar {
+    fn from(bar: &Bar) -> Self {
+        Self {
+            time: bar.timestamp.to_rfc3339(),
+            open: bar.open,
+            high: bar.high,
+            low: bar.low,
+            close: bar.close,
+            volume: bar.volume as f64,
+        }
+    }
+}
+
+/// What a script sees
+pub struct ScriptInput {
+    pub symbol: String,
+    pub bars: Vec<ScriptBar>,
+    // Signed shares by symbol
+    pub positions: HashMap<String, f64>,
+    pub equity: Option<f64>,
+    pub max_operations: u64,
+}
+
+/// Result of one run
+pub struct ScriptRun {
+    pub logs: Vec<String>,
+    pub orders: Vec<ScriptOrder>,
+    pub error: Option<String>,
+}
+
+/// Number argument that may be written as an integer or a float
+fn number(value: &Dynamic, name: &str) -> Result<f64, Box<EvalAltResult>> {
+    value
+        .as_float()
+        .or_else(|_| value.as_int().map(|int| int as f64))
+        .map_err(|_| format!("{} must be a number", name).into())
+}
+
+fn bar_map(bar: &ScriptBar) -> Dynamic {
+    let mut map = Map::new();
+    map.insert("time".into(), bar.time.clone().into());
+    map.insert("open".into(), bar.open.into());
+    map.insert("high".into(), bar.high.into());
+    map.insert("low".into(), bar.low.into());
+    map.insert("close".into(), bar.close.into());
+    map.insert("volume".into(), bar.volume.into());
+    map.into()
+}
+
+/// Run a script to completion (or its operation limit)
+pub fn run(source: &str, input: ScriptInput) -> ScriptRun {
+    let logs = Rc::new(RefCell::new(Vec::new()));
+    let orders = Rc::new(RefCell::new(Vec::new()));
+    let closes: Rc<Vec<f64>> = Rc::new(input.bars.iter().map(|bar| bar.close).collect());
+
+    let mut engine = Engine::new();
+    engine.set_max_operations(input.max_operations);
+    engine.set_max_call_levels(32);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(10_000);
+    engine.set_max_array_size(100_000);
+    engine.set_max_map_size(1_000);
+    engine.disable_symbol("eval");
+
+    let print_logs = logs.clone();
+    engine.on_print(move |text| print_logs.borrow_mut().push(text.to_string()));
+    let debug_logs = logs.clone();
+    engine.on_debug(move |text, _, _| debug_logs.borrow_mut().push(text.to_string()));
+
+    for (name, indicator) in [
+        (
+            "sma",
+            indicators::sma as fn(&[f64], usize) -> Vec<Option<f64>>,
+        ),
+        ("ema", indicators::ema),
+        ("rsi", indicators::rsi),
+    ] {
+        let closes = closes.clone();
+        engine.register_fn(name, move |period: i64| -> Dynamic {
+            usize::try_from(period)
+                .ok()
+                .and_then(|period| indicators::last(&indicator(&closes, period)))
+                .map(Dynamic::from_float)
+                .unwrap_or(Dynamic::UNIT)
+        });
+    }
+
+    let positions = input.positions;
+    engine.register_fn("position", move |symbol: &str| -> f64 {
+        positions
+            .get(&symbol.to_uppercase())
+            .copied()
+            .unwrap_or(0.0)
+    });
+
+    for (name, side) in [("buy", HotkeySide::Buy), ("sell", HotkeySide::Sell)] {
+        let market_orders = orders.clone();
+        engine.register_fn(
+            name,
+            move |symbol: &str, qty: Dynamic| -> Result<(), Box<EvalAltResult>> {
+                market_orders.borrow_mut().push(ScriptOrder {
+                    symbol: symbol.to_uppercase(),
+                    side,
+                    qty: number(&qty, "qty")?,
+                    limit_price: None,
+                });
+                Ok(())
+            },
+        );
+        let limit_orders = orders.clone();
+        engine.register_fn(
+            format!("{}_limit", name),
+            move |symbol: &str, qty: Dynamic, price: Dynamic| -> Result<(), Box<EvalAltResult>> {
+                limit_orders.borrow_mut().push(ScriptOrder {
+                    symbol: symbol.to_uppercase(),
+                    side,
+                    qty: number(&qty, "qty")?,
+                    limit_price: Some(number(&price, "price")?),
+                });
+                Ok(())
+            },
+        );
+    }
+
+    let mut scope = Scope::new();
+    scope.push_constant("symbol", input.symbol);
+    scope.push_constant("bars", input.bars.iter().map(bar_map).collect::<Array>());
+    scope.push_constant(
+        "equity",
+        input
+            .equity
+            .map(Dynamic::from_float)
+            .unwrap_or(Dynamic::UNIT),
+    );
+
+    let error = engine
+        .run_with_scope(&mut scope, source)
+        .err()
+        .map(|error| error.to_string());
+
+    ScriptRun {
+        logs: logs.take(),
+        orders: orders.take(),
+        error,
+    }
+}
+
+/// `<config dir>/alpaca_terminal/scripts`
+pub fn scripts_dir() -> Option<PathBuf> {
+    crate::settings::config_dir().map(|dir| dir.join("scripts"))
+}
+
+/// Names (without `.rhai`) of the scripts in the scripts folder, creating the folder with
+/// an example script the first time
+pub fn list_scripts() -> Result<Vec<String>, TerminalError> {
+    let dir = scripts_dir()
+        .ok_or_else(|| TerminalError::Config("No config directory available".to_string()))?;
+    if !dir.exists() {
+        std::fs::create_dir_all(&dir)
+            .and_then(|_| std::fs::write(dir.join("example.rhai"), EXAMPLE_SCRIPT))
+            .map_err(|e| {
+                TerminalError::Config(format!("Failed to create {}: {}", dir.display(), e))
+            })?;
+        info!("✅ Created scripts folder {}", dir.display());
+    }
+
+    let entries = std::fs::read_dir(&dir)
+        .map_err(|e| TerminalError::Config(format!("Failed to read {}: {}", dir.display(), e)))?;
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rhai"))
+        .filter_map(|path| Some(path.file_stem()?.to_string_lossy().to_string()))
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+pub fn read_script(name: &str) -> Result<String, TerminalError> {
+    let path = scripts_dir()
+        .ok_or_else(|| TerminalError::Config("No config directory available".to_string()))?
+        .join(format!("{}.rhai", name));
+    std::fs::read_to_string(&path)
+        .map_err(|e| TerminalError::Config(format!("Failed to read {}: {}", path.display(), e)))
+}
+
+/// Line in the Scripts panel output
+pub struct OutputLine {
+    pub at: DateTime<Local>,
+    pub script: String,
+    pub text: String,
+    pub is_error: bool,
+}
+
+/// Scripts panel state
+pub struct Scripts {
+    pub open: bool,
+    pub names: Vec<String>,
+    // Scripts run on every streamed bar of the charted symbol
+    pub auto: HashSet<String>,
+    // Scripts running in the background
+    pub running: HashSet<String>,
+    // Newest last
+    pub output: Vec<OutputLine>,
+}
+
+impl Scripts {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            names: Vec::new(),
+            auto: HashSet::new(),
+            running: HashSet::new(),
+            output: Vec::new(),
+        }
+    }
+
+    pub fn push_output(&mut self, script: &str, text: String, is_error: bool) {
+        if is_error {
+            warn!("📜 {}: {}", script, text);
+        } else {
+            info!("📜 {}: {}", script, text);
+        }
+        self.output.push(OutputLine {
+            at: Local::now(),
+            script: script.to_string(),
+            text,
+            is_error,
+        });
+        if self.output.len() > MAX_OUTPUT {
+            self.output.remove(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(qty: f64, limit_price: Option<f64>) -> ScriptOrder {
+        ScriptOrder {
+            symbol: "AAPL".to_string(),
+            side: HotkeySide::Buy,
+            qty,
+            limit_price,
+        }
+    }
+
+    #[test]
+    fn validate_accepts_positive_numbers() {
+        assert!(order(1.0, None).validate().is_ok());
+        assert!(order(0.5, Some(187.25)).validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_bad_quantities() {
+        for qty in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(order(qty, None).validate().is_err(), "qty {}", qty);
+        }
+    }
+
+    #[test]
+    fn validate_rejects_bad_limit_prices() {
+        for limit in [0.0, -10.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(
+                order(1.0, Some(limit)).validate().is_err(),
+                "limit {}",
+                limit
+            );
+        }
+    }
+}
@@ -0,0 +1,539 @@
+// Service module: background worker that runs REST calls for the UI. The terminal sends
+// typed requests over a channel and handles the typed responses as they come back.
+
+use crate::account::{
+    self, AccountSnapshot, ActivityHistory, FooterTab, Order, OrdersPage, Position,
+};
+use crate::account_config::{self, AccountConfig};
+use crate::asset::{self, AssetInfo};
+use crate::auto_exit::ExitOrder;
+use crate::chart::ChartId;
+use crate::clock::{self, CalendarEvent, MarketClock};
+use crate::config::{DataFeed, RetryConfig};
+use crate::corporate_actions::{self, CorporateAction};
+use crate::error::TerminalError;
+use crate::hotkeys::HotkeySide;
+use crate::news::{self, NewsArticle};
+use crate::options::{self, OptionLeg};
+use crate::rate_limit;
+use crate::snapshot::{self, SymbolSnapshot};
+use alpaca_markets::Bar;
+use alpaca_markets::models::{OrderSide, OrderTimeInForce, OrderType};
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+use tracing::{debug, error, info, info_span, warn};
+
+/// Where an order was entered, so the response can update the right place
+#[derive(Clone)]
+pub enum OrderOrigin {
+    // Sidebar (or popped-out) order form
+    Form,
+    // Hotkeys, chart window tickets and the scalp pad (always market orders)
+    Market { side: HotkeySide },
+    // Staged order submitted at its scheduled time
+    Scheduled { side: HotkeySide },
+    // A recurring buy plan's run, so its history records the result
+    Recurring { plan: u64 },
+    // An automation rule that fired, so the execution log records the result
+    Automation { rule: u64 },
+    // A strategy script, so its output shows the result
+    Script { name: String },
+}
+
+impl OrderOrigin {
+    pub fn label(&self) -> String {
+        match self {
+            OrderOrigin::Form => "Form".to_string(),
+            OrderOrigin::Market { .. } => "Hotkey".to_string(),
+            OrderOrigin::Scheduled { .. } => "Scheduled".to_string(),
+            OrderOrigin::Recurring { plan } => format!("Recurring #{}", plan),
+            OrderOrigin::Automation { rule } => format!("Rule #{}", rule),
+            OrderOrigin::Script { name } => format!("Script {}", name),
+        }
+    }
+}
+
+pub struct OrderTicket {
+    pub symbol: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub qty: f64,
+    // Dollar amount to buy instead of `qty` (fractional, market day orders only)
+    pub notional: Option<f64>,
+    pub limit_price: Option<f64>,
+    pub time_in_force: OrderTimeInForce,
+}
+
+impl OrderTicket {
+    /// Send the order (the API enums aren't Clone, so they're copied for each attempt)
+    fn submit(&self) -> Result<String, TerminalError> {
+        let side = match self.side {
+            OrderSide::Buy => OrderSide::Buy,
+            OrderSide::Sell => OrderSide::Sell,
+        };
+        let order_type = match self.order_type {
+            OrderType::Limit => OrderType::Limit,
+            _ => OrderType::Market,
+        };
+        let time_in_force = match self.time_in_force {
+            OrderTimeInForce::Gtc => OrderTimeInForce::Gtc,
+            OrderTimeInForce::Ioc => OrderTimeInForce::Ioc,
+            _ => OrderTimeInForce::Day,
+        };
+        account::submit_order_sync(
+            self.symbol.clone(),
+            side,
+            order_type,
+            self.qty,
+            self.notional,
+            self.limit_price,
+            time_in_force,
+        )
+    }
+}
+
+pub enum ServiceRequest {
+    Bars {
+        chart: ChartId,
+        // Echoed back so results of superseded fetches can be dropped
+        generation: u64,
+        symbol: String,
+        timeframe: String,
+        limit: u32,
+        feed: DataFeed,
+    },
+    // Bars from `since` to now, to fill the gap left by a stream reconnect
+    Backfill {
+        chart: ChartId,
+        generation: u64,
+        symbol: String,
+        timeframe: String,
+        since: DateTime<Utc>,
+        feed: DataFeed,
+    },
+    Account,
+    Positions,
+    // A page of open orders; `until` set when loading older pages
+    Orders {
+        until: Option<DateTime<Utc>>,
+    },
+    OrderHistory {
+        since: Option<DateTime<Utc>>,
+    },
+    Activities,
+    SubmitOrder {
+        origin: OrderOrigin,
+        ticket: OrderTicket,
+    },
+    // Take-profit/stop-loss OCO for a filled manual entry
+    SubmitExit(ExitOrder),
+    CancelOrder(String),
+    ClosePosition(String),
+    // Multi-leg options order
+    SubmitStrategy {
+        legs: Vec<OptionLeg>,
+        quantity: u32,
+        net_price: f64,
+    },
+    // Symbol details for the chart header
+    CorporateActions(String),
+    Snapshot {
+        symbol: String,
+        feed: DataFeed,
+    },
+    Asset(String),
+    Clock,
+    Calendar,
+    AccountConfig,
+    UpdateAccountConfig {
+        config: AccountConfig,
+        // Echoed back so a failed save can restore what was shown before
+        previous: Option<AccountConfig>,
+    },
+    // Articles for the news panel's symbols
+    News(Vec<String>),
+}
+
+/// Where a fetch's loading indicator is shown
+#[derive(Clone, PartialEq)]
+pub enum RetryTarget {
+    Chart { chart: ChartId, generation: u64 },
+    Tab(FooterTab),
+}
+
+/// Result of a request, carrying whatever the UI needs to apply it
+pub enum ServiceResponse {
+    // A fetch failed transiently and is about to be tried again
+    Retrying {
+        target: RetryTarget,
+        attempt: u32,
+        attempts: u32,
+    },
+    Bars {
+        chart: ChartId,
+        generation: u64,
+        result: Result<Vec<Bar>, TerminalError>,
+    },
+    Backfill {
+        chart: ChartId,
+        generation: u64,
+        result: Result<Vec<Bar>, TerminalError>,
+    },
+    Account(Result<AccountSnapshot, TerminalError>),
+    Positions(Result<Vec<Position>, TerminalError>),
+    Orders {
+        more: bool,
+        result: Result<OrdersPage, TerminalError>,
+    },
+    OrderHistory(Result<Vec<Order>, TerminalError>),
+    Activities(Result<ActivityHistory, TerminalError>),
+    OrderSubmitted {
+        origin: OrderOrigin,
+        symbol: String,
+        qty: f64,
+        result: Result<String, TerminalError>,
+    },
+    ExitSubmitted {
+        exit: ExitOrder,
+        result: Result<String, TerminalError>,
+    },
+    OrderCanceled {
+        order_id: String,
+        result: Result<(), TerminalError>,
+    },
+    PositionClosed {
+        symbol: String,
+        result: Result<(), TerminalError>,
+    },
+    StrategySubmitted(Result<String, TerminalError>),
+    CorporateActions {
+        symbol: String,
+        result: Result<Vec<CorporateAction>, TerminalError>,
+    },
+    Snapshot(Result<SymbolSnapshot, TerminalError>),
+    Asset(Result<AssetInfo, TerminalError>),
+    Clock(Result<MarketClock, TerminalError>),
+    Calendar(Result<Vec<CalendarEvent>, TerminalError>),
+    AccountConfig(Result<AccountConfig, TerminalError>),
+    AccountConfigUpdated {
+        previous: Option<AccountConfig>,
+        result: Result<AccountConfig, TerminalError>,
+    },
+    News {
+        symbols: Vec<String>,
+        result: Result<Vec<NewsArticle>, TerminalError>,
+    },
+}
+
+impl ServiceResponse {
+    /// Footer tab whose fetch this response completes
+    pub fn footer_tab(&self) -> Option<FooterTab> {
+        match self {
+            ServiceResponse::Account(_) => Some(FooterTab::Account),
+            ServiceResponse::Positions(_) => Some(FooterTab::Positions),
+            ServiceResponse::Orders { .. } => Some(FooterTab::Orders),
+            ServiceResponse::OrderHistory(_) => Some(FooterTab::History),
+            ServiceResponse::Activities(_) => Some(FooterTab::Activities),
+            _ => None,
+        }
+    }
+}
+
+impl ServiceRequest {
+    fn name(&self) -> &'static str {
+        match self {
+            ServiceRequest::Bars { .. } => "bars",
+            ServiceRequest::Backfill { .. } => "backfill",
+            ServiceRequest::Account => "account",
+            ServiceRequest::Positions => "positions",
+            ServiceRequest::Orders { .. } => "orders",
+            ServiceRequest::OrderHistory { .. } => "order history",
+            ServiceRequest::Activities => "activities",
+            ServiceRequest::SubmitOrder { .. } => "submit order",
+            ServiceRequest::SubmitExit(_) => "submit exit",
+            ServiceRequest::CancelOrder(_) => "cancel order",
+            ServiceRequest::ClosePosition(_) => "close position",
+            ServiceRequest::SubmitStrategy { .. } => "submit strategy",
+            ServiceRequest::CorporateActions(_) => "corporate actions",
+            ServiceRequest::Snapshot { .. } => "snapshot",
+            ServiceRequest::Asset(_) => "asset",
+            ServiceRequest::Clock => "clock",
+            ServiceRequest::Calendar => "calendar",
+            ServiceRequest::AccountConfig => "account configuration",
+            ServiceRequest::UpdateAccountConfig { .. } => "update account configuration",
+            ServiceRequest::News(_) => "news",
+        }
+    }
+
+    /// Run the blocking REST call. Fetches are retried on transient failures; order
+    /// actions only after a 429, since a network error may have hit after the server
+    /// acted on them.
+    fn execute(self, retry: &Retry) -> ServiceResponse {
+        match self {
+            ServiceRequest::Bars {
+                chart,
+                generation,
+                symbol,
+                timeframe,
+                limit,
+                feed,
+            } => ServiceResponse::Bars {
+                chart,
+                generation,
+                result: retry.run(RetryTarget::Chart { chart, generation }, || {
+                    crate::fetch_bars_sync(&symbol, &timeframe, limit, feed)
+                }),
+            },
+            ServiceRequest::Backfill {
+                chart,
+                generation,
+                symbol,
+                timeframe,
+                since,
+                feed,
+            } => ServiceResponse::Backfill {
+                chart,
+                generation,
+                result: retry.run(RetryTarget::Chart { chart, generation }, || {
+                    crate::fetch_bars_since_sync(&symbol, &timeframe, since, feed)
+                }),
+            },
+            ServiceRequest::Account => ServiceResponse::Account(retry.run(
+                RetryTarget::Tab(FooterTab::Account),
+                account::fetch_account_sync,
+            )),
+            ServiceRequest::Positions => ServiceResponse::Positions(retry.run(
+                RetryTarget::Tab(FooterTab::Positions),
+                account::fetch_positions_sync,
+            )),
+            ServiceRequest::Orders { until } => ServiceResponse::Orders {
+                more: until.is_some(),
+                result: retry.run(RetryTarget::Tab(FooterTab::Orders), || {
+                    account::fetch_orders_sync(until)
+                }),
+            },
+            ServiceRequest::OrderHistory { since } => ServiceResponse::OrderHistory(
+                retry.run(RetryTarget::Tab(FooterTab::History), || {
+                    account::fetch_order_history_sync(since)
+                }),
+            ),
+            ServiceRequest::Activities => ServiceResponse::Activities(retry.run(
+                RetryTarget::Tab(FooterTab::Activities),
+                account::fetch_activities_sync,
+            )),
+            ServiceRequest::SubmitOrder { origin, ticket } => ServiceResponse::OrderSubmitted {
+                origin,
+                symbol: ticket.symbol.clone(),
+                qty: ticket.qty,
+                result: retry.resend(|| ticket.submit()),
+            },
+            ServiceRequest::SubmitExit(exit) => ServiceResponse::ExitSubmitted {
+                result: retry.resend(|| {
+                    account::submit_oco_exit_sync(
+                        exit.symbol.clone(),
+                        exit.side.order_side(),
+                        exit.qty,
+                        exit.take_profit,
+                        exit.stop_loss,
+                    )
+                }),
+                exit,
+            },
+            ServiceRequest::CancelOrder(order_id) => ServiceResponse::OrderCanceled {
+                result: retry.resend(|| account::cancel_order_sync(order_id.clone())),
+                order_id,
+            },
+            ServiceRequest::ClosePosition(symbol) => ServiceResponse::PositionClosed {
+                result: retry.resend(|| account::close_position_sync(symbol.clone())),
+                symbol,
+            },
+            ServiceRequest::SubmitStrategy {
+                legs,
+                quantity,
+                net_price,
+            } => ServiceResponse::StrategySubmitted(
+                retry.resend(|| options::submit_strategy_sync(legs.clone(), quantity, net_price)),
+            ),
+            ServiceRequest::CorporateActions(symbol) => ServiceResponse::CorporateActions {
+                result: retry.run_quietly(|| {
+                    corporate_actions::fetch_corporate_actions_sync(symbol.clone())
+                }),
+                symbol,
+            },
+            ServiceRequest::Snapshot { symbol, feed } => ServiceResponse::Snapshot(
+                retry.run_quietly(|| snapshot::fetch_snapshot_sync(symbol.clone(), feed)),
+            ),
+            ServiceRequest::Asset(symbol) => ServiceResponse::Asset(
+                retry.run_quietly(|| asset::fetch_asset_sync(symbol.clone())),
+            ),
+            ServiceRequest::Clock => {
+                ServiceResponse::Clock(retry.run_quietly(clock::fetch_clock_sync))
+            }
+            ServiceRequest::Calendar => {
+                ServiceResponse::Calendar(retry.run_quietly(clock::fetch_calendar_events_sync))
+            }
+            ServiceRequest::AccountConfig => ServiceResponse::AccountConfig(
+                retry.run_quietly(account_config::fetch_account_config_sync),
+            ),
+            ServiceRequest::UpdateAccountConfig { config, previous } => {
+                ServiceResponse::AccountConfigUpdated {
+                    previous,
+                    result: retry
+                        .resend(|| account_config::update_account_config_sync(config.clone())),
+                }
+            }
+            ServiceRequest::News(symbols) => ServiceResponse::News {
+                result: retry.run_quietly(|| news::fetch_news_sync(symbols.clone())),
+                symbols,
+            },
+        }
+    }
+}
+
+/// Retry policy for one request, reporting each retry to the UI
+struct Retry {
+    name: &'static str,
+    config: RetryConfig,
+    responses: mpsc::UnboundedSender<ServiceResponse>,
+}
+
+impl Retry {
+    /// Retry a fetch after network errors and rate limits
+    fn run<T>(
+        &self,
+        target: RetryTarget,
+        fetch: impl FnMut() -> Result<T, TerminalError>,
+    ) -> Result<T, TerminalError> {
+        self.attempt(true, Some(target), fetch)
+    }
+
+    /// Retry a fetch that has no loading indicator to report to
+    fn run_quietly<T>(
+        &self,
+        fetch: impl FnMut() -> Result<T, TerminalError>,
+    ) -> Result<T, TerminalError> {
+        self.attempt(true, None, fetch)
+    }
+
+    /// Send a request again only if it was rate limited (the API refused it unprocessed)
+    fn resend<T>(
+        &self,
+        send: impl FnMut() -> Result<T, TerminalError>,
+    ) -> Result<T, TerminalError> {
+        self.attempt(false, None, send)
+    }
+
+    fn attempt<T>(
+        &self,
+        transient: bool,
+        target: Option<RetryTarget>,
+        mut fetch: impl FnMut() -> Result<T, TerminalError>,
+    ) -> Result<T, TerminalError> {
+        let mut attempt = 1;
+        let mut resends = 0;
+        loop {
+            match fetch() {
+                // The rate limiter holds the next attempt back until the pause is over
+                Err(TerminalError::RateLimited) if resends < rate_limit::MAX_RESENDS => {
+                    resends += 1;
+                    info!(
+                        "🚦 {} was rate limited, sending again ({}/{})",
+                        self.name,
+                        resends,
+                        rate_limit::MAX_RESENDS
+                    );
+                }
+                Err(error)
+                    if transient && error.is_transient() && attempt < self.config.attempts =>
+                {
+                    let delay = backoff_delay(&self.config, attempt);
+                    attempt += 1;
+                    warn!(
+                        "🔁 {} failed ({}), retrying in {}ms ({}/{})",
+                        self.name,
+                        error,
+                        delay.as_millis(),
+                        attempt,
+                        self.config.attempts
+                    );
+                    if let Some(target) = &target {
+                        let _ = self.responses.send(ServiceResponse::Retrying {
+                            target: target.clone(),
+                            attempt,
+                            attempts: self.config.attempts,
+                        });
+                    }
+                    thread::sleep(delay);
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Exponential backoff after the given (1-based) failed attempt, with jitter
+fn backoff_delay(config: &RetryConfig, attempt: u32) -> Duration {
+    let base = config
+        .backoff_ms
+        .saturating_mul(1 << (attempt - 1).min(16))
+        .min(config.max_backoff_ms);
+    // The clock's nanoseconds are random enough to keep retries from lining up
+    let noise = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos() as f64 / 1e9)
+        .unwrap_or(0.5);
+    let factor = 1.0 + config.jitter * (noise * 2.0 - 1.0);
+    Duration::from_millis((base as f64 * factor) as u64)
+}
+
+/// Handle for sending requests to the service thread
+pub struct DataService {
+    requests: mpsc::UnboundedSender<ServiceRequest>,
+    // Shared with the service thread so config reloads apply to the next request
+    retry: Arc<Mutex<RetryConfig>>,
+}
+
+impl DataService {
+    /// Start the service thread; responses are sent to `responses`
+    pub fn start(responses: mpsc::UnboundedSender<ServiceResponse>, retry: RetryConfig) -> Self {
+        let (requests, mut receiver) = mpsc::unbounded_channel::<ServiceRequest>();
+        let retry = Arc::new(Mutex::new(retry));
+        let retry_config = retry.clone();
+
+        thread::spawn(move || {
+            let _span = info_span!("data_service").entered();
+            info!("🚀 Data service started");
+            while let Some(request) = receiver.blocking_recv() {
+                debug!("📨 Service request: {}", request.name());
+                let retry = Retry {
+                    name: request.name(),
+                    config: retry_config.lock().map(|c| c.clone()).unwrap_or_default(),
+                    responses: responses.clone(),
+                };
+                // Each call gets its own thread so a slow endpoint doesn't hold up the rest
+                thread::spawn(move || {
+                    let _ = retry.responses.send(request.execute(&retry));
+                });
+            }
+            info!("🛑 Data service stopped");
+        });
+
+        Self { requests, retry }
+    }
+
+    pub fn set_retry_config(&self, config: RetryConfig) {
+        if let Ok(mut retry) = self.retry.lock() {
+            *retry = config;
+        }
+    }
+
+    pub fn send(&self, request: ServiceRequest) {
+        let name = request.name();
+        if self.requests.send(request).is_err() {
+            error!("✗ Data service is not running, dropped {} request", name);
+        }
+    }
+}
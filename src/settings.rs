@@ -0,0 +1,169 @@
+// Settings module for persisting UI preferences between sessions
+
+use crate::account::FooterTab;
+use crate::auto_exit::AutoExitSettings;
+use crate::columns::{OrderColumn, PositionColumn, TableColumns};
+use crate::overlays::ChartOverlays;
+use crate::scalp::ScalpSettings;
+use crate::screener::ScreenFilter;
+use crate::sound::SoundCue;
+use crate::workspace::Workspace;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing::{error, info};
+
+/// Number of recently viewed symbols remembered
+pub const MAX_RECENT_SYMBOLS: usize = 10;
+
+/// UI preferences saved to `<config dir>/alpaca_terminal/settings.json`
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub positions_columns: TableColumns<PositionColumn>,
+    pub orders_columns: TableColumns<OrderColumn>,
+    // Most recently charted symbols, newest first
+    pub recent_symbols: Vec<String>,
+    // Starred symbols shown as quick-switch chips above the chart
+    pub favorite_symbols: Vec<String>,
+    // Audio cues: global mute plus cues switched off individually
+    pub sound_muted: bool,
+    pub disabled_sound_cues: Vec<SoundCue>,
+    // One-key market orders: off by default as a safety measure
+    pub hotkeys_enabled: bool,
+    pub hotkey_quantity: String,
+    pub hotkey_skip_confirmation: bool,
+    // Bid/ask depth panel beside the main chart (streams its symbol's quotes while open)
+    pub depth_open: bool,
+    // Indicators drawn over the charts
+    pub overlays: ChartOverlays,
+    // Take-profit/stop-loss exits attached to manual entries once they fill
+    pub auto_exits: AutoExitSettings,
+    // Compact one-click pad shown in place of the order form
+    pub scalp: ScalpSettings,
+    // Where the terminal was left when it last closed
+    pub session: SessionState,
+    // Named layouts and the one last selected
+    pub workspaces: Vec<Workspace>,
+    pub active_workspace: Option<String>,
+    // Named screener filters (Movers tab)
+    pub saved_screens: Vec<ScreenFilter>,
+}
+
+/// UI state saved on exit and restored on the next start (None falls back to config.toml)
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    pub symbol: Option<String>,
+    pub timeframe: Option<String>,
+    pub bar_limit: Option<u32>,
+    pub footer_tab: Option<FooterTab>,
+    // Logical pixels
+    pub window_size: Option<(f32, f32)>,
+}
+
+/// Directory holding the terminal's settings and state files
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("alpaca_terminal"))
+}
+
+fn settings_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("settings.json"))
+}
+
+impl Settings {
+    /// Move a symbol to the front of the recent list
+    pub fn remember_symbol(&mut self, symbol: &str) {
+        self.recent_symbols.retain(|s| s != symbol);
+        self.recent_symbols.insert(0, symbol.to_string());
+        self.recent_symbols.truncate(MAX_RECENT_SYMBOLS);
+    }
+
+    /// Star or unstar a symbol
+    pub fn toggle_favorite(&mut self, symbol: &str) {
+        if let Some(index) = self.favorite_symbols.iter().position(|s| s == symbol) {
+            self.favorite_symbols.remove(index);
+        } else {
+            self.favorite_symbols.push(symbol.to_string());
+        }
+    }
+
+    pub fn is_favorite(&self, symbol: &str) -> bool {
+        self.favorite_symbols.iter().any(|s| s == symbol)
+    }
+
+    /// Enable or disable a single audio cue
+    pub fn toggle_sound_cue(&mut self, cue: SoundCue) {
+        if let Some(index) = self.disabled_sound_cues.iter().position(|c| *c == cue) {
+            self.disabled_sound_cues.remove(index);
+        } else {
+            self.disabled_sound_cues.push(cue);
+        }
+    }
+
+    pub fn sound_cue_enabled(&self, cue: SoundCue) -> bool {
+        !self.sound_muted && !self.disabled_sound_cues.contains(&cue)
+    }
+
+    /// Add a workspace or replace the one with the same name, and make it active
+    pub fn upsert_workspace(&mut self, workspace: Workspace) {
+        self.active_workspace = Some(workspace.name.clone());
+        match self
+            .workspaces
+            .iter_mut()
+            .find(|w| w.name == workspace.name)
+        {
+            Some(existing) => *existing = workspace,
+            None => self.workspaces.push(workspace),
+        }
+    }
+
+    pub fn remove_workspace(&mut self, name: &str) {
+        self.workspaces.retain(|w| w.name != name);
+        if self.active_workspace.as_deref() == Some(name) {
+            self.active_workspace = None;
+        }
+    }
+
+    pub fn upsert_screen(&mut self, screen: ScreenFilter) {
+        match self
+            .saved_screens
+            .iter_mut()
+            .find(|s| s.name == screen.name)
+        {
+            Some(existing) => *existing = screen,
+            None => self.saved_screens.push(screen),
+        }
+    }
+
+    pub fn remove_screen(&mut self, name: &str) {
+        self.saved_screens.retain(|s| s.name != name);
+    }
+
+    /// Load settings from disk, falling back to defaults if missing or invalid
+    pub fn load() -> Self {
+        let Some(path) = settings_path() else {
+            return Self::default();
+        };
+
+        match crate::persist::load::<Settings>(&path, "settings") {
+            Some(settings) => {
+                info!("✅ Settings loaded from {}", path.display());
+                settings
+            }
+            None => Self::default(),
+        }
+    }
+
+    /// Write settings to disk
+    pub fn save(&self) {
+        let Some(path) = settings_path() else {
+            return;
+        };
+
+        let result = crate::persist::save(&path, self);
+
+        if let Err(e) = result {
+            error!("✗ Error saving settings to {}: {}", path.display(), e);
+        }
+    }
+}
@@ -0,0 +1,330 @@
+// Simulator module: forward-test broker. While enabled, orders from the form, hotkeys,
+// schedules, recurring plans, automation rules and scripts are kept here instead of being
+// sent to Alpaca, and fill against the live trade stream into simulated cash and positions.
+
+use crate::hotkeys::HotkeySide;
+use crate::service::OrderTicket;
+use alpaca_markets::models::OrderSide;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing::{error, info, warn};
+
+/// Fills kept in the history
+const MAX_FILLS: usize = 500;
+
+/// Quantities smaller than this are treated as flat
+const EPSILON: f64 = 1e-9;
+
+/// Order waiting for a trade to fill against. Market orders take the next trade; limit
+/// orders the first trade at or through the limit. Orders stay open until they fill or
+/// are canceled.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SimOrder {
+    pub id: u64,
+    pub symbol: String,
+    pub side: HotkeySide,
+    pub qty: f64,
+    // Dollar amount instead of `qty`, converted to shares at the fill price
+    pub notional: Option<f64>,
+    pub limit_price: Option<f64>,
+    pub placed_at: DateTime<Utc>,
+    // Where the order was entered ("Form", "Rule #3", ...)
+    pub source: String,
+}
+
+impl SimOrder {
+    pub fn describe(&self) -> String {
+        let size = match self.notional {
+            Some(notional) => format!("${:.2} of", notional),
+            None => self.qty.to_string(),
+        };
+        match self.limit_price {
+            Some(limit) => format!(
+                "{} {} {} limit ${:.2}",
+                self.side.label(),
+                size,
+                self.symbol,
+                limit
+            ),
+            None => format!("{} {} {} market", self.side.label(), size, self.symbol),
+        }
+    }
+
+    fn fills_at(&self, price: f64) -> bool {
+        match (self.limit_price, self.side) {
+            (None, _) => true,
+            (Some(limit), HotkeySide::Buy) => price <= limit,
+            (Some(limit), HotkeySide::Sell) => price >= limit,
+        }
+    }
+}
+
+/// Simulated holding; `qty` is negative when short
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SimPosition {
+    pub symbol: String,
+    pub qty: f64,
+    pub avg_price: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SimFill {
+    pub at: DateTime<Utc>,
+    pub order_id: u64,
+    pub symbol: String,
+    pub side: HotkeySide,
+    pub qty: f64,
+    pub price: f64,
+    // P&L of the part of the fill that reduced a position
+    pub realized: f64,
+    pub source: String,
+}
+
+/// What happened to an open order on a trade
+pub enum SimEvent {
+    Filled(SimFill),
+    // Not enough simulated cash left when the order came to fill
+    Rejected { order: SimOrder, reason: String },
+}
+
+/// Simulated account, saved to `<config dir>/alpaca_terminal/simulator.json` so a forward
+/// test carries on across sessions
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimBroker {
+    // Forward-test mode: new orders come here instead of going to Alpaca
+    pub enabled: bool,
+    pub starting_cash: f64,
+    pub cash: f64,
+    pub positions: Vec<SimPosition>,
+    pub orders: Vec<SimOrder>,
+    // Newest last
+    pub fills: Vec<SimFill>,
+    pub realized: f64,
+    next_id: u64,
+}
+
+fn simulator_path() -> Option<PathBuf> {
+    crate::settings::config_dir().map(|dir| dir.join("simulator.json"))
+}
+
+impl SimBroker {
+    fn new(starting_cash: f64) -> Self {
+        Self {
+            starting_cash,
+            cash: starting_cash,
+            ..Self::default()
+        }
+    }
+
+    /// Take an order, or say why it can't be placed. `price` is the last known trade, used
+    /// to check buying power for market buys.
+    pub fn place(
+        &mut self,
+        ticket: &OrderTicket,
+        source: String,
+        price: Option<f64>,
+    ) -> Result<u64, String> {
+        let side = match ticket.side {
+            OrderSide::Buy => HotkeySide::Buy,
+            OrderSide::Sell => HotkeySide::Sell,
+        };
+        if ticket.notional.is_none() && ticket.qty <= 0.0 {
+            return Err("Quantity must be positive".to_string());
+        }
+        if side == HotkeySide::Buy {
+            let cost = match ticket.notional {
+                Some(notional) => Some(notional),
+                None => ticket.limit_price.or(price).map(|price| price * ticket.qty),
+            };
+            if cost.is_some_and(|cost| cost > self.cash) {
+                return Err(format!(
+                    "Insufficient simulated cash (${:.2} available)",
+                    self.cash
+                ));
+            }
+        }
+
+        self.next_id += 1;
+        let order = SimOrder {
+            id: self.next_id,
+            symbol: ticket.symbol.clone(),
+            side,
+            qty: ticket.qty,
+            notional: ticket.notional,
+            limit_price: ticket.limit_price,
+            placed_at: Utc::now(),
+            source,
+        };
+        info!("🧪 Simulated order #{}: {}", order.id, order.describe());
+        self.orders.push(order);
+        Ok(self.next_id)
+    }
+
+    pub fn cancel(&mut self, id: u64) -> bool {
+        let before = self.orders.len();
+        self.orders.retain(|order| order.id != id);
+        self.orders.len() != before
+    }
+
+    /// Fill the symbol's open orders that a trade at `price` reaches
+    pub fn on_trade(&mut self, symbol: &str, price: f64) -> Vec<SimEvent> {
+        if !self.orders.iter().any(|order| order.symbol == symbol) {
+            return Vec::new();
+        }
+
+        let (due, open): (Vec<SimOrder>, Vec<SimOrder>) = std::mem::take(&mut self.orders)
+            .into_iter()
+            .partition(|order| order.symbol == symbol && order.fills_at(price));
+        self.orders = open;
+
+        let mut events = Vec::new();
+        for order in due {
+            // Notional orders buy fractional shares, rounded down to a millionth
+            let qty = order
+                .notional
+                .map(|notional| (notional / price * 1e6).floor() / 1e6)
+                .unwrap_or(order.qty);
+            if order.side == HotkeySide::Buy && qty * price > self.cash + EPSILON {
+                warn!("🧪 Simulated order #{} rejected: not enough cash", order.id);
+                events.push(SimEvent::Rejected {
+                    reason: format!("needs ${:.2}, ${:.2} available", qty * price, self.cash),
+                    order,
+                });
+                continue;
+            }
+
+            let realized = self.apply_fill(symbol, order.side, qty, price);
+            let fill = SimFill {
+                at: Utc::now(),
+                order_id: order.id,
+                symbol: symbol.to_string(),
+                side: order.side,
+                qty,
+                price,
+                realized,
+                source: order.source,
+            };
+            info!(
+                "🧪 Simulated fill #{}: {} {} {} @ ${:.2}",
+                fill.order_id,
+                fill.side.label(),
+                fill.qty,
+                fill.symbol,
+                fill.price
+            );
+            self.fills.push(fill.clone());
+            events.push(SimEvent::Filled(fill));
+        }
+        if self.fills.len() > MAX_FILLS {
+            self.fills.drain(..self.fills.len() - MAX_FILLS);
+        }
+        events
+    }
+
+    /// Move cash and the position for a fill, returning the realized P&L
+    fn apply_fill(&mut self, symbol: &str, side: HotkeySide, qty: f64, price: f64) -> f64 {
+        let signed = match side {
+            HotkeySide::Buy => qty,
+            HotkeySide::Sell => -qty,
+        };
+        self.cash -= signed * price;
+
+        let Some(index) = self.positions.iter().position(|p| p.symbol == symbol) else {
+            self.positions.push(SimPosition {
+                symbol: symbol.to_string(),
+                qty: signed,
+                avg_price: price,
+            });
+            return 0.0;
+        };
+
+        let position = &mut self.positions[index];
+        let mut realized = 0.0;
+        if position.qty * signed > 0.0 {
+            position.avg_price = (position.avg_price * position.qty.abs() + price * qty)
+                / (position.qty.abs() + qty);
+        } else {
+            let closing = position.qty.abs().min(qty);
+            realized = closing * (price - position.avg_price) * position.qty.signum();
+            // Selling more than held (or covering more than short) flips the position
+            if qty > position.qty.abs() {
+                position.avg_price = price;
+            }
+        }
+        position.qty += signed;
+        if position.qty.abs() < EPSILON {
+            self.positions.remove(index);
+        }
+        self.realized += realized;
+        realized
+    }
+
+    /// Cash plus positions marked at `mark` (falling back to their average price)
+    pub fn equity(&self, mark: impl Fn(&str) -> Option<f64>) -> f64 {
+        self.cash
+            + self
+                .positions
+                .iter()
+                .map(|position| position.qty * mark(&position.symbol).unwrap_or(position.avg_price))
+                .sum::<f64>()
+    }
+
+    /// Symbols with open orders or positions, which need trades streamed
+    pub fn symbols(&self) -> Vec<String> {
+        let mut symbols: Vec<String> = Vec::new();
+        let held = self.positions.iter().map(|position| &position.symbol);
+        for symbol in self.orders.iter().map(|order| &order.symbol).chain(held) {
+            if !symbols.contains(symbol) {
+                symbols.push(symbol.clone());
+            }
+        }
+        symbols
+    }
+
+    /// Start over with `starting_cash`, keeping the mode
+    pub fn reset(&mut self, starting_cash: f64) {
+        let enabled = self.enabled;
+        *self = Self::new(starting_cash);
+        self.enabled = enabled;
+        info!("🧪 Simulated account reset to ${:.2}", starting_cash);
+    }
+
+    /// Load the simulated account from disk, starting a new one with `starting_cash` if
+    /// missing or invalid
+    pub fn load(starting_cash: f64) -> Self {
+        let Some(path) = simulator_path() else {
+            return Self::new(starting_cash);
+        };
+
+        match crate::persist::load::<SimBroker>(&path, "simulator") {
+            Some(broker) => {
+                info!(
+                    "✅ Loaded simulated account with {} positions from {}",
+                    broker.positions.len(),
+                    path.display()
+                );
+                broker
+            }
+            None => Self::new(starting_cash),
+        }
+    }
+
+    /// Write the simulated account to disk
+    pub fn save(&self) {
+        let Some(path) = simulator_path() else {
+            return;
+        };
+
+        let result = crate::persist::save(&path, self);
+
+        if let Err(e) = result {
+            error!(
+                "✗ Error saving simulated account to {}: {}",
+                path.display(),
+                e
+            );
+        }
+    }
+}
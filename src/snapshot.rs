@@ -0,0 +1,214 @@
+// Snapshot module: latest trade, quote and daily bars for the charted symbol, so the header
+// has a price as soon as a symbol is selected instead of after the next streamed bar
+
+use crate::config::DataFeed;
+use crate::error::TerminalError;
+use alpaca_markets::{Bar, MarketDataClient, Snapshot};
+use chrono::{DateTime, NaiveTime, Utc};
+use chrono_tz::America::New_York;
+
+#[derive(Clone)]
+pub struct SymbolSnapshot {
+    pub symbol: String,
+    pub last_price: Option<f64>,
+    pub last_trade_at: Option<DateTime<Utc>>,
+    pub bid: Option<f64>,
+    pub ask: Option<f64>,
+    pub minute_bar: Option<Bar>,
+    pub daily_bar: Option<Bar>,
+    pub prev_daily_bar: Option<Bar>,
+}
+
+/// Extended-hours session of a trade
+#[derive(Clone, Copy, PartialEq)]
+pub enum ExtendedSession {
+    PreMarket,
+    AfterHours,
+}
+
+impl ExtendedSession {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExtendedSession::PreMarket => "Pre-market",
+            ExtendedSession::AfterHours => "After hours",
+        }
+    }
+}
+
+/// Last extended-hours price next to the regular-session close it moved from
+pub struct ExtendedQuote {
+    pub session: ExtendedSession,
+    pub price: f64,
+    // Change and percent change against `regular_close`
+    pub change: (f64, f64),
+    pub regular_close: f64,
+    // The regular session's own change against the session before it, when known
+    pub regular_change: Option<(f64, f64)>,
+}
+
+/// Regular session hours in New York time
+fn regular_open() -> NaiveTime {
+    NaiveTime::from_hms_opt(9, 30, 0).unwrap()
+}
+
+fn regular_close() -> NaiveTime {
+    NaiveTime::from_hms_opt(16, 0, 0).unwrap()
+}
+
+fn change_from(price: f64, reference: f64) -> Option<(f64, f64)> {
+    if reference <= 0.0 {
+        return None;
+    }
+    let change = price - reference;
+    Some((change, change / reference * 100.0))
+}
+
+impl SymbolSnapshot {
+    pub fn previous_close(&self) -> Option<f64> {
+        self.prev_daily_bar.as_ref().map(|bar| bar.close)
+    }
+
+    /// Change and percent change of `price` against the previous session's close
+    pub fn change(&self, price: f64) -> Option<(f64, f64)> {
+        change_from(price, self.previous_close()?)
+    }
+
+    /// Latest trade, or today's close when there hasn't been one
+    pub fn price(&self) -> Option<f64> {
+        self.last_price
+            .or_else(|| self.daily_bar.as_ref().map(|bar| bar.close))
+    }
+
+    /// Percent change of the latest price on the day
+    pub fn day_change_percent(&self) -> Option<f64> {
+        self.change(self.price()?).map(|(_, percent)| percent)
+    }
+
+    /// Value traded today (close × volume)
+    pub fn dollar_volume(&self) -> Option<f64> {
+        self.daily_bar
+            .as_ref()
+            .map(|bar| bar.close * bar.volume as f64)
+    }
+
+    fn from_api(symbol: String, snapshot: Snapshot) -> Self {
+        Self {
+            last_price: snapshot.latest_trade.as_ref().map(|trade| trade.price),
+            last_trade_at: snapshot.latest_trade.as_ref().map(|trade| trade.timestamp),
+            bid: snapshot.latest_quote.as_ref().map(|quote| quote.bid_price),
+            ask: snapshot.latest_quote.as_ref().map(|quote| quote.ask_price),
+            minute_bar: snapshot.minute_bar,
+            daily_bar: snapshot.daily_bar,
+            prev_daily_bar: snapshot.prev_daily_bar,
+            symbol,
+        }
+    }
+
+    /// Session of the last trade, if it printed outside regular hours
+    pub fn extended_session(&self) -> Option<ExtendedSession> {
+        let time = self.last_trade_at?.with_timezone(&New_York).time();
+        if time < regular_open() {
+            Some(ExtendedSession::PreMarket)
+        } else if time >= regular_close() {
+            Some(ExtendedSession::AfterHours)
+        } else {
+            None
+        }
+    }
+
+    /// `price` as an extended-hours quote, measured from the close of the last completed
+    /// regular session rather than the previous day's close
+    pub fn extended_quote(&self, price: f64) -> Option<ExtendedQuote> {
+        let session = self.extended_session()?;
+        let daily = self.daily_bar.as_ref()?;
+
+        // Before the open the daily bar may already be today's, so the last completed
+        // session is the one before it
+        let daily_is_complete = match session {
+            ExtendedSession::AfterHours => true,
+            ExtendedSession::PreMarket => {
+                let traded_on = self.last_trade_at?.with_timezone(&New_York).date_naive();
+                daily.timestamp.with_timezone(&New_York).date_naive() < traded_on
+            }
+        };
+        let (regular_close, regular_change) = if daily_is_complete {
+            let close = daily.close;
+            (close, self.change(close))
+        } else {
+            (self.previous_close()?, None)
+        };
+
+        Some(ExtendedQuote {
+            session,
+            price,
+            change: change_from(price, regular_close)?,
+            regular_close,
+            regular_change,
+        })
+    }
+}
+
+/// Fetch the snapshot for a stock or crypto symbol
+pub fn fetch_snapshot_sync(
+    symbol: String,
+    data_feed: DataFeed,
+) -> Result<SymbolSnapshot, TerminalError> {
+    crate::api::block_on(async {
+        let config = data_feed.configure(crate::credentials::alpaca_config()?);
+
+        let client = MarketDataClient::new(config);
+
+        let result = if crate::chart::is_crypto_symbol(&symbol) {
+            client.get_crypto_snapshot(&symbol).await
+        } else {
+            client.get_snapshot(&symbol).await
+        };
+
+        match result {
+            Ok(snapshot) => Ok(SymbolSnapshot::from_api(symbol, snapshot)),
+            Err(e) => Err(TerminalError::for_symbol(e, &symbol)),
+        }
+    })
+}
+
+/// Symbols per multi-symbol snapshot request
+const SNAPSHOT_BATCH: usize = 100;
+
+/// Fetch snapshots for many symbols, stocks and crypto pairs mixed, in batched requests.
+/// Symbols the API has no data for are left out.
+pub fn fetch_snapshots_sync(
+    symbols: &[String],
+    data_feed: DataFeed,
+) -> Result<Vec<SymbolSnapshot>, TerminalError> {
+    let (crypto, stocks): (Vec<&str>, Vec<&str>) = symbols
+        .iter()
+        .map(String::as_str)
+        .partition(|symbol| crate::chart::is_crypto_symbol(symbol));
+
+    let mut snapshots = Vec::with_capacity(symbols.len());
+    for (batch, is_crypto) in stocks
+        .chunks(SNAPSHOT_BATCH)
+        .map(|batch| (batch, false))
+        .chain(crypto.chunks(SNAPSHOT_BATCH).map(|batch| (batch, true)))
+    {
+        let result = crate::api::block_on(async {
+            let config = data_feed.configure(crate::credentials::alpaca_config()?);
+            let client = MarketDataClient::new(config);
+            let result = if is_crypto {
+                client.get_crypto_snapshots(batch).await
+            } else {
+                client.get_snapshots(batch).await
+            };
+            result.map_err(TerminalError::api)
+        })?;
+        snapshots.extend(
+            result
+                .into_iter()
+                .map(|(symbol, snapshot)| SymbolSnapshot::from_api(symbol, snapshot)),
+        );
+    }
+
+    // Keep the caller's order
+    snapshots.sort_by_key(|snapshot| symbols.iter().position(|symbol| *symbol == snapshot.symbol));
+    Ok(snapshots)
+}
@@ -0,0 +1,86 @@
+// Sound module for audio cues on fills, order events and alerts
+
+use rodio::source::{SineWave, Source};
+use serde::{Deserialize, Serialize};
+use std::thread;
+use std::time::Duration;
+use tracing::warn;
+
+/// Output volume of the generated tones (0.0 - 1.0)
+const CUE_VOLUME: f32 = 0.2;
+
+/// Event that can play an audio cue
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SoundCue {
+    Fill,
+    PartialFill,
+    Rejection,
+    Alert,
+}
+
+impl SoundCue {
+    pub const ALL: [SoundCue; 4] = [
+        SoundCue::Fill,
+        SoundCue::PartialFill,
+        SoundCue::Rejection,
+        SoundCue::Alert,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SoundCue::Fill => "Fill",
+            SoundCue::PartialFill => "Partial Fill",
+            SoundCue::Rejection => "Rejection",
+            SoundCue::Alert => "Alert",
+        }
+    }
+
+    /// Tone sequence as (frequency Hz, duration ms): rising for fills, falling for rejections
+    fn tones(&self) -> &'static [(f32, u64)] {
+        match self {
+            SoundCue::Fill => &[(660.0, 90), (880.0, 140)],
+            SoundCue::PartialFill => &[(660.0, 90)],
+            SoundCue::Rejection => &[(440.0, 120), (330.0, 200)],
+            SoundCue::Alert => &[(988.0, 120), (1319.0, 120), (988.0, 120), (1319.0, 120)],
+        }
+    }
+
+    /// Cue for an order stream event, if it has one
+    pub fn for_order_event(event: &str) -> Option<SoundCue> {
+        match event {
+            "fill" => Some(SoundCue::Fill),
+            "partial_fill" => Some(SoundCue::PartialFill),
+            "rejected" => Some(SoundCue::Rejection),
+            _ => None,
+        }
+    }
+}
+
+/// Play a cue on a background thread (the output stream must live until playback ends)
+pub fn play(cue: SoundCue) {
+    thread::spawn(move || {
+        let (_stream, handle) = match rodio::OutputStream::try_default() {
+            Ok(output) => output,
+            Err(e) => {
+                warn!("⚠️  No audio output device: {}", e);
+                return;
+            }
+        };
+        let sink = match rodio::Sink::try_new(&handle) {
+            Ok(sink) => sink,
+            Err(e) => {
+                warn!("⚠️  Failed to open audio sink: {}", e);
+                return;
+            }
+        };
+
+        for &(frequency, millis) in cue.tones() {
+            sink.append(
+                SineWave::new(frequency)
+                    .take_duration(Duration::from_millis(millis))
+                    .amplify(CUE_VOLUME),
+            );
+        }
+        sink.sleep_until_end();
+    });
+}
@@ -1,24 +1,48 @@
+use crate::config::DataFeed;
 use alpaca_markets::{
-    AlpacaConfig,
     clients::trading_stream::TradingStreamClient,
     wss::trading::{StreamData, TradeUpdate, TradingWebSocketMessage},
 };
+use std::collections::{HashMap, HashSet};
 use std::thread;
 use tokio::sync::mpsc;
+use tracing::{debug, error, info, info_span, warn};
 
 /// Message types that can be sent from the WebSocket to the UI
 #[derive(Clone, Debug)]
 pub enum StreamUpdate {
     Connected,
+    // Trading stream connected again after dropping
+    Reconnected,
     Disconnected,
     TradeUpdate(OrderUpdate),
     AccountUpdate(AccountInfo),
     BarUpdate(BarUpdate),
+    TradeTick(TradeTick),
+    // Best bid and offer for the symbol shown in the depth panel
+    Quote(QuoteTick),
     MarketDataConnected,
+    // Connected again after dropping; bars sent while it was down were missed
+    MarketDataReconnected,
     MarketDataDisconnected,
+    News(NewsHeadline),
+    NewsConnected,
+    NewsDisconnected,
+    // Control frame or message the UI doesn't use; shows the connection is alive
+    Heartbeat(StreamKind),
+    // How long the last connect and authenticate handshake took
+    Handshake { stream: StreamKind, millis: u64 },
     Error(String),
 }
 
+/// Which websocket a health update is about
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StreamKind {
+    Trading,
+    MarketData,
+    News,
+}
+
 /// Order update information from trade events
 #[derive(Clone, Debug)]
 pub struct OrderUpdate {
@@ -30,6 +54,8 @@ pub struct OrderUpdate {
     pub limit_price: Option<String>,
     pub status: String,
     pub created_at: String,
+    pub filled_qty: String,
+    pub filled_avg_price: Option<String>,
     pub event: String,
 }
 
@@ -55,6 +81,38 @@ pub struct BarUpdate {
     pub vwap: Option<String>,
 }
 
+/// Last trade price from the market data stream
+#[derive(Clone, Debug)]
+pub struct TradeTick {
+    pub symbol: String,
+    pub price: String,
+    // Exchange time of the trade
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// Top-of-book quote, streamed for the main chart's symbol while the depth panel is open
+#[derive(Clone, Debug)]
+pub struct QuoteTick {
+    pub symbol: String,
+    pub bid_price: String,
+    pub bid_size: String,
+    pub ask_price: String,
+    pub ask_size: String,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// Breaking headline from the news stream
+#[derive(Clone, Debug)]
+pub struct NewsHeadline {
+    pub id: i64,
+    pub headline: String,
+    pub summary: String,
+    pub source: String,
+    pub url: Option<String>,
+    pub symbols: Vec<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
 /// WebSocket stream manager
 pub struct StreamManager {
     sender: mpsc::UnboundedSender<StreamUpdate>,
@@ -86,43 +144,36 @@ impl StreamManager {
     /// Start the WebSocket connection in a background task
     pub fn start_stream(sender: mpsc::UnboundedSender<StreamUpdate>) -> thread::JoinHandle<()> {
         thread::spawn(move || {
+            let _span = info_span!("trading_stream").entered();
             // Create a Tokio runtime for this thread
             let rt = tokio::runtime::Runtime::new().unwrap();
             rt.block_on(async move {
-                println!("🚀 Starting Alpaca Trading WebSocket stream...");
+                info!("🚀 Starting Alpaca Trading WebSocket stream...");
 
                 // Create configuration
-                let config = match AlpacaConfig::from_env() {
-                    Ok(config) => {
-                        println!("✅ Configuration loaded from environment variables");
-                        config
-                    }
-                    Err(_) => {
-                        println!("⚠️  Environment variables not found. Using demo configuration.");
-                        println!(
-                            "   To use real data, set APCA_API_KEY_ID and APCA_API_SECRET_KEY"
-                        );
-
-                        AlpacaConfig::new(
-                            "DEMO_KEY".to_string(),
-                            "DEMO_SECRET".to_string(),
-                            true, // Use paper trading
-                        )
+                let config = match crate::credentials::alpaca_config() {
+                    Ok(config) => config,
+                    Err(e) => {
+                        warn!("⚠️  {}", e);
+                        let _ = sender.send(StreamUpdate::Disconnected);
+                        return;
                     }
                 };
 
                 // Create trading stream client
                 let mut client = TradingStreamClient::new(config);
 
-                println!("🔌 Connecting to Alpaca Trading WebSocket...");
+                info!("🔌 Connecting to Alpaca Trading WebSocket...");
 
+                let started = std::time::Instant::now();
                 match client.connect().await {
                     Ok(_) => {
-                        println!("✅ Connected to trading stream!");
+                        info!("✅ Connected to trading stream!");
                         let _ = sender.send(StreamUpdate::Connected);
+                        let _ = sender.send(handshake_update(StreamKind::Trading, started));
                     }
                     Err(e) => {
-                        eprintln!("❌ Connection failed: {}", e);
+                        error!("❌ Connection failed: {}", e);
                         let _ =
                             sender.send(StreamUpdate::Error(format!("Connection failed: {}", e)));
                         let _ = sender.send(StreamUpdate::Disconnected);
@@ -134,18 +185,19 @@ impl StreamManager {
                 loop {
                     match client.next_message().await {
                         Ok(Some(message)) => {
-                            if let Some(update) = process_message(message) {
-                                if sender.send(update).is_err() {
-                                    println!("❌ Failed to send update to UI (channel closed)");
-                                    break;
-                                }
+                            let update = process_message(message)
+                                .unwrap_or(StreamUpdate::Heartbeat(StreamKind::Trading));
+                            if sender.send(update).is_err() {
+                                warn!("❌ Failed to send update to UI (channel closed)");
+                                break;
                             }
                         }
                         Ok(None) => {
                             // None can mean:
                             // 1. Control frame (Ping/Pong) - already logged by library
                             // 2. Parse error - already logged by library with raw message
-                            // Just continue processing, no additional warning needed
+                            // Either way the connection is alive
+                            let _ = sender.send(StreamUpdate::Heartbeat(StreamKind::Trading));
                             continue;
                         }
                         Err(e) => {
@@ -154,26 +206,29 @@ impl StreamManager {
                             if error_str.contains("Serialization error")
                                 || error_str.contains("Unsupported message type")
                             {
-                                println!("⚠️  Skipping unsupported message type: {}", error_str);
+                                warn!("⚠️  Skipping unsupported message type: {}", error_str);
                                 // Continue processing, don't disconnect
                                 continue;
                             }
 
-                            eprintln!("❌ Error receiving message: {}", e);
+                            error!("❌ Error receiving message: {}", e);
                             let _ =
                                 sender.send(StreamUpdate::Error(format!("Stream error: {}", e)));
 
                             // Try to reconnect after a delay
-                            println!("🔄 Attempting to reconnect in 5 seconds...");
+                            info!("🔄 Attempting to reconnect in 5 seconds...");
                             tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
 
+                            let started = std::time::Instant::now();
                             match client.connect().await {
                                 Ok(_) => {
-                                    println!("✅ Reconnected successfully!");
-                                    let _ = sender.send(StreamUpdate::Connected);
+                                    info!("✅ Reconnected successfully!");
+                                    let _ = sender.send(StreamUpdate::Reconnected);
+                                    let _ =
+                                        sender.send(handshake_update(StreamKind::Trading, started));
                                 }
                                 Err(e) => {
-                                    eprintln!("❌ Reconnection failed: {}", e);
+                                    error!("❌ Reconnection failed: {}", e);
                                     let _ = sender.send(StreamUpdate::Disconnected);
                                     break;
                                 }
@@ -182,7 +237,7 @@ impl StreamManager {
                     }
                 }
 
-                println!("🛑 WebSocket stream task ended");
+                info!("🛑 WebSocket stream task ended");
             })
         })
     }
@@ -193,7 +248,7 @@ fn process_message(message: TradingWebSocketMessage) -> Option<StreamUpdate> {
     match message {
         TradingWebSocketMessage::StreamMessage(stream_msg) => match stream_msg.data {
             StreamData::TradeUpdate(trade_update) => {
-                println!(
+                debug!(
                     "🔄 Trade Update: {} - Order {} ({}) is now {}",
                     trade_update.event,
                     trade_update.order.id,
@@ -206,7 +261,7 @@ fn process_message(message: TradingWebSocketMessage) -> Option<StreamUpdate> {
                 )))
             }
             StreamData::AccountUpdate(account_update) => {
-                println!(
+                debug!(
                     "💰 Account Update: Buying Power: ${}, Cash: ${}",
                     account_update.buying_power, account_update.cash
                 );
@@ -218,27 +273,27 @@ fn process_message(message: TradingWebSocketMessage) -> Option<StreamUpdate> {
                 }))
             }
             StreamData::Listening(listening) => {
-                println!("👂 Subscribed to: {:?}", listening.streams);
+                info!("👂 Subscribed to: {:?}", listening.streams);
                 None
             }
         },
         TradingWebSocketMessage::Connected(connected) => {
-            println!("🔗 Connection: {}", connected.msg);
+            info!("🔗 Connection: {}", connected.msg);
             None
         }
         TradingWebSocketMessage::Authorization(auth) => {
-            println!("🔐 Auth: {} -> {}", auth.action, auth.status);
+            info!("🔐 Auth: {} -> {}", auth.action, auth.status);
             None
         }
         TradingWebSocketMessage::Error(error) => {
-            eprintln!("❌ Error [{}]: {}", error.code, error.msg);
+            error!("❌ Error [{}]: {}", error.code, error.msg);
             Some(StreamUpdate::Error(format!(
                 "[{}] {}",
                 error.code, error.msg
             )))
         }
         TradingWebSocketMessage::Unknown(data) => {
-            println!("❓ Unknown message: {}", data);
+            info!("❓ Unknown message: {}", data);
             None
         }
     }
@@ -258,11 +313,243 @@ fn convert_trade_update(trade: TradeUpdate) -> OrderUpdate {
         limit_price: trade.order.limit_price.clone(),
         status: trade.order.status.clone(),
         created_at: trade.order.created_at.to_rfc3339(),
+        filled_qty: trade.order.filled_qty.clone(),
+        filled_avg_price: trade.order.filled_avg_price.clone(),
         event: trade.event.to_string(),
     }
 }
 
 /// Market Data Stream Manager
+/// Most symbols the IEX feed streams on the free plan; SIP and crypto aren't capped here
+pub const IEX_SYMBOL_LIMIT: usize = 30;
+
+/// Part of the UI that needs symbols streamed, in priority order for the symbol limit
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub enum SymbolSource {
+    MainChart,
+    ChartWindows,
+    // Armed automation rules place orders, so they outrank display-only sources
+    Automation,
+    // Open simulated orders fill on trades, and simulated positions are marked with them
+    Simulator,
+    Watchlist,
+    Alerts,
+}
+
+/// Change to a running market data stream's subscriptions
+#[derive(Debug)]
+pub enum StreamCommand {
+    Subscribe(Vec<String>),
+    Unsubscribe(Vec<String>),
+    // Replace the symbols quotes are streamed for
+    Quotes(Vec<String>),
+}
+
+/// Reference-counted market data subscriptions. Each source declares the symbols it needs;
+/// a symbol stays subscribed while any source needs it, and running streams are
+/// subscribed and unsubscribed incrementally instead of being restarted.
+pub struct SubscriptionManager {
+    // Sources that need each symbol
+    needed: HashMap<String, HashSet<SymbolSource>>,
+    // Symbols the running streams are subscribed to
+    subscribed: HashSet<String>,
+    // Symbol whose quotes are needed, and the one the running streams stream quotes for.
+    // Quotes are heavy, so only one symbol gets them.
+    quote_symbol: Option<String>,
+    quoting: Option<String>,
+    equity: Option<mpsc::UnboundedSender<StreamCommand>>,
+    crypto: Option<mpsc::UnboundedSender<StreamCommand>>,
+    // Where the running streams send updates (None until the first restart)
+    updates: Option<mpsc::UnboundedSender<StreamUpdate>>,
+    data_feed: DataFeed,
+}
+
+impl SubscriptionManager {
+    pub fn new() -> Self {
+        Self {
+            needed: HashMap::new(),
+            subscribed: HashSet::new(),
+            quote_symbol: None,
+            quoting: None,
+            equity: None,
+            crypto: None,
+            updates: None,
+            data_feed: DataFeed::default(),
+        }
+    }
+
+    /// Replace the symbols one source needs
+    pub fn set(&mut self, source: SymbolSource, symbols: impl IntoIterator<Item = String>) {
+        for sources in self.needed.values_mut() {
+            sources.remove(&source);
+        }
+        for symbol in symbols {
+            self.needed.entry(symbol).or_default().insert(source);
+        }
+        self.needed.retain(|_, sources| !sources.is_empty());
+    }
+
+    /// Set the one symbol quotes are streamed for (None stops quotes)
+    pub fn set_quotes(&mut self, symbol: Option<String>) {
+        self.quote_symbol = symbol;
+    }
+
+    pub fn is_subscribed(&self, symbol: &str) -> bool {
+        self.subscribed.contains(symbol)
+    }
+
+    /// Symbols to stream: everything needed, with equities on IEX cut to the plan's limit
+    /// (charts first, then the watchlist, then alerts)
+    fn wanted(&self) -> HashSet<String> {
+        let mut symbols: Vec<(&SymbolSource, &String)> = self
+            .needed
+            .iter()
+            .filter_map(|(symbol, sources)| sources.iter().min().map(|source| (source, symbol)))
+            .collect();
+        symbols.sort();
+
+        let mut wanted = HashSet::new();
+        let mut equities = 0;
+        let mut dropped = Vec::new();
+        for (_, symbol) in symbols {
+            if !crate::chart::is_crypto_symbol(symbol) && self.data_feed == DataFeed::Iex {
+                if equities >= IEX_SYMBOL_LIMIT {
+                    dropped.push(symbol.as_str());
+                    continue;
+                }
+                equities += 1;
+            }
+            wanted.insert(symbol.clone());
+        }
+        if !dropped.is_empty() {
+            warn!(
+                "⚠️  IEX streams at most {} symbols, not streaming {:?}",
+                IEX_SYMBOL_LIMIT, dropped
+            );
+        }
+        wanted
+    }
+
+    /// Start new streams for everything needed (startup, or the data feed changed). The old
+    /// streams stop once their command channels are dropped.
+    pub fn restart(&mut self, data_feed: DataFeed) -> mpsc::UnboundedReceiver<StreamUpdate> {
+        let (updates, receiver) = mpsc::unbounded_channel();
+        self.data_feed = data_feed;
+        self.equity = None;
+        self.crypto = None;
+        self.subscribed.clear();
+        self.quoting = None;
+        self.updates = Some(updates);
+        self.sync();
+        receiver
+    }
+
+    /// Subscribe newly needed symbols and unsubscribe ones nothing needs any more
+    pub fn sync(&mut self) {
+        let Some(updates) = self.updates.clone() else {
+            return;
+        };
+        let wanted = self.wanted();
+        for crypto in [false, true] {
+            let on_feed = |symbol: &&String| crate::chart::is_crypto_symbol(symbol) == crypto;
+            let added: Vec<String> = wanted
+                .difference(&self.subscribed)
+                .filter(on_feed)
+                .cloned()
+                .collect();
+            let removed: Vec<String> = self
+                .subscribed
+                .difference(&wanted)
+                .filter(on_feed)
+                .cloned()
+                .collect();
+            if added.is_empty() && removed.is_empty() {
+                continue;
+            }
+
+            let data_feed = self.data_feed;
+            let handle = if crypto {
+                &mut self.crypto
+            } else {
+                &mut self.equity
+            };
+            let running = handle
+                .as_ref()
+                .is_some_and(|commands| send_changes(commands, &added, &removed));
+            if running {
+                info!("📡 Subscribed {:?}, unsubscribed {:?}", added, removed);
+            } else {
+                // Not running yet, or its thread ended after a failed reconnect
+                if self.quoting.as_ref().is_some_and(|symbol| on_feed(&symbol)) {
+                    self.quoting = None;
+                }
+                let symbols: Vec<String> = wanted.iter().filter(on_feed).cloned().collect();
+                *handle = (!symbols.is_empty()).then(|| {
+                    if crypto {
+                        MarketDataStreamManager::start_crypto_stream(updates.clone(), symbols)
+                    } else {
+                        MarketDataStreamManager::start_stream(updates.clone(), symbols, data_feed)
+                    }
+                });
+            }
+        }
+        self.subscribed = wanted;
+        self.sync_quotes();
+    }
+
+    /// Move the quote subscription to the wanted symbol, if it's streamed at all
+    fn sync_quotes(&mut self) {
+        let wanted = self
+            .quote_symbol
+            .clone()
+            .filter(|symbol| self.subscribed.contains(symbol));
+        if wanted == self.quoting {
+            return;
+        }
+        if let Some(old) = self.quoting.take() {
+            if let Some(commands) = self.handle(&old) {
+                let _ = commands.send(StreamCommand::Quotes(Vec::new()));
+            }
+        }
+        if let Some(symbol) = wanted {
+            let sent = self.handle(&symbol).is_some_and(|commands| {
+                commands
+                    .send(StreamCommand::Quotes(vec![symbol.clone()]))
+                    .is_ok()
+            });
+            if sent {
+                info!("💱 Streaming quotes for {}", symbol);
+                self.quoting = Some(symbol);
+            }
+        }
+    }
+
+    /// Command channel of the stream a symbol trades on
+    fn handle(&self, symbol: &str) -> Option<&mpsc::UnboundedSender<StreamCommand>> {
+        if crate::chart::is_crypto_symbol(symbol) {
+            self.crypto.as_ref()
+        } else {
+            self.equity.as_ref()
+        }
+    }
+}
+
+/// Send subscription changes to a stream; false if its thread has ended
+fn send_changes(
+    commands: &mpsc::UnboundedSender<StreamCommand>,
+    added: &[String],
+    removed: &[String],
+) -> bool {
+    (added.is_empty()
+        || commands
+            .send(StreamCommand::Subscribe(added.to_vec()))
+            .is_ok())
+        && (removed.is_empty()
+            || commands
+                .send(StreamCommand::Unsubscribe(removed.to_vec()))
+                .is_ok())
+}
+
 pub struct MarketDataStreamManager {
     sender: mpsc::UnboundedSender<StreamUpdate>,
 }
@@ -273,95 +560,162 @@ impl MarketDataStreamManager {
         Self { sender }
     }
 
-    /// Start the market data WebSocket connection in a background task
+    /// Start the equities market data WebSocket connection (IEX or SIP feed) in a background
+    /// task. Dropping the returned sender stops the stream.
     pub fn start_stream(
         sender: mpsc::UnboundedSender<StreamUpdate>,
         symbols: Vec<String>,
+        data_feed: DataFeed,
+    ) -> mpsc::UnboundedSender<StreamCommand> {
+        let (commands, receiver) = mpsc::unbounded_channel();
+        Self::spawn_stream(
+            sender,
+            symbols,
+            MarketDataFeed::Stocks(data_feed),
+            Some(receiver),
+        );
+        commands
+    }
+
+    /// Start the crypto market data WebSocket connection in a background task
+    pub fn start_crypto_stream(
+        sender: mpsc::UnboundedSender<StreamUpdate>,
+        symbols: Vec<String>,
+    ) -> mpsc::UnboundedSender<StreamCommand> {
+        let (commands, receiver) = mpsc::unbounded_channel();
+        Self::spawn_stream(sender, symbols, MarketDataFeed::Crypto, Some(receiver));
+        commands
+    }
+
+    /// Start the news WebSocket connection for headlines mentioning the symbols
+    pub fn start_news_stream(
+        sender: mpsc::UnboundedSender<StreamUpdate>,
+        symbols: Vec<String>,
+    ) -> thread::JoinHandle<()> {
+        Self::spawn_stream(sender, symbols, MarketDataFeed::News, None)
+    }
+
+    fn spawn_stream(
+        sender: mpsc::UnboundedSender<StreamUpdate>,
+        mut symbols: Vec<String>,
+        feed: MarketDataFeed,
+        mut commands: Option<mpsc::UnboundedReceiver<StreamCommand>>,
     ) -> thread::JoinHandle<()> {
         thread::spawn(move || {
+            let _span = info_span!("market_data_stream", feed = feed.label()).entered();
             // Create a Tokio runtime for this thread
             let rt = tokio::runtime::Runtime::new().unwrap();
             rt.block_on(async move {
-                println!("🚀 Starting Alpaca Market Data WebSocket stream...");
-                println!("📊 Subscribing to bars for symbols: {:?}", symbols);
+                info!(
+                    "🚀 Starting Alpaca {} Market Data WebSocket stream...",
+                    feed.label()
+                );
+                info!("📊 Subscribing to {:?}", symbols);
 
                 // Create configuration
-                let config = match AlpacaConfig::from_env() {
-                    Ok(config) => {
-                        println!("✅ Market Data configuration loaded from environment variables");
-                        config
-                    }
-                    Err(_) => {
-                        println!("⚠️  Environment variables not found. Using demo configuration.");
-                        println!(
-                            "   To use real data, set APCA_API_KEY_ID and APCA_API_SECRET_KEY"
-                        );
-
-                        AlpacaConfig::new(
-                            "DEMO_KEY".to_string(),
-                            "DEMO_SECRET".to_string(),
-                            true, // Use paper trading
-                        )
+                let config = match crate::credentials::alpaca_config() {
+                    Ok(config) => config,
+                    Err(e) => {
+                        warn!("⚠️  {}", e);
+                        let _ = sender.send(feed.disconnected_update());
+                        return;
                     }
                 };
 
                 // Import market data stream client
-                use alpaca_markets::clients::market_data_stream::{MarketDataStreamClient, Feed};
+                use alpaca_markets::clients::market_data_stream::{Feed, MarketDataStreamClient};
 
-                // Create market data stream client (using IEX feed)
-                let mut client = MarketDataStreamClient::new(config, Feed::Iex);
+                // Create market data stream client for the requested feed
+                let mut client = match feed {
+                    MarketDataFeed::Stocks(DataFeed::Iex) => {
+                        MarketDataStreamClient::new(config, Feed::Iex)
+                    }
+                    MarketDataFeed::Stocks(DataFeed::Sip) => {
+                        MarketDataStreamClient::new(config, Feed::Sip)
+                    }
+                    MarketDataFeed::Crypto => MarketDataStreamClient::new_crypto(config),
+                    MarketDataFeed::News => MarketDataStreamClient::new_news(config),
+                };
 
-                println!("🔌 Connecting to Alpaca Market Data WebSocket...");
+                info!(
+                    "🔌 Connecting to Alpaca {} Market Data WebSocket...",
+                    feed.label()
+                );
 
+                let started = std::time::Instant::now();
                 match client.connect().await {
                     Ok(_) => {
-                        println!("✅ Connected to market data stream!");
-                        let _ = sender.send(StreamUpdate::MarketDataConnected);
+                        info!("✅ Connected to {} market data stream!", feed.label());
+                        let _ = sender.send(feed.connected_update());
+                        let _ = sender.send(handshake_update(feed.kind(), started));
                     }
                     Err(e) => {
-                        eprintln!("❌ Market Data connection failed: {}", e);
+                        error!("❌ Market Data connection failed: {}", e);
                         let _ = sender.send(StreamUpdate::Error(format!(
                             "Market Data connection failed: {}",
                             e
                         )));
-                        let _ = sender.send(StreamUpdate::MarketDataDisconnected);
+                        let _ = sender.send(feed.disconnected_update());
                         return;
                     }
                 }
 
-                // Subscribe to bars for the specified symbols
-                // Convert Vec<String> to Vec<&str>
-                let symbol_refs: Vec<&str> = symbols.iter().map(|s| s.as_str()).collect();
-                if let Err(e) = client.subscribe(None, None, Some(&symbol_refs)).await {
-                    eprintln!("❌ Failed to subscribe to bars: {}", e);
-                    let _ = sender.send(StreamUpdate::Error(format!(
-                        "Failed to subscribe to bars: {}",
-                        e
-                    )));
-                    let _ = sender.send(StreamUpdate::MarketDataDisconnected);
+                // Subscribe to bars and trades (or headlines) for the specified symbols
+                if let Err(e) = subscribe_feed(&mut client, feed, &symbols).await {
+                    error!("❌ Failed to subscribe: {}", e);
+                    let _ = sender.send(StreamUpdate::Error(format!("Failed to subscribe: {}", e)));
+                    let _ = sender.send(feed.disconnected_update());
                     return;
                 }
 
-                println!("✅ Subscribed to bars for {:?}", symbols);
+                info!("✅ Subscribed to {} stream for {:?}", feed.label(), symbols);
 
-                // Process messages
+                // Symbols quotes are streamed for, set by command after the first subscribe
+                let mut quotes: Vec<String> = Vec::new();
+
+                // Process messages and subscription changes
                 loop {
-                    match client.next_message().await {
+                    let next = tokio::select! {
+                        command = next_command(&mut commands) => {
+                            match command {
+                                Some(command) => {
+                                    apply_command(
+                                        &mut client,
+                                        feed,
+                                        &mut symbols,
+                                        &mut quotes,
+                                        command,
+                                    )
+                                    .await
+                                }
+                                None => {
+                                    info!("🛑 {} stream replaced, stopping", feed.label());
+                                    return;
+                                }
+                            }
+                            continue;
+                        }
+                        next = client.next_message() => next,
+                    };
+                    match next {
                         Ok(Some(messages)) => {
                             // next_message() returns Vec<MarketDataMessage>
                             for message in messages {
-                                if let Some(update) = process_market_data_message(message) {
-                                    if sender.send(update).is_err() {
-                                        println!(
-                                            "❌ Failed to send market data update to UI (channel closed)"
-                                        );
-                                        break;
-                                    }
+                                let update = process_market_data_message(message)
+                                    .unwrap_or(StreamUpdate::Heartbeat(feed.kind()));
+                                if sender.send(update).is_err() {
+                                    // UI dropped the receiver (stream replaced or window closed)
+                                    info!(
+                                        "🛑 {} market data receiver closed, stopping stream",
+                                        feed.label()
+                                    );
+                                    return;
                                 }
                             }
                         }
                         Ok(None) => {
-                            // Control frame or unparsable message
+                            // Control frame or unparsable message; the connection is alive
+                            let _ = sender.send(StreamUpdate::Heartbeat(feed.kind()));
                             continue;
                         }
                         Err(e) => {
@@ -370,39 +724,45 @@ impl MarketDataStreamManager {
                             if error_str.contains("Serialization error")
                                 || error_str.contains("Unsupported message type")
                             {
-                                println!(
+                                warn!(
                                     "⚠️  Skipping unsupported market data message type: {}",
                                     error_str
                                 );
                                 continue;
                             }
 
-                            eprintln!("❌ Error receiving market data message: {}", e);
+                            error!("❌ Error receiving market data message: {}", e);
                             let _ = sender.send(StreamUpdate::Error(format!(
                                 "Market data stream error: {}",
                                 e
                             )));
 
                             // Try to reconnect after a delay
-                            println!("🔄 Attempting to reconnect market data stream in 5 seconds...");
+                            info!("🔄 Attempting to reconnect market data stream in 5 seconds...");
                             tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
 
+                            let started = std::time::Instant::now();
                             match client.connect().await {
                                 Ok(_) => {
-                                    println!("✅ Market data reconnected successfully!");
-                                    let _ = sender.send(StreamUpdate::MarketDataConnected);
-
-                                    // Re-subscribe to bars
-                                    let symbol_refs: Vec<&str> = symbols.iter().map(|s| s.as_str()).collect();
-                                    if let Err(e) = client.subscribe(None, None, Some(&symbol_refs)).await {
-                                        eprintln!("❌ Failed to re-subscribe to bars: {}", e);
-                                        let _ = sender.send(StreamUpdate::MarketDataDisconnected);
+                                    info!("✅ Market data reconnected successfully!");
+                                    let _ = sender.send(feed.reconnected_update());
+                                    let _ = sender.send(handshake_update(feed.kind(), started));
+
+                                    // Re-subscribe to bars and trades (or headlines)
+                                    if let Err(e) =
+                                        subscribe_feed(&mut client, feed, &symbols).await
+                                    {
+                                        error!("❌ Failed to re-subscribe: {}", e);
+                                        let _ = sender.send(feed.disconnected_update());
                                         break;
                                     }
+                                    if !quotes.is_empty() {
+                                        subscribe_quotes(&mut client, &quotes).await;
+                                    }
                                 }
                                 Err(e) => {
-                                    eprintln!("❌ Market data reconnection failed: {}", e);
-                                    let _ = sender.send(StreamUpdate::MarketDataDisconnected);
+                                    error!("❌ Market data reconnection failed: {}", e);
+                                    let _ = sender.send(feed.disconnected_update());
                                     break;
                                 }
                             }
@@ -410,12 +770,164 @@ impl MarketDataStreamManager {
                     }
                 }
 
-                println!("🛑 Market Data WebSocket stream task ended");
+                info!(
+                    "🛑 {} Market Data WebSocket stream task ended",
+                    feed.label()
+                );
             })
         })
     }
 }
 
+/// Which market data WebSocket a stream connects to
+#[derive(Clone, Copy, Debug)]
+enum MarketDataFeed {
+    Stocks(DataFeed),
+    Crypto,
+    News,
+}
+
+impl MarketDataFeed {
+    fn label(&self) -> &'static str {
+        match self {
+            MarketDataFeed::Stocks(data_feed) => data_feed.label(),
+            MarketDataFeed::Crypto => "Crypto",
+            MarketDataFeed::News => "News",
+        }
+    }
+
+    fn kind(&self) -> StreamKind {
+        match self {
+            MarketDataFeed::News => StreamKind::News,
+            _ => StreamKind::MarketData,
+        }
+    }
+
+    /// Status update sent once the feed is connected (news has its own indicator)
+    fn connected_update(&self) -> StreamUpdate {
+        match self {
+            MarketDataFeed::News => StreamUpdate::NewsConnected,
+            _ => StreamUpdate::MarketDataConnected,
+        }
+    }
+
+    fn reconnected_update(&self) -> StreamUpdate {
+        match self {
+            MarketDataFeed::News => StreamUpdate::NewsConnected,
+            _ => StreamUpdate::MarketDataReconnected,
+        }
+    }
+
+    fn disconnected_update(&self) -> StreamUpdate {
+        match self {
+            MarketDataFeed::News => StreamUpdate::NewsDisconnected,
+            _ => StreamUpdate::MarketDataDisconnected,
+        }
+    }
+}
+
+/// Handshake time for a connect that started at `started`
+fn handshake_update(stream: StreamKind, started: std::time::Instant) -> StreamUpdate {
+    StreamUpdate::Handshake {
+        stream,
+        millis: started.elapsed().as_millis() as u64,
+    }
+}
+
+/// Next subscription change. Streams started without a command channel (news) wait forever.
+async fn next_command(
+    commands: &mut Option<mpsc::UnboundedReceiver<StreamCommand>>,
+) -> Option<StreamCommand> {
+    match commands {
+        Some(commands) => commands.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Apply a subscription change. `symbols` and `quotes` are updated either way so a
+/// reconnect subscribes to the current set.
+async fn apply_command(
+    client: &mut alpaca_markets::clients::market_data_stream::MarketDataStreamClient,
+    feed: MarketDataFeed,
+    symbols: &mut Vec<String>,
+    quotes: &mut Vec<String>,
+    command: StreamCommand,
+) {
+    match command {
+        StreamCommand::Quotes(wanted) => {
+            let removed: Vec<&str> = quotes
+                .iter()
+                .filter(|symbol| !wanted.contains(symbol))
+                .map(|s| s.as_str())
+                .collect();
+            if !removed.is_empty() {
+                match client.unsubscribe(None, Some(&removed), None).await {
+                    Ok(_) => info!("➖ Stopped quotes for {:?}", removed),
+                    Err(e) => error!("❌ Failed to stop quotes for {:?}: {}", removed, e),
+                }
+            }
+            let added: Vec<String> = wanted
+                .iter()
+                .filter(|symbol| !quotes.contains(symbol))
+                .cloned()
+                .collect();
+            if !added.is_empty() {
+                subscribe_quotes(client, &added).await;
+            }
+            *quotes = wanted;
+        }
+        StreamCommand::Subscribe(added) => {
+            match subscribe_feed(client, feed, &added).await {
+                Ok(()) => info!("➕ Subscribed to {:?}", added),
+                Err(e) => error!("❌ Failed to subscribe to {:?}: {}", added, e),
+            }
+            symbols.extend(added);
+        }
+        StreamCommand::Unsubscribe(removed) => {
+            let symbol_refs: Vec<&str> = removed.iter().map(|s| s.as_str()).collect();
+            match client
+                .unsubscribe(Some(&symbol_refs), None, Some(&symbol_refs))
+                .await
+            {
+                Ok(_) => info!("➖ Unsubscribed from {:?}", removed),
+                Err(e) => error!("❌ Failed to unsubscribe from {:?}: {}", removed, e),
+            }
+            symbols.retain(|symbol| !removed.contains(symbol));
+        }
+    }
+}
+
+/// Subscribe to bars and trades, or to headlines on the news feed
+async fn subscribe_feed(
+    client: &mut alpaca_markets::clients::market_data_stream::MarketDataStreamClient,
+    feed: MarketDataFeed,
+    symbols: &[String],
+) -> Result<(), String> {
+    // Convert Vec<String> to Vec<&str>
+    let symbol_refs: Vec<&str> = symbols.iter().map(|s| s.as_str()).collect();
+    let result = match feed {
+        MarketDataFeed::News => client.subscribe_news(&symbol_refs).await,
+        _ => {
+            client
+                .subscribe(Some(&symbol_refs), None, Some(&symbol_refs))
+                .await
+        }
+    };
+    result.map_err(|e| e.to_string())
+}
+
+/// Add quote subscriptions for the symbols
+async fn subscribe_quotes(
+    client: &mut alpaca_markets::clients::market_data_stream::MarketDataStreamClient,
+    symbols: &[String],
+) {
+    let symbol_refs: Vec<&str> = symbols.iter().map(|s| s.as_str()).collect();
+    match client.subscribe(None, Some(&symbol_refs), None).await {
+        Ok(_) => info!("➕ Streaming quotes for {:?}", symbols),
+        Err(e) => error!("❌ Failed to stream quotes for {:?}: {}", symbols, e),
+    }
+}
+
 /// Process incoming market data WebSocket messages and convert to StreamUpdate
 fn process_market_data_message(
     message: alpaca_markets::wss::market_data::MarketDataMessage,
@@ -424,7 +936,7 @@ fn process_market_data_message(
 
     match message {
         MarketDataMessage::Bar(bar) => {
-            println!(
+            debug!(
                 "📊 Bar Update: {} @ {} - O:{} H:{} L:{} C:{} V:{}",
                 bar.symbol, bar.timestamp, bar.open, bar.high, bar.low, bar.close, bar.volume
             );
@@ -442,25 +954,48 @@ fn process_market_data_message(
             }))
         }
         MarketDataMessage::Trade(trade) => {
-            println!(
+            debug!(
                 "💹 Trade: {} @ {} - Price: {}, Size: {}",
                 trade.symbol, trade.timestamp, trade.price, trade.size
             );
-            None // Not handling trades yet
+            Some(StreamUpdate::TradeTick(TradeTick {
+                symbol: trade.symbol,
+                price: trade.price.to_string(),
+                timestamp: trade.timestamp,
+            }))
         }
         MarketDataMessage::Quote(quote) => {
-            println!(
+            debug!(
                 "💱 Quote: {} @ {} - Bid: {}, Ask: {}",
                 quote.symbol, quote.timestamp, quote.bid_price, quote.ask_price
             );
-            None // Not handling quotes yet
+            Some(StreamUpdate::Quote(QuoteTick {
+                symbol: quote.symbol,
+                bid_price: quote.bid_price.to_string(),
+                bid_size: quote.bid_size.to_string(),
+                ask_price: quote.ask_price.to_string(),
+                ask_size: quote.ask_size.to_string(),
+                timestamp: quote.timestamp,
+            }))
+        }
+        MarketDataMessage::News(news) => {
+            debug!("📰 News: [{}] {}", news.symbols.join(","), news.headline);
+            Some(StreamUpdate::News(NewsHeadline {
+                id: news.id,
+                headline: news.headline,
+                summary: news.summary,
+                source: news.source,
+                url: news.url.filter(|url| !url.is_empty()),
+                symbols: news.symbols,
+                created_at: news.created_at,
+            }))
         }
         MarketDataMessage::Subscription(sub) => {
-            println!("👂 Market Data Subscriptions: {:?}", sub);
+            debug!("👂 Market Data Subscriptions: {:?}", sub);
             None
         }
         MarketDataMessage::Error(error) => {
-            eprintln!("❌ Market Data Error: {:?}", error);
+            error!("❌ Market Data Error: {:?}", error);
             Some(StreamUpdate::Error(format!(
                 "Market Data Error: {:?}",
                 error
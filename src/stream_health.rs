@@ -0,0 +1,128 @@
+// Stream health module: per-websocket telemetry shown in the connection status popover
+
+use crate::stream::{StreamKind, StreamUpdate};
+use chrono::{DateTime, Utc};
+use std::time::{Duration, Instant};
+
+/// A connected market data stream silent for this long is flagged (no bars, trades or pings)
+pub const STALE_AFTER: Duration = Duration::from_secs(90);
+
+/// What we know about one websocket
+#[derive(Default)]
+pub struct ChannelHealth {
+    pub connected: bool,
+    pub connected_at: Option<Instant>,
+    // Any message, including control frames
+    pub last_message_at: Option<Instant>,
+    pub messages: u64,
+    pub reconnects: u32,
+    // Time for the last connect and authenticate handshake
+    pub handshake_ms: Option<u64>,
+    // Exchange timestamp of the last trade or quote to its arrival here. Includes any offset
+    // between the local and exchange clocks, so it's clamped at zero.
+    pub latency_ms: Option<i64>,
+}
+
+impl ChannelHealth {
+    fn connected(&mut self) {
+        self.connected = true;
+        self.connected_at = Some(Instant::now());
+        self.last_message_at = Some(Instant::now());
+    }
+
+    fn disconnected(&mut self) {
+        self.connected = false;
+        self.connected_at = None;
+        self.latency_ms = None;
+    }
+
+    fn message(&mut self) {
+        self.messages += 1;
+        self.last_message_at = Some(Instant::now());
+    }
+
+    /// A market data message stamped by the exchange at `timestamp` arrived now
+    fn timestamped_message(&mut self, timestamp: DateTime<Utc>) {
+        self.message();
+        self.latency_ms = Some((Utc::now() - timestamp).num_milliseconds().max(0));
+    }
+
+    /// Time since the last message
+    pub fn idle(&self) -> Option<Duration> {
+        self.last_message_at.map(|at| at.elapsed())
+    }
+
+    /// Connected, but nothing has arrived for a while
+    pub fn is_stale(&self) -> bool {
+        self.connected && self.idle().is_some_and(|idle| idle >= STALE_AFTER)
+    }
+}
+
+/// Short age such as "12s", "4m" or "2h"
+pub fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 3600 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}h", secs / 3600)
+    }
+}
+
+pub struct StreamHealth {
+    pub trading: ChannelHealth,
+    pub market_data: ChannelHealth,
+    pub news: ChannelHealth,
+    // Status popover
+    pub open: bool,
+}
+
+impl StreamHealth {
+    pub fn new() -> Self {
+        Self {
+            trading: ChannelHealth::default(),
+            market_data: ChannelHealth::default(),
+            news: ChannelHealth::default(),
+            open: false,
+        }
+    }
+
+    fn channel_mut(&mut self, kind: StreamKind) -> &mut ChannelHealth {
+        match kind {
+            StreamKind::Trading => &mut self.trading,
+            StreamKind::MarketData => &mut self.market_data,
+            StreamKind::News => &mut self.news,
+        }
+    }
+
+    /// Update the counters for a message from any of the streams
+    pub fn record(&mut self, update: &StreamUpdate) {
+        match update {
+            StreamUpdate::Connected => self.trading.connected(),
+            StreamUpdate::Reconnected => {
+                self.trading.connected();
+                self.trading.reconnects += 1;
+            }
+            StreamUpdate::Disconnected => self.trading.disconnected(),
+            StreamUpdate::TradeUpdate(_) | StreamUpdate::AccountUpdate(_) => self.trading.message(),
+            StreamUpdate::MarketDataConnected => self.market_data.connected(),
+            StreamUpdate::MarketDataReconnected => {
+                self.market_data.connected();
+                self.market_data.reconnects += 1;
+            }
+            StreamUpdate::MarketDataDisconnected => self.market_data.disconnected(),
+            StreamUpdate::BarUpdate(_) => self.market_data.message(),
+            StreamUpdate::TradeTick(tick) => self.market_data.timestamped_message(tick.timestamp),
+            StreamUpdate::Quote(quote) => self.market_data.timestamped_message(quote.timestamp),
+            StreamUpdate::NewsConnected => self.news.connected(),
+            StreamUpdate::NewsDisconnected => self.news.disconnected(),
+            StreamUpdate::News(_) => self.news.message(),
+            StreamUpdate::Heartbeat(kind) => self.channel_mut(*kind).message(),
+            StreamUpdate::Handshake { stream, millis } => {
+                self.channel_mut(*stream).handshake_ms = Some(*millis)
+            }
+            StreamUpdate::Error(_) => {}
+        }
+    }
+}
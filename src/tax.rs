@@ -0,0 +1,108 @@
+// Tax module: per-lot report of realized and open lots from the FIFO lot ledger, with
+// short/long-term holding period classification
+
+use crate::pnl::LotLedger;
+use chrono::{DateTime, Months, Utc};
+
+/// Holding period class
+#[derive(Clone, Copy, PartialEq)]
+pub enum Term {
+    Short,
+    Long,
+}
+
+impl Term {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Term::Short => "Short-term",
+            Term::Long => "Long-term",
+        }
+    }
+}
+
+/// One lot: closed (sold or covered) or still open
+pub struct TaxLot {
+    pub symbol: String,
+    pub qty: f64,
+    pub is_long: bool,
+    pub opened_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+    // Price paid (the cover price for a short)
+    pub cost_basis: Option<f64>,
+    // Price received (the opening sale for a short)
+    pub proceeds: Option<f64>,
+}
+
+impl TaxLot {
+    /// Days from open to close, or to now for an open lot
+    pub fn holding_days(&self) -> i64 {
+        (self.closed_at.unwrap_or_else(Utc::now) - self.opened_at).num_days()
+    }
+
+    /// Long-term when a long lot was held more than a year. Short sales are short-term
+    /// whatever their length.
+    pub fn term(&self) -> Term {
+        let end = self.closed_at.unwrap_or_else(Utc::now);
+        let year_later = self
+            .opened_at
+            .date_naive()
+            .checked_add_months(Months::new(12));
+        if self.is_long && year_later.is_some_and(|date| end.date_naive() > date) {
+            Term::Long
+        } else {
+            Term::Short
+        }
+    }
+
+    /// Realized gain (negative for a loss); None while the lot is open
+    pub fn gain(&self) -> Option<f64> {
+        Some(self.proceeds? - self.cost_basis?)
+    }
+}
+
+/// Closed lots, most recently closed first, followed by open lots, oldest first
+pub fn report(ledger: &LotLedger) -> Vec<TaxLot> {
+    let mut closed: Vec<TaxLot> = ledger
+        .closed_trades
+        .iter()
+        .map(|trade| {
+            let (cost, sale) = if trade.is_long {
+                (trade.entry_price, trade.exit_price)
+            } else {
+                (trade.exit_price, trade.entry_price)
+            };
+            TaxLot {
+                symbol: trade.symbol.clone(),
+                qty: trade.qty,
+                is_long: trade.is_long,
+                opened_at: trade.opened_at,
+                closed_at: Some(trade.closed_at),
+                cost_basis: Some(cost * trade.qty),
+                proceeds: Some(sale * trade.qty),
+            }
+        })
+        .collect();
+    closed.sort_by(|a, b| b.closed_at.cmp(&a.closed_at));
+
+    let mut open: Vec<TaxLot> = ledger
+        .open_lots
+        .values()
+        .flatten()
+        .map(|lot| {
+            let is_long = lot.qty > 0.0;
+            TaxLot {
+                symbol: lot.symbol.clone(),
+                qty: lot.qty.abs(),
+                is_long,
+                opened_at: lot.opened_at,
+                closed_at: None,
+                cost_basis: is_long.then(|| lot.cost_basis()),
+                proceeds: (!is_long).then(|| lot.cost_basis()),
+            }
+        })
+        .collect();
+    open.sort_by_key(|lot| lot.opened_at);
+
+    closed.extend(open);
+    closed
+}
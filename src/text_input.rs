@@ -0,0 +1,322 @@
+// Text input module: single-line editable text with a cursor, selection and clipboard
+
+use gpui::{App, ClipboardItem, Div, Keystroke, div, prelude::*, px, rgb};
+use std::ops::Range;
+
+/// Characters an input accepts
+#[derive(Clone, Copy, PartialEq)]
+pub enum InputMode {
+    // Letters, digits and '/' for crypto pairs such as BTC/USD
+    Symbol,
+    // Digits with at most one decimal point
+    Decimal,
+    Integer,
+    // ASCII letters and digits, case preserved (API keys and secrets)
+    ApiKey,
+    // Any printable text (names)
+    Text,
+}
+
+impl InputMode {
+    fn accepts(&self, c: char) -> bool {
+        match self {
+            InputMode::Symbol => c.is_alphanumeric() || c == '/',
+            InputMode::Decimal => c.is_ascii_digit() || c == '.',
+            InputMode::Integer => c.is_ascii_digit(),
+            InputMode::ApiKey => c.is_ascii_alphanumeric(),
+            InputMode::Text => !c.is_control(),
+        }
+    }
+
+    /// What the input expects, for error messages
+    pub fn describe(&self) -> &'static str {
+        match self {
+            InputMode::Symbol => "symbol",
+            InputMode::Decimal => "number",
+            InputMode::Integer => "whole number",
+            InputMode::ApiKey => "API key",
+            InputMode::Text => "name",
+        }
+    }
+
+    /// Clean up pasted text: trim, drop a leading `$` (cashtags, prices) and thousands
+    /// separators, upper-case symbols. Returns None unless every remaining character fits.
+    fn normalize_paste(&self, text: &str) -> Option<String> {
+        let text = text.trim();
+        let text = match self {
+            InputMode::Symbol => text.strip_prefix('$').unwrap_or(text).to_uppercase(),
+            InputMode::Decimal | InputMode::Integer => {
+                text.strip_prefix('$').unwrap_or(text).replace(',', "")
+            }
+            InputMode::ApiKey | InputMode::Text => text.to_string(),
+        };
+
+        let valid = !text.is_empty()
+            && text.chars().all(|c| self.accepts(c))
+            && (*self != InputMode::Decimal || text.matches('.').count() <= 1);
+        valid.then_some(text)
+    }
+}
+
+/// Result of a keystroke sent to an input
+#[derive(PartialEq)]
+pub enum KeyOutcome {
+    // Not an editing key; the caller handles it (enter, escape, tab, up/down)
+    Ignored,
+    Handled,
+    // Clipboard text didn't fit the input mode and was not inserted
+    PasteRejected,
+}
+
+pub struct TextInput {
+    text: String,
+    // Byte offsets (always on char boundaries); the selection runs from anchor to cursor
+    cursor: usize,
+    anchor: Option<usize>,
+    mode: InputMode,
+    // Shown as bullets and never copied to the clipboard (secrets)
+    masked: bool,
+}
+
+impl TextInput {
+    pub fn new(text: impl Into<String>, mode: InputMode) -> Self {
+        let text = text.into();
+        Self {
+            cursor: text.len(),
+            text,
+            anchor: None,
+            mode,
+            masked: false,
+        }
+    }
+
+    pub fn masked(mut self) -> Self {
+        self.masked = true;
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn mode(&self) -> InputMode {
+        self.mode
+    }
+
+    /// Replace the contents, leaving the cursor at the end
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.cursor = self.text.len();
+        self.anchor = None;
+    }
+
+    pub fn clear(&mut self) {
+        self.set_text(String::new());
+    }
+
+    fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor?;
+        (anchor != self.cursor).then(|| anchor.min(self.cursor)..anchor.max(self.cursor))
+    }
+
+    fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.cursor = self.text.len();
+    }
+
+    fn previous_boundary(&self, offset: usize) -> usize {
+        self.text[..offset]
+            .char_indices()
+            .next_back()
+            .map(|(index, _)| index)
+            .unwrap_or(0)
+    }
+
+    fn next_boundary(&self, offset: usize) -> usize {
+        self.text[offset..]
+            .chars()
+            .next()
+            .map(|c| offset + c.len_utf8())
+            .unwrap_or(self.text.len())
+    }
+
+    /// Move the cursor, extending the selection when `select` is set
+    fn move_to(&mut self, offset: usize, select: bool) {
+        if select {
+            self.anchor.get_or_insert(self.cursor);
+        } else {
+            self.anchor = None;
+        }
+        self.cursor = offset;
+    }
+
+    /// Insert text at the cursor (replacing the selection), dropping characters the mode rejects
+    fn insert(&mut self, text: &str) {
+        let range = self.selection().unwrap_or(self.cursor..self.cursor);
+        let mut has_point = self.mode == InputMode::Decimal
+            && (self.text[..range.start].contains('.') || self.text[range.end..].contains('.'));
+
+        let filtered: String = text
+            .chars()
+            .filter(|&c| {
+                if !self.mode.accepts(c) {
+                    return false;
+                }
+                if c == '.' {
+                    if has_point {
+                        return false;
+                    }
+                    has_point = true;
+                }
+                true
+            })
+            .collect();
+
+        self.text.replace_range(range.clone(), &filtered);
+        self.cursor = range.start + filtered.len();
+        self.anchor = None;
+    }
+
+    fn delete_selection(&mut self) -> bool {
+        match self.selection() {
+            Some(range) => {
+                self.text.replace_range(range.clone(), "");
+                self.cursor = range.start;
+                self.anchor = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn backspace(&mut self) {
+        if !self.delete_selection() && self.cursor > 0 {
+            let start = self.previous_boundary(self.cursor);
+            self.text.replace_range(start..self.cursor, "");
+            self.cursor = start;
+        }
+    }
+
+    fn delete(&mut self) {
+        if !self.delete_selection() && self.cursor < self.text.len() {
+            let end = self.next_boundary(self.cursor);
+            self.text.replace_range(self.cursor..end, "");
+        }
+    }
+
+    /// Insert clipboard text, replacing the selection
+    fn paste(&mut self, text: &str) -> KeyOutcome {
+        match self.mode.normalize_paste(text) {
+            Some(text) => {
+                // insert() still drops a second decimal point if the text already has one
+                self.insert(&text);
+                KeyOutcome::Handled
+            }
+            None => KeyOutcome::PasteRejected,
+        }
+    }
+
+    /// Apply an editing keystroke
+    pub fn handle_key(&mut self, keystroke: &Keystroke, cx: &mut App) -> KeyOutcome {
+        let select = keystroke.modifiers.shift;
+
+        if keystroke.modifiers.secondary() {
+            match keystroke.key.as_str() {
+                "a" => self.select_all(),
+                "c" | "x" if self.masked => {}
+                "c" => {
+                    if let Some(range) = self.selection() {
+                        cx.write_to_clipboard(ClipboardItem::new_string(
+                            self.text[range].to_string(),
+                        ));
+                    }
+                }
+                "x" => {
+                    if let Some(range) = self.selection() {
+                        cx.write_to_clipboard(ClipboardItem::new_string(
+                            self.text[range].to_string(),
+                        ));
+                        self.delete_selection();
+                    }
+                }
+                "v" => {
+                    if let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) {
+                        return self.paste(&text);
+                    }
+                }
+                _ => return KeyOutcome::Ignored,
+            }
+            return KeyOutcome::Handled;
+        }
+
+        match keystroke.key.as_str() {
+            "left" => {
+                let offset = match self.selection() {
+                    Some(range) if !select => range.start,
+                    _ => self.previous_boundary(self.cursor),
+                };
+                self.move_to(offset, select);
+            }
+            "right" => {
+                let offset = match self.selection() {
+                    Some(range) if !select => range.end,
+                    _ => self.next_boundary(self.cursor),
+                };
+                self.move_to(offset, select);
+            }
+            "home" => self.move_to(0, select),
+            "end" => self.move_to(self.text.len(), select),
+            "backspace" => self.backspace(),
+            "delete" => self.delete(),
+            _ => match &keystroke.key_char {
+                Some(key_char) if !keystroke.modifiers.control && !keystroke.modifiers.alt => {
+                    self.insert(key_char)
+                }
+                _ => return KeyOutcome::Ignored,
+            },
+        }
+        KeyOutcome::Handled
+    }
+
+    /// Text as displayed: bullets for masked inputs (mode keeps masked text ASCII,
+    /// so byte ranges line up with the bullets)
+    fn display(&self, range: Range<usize>) -> String {
+        if self.masked {
+            "•".repeat(range.len())
+        } else {
+            self.text[range].to_string()
+        }
+    }
+
+    /// Contents with the cursor and selection highlight while focused, the placeholder
+    /// when empty and unfocused
+    pub fn render(&self, focused: bool, placeholder: &str) -> Div {
+        if !focused {
+            return div().child(if self.text.is_empty() {
+                placeholder.to_string()
+            } else {
+                self.display(0..self.text.len())
+            });
+        }
+
+        let range = self.selection().unwrap_or(self.cursor..self.cursor);
+        let cursor = || div().w(px(1.0)).h(px(16.0)).bg(rgb(0xffffff));
+
+        div()
+            .flex()
+            .items_center()
+            .child(self.display(0..range.start))
+            .when(self.cursor == range.start, |this| this.child(cursor()))
+            .when(!range.is_empty(), |this| {
+                this.child(div().bg(rgb(0x1f6feb)).child(self.display(range.clone())))
+            })
+            .when(self.cursor == range.end && !range.is_empty(), |this| {
+                this.child(cursor())
+            })
+            .child(self.display(range.end..self.text.len()))
+    }
+}
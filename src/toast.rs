@@ -0,0 +1,144 @@
+// Toast module for transient messages and background failures
+
+/// How long a toast stays on screen
+pub const TOAST_DURATION_SECS: u64 = 5;
+
+/// Most toasts shown at once (oldest are dropped)
+pub const MAX_TOASTS: usize = 5;
+
+/// Toast color coding
+#[derive(Clone, Copy, PartialEq)]
+pub enum ToastLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+/// Operation a failed toast can re-run from its Retry button
+#[derive(Clone, PartialEq)]
+pub enum RetryAction {
+    Bars,
+    Asset,
+    CorporateActions,
+    Account,
+    AccountConfig,
+    Calendar,
+    Positions,
+    Orders,
+    MoreOrders,
+    OrderHistory,
+    Activities,
+    News,
+    Alerts,
+    CancelOrder(String),
+    ClosePosition(String),
+}
+
+#[derive(Clone)]
+pub struct Toast {
+    pub id: u64,
+    pub level: ToastLevel,
+    pub message: String,
+    // What to do about an error, shown under the message
+    pub hint: Option<&'static str>,
+    // Toasts with a retry action stay until dismissed or retried
+    pub action: Option<RetryAction>,
+}
+
+/// Toasts on screen, oldest first
+pub struct Toasts {
+    pub items: Vec<Toast>,
+    next_id: u64,
+}
+
+impl Toasts {
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Show a toast, returning its id for dismissal
+    pub fn push(
+        &mut self,
+        level: ToastLevel,
+        message: String,
+        hint: Option<&'static str>,
+        action: Option<RetryAction>,
+    ) -> u64 {
+        self.next_id += 1;
+        self.items.push(Toast {
+            id: self.next_id,
+            level,
+            message,
+            hint,
+            action,
+        });
+        if self.items.len() > MAX_TOASTS {
+            self.items.remove(0);
+        }
+        self.next_id
+    }
+
+    pub fn dismiss(&mut self, id: u64) {
+        self.items.retain(|toast| toast.id != id);
+    }
+
+    /// Whether a toast with this message is already on screen
+    pub fn is_showing(&self, message: &str) -> bool {
+        self.items.iter().any(|toast| toast.message == message)
+    }
+}
+
+/// Toast for an order lifecycle event from the trading stream
+pub fn order_event_toast(
+    order_update: &crate::stream::OrderUpdate,
+) -> Option<(ToastLevel, String)> {
+    let side = order_update.side.to_uppercase();
+    let price = order_update
+        .filled_avg_price
+        .as_ref()
+        .map(|p| format!(" @ ${}", p))
+        .unwrap_or_default();
+
+    match order_update.event.as_str() {
+        "new" => Some((
+            ToastLevel::Info,
+            format!(
+                "New {} {} {} {}",
+                order_update.order_type, side, order_update.qty, order_update.symbol
+            ),
+        )),
+        "partial_fill" => Some((
+            ToastLevel::Warning,
+            format!(
+                "Partial fill: {} {} {}/{}{}",
+                side, order_update.symbol, order_update.filled_qty, order_update.qty, price
+            ),
+        )),
+        "fill" => Some((
+            ToastLevel::Success,
+            format!(
+                "Filled: {} {} {}{}",
+                side, order_update.filled_qty, order_update.symbol, price
+            ),
+        )),
+        "canceled" => Some((
+            ToastLevel::Info,
+            format!(
+                "Canceled: {} {} {}",
+                side, order_update.qty, order_update.symbol
+            ),
+        )),
+        "rejected" => Some((
+            ToastLevel::Error,
+            format!(
+                "Rejected: {} {} {}",
+                side, order_update.qty, order_update.symbol
+            ),
+        )),
+        _ => None,
+    }
+}
@@ -0,0 +1,24 @@
+// Universe module: symbol sets for the market-wide views
+
+use crate::config::Universe;
+
+/// S&P 100 constituents (early 2025). Alpaca writes share classes with a dot (BRK.B).
+pub const SP100: &[&str] = &[
+    "AAPL", "ABBV", "ABT", "ACN", "ADBE", "AIG", "AMD", "AMGN", "AMT", "AMZN", "AVGO", "AXP", "BA",
+    "BAC", "BK", "BKNG", "BLK", "BMY", "BRK.B", "C", "CAT", "CHTR", "CL", "CMCSA", "COF", "COP",
+    "COST", "CRM", "CSCO", "CVS", "CVX", "DE", "DHR", "DIS", "DUK", "EMR", "F", "FDX", "GD", "GE",
+    "GILD", "GM", "GOOG", "GOOGL", "GS", "HD", "HON", "IBM", "INTC", "INTU", "ISRG", "JNJ", "JPM",
+    "KHC", "KO", "LIN", "LLY", "LMT", "LOW", "MA", "MCD", "MDLZ", "MDT", "MET", "META", "MMM",
+    "MO", "MRK", "MS", "MSFT", "NEE", "NFLX", "NKE", "NOW", "NVDA", "ORCL", "PEP", "PFE", "PG",
+    "PLTR", "PM", "PYPL", "QCOM", "RTX", "SBUX", "SCHW", "SO", "SPG", "T", "TGT", "TMO", "TMUS",
+    "TSLA", "TXN", "UBER", "UNH", "UNP", "UPS", "USB", "V", "VZ", "WFC", "WMT", "XOM",
+];
+
+/// Symbols in a universe. `custom` is the configured list, `favorites` the watchlist.
+pub fn symbols(universe: Universe, custom: &[String], favorites: &[String]) -> Vec<String> {
+    match universe {
+        Universe::Sp100 => SP100.iter().map(|symbol| symbol.to_string()).collect(),
+        Universe::Watchlist => favorites.to_vec(),
+        Universe::Custom => custom.to_vec(),
+    }
+}
@@ -0,0 +1,37 @@
+// Workspace module: named layouts (chart, watchlist, footer tab and table columns) saved
+// in the settings file
+
+use crate::account::FooterTab;
+use crate::columns::{OrderColumn, PositionColumn, TableColumns};
+use crate::text_input::{InputMode, TextInput};
+use serde::{Deserialize, Serialize};
+
+/// Snapshot of the layout, restored when the workspace is selected
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Workspace {
+    pub name: String,
+    pub symbol: String,
+    pub timeframe: String,
+    pub bar_limit: u32,
+    pub footer_tab: FooterTab,
+    // Starred symbols shown as quick-switch chips (the watchlist)
+    pub favorite_symbols: Vec<String>,
+    pub positions_columns: TableColumns<PositionColumn>,
+    pub orders_columns: TableColumns<OrderColumn>,
+}
+
+/// Header menu for switching, saving and removing workspaces
+pub struct WorkspaceMenu {
+    pub open: bool,
+    // Name the current layout is saved under
+    pub name: TextInput,
+}
+
+impl WorkspaceMenu {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            name: TextInput::new("", InputMode::Text),
+        }
+    }
+}